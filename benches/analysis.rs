@@ -0,0 +1,166 @@
+//! Benchmarks for the analysis hot paths — project discovery, history
+//! analysis and changelog rendering — against a synthetic monorepo.
+//!
+//! Run with `just bench` (or `cargo bench --bench analysis`). The
+//! performance budget these numbers are held to is in CONTRIBUTING.md;
+//! `belaf debug timings` gives the same per-stage breakdown on a real
+//! repository.
+
+use std::{fs, path::Path};
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use tempfile::TempDir;
+
+use belaf::core::{
+    bump::BumpConfig,
+    changelog::{ChangelogConfig, Commit, GitConfig},
+    config::ConfigurationFile,
+    ecosystem::format_handler::{FormatHandlerRegistry, WorkspaceDiscovererRegistry},
+    git::repository::Repository,
+    release_unit::discovery::discover_implicit_release_units,
+    session::AppSession,
+    workflow::generate_changelog_entry,
+};
+
+const CARGO_CRATES: usize = 60;
+const NPM_PACKAGES: usize = 20;
+const COMMITS: usize = 400;
+const CHANGELOG_COMMITS: usize = 500;
+
+const COMMIT_KINDS: &[&str] = &["feat", "fix", "refactor", "perf", "docs", "chore"];
+
+fn write(root: &Path, relative: &str, content: &str) {
+    let path = root.join(relative);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
+
+fn commit_all(repo: &git2::Repository, message: &str) {
+    let mut index = repo.index().unwrap();
+    index
+        .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+        .unwrap();
+    index.write().unwrap();
+    let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+    let sig = git2::Signature::now("bench", "bench@example.com").unwrap();
+    let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+    let parents: Vec<&git2::Commit> = parent.iter().collect();
+    repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+        .unwrap();
+}
+
+/// A monorepo of independent cargo crates and npm packages with a few
+/// hundred conventional commits spread across them.
+fn synthetic_monorepo() -> TempDir {
+    let dir = TempDir::new().unwrap();
+    let root = dir.path();
+    let repo = git2::Repository::init(root).unwrap();
+    repo.remote("origin", "https://github.com/bench/monorepo.git")
+        .unwrap();
+
+    for i in 0..CARGO_CRATES {
+        write(
+            root,
+            &format!("crates/crate-{i}/Cargo.toml"),
+            &format!(
+                "[package]\nname = \"crate-{i}\"\nversion = \"0.{i}.0\"\nedition = \"2021\"\n"
+            ),
+        );
+        write(root, &format!("crates/crate-{i}/src/lib.rs"), "\n");
+    }
+    for i in 0..NPM_PACKAGES {
+        write(
+            root,
+            &format!("packages/pkg-{i}/package.json"),
+            &format!("{{\"name\": \"@bench/pkg-{i}\", \"version\": \"1.{i}.0\", \"main\": \"index.js\"}}\n"),
+        );
+    }
+    commit_all(&repo, "chore: initial import");
+
+    for n in 0..COMMITS {
+        let i = n % CARGO_CRATES;
+        let lib = format!("crates/crate-{i}/src/lib.rs");
+        let mut content = fs::read_to_string(root.join(&lib)).unwrap();
+        content.push_str(&format!("pub fn change_{n}() {{}}\n"));
+        write(root, &lib, &content);
+        let kind = COMMIT_KINDS[n % COMMIT_KINDS.len()];
+        commit_all(&repo, &format!("{kind}(crate-{i}): change number {n}"));
+    }
+
+    dir
+}
+
+fn bench_discovery(c: &mut Criterion, fixture: &TempDir) {
+    let repo = Repository::open(fixture.path()).unwrap();
+    let handlers = FormatHandlerRegistry::with_defaults();
+    let discoverers = WorkspaceDiscovererRegistry::with_defaults();
+
+    // Without a `belaf/` directory the discovery cache stays in memory
+    // and starts empty on every call.
+    c.bench_function("discovery/uncached", |b| {
+        b.iter(|| discover_implicit_release_units(&repo, &handlers, &discoverers, &[]).unwrap())
+    });
+
+    fs::create_dir_all(fixture.path().join("belaf")).unwrap();
+    discover_implicit_release_units(&repo, &handlers, &discoverers, &[]).unwrap();
+    c.bench_function("discovery/cached", |b| {
+        b.iter(|| discover_implicit_release_units(&repo, &handlers, &discoverers, &[]).unwrap())
+    });
+}
+
+fn bench_analyze_histories(c: &mut Criterion, fixture: &TempDir) {
+    // The session locates its repository from the environment.
+    std::env::set_current_dir(fixture.path()).unwrap();
+
+    c.bench_function("analyze_histories", |b| {
+        b.iter_batched(
+            || AppSession::initialize_default().unwrap(),
+            |sess| sess.analyze_histories().unwrap(),
+            BatchSize::PerIteration,
+        )
+    });
+}
+
+fn bench_changelog_rendering(c: &mut Criterion) {
+    let config = ConfigurationFile::get("belaf-bench-no-config.toml").unwrap();
+    let git_config = GitConfig::from_user_config(&config.changelog);
+    let changelog_config = ChangelogConfig::from_user_config(&config.changelog);
+    let bump_config = BumpConfig::from_user_config(&config.bump);
+
+    let commits: Vec<Commit> = (0..CHANGELOG_COMMITS)
+        .map(|n| {
+            let kind = COMMIT_KINDS[n % COMMIT_KINDS.len()];
+            Commit::new(
+                format!("{n:040x}"),
+                format!("{kind}(core): change number {n}\n\nCloses #{n}"),
+            )
+        })
+        .collect();
+
+    c.bench_function("changelog_rendering", |b| {
+        b.iter(|| {
+            generate_changelog_entry(
+                "1.0.0",
+                &commits,
+                &git_config,
+                &changelog_config,
+                &bump_config,
+            )
+            .unwrap()
+        })
+    });
+}
+
+fn analysis(c: &mut Criterion) {
+    let fixture = synthetic_monorepo();
+    bench_discovery(c, &fixture);
+    bench_analyze_histories(c, &fixture);
+    bench_changelog_rendering(c);
+}
+
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(20);
+    targets = analysis
+}
+criterion_main!(benches);
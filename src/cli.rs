@@ -6,7 +6,7 @@ use clap::{Args, Parser, Subcommand, ValueEnum};
     about = "Release management CLI for monorepos",
     long_about = "A powerful CLI tool for semantic versioning and release management.\nSupports Rust, Node.js, Python, Go, Elixir, Swift, and C# projects.",
     version,
-    after_help = "For detailed command help, run: belaf <COMMAND> --help.\n\nFor AI agents: run `belaf describe --json` for a machine-readable surface map (commands, exit codes, env vars, JSON output schemas). All commands support `--ci` for non-interactive use; `status`, `graph`, `explain`, `describe`, and `schema` support `--format=json`."
+    after_help = "For detailed command help, run: belaf <COMMAND> --help.\n\nFor AI agents: run `belaf describe --json` for a machine-readable surface map (commands, exit codes, env vars, JSON output schemas). All commands support `--ci` for non-interactive use; `status`, `approve`, `graph`, `affected`, `lint-commits`, `owners`, `audit show`, `explain`, `describe`, and `schema` support `--format=json`; with it, errors are printed to stderr as one JSON line with a stable `code`."
 )]
 #[command(disable_version_flag = true)]
 pub struct Cli {
@@ -22,6 +22,46 @@ pub struct Cli {
     #[arg(long, global = true, help = "Disable colored output")]
     pub no_color: bool,
 
+    #[arg(
+        long,
+        global = true,
+        env = "BELAF_PROFILE",
+        value_name = "NAME",
+        help = "Auth profile to use instead of the one selected for this repository"
+    )]
+    pub profile: Option<String>,
+
+    #[arg(
+        long,
+        global = true,
+        env = "BELAF_SCOPE",
+        value_name = "DIR",
+        help = "Only load release units under this directory (discovery, history analysis, drift check)"
+    )]
+    pub scope: Option<std::path::PathBuf>,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "N",
+        help = "Analyse at most N commits of each release unit's history (overrides [repo.analysis] max_commits)"
+    )]
+    pub max_depth: Option<std::num::NonZeroUsize>,
+
+    #[arg(
+        long,
+        global = true,
+        help = "Render changelog entries afresh instead of reusing those in belaf/.cache/changelog"
+    )]
+    pub no_cache: bool,
+
+    #[arg(
+        long = "i-know-what-im-doing",
+        global = true,
+        help = "Push and open pull requests even when the upstream remote is not one of [repo] upstream_urls"
+    )]
+    pub i_know_what_im_doing: bool,
+
     #[arg(short = 'V', long, help = "Print version information")]
     pub version: bool,
 
@@ -57,22 +97,114 @@ pub enum Commands {
 
     #[command(
         about = "Show release status and changelog",
-        long_about = "Display current release status and preview upcoming changes.\n\nShows:\n  • Projects with uncommitted changes\n  • Projects ready for release\n  • Dependency order for releases\n  • Preview of changelog entries based on Git commits\n\nUse this before 'prepare' to verify what will be released."
+        long_about = "Display current release status and preview upcoming changes.\n\nShows:\n  • Projects with uncommitted changes\n  • Projects ready for release\n  • Dependency order for releases\n  • Preview of changelog entries based on Git commits\n  • Open release PRs with their check and merge state (when logged in)\n\nText output groups the units by ecosystem, most release-ready first:\nbreaking changes, then features, then fixes. --filter narrows every output\nto changed, clean, or breaking units.\n\nUse this before 'prepare' to verify what will be released.\n\nWith --ci, exits 4 when one of the [ci.status_gates] conditions holds, e.g.\nan unreleased breaking change older than N days, for use as a merge gate.\n\nWith --at REF the status is shown as it was at REF: versions, commits and\nconfiguration as of that commit, against the release tags on its history.\nRelease PRs are left out. Useful for post-mortems:\n  belaf status --at v2.3.0~1\n  belaf status --at 4f1c2e9 --format json"
     )]
     Status(StatusArgs),
 
     #[command(
         about = "Prepare a release (bump versions)",
-        long_about = "Prepare a new release by bumping versions and updating changelogs.\n\nBump types:\n  • major: Breaking changes (1.0.0 → 2.0.0)\n  • minor: New features (1.0.0 → 1.1.0)\n  • patch: Bug fixes (1.0.0 → 1.0.1)\n  • auto: Automatic bump based on conventional commits\n\nThis command:\n  • Creates a release branch\n  • Updates version numbers in all affected project files\n  • Generates/updates CHANGELOG.md for each project\n  • Creates a release manifest\n  • Commits, pushes, and creates a Pull Request\n\nModes:\n  • TUI mode (default): Interactive 4-step wizard with auto-suggestions\n  • CI mode (--ci): Full automation with PR creation"
+        long_about = "Prepare a new release by bumping versions and updating changelogs.\n\nBump types:\n  • major: Breaking changes (1.0.0 → 2.0.0)\n  • minor: New features (1.0.0 → 1.1.0)\n  • patch: Bug fixes (1.0.0 → 1.0.1)\n  • auto: Automatic bump based on conventional commits\n\nThis command:\n  • Creates a release branch\n  • Updates version numbers in all affected project files\n  • Generates/updates CHANGELOG.md for each project\n  • Creates a release manifest\n  • Commits, pushes, and creates a Pull Request\n\nRefuses to run while an open release PR already covers one of the units;\nmerge or close it first, or pass --force. The new PR then carries an edit log\nof what changed since the open one: new commits, moved versions, units added\nor dropped.\n\nA unit without changes since its last release, e.g. one rebuilt for a CVE in\nits base image, is released with --allow-empty:\n  belaf prepare --ci --allow-empty -p mylib:patch --message \"Rebuilt for CVE-2026-1234\"\nThe message becomes its changelog entry.\n\nTooling that computes a release train passes it with --projects-from-file:\none unit per line, `name` or `name:bump`, `#` starting a comment. Only the\nlisted units are released; --release-unit overrides apply on top:\n  compute-train | belaf prepare --ci --projects-from-file -\n\nIf a run fails part way, e.g. when the push is rejected, the next one\nrefuses to start. --resume continues from the last completed step, or\nrolls the release back when it stopped while writing files.\n\nModes:\n  • TUI mode (default): Interactive 4-step wizard with auto-suggestions\n  • CI mode (--ci): Full automation with PR creation"
     )]
     Prepare(PrepareArgs),
 
+    #[command(
+        about = "Approve a prepared release, or check its approvals",
+        long_about = "Sign off on a release prepared under a [release.approvers] gate.\n\nRun on the release branch with the id of a manifest in belaf/releases/ (a\nunique prefix is enough). Your authenticated GitHub login is added to the\nmanifest; commit and push it to the release PR, and approve the PR on\nGitHub. Only listed approvers count, whoever prepared the release cannot\napprove it, and a sign-off counts only with an approving review of the\nrelease PR from the same login.\n\nWith --check nothing is written: the command exits 0 when the release has\nenough approvals and 4 (precondition) otherwise. The PR's reviews are read\nwith GITHUB_TOKEN or the stored token. Run it in CI as a required\ncheck on release PRs.\n\nExamples:\n  belaf approve 0192f3a1              # Record your approval\n  belaf approve 0192f3a1 --check      # CI: fail until approved"
+    )]
+    Approve(ApproveArgs),
+
+    #[command(
+        about = "Promote a prerelease to its stable release",
+        long_about = "Release the stable version of a unit whose latest tag is a prerelease,\ne.g. 1.3.0 from 1.3.0-rc.2.\n\nThis command:\n  • Strips the pre-release identifier from the version\n  • Merges the prerelease entries at the top of the changelog into one\n    stable entry\n  • Creates a release manifest, commits, pushes, and opens a Pull Request\n\nThe GitHub App tags and publishes the release when the PR merges, as for\n`prepare`. The unit must have no commits since its prerelease tag; release\nthose as another prerelease first. Units in a group are promoted together:\nlist every member.\n\nExamples:\n  belaf promote my-crate\n  belaf promote @org/core @org/core-wasm"
+    )]
+    Promote(PromoteArgs),
+
+    #[command(
+        about = "Publish Homebrew, Scoop and AUR manifests for a released CLI",
+        long_about = "Regenerate the package manager manifests configured under\n[packaging.<unit>] for the unit's latest release tag, and commit them to\nthe tap, bucket or AUR mirror repository.\n\nRun it after the release PR merged and the release archives were built:\n  • --artifacts names the directory holding the archives; each is matched\n    to a platform by the target triple in its file name\n    (mycli-aarch64-apple-darwin.tar.gz) and hashed with SHA-256\n  • download URLs default to the forge's release assets\n\nThe GitHub App must be installed on the package repositories.\n\nExamples:\n  belaf package mycli --artifacts dist/\n  belaf package mycli --artifacts dist/ --dry-run"
+    )]
+    Package(PackageArgs),
+
+    #[command(
+        about = "Create a merged release's tags, annotated with its changelog",
+        long_about = "Create the git tags of a release whose PR merged, at HEAD of the base\nbranch. Each tag is annotated: its message is the release's changelog entry,\nso `git tag -l -n99` and tooling that reads tag messages show the release\nnotes. Members of a group share one tag.\n\nWith --sign, or git's `tag.gpgSign`, the tags are signed like `git tag -s`,\nwith `user.signingkey` through `gpg.program`.\n\nTags that already exist are skipped. Nothing is pushed. The tags created\nare recorded in belaf/audit.jsonl.\n\nWith [trackers.jira], each release's Jira fix version is created, set on the\nissues its commits mention and those issues are moved to Done; the API\ntoken is read from BELAF_JIRA_TOKEN.\n\nExamples:\n  belaf tag 0192f3a1\n  belaf tag 0192f3a1 --sign && git push origin --tags"
+    )]
+    Tag(TagArgs),
+
+    #[command(
+        about = "Ship a merged release in two steps: cut drafts, then publish them",
+        long_about = "Ship a merged release in two steps: `belaf release cut` tags it and opens\ndraft GitHub releases, `belaf release publish` makes them public.\n\nWith --snapshot, release a snapshot of HEAD instead, e.g. from a nightly CI\njob. Each unit with commits since its last release gets the version those\ncommits call for, as a prerelease stamped with the date and commit:\n  1.3.0-nightly.20250601+4f1c2e9   (Python: 1.3.0.dev20250601+4f1c2e9)\nThis command:\n  • Commits the versions to the units' manifests on top of HEAD, without\n    moving a branch, and restores the working tree\n  • Publishes npm packages under the `next` dist-tag; crates.io and other\n    registries have no prerelease channel and are skipped\n  • With --tag, tags that commit as snapshot/<release tag>, pushes the tags\n    and creates a GitHub prerelease per tag\nNo changelog, release manifest or pull request is written.\n\nExamples:\n  belaf release cut 0192f3a1 --artifacts dist/\n  belaf release --snapshot --tag\n  belaf release --snapshot --label canary --dry-run --format json",
+        args_conflicts_with_subcommands = true,
+        arg_required_else_help = true
+    )]
+    Release(ReleaseArgs),
+
+    #[command(
+        about = "Open a release PR, the repository, a changelog or a release page",
+        long_about = "Open what you look at around a release:\n  • pr: the newest open release PR, or the newest one releasing PROJECT\n  • repo: the upstream repository's web page\n  • changelog: PROJECT's changelog file\n  • release: the forge's release page for PROJECT's latest tag\n\nPROJECT may be left out when the repository has a single release unit.\nLinks follow `[forge]`, so they point at GitHub Enterprise Server when\nconfigured. Listing release PRs needs a login.\n\nExamples:\n  belaf open pr\n  belaf open release api\n  belaf open changelog api --print"
+    )]
+    Open(OpenArgs),
+
+    #[command(
+        about = "List past releases and the commits each one shipped",
+        long_about = "List the releases recorded in belaf/releases/, newest first: date, unit,\nversion, bump and the commit range (`previous_tag..sha`) the release was\nprepared from.\n\nWith --show-commits, each release's commits are listed below it, with their\nsummary when the commit is in the local repository.\n\nExamples:\n  belaf history\n  belaf history api --show-commits\n  belaf history --format json"
+    )]
+    History(HistoryArgs),
+
+    #[command(
+        about = "Find the first release of each project that contains a commit",
+        long_about = "Report, for each release unit, the first release whose tag contains COMMIT:\nthe lowest version among the unit's release tags that COMMIT is an ancestor\nof. Units whose tags don't contain it are listed as unreleased when COMMIT is\non HEAD, so their next release will ship it, and as not on this branch\notherwise.\n\nCOMMIT is anything git can resolve to a commit: a SHA or a prefix of one, a\nbranch, a tag, HEAD~3. Tags must be fetched for the answer to be complete.\n\nExamples:\n  belaf which-release 3f2a9c1\n  belaf which-release 3f2a9c1 api\n  belaf which-release origin/main~5 --format json"
+    )]
+    WhichRelease(WhichReleaseArgs),
+
+    #[command(
+        about = "Verify a release tag: signature, version, changelog and artifacts",
+        long_about = "Check a release tag as a consumer or auditor would. For the commit TAG\npoints at:\n  • signature: the tag is signed and `git verify-tag` accepts it, with the\n    keys git is configured to trust\n  • version: the Cargo.toml, package.json or pyproject.toml of each release\n    unit the tag belongs to says the tag's version\n  • changelog: each unit's changelog has an entry for the version\n  • artifacts, with --artifacts DIR: every file in DIR is an artifact the\n    release recorded when it was cut (`belaf release cut`), with the same\n    SHA-256\n\nExits 4 (precondition) when a check fails. Tags and belaf/releases/ must be\nfetched.\n\nExamples:\n  belaf verify-tag my-crate-v1.1.0\n  belaf verify-tag @acme/web@v2.0.0 --artifacts downloads/ --format json"
+    )]
+    VerifyTag(VerifyTagArgs),
+
+    #[command(
+        about = "Upgrade belaf/config.toml and release manifests written by an older belaf",
+        long_about = "Rewrite files an older belaf wrote in the current format:\n  • belaf/config.toml: retired keys are replaced, e.g. [[group]] by\n    [group.<id>]; comments and formatting are kept\n  • belaf/releases/*.json: manifests are upgraded to the current schema\n    version\n\nOther commands already read old manifests by upgrading them in memory, and\npoint here when the config fails to load because of a retired key.\n\nWith --check nothing is written: the command exits 4 (precondition) when a\nfile needs migrating.\n\nExamples:\n  belaf migrate\n  belaf migrate --check"
+    )]
+    Migrate(MigrateArgs),
+
     #[command(
         about = "Show project dependency graph",
-        long_about = "Display the project dependency graph.\n\nInteractive TUI mode (default):\n  • Navigate through projects with arrow keys\n  • View dependency details\n  • Visual dependency tree\n\nBrowser mode (--web):\n  • Interactive Cytoscape.js graph\n  • Multiple layouts (Hierarchy, Force, Circle)\n  • Search, zoom, export PNG\n\nOutput formats (--format):\n  • ascii: ASCII art graph\n  • dot: Graphviz DOT format\n  • json: JSON for programmatic use\n\nCI mode (--ci): JSON output, no TUI"
+        long_about = "Display the project dependency graph.\n\nInteractive TUI mode (default):\n  • Navigate through projects with arrow keys\n  • View dependency details\n  • Visual dependency tree\n\nBrowser mode (--web):\n  • Interactive Cytoscape.js graph\n  • Multiple layouts (Hierarchy, Force, Circle)\n  • Search, zoom, export PNG\n\nOutput formats (--format):\n  • ascii: ASCII art graph\n  • dot: Graphviz DOT format\n  • json: nodes and edges for programmatic use; see `belaf schema graph`\n\nCI mode (--ci): JSON output, no TUI"
     )]
     Graph(GraphArgs),
 
+    #[command(
+        about = "List release units changed relative to a base ref",
+        long_about = "List the release units whose files changed on HEAD since it diverged from\n--base, plus every unit that depends on one of them, directly or transitively.\nMeant for CI test selection: run only the suites of the units listed.\n\nFiles are attributed to units by path, the same way history analysis does.\nOnly committed changes count; the working tree is ignored.\n\nUse --format=json for machine-readable output, e.g.\n  belaf affected --base origin/main --format json | jq -r '.units[].name'"
+    )]
+    Affected(AffectedArgs),
+
+    #[command(
+        about = "Preview a feature pull request's release impact in a comment on it",
+        long_about = "Post a comment on pull request --pr showing the release units its commits\nchange, the bump each would get and the changelog entry they contribute.\nThe commits are those on HEAD since it diverged from --base, attributed to\nunits the same way history analysis does. Rerunning edits the same comment\ninstead of posting another.\n\nMeant for CI on pull requests. The comment is posted with GITHUB_TOKEN, or\nthe token stored by `belaf auth login --with-token`; either needs\n`Pull requests: Read and write`. --dry-run prints the comment instead.\n\nExample (GitHub Actions):\n  belaf comment --pr ${{ github.event.number }} --base origin/${{ github.base_ref }}"
+    )]
+    Comment(CommentArgs),
+
+    #[command(
+        about = "Label a feature pull request by the release units it changes and its bump",
+        long_about = "Put labels on pull request --pr from the commits on HEAD since it diverged\nfrom --base, attributed to release units the same way history analysis does:\n  • area:<unit> for each unit the commits change\n  • semver:<major|minor|patch> for the largest bump they call for\n  • breaking when any of them is a breaking change\n\nLabels the repository lacks are created. Labels of this shape that no longer\napply are taken off; other labels are left alone.\n\nMeant for CI on pull requests. The labels are set with GITHUB_TOKEN, or the\ntoken stored by `belaf auth login --with-token`. --dry-run prints them instead.\n\nExample (GitHub Actions):\n  belaf label --pr ${{ github.event.number }} --base origin/${{ github.base_ref }}"
+    )]
+    Label(LabelArgs),
+
+    #[command(
+        about = "Check pending commits against the release units' commit scopes",
+        long_about = "Check every commit since each release unit's last release against the\nscopes listed in its `[release_unit.<name>] scopes`. A conventional commit\nwhose scope is not listed is reported; commits without a scope, and units\nwithout a `scopes` list, are not checked. Under `[commit_attribution] strategy =\n\"trailer_first\"`, `Belaf-Projects` trailers naming no release unit are\nreported too.\n\nProblems are warnings: the command exits 0 unless --strict is given, then\nit exits 4 (precondition).\n\nExamples:\n  belaf lint-commits\n  belaf lint-commits --strict --format json"
+    )]
+    LintCommits(LintCommitsArgs),
+
+    #[command(
+        about = "Show the CODEOWNERS owners of each release unit",
+        long_about = "Map each release unit to its owners from the repository's CODEOWNERS file\n(.github/CODEOWNERS, CODEOWNERS or docs/CODEOWNERS, first found wins).\nA unit is owned by whoever owns the files directly in its directory.\n\nThe same owners appear in `belaf status --format json`, are listed in the\nrelease PR body, and are requested as reviewers on the release PR.\n\nExamples:\n  belaf owners                  # All units\n  belaf owners mylib            # One unit\n  belaf owners --format json"
+    )]
+    Owners(OwnersArgs),
+
     #[command(
         about = "Generate changelog from commits",
         long_about = "Generate changelog entries based on conventional commits.\n\nThis command generates changelogs without the full release workflow.\nUseful for previewing changes or generating changelogs as a separate step.\n\nModes:\n  • Default: Write changelog files to disk\n  • Preview (--preview): Show changelog without writing files\n  • Stdout (--stdout): Output to stdout instead of files\n\nExamples:\n  belaf changelog                    # Generate all changelogs\n  belaf changelog --preview          # Preview without writing\n  belaf changelog --project mylib    # Only for specific project\n  belaf changelog --stdout           # Output to terminal"
@@ -87,13 +219,13 @@ pub enum Commands {
 
     #[command(
         about = "Print a machine-readable map of the CLI surface (for AI agents)",
-        long_about = "Emit a structured description of every command, argument, environment\nvariable, exit code, and embedded schema. Designed for AI agents that\nlanded in a repo with `belaf` on $PATH and have no other context.\n\nDefault output is JSON. Pass --text for a human-friendly summary;\n--json is also accepted (and is a no-op since JSON is the default).\n\nSchema of the output (top-level keys):\n  • name, version           — binary identity\n  • commands[]              — every subcommand with args + help\n  • env_vars[]              — relevant environment variables\n  • exit_codes[]            — stable exit codes and their meanings\n  • schemas[]               — names of embedded JSON schemas\n  • example_workflows[]     — common multi-command sequences"
+        long_about = "Emit a structured description of every command, argument, environment\nvariable, exit code, and embedded schema. Designed for AI agents that\nlanded in a repo with `belaf` on $PATH and have no other context.\n\nDefault output is JSON. Pass --text for a human-friendly summary;\n--json is also accepted (and is a no-op since JSON is the default).\n\nSchema of the output (top-level keys):\n  • name, version           — binary identity\n  • commands[]              — every subcommand with args + help\n  • env_vars[]              — relevant environment variables\n  • exit_codes[]            — stable exit codes and their meanings\n  • error_codes[]           — stable `code`s of the JSON error on stderr\n  • schemas[]               — names of embedded JSON schemas\n  • example_workflows[]     — common multi-command sequences"
     )]
     Describe(DescribeArgs),
 
     #[command(
         about = "Print an embedded JSON Schema by name",
-        long_about = "Print a JSON Schema document embedded in the binary. Useful for\nagents that want to validate manifests, status output, or other\nstructured data that belaf produces.\n\nAvailable schemas:\n  • manifest — release manifest (v1, JSON Schema Draft 2020-12)\n\nUse `belaf describe --json` to discover the current list."
+        long_about = "Print a JSON Schema document embedded in the binary. Useful for\nagents that want to validate manifests, status output, or other\nstructured data that belaf produces.\n\nAvailable schemas:\n  • manifest — release manifest (v1, JSON Schema Draft 2020-12)\n  • graph    — `belaf graph --format json` output (v1)\n\nUse `belaf describe --json` to discover the current list."
     )]
     Schema(SchemaArgs),
 
@@ -102,6 +234,55 @@ pub enum Commands {
         long_about = "Inspect the current state and report what's healthy / broken. Checks:\n  • Auth state (keyring token present? expired? still valid against the API?)\n  • Config (belaf/config.toml present? parses?)\n  • Repository (inside a git repo? clean tree?)\n  • Ecosystems (how many ReleaseUnits would auto-detect find?)\n  • API connectivity (api.belaf.dev reachable?)\n  • Environment (BELAF_* overrides, CI detection)\n\nDefault output is human-readable. Pass --json for an agent-friendly\nstructured payload (status field per check, plus an overall `ok` bool)."
     )]
     Doctor(DoctorArgs),
+
+    #[command(
+        about = "Print versions, OS, config summary and recent errors for a bug report",
+        long_about = "Collect what a bug report needs into one fenced block: the belaf, git and\nlibgit2 versions, the OS, the ecosystems auto-detection finds in this\nrepository, a summary of belaf/config.toml, and the last few errors belaf\nprinted. Paste it into https://github.com/ilblu/belaf/issues/new.\n\nPass --redact to mask the repository path, home directory, remote URLs and\nyour git name and email first.\n\nExamples:\n  belaf env\n  belaf env --redact | pbcopy"
+    )]
+    Env(EnvArgs),
+
+    #[command(subcommand, about = "Query the log of operations belaf performed")]
+    Audit(AuditCommands),
+
+    #[command(subcommand, about = "Export release notes for publishing elsewhere")]
+    Export(ExportCommands),
+
+    #[command(subcommand, about = "Diagnostics about belaf itself")]
+    Debug(DebugCommands),
+}
+
+impl Commands {
+    /// Whether the command was asked for machine-readable output. Errors
+    /// are then printed as JSON on stderr too (see
+    /// [`crate::core::errors::render_json`]).
+    pub fn wants_json(&self) -> bool {
+        match self {
+            Self::Status(args) => args.ci || matches!(args.format, Some(ReleaseOutputFormat::Json)),
+            Self::Approve(args) => args.format == Some(ApproveOutputFormat::Json),
+            Self::History(args) => args.format == Some(HistoryOutputFormat::Json),
+            Self::WhichRelease(args) => args.format == Some(WhichReleaseOutputFormat::Json),
+            Self::VerifyTag(args) => args.format == Some(VerifyTagOutputFormat::Json),
+            Self::Graph(args) => matches!(args.format, Some(GraphOutputFormat::Json)),
+            Self::Affected(args) => args.format == Some(AffectedOutputFormat::Json),
+            Self::LintCommits(args) => args.format == Some(LintCommitsOutputFormat::Json),
+            Self::Owners(args) => args.format == Some(OwnersOutputFormat::Json),
+            Self::Explain(args) => args.format == Some(ExplainOutputFormat::Json),
+            Self::Describe(args) => !args.text,
+            Self::Doctor(args) => args.json,
+            Self::Release(ReleaseArgs {
+                command: Some(ReleaseCommands::Publish(args)),
+                ..
+            }) => args.format == Some(ReleasePublishOutputFormat::Json),
+            Self::Release(args) => {
+                args.snapshot && args.format == Some(ReleaseSnapshotOutputFormat::Json)
+            }
+            Self::Audit(AuditCommands::Show(args)) => args.format == Some(AuditOutputFormat::Json),
+            Self::Debug(DebugCommands::Timings(args)) => {
+                args.format == Some(TimingsOutputFormat::Json)
+            }
+            _ => false,
+        }
+    }
 }
 
 #[derive(Args)]
@@ -155,18 +336,273 @@ pub struct DoctorArgs {
     pub json: bool,
 }
 
+#[derive(Args)]
+pub struct EnvArgs {
+    #[arg(
+        long,
+        help = "Mask the repository path, home directory, remote URLs and git identity"
+    )]
+    pub redact: bool,
+}
+
+#[derive(Subcommand)]
+pub enum AuditCommands {
+    #[command(
+        about = "List recorded operations, oldest first",
+        long_about = "Print the entries of belaf/audit.jsonl: every `init`, `prepare`, `approve`,\n`tag`, `release cut`, `release publish` and `release --snapshot` run in this repository, with its time, git user, arguments, and\nthe branch, release manifest and tags it produced.\n\nEach entry is committed together with the files the operation wrote, so the\ncommit that added it is the operation's resulting commit; it is shown too\n(\"uncommitted\" until then).\n\nExamples:\n  belaf audit show --command prepare\n  belaf audit show --user alice --limit 10 --format json"
+    )]
+    Show(AuditShowArgs),
+}
+
+#[derive(Args)]
+pub struct AuditShowArgs {
+    #[arg(
+        long,
+        value_name = "NAME",
+        help = "Only entries of this subcommand, e.g. `prepare`"
+    )]
+    pub command: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "TEXT",
+        help = "Only entries whose user contains TEXT (case-insensitive)"
+    )]
+    pub user: Option<String>,
+
+    #[arg(
+        long,
+        value_name = "N",
+        help = "Only the N most recent matching entries"
+    )]
+    pub limit: Option<usize>,
+
+    #[arg(
+        long,
+        value_enum,
+        value_name = "FORMAT",
+        help = "Output format (default: text)"
+    )]
+    pub format: Option<AuditOutputFormat>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum AuditOutputFormat {
+    Text,
+    Json,
+}
+
+#[derive(Subcommand)]
+pub enum ExportCommands {
+    #[command(
+        about = "Write a release notes site: an index per project and a page per version",
+        long_about = "Render every release in the release units' changelogs into pages a static\nsite generator can publish:\n  • OUT/index: every project with its latest version\n  • OUT/<project>/index: the project's versions, newest first\n  • OUT/<project>/<version>: the release's changelog entry\n\nEntries are taken from each unit's changelog file as the `[changelog]`\ntemplates rendered them, split at their `## ` headings. Markdown pages carry\nfront matter (title, project, version, date); --format html writes\nstandalone HTML pages instead. Existing files in OUT are overwritten, others\nare left alone.\n\nExamples:\n  belaf export site --out docs/releases/\n  belaf export site --out public/releases --format html"
+    )]
+    Site(ExportSiteArgs),
+}
+
+#[derive(Args)]
+pub struct ExportSiteArgs {
+    #[arg(long, value_name = "DIR", help = "Directory to write the pages to")]
+    pub out: std::path::PathBuf,
+
+    #[arg(
+        long,
+        value_enum,
+        value_name = "FORMAT",
+        help = "Page format (default: markdown)"
+    )]
+    pub format: Option<SiteFormat>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum SiteFormat {
+    Markdown,
+    Html,
+}
+
+#[derive(Args)]
+pub struct ReleaseArgs {
+    #[command(subcommand)]
+    pub command: Option<ReleaseCommands>,
+
+    #[arg(
+        long,
+        help = "Release a snapshot of each unit's next version from HEAD"
+    )]
+    pub snapshot: bool,
+
+    #[arg(
+        long,
+        value_name = "LABEL",
+        default_value = crate::core::snapshot::DEFAULT_LABEL,
+        requires = "snapshot",
+        help = "Prerelease label of the snapshot versions"
+    )]
+    pub label: String,
+
+    #[arg(
+        long,
+        requires = "snapshot",
+        help = "Tag the snapshot commit and create a GitHub prerelease per tag"
+    )]
+    pub tag: bool,
+
+    #[arg(
+        long,
+        requires = "snapshot",
+        help = "Print the snapshot versions instead of writing and publishing them"
+    )]
+    pub dry_run: bool,
+
+    #[arg(
+        long,
+        value_enum,
+        value_name = "FORMAT",
+        requires = "snapshot",
+        help = "Output format (default: text)"
+    )]
+    pub format: Option<ReleaseSnapshotOutputFormat>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ReleaseSnapshotOutputFormat {
+    Text,
+    Json,
+}
+
+#[derive(Subcommand)]
+pub enum ReleaseCommands {
+    #[command(
+        about = "Tag a merged release and open draft GitHub releases with its artifacts",
+        long_about = "The first step of a staged release, run instead of `belaf tag` at HEAD of\nthe base branch once the release PR merged:\n  • Creates the release's annotated tags, as `belaf tag` does, and pushes them\n  • Opens a draft GitHub release per tag, with its changelog entry\n  • Uploads the files in --artifacts DIR to the drafts: those directly in DIR\n    to every release, those in DIR/<unit>/ to that unit's only\n  • With [signing], adds a SHA256SUMS file and a cosign or minisign\n    signature of it and of each artifact\n  • Records the drafts and each artifact's size and SHA-256 in the manifest\n\nCommit the manifest afterwards. Drafts are only visible to the repository's\nwriters; nothing is announced until `belaf release publish`. If a cut fails\nhalfway, run it again: it finishes the drafts already opened. The token is\nGITHUB_TOKEN, or the one from `belaf auth login --with-token`.\n\nExamples:\n  belaf release cut 0192f3a1 --artifacts dist/\n  belaf release cut 0192f3a1 --dry-run"
+    )]
+    Cut(ReleaseCutArgs),
+
+    #[command(
+        about = "Publish the draft releases of a cut release",
+        long_about = "The second step of a staged release, once QA signed off on the drafts of\n`belaf release cut`. Checks that each draft still holds the artifacts\nrecorded when it was cut, then makes the drafts public, sends the\n`released` webhook and updates Jira and milestones as `belaf tag` does.\nRegistries that publish from GitHub releases pick the release up from\nthere. Nothing is published if any draft changed.\n\nDrafts are published dependencies first; those that don't depend on each\nother concurrently, up to --jobs (default: [release.publish] parallelism)\nat a time. A failed draft holds back the drafts depending on it; run the\ncommand again to publish the rest.\n\nCommit the manifest afterwards.\n\nUse --format=json for each draft's outcome.\n\nExamples:\n  belaf release publish 0192f3a1\n  belaf release publish 0192f3a1 --jobs 8 --format json\n  belaf release publish 0192f3a1 --dry-run"
+    )]
+    Publish(ReleasePublishArgs),
+}
+
+#[derive(Args)]
+pub struct ReleaseCutArgs {
+    #[arg(
+        value_name = "RELEASE_ID",
+        help = "Manifest id in belaf/releases/, or a unique prefix of it"
+    )]
+    pub release_id: String,
+
+    #[arg(long, value_name = "DIR", help = "Directory of the files to attach")]
+    pub artifacts: Option<std::path::PathBuf>,
+
+    #[arg(long, help = "Sign the tags with gpg, like `git tag -s`")]
+    pub sign: bool,
+
+    #[arg(long, help = "Print the tags and drafts instead of creating them")]
+    pub dry_run: bool,
+}
+
+#[derive(Args)]
+pub struct ReleasePublishArgs {
+    #[arg(
+        value_name = "RELEASE_ID",
+        help = "Manifest id in belaf/releases/, or a unique prefix of it"
+    )]
+    pub release_id: String,
+
+    #[arg(
+        long,
+        value_name = "N",
+        help = "Drafts to publish at the same time (default: [release.publish] parallelism)"
+    )]
+    pub jobs: Option<usize>,
+
+    #[arg(long, help = "Check the drafts and print what would be published")]
+    pub dry_run: bool,
+
+    #[arg(
+        long,
+        value_enum,
+        value_name = "FORMAT",
+        help = "Output format (default: text)"
+    )]
+    pub format: Option<ReleasePublishOutputFormat>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ReleasePublishOutputFormat {
+    Text,
+    Json,
+}
+
+#[derive(Subcommand)]
+pub enum DebugCommands {
+    #[command(
+        about = "Time each stage of the analysis behind `status`",
+        long_about = "Run the read-only analysis that `status` and `changelog` perform and print\nhow long each stage took:\n  • Opening the repository and loading belaf/config.toml\n  • Resolving `[release_unit.<name>]` blocks and discovering manifests\n  • Recovering versions from tags and building the graph\n  • Walking each unit's history and rendering its changelog entry\n\nNothing is written, fetched or pushed. Combine with --scope to time one\ndirectory of a monorepo.\n\nUse --format=json for machine-readable output (durations in milliseconds)."
+    )]
+    Timings(TimingsArgs),
+}
+
+#[derive(Args)]
+pub struct TimingsArgs {
+    #[arg(
+        long,
+        value_enum,
+        value_name = "FORMAT",
+        help = "Output format (default: text)"
+    )]
+    pub format: Option<TimingsOutputFormat>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum TimingsOutputFormat {
+    Text,
+    Json,
+}
+
 #[derive(Subcommand)]
 pub enum AuthCommands {
-    #[command(about = "Show authentication status")]
+    #[command(
+        about = "Log in, optionally under a named profile",
+        long_about = "Log in to belaf via the device flow and store the token in the OS keyring.\n\nWith --profile <name>, the token is kept under that profile, so several GitHub\naccounts can stay logged in side by side. A repository picks its profile by:\n  • --profile or BELAF_PROFILE\n  • `[auth] profile` in belaf/config.toml\n  • the first profile whose --match pattern covers the upstream remote\n  • otherwise the `default` profile\n\nWith --with-token, belaf takes a GitHub fine-grained personal access token\ninstead, for repositories the belaf GitHub App can't be installed on. The token\nis checked against the repository of the current checkout right away (can it\nread, push the release branch, open pull requests?) and stored with what it\nmay do; `prepare` then uses it there and stops early when it lacks a\npermission.\n\nExample: belaf auth login --profile work --match 'acme/*'\nExample: belaf auth login --with-token < token.txt"
+    )]
+    Login(LoginArgs),
+
+    #[command(about = "List auth profiles and which one is active here")]
+    Profiles,
+
+    #[command(
+        about = "Show authentication status",
+        long_about = "Show authentication status.\n\nShows:\n  • The logged-in user and token expiry\n  • Whether the belaf GitHub App is installed on the current repository\n  • The permissions `prepare` needs (contents: write, pull_requests: write)\n  • Remaining GitHub rate-limit quota, when the API reports it\n\nExits non-zero when not logged in, the token has expired, or the App is\nnot installed on the current repository."
+    )]
     Status,
 
     #[command(about = "Show current user name")]
     Whoami,
 
-    #[command(about = "Log out and remove stored credentials")]
+    #[command(about = "Log out and remove the stored credentials of the active profile")]
     Logout,
 }
 
+#[derive(Args)]
+pub struct LoginArgs {
+    #[arg(
+        long = "match",
+        value_name = "OWNER/REPO",
+        help = "Use this profile for repositories matching the glob (repeatable, e.g. 'acme/*')"
+    )]
+    pub remotes: Vec<String>,
+
+    #[arg(
+        long,
+        help = "Log in with a GitHub fine-grained personal access token, pasted or read from stdin, instead of the belaf App"
+    )]
+    pub with_token: bool,
+}
+
 #[derive(Args)]
 pub struct InitArgs {
     #[arg(short, long, help = "Force operation even in unexpected conditions")]
@@ -196,8 +632,25 @@ pub struct StatusArgs {
     #[arg(short, long, value_enum, help = "Output format (table, text, json)")]
     pub format: Option<ReleaseOutputFormat>,
 
-    #[arg(long, help = "CI/CD mode: JSON output, no TUI")]
+    #[arg(
+        long,
+        help = "CI/CD mode: JSON output, no TUI; exits 4 when a [ci.status_gates] gate fails"
+    )]
     pub ci: bool,
+
+    #[arg(
+        long,
+        value_enum,
+        help = "Only show units that are changed, clean, or breaking"
+    )]
+    pub filter: Option<StatusFilter>,
+
+    #[arg(
+        long,
+        value_name = "REF",
+        help = "Show the status as it was at REF (a commit, tag or branch)"
+    )]
+    pub at: Option<String>,
 }
 
 #[derive(Args)]
@@ -216,6 +669,13 @@ pub struct PrepareArgs {
     )]
     pub release_unit: Option<Vec<String>>,
 
+    #[arg(
+        long,
+        value_name = "FILE",
+        help = "Release only the units listed in FILE, one `name` or `name:bump` per line (use `-` for stdin in --ci mode)"
+    )]
+    pub projects_from_file: Option<String>,
+
     #[arg(
         long,
         value_name = "FILE",
@@ -229,6 +689,229 @@ pub struct PrepareArgs {
         help = "Run a shell command and parse its stdout as JSON bump decisions"
     )]
     pub bump_source_cmd: Option<String>,
+
+    #[arg(
+        long,
+        help = "Prepare even if an open release PR already covers some of the units"
+    )]
+    pub force: bool,
+
+    #[arg(
+        long,
+        value_name = "BRANCH",
+        help = "Prepare patch releases from a maintenance branch such as release/1.x"
+    )]
+    pub branch: Option<String>,
+
+    #[arg(
+        long,
+        requires_all = ["release_unit", "message"],
+        help = "Release the --release-unit units even if nothing changed since their last release"
+    )]
+    pub allow_empty: bool,
+
+    #[arg(
+        long,
+        value_name = "TEXT",
+        requires = "allow_empty",
+        help = "Changelog entry of an --allow-empty release without commits"
+    )]
+    pub message: Option<String>,
+
+    #[arg(
+        long,
+        conflicts_with_all = ["release_unit", "projects_from_file", "bump_source", "bump_source_cmd", "force", "branch", "allow_empty"],
+        help = "Finish a release an earlier run left incomplete, or roll it back"
+    )]
+    pub resume: bool,
+}
+
+#[derive(Args)]
+pub struct PromoteArgs {
+    #[arg(
+        value_name = "RELEASE_UNIT",
+        required = true,
+        help = "Release units to promote"
+    )]
+    pub release_units: Vec<String>,
+
+    #[arg(
+        long,
+        help = "Promote even if an open release PR already covers some of the units"
+    )]
+    pub force: bool,
+}
+
+#[derive(Args)]
+pub struct PackageArgs {
+    #[arg(value_name = "RELEASE_UNIT", help = "Release unit to package")]
+    pub release_unit: String,
+
+    #[arg(
+        long,
+        value_name = "DIR",
+        help = "Directory with the release's archives"
+    )]
+    pub artifacts: std::path::PathBuf,
+
+    #[arg(long, help = "Print the manifests instead of committing them")]
+    pub dry_run: bool,
+}
+
+#[derive(Args)]
+pub struct TagArgs {
+    #[arg(
+        value_name = "RELEASE_ID",
+        help = "Manifest id in belaf/releases/, or a unique prefix of it"
+    )]
+    pub release_id: String,
+
+    #[arg(long, help = "Sign the tags with gpg, like `git tag -s`")]
+    pub sign: bool,
+
+    #[arg(
+        long,
+        help = "Print the tag messages and Jira updates instead of making them"
+    )]
+    pub dry_run: bool,
+}
+
+#[derive(Args)]
+pub struct OpenArgs {
+    #[arg(value_enum, value_name = "TARGET", help = "What to open")]
+    pub target: OpenTarget,
+
+    #[arg(value_name = "PROJECT", help = "Release unit the target belongs to")]
+    pub project: Option<String>,
+
+    #[arg(long, help = "Print the URL or path instead of opening it")]
+    pub print: bool,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum OpenTarget {
+    Pr,
+    Repo,
+    Changelog,
+    Release,
+}
+
+#[derive(Args)]
+pub struct HistoryArgs {
+    #[arg(value_name = "PROJECT", help = "Only releases of this release unit")]
+    pub project: Option<String>,
+
+    #[arg(long, help = "List the commits each release shipped")]
+    pub show_commits: bool,
+
+    #[arg(
+        long,
+        value_name = "N",
+        help = "Only the N most recent matching releases"
+    )]
+    pub limit: Option<usize>,
+
+    #[arg(
+        long,
+        value_enum,
+        value_name = "FORMAT",
+        help = "Output format (default: text)"
+    )]
+    pub format: Option<HistoryOutputFormat>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum HistoryOutputFormat {
+    Text,
+    Json,
+}
+
+#[derive(Args)]
+pub struct WhichReleaseArgs {
+    #[arg(
+        value_name = "COMMIT",
+        help = "Commit to look for: a SHA, branch, tag, ..."
+    )]
+    pub commit: String,
+
+    #[arg(value_name = "PROJECT", help = "Only this release unit")]
+    pub project: Option<String>,
+
+    #[arg(
+        long,
+        value_enum,
+        value_name = "FORMAT",
+        help = "Output format (default: text)"
+    )]
+    pub format: Option<WhichReleaseOutputFormat>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum WhichReleaseOutputFormat {
+    Text,
+    Json,
+}
+
+#[derive(Args)]
+pub struct VerifyTagArgs {
+    #[arg(value_name = "TAG", help = "Release tag to verify")]
+    pub tag: String,
+
+    #[arg(
+        long,
+        value_name = "DIR",
+        help = "Directory of downloaded artifacts to check against the release"
+    )]
+    pub artifacts: Option<std::path::PathBuf>,
+
+    #[arg(long, help = "Don't fail when the tag is not signed")]
+    pub allow_unsigned: bool,
+
+    #[arg(
+        long,
+        value_enum,
+        value_name = "FORMAT",
+        help = "Output format (default: text)"
+    )]
+    pub format: Option<VerifyTagOutputFormat>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum VerifyTagOutputFormat {
+    Text,
+    Json,
+}
+
+#[derive(Args)]
+pub struct MigrateArgs {
+    #[arg(long, help = "Only report outdated files; exit 4 if there are any")]
+    pub check: bool,
+}
+
+#[derive(Args)]
+pub struct ApproveArgs {
+    #[arg(
+        value_name = "RELEASE_ID",
+        help = "Manifest id in belaf/releases/, or a unique prefix of it"
+    )]
+    pub release_id: String,
+
+    #[arg(long, help = "Only verify the recorded approvals; exit 4 if too few")]
+    pub check: bool,
+
+    #[arg(
+        long,
+        value_enum,
+        value_name = "FORMAT",
+        help = "Output format (default: text)"
+    )]
+    pub format: Option<ApproveOutputFormat>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum ApproveOutputFormat {
+    Text,
+    Json,
 }
 
 #[derive(Args)]
@@ -244,6 +927,108 @@ pub struct GraphArgs {
 
     #[arg(long, short, help = "Save HTML graph to file (implies --web)")]
     pub out: Option<String>,
+
+    #[arg(
+        long,
+        help = "Include each project's direct external dependencies as leaf nodes (not shown in the TUI)"
+    )]
+    pub external: bool,
+}
+
+#[derive(Args)]
+pub struct AffectedArgs {
+    #[arg(
+        long,
+        value_name = "REF",
+        help = "Ref to compare HEAD against, e.g. `origin/main`"
+    )]
+    pub base: String,
+
+    #[arg(
+        long,
+        value_enum,
+        value_name = "FORMAT",
+        help = "Output format (default: text)"
+    )]
+    pub format: Option<AffectedOutputFormat>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum AffectedOutputFormat {
+    Text,
+    Json,
+}
+
+#[derive(Args)]
+pub struct CommentArgs {
+    #[arg(long, value_name = "NUMBER", help = "Pull request to comment on")]
+    pub pr: u64,
+
+    #[arg(
+        long,
+        value_name = "REF",
+        help = "Ref the pull request merges into, e.g. `origin/main`"
+    )]
+    pub base: String,
+
+    #[arg(long, help = "Print the comment instead of posting it")]
+    pub dry_run: bool,
+}
+
+#[derive(Args)]
+pub struct LabelArgs {
+    #[arg(long, value_name = "NUMBER", help = "Pull request to label")]
+    pub pr: u64,
+
+    #[arg(
+        long,
+        value_name = "REF",
+        help = "Ref the pull request merges into, e.g. `origin/main`"
+    )]
+    pub base: String,
+
+    #[arg(long, help = "Print the labels instead of setting them")]
+    pub dry_run: bool,
+}
+
+#[derive(Args)]
+pub struct LintCommitsArgs {
+    #[arg(long, help = "Exit 4 if a commit uses an unknown scope")]
+    pub strict: bool,
+
+    #[arg(
+        long,
+        value_enum,
+        value_name = "FORMAT",
+        help = "Output format (default: text)"
+    )]
+    pub format: Option<LintCommitsOutputFormat>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum LintCommitsOutputFormat {
+    Text,
+    Json,
+}
+
+#[derive(Args)]
+pub struct OwnersArgs {
+    #[arg(value_name = "PROJECT", help = "Only show this release unit")]
+    pub project: Option<String>,
+
+    #[arg(
+        long,
+        value_enum,
+        value_name = "FORMAT",
+        help = "Output format (default: text)"
+    )]
+    pub format: Option<OwnersOutputFormat>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum OwnersOutputFormat {
+    Text,
+    Json,
 }
 
 #[derive(Args)]
@@ -264,9 +1049,20 @@ pub struct ChangelogArgs {
     #[arg(short, long, help = "Custom output file path (overrides config)")]
     pub output: Option<String>,
 
-    #[arg(long, help = "Include unreleased changes (no version tag)")]
+    #[arg(
+        long,
+        help = "Preview unreleased changes (no version tag); add --write to update the files"
+    )]
     pub unreleased: bool,
 
+    #[arg(
+        long,
+        requires = "unreleased",
+        conflicts_with_all = ["preview", "stdout"],
+        help = "With --unreleased, refresh the Unreleased section of each changelog in place"
+    )]
+    pub write: bool,
+
     #[arg(long, help = "CI/CD mode: suppress info messages, only errors")]
     pub ci: bool,
 }
@@ -281,6 +1077,16 @@ pub enum GraphOutputFormat {
     Json,
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum StatusFilter {
+    #[value(help = "Units with commits since their last release")]
+    Changed,
+    #[value(help = "Units without any")]
+    Clean,
+    #[value(help = "Units with breaking changes since their last release")]
+    Breaking,
+}
+
 #[derive(Clone, ValueEnum)]
 pub enum ReleaseOutputFormat {
     #[value(help = "Formatted table output")]
@@ -0,0 +1,136 @@
+//! `belaf affected` — the release units a branch touches, for CI test
+//! selection.
+//!
+//! Collects the files changed on HEAD since it diverged from `--base`,
+//! attributes them to units with the same path matchers history
+//! analysis uses, then adds every unit that depends on a touched one,
+//! directly or transitively. Nothing is written.
+//!
+//! `--format=json` is meant for CI scripts, e.g.
+//! `belaf affected --base origin/main --format json | jq -r '.units[].name'`.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use owo_colors::OwoColorize;
+use serde::Serialize;
+
+use crate::cli::AffectedOutputFormat;
+use crate::core::session::AppSession;
+
+#[derive(Serialize)]
+struct AffectedPayload {
+    base: String,
+    merge_base: String,
+    changed_files: usize,
+    units: Vec<AffectedUnit>,
+}
+
+#[derive(Serialize)]
+struct AffectedUnit {
+    name: String,
+    ecosystem: Option<String>,
+    prefix: String,
+    reason: AffectedReason,
+    /// Changed files attributed to this unit.
+    changed_files: usize,
+    /// Affected units this one depends on directly.
+    via: Vec<String>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum AffectedReason {
+    Changed,
+    Dependent,
+}
+
+pub fn run(base: String, format: Option<AffectedOutputFormat>) -> Result<i32> {
+    let sess = AppSession::initialize_default()?;
+    let (merge_base, paths) = sess.repo.paths_changed_since(&base)?;
+    let graph = sess.graph();
+
+    let mut changed: HashMap<usize, usize> = HashMap::new();
+    for id in graph.toposorted() {
+        let unit = graph.lookup(id);
+        let n = paths
+            .iter()
+            .filter(|p| unit.repo_paths.repo_path_matches(p))
+            .count();
+        if n > 0 {
+            changed.insert(id, n);
+        }
+    }
+
+    let seeds: Vec<usize> = changed.keys().copied().collect();
+    let affected = graph.with_dependents(&seeds);
+
+    let units = affected
+        .iter()
+        .map(|id| {
+            let unit = graph.lookup(*id);
+            let changed_files = changed.get(id).copied().unwrap_or_default();
+            AffectedUnit {
+                name: unit.user_facing_name.clone(),
+                ecosystem: unit.qualified_names().get(1).cloned(),
+                prefix: unit.prefix().escaped(),
+                reason: if changed_files > 0 {
+                    AffectedReason::Changed
+                } else {
+                    AffectedReason::Dependent
+                },
+                changed_files,
+                via: unit
+                    .internal_deps
+                    .iter()
+                    .filter(|dep| affected.contains(&dep.ident))
+                    .map(|dep| graph.lookup(dep.ident).user_facing_name.clone())
+                    .collect(),
+            }
+        })
+        .collect();
+
+    let payload = AffectedPayload {
+        base,
+        merge_base: merge_base.to_string(),
+        changed_files: paths.len(),
+        units,
+    };
+
+    if format == Some(AffectedOutputFormat::Json) {
+        println!("{}", serde_json::to_string_pretty(&payload)?);
+        return Ok(0);
+    }
+
+    println!(
+        "{} {}",
+        "Affected release units".bold(),
+        format!(
+            "({} files changed since {}, merge base {:.8})",
+            payload.changed_files, payload.base, payload.merge_base
+        )
+        .dimmed()
+    );
+    println!();
+    if payload.units.is_empty() {
+        println!("  No release units affected.");
+        return Ok(0);
+    }
+    for unit in &payload.units {
+        match unit.reason {
+            AffectedReason::Changed => println!(
+                "  {} {} {}",
+                "●".green(),
+                unit.name,
+                format!("({} files changed)", unit.changed_files).dimmed()
+            ),
+            AffectedReason::Dependent => println!(
+                "  {} {} {}",
+                "○".yellow(),
+                unit.name,
+                format!("(depends on {})", unit.via.join(", ")).dimmed()
+            ),
+        }
+    }
+    Ok(0)
+}
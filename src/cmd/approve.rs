@@ -0,0 +1,119 @@
+//! `belaf approve` — sign off on a prepared release, or check that it
+//! has enough sign-offs.
+//!
+//! Run on the release branch. `<release-id>` is the manifest id (any
+//! unique prefix of it will do) of a file in `belaf/releases/`. Without
+//! `--check` the authenticated user's approval is added to the manifest,
+//! to be committed and pushed to the release PR along with the
+//! [`crate::core::audit`] entry recording it; with `--check` nothing
+//! is written and the exit code says whether the release may ship. A
+//! sign-off only counts with an approving review of the release PR from
+//! the same login, so `--check` reads the PR's reviews, with
+//! `GITHUB_TOKEN` or the stored token; see [`approval::verify`].
+
+use anyhow::{bail, Context, Result};
+use owo_colors::OwoColorize;
+use serde::Serialize;
+
+use crate::cli::ApproveOutputFormat;
+use crate::core::{
+    approval::{self, ApprovalStatus},
+    audit::{self, AuditEntry},
+    exit_code::ExitCode,
+    releases,
+    session::AppSession,
+};
+
+#[derive(Serialize)]
+struct ApprovePayload {
+    release_id: String,
+    manifest: String,
+    approved: bool,
+    #[serde(flatten)]
+    status: ApprovalStatus,
+}
+
+pub async fn run(
+    release_id: String,
+    check: bool,
+    format: Option<ApproveOutputFormat>,
+) -> Result<i32> {
+    let sess = AppSession::initialize_default()?;
+    let (path, rel_path) = releases::find(&sess.repo, &release_id)?;
+    let mut manifest = releases::read(&path)?.manifest;
+    let approvers = sess.config_approvers();
+
+    if !check {
+        let Some(approvers) = approvers else {
+            bail!("no [release.approvers] in belaf/config.toml; releases need no approval");
+        };
+        let login = approval::current_login().await?;
+        // Prepared before the gate was configured: nobody is known to
+        // have prepared it, so only the approver list applies.
+        let block = manifest
+            .approval
+            .get_or_insert_with(|| approval::requirement(approvers, None));
+        approval::sign(block, approvers, &login)?;
+        manifest
+            .save_to_file(&path)
+            .with_context(|| format!("failed to write `{}`", path.display()))?;
+        let entry = AuditEntry::new(&sess.repo, "approve").with_manifest(&manifest.manifest_id);
+        audit::append(&sess.repo, &entry)?;
+    }
+
+    let mut status = approval::status(manifest.approval.as_ref(), approvers, None);
+    // Enough sign-offs on paper: see whether the forge backs them.
+    if check && status.required > 0 && status.is_approved() {
+        status = approval::verify(&sess.repo, &manifest, &rel_path, approvers).await?;
+    }
+    let payload = ApprovePayload {
+        release_id: manifest.manifest_id.clone(),
+        manifest: rel_path,
+        approved: status.is_approved(),
+        status,
+    };
+    let code = if payload.approved || !check {
+        ExitCode::Ok
+    } else {
+        ExitCode::Precondition
+    };
+
+    if format == Some(ApproveOutputFormat::Json) {
+        println!("{}", serde_json::to_string_pretty(&payload)?);
+        return Ok(code.into());
+    }
+
+    let count = format!(
+        "{}/{}",
+        payload.status.approved_by.len(),
+        payload.status.required
+    );
+    if !check {
+        println!(
+            "{} Approval recorded in {} ({count}).",
+            "✓".green().bold(),
+            payload.manifest
+        );
+        println!("  Commit and push it to the release branch so the release PR picks it up,");
+        println!("  and approve the release PR: a sign-off only counts with an approving review.");
+    } else if payload.approved {
+        println!(
+            "{} Release {} is approved ({count}).",
+            "✓".green().bold(),
+            payload.release_id
+        );
+    } else {
+        println!(
+            "{} Release {} needs more approvals ({count}).",
+            "✗".red().bold(),
+            payload.release_id
+        );
+    }
+    if !payload.status.approved_by.is_empty() {
+        println!("  Approved by: {}", payload.status.approved_by.join(", "));
+    }
+    for rejected in &payload.status.rejected {
+        println!("  {} {}", "ignored".dimmed(), rejected);
+    }
+    Ok(code.into())
+}
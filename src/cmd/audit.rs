@@ -0,0 +1,88 @@
+//! `belaf audit show` — query `belaf/audit.jsonl`.
+//!
+//! See [`crate::core::audit`] for what is recorded and when. Reading
+//! needs only the repository, not a loadable `belaf/config.toml`, so the
+//! log stays readable after a broken config change.
+
+use anyhow::{Context, Result};
+use owo_colors::OwoColorize;
+use serde::Serialize;
+
+use crate::cli::AuditOutputFormat;
+use crate::core::{
+    audit::{self, AuditEntry, AUDIT_LOG},
+    git::repository::Repository,
+};
+
+#[derive(Serialize)]
+struct ShownEntry {
+    #[serde(flatten)]
+    entry: AuditEntry,
+    /// Commit that added the entry; `None` while uncommitted.
+    commit: Option<String>,
+}
+
+pub fn run_show(
+    command: Option<String>,
+    user: Option<String>,
+    limit: Option<usize>,
+    format: Option<AuditOutputFormat>,
+) -> Result<i32> {
+    let repo = Repository::open_from_env().context("belaf is not in a Git working directory")?;
+    let user = user.map(|u| u.to_lowercase());
+
+    let mut entries: Vec<ShownEntry> = audit::read(&repo)?
+        .into_iter()
+        .filter(|(e, _)| command.as_ref().is_none_or(|c| &e.command == c))
+        .filter(|(e, _)| {
+            user.as_ref()
+                .is_none_or(|u| e.user.to_lowercase().contains(u))
+        })
+        .map(|(entry, cid)| ShownEntry {
+            entry,
+            commit: cid.map(|c| c.to_string()),
+        })
+        .collect();
+    if let Some(n) = limit {
+        entries.drain(..entries.len().saturating_sub(n));
+    }
+
+    if format == Some(AuditOutputFormat::Json) {
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+        return Ok(0);
+    }
+
+    if entries.is_empty() {
+        println!("{} No matching entries in {AUDIT_LOG}.", "ℹ".cyan().bold());
+        return Ok(0);
+    }
+    for shown in &entries {
+        let e = &shown.entry;
+        let commit = match &shown.commit {
+            Some(c) => c[..c.len().min(8)].to_string(),
+            None => "uncommitted".to_string(),
+        };
+        println!(
+            "{}  {}  {}  {}",
+            e.timestamp.dimmed(),
+            e.command.bold(),
+            e.user,
+            commit.yellow()
+        );
+        println!("  belaf {}", e.args.join(" "));
+        let mut produced = Vec::new();
+        if let Some(branch) = &e.branch {
+            produced.push(format!("branch {branch}"));
+        }
+        if let Some(manifest) = &e.manifest {
+            produced.push(format!("manifest {manifest}"));
+        }
+        if !e.tags.is_empty() {
+            produced.push(format!("tags {}", e.tags.join(", ")));
+        }
+        if !produced.is_empty() {
+            println!("  {}", produced.join("; ").dimmed());
+        }
+    }
+    Ok(0)
+}
@@ -20,8 +20,13 @@ pub fn run(
     project_filter: Option<String>,
     output_path: Option<String>,
     unreleased: bool,
+    write: bool,
     ci: bool,
 ) -> Result<i32> {
+    // Unreleased changes are only previewed unless `--write` asks for
+    // the Unreleased sections to be refreshed.
+    let preview = preview || (unreleased && !write && !stdout);
+
     if !ci {
         info!(
             "generating changelog with belaf version {}",
@@ -29,7 +34,12 @@ pub fn run(
         );
     }
 
-    let sess = AppSession::initialize_default()?;
+    // Writing needs a working tree; a preview reads a bare repository too.
+    let sess = if preview || stdout {
+        AppSession::initialize_read_only()?
+    } else {
+        AppSession::initialize_default()?
+    };
 
     let q = GraphQueryBuilder::default();
     let idents = sess.graph().query(q)?;
@@ -46,7 +56,6 @@ pub fn run(
         .context("failed to analyze project histories")?;
 
     let git_config = GitConfig::from_user_config(&sess.changelog_config);
-    let changelog_config = ChangelogConfig::from_user_config(&sess.changelog_config);
     let bump_config = BumpConfig::from_user_config(&sess.bump_config);
 
     let github_remote = extract_github_remote(&sess.repo);
@@ -102,16 +111,13 @@ pub fn run(
         let new_version = if unreleased || suggested_bump.as_str() == "no bump" {
             None
         } else {
-            let mut version_clone = unit.version.clone();
-            let bump_scheme = version_clone
-                .parse_bump_scheme(suggested_bump.as_str())
+            let next = unit
+                .version
+                .bumped(suggested_bump.as_str())
                 .with_context(|| {
-                    format!("invalid bump scheme for project {}", unit.user_facing_name)
+                    format!("failed to apply version bump to {}", unit.user_facing_name)
                 })?;
-            bump_scheme.apply(&mut version_clone).with_context(|| {
-                format!("failed to apply version bump to {}", unit.user_facing_name)
-            })?;
-            Some(version_clone.to_string())
+            Some(next.to_string())
         };
 
         let qnames = unit.qualified_names();
@@ -120,8 +126,24 @@ pub fn run(
             .map(|s| Ecosystem::classify(s))
             .unwrap_or_else(|| Ecosystem::classify("cargo"));
 
+        // The new tag has the shape of the previous one.
+        let new_tag = match (
+            history.release_tag(),
+            history.release_version(),
+            &new_version,
+        ) {
+            (Some(tag), Some(tagged), Some(new)) => Some(tag.replacen(&tagged.to_string(), new, 1)),
+            _ => None,
+        };
+
+        let previous_version = history.release_version().map(|v| v.to_string());
         let prefix = unit.prefix().escaped();
         let write_to_file = !preview && !stdout;
+        let changelog_config = ChangelogConfig::from_user_config(
+            &sess
+                .changelog_config
+                .for_project(&unit.user_facing_name, ecosystem.as_str()),
+        );
 
         let params = ChangelogGenerationParams {
             repo: &sess.repo,
@@ -137,6 +159,9 @@ pub fn run(
             github_owner: github_remote.as_ref().map(|r| r.owner.as_str()),
             github_repo: github_remote.as_ref().map(|r| r.repo.as_str()),
             github_token: github_token.clone(),
+            previous_version: previous_version.as_deref(),
+            tags: history.release_tag().zip(new_tag.as_deref()),
+            replace_unreleased: write,
         };
         let result = generate_and_write_project_changelog(&params)?;
 
@@ -160,6 +185,15 @@ pub fn run(
             );
         } else if stdout {
             print!("{}", result.content);
+        } else if result.unchanged {
+            if !ci {
+                println!(
+                    "  {} {} {}",
+                    "·".dimmed(),
+                    unit.user_facing_name.bold(),
+                    "Unreleased section already up to date".dimmed()
+                );
+            }
         } else if !ci {
             let version_info = match new_version.as_deref() {
                 Some(nv) => format!("{} → {}", current_version.dimmed(), nv.green()),
@@ -180,13 +214,20 @@ pub fn run(
                 version_info,
                 path_display.dimmed()
             );
+            for path in &result.localized_paths {
+                println!("      {} {}", "+".dimmed(), path.escaped().dimmed());
+            }
         }
 
         processed_count += 1;
         ci_projects.push(unit.user_facing_name.clone());
+        if result.unchanged {
+            continue;
+        }
         if let Some(p) = result.path.as_ref() {
             ci_files_written.push(p.escaped().to_string());
         }
+        ci_files_written.extend(result.localized_paths.iter().map(|p| p.escaped()));
     }
 
     if !ci {
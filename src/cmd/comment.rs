@@ -0,0 +1,38 @@
+//! `belaf comment` — keep a preview of a feature PR's release impact in
+//! a comment on it.
+//!
+//! Renders what the PR's commits add to each unit's next release (see
+//! [`pr_impact`]): the bump they call for and their changelog entry. The
+//! comment carries a marker (see [`pr::PREVIEW_MARKER`]), so CI can run
+//! this on every push and the PR keeps a single, current preview.
+//!
+//! It is posted through the GitHub REST API; see [`rest::for_upstream`].
+
+use anyhow::{Context, Result};
+use owo_colors::OwoColorize;
+
+use crate::core::{
+    github::{pr, rest},
+    pr_impact,
+    session::AppSession,
+};
+
+pub async fn run(number: u64, base: String, dry_run: bool) -> Result<i32> {
+    let sess = AppSession::initialize_default()?;
+    let units = pr_impact::analyze(&sess, &base, true)?;
+
+    let body = pr::generate_preview_comment(&units, &sess.repo.head_commit_sha()?);
+    if dry_run {
+        print!("{body}");
+        return Ok(0);
+    }
+
+    sess.repo.check_upstream("comment on a pull request")?;
+    let (client, owner, repo) = rest::for_upstream(&sess.repo)?;
+    let url = client
+        .upsert_comment(&owner, &repo, number, pr::PREVIEW_MARKER, &body)
+        .await
+        .with_context(|| format!("failed to comment on pull request #{number}"))?;
+    println!("{} Preview posted: {}", "✓".green().bold(), url);
+    Ok(0)
+}
@@ -6,7 +6,9 @@ use owo_colors::OwoColorize;
 pub fn generate(shell: Shell) {
     let mut cmd = Cli::command();
     let bin_name = cmd.get_name().to_string();
-    gen_completions(shell, &mut cmd, bin_name, &mut std::io::stdout());
+    let mut script = Vec::new();
+    gen_completions(shell, &mut cmd, bin_name, &mut script);
+    print!("{}", String::from_utf8_lossy(&script));
 }
 
 pub fn print_version() {
@@ -1,22 +1,31 @@
 //! No-arg entry TUI — the menu users land on when they run `belaf`
 //! with no subcommand. Dispatches into `init` / `prepare` / `status`
-//! based on the menu selection plus a small stats panel.
+//! based on the menu selection, and shows what is in flight: each
+//! project's version, pending commits and open release PR, plus the
+//! most recent releases recorded in `belaf/releases/`.
+//!
+//! `[dashboard]` in `belaf/config.toml` picks the menu entries and
+//! their order and how many recent releases are listed; see
+//! [`crate::core::config::syntax::DashboardConfiguration`]. It is read
+//! with [`peek_section`] so a broken config still gets a menu.
+//!
+//! Keys: the menu's own, `↑`/`↓` to pick a project, `o` to open its
+//! release PR in the browser (the first open one when it has none),
+//! `r` to reload everything. `q` / `Esc` quit even when `quit` is
+//! hidden.
 //!
 //! ## On `release_unit_view`
 //!
 //! The 1.0 plan called for `dashboard.rs` to consume
 //! `core::ui::release_unit_view::ReleaseUnitView` in
 //! `RenderMode::Dashboard` (read-only). The plan was based on an
-//! outdated mental model: this dashboard is a **menu launcher** — it
-//! renders the logo, a 3-item menu, and aggregate stats (total
-//! projects + pending commits). It does not render a list of
-//! ReleaseUnits, so there's nothing for the shared view to bind to.
+//! outdated mental model: this dashboard is a **menu launcher**. Its
+//! project panel is a one-line-per-unit status summary, not a
+//! selection list, so there's nothing for the shared view to bind to.
 //!
 //! The shared component is consumed by the `init` wizard
 //! (`unified_selection.rs`) and by the `prepare` wizard's solo-row
-//! rendering (`prepare/wizard.rs::render_solo_row`). If a future
-//! dashboard ever surfaces a unit list, that's where it would call
-//! into the shared view.
+//! rendering (`prepare/wizard.rs::render_solo_row`).
 
 use std::io::{self, stdout};
 
@@ -31,11 +40,22 @@ use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::Paragraph,
+    widgets::{Block, Borders, Paragraph},
     Frame, Terminal,
 };
 
-use crate::core::{git::repository::Repository, session::AppSession};
+use crate::core::{
+    config::{
+        peek_section,
+        syntax::{DashboardConfiguration, DashboardItem},
+    },
+    git::repository::Repository,
+    github::pending::{find_pending_releases, PendingRelease},
+    releases,
+    session::AppSession,
+    ui::text,
+};
+use crate::utils::theme;
 
 const LOGO: [&str; 7] = [
     "██████╗ ███████╗██╗      █████╗ ███████╗",
@@ -47,52 +67,73 @@ const LOGO: [&str; 7] = [
     "        Release Management              ",
 ];
 
+/// Recent releases listed when `[dashboard] recent_releases` is unset.
+const DEFAULT_RECENT_RELEASES: usize = 5;
+
 struct MenuItem {
+    item: DashboardItem,
     icon: &'static str,
     label: &'static str,
     key: char,
+    action: DashboardAction,
 }
 
 const MENU_ITEMS: [MenuItem; 8] = [
     MenuItem {
+        item: DashboardItem::Prepare,
         icon: "📦",
         label: "Prepare release",
         key: 'p',
+        action: DashboardAction::Prepare,
     },
     MenuItem {
+        item: DashboardItem::Status,
         icon: "📊",
         label: "Show status",
         key: 's',
+        action: DashboardAction::Status,
     },
     MenuItem {
+        item: DashboardItem::Graph,
         icon: "🔗",
         label: "Dependency graph",
         key: 'g',
+        action: DashboardAction::Graph,
     },
     MenuItem {
+        item: DashboardItem::Changelog,
         icon: "📝",
         label: "Generate changelog",
         key: 'c',
+        action: DashboardAction::Changelog,
     },
     MenuItem {
+        item: DashboardItem::Init,
         icon: "⚙ ",
         label: "Initialize project",
         key: 'i',
+        action: DashboardAction::Init,
     },
     MenuItem {
+        item: DashboardItem::Web,
         icon: "🌐",
         label: "Open web dashboard",
         key: 'w',
+        action: DashboardAction::Web,
     },
     MenuItem {
+        item: DashboardItem::Help,
         icon: "❓",
         label: "Help",
         key: '?',
+        action: DashboardAction::Help,
     },
     MenuItem {
+        item: DashboardItem::Quit,
         icon: "🚪",
         label: "Quit",
         key: 'q',
+        action: DashboardAction::Quit,
     },
 ];
 
@@ -110,7 +151,6 @@ pub enum DashboardAction {
 }
 
 struct DashboardStats {
-    unit_count: usize,
     pending_commits: usize,
     current_branch: String,
     is_initialized: bool,
@@ -119,7 +159,6 @@ struct DashboardStats {
 impl Default for DashboardStats {
     fn default() -> Self {
         Self {
-            unit_count: 0,
             pending_commits: 0,
             current_branch: String::from("unknown"),
             is_initialized: false,
@@ -127,103 +166,239 @@ impl Default for DashboardStats {
     }
 }
 
-pub fn run() -> Result<DashboardAction> {
-    let stats = load_stats();
-
-    enable_raw_mode()?;
-    let mut stdout = stdout();
-    execute!(stdout, EnterAlternateScreen)?;
-
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
-
-    let result = run_dashboard(&mut terminal, &stats);
+/// One row of the project panel.
+struct UnitStatus {
+    name: String,
+    version: Option<String>,
+    pending_commits: usize,
+}
 
-    disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+/// One row of the recent-releases panel.
+struct RecentRelease {
+    date: String,
+    summary: String,
+}
 
-    result
+struct Dashboard {
+    menu: Vec<&'static MenuItem>,
+    recent_limit: usize,
+    stats: DashboardStats,
+    units: Vec<UnitStatus>,
+    /// Open release PRs; empty when the forge could not be asked.
+    pending: Vec<PendingRelease>,
+    releases: Vec<RecentRelease>,
+    selected: usize,
+    message: Option<String>,
 }
 
-fn load_stats() -> DashboardStats {
-    let mut stats = DashboardStats::default();
+impl Dashboard {
+    fn load() -> Self {
+        let config = peek_section::<DashboardConfiguration>("dashboard")
+            .map(|(config, _)| config)
+            .unwrap_or_default();
+        let mut dashboard = Self {
+            menu: menu_items(config.actions.as_deref()),
+            recent_limit: config.recent_releases.unwrap_or(DEFAULT_RECENT_RELEASES),
+            stats: DashboardStats::default(),
+            units: Vec::new(),
+            pending: Vec::new(),
+            releases: Vec::new(),
+            selected: 0,
+            message: None,
+        };
+        dashboard.refresh();
+        dashboard
+    }
 
-    if let Ok(repo) = Repository::open_from_env() {
+    /// Re-read the repository, the projects' histories and the open
+    /// release PRs.
+    fn refresh(&mut self) {
+        self.stats = DashboardStats::default();
+        self.units.clear();
+        self.pending.clear();
+        self.releases.clear();
+
+        let Ok(repo) = Repository::open_from_env() else {
+            return;
+        };
         if let Ok(Some(branch)) = repo.current_branch_name() {
-            stats.current_branch = branch;
+            self.stats.current_branch = branch;
         }
-
         let config_path = repo.resolve_config_dir().join("config.toml");
-        stats.is_initialized = config_path.exists();
+        self.stats.is_initialized = config_path.exists();
+        self.releases = load_recent_releases(&repo, self.recent_limit);
 
-        if stats.is_initialized {
+        if self.stats.is_initialized {
             if let Ok(session) = AppSession::initialize_default() {
-                stats.unit_count = session.graph().projects().count();
-                stats.pending_commits = count_pending_commits(&session);
+                self.units = load_units(&session);
+                self.pending = find_pending_releases(&session).unwrap_or_default();
             }
         }
+        self.stats.pending_commits = self.units.iter().map(|u| u.pending_commits).sum();
+        self.selected = self.selected.min(self.units.len().saturating_sub(1));
+    }
+
+    /// The open release PR covering `unit`.
+    fn pending_for(&self, unit: &str) -> Option<&PendingRelease> {
+        self.pending.iter().find(|pr| pr.covers(unit))
     }
 
-    stats
+    /// Open the selected project's release PR, or the first open one.
+    fn open_release_pr(&mut self) {
+        let pr = self
+            .units
+            .get(self.selected)
+            .and_then(|u| self.pending_for(&u.name))
+            .or_else(|| self.pending.first());
+        self.message = Some(match pr {
+            Some(PendingRelease { url: Some(url), .. }) => match open::that(url) {
+                Ok(()) => format!("Opened {url}"),
+                Err(e) => format!("failed to open browser: {e}"),
+            },
+            Some(pr) => format!("Release PR #{} has no URL", pr.number),
+            None => "No open release PR".to_string(),
+        });
+    }
 }
 
-fn count_pending_commits(session: &AppSession) -> usize {
-    if let Ok(histories) = session.analyze_histories() {
-        session
-            .graph()
-            .projects()
-            .map(|p| histories.lookup(p.ident()).n_commits())
-            .sum()
-    } else {
-        0
+/// The entries `actions` names, in its order; every entry when unset.
+fn menu_items(actions: Option<&[DashboardItem]>) -> Vec<&'static MenuItem> {
+    match actions {
+        Some(actions) => actions
+            .iter()
+            .filter_map(|action| MENU_ITEMS.iter().find(|m| m.item == *action))
+            .collect(),
+        None => MENU_ITEMS.iter().collect(),
     }
 }
 
+fn load_units(session: &AppSession) -> Vec<UnitStatus> {
+    let Ok(histories) = session.analyze_histories() else {
+        return Vec::new();
+    };
+    session
+        .graph()
+        .projects()
+        .map(|unit| {
+            let history = histories.lookup(unit.ident());
+            let version = history
+                .release_info(&session.repo)
+                .ok()
+                .and_then(|info| Some(info.lookup_project(unit)?.version.to_string()));
+            UnitStatus {
+                name: unit.user_facing_name.clone(),
+                version,
+                pending_commits: history.n_commits(),
+            }
+        })
+        .collect()
+}
+
+/// The last `limit` manifests in `belaf/releases/`, newest first.
+fn load_recent_releases(repo: &Repository, limit: usize) -> Vec<RecentRelease> {
+    let paths = releases::list(repo).unwrap_or_default();
+    paths
+        .iter()
+        .rev()
+        .take(limit)
+        .filter_map(|path| releases::read_in(repo, path).ok())
+        .map(|stored| {
+            let manifest = stored.manifest;
+            RecentRelease {
+                date: manifest.created_at.chars().take(10).collect(),
+                summary: manifest
+                    .releases
+                    .iter()
+                    .map(|r| format!("{} {}", r.name, r.new_version))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            }
+        })
+        .collect()
+}
+
+pub fn run() -> Result<DashboardAction> {
+    let mut dashboard = Dashboard::load();
+
+    enable_raw_mode()?;
+    let mut stdout = stdout();
+    execute!(stdout, EnterAlternateScreen)?;
+
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend)?;
+
+    let result = run_dashboard(&mut terminal, &mut dashboard);
+
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+
+    result
+}
+
 fn run_dashboard(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
-    stats: &DashboardStats,
+    dashboard: &mut Dashboard,
 ) -> Result<DashboardAction> {
     loop {
-        terminal.draw(|f| render(f, stats))?;
+        terminal.draw(|f| {
+            render(f, dashboard);
+            theme::apply_color_mode(f);
+        })?;
 
         if let Event::Key(key) = event::read()? {
             if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
                 return Ok(DashboardAction::Quit);
             }
 
+            dashboard.message = None;
             match key.code {
-                KeyCode::Char('p') => return Ok(DashboardAction::Prepare),
-                KeyCode::Char('s') => return Ok(DashboardAction::Status),
-                KeyCode::Char('g') => return Ok(DashboardAction::Graph),
-                KeyCode::Char('c') => return Ok(DashboardAction::Changelog),
-                KeyCode::Char('i') => return Ok(DashboardAction::Init),
-                KeyCode::Char('w') => return Ok(DashboardAction::Web),
-                KeyCode::Char('?') | KeyCode::Char('h') => return Ok(DashboardAction::Help),
                 KeyCode::Char('q') | KeyCode::Esc => return Ok(DashboardAction::Quit),
+                KeyCode::Up | KeyCode::Char('k') => {
+                    dashboard.selected = dashboard.selected.saturating_sub(1);
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    if dashboard.selected + 1 < dashboard.units.len() {
+                        dashboard.selected += 1;
+                    }
+                }
+                KeyCode::Char('o') => dashboard.open_release_pr(),
+                KeyCode::Char('r') => {
+                    dashboard.refresh();
+                    dashboard.message = Some("Refreshed".to_string());
+                }
+                KeyCode::Char(c) => {
+                    let c = if c == 'h' { '?' } else { c };
+                    if let Some(item) = dashboard.menu.iter().find(|m| m.key == c) {
+                        return Ok(item.action);
+                    }
+                }
                 _ => {}
             }
         }
     }
 }
 
-fn render(frame: &mut Frame, stats: &DashboardStats) {
+fn render(frame: &mut Frame, dashboard: &Dashboard) {
     let area = frame.area();
 
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Min(0),
+            Constraint::Length(1),
             Constraint::Length(9),
+            Constraint::Length(dashboard.menu.len() as u16 + 1),
             Constraint::Length(2),
-            Constraint::Length(MENU_ITEMS.len() as u16 + 2),
-            Constraint::Length(2),
+            Constraint::Length(1),
             Constraint::Min(0),
+            Constraint::Length(1),
         ])
         .split(area);
 
     render_logo(frame, chunks[1]);
-    render_menu(frame, chunks[3], stats.is_initialized);
-    render_stats(frame, chunks[4], stats);
+    render_menu(frame, chunks[2], dashboard);
+    render_stats(frame, chunks[3], dashboard);
+    render_panels(frame, chunks[5], dashboard);
+    render_hints(frame, chunks[6], dashboard);
 }
 
 fn render_logo(frame: &mut Frame, area: Rect) {
@@ -244,12 +419,13 @@ fn render_logo(frame: &mut Frame, area: Rect) {
     frame.render_widget(logo, area);
 }
 
-fn render_menu(frame: &mut Frame, area: Rect, is_initialized: bool) {
-    let menu_lines: Vec<Line> = MENU_ITEMS
+fn render_menu(frame: &mut Frame, area: Rect, dashboard: &Dashboard) {
+    let menu_lines: Vec<Line> = dashboard
+        .menu
         .iter()
         .map(|item| {
-            let is_init_item = item.key == 'i';
-            let should_highlight = !is_initialized && is_init_item;
+            let is_init_item = item.item == DashboardItem::Init;
+            let should_highlight = !dashboard.stats.is_initialized && is_init_item;
 
             let key_style = if should_highlight {
                 Style::default()
@@ -266,7 +442,11 @@ fn render_menu(frame: &mut Frame, area: Rect, is_initialized: bool) {
             };
 
             Line::from(vec![
-                Span::raw("         "),
+                Span::raw(if should_highlight {
+                    "       ▸ "
+                } else {
+                    "         "
+                }),
                 Span::raw(item.icon),
                 Span::raw("  "),
                 Span::styled(format!("{:<24}", item.label), label_style),
@@ -281,13 +461,14 @@ fn render_menu(frame: &mut Frame, area: Rect, is_initialized: bool) {
     frame.render_widget(menu, centered_area);
 }
 
-fn render_stats(frame: &mut Frame, area: Rect, stats: &DashboardStats) {
+fn render_stats(frame: &mut Frame, area: Rect, dashboard: &Dashboard) {
+    let stats = &dashboard.stats;
     let stats_line = if stats.is_initialized {
         Line::from(vec![
             Span::raw("         "),
             Span::styled("⚡ ", Style::default().fg(Color::Yellow)),
             Span::styled(
-                format!("{} projects", stats.unit_count),
+                format!("{} projects", dashboard.units.len()),
                 Style::default().fg(Color::Gray),
             ),
             Span::styled(" │ ", Style::default().fg(Color::Gray)),
@@ -296,6 +477,11 @@ fn render_stats(frame: &mut Frame, area: Rect, stats: &DashboardStats) {
                 Style::default().fg(Color::Gray),
             ),
             Span::styled(" │ ", Style::default().fg(Color::Gray)),
+            Span::styled(
+                format!("{} release PRs", dashboard.pending.len()),
+                Style::default().fg(Color::Gray),
+            ),
+            Span::styled(" │ ", Style::default().fg(Color::Gray)),
             Span::styled(
                 stats.current_branch.clone(),
                 Style::default().fg(Color::Cyan),
@@ -318,12 +504,175 @@ fn render_stats(frame: &mut Frame, area: Rect, stats: &DashboardStats) {
 
     let stats_widget = Paragraph::new(vec![Line::from(""), stats_line]).alignment(Alignment::Left);
 
-    let centered_area = centered_horizontal(area, 60);
+    let centered_area = centered_horizontal(area, 72);
     frame.render_widget(stats_widget, centered_area);
 }
 
+fn render_panels(frame: &mut Frame, area: Rect, dashboard: &Dashboard) {
+    let show_releases = dashboard.recent_limit > 0;
+    if !dashboard.stats.is_initialized && !show_releases {
+        return;
+    }
+    let area = centered_horizontal(area, 100);
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(if show_releases {
+            [Constraint::Percentage(55), Constraint::Percentage(45)]
+        } else {
+            [Constraint::Percentage(100), Constraint::Percentage(0)]
+        })
+        .split(area);
+
+    if dashboard.stats.is_initialized {
+        render_projects(frame, columns[0], dashboard);
+    }
+    if show_releases {
+        render_recent_releases(frame, columns[1], dashboard);
+    }
+}
+
+fn render_projects(frame: &mut Frame, area: Rect, dashboard: &Dashboard) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Gray))
+        .title(" Projects ");
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let name_width = dashboard
+        .units
+        .iter()
+        .map(|u| text::display_width(&u.name))
+        .max()
+        .unwrap_or(0)
+        .min(24);
+    // Keep the selected row in view when the list outgrows the panel.
+    let height = inner.height as usize;
+    let skip = (dashboard.selected + 1).saturating_sub(height);
+    let lines: Vec<Line> = dashboard
+        .units
+        .iter()
+        .enumerate()
+        .skip(skip)
+        .take(height)
+        .map(|(idx, unit)| {
+            let is_selected = idx == dashboard.selected;
+            let pr = match dashboard.pending_for(&unit.name) {
+                Some(pr) => Span::styled(
+                    format!("  PR #{} ({})", pr.number, pr.checks_label()),
+                    Style::default().fg(Color::Cyan),
+                ),
+                None => Span::raw(""),
+            };
+            let commits_style = if unit.pending_commits > 0 {
+                Style::default().fg(Color::Yellow)
+            } else {
+                Style::default().fg(Color::Gray)
+            };
+            let line = Line::from(vec![
+                Span::raw(if is_selected { "▶ " } else { "  " }),
+                Span::styled(
+                    text::fit(&unit.name, name_width),
+                    Style::default().fg(Color::White),
+                ),
+                Span::styled(
+                    format!("  {:<10}", unit.version.as_deref().unwrap_or("—")),
+                    Style::default().fg(Color::Gray),
+                ),
+                Span::styled(
+                    format!("{:>4} commits", unit.pending_commits),
+                    commits_style,
+                ),
+                pr,
+            ]);
+            if is_selected {
+                line.style(theme::row_highlight())
+            } else {
+                line
+            }
+        })
+        .collect();
+
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+fn render_recent_releases(frame: &mut Frame, area: Rect, dashboard: &Dashboard) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Gray))
+        .title(" Recent releases ");
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let summary_width = (inner.width as usize).saturating_sub(12);
+    let lines: Vec<Line> = if dashboard.releases.is_empty() {
+        vec![Line::from(Span::styled(
+            "  No releases recorded yet",
+            Style::default().fg(Color::Gray),
+        ))]
+    } else {
+        dashboard
+            .releases
+            .iter()
+            .map(|release| {
+                Line::from(vec![
+                    Span::styled(
+                        format!("{:<10}  ", release.date),
+                        Style::default().fg(Color::Gray),
+                    ),
+                    Span::styled(
+                        text::truncate(&release.summary, summary_width).into_owned(),
+                        Style::default().fg(Color::White),
+                    ),
+                ])
+            })
+            .collect()
+    };
+
+    frame.render_widget(Paragraph::new(lines), inner);
+}
+
+fn render_hints(frame: &mut Frame, area: Rect, dashboard: &Dashboard) {
+    let line = match &dashboard.message {
+        Some(message) => Line::from(Span::styled(
+            message.clone(),
+            Style::default().fg(Color::Yellow),
+        )),
+        None => Line::from(vec![
+            Span::styled("↑↓", Style::default().fg(Color::Cyan)),
+            Span::styled(" select  ", Style::default().fg(Color::Gray)),
+            Span::styled("o", Style::default().fg(Color::Cyan)),
+            Span::styled(" open release PR  ", Style::default().fg(Color::Gray)),
+            Span::styled("r", Style::default().fg(Color::Cyan)),
+            Span::styled(" refresh  ", Style::default().fg(Color::Gray)),
+            Span::styled("q", Style::default().fg(Color::Red)),
+            Span::styled(" quit", Style::default().fg(Color::Gray)),
+        ]),
+    };
+    frame.render_widget(Paragraph::new(line).alignment(Alignment::Center), area);
+}
+
 fn centered_horizontal(area: Rect, width: u16) -> Rect {
     let actual_width = width.min(area.width);
     let x = area.x + (area.width.saturating_sub(actual_width)) / 2;
     Rect::new(x, area.y, actual_width, area.height)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn configured_actions_pick_and_order_the_menu() {
+        let keys = |items: Vec<&MenuItem>| items.iter().map(|m| m.key).collect::<String>();
+        assert_eq!(keys(menu_items(None)), "psgciw?q");
+        assert_eq!(
+            keys(menu_items(Some(&[
+                DashboardItem::Status,
+                DashboardItem::Prepare,
+                DashboardItem::Quit,
+            ]))),
+            "spq"
+        );
+    }
+}
@@ -0,0 +1,119 @@
+//! `belaf debug` — diagnostics about belaf itself.
+//!
+//! `timings` replays the read-only half of `status` / `changelog` —
+//! session setup, history analysis, one changelog entry per unit —
+//! with [`crate::core::timings`] recording, then prints the totals per
+//! stage. Useful to tell which stage a slow run spends its time in.
+
+use std::time::Instant;
+
+use anyhow::{Context, Result};
+use owo_colors::OwoColorize;
+use serde::Serialize;
+
+use crate::cli::TimingsOutputFormat;
+use crate::core::{
+    bump::BumpConfig,
+    changelog::{ChangelogConfig, Commit, GitConfig},
+    graph::GraphQueryBuilder,
+    session::AppSession,
+    timings::{self, StageTiming},
+    workflow::generate_changelog_entry,
+};
+
+#[derive(Serialize)]
+struct TimingsPayload {
+    units: usize,
+    commits: usize,
+    total_ms: f64,
+    stages: Vec<StageTiming>,
+}
+
+pub fn run_timings(format: Option<TimingsOutputFormat>) -> Result<i32> {
+    timings::enable();
+    let started = Instant::now();
+
+    let sess = AppSession::initialize_default()?;
+    let idents = sess.graph().query(GraphQueryBuilder::default())?;
+    let histories = sess
+        .analyze_histories()
+        .context("failed to analyze project histories")?;
+
+    let git_config = GitConfig::from_user_config(&sess.changelog_config);
+    let bump_config = BumpConfig::from_user_config(&sess.bump_config);
+
+    let mut n_commits = 0;
+    for ident in &idents {
+        let unit = sess.graph().lookup(*ident);
+        let commits: Vec<Commit> = {
+            let _timing = timings::stage("commit details");
+            histories
+                .lookup(*ident)
+                .commits()
+                .into_iter()
+                .filter_map(|cid| sess.repo.get_commit_details(*cid).ok())
+                .collect()
+        };
+        n_commits += commits.len();
+        if commits.is_empty() {
+            continue;
+        }
+        let ecosystem = unit.qualified_names().get(1).cloned().unwrap_or_default();
+        let changelog_config = ChangelogConfig::from_user_config(
+            &sess
+                .changelog_config
+                .for_project(&unit.user_facing_name, &ecosystem),
+        );
+        generate_changelog_entry(
+            &unit.version.to_string(),
+            &commits,
+            &git_config,
+            &changelog_config,
+            &bump_config,
+        )
+        .with_context(|| {
+            format!(
+                "failed to render the changelog of {}",
+                unit.user_facing_name
+            )
+        })?;
+    }
+
+    let payload = TimingsPayload {
+        units: idents.len(),
+        commits: n_commits,
+        total_ms: started.elapsed().as_secs_f64() * 1000.0,
+        stages: timings::recorded(),
+    };
+
+    if format == Some(TimingsOutputFormat::Json) {
+        println!("{}", serde_json::to_string_pretty(&payload)?);
+        return Ok(0);
+    }
+
+    println!("{}", "belaf debug timings".bold());
+    println!(
+        "{}",
+        format!("{} units, {} commits", payload.units, payload.commits).dimmed()
+    );
+    println!();
+    for s in &payload.stages {
+        let calls = if s.calls > 1 {
+            format!(" ({}×)", s.calls).dimmed().to_string()
+        } else {
+            String::new()
+        };
+        println!(
+            "  {:<24} {:>10.1} ms{}",
+            s.stage,
+            s.elapsed.as_secs_f64() * 1000.0,
+            calls
+        );
+    }
+    println!(
+        "  {} {:>10.1} ms",
+        format!("{:<24}", "total").bold(),
+        payload.total_ms
+    );
+    Ok(0)
+}
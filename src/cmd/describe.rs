@@ -18,6 +18,7 @@ use serde::Serialize;
 
 use crate::cli::Cli;
 use crate::cmd::schema::AVAILABLE_SCHEMAS;
+use crate::core::errors::ErrorCode;
 use crate::core::exit_code::ExitCode;
 
 #[derive(Serialize)]
@@ -29,6 +30,7 @@ struct DescribeOutput {
     commands: Vec<CommandDoc>,
     env_vars: Vec<EnvVarDoc>,
     exit_codes: Vec<ExitCodeDoc>,
+    error_codes: Vec<ErrorCodeDoc>,
     schemas: Vec<SchemaDoc>,
     example_workflows: Vec<WorkflowDoc>,
 }
@@ -74,6 +76,14 @@ struct ExitCodeDoc {
     description: &'static str,
 }
 
+/// A `code` of the `--format json` error payload on stderr.
+#[derive(Serialize)]
+struct ErrorCodeDoc {
+    code: &'static str,
+    exit_code: i32,
+    description: &'static str,
+}
+
 #[derive(Serialize)]
 struct SchemaDoc {
     name: &'static str,
@@ -100,6 +110,30 @@ const ENV_VARS: &[EnvVarDoc] = &[
         name: "BELAF_NO_KEYRING",
         purpose: "Set to `1` to disable the OS keyring. Required in headless / test environments where the keyring crate hangs.",
     },
+    EnvVarDoc {
+        name: "BELAF_TOKEN_STORE",
+        purpose: "Set to `file` to keep tokens in `credentials/<profile>.json` under the user config directory instead of the OS keychain. belaf falls back to that file on its own when no keychain is reachable.",
+    },
+    EnvVarDoc {
+        name: "BELAF_PROFILE",
+        purpose: "Auth profile whose keyring token to use, same as `--profile`. Overrides `[auth] profile` and remote matching.",
+    },
+    EnvVarDoc {
+        name: "BELAF_SCOPE",
+        purpose: "Directory to restrict release-unit discovery and history analysis to, same as `--scope`.",
+    },
+    EnvVarDoc {
+        name: "NO_COLOR",
+        purpose: "Any value disables colors, same as `--no-color`, in plain output and the terminal UIs. Bump levels keep their ▲ / ◆ / ● shapes.",
+    },
+    EnvVarDoc {
+        name: "BELAF_ICONS",
+        purpose: "Glyph set of the terminal UIs: `unicode` (default), `nerd` for Nerd Font icons, or `ascii` for dumb terminals and CI logs.",
+    },
+    EnvVarDoc {
+        name: "BELAF_DETERMINISTIC",
+        purpose: "Set to `1` for reproducible output: a fixed clock (2024-01-01T00:00:00Z), seeded release branch suffixes and manifest ids, and no spinners or update check.",
+    },
     EnvVarDoc {
         name: "RUST_LOG",
         purpose: "Standard tracing filter. CLI verbosity flags (-v / -vv / -vvv) override this.",
@@ -112,6 +146,10 @@ const ENV_VARS: &[EnvVarDoc] = &[
         name: "GITHUB_ACTIONS",
         purpose: "Auto-detected (alongside GITLAB_CI etc). Same effect as CI.",
     },
+    EnvVarDoc {
+        name: "GITHUB_REF",
+        purpose: "Set by GitHub Actions. When HEAD is detached, `prepare` releases for the branch it names unless `[git] base_branch` is set.",
+    },
     EnvVarDoc {
         name: "ACTIONS_ID_TOKEN_REQUEST_URL",
         purpose: "Set automatically by GitHub Actions when the job has `permissions: id-token: write`. belaf falls back to OIDC token exchange via /api/cli/auth/oidc/exchange when the keyring is empty.",
@@ -132,6 +170,35 @@ const WORKFLOWS: &[WorkflowDoc] = &[
             "# (resulting PR is reviewed and merged manually or by automation)",
         ],
     },
+    WorkflowDoc {
+        name: "release-approval",
+        description:
+            "Two-person rule for releases gated by [release.approvers]: an approver signs off on the release branch, CI blocks the release PR until enough have.",
+        steps: &[
+            "belaf approve <release-id>",
+            "git commit -am 'chore(release): approve' && git push",
+            "# CI, as a required check on the release PR:",
+            "belaf approve <release-id> --check",
+        ],
+    },
+    WorkflowDoc {
+        name: "promote-prerelease",
+        description:
+            "Ship the stable version of a tested prerelease: strips -rc.N/-beta.N from the unit's latest tag, merges the prerelease changelog entries and opens the release PR.",
+        steps: &[
+            "belaf promote <release-unit>",
+            "# (the GitHub App tags and publishes the stable release when the PR merges)",
+        ],
+    },
+    WorkflowDoc {
+        name: "package-release",
+        description:
+            "After a release's archives are built, commit a Homebrew formula, Scoop manifest and AUR PKGBUILD for it to the repositories in [packaging.<unit>].",
+        steps: &[
+            "belaf package <release-unit> --artifacts dist --dry-run",
+            "belaf package <release-unit> --artifacts dist",
+        ],
+    },
     WorkflowDoc {
         name: "inspect-config",
         description:
@@ -141,6 +208,14 @@ const WORKFLOWS: &[WorkflowDoc] = &[
             "belaf graph --format=json",
         ],
     },
+    WorkflowDoc {
+        name: "ci-test-selection",
+        description:
+            "Run only the test suites of the release units a pull request touches, including units that depend on them.",
+        steps: &[
+            "belaf affected --base origin/main --format=json | jq -r '.units[].name'",
+        ],
+    },
     WorkflowDoc {
         name: "preview-changelog",
         description:
@@ -196,6 +271,14 @@ fn build_output(root: &clap::Command) -> DescribeOutput {
                 description: c.description(),
             })
             .collect(),
+        error_codes: ErrorCode::all()
+            .iter()
+            .map(|c| ErrorCodeDoc {
+                code: c.label(),
+                exit_code: c.exit_code().into(),
+                description: c.description(),
+            })
+            .collect(),
         schemas: AVAILABLE_SCHEMAS
             .iter()
             .map(|(name, description)| SchemaDoc { name, description })
@@ -283,6 +366,15 @@ fn render_text(out: &DescribeOutput) {
     }
     println!();
 
+    println!("ERROR CODES (`code` of the JSON error on stderr under --format json)");
+    for ec in &out.error_codes {
+        println!(
+            "  {:<16}  exit {}  {}",
+            ec.code, ec.exit_code, ec.description
+        );
+    }
+    println!();
+
     println!("ENV VARS");
     for ev in &out.env_vars {
         println!("  {}", ev.name);
@@ -100,6 +100,8 @@ struct DoctorReport {
     config: Check,
     repository: Check,
     ecosystems: Check,
+    /// `[checks] publish_metadata`; skipped unless enabled.
+    metadata: Check,
     api: Check,
     environment: EnvironmentReport,
 }
@@ -164,7 +166,7 @@ async fn build_report() -> DoctorReport {
         Ok(None) if environment.keyring_disabled => Check::skipped(
             "keyring disabled (BELAF_NO_KEYRING set); auth check skipped — set up auth via OIDC env vars or `belaf install` in an interactive shell",
         ),
-        Ok(None) => Check::error("no token in keyring; run `belaf install` to authenticate"),
+        Ok(None) => Check::error("no stored token (keychain or token file); run `belaf install` to authenticate"),
         Err(e) => Check::error(format!("token storage error: {e}")),
     };
 
@@ -224,6 +226,13 @@ async fn build_report() -> DoctorReport {
         (_, Err(e)) => Check::skipped(format!("not in a git repo — auto-detect skipped ({e})")),
     };
 
+    // === Publish metadata ([checks] publish_metadata) ===
+    let metadata = match &config_loaded {
+        Some(cfg) if cfg.checks.publish_metadata => check_publish_metadata(),
+        Some(_) => Check::skipped("`[checks] publish_metadata` not enabled"),
+        None => Check::skipped("config invalid or missing — metadata check skipped"),
+    };
+
     // === API connectivity ===
     let api = probe_api_health(&environment.api_url).await;
 
@@ -231,6 +240,7 @@ async fn build_report() -> DoctorReport {
         && !config.is_blocker()
         && !repository.is_blocker()
         && !ecosystems.is_blocker()
+        && !metadata.is_blocker()
         && !api.is_blocker();
 
     DoctorReport {
@@ -239,11 +249,38 @@ async fn build_report() -> DoctorReport {
         config,
         repository,
         ecosystems,
+        metadata,
         api,
         environment,
     }
 }
 
+/// Every unit's manifest against [`crate::core::publish_checks`].
+fn check_publish_metadata() -> Check {
+    use crate::core::{publish_checks, session::AppSession};
+
+    let sess = match AppSession::initialize_default() {
+        Ok(sess) => sess,
+        Err(e) => return Check::skipped(format!("could not load release units — {e}")),
+    };
+    let mut problems = Vec::new();
+    for unit in sess.graph().projects_slice() {
+        match publish_checks::check_unit(&sess, unit.ident()) {
+            Ok(found) => problems.extend(found),
+            Err(e) => return Check::error(format!("{}: {e:#}", unit.user_facing_name)),
+        }
+    }
+    if problems.is_empty() {
+        return Check::ok("every release unit has its publish metadata");
+    }
+    let detail: Vec<String> = problems.iter().map(|p| p.describe()).collect();
+    Check::error(format!(
+        "{} publish metadata field(s) missing; `prepare` will refuse to release",
+        problems.len()
+    ))
+    .with_detail(detail.join("; "))
+}
+
 /// HTTP probe against `<api_url>/health`. Short timeout (3s) so the
 /// doctor command stays snappy even when the network is degraded.
 /// Translates concrete failures into action-oriented messages so an
@@ -255,10 +292,11 @@ async fn probe_api_health(base_url: &str) -> Check {
     }
 
     let url = format!("{}/health", base_url.trim_end_matches('/'));
-    let client = match reqwest::Client::builder()
-        .timeout(std::time::Duration::from_secs(3))
-        .build()
-    {
+    let client = match crate::core::net::client_builder().and_then(|b| {
+        b.timeout(std::time::Duration::from_secs(3))
+            .build()
+            .map_err(ApiError::from)
+    }) {
         Ok(c) => c,
         Err(e) => return Check::warn(format!("could not build HTTP client: {e}")),
     };
@@ -361,6 +399,7 @@ fn render_text(r: &DoctorReport) {
     render_check("config      ", &r.config);
     render_check("repository  ", &r.repository);
     render_check("ecosystems  ", &r.ecosystems);
+    render_check("metadata    ", &r.metadata);
     render_check("api         ", &r.api);
     println!();
     println!("{}", "environment".bold());
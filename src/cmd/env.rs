@@ -0,0 +1,176 @@
+//! `belaf env` — everything a bug report needs, in one block.
+//!
+//! Prints the belaf, git and libgit2 versions, the OS, the ecosystems
+//! auto-detection finds in the current repository, a summary of
+//! `belaf/config.toml`, and the last few errors belaf reported (see
+//! [`crate::core::errors::record_error`]), fenced so it pastes straight
+//! into a GitHub issue. `--redact` masks the repository path, the home
+//! directory, remote URLs and the git identity before printing.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+use anyhow::Result;
+
+use crate::core::{
+    config::ConfigurationFile,
+    ecosystem::format_handler::{FormatHandlerRegistry, WorkspaceDiscovererRegistry},
+    errors::recent_errors,
+    git::repository::{RepoPathBuf, Repository},
+    release_unit::discovery::discover_implicit_release_units,
+};
+
+pub fn run(redact: bool) -> Result<i32> {
+    let repo = Repository::open_from_env().ok();
+    let mut out = String::new();
+
+    writeln!(out, "belaf       {}", env!("CARGO_PKG_VERSION"))?;
+    writeln!(out, "git         {}", git_version())?;
+    writeln!(out, "libgit2     {}", libgit2_version())?;
+    writeln!(out, "os          {}", os_description())?;
+    writeln!(out)?;
+
+    match &repo {
+        Some(repo) => {
+            writeln!(out, "repository  {}", workdir(repo))?;
+            let remote = repo.upstream_url().unwrap_or_else(|_| "(none)".to_owned());
+            writeln!(out, "remote      {remote}")?;
+            writeln!(out, "ecosystems  {}", ecosystems(repo))?;
+            writeln!(out, "config      {}", config_summary(repo))?;
+        }
+        None => writeln!(out, "repository  (not in a git working tree)")?,
+    }
+
+    let errors = recent_errors();
+    writeln!(out)?;
+    if errors.is_empty() {
+        writeln!(out, "recent errors: none")?;
+    }
+    for (i, e) in errors.iter().rev().enumerate() {
+        if i == 0 {
+            writeln!(out, "recent errors, newest first:")?;
+        }
+        writeln!(out, "--- {}  belaf {}", e.timestamp, e.args.join(" "))?;
+        writeln!(out, "{}", e.diagnostic.trim_end())?;
+    }
+
+    let out = if redact {
+        redactions(repo.as_ref())
+            .into_iter()
+            .fold(out, |text, (secret, mask)| text.replace(&secret, mask))
+    } else {
+        out
+    };
+    println!("```text\n{out}```");
+    Ok(0)
+}
+
+fn workdir(repo: &Repository) -> String {
+    let path = repo.resolve_workdir(&RepoPathBuf::new(b""));
+    path.to_string_lossy().trim_end_matches('/').to_owned()
+}
+
+/// `git --version`, minus the `git version` prefix.
+fn git_version() -> String {
+    match std::process::Command::new("git").arg("--version").output() {
+        Ok(o) if o.status.success() => String::from_utf8_lossy(&o.stdout)
+            .trim()
+            .trim_start_matches("git version ")
+            .to_owned(),
+        Ok(o) => format!("(`git --version` failed: {})", o.status),
+        Err(_) => "(not found on PATH)".to_owned(),
+    }
+}
+
+fn libgit2_version() -> String {
+    let v = git2::Version::get();
+    let (major, minor, patch) = v.libgit2_version();
+    let vendored = if v.vendored() { ", vendored" } else { "" };
+    format!(
+        "{major}.{minor}.{patch} (git2 {}{vendored})",
+        v.crate_version()
+    )
+}
+
+/// OS and architecture, plus the distribution on Linux.
+fn os_description() -> String {
+    let mut desc = format!("{} {}", std::env::consts::OS, std::env::consts::ARCH);
+    let pretty_name = std::fs::read_to_string("/etc/os-release")
+        .ok()
+        .and_then(|release| {
+            release.lines().find_map(|l| {
+                l.strip_prefix("PRETTY_NAME=")
+                    .map(|v| v.trim_matches('"').to_owned())
+            })
+        });
+    if let Some(name) = pretty_name {
+        let _ = write!(desc, " ({name})");
+    }
+    desc
+}
+
+/// Auto-detected release units per ecosystem, e.g. `cargo=2, npm=1`.
+fn ecosystems(repo: &Repository) -> String {
+    let handlers = FormatHandlerRegistry::with_defaults();
+    let discoverers = WorkspaceDiscovererRegistry::with_defaults();
+    let units = match discover_implicit_release_units(repo, &handlers, &discoverers, &[]) {
+        Ok(units) => units,
+        Err(e) => return format!("(auto-detect failed: {e})"),
+    };
+    if units.is_empty() {
+        return "(none detected)".to_owned();
+    }
+    let mut by_eco: BTreeMap<&str, usize> = BTreeMap::new();
+    for u in &units {
+        let eco = u.qnames.get(1).map(String::as_str).unwrap_or("(unknown)");
+        *by_eco.entry(eco).or_insert(0) += 1;
+    }
+    by_eco
+        .into_iter()
+        .map(|(k, v)| format!("{k}={v}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn config_summary(repo: &Repository) -> String {
+    let path = repo.resolve_config_dir().join("config.toml");
+    if !path.exists() {
+        return "belaf/config.toml not found".to_owned();
+    }
+    match ConfigurationFile::get(&path) {
+        Ok(cfg) => format!(
+            "belaf/config.toml: {} [release_unit] block(s), {} group(s), {} bump source(s){}",
+            cfg.release_units.len(),
+            cfg.groups.len(),
+            cfg.bump_sources.len(),
+            if cfg.release.approvers.is_some() {
+                ", [release.approvers] set"
+            } else {
+                ""
+            }
+        ),
+        Err(e) => format!("belaf/config.toml invalid: {e:#}"),
+    }
+}
+
+/// What `--redact` replaces, longest first so the repository path is
+/// masked before the home directory that usually contains it.
+fn redactions(repo: Option<&Repository>) -> Vec<(String, &'static str)> {
+    let mut r = Vec::new();
+    if let Some(repo) = repo {
+        r.push((workdir(repo), "<repo>"));
+        if let Ok(url) = repo.upstream_url() {
+            r.push((url, "<remote>"));
+        }
+        if let Ok(sig) = repo.get_signature() {
+            r.extend(sig.name().map(|n| (n.to_owned(), "<name>")));
+            r.extend(sig.email().map(|e| (e.to_owned(), "<email>")));
+        }
+    }
+    if let Some(home) = dirs::home_dir().filter(|h| h.parent().is_some()) {
+        r.push((home.to_string_lossy().into_owned(), "~"));
+    }
+    r.retain(|(secret, _)| !secret.is_empty());
+    r.sort_by_key(|(secret, _)| std::cmp::Reverse(secret.len()));
+    r
+}
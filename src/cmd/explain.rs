@@ -15,11 +15,14 @@ use serde::Serialize;
 
 use crate::cli::ExplainOutputFormat;
 use crate::core::config::ConfigurationFile;
+use crate::core::errors::ConfigError;
 use crate::core::git::repository::Repository;
 use crate::core::release_unit::{detector, resolver::resolve, ResolveOrigin, VersionSource};
 
 #[derive(Serialize)]
 struct ExplainPayload {
+    /// The config files merged, lowest precedence first.
+    config_files: Vec<String>,
     units: Vec<ExplainUnit>,
     drift: ExplainDrift,
     ignore_paths: Vec<String>,
@@ -34,6 +37,8 @@ struct ExplainUnit {
     source: ExplainSource,
     satellites: Vec<String>,
     tag_format: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    previous_names: Vec<String>,
     visibility: String,
     cascade_from: Option<ExplainCascade>,
 }
@@ -81,7 +86,7 @@ pub fn run(format: Option<ExplainOutputFormat>) -> Result<i32> {
     let mut cfg_path = repo.resolve_config_dir();
     cfg_path.push("config.toml");
     let cfg = ConfigurationFile::get(&cfg_path)
-        .with_context(|| format!("failed to load config at {}", cfg_path.display()))?;
+        .with_context(|| ConfigError(format!("failed to load config at {}", cfg_path.display())))?;
 
     // `belaf explain` only shows resolved units sourced from the config
     // (explicit + glob). Partial overrides require a discovery pass to
@@ -105,6 +110,17 @@ pub fn run(format: Option<ExplainOutputFormat>) -> Result<i32> {
     println!();
     println!("{}", "Belaf — config explain".bold());
     println!();
+    let config_files = config_files(&repo, &cfg);
+    if config_files.len() > 1 {
+        println!(
+            "{} (lowest precedence first)",
+            "Config files:".green().bold()
+        );
+        for file in &config_files {
+            println!("  {file}");
+        }
+        println!();
+    }
 
     if resolved.is_empty() {
         println!(
@@ -181,6 +197,9 @@ pub fn run(format: Option<ExplainOutputFormat>) -> Result<i32> {
         if let Some(tf) = &r.unit.tag_format {
             println!("    tag_format: {}", tf);
         }
+        if !r.unit.previous_names.is_empty() {
+            println!("    renamed   : from {}", r.unit.previous_names.join(", "));
+        }
         if r.unit.visibility != crate::core::release_unit::Visibility::Public {
             println!("    visibility: {}", r.unit.visibility.wire_key().yellow());
         }
@@ -261,6 +280,7 @@ fn build_json_payload(
                 .map(|s| s.escaped().to_string())
                 .collect(),
             tag_format: r.unit.tag_format.clone(),
+            previous_names: r.unit.previous_names.clone(),
             visibility: r.unit.visibility.wire_key().to_string(),
             cascade_from: r.unit.cascade_from.as_ref().map(|c| ExplainCascade {
                 source: c.source.clone(),
@@ -279,9 +299,20 @@ fn build_json_payload(
     };
 
     ExplainPayload {
+        config_files: config_files(repo, cfg),
         units,
         drift,
         ignore_paths: cfg.ignore_paths.paths.clone(),
         allow_uncovered: cfg.allow_uncovered.paths.clone(),
     }
 }
+
+fn config_files(repo: &Repository, cfg: &ConfigurationFile) -> Vec<String> {
+    cfg.sources
+        .iter()
+        .map(|p| match repo.convert_path(p) {
+            Ok(rel) => rel.escaped().to_string(),
+            Err(_) => p.display().to_string(),
+        })
+        .collect()
+}
@@ -0,0 +1,266 @@
+//! `belaf export site` — a release notes site from the changelogs.
+//!
+//! Every release unit's changelog file is split into its entries, one
+//! per `## ` heading, as the `[changelog]` templates rendered them (and
+//! as any hand edits left them). The entries become a page per version,
+//! listed by a page per project, listed by a top-level index:
+//!
+//! ```text
+//! OUT/index.md
+//! OUT/<project>/index.md
+//! OUT/<project>/<version>.md
+//! ```
+//!
+//! Markdown pages start with YAML front matter, which Jekyll, Hugo,
+//! MkDocs and friends read; `--format html` writes standalone pages
+//! with `.html` links instead.
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use owo_colors::OwoColorize;
+
+use crate::cli::SiteFormat;
+use crate::core::{changelog::ChangelogConfig, session::AppSession, workflow::changelog_path};
+
+/// One release entry of a changelog.
+#[derive(Debug, PartialEq)]
+struct Entry {
+    version: String,
+    date: Option<String>,
+    /// The entry's Markdown, from its heading on.
+    body: String,
+}
+
+struct ProjectPages {
+    name: String,
+    slug: String,
+    entries: Vec<Entry>,
+}
+
+pub fn run_site(out: std::path::PathBuf, format: Option<SiteFormat>) -> Result<i32> {
+    let sess = AppSession::initialize_default()?;
+    let html = format == Some(SiteFormat::Html);
+    let ext = if html { "html" } else { "md" };
+    let config = ChangelogConfig::from_user_config(&sess.changelog_config);
+
+    let mut projects: Vec<ProjectPages> = sess
+        .graph()
+        .projects()
+        .filter_map(|unit| {
+            let path = changelog_path(&config, &unit.prefix().escaped());
+            let text = fs::read_to_string(sess.repo.resolve_workdir(path.as_ref())).ok()?;
+            let entries = split_entries(&text);
+            (!entries.is_empty()).then(|| ProjectPages {
+                name: unit.user_facing_name.clone(),
+                slug: slug(&unit.user_facing_name),
+                entries,
+            })
+        })
+        .collect();
+    projects.sort_by(|a, b| a.name.cmp(&b.name));
+
+    if projects.is_empty() {
+        println!(
+            "{} No released versions in the changelogs; nothing to export.",
+            "ℹ".cyan().bold()
+        );
+        return Ok(0);
+    }
+
+    let mut index = String::from("# Releases\n\n");
+    let mut pages = 1;
+    for project in &projects {
+        let latest = &project.entries[0];
+        index.push_str(&format!(
+            "- [{}]({}/index.{ext}) — {}{}\n",
+            project.name,
+            project.slug,
+            latest.version,
+            dated(latest)
+        ));
+
+        let mut listing = format!("# {}\n\n", project.name);
+        for entry in &project.entries {
+            listing.push_str(&format!(
+                "- [{}]({}.{ext}){}\n",
+                entry.version,
+                entry.version,
+                dated(entry)
+            ));
+            let title = format!("{} {}", project.name, entry.version);
+            let page = out
+                .join(&project.slug)
+                .join(format!("{}.{ext}", entry.version));
+            let content = if html {
+                html_page(&title, &entry.body)
+            } else {
+                front_matter(&project.name, entry) + &entry.body
+            };
+            write_page(&page, &content)?;
+            pages += 1;
+        }
+        if html {
+            listing = html_page(&project.name, &listing);
+        }
+        write_page(
+            &out.join(&project.slug).join(format!("index.{ext}")),
+            &listing,
+        )?;
+        pages += 1;
+    }
+    if html {
+        index = html_page("Releases", &index);
+    }
+    write_page(&out.join(format!("index.{ext}")), &index)?;
+
+    println!(
+        "{} Wrote {pages} pages for {} project(s) to {}",
+        "✓".green().bold(),
+        projects.len(),
+        out.display()
+    );
+    Ok(0)
+}
+
+/// The release entries of `changelog`, newest first as the file lists
+/// them. An `Unreleased` entry is skipped.
+fn split_entries(changelog: &str) -> Vec<Entry> {
+    let mut entries: Vec<Entry> = Vec::new();
+    let mut current: Option<Entry> = None;
+    for line in changelog.split_inclusive('\n') {
+        if let Some(heading) = line.strip_prefix("## ") {
+            entries.extend(current.take());
+            let (version, date) = parse_heading(heading.trim());
+            current = Some(Entry {
+                version,
+                date,
+                body: String::new(),
+            });
+        }
+        if let Some(entry) = &mut current {
+            entry.body.push_str(line);
+        }
+    }
+    entries.extend(current);
+    entries.retain(|e| !e.version.eq_ignore_ascii_case("unreleased"));
+    for entry in &mut entries {
+        entry.body = format!("{}\n", entry.body.trim_end());
+    }
+    entries
+}
+
+/// The version and date of a `[1.2.0] - 2026-10-16`-style heading.
+fn parse_heading(heading: &str) -> (String, Option<String>) {
+    let (version, rest) = match heading.strip_prefix('[') {
+        Some(linked) => match linked.split_once(']') {
+            Some((version, rest)) => {
+                // `[1.2.0](compare-url)`
+                let rest = match rest.strip_prefix('(') {
+                    Some(link) => link.split_once(')').map_or("", |(_, r)| r),
+                    None => rest,
+                };
+                (version, rest)
+            }
+            None => (linked, ""),
+        },
+        None => heading.split_once(' ').unwrap_or((heading, "")),
+    };
+    let date = rest
+        .trim()
+        .trim_start_matches(['-', '–', '—'])
+        .trim()
+        .trim_matches(['(', ')']);
+    let version = version.trim().trim_start_matches('v').to_string();
+    (version, (!date.is_empty()).then(|| date.to_string()))
+}
+
+/// A file-name-safe form of a unit name: `@org/pkg` → `org-pkg`.
+fn slug(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() || matches!(c, '.' | '_') {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_end_matches('-').to_string()
+}
+
+fn dated(entry: &Entry) -> String {
+    entry
+        .date
+        .as_deref()
+        .map(|date| format!(" ({date})"))
+        .unwrap_or_default()
+}
+
+fn front_matter(project: &str, entry: &Entry) -> String {
+    let mut front = format!(
+        "---\ntitle: \"{project} {}\"\nproject: \"{project}\"\nversion: \"{}\"\n",
+        entry.version, entry.version
+    );
+    if let Some(date) = &entry.date {
+        front.push_str(&format!("date: {date}\n"));
+    }
+    front.push_str("---\n\n");
+    front
+}
+
+fn html_page(title: &str, markdown: &str) -> String {
+    let mut body = String::new();
+    let parser = pulldown_cmark::Parser::new_ext(markdown, pulldown_cmark::Options::all());
+    pulldown_cmark::html::push_html(&mut body, parser);
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body>\n{body}</body>\n</html>\n",
+        escape_html(title)
+    )
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn write_page(path: &Path, content: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create `{}`", parent.display()))?;
+    }
+    fs::write(path, content).with_context(|| format!("failed to write `{}`", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changelogs_split_into_dated_entries() {
+        let changelog = "# Changelog\n\n## [Unreleased]\n\n- wip\n\n\
+                         ## [1.1.0](https://x/compare/v1.0.0...v1.1.0) - 2026-10-16\n\n\
+                         ### Features\n\n- shiny\n\n\n## v1.0.0\n\n- first\n";
+        let entries = split_entries(changelog);
+        assert_eq!(
+            entries,
+            vec![
+                Entry {
+                    version: "1.1.0".into(),
+                    date: Some("2026-10-16".into()),
+                    body: "## [1.1.0](https://x/compare/v1.0.0...v1.1.0) - 2026-10-16\n\n\
+                           ### Features\n\n- shiny\n"
+                        .into(),
+                },
+                Entry {
+                    version: "1.0.0".into(),
+                    date: None,
+                    body: "## v1.0.0\n\n- first\n".into(),
+                },
+            ]
+        );
+        assert_eq!(slug("@org/pkg"), "org-pkg");
+    }
+}
@@ -1,9 +1,16 @@
+use std::collections::{HashMap, HashSet};
+
 use anyhow::Result;
-use tracing::info;
+use serde::Serialize;
+use tracing::{info, warn};
 
 use crate::{
     cli::GraphOutputFormat,
-    core::{graph::GraphQueryBuilder, session::AppSession},
+    core::{
+        ecosystem::format_handler::{DeclaredDep, FormatHandlerRegistry},
+        graph::GraphQueryBuilder,
+        session::AppSession,
+    },
 };
 
 #[path = "graph/wizard.rs"]
@@ -17,14 +24,15 @@ pub fn run(
     ci: bool,
     web: bool,
     out: Option<String>,
+    external: bool,
 ) -> Result<i32> {
     use crate::core::ui::utils::should_use_tui;
 
     if web || out.is_some() {
-        return browser::open_browser(out.as_deref());
+        return browser::open_browser(out.as_deref(), external);
     }
 
-    if should_use_tui(ci, &format) {
+    if !external && should_use_tui(ci, &format) {
         return wizard::run();
     }
 
@@ -33,7 +41,7 @@ pub fn run(
         env!("CARGO_PKG_VERSION")
     );
 
-    let sess = AppSession::initialize_default()?;
+    let sess = AppSession::initialize_read_only()?;
 
     let q = GraphQueryBuilder::default();
     let idents = sess.graph().query(q)?;
@@ -49,16 +57,101 @@ pub fn run(
         format.unwrap_or(GraphOutputFormat::Ascii)
     };
 
+    let externals = external
+        .then(|| external_deps(&sess, &idents))
+        .transpose()?;
+    let externals = externals.as_ref();
+
     match output_format {
-        GraphOutputFormat::Ascii => render_ascii(&sess, &idents),
-        GraphOutputFormat::Dot => render_dot(&sess, &idents),
-        GraphOutputFormat::Json => render_json(&sess, &idents)?,
+        GraphOutputFormat::Ascii => render_ascii(&sess, &idents, externals),
+        GraphOutputFormat::Dot => render_dot(&sess, &idents, externals),
+        GraphOutputFormat::Json => render_json(&sess, &idents, externals)?,
     }
 
     Ok(0)
 }
 
-fn render_ascii(sess: &AppSession, idents: &[usize]) {
+/// Direct external dependencies of each unit in `idents`, read from
+/// the manifests in the unit's directory that its ecosystem's handler
+/// claims. Dependencies on other units are dropped — those are already
+/// internal edges.
+fn external_deps(sess: &AppSession, idents: &[usize]) -> Result<HashMap<usize, Vec<DeclaredDep>>> {
+    let registry = FormatHandlerRegistry::with_defaults();
+    let graph = sess.graph();
+
+    let internal_names: HashSet<&str> = idents
+        .iter()
+        .flat_map(|id| {
+            let unit = graph.lookup(*id);
+            unit.qualified_names()
+                .first()
+                .map(String::as_str)
+                .into_iter()
+                .chain([unit.user_facing_name.as_str()])
+        })
+        .collect();
+
+    let mut by_prefix: HashMap<Vec<u8>, Vec<usize>> = HashMap::new();
+    for id in idents {
+        let prefix = graph.lookup(*id).prefix().as_ref().to_vec();
+        by_prefix.entry(prefix).or_default().push(*id);
+    }
+
+    let mut externals: HashMap<usize, Vec<DeclaredDep>> = HashMap::new();
+    sess.repo.scan_paths(|path| {
+        let (dir, _) = path.split_basename();
+        let Some(ids) = by_prefix.get(dir.as_ref()) else {
+            return Ok(());
+        };
+        for id in ids {
+            let unit = graph.lookup(*id);
+            let Some(handler) = unit
+                .qualified_names()
+                .get(1)
+                .and_then(|eco| registry.lookup(eco))
+            else {
+                continue;
+            };
+            if !handler.is_manifest_file(path) {
+                continue;
+            }
+
+            let declared = sess
+                .repo
+                .read_to_string(path)
+                .map_err(anyhow::Error::from)
+                .and_then(|content| handler.declared_deps(&content));
+            let declared = match declared {
+                Ok(d) => d,
+                Err(e) => {
+                    warn!("could not read dependencies from `{}`: {e}", path.escaped());
+                    continue;
+                }
+            };
+
+            let deps = externals.entry(*id).or_default();
+            for dep in declared {
+                if !internal_names.contains(dep.name.as_str())
+                    && !deps.iter().any(|d| d.name == dep.name)
+                {
+                    deps.push(dep);
+                }
+            }
+        }
+        Ok(())
+    })?;
+
+    for deps in externals.values_mut() {
+        deps.sort_by(|a, b| a.name.cmp(&b.name));
+    }
+    Ok(externals)
+}
+
+fn render_ascii(
+    sess: &AppSession,
+    idents: &[usize],
+    externals: Option<&HashMap<usize, Vec<DeclaredDep>>>,
+) {
     println!();
     println!("╭─────────────────────────────────────────────────────────╮");
     println!("│              ReleaseUnit Dependency Graph              │");
@@ -70,30 +163,46 @@ fn render_ascii(sess: &AppSession, idents: &[usize]) {
     for ident in idents {
         let unit = sess.graph().lookup(*ident);
         let deps = &unit.internal_deps;
+        let ext = externals
+            .and_then(|e| e.get(ident))
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let n_edges = deps.len() + ext.len();
 
         if deps.is_empty() {
             println!("  ○ {} @ {}", unit.user_facing_name, unit.version);
         } else {
             has_deps = true;
             println!("  ● {} @ {}", unit.user_facing_name, unit.version);
-            for (i, dep) in deps.iter().enumerate() {
-                let dep_proj = sess.graph().lookup(dep.ident);
-                let prefix = if i == deps.len() - 1 {
-                    "└──"
-                } else {
-                    "├──"
-                };
-                println!(
-                    "    {} → {} @ {}",
-                    prefix, dep_proj.user_facing_name, dep_proj.version
-                );
-            }
+        }
+        for (i, dep) in deps.iter().enumerate() {
+            let dep_proj = sess.graph().lookup(dep.ident);
+            let prefix = if i == n_edges - 1 {
+                "└──"
+            } else {
+                "├──"
+            };
+            println!(
+                "    {} → {} @ {}",
+                prefix, dep_proj.user_facing_name, dep_proj.version
+            );
+        }
+        for (i, dep) in ext.iter().enumerate() {
+            let prefix = if deps.len() + i == n_edges - 1 {
+                "└──"
+            } else {
+                "├──"
+            };
+            println!("    {} ⇢ {} {}", prefix, dep.name, dep.requirement);
         }
         println!();
     }
 
     println!("╭─────────────────────────────────────────────────────────╮");
     println!("│  Legend: ○ = no deps  ● = has deps  → = depends on     │");
+    if externals.is_some() {
+        println!("│          ⇢ = external dependency                       │");
+    }
     println!("╰─────────────────────────────────────────────────────────╯");
 
     if !has_deps {
@@ -111,7 +220,11 @@ fn render_ascii(sess: &AppSession, idents: &[usize]) {
     println!();
 }
 
-fn render_dot(sess: &AppSession, idents: &[usize]) {
+fn render_dot(
+    sess: &AppSession,
+    idents: &[usize],
+    externals: Option<&HashMap<usize, Vec<DeclaredDep>>>,
+) {
     println!("digraph dependencies {{");
     println!("    rankdir=TB;");
     println!("    node [shape=box, style=rounded];");
@@ -123,6 +236,23 @@ fn render_dot(sess: &AppSession, idents: &[usize]) {
         println!("    \"{}\" [label=\"{}\"];", unit.user_facing_name, label);
     }
 
+    // One node per external package, whatever requirement each unit
+    // states; the requirement labels the edge instead.
+    let mut external_names: Vec<&str> = externals
+        .into_iter()
+        .flat_map(HashMap::values)
+        .flatten()
+        .map(|d| d.name.as_str())
+        .collect();
+    external_names.sort_unstable();
+    external_names.dedup();
+    for name in external_names {
+        println!(
+            "    \"ext:{}\" [label=\"{}\", shape=ellipse, style=dashed];",
+            name, name
+        );
+    }
+
     println!();
 
     for ident in idents {
@@ -134,18 +264,76 @@ fn render_dot(sess: &AppSession, idents: &[usize]) {
                 unit.user_facing_name, dep_proj.user_facing_name
             );
         }
+        for dep in externals.and_then(|e| e.get(ident)).into_iter().flatten() {
+            println!(
+                "    \"{}\" -> \"ext:{}\" [label=\"{}\", style=dashed];",
+                unit.user_facing_name, dep.name, dep.requirement
+            );
+        }
     }
 
     println!("}}");
 }
 
-fn render_json(sess: &AppSession, idents: &[usize]) -> Result<()> {
+/// A release unit in `belaf graph --format json`; see
+/// `schemas/graph.v1.schema.json`.
+#[derive(Serialize)]
+struct GraphNode<'a> {
+    name: &'a str,
+    version: String,
+    ecosystem: Option<&'a str>,
+    prefix: String,
+    qualified_names: &'a [String],
+}
+
+/// A dependency of a release unit: on another unit (`internal`) or, with
+/// `--external`, on a package from outside the repository (`external`).
+#[derive(Serialize)]
+struct GraphEdge<'a> {
+    from: &'a str,
+    to: &'a str,
+    kind: &'static str,
+    /// The requirement as the manifest writes it.
+    requirement: &'a str,
+}
+
+fn render_json(
+    sess: &AppSession,
+    idents: &[usize],
+    externals: Option<&HashMap<usize, Vec<DeclaredDep>>>,
+) -> Result<()> {
     use serde_json::json;
 
     let mut projects = Vec::new();
+    let mut nodes = Vec::new();
+    let mut edges = Vec::new();
 
     for ident in idents {
         let unit = sess.graph().lookup(*ident);
+        nodes.push(GraphNode {
+            name: &unit.user_facing_name,
+            version: unit.version.to_string(),
+            ecosystem: unit.qualified_names().get(1).map(String::as_str),
+            prefix: unit.prefix().escaped(),
+            qualified_names: unit.qualified_names(),
+        });
+        for dep in &unit.internal_deps {
+            edges.push(GraphEdge {
+                from: &unit.user_facing_name,
+                to: &sess.graph().lookup(dep.ident).user_facing_name,
+                kind: "internal",
+                requirement: &dep.literal,
+            });
+        }
+        for dep in externals.and_then(|e| e.get(ident)).into_iter().flatten() {
+            edges.push(GraphEdge {
+                from: &unit.user_facing_name,
+                to: &dep.name,
+                kind: "external",
+                requirement: &dep.requirement,
+            });
+        }
+
         let deps: Vec<String> = unit
             .internal_deps
             .iter()
@@ -155,12 +343,17 @@ fn render_json(sess: &AppSession, idents: &[usize]) -> Result<()> {
             })
             .collect();
 
-        projects.push(json!({
+        let mut project = json!({
             "name": unit.user_facing_name,
             "version": unit.version.to_string(),
             "prefix": unit.prefix().escaped(),
             "dependencies": deps,
-        }));
+        });
+        if let Some(externals) = externals {
+            project["external_dependencies"] =
+                json!(externals.get(ident).cloned().unwrap_or_default());
+        }
+        projects.push(project);
     }
 
     let toposorted: Vec<String> = sess
@@ -170,6 +363,9 @@ fn render_json(sess: &AppSession, idents: &[usize]) -> Result<()> {
         .collect();
 
     let output = json!({
+        "schema_version": "1",
+        "nodes": nodes,
+        "edges": edges,
         "projects": projects,
         "release_order": toposorted,
     });
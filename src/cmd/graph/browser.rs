@@ -1,6 +1,6 @@
 use anyhow::Result;
 use serde::Serialize;
-use std::io::Write;
+use std::{collections::BTreeMap, io::Write};
 
 use crate::core::{graph::GraphQueryBuilder, session::AppSession};
 
@@ -26,10 +26,12 @@ struct GraphNode {
 struct GraphEdge {
     source: String,
     target: String,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    external: bool,
 }
 
-pub fn open_browser(output_path: Option<&str>) -> Result<i32> {
-    let sess = AppSession::initialize_default()?;
+pub fn open_browser(output_path: Option<&str>, external: bool) -> Result<i32> {
+    let sess = AppSession::initialize_read_only()?;
 
     let q = GraphQueryBuilder::default();
     let idents = sess
@@ -66,6 +68,38 @@ pub fn open_browser(output_path: Option<&str>) -> Result<i32> {
             edges.push(GraphEdge {
                 source: unit.user_facing_name.clone(),
                 target: dep_proj.user_facing_name.clone(),
+                external: false,
+            });
+        }
+    }
+
+    if external {
+        // External packages get one leaf node each, namespaced so they
+        // can't collide with a unit of the same name; the node lists
+        // every requirement stated across the repo.
+        let externals = super::external_deps(&sess, &idents)?;
+        let mut requirements: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        for &ident in &idents {
+            let unit = sess.graph().lookup(ident);
+            for dep in externals.get(&ident).into_iter().flatten() {
+                let reqs = requirements.entry(&dep.name).or_default();
+                if !reqs.contains(&dep.requirement.as_str()) {
+                    reqs.push(&dep.requirement);
+                }
+                edges.push(GraphEdge {
+                    source: unit.user_facing_name.clone(),
+                    target: format!("ext:{}", dep.name),
+                    external: true,
+                });
+            }
+        }
+        for (name, reqs) in requirements {
+            nodes.push(GraphNode {
+                id: format!("ext:{name}"),
+                label: name.to_string(),
+                node_type: "external".to_string(),
+                version: reqs.join(", "),
+                deps_count: 0,
             });
         }
     }
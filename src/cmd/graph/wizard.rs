@@ -15,6 +15,7 @@ use std::collections::HashMap;
 use std::io::stdout;
 
 use crate::core::{graph::GraphQueryBuilder, session::AppSession};
+use crate::utils::theme;
 
 use super::browser;
 
@@ -138,7 +139,7 @@ impl App {
 }
 
 pub fn run() -> Result<i32> {
-    let sess = AppSession::initialize_default()?;
+    let sess = AppSession::initialize_read_only()?;
 
     let q = GraphQueryBuilder::default();
     let idents = sess
@@ -170,7 +171,10 @@ where
     B::Error: Send + Sync + 'static,
 {
     loop {
-        terminal.draw(|f| ui(f, app))?;
+        terminal.draw(|f| {
+            ui(f, app);
+            theme::apply_color_mode(f);
+        })?;
 
         if let Event::Key(key) = event::read()? {
             if key.kind != KeyEventKind::Press {
@@ -199,7 +203,7 @@ where
                 KeyCode::Char('g') => {
                     disable_raw_mode()?;
                     stdout().execute(LeaveAlternateScreen)?;
-                    browser::open_browser(None)?;
+                    browser::open_browser(None, false)?;
                     enable_raw_mode()?;
                     stdout().execute(EnterAlternateScreen)?;
                     terminal.clear()?;
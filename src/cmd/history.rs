@@ -0,0 +1,149 @@
+//! `belaf history` — list past releases and the commits they shipped.
+//!
+//! Reads the manifests in `belaf/releases/`, newest first. Each release
+//! records the commit range it was prepared from (`previous_tag..sha`,
+//! or just `sha` for a first release) and the SHAs of the unit's commits
+//! in it, so "which commits shipped in api 1.4.2" has an answer long
+//! after the release PR merged. Manifests written before the range was
+//! recorded list the release without it.
+//!
+//! Like `belaf audit show`, this needs only the repository, not a
+//! loadable `belaf/config.toml`.
+
+use anyhow::{Context, Result};
+use owo_colors::OwoColorize;
+use serde::Serialize;
+
+use crate::cli::HistoryOutputFormat;
+use crate::core::{
+    git::repository::Repository,
+    manifest::{ReleaseEntry, MANIFEST_DIR},
+    releases,
+};
+
+#[derive(Serialize)]
+struct HistoryEntry {
+    manifest_id: String,
+    created_at: String,
+    name: String,
+    previous_version: String,
+    new_version: String,
+    bump_type: String,
+    tag_name: String,
+    commit_range: Option<String>,
+    commits: Vec<ShippedCommit>,
+}
+
+#[derive(Serialize)]
+struct ShippedCommit {
+    sha: String,
+    /// `None` when the commit isn't in the local repository.
+    summary: Option<String>,
+}
+
+pub fn run(
+    project: Option<String>,
+    show_commits: bool,
+    limit: Option<usize>,
+    format: Option<HistoryOutputFormat>,
+) -> Result<i32> {
+    let repo =
+        Repository::open_from_env_read_only().context("belaf is not in a Git working directory")?;
+    let json = format == Some(HistoryOutputFormat::Json);
+
+    let mut entries = Vec::new();
+    for path in releases::list(&repo)?.iter().rev() {
+        let manifest = releases::read_in(&repo, path)?.manifest;
+        for release in &manifest.releases {
+            if project.as_ref().is_some_and(|p| *p != release.name) {
+                continue;
+            }
+            entries.push(HistoryEntry {
+                manifest_id: manifest.manifest_id.clone(),
+                created_at: manifest.created_at.clone(),
+                name: release.name.clone(),
+                previous_version: release.previous_version.clone(),
+                new_version: release.new_version.clone(),
+                bump_type: release.bump_type.as_str().to_string(),
+                tag_name: release.tag_name.clone(),
+                commit_range: release.commit_range.clone(),
+                commits: shipped_commits(&repo, release, json || show_commits),
+            });
+        }
+    }
+    if let Some(n) = limit {
+        entries.truncate(n);
+    }
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+        return Ok(0);
+    }
+
+    if entries.is_empty() {
+        match &project {
+            Some(name) => println!(
+                "{} No releases of {name} in {MANIFEST_DIR}/.",
+                "ℹ".cyan().bold()
+            ),
+            None => println!("{} No releases in {MANIFEST_DIR}/.", "ℹ".cyan().bold()),
+        }
+        return Ok(0);
+    }
+    for entry in &entries {
+        let range = match &entry.commit_range {
+            Some(range) => short_range(range).yellow().to_string(),
+            None => "range not recorded".dimmed().to_string(),
+        };
+        let date: String = entry.created_at.chars().take(10).collect();
+        println!(
+            "{}  {} {} ({})  {range}",
+            date.dimmed(),
+            entry.name.bold(),
+            entry.new_version,
+            entry.bump_type
+        );
+        if !show_commits {
+            continue;
+        }
+        if entry.commits.is_empty() {
+            println!("  {}", "no commits recorded".dimmed());
+        }
+        for commit in &entry.commits {
+            println!(
+                "  {}  {}",
+                short_sha(&commit.sha).yellow(),
+                commit.summary.as_deref().unwrap_or("(not in this clone)")
+            );
+        }
+    }
+    Ok(0)
+}
+
+/// `release`'s commits, with their summaries looked up when `describe`.
+fn shipped_commits(
+    repo: &Repository,
+    release: &ReleaseEntry,
+    describe: bool,
+) -> Vec<ShippedCommit> {
+    release
+        .commits
+        .iter()
+        .map(|sha| ShippedCommit {
+            sha: sha.clone(),
+            summary: describe.then(|| repo.commit_summary_of(sha)).flatten(),
+        })
+        .collect()
+}
+
+fn short_sha(sha: &str) -> &str {
+    &sha[..sha.len().min(8)]
+}
+
+/// `range` with its release commit abbreviated, e.g. `v1.4.1..3f2a9c1e`.
+fn short_range(range: &str) -> String {
+    match range.split_once("..") {
+        Some((from, to)) => format!("{from}..{}", short_sha(to)),
+        None => short_sha(range).to_string(),
+    }
+}
@@ -10,6 +10,7 @@ use tracing::{error, info, warn};
 
 use crate::atry;
 use crate::core::{
+    audit::{self, AuditEntry},
     errors::{Error, Result},
     resolved_release_unit::DepRequirement,
     session::AppBuilder,
@@ -380,6 +381,11 @@ impl BootstrapCommand {
             ["failed to create baseline tag"]
             (note "ensure your Git config has both `user.email` and `user.name` set")
         );
+        let entry = AuditEntry::new(&repo, "init").with_tags(vec!["belaf-baseline".to_owned()]);
+        atry!(
+            audit::append(&repo, &entry);
+            ["failed to record the init in the audit log"]
+        );
 
         info!("modifications complete!");
         out!();
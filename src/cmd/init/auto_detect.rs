@@ -69,6 +69,7 @@ pub struct DetectionCounters {
     pub sdk_cascade_member: usize,
     pub single_project: usize,
     pub nested_monorepo: usize,
+    pub submodule: usize,
 }
 
 impl DetectionCounters {
@@ -86,7 +87,7 @@ impl DetectionCounters {
     }
 
     pub fn total_advisory_hints(&self) -> usize {
-        self.nested_monorepo
+        self.nested_monorepo + self.submodule
     }
 }
 
@@ -291,6 +292,15 @@ fn emit_hint_comment(
                 "\n# Nested submodule at {path} — {note}.\n# Consider running `belaf init` inside the submodule and excluding\n# its path from this repo's detection rather than driving both from one config.\n",
             ));
         }
+        HintKind::Submodule => {
+            counters.submodule += 1;
+            let path = m.path.escaped();
+            let name = path.rsplit('/').next().unwrap_or(&path);
+            snippet.push_str(&format!(
+                "\n# Submodule at {path} — `belaf prepare` can move it to its newest\n# `v{{version}}` tag on every release:\n# [submodule.{name}]\n# path = {}\n",
+                toml_quote(&path),
+            ));
+        }
     }
 }
 
@@ -33,11 +33,13 @@ use ratatui::{backend::CrosstermBackend, Terminal};
 use crate::{
     atry,
     core::{
+        audit::{self, AuditEntry},
         git::repository::{PathMatcher, RepoPathBuf, Repository},
         release_unit::detector,
         resolved_release_unit::DepRequirement,
         session::{AppBuilder, AppSession},
     },
+    utils::theme,
 };
 
 use super::auto_detect;
@@ -156,6 +158,7 @@ fn run_wizard_loop(
             terminal.draw(|frame| {
                 let area = frame.area();
                 top.render(frame, area, state);
+                theme::apply_color_mode(frame);
             })?;
         }
 
@@ -353,6 +356,8 @@ fn execute_bootstrap(state: &WizardState, repo: &Repository) -> Result<String> {
     sess.rewrite_belaf_requirements()?;
 
     repo.create_baseline_tag()?;
+    let entry = AuditEntry::new(repo, "init").with_tags(vec!["belaf-baseline".to_owned()]);
+    audit::append(repo, &entry)?;
 
     let action = if state.config_exists {
         "reconfigured"
@@ -12,6 +12,8 @@ use ratatui::{
     Frame,
 };
 
+use crate::core::ui::text;
+
 use super::{
     chrome::{self, palette, step_index, STEP_TOTAL},
     state::{DetectedUnit, WizardState},
@@ -153,7 +155,7 @@ fn render(frame: &mut Frame, area: Rect, state: &WizardState, list_state: &mut L
     let pill_width = stats.keys().map(|k| k.len()).max().unwrap_or(4).max(4);
     let name_width = selected
         .iter()
-        .map(|u| u.name.chars().count())
+        .map(|u| text::display_width(&u.name))
         .max()
         .unwrap_or(20)
         .min(40);
@@ -233,14 +235,7 @@ fn project_row(u: &DetectedUnit, name_width: usize, pill_width: usize) -> ListIt
     let eco = u.ecosystem.as_deref().unwrap_or("other");
     let eco_color = chrome::ecosystem_color(eco);
 
-    let display_name = if u.name.chars().count() > name_width {
-        let mut s: String = u.name.chars().take(name_width.saturating_sub(1)).collect();
-        s.push('…');
-        s
-    } else {
-        let pad = name_width - u.name.chars().count();
-        format!("{}{}", u.name, " ".repeat(pad))
-    };
+    let display_name = text::fit(&u.name, name_width);
 
     let pill = format!("{:<width$}", eco, width = pill_width);
 
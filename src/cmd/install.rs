@@ -1,14 +1,21 @@
 use anyhow::{Context, Result};
 use indicatif::{ProgressBar, ProgressStyle};
 use owo_colors::OwoColorize;
+use std::io::{IsTerminal, Read};
 use std::process::Command;
 use std::time::{Duration, Instant};
 use tokio::time::sleep;
 use tracing::warn;
 
+use crate::core::api::client::RateLimitInfo;
 use crate::core::api::{ApiClient, ApiError, DeviceCodeResponse, StoredToken};
-use crate::core::auth::token::{delete_token, load_token, save_token};
-use crate::core::git::url::parse_github_url;
+use crate::core::auth::pat::{self, Capabilities, StoredPat};
+use crate::core::auth::permissions::{preflight, AccessError, Permission, PREPARE_PERMISSIONS};
+use crate::core::auth::profile::{self, ProfileRegistry, ProfileSource, DEFAULT_PROFILE};
+use crate::core::auth::token::{
+    delete_pat, delete_token, load_pat, load_profile_token, load_token, save_pat, save_token,
+};
+use crate::core::github::{forge, rest::RestClient};
 
 const MIN_POLL_INTERVAL_SECS: u64 = 5;
 const INSTALLATION_TIMEOUT_SECS: u64 = 300;
@@ -17,51 +24,9 @@ const MAX_TRANSIENT_ERRORS: u32 = 5;
 const MAX_POLL_RETRIES: u32 = 180;
 
 pub async fn run() -> Result<i32> {
-    let client = ApiClient::new();
+    let client = ApiClient::try_new()?;
 
-    let needs_auth = needs_authentication(&client).await;
-
-    if needs_auth {
-        println!("{} Authenticating with belaf...\n", "🔐".bold());
-
-        let device_codes = client.request_device_code().await?;
-
-        println!(
-            "Please visit: {}",
-            device_codes.verification_uri.cyan().underline()
-        );
-        println!("Enter code:   {}\n", device_codes.user_code.yellow().bold());
-
-        if open::that(&device_codes.verification_uri_complete).is_ok() {
-            println!("{} Opening browser...", "🌐".bold());
-        }
-
-        let spinner = create_spinner("Waiting for authorization...");
-
-        let token_result = poll_for_token(&client, &device_codes).await?;
-        spinner.finish_and_clear();
-
-        let stored_token = StoredToken::new(token_result.access_token, token_result.expires_in);
-        save_token(&stored_token)?;
-
-        let user = client.get_user_info(&stored_token).await?;
-        println!(
-            "\n{} Authenticated as: {} ({})",
-            "✓".green(),
-            user.display_name().cyan(),
-            user.email.as_deref().unwrap_or("no email")
-        );
-    } else {
-        let token =
-            load_token()?.ok_or_else(|| anyhow::anyhow!("Token must exist after auth check"))?;
-        let user = client.get_user_info(&token).await?;
-        println!(
-            "{} Already authenticated as: {} ({})",
-            "✓".green(),
-            user.display_name().cyan(),
-            user.email.as_deref().unwrap_or("no email")
-        );
-    }
+    authenticate(&client).await?;
 
     let (owner, repo_name) = detect_repository()?;
     let full_repo = format!("{}/{}", owner, repo_name);
@@ -158,14 +123,243 @@ pub async fn run() -> Result<i32> {
     }
 }
 
+/// Device-flow login for the active profile, unless it already holds a
+/// working token.
+async fn authenticate(client: &ApiClient) -> Result<()> {
+    if needs_authentication(client).await {
+        println!("{} Authenticating with belaf...\n", "🔐".bold());
+
+        let device_codes = client.request_device_code().await?;
+
+        println!(
+            "Please visit: {}",
+            device_codes.verification_uri.cyan().underline()
+        );
+        println!("Enter code:   {}\n", device_codes.user_code.yellow().bold());
+
+        if open::that(&device_codes.verification_uri_complete).is_ok() {
+            println!("{} Opening browser...", "🌐".bold());
+        }
+
+        let spinner = create_spinner("Waiting for authorization...");
+
+        let token_result = poll_for_token(client, &device_codes).await?;
+        spinner.finish_and_clear();
+
+        let stored_token = StoredToken::new(token_result.access_token, token_result.expires_in);
+        save_token(&stored_token)?;
+
+        let user = client.get_user_info(&stored_token).await?;
+        println!(
+            "\n{} Authenticated as: {} ({}){}",
+            "✓".green(),
+            user.display_name().cyan(),
+            user.email.as_deref().unwrap_or("no email"),
+            profile_suffix()
+        );
+    } else {
+        let token =
+            load_token()?.ok_or_else(|| anyhow::anyhow!("Token must exist after auth check"))?;
+        let user = client.get_user_info(&token).await?;
+        println!(
+            "{} Already authenticated as: {} ({}){}",
+            "✓".green(),
+            user.display_name().cyan(),
+            user.email.as_deref().unwrap_or("no email"),
+            profile_suffix()
+        );
+    }
+    Ok(())
+}
+
+/// ` [profile: work]` when a named profile is active, else nothing.
+fn profile_suffix() -> String {
+    let name = profile::active_name();
+    if name == DEFAULT_PROFILE {
+        String::new()
+    } else {
+        format!(" {}", format!("[profile: {name}]").dimmed())
+    }
+}
+
+/// `belaf auth login`: authenticate the active profile and remember it,
+/// with any `--match` patterns, in the user's profile list.
+pub async fn login(remotes: Vec<String>, with_token: bool) -> Result<i32> {
+    if with_token {
+        login_with_token().await?;
+    } else {
+        let client = ApiClient::try_new()?;
+        authenticate(&client).await?;
+    }
+
+    let name = profile::active_name();
+    if name != DEFAULT_PROFILE || !remotes.is_empty() {
+        let mut registry = ProfileRegistry::load()?;
+        registry.add(&name, &remotes);
+        registry.save()?;
+        for remote in &remotes {
+            println!(
+                "  Repositories matching {} use profile {}",
+                remote.cyan(),
+                name.cyan()
+            );
+        }
+    }
+    Ok(0)
+}
+
+/// `belaf auth login --with-token`: take a personal access token, check
+/// what it may do on this repository and store it with the answers.
+async fn login_with_token() -> Result<()> {
+    let (owner, repo_name) = detect_repository().context(
+        "run `belaf auth login --with-token` in a checkout of the repository the token is for",
+    )?;
+    let full_repo = format!("{owner}/{repo_name}");
+
+    let token = if std::io::stdin().is_terminal() {
+        println!(
+            "{}\n",
+            pat::guidance(forge::current().web_url(), &owner, &repo_name)
+        );
+        dialoguer::Password::new()
+            .with_prompt("Paste the token")
+            .interact()?
+    } else {
+        let mut token = String::new();
+        std::io::stdin()
+            .read_to_string(&mut token)
+            .context("failed to read the token from stdin")?;
+        token
+    };
+    let token = token.trim().to_string();
+    if token.is_empty() {
+        anyhow::bail!("no token given");
+    }
+
+    println!(
+        "{} Checking the token against {}...",
+        "🔐".bold(),
+        full_repo.cyan()
+    );
+    let client = RestClient::new(&token)?;
+    let found = pat::validate(&client, &owner, &repo_name).await?;
+    print_capabilities(&found.capabilities);
+
+    let mut stored = load_pat()
+        .ok()
+        .flatten()
+        .filter(|previous| previous.token == token)
+        .unwrap_or_else(|| StoredPat {
+            token,
+            expires_at: None,
+            repos: Default::default(),
+        });
+    stored.expires_at = found.expires_at;
+    stored.repos.insert(full_repo.clone(), found.capabilities);
+    save_pat(&stored)?;
+
+    println!(
+        "\n{} Token stored for {}{}",
+        "✓".green(),
+        full_repo.cyan(),
+        profile_suffix()
+    );
+    if let Some(expires_at) = stored.expires_at {
+        println!("  Expires: {}", expires_at.to_string().dimmed());
+    }
+    if let Err(e) = found.capabilities.require(&full_repo, PREPARE_PERMISSIONS) {
+        if let Some(access) = e.downcast_ref::<AccessError>() {
+            println!("{} `belaf prepare` will stop: {access}", "⚠".yellow());
+            println!("  {}", access.hint());
+        }
+    }
+    Ok(())
+}
+
+/// One line per thing `prepare` does with a personal access token.
+fn print_capabilities(capabilities: &Capabilities) {
+    let mark = |ok: bool| {
+        if ok {
+            "✓".green().to_string()
+        } else {
+            "✗".red().to_string()
+        }
+    };
+    println!("  {} read the repository", mark(capabilities.read));
+    for permission in PREPARE_PERMISSIONS {
+        println!(
+            "  {} {} {}",
+            mark(capabilities.allows(*permission)),
+            permission.operation(),
+            format!("({})", permission.token_label()).dimmed()
+        );
+    }
+}
+
+/// `belaf auth profiles`: every known profile, its match patterns and
+/// whether a token is stored, marking the one active here.
+pub fn profiles() -> Result<i32> {
+    let registry = ProfileRegistry::load()?;
+    let (active, source) = profile::active();
+
+    let mut names: Vec<&str> = registry.profiles.keys().map(String::as_str).collect();
+    if !registry.profiles.contains_key(DEFAULT_PROFILE) {
+        names.insert(0, DEFAULT_PROFILE);
+    }
+    if !names.contains(&active.as_str()) {
+        names.push(&active);
+    }
+
+    for name in names {
+        let marker = if name == active {
+            "*".green().to_string()
+        } else {
+            " ".to_string()
+        };
+        let state = match load_profile_token(name) {
+            Ok(Some(token)) if !token.is_expired() => "logged in".green().to_string(),
+            Ok(Some(_)) => "token expired".yellow().to_string(),
+            Ok(None) => "not logged in".dimmed().to_string(),
+            Err(e) => format!("{}", e.to_string().red()),
+        };
+        println!("{marker} {} ({state})", name.cyan());
+        if let Some(entry) = registry.profiles.get(name) {
+            for remote in &entry.remotes {
+                println!("    match {}", remote);
+            }
+        }
+    }
+    println!(
+        "\nActive here: {} ({})",
+        active.cyan(),
+        source.to_string().dimmed()
+    );
+    Ok(0)
+}
+
 pub async fn logout() -> Result<i32> {
     delete_token()?;
-    println!("{} Logged out successfully.", "✓".green());
+    delete_pat()?;
+    let name = profile::active_name();
+    if name != DEFAULT_PROFILE {
+        let mut registry = ProfileRegistry::load()?;
+        if registry.remove(&name) {
+            registry.save()?;
+        }
+    }
+    println!(
+        "{} Logged out successfully.{}",
+        "✓".green(),
+        profile_suffix()
+    );
     Ok(0)
 }
 
 pub async fn status() -> Result<i32> {
-    let client = ApiClient::new();
+    if let Some(code) = token_status()? {
+        return Ok(code);
+    }
+    let client = ApiClient::try_new()?;
 
     match load_token()? {
         Some(token) if !token.is_expired() => match client.get_user_info(&token).await {
@@ -176,10 +370,21 @@ pub async fn status() -> Result<i32> {
                     user.display_name().cyan(),
                     user.email.as_deref().unwrap_or("no email")
                 );
+                let (name, source) = profile::active();
+                if source != ProfileSource::Default {
+                    println!(
+                        "  Profile: {} ({})",
+                        name.cyan(),
+                        source.to_string().dimmed()
+                    );
+                }
                 if let Some(expires_at) = token.expires_at {
                     println!("  Expires: {}", expires_at.to_string().dimmed());
                 }
-                Ok(0)
+                if let Some(rate) = client.rate_limit() {
+                    print_rate_limit(&rate);
+                }
+                print_repository_access(&client, &token).await
             }
             Err(ApiError::Unauthorized) => {
                 println!("{} Token expired or invalid", "✗".red());
@@ -201,8 +406,93 @@ pub async fn status() -> Result<i32> {
     }
 }
 
+/// Report the personal access token `prepare` uses in this checkout, if
+/// there is one; `None` leaves the report to the App login.
+fn token_status() -> Result<Option<i32>> {
+    let Ok((owner, repo_name)) = detect_repository() else {
+        return Ok(None);
+    };
+    let full_repo = format!("{owner}/{repo_name}");
+    let Some(stored) = load_pat()? else {
+        return Ok(None);
+    };
+    let Some(capabilities) = stored.repos.get(&full_repo) else {
+        return Ok(None);
+    };
+
+    if stored.is_expired() {
+        println!("{} Personal access token expired", "✗".red());
+        println!("  Run 'belaf auth login --with-token' with a new token.");
+        return Ok(Some(1));
+    }
+    println!("{} Authenticated with a personal access token", "✓".green());
+    if let Some(expires_at) = stored.expires_at {
+        println!("  Expires: {}", expires_at.to_string().dimmed());
+    }
+    println!("  Repository: {}", full_repo.cyan());
+    print_capabilities(capabilities);
+    let missing = PREPARE_PERMISSIONS
+        .iter()
+        .any(|p: &Permission| !capabilities.allows(*p));
+    Ok(Some(i32::from(missing)))
+}
+
+/// When run inside a GitHub checkout, report whether the belaf App can
+/// act on it and what `prepare` will need. Returns the exit code for
+/// `auth status`: a missing installation is a failure, not being in a
+/// repository is not.
+async fn print_repository_access(client: &ApiClient, token: &StoredToken) -> Result<i32> {
+    let Ok((owner, repo_name)) = detect_repository() else {
+        return Ok(0);
+    };
+    let full_repo = format!("{}/{}", owner, repo_name);
+
+    match preflight(client, token, &owner, &repo_name).await {
+        Ok(()) => {
+            println!(
+                "  Repository: {} {}",
+                full_repo.cyan(),
+                "(App installed)".green()
+            );
+            let needed: Vec<&str> = PREPARE_PERMISSIONS.iter().map(|p| p.label()).collect();
+            println!("  prepare needs: {}", needed.join(", ").dimmed());
+            Ok(0)
+        }
+        Err(e) => match e.downcast_ref::<AccessError>() {
+            Some(access) => {
+                println!("{} {}", "✗".red(), access);
+                println!("  {}", access.hint());
+                Ok(1)
+            }
+            None => {
+                warn!("could not check App installation for {full_repo}: {e}");
+                Ok(0)
+            }
+        },
+    }
+}
+
+/// One line summarising the GitHub rate-limit headers the API forwarded.
+fn print_rate_limit(rate: &RateLimitInfo) {
+    let Some(remaining) = rate.remaining else {
+        return;
+    };
+    let limit = rate.limit.map(|l| format!("/{l}")).unwrap_or_default();
+    let reset = rate
+        .reset_at
+        .and_then(|r| time::OffsetDateTime::from_unix_timestamp(r as i64).ok())
+        .map(|r| format!(" (resets {})", r).dimmed().to_string())
+        .unwrap_or_default();
+    let line = format!("{remaining}{limit} remaining");
+    if remaining == 0 {
+        println!("  API rate limit: {}{}", line.red(), reset);
+    } else {
+        println!("  API rate limit: {}{}", line, reset);
+    }
+}
+
 pub async fn whoami() -> Result<i32> {
-    let client = ApiClient::new();
+    let client = ApiClient::try_new()?;
 
     match load_token()? {
         Some(token) if !token.is_expired() => match client.get_user_info(&token).await {
@@ -314,7 +604,7 @@ fn detect_repository() -> Result<(String, String)> {
     }
 
     let url = String::from_utf8_lossy(&output.stdout).trim().to_string();
-    parse_github_url(&url)
+    forge::current().parse_remote(&url)
 }
 
 fn create_spinner(message: &str) -> ProgressBar {
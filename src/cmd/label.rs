@@ -0,0 +1,50 @@
+//! `belaf label` — label a feature PR by the units it changes and the
+//! bump it calls for.
+//!
+//! Looks at the same commits as `belaf comment` (see [`pr_impact`]) and
+//! puts `area:<unit>`, `semver:<bump>` and `breaking` on the PR; see
+//! [`pr::generate_pr_labels`]. Labels the repository lacks are created.
+//! Labels of that shape that no longer apply, say `semver:patch` after a
+//! `feat:` commit was pushed, are taken off; other labels are left alone.
+
+use anyhow::{Context, Result};
+use owo_colors::OwoColorize;
+
+use crate::core::{
+    github::{pr, rest},
+    pr_impact,
+    session::AppSession,
+};
+
+pub async fn run(number: u64, base: String, dry_run: bool) -> Result<i32> {
+    let sess = AppSession::initialize_default()?;
+    let units = pr_impact::analyze(&sess, &base, false)?;
+    let labels = pr::generate_pr_labels(&units);
+
+    if dry_run {
+        for label in &labels {
+            println!("{}", label.name);
+        }
+        return Ok(0);
+    }
+
+    sess.repo.check_upstream("label a pull request")?;
+    let (client, owner, repo) = rest::for_upstream(&sess.repo)?;
+    let (added, removed) = client
+        .sync_labels(&owner, &repo, number, &labels, pr::is_generated_label)
+        .await
+        .with_context(|| format!("failed to label pull request #{number}"))?;
+
+    if added.is_empty() && removed.is_empty() {
+        println!("{} Labels of #{number} are up to date.", "✓".green().bold());
+        return Ok(0);
+    }
+    println!("{} Labelled #{number}.", "✓".green().bold());
+    for name in &added {
+        println!("  {} {}", "+".green(), name);
+    }
+    for name in &removed {
+        println!("  {} {}", "-".red(), name);
+    }
+    Ok(0)
+}
@@ -0,0 +1,151 @@
+//! `belaf lint-commits` — check the commits pending release against the
+//! units' commit conventions.
+//!
+//! Each unit's commits since its last release tag are checked against its
+//! `[release_unit.<name>] scopes`; see [`crate::core::commit_scopes`].
+//! Under `[commit_attribution] strategy = "trailer_first"`, their
+//! `Belaf-Projects` trailers must name known units; see
+//! [`crate::core::project_trailers`].
+//! Problems are reported, not fixed: history is already written. With
+//! `--strict` they fail the run, for use as a CI gate on pull requests.
+
+use std::collections::HashSet;
+
+use anyhow::Result;
+use owo_colors::OwoColorize;
+use serde::Serialize;
+
+use crate::cli::LintCommitsOutputFormat;
+use crate::core::{commit_scopes, exit_code::ExitCode, project_trailers, session::AppSession};
+
+#[derive(Serialize)]
+struct LintPayload {
+    checked_commits: usize,
+    problems: Vec<LintProblem>,
+    trailer_problems: Vec<TrailerProblem>,
+}
+
+#[derive(Serialize)]
+struct LintProblem {
+    unit: String,
+    commit: String,
+    summary: String,
+    scope: String,
+    allowed_scopes: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct TrailerProblem {
+    commit: String,
+    summary: String,
+    unknown_projects: Vec<String>,
+}
+
+pub fn run(strict: bool, format: Option<LintCommitsOutputFormat>) -> Result<i32> {
+    let sess = AppSession::initialize_default()?;
+    let histories = sess.analyze_histories()?;
+
+    let mut payload = LintPayload {
+        checked_commits: 0,
+        problems: Vec::new(),
+        trailer_problems: Vec::new(),
+    };
+    let unit_names: Vec<String> = sess
+        .graph()
+        .projects()
+        .map(|u| u.user_facing_name.clone())
+        .collect();
+    let mut seen = HashSet::new();
+    for unit in sess.graph().projects_slice() {
+        let allowed = sess.allowed_scopes(unit.ident());
+        for cid in histories.lookup(unit.ident()).commits() {
+            payload.checked_commits += 1;
+            let summary = sess.repo.get_commit_summary(*cid)?;
+            if let Some(scope) = commit_scopes::unknown_scope(&summary, allowed) {
+                payload.problems.push(LintProblem {
+                    unit: unit.user_facing_name.clone(),
+                    commit: cid.to_string(),
+                    summary: summary.clone(),
+                    scope,
+                    allowed_scopes: allowed.to_vec(),
+                });
+            }
+            let sha = cid.to_string();
+            if !sess.repo.project_trailers() || !seen.insert(sha.clone()) {
+                continue;
+            }
+            let message = sess.repo.commit_message_of(&sha).unwrap_or_default();
+            let unknown = project_trailers::unknown_projects(&message, &unit_names);
+            if !unknown.is_empty() {
+                payload.trailer_problems.push(TrailerProblem {
+                    commit: sha,
+                    summary,
+                    unknown_projects: unknown,
+                });
+            }
+        }
+    }
+
+    let n_problems = payload.problems.len() + payload.trailer_problems.len();
+    let code = if strict && n_problems > 0 {
+        ExitCode::Precondition.into()
+    } else {
+        0
+    };
+
+    if format == Some(LintCommitsOutputFormat::Json) {
+        println!("{}", serde_json::to_string_pretty(&payload)?);
+        return Ok(code);
+    }
+
+    if n_problems == 0 {
+        println!(
+            "{} {} pending commit(s) follow the units' commit conventions.",
+            "✓".green().bold(),
+            payload.checked_commits
+        );
+        return Ok(code);
+    }
+    for problem in &payload.problems {
+        println!(
+            "{} {}: {:.8} {}",
+            "⚠".yellow().bold(),
+            problem.unit,
+            problem.commit,
+            problem.summary
+        );
+        println!(
+            "    scope `{}` is not one of: {}",
+            problem.scope,
+            problem.allowed_scopes.join(", ")
+        );
+    }
+    for problem in &payload.trailer_problems {
+        println!(
+            "{} {:.8} {}",
+            "⚠".yellow().bold(),
+            problem.commit,
+            problem.summary
+        );
+        println!(
+            "    {} names no release unit: {}",
+            project_trailers::TRAILER,
+            problem.unknown_projects.join(", ")
+        );
+    }
+    if !payload.problems.is_empty() {
+        println!(
+            "  {} of {} pending commit(s) use a scope outside their unit's `scopes`.",
+            payload.problems.len(),
+            payload.checked_commits
+        );
+    }
+    if !payload.trailer_problems.is_empty() {
+        println!(
+            "  {} pending commit(s) name unknown units in a {} trailer.",
+            payload.trailer_problems.len(),
+            project_trailers::TRAILER
+        );
+    }
+    Ok(code)
+}
@@ -0,0 +1,82 @@
+//! `belaf migrate` — rewrite files an older belaf wrote in the current
+//! format: `belaf/config.toml` (see [`crate::core::config::migrations`])
+//! and the release manifests in `belaf/releases/` (see
+//! [`crate::core::releases`]). Like `belaf audit`, this needs only the
+//! repository, not a loadable `belaf/config.toml` — fixing one is the
+//! point.
+
+use anyhow::{Context, Result};
+use owo_colors::OwoColorize;
+
+use crate::core::{
+    config::migrations,
+    exit_code::ExitCode,
+    git::repository::Repository,
+    manifest::{MANIFEST_DIR, SCHEMA_VERSION},
+    releases,
+};
+
+pub fn run(check: bool) -> Result<i32> {
+    let repo = Repository::open_from_env().context("belaf is not in a Git working directory")?;
+    let mut outdated = 0;
+
+    let cfg_path = repo.resolve_config_dir().join("config.toml");
+    if cfg_path.exists() {
+        let text = std::fs::read_to_string(&cfg_path)
+            .with_context(|| format!("failed to read `{}`", cfg_path.display()))?;
+        let migrated = migrations::migrate(&text)
+            .with_context(|| format!("failed to parse `{}`", cfg_path.display()))?;
+        if let Some(migrated) = migrated {
+            outdated += 1;
+            if check {
+                println!("{} belaf/config.toml uses retired keys", "✗".red().bold());
+            } else {
+                std::fs::write(&cfg_path, &migrated.text)
+                    .with_context(|| format!("failed to write `{}`", cfg_path.display()))?;
+                println!("{} Migrated belaf/config.toml", "✓".green().bold());
+            }
+            for change in &migrated.changes {
+                println!("    {change}");
+            }
+        }
+    }
+
+    for path in releases::list(&repo)? {
+        let stored = releases::read_in(&repo, &path)?;
+        let Some(from) = stored.migrated_from else {
+            continue;
+        };
+        outdated += 1;
+        let path = stored.path;
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        let display = format!("{MANIFEST_DIR}/{name}");
+        if check {
+            println!(
+                "{} {display} is schema version {from}, needs {SCHEMA_VERSION}",
+                "✗".red().bold()
+            );
+            continue;
+        }
+        stored
+            .manifest
+            .save_to_file(&path)
+            .with_context(|| format!("failed to write `{}`", path.display()))?;
+        println!(
+            "{} Migrated {display} from schema version {from} to {SCHEMA_VERSION}",
+            "✓".green().bold()
+        );
+    }
+
+    if outdated == 0 {
+        println!(
+            "{} belaf/config.toml and the release manifests are up to date.",
+            "✓".green().bold()
+        );
+    } else if check {
+        println!("  Run `belaf migrate` to upgrade them.");
+        return Ok(ExitCode::Precondition.into());
+    } else {
+        println!("  Review and commit the changes.");
+    }
+    Ok(0)
+}
@@ -0,0 +1,127 @@
+//! `belaf open` — jump to a release's pages and files.
+//!
+//! Targets:
+//!
+//! - `pr` — the newest open release PR, or the newest one releasing
+//!   `<project>`. Needs a login, like `status` does to list them.
+//! - `repo` — the upstream repository's web page.
+//! - `changelog` — the project's changelog file in the working tree.
+//! - `release` — the forge's release page for the project's latest tag.
+//!
+//! Web links are built by [`forge::current`], so they point at the
+//! GitHub Enterprise Server install when `[forge]` names one. With
+//! `--print` the URL or path is printed instead of opened.
+
+use anyhow::{bail, Context, Result};
+use owo_colors::OwoColorize;
+
+use crate::cli::OpenTarget;
+use crate::core::{
+    changelog::ChangelogConfig,
+    github::{forge, pending::find_pending_releases},
+    resolved_release_unit::ReleaseUnitId,
+    session::AppSession,
+    workflow::changelog_path,
+};
+
+pub fn run(target: OpenTarget, project: Option<String>, print: bool) -> Result<i32> {
+    let sess = AppSession::initialize_default()?;
+
+    let location = match target {
+        OpenTarget::Pr => pr_url(&sess, project.as_deref())?,
+        OpenTarget::Repo => repo_url(&sess)?,
+        OpenTarget::Changelog => {
+            let ident = resolve_project(&sess, project.as_deref())?;
+            let unit = sess.graph().lookup(ident);
+            let config = ChangelogConfig::from_user_config(&sess.changelog_config);
+            let path = changelog_path(&config, &unit.prefix().escaped());
+            let path = sess.repo.resolve_workdir(path.as_ref());
+            if !path.is_file() {
+                bail!(
+                    "{} has no changelog yet (`{}`)",
+                    unit.user_facing_name,
+                    path.display()
+                );
+            }
+            path.display().to_string()
+        }
+        OpenTarget::Release => {
+            let ident = resolve_project(&sess, project.as_deref())?;
+            let histories = sess
+                .analyze_histories()
+                .context("failed to analyze project histories")?;
+            let Some(tag) = histories.lookup(ident).release_tag() else {
+                bail!(
+                    "{} has no release tag yet",
+                    sess.graph().lookup(ident).user_facing_name
+                );
+            };
+            format!("{}/releases/tag/{tag}", repo_url(&sess)?)
+        }
+    };
+
+    if print {
+        println!("{location}");
+        return Ok(0);
+    }
+    println!("{} Opening {location}", "→".cyan());
+    open::that(&location).with_context(|| format!("failed to open {location}"))?;
+    Ok(0)
+}
+
+fn repo_url(sess: &AppSession) -> Result<String> {
+    let upstream = sess
+        .repo
+        .upstream_url()
+        .context("the repository has no upstream remote")?;
+    forge::current()
+        .repo_url_for_remote(&upstream)
+        .with_context(|| format!("upstream remote `{upstream}` is not on the forge"))
+}
+
+/// The newest open release PR, limited to those releasing `project`.
+fn pr_url(sess: &AppSession, project: Option<&str>) -> Result<String> {
+    if let Some(name) = project {
+        resolve_project(sess, Some(name))?;
+    }
+    let Some(pending) = find_pending_releases(sess) else {
+        bail!(
+            "could not list the release PRs; run `belaf auth login`, or browse {}/pulls",
+            repo_url(sess)?
+        );
+    };
+    let newest = pending
+        .into_iter()
+        .filter(|pr| project.is_none_or(|name| pr.covers(name)))
+        .max_by_key(|pr| pr.number);
+    match (newest, project) {
+        (Some(pr), _) => match pr.url {
+            Some(url) => Ok(url),
+            None => Ok(format!("{}/pull/{}", repo_url(sess)?, pr.number)),
+        },
+        (None, Some(name)) => bail!("no open release PR releases {name}"),
+        (None, None) => bail!("no release PR is open"),
+    }
+}
+
+/// `project`, or the only release unit when none is given.
+fn resolve_project(sess: &AppSession, project: Option<&str>) -> Result<ReleaseUnitId> {
+    let graph = sess.graph();
+    match project {
+        Some(name) => graph.lookup_ident(name).with_context(|| {
+            let mut names: Vec<_> = graph
+                .projects()
+                .map(|u| u.user_facing_name.as_str())
+                .collect();
+            names.sort_unstable();
+            format!("no release unit `{name}`; known: {}", names.join(", "))
+        }),
+        None => {
+            let mut units = graph.projects();
+            match (units.next(), units.next()) {
+                (Some(unit), None) => Ok(unit.ident()),
+                _ => bail!("name the project: the repository has several release units"),
+            }
+        }
+    }
+}
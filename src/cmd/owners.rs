@@ -0,0 +1,97 @@
+//! `belaf owners` — who owns each release unit, per `CODEOWNERS`.
+//!
+//! A unit's owners are the owners of the files directly in its
+//! directory; see [`crate::core::github::codeowners`]. The same owners
+//! appear in `status --format json` and are asked to review the release
+//! PR of the units they own.
+
+use anyhow::Result;
+use owo_colors::OwoColorize;
+use serde::Serialize;
+
+use crate::cli::OwnersOutputFormat;
+use crate::core::{github::codeowners::CodeOwners, graph::GraphQueryBuilder, session::AppSession};
+
+#[derive(Serialize)]
+struct OwnersPayload {
+    /// The `CODEOWNERS` file read, if any.
+    codeowners: Option<String>,
+    units: Vec<UnitOwners>,
+}
+
+#[derive(Serialize)]
+struct UnitOwners {
+    name: String,
+    prefix: String,
+    owners: Vec<String>,
+    /// The pattern of the deciding line.
+    rule: Option<String>,
+}
+
+pub fn run(project: Option<String>, format: Option<OwnersOutputFormat>) -> Result<i32> {
+    let sess = AppSession::initialize_default()?;
+    let codeowners = CodeOwners::load(&sess.repo);
+
+    let mut q = GraphQueryBuilder::default();
+    q.names(project);
+    let idents = sess.graph().query(q)?;
+
+    let units = idents
+        .iter()
+        .map(|id| {
+            let unit = sess.graph().lookup(*id);
+            let rule = codeowners.rule_for(unit.prefix());
+            UnitOwners {
+                name: unit.user_facing_name.clone(),
+                prefix: unit.prefix().escaped(),
+                owners: rule.map(|r| r.owners.clone()).unwrap_or_default(),
+                rule: rule.map(|r| r.pattern.clone()),
+            }
+        })
+        .collect();
+
+    let payload = OwnersPayload {
+        codeowners: codeowners.path().map(|p| p.escaped()),
+        units,
+    };
+
+    if format == Some(OwnersOutputFormat::Json) {
+        println!("{}", serde_json::to_string_pretty(&payload)?);
+        return Ok(0);
+    }
+
+    let Some(path) = &payload.codeowners else {
+        println!(
+            "{} No CODEOWNERS file found (looked in .github/, the root and docs/).",
+            "ℹ".cyan().bold()
+        );
+        return Ok(0);
+    };
+
+    println!(
+        "{} {}",
+        "Release unit owners".bold(),
+        format!("(from {path})").dimmed()
+    );
+    println!();
+    let width = payload
+        .units
+        .iter()
+        .map(|u| u.name.len())
+        .max()
+        .unwrap_or(0);
+    for unit in &payload.units {
+        let owners = if unit.owners.is_empty() {
+            "(unowned)".dimmed().to_string()
+        } else {
+            unit.owners.join(" ")
+        };
+        let rule = unit
+            .rule
+            .as_ref()
+            .map(|r| format!("  {r}").dimmed().to_string())
+            .unwrap_or_default();
+        println!("  {:<width$}  {owners}{rule}", unit.name);
+    }
+    Ok(0)
+}
@@ -0,0 +1,148 @@
+//! `belaf package` — publish package manager manifests for a release.
+//!
+//! See [`crate::core::packaging`]. Packages the unit's latest release
+//! tag, so it runs after the release PR merged and the archives were
+//! built, typically in the workflow that builds them. A manifest that
+//! fails to publish sends the `publish_failed` webhook; see
+//! [`crate::core::notify`].
+
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use owo_colors::OwoColorize;
+
+use crate::core::{
+    api::{ApiClient, ApiError},
+    auth::{
+        permissions::{explain_api_error, Permission},
+        token::load_or_exchange_token,
+    },
+    config::syntax::WebhookEvent,
+    github::forge,
+    notify,
+    packaging::{self, Package, PackageManager},
+    session::AppSession,
+};
+
+pub async fn run(release_unit: String, artifacts: PathBuf, dry_run: bool) -> Result<i32> {
+    let sess = AppSession::initialize_default()?;
+    let Some(cfg) = sess.config_packaging(&release_unit) else {
+        bail!("no [packaging.{release_unit}] in belaf/config.toml");
+    };
+    let ident = sess
+        .graph()
+        .lookup_ident(&release_unit)
+        .context("BUG: [packaging] units are validated when the config loads")?;
+
+    let histories = sess
+        .analyze_histories()
+        .context("failed to analyze project histories")?;
+    let history = histories.lookup(ident);
+    let (Some(version), Some(tag)) = (history.release_version(), history.release_tag()) else {
+        bail!("{release_unit} has no release tag to package");
+    };
+    let version = version.to_string();
+
+    let repo_page = sess
+        .repo
+        .upstream_url()
+        .ok()
+        .and_then(|url| forge::current().repo_url_for_remote(&url));
+    let url_template = match (&cfg.url, &repo_page) {
+        (Some(url), _) => url.clone(),
+        (None, Some(page)) => format!("{page}/releases/download/{{tag}}/{{file}}"),
+        (None, None) => bail!(
+            "set `url` in [packaging.{release_unit}]: the upstream remote is not on the forge"
+        ),
+    };
+    let artifacts = packaging::collect_artifacts(&artifacts, &url_template, tag, &version)?;
+    if artifacts.is_empty() {
+        bail!(
+            "no release archives found; their file names must contain a target triple \
+             such as `x86_64-unknown-linux-gnu`"
+        );
+    }
+    let package = Package {
+        binary: cfg.binary.clone().unwrap_or_else(|| release_unit.clone()),
+        version: version.clone(),
+        description: cfg
+            .description
+            .clone()
+            .unwrap_or_else(|| release_unit.clone()),
+        homepage: cfg.homepage.clone().or(repo_page).unwrap_or_default(),
+        license: cfg.license.clone(),
+        artifacts,
+    };
+    let files = PackageManager::configured(cfg)
+        .into_iter()
+        .map(|(manager, repo)| {
+            packaging::render(manager, repo, &package)
+                .with_context(|| format!("cannot build the {} manifest", manager.label()))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    if dry_run {
+        for file in &files {
+            println!(
+                "{} {} → {}:{}\n",
+                "ℹ".cyan().bold(),
+                file.manager.label(),
+                file.repo,
+                file.path
+            );
+            println!("{}", file.content);
+        }
+        return Ok(0);
+    }
+
+    let client = ApiClient::try_new()?;
+    let token = load_or_exchange_token(&client)
+        .await?
+        .filter(|t| !t.is_expired())
+        .ok_or(ApiError::Unauthorized)?;
+    let message = format!("{release_unit} {version}");
+    for file in &files {
+        let (owner, name) = file
+            .repo
+            .split_once('/')
+            .context("BUG: package repositories are validated when the config loads")?;
+        let credentials = client
+            .get_git_credentials(&token, owner, name)
+            .await
+            .map_err(|e| explain_api_error(e, Permission::Contents, owner, name))?;
+        let remote = format!("{}.git", forge::current().repo_url(owner, name));
+        let changed = packaging::publish(&remote, Some(&credentials.token), file, &message)
+            .inspect_err(|e| {
+                notify::emit(
+                    sess.webhooks(),
+                    WebhookEvent::PublishFailed,
+                    serde_json::json!({
+                        "name": release_unit,
+                        "version": version,
+                        "tag": tag,
+                        "manager": file.manager.label(),
+                        "repo": file.repo,
+                        "path": file.path,
+                        "error": format!("{e:#}"),
+                    }),
+                )
+            })?;
+        if changed {
+            println!(
+                "{} Committed {} {version} to {}:{}",
+                "✓".green().bold(),
+                file.manager.label(),
+                file.repo,
+                file.path
+            );
+        } else {
+            println!(
+                "{} {}:{} is already at {version}",
+                "ℹ".cyan().bold(),
+                file.repo,
+                file.path
+            );
+        }
+    }
+    Ok(0)
+}
@@ -1,4 +1,4 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use owo_colors::OwoColorize;
 use tracing::{info, warn};
 
@@ -10,7 +10,10 @@ use crate::core::{
     github::client::parse_github_url,
     group::GroupSet,
     session::{AppBuilder, AppSession},
-    workflow::{BumpChoice, PrepareContext, ReleaseUnitSelection},
+    workflow::{
+        preflight_github_access, roll_back_release, BumpChoice, EmptyRelease, Journal, JournalStep,
+        PrepareContext, ReleasePipeline, ReleaseUnitSelection,
+    },
 };
 
 mod wizard;
@@ -39,7 +42,7 @@ fn report_drift_telemetry(sess: &AppSession, uncovered_paths: &[String]) {
 
     let paths = uncovered_paths.to_vec();
     let drift_future = async move {
-        let api_client = ApiClient::new();
+        let api_client = ApiClient::try_new().map_err(|e| format!("{e}"))?;
         let token = match load_or_exchange_token(&api_client).await {
             Ok(Some(t)) => t,
             // No keyring token and no Actions OIDC env — pre-install state.
@@ -111,7 +114,7 @@ fn print_no_changes_message_ci() {
 #[derive(serde::Serialize)]
 struct CiStatus {
     /// Stable, snake_case status label. One of: `nothing_to_do`,
-    /// `no_actionable_bumps`, `released`.
+    /// `no_actionable_bumps`, `released`, `rolled_back`.
     status: &'static str,
     /// Best-effort PR URL when `status == "released"`. Null otherwise
     /// (and when github auth is unavailable).
@@ -135,11 +138,18 @@ fn emit_ci_status(status: CiStatus) {
     }
 }
 
+/// `empty_message` is the `--message` of an `--allow-empty` run.
+#[expect(clippy::too_many_arguments)]
 pub fn run(
     ci: bool,
     project_overrides: Option<Vec<String>>,
+    projects_from_file: Option<String>,
     bump_source: Option<String>,
     bump_source_cmd: Option<String>,
+    force: bool,
+    branch: Option<String>,
+    empty_message: Option<String>,
+    resume: bool,
 ) -> Result<i32> {
     use crate::core::ui::utils::is_interactive_terminal;
     use anyhow::bail;
@@ -149,8 +159,36 @@ pub fn run(
         env!("CARGO_PKG_VERSION")
     );
 
+    if resume {
+        return run_resume(ci);
+    }
+
+    let empty_release = empty_message.map(|message| EmptyRelease {
+        units: project_overrides
+            .iter()
+            .flatten()
+            .filter_map(|o| o.split_once(':').map(|(name, _)| name.to_string()))
+            .collect(),
+        message,
+    });
+
     if ci {
-        return run_ci_mode(project_overrides, bump_source, bump_source_cmd);
+        if projects_from_file.as_deref() == Some("-") && bump_source.as_deref() == Some("-") {
+            bail!("`--projects-from-file -` and `--bump-source -` can't both read stdin");
+        }
+        let project_list = projects_from_file
+            .as_deref()
+            .map(ProjectList::read)
+            .transpose()?;
+        return run_ci_mode(
+            project_overrides,
+            project_list,
+            bump_source,
+            bump_source_cmd,
+            force,
+            branch,
+            empty_release,
+        );
     }
 
     if !is_interactive_terminal() {
@@ -169,30 +207,57 @@ pub fn run(
              Use `--bump-source <FILE>` in interactive mode, or pass --ci."
         );
     }
-
-    run_interactive_mode(project_overrides, bump_source, bump_source_cmd)
+    if matches!(projects_from_file.as_deref(), Some("-")) {
+        bail!(
+            "Error: `--projects-from-file -` (stdin) is only supported in --ci mode.\n\n\
+             Use `--projects-from-file <FILE>` in interactive mode, or pass --ci."
+        );
+    }
+    let project_list = projects_from_file
+        .as_deref()
+        .map(ProjectList::read)
+        .transpose()?;
+
+    run_interactive_mode(
+        project_overrides,
+        project_list,
+        bump_source,
+        bump_source_cmd,
+        force,
+        branch,
+        empty_release,
+    )
 }
 
 fn run_ci_mode(
     project_overrides: Option<Vec<String>>,
+    project_list: Option<ProjectList>,
     cli_bump_source: Option<String>,
     cli_bump_source_cmd: Option<String>,
+    force: bool,
+    branch: Option<String>,
+    empty_release: Option<EmptyRelease>,
 ) -> Result<i32> {
     info!("running in CI mode (PR-based workflow)");
 
-    let mut sess = AppBuilder::new()?.fetch_tags_first(true).initialize()?;
+    let mut sess = AppBuilder::new()?
+        .fetch_tags_first(true)
+        .maintenance_branch(branch)
+        .initialize()?;
     let drift_paths = sess.drift_uncovered_paths();
     report_drift_telemetry(&sess, &drift_paths);
     if !drift_paths.is_empty() {
         anyhow::bail!("{}", sess.pre_prepare_drift_check().unwrap_err());
     }
+    preflight_github_access(&sess)?;
     let config_bump_sources = sess.config_bump_sources().to_vec();
     // Snapshot groups before ctx takes a mutable borrow on sess. The
     // GroupSet is cloneable and we only read from it during validation, so
     // there's no consistency risk vs. the live graph.
     let groups = sess.graph().groups().clone();
 
-    let mut ctx = PrepareContext::initialize(&mut sess, false)?;
+    let mut ctx = PrepareContext::initialize(&mut sess, false, force)?;
+    ctx.empty_release = empty_release;
     ctx.discover_projects()?;
 
     if !ctx.has_candidates() {
@@ -211,20 +276,30 @@ fn run_ci_mode(
         .iter()
         .cloned()
         .map(|candidate| ReleaseUnitSelection {
+            bump_choice: BumpChoice::default_for(&candidate),
             candidate,
-            bump_choice: BumpChoice::Auto,
+            custom_version: None,
             cached_changelog: None,
         })
         .collect();
 
     // Precedence: config bump-source defaults → explicit --bump-source* CLI →
-    // --project overrides. Later wins, so we apply in that order.
+    // --projects-from-file → --project overrides. Later wins, so we apply in
+    // that order.
     apply_config_bump_sources(&mut selections, &config_bump_sources)?;
     apply_cli_bump_source(
         &mut selections,
         cli_bump_source.as_deref(),
         cli_bump_source_cmd.as_deref(),
     )?;
+    if let Some(list) = &project_list {
+        // A bad list is a typo away; don't leave a half-started release
+        // behind for `--resume` over it.
+        if let Err(e) = apply_project_list(&mut selections, list) {
+            ctx.cleanup();
+            return Err(e);
+        }
+    }
     if let Some(overrides) = project_overrides {
         apply_project_overrides(&mut selections, &overrides)?;
     }
@@ -276,21 +351,86 @@ fn run_ci_mode(
     Ok(0)
 }
 
+/// `belaf prepare --resume`: finish the release an earlier run left
+/// incomplete, or roll it back. See [`crate::core::workflow::Journal`].
+fn run_resume(ci: bool) -> Result<i32> {
+    let mut sess = AppBuilder::new()?.initialize()?;
+    let Some(journal) = Journal::load(&sess.repo)? else {
+        eprintln!("{} No unfinished release to resume.", "ℹ".cyan().bold());
+        if ci {
+            emit_ci_status(CiStatus {
+                status: "nothing_to_do",
+                pr_url: None,
+                release_units: vec![],
+            });
+        }
+        return Ok(0);
+    };
+
+    if journal.step < JournalStep::FilesWritten {
+        roll_back_release(&mut sess, &journal)?;
+        eprintln!(
+            "{} Rolled back the release on `{}`, which stopped {}; back on `{}`.",
+            "✓".green().bold(),
+            journal.release_branch,
+            journal.step.describe(),
+            journal.base_branch
+        );
+        eprintln!("  {} Run `belaf prepare` to start over.", "→".dimmed());
+        if ci {
+            emit_ci_status(CiStatus {
+                status: "rolled_back",
+                pr_url: None,
+                release_units: vec![],
+            });
+        }
+        return Ok(0);
+    }
+
+    let release_units: Vec<CiStatusUnit> = journal
+        .release_units
+        .iter()
+        .map(|u| CiStatusUnit {
+            name: u.name.clone(),
+            bump: u.bump.clone(),
+        })
+        .collect();
+    let pr_url = ReleasePipeline::resume(&mut sess, journal)?;
+    if ci {
+        emit_ci_status(CiStatus {
+            status: "released",
+            pr_url: Some(pr_url),
+            release_units,
+        });
+    } else {
+        println!("{} Release PR: {pr_url}", "✓".green().bold());
+    }
+    Ok(0)
+}
+
 fn run_interactive_mode(
     project_overrides: Option<Vec<String>>,
+    project_list: Option<ProjectList>,
     bump_source: Option<String>,
     bump_source_cmd: Option<String>,
+    force: bool,
+    branch: Option<String>,
+    empty_release: Option<EmptyRelease>,
 ) -> Result<i32> {
     // The interactive wizard owns its own selections state machine; we
     // pre-collect external decisions here and propagate them so the
     // wizard's "suggested bump" column reflects the same precedence as
     // CI mode. See `wizard::run_with_overrides_and_decisions`.
-    let sess = AppBuilder::new()?.fetch_tags_first(true).initialize()?;
+    let sess = AppBuilder::new()?
+        .fetch_tags_first(true)
+        .maintenance_branch(branch.clone())
+        .initialize()?;
     let drift_paths = sess.drift_uncovered_paths();
     report_drift_telemetry(&sess, &drift_paths);
     if !drift_paths.is_empty() {
         anyhow::bail!("{}", sess.pre_prepare_drift_check().unwrap_err());
     }
+    preflight_github_access(&sess)?;
     let config_bump_sources = sess.config_bump_sources().to_vec();
     drop(sess);
 
@@ -300,7 +440,14 @@ fn run_interactive_mode(
     if let Some(d) = collect_cli_decisions(bump_source.as_deref(), bump_source_cmd.as_deref())? {
         decisions.extend(d);
     }
-    wizard::run_with_overrides_and_decisions(project_overrides, decisions)
+    wizard::run_with_overrides_and_decisions(
+        project_overrides,
+        project_list,
+        decisions,
+        force,
+        branch,
+        empty_release,
+    )
 }
 
 /// Apply `[[bump_source]]` config entries to the selections list. Each
@@ -481,6 +628,96 @@ fn apply_decisions(
     Ok(())
 }
 
+/// `--projects-from-file`: the units of a release train, one per line as
+/// `name` or `name:bump`. Blank lines and `#` comments are skipped. Units
+/// not listed are left out of the release.
+struct ProjectList {
+    names: Vec<String>,
+    /// The `name:bump` lines, in `--release-unit` form.
+    overrides: Vec<String>,
+}
+
+impl ProjectList {
+    /// Read the list from `path`, or from stdin for `-`.
+    fn read(path: &str) -> Result<Self> {
+        let text = if path == "-" {
+            std::io::read_to_string(std::io::stdin())
+                .context("failed to read the project list from stdin")?
+        } else {
+            std::fs::read_to_string(path)
+                .with_context(|| format!("failed to read the project list `{path}`"))?
+        };
+        Self::parse(&text)
+    }
+
+    fn parse(text: &str) -> Result<Self> {
+        let mut list = Self {
+            names: Vec::new(),
+            overrides: Vec::new(),
+        };
+        for (index, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let (name, bump) = match line.split_once(':') {
+                Some((name, bump)) => (name.trim(), Some(bump.trim())),
+                None => (line, None),
+            };
+            if name.is_empty() || name.contains(char::is_whitespace) {
+                anyhow::bail!(
+                    "line {} of the project list: expected `name` or `name:bump`, got `{line}`",
+                    index + 1
+                );
+            }
+            list.names.push(name.to_string());
+            if let Some(bump) = bump {
+                list.overrides.push(format!("{name}:{bump}"));
+            }
+        }
+        Ok(list)
+    }
+
+    fn contains(&self, name: &str) -> bool {
+        self.names.iter().any(|n| n == name)
+    }
+
+    /// Fail on listed units that aren't among the release candidates.
+    fn check_names(&self, candidates: &[String]) -> Result<()> {
+        let unknown: Vec<&str> = self
+            .names
+            .iter()
+            .filter(|name| !candidates.contains(name))
+            .map(String::as_str)
+            .collect();
+        if !unknown.is_empty() {
+            anyhow::bail!(
+                "--projects-from-file lists units that don't exist or have nothing to release: {}. \
+                 Available: {}",
+                unknown.join(", "),
+                candidates.join(", ")
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Leave the units `list` doesn't name out of the release and apply its
+/// bumps.
+fn apply_project_list(selections: &mut [ReleaseUnitSelection], list: &ProjectList) -> Result<()> {
+    let names: Vec<String> = selections
+        .iter()
+        .map(|s| s.candidate.name.clone())
+        .collect();
+    list.check_names(&names)?;
+    for selection in selections.iter_mut() {
+        if !list.contains(&selection.candidate.name) {
+            selection.bump_choice = BumpChoice::Skip;
+        }
+    }
+    apply_project_overrides(selections, &list.overrides)
+}
+
 fn apply_project_overrides(
     selections: &mut [ReleaseUnitSelection],
     overrides: &[String],
@@ -15,26 +15,31 @@ use std::thread;
 use std::time::Duration;
 use tracing::info;
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use crate::core::{
-    bump::{BumpConfig, BumpRecommendation},
+    bump::{extract_scope, BumpConfig, BumpReason, BumpRecommendation, ScopeMatcher},
     changelog::{ChangelogConfig, Commit, GitConfig},
+    commit_scopes::{self, ScopeUsage},
     config::syntax::{BumpConfiguration, ChangelogConfiguration},
-    git::repository::RepoPathBuf,
+    git::repository::{CommitInspection, RepoPathBuf, Repository},
+    github::pending::PendingRelease,
     session::AppBuilder,
     ui::components::toggle_panel::TogglePanel,
     wire::known::Ecosystem,
     workflow::{
-        generate_changelog_entry, BumpChoice, PrepareContext, ReleaseUnitCandidate,
-        ReleaseUnitSelection,
+        generate_changelog_entry, validate_custom_version, BumpChoice, ConflictPlanner,
+        ConflictResolution, DepConflict, EmptyRelease, FollowUp, PlannedUnit, PrepareContext,
+        ReleaseEdits, ReleaseUnitCandidate, ReleaseUnitSelection,
     },
 };
+use crate::utils::theme;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum WizardStep {
     ReleaseUnitSelection,
     UnitConfig { unit_index: usize },
+    DepConflicts,
     Confirmation,
 }
 
@@ -42,6 +47,8 @@ struct ReleaseUnitItem {
     candidate: ReleaseUnitCandidate,
     selected: bool,
     chosen_bump: Option<BumpChoice>,
+    /// Validated version for `BumpChoice::Custom`.
+    custom_version: Option<String>,
     cached_changelog: Option<String>,
     existing_changelog: String,
     /// Resolved group id, if this project is a member of a `[[group]]`.
@@ -68,6 +75,7 @@ impl ReleaseUnitItem {
             candidate,
             selected: true,
             chosen_bump: None,
+            custom_version: None,
             cached_changelog: None,
             existing_changelog,
             group_id: None,
@@ -94,16 +102,65 @@ impl ReleaseUnitItem {
         self.candidate.suggested_bump
     }
 
+    fn bump_reasons(&self) -> &[BumpReason] {
+        &self.candidate.bump_reasons
+    }
+
     fn commits(&self) -> &[Commit] {
         &self.candidate.commits
     }
 
+    /// Scopes of the pending commits that the unit's `scopes` don't list.
+    fn unknown_scopes(&self) -> Vec<String> {
+        let messages = self.candidate.commits.iter().map(|c| c.message.as_str());
+        ScopeUsage::tally(messages, &self.candidate.scopes).unknown
+    }
+
+    /// "commits say minor, API diff says MAJOR" when the API diff requires
+    /// a bigger release than the commits suggest.
+    fn api_bump_warning(&self) -> Option<String> {
+        let suggested = self.suggested_bump();
+        let required = self.candidate.api_bump?;
+        (required.merge(suggested) != suggested).then(|| {
+            format!(
+                "commits say {}, API diff says {}",
+                suggested.as_str(),
+                required.as_str().to_uppercase()
+            )
+        })
+    }
+
     fn ecosystem(&self) -> &Ecosystem {
         &self.candidate.ecosystem
     }
 
     fn effective_bump(&self) -> BumpChoice {
-        self.chosen_bump.unwrap_or(BumpChoice::Auto)
+        self.chosen_bump
+            .unwrap_or_else(|| BumpChoice::default_for(&self.candidate))
+    }
+
+    fn bump_choices(&self) -> Vec<BumpChoice> {
+        BumpChoice::choices_for(&self.candidate)
+    }
+
+    fn bump_skipped(&self) -> bool {
+        self.effective_bump() == BumpChoice::Skip
+    }
+
+    /// What `choice` releases this unit at, as `finalize` computes it; the
+    /// current version while a custom one is still missing.
+    fn next_version(&self, choice: BumpChoice) -> String {
+        self.candidate
+            .next_version(choice, self.custom_version.as_deref())
+            .map_or_else(|_| self.current_version().to_string(), |v| v.to_string())
+    }
+
+    /// The custom version when one is chosen, the bump text otherwise.
+    fn planned_bump(&self) -> &str {
+        match (self.effective_bump(), &self.custom_version) {
+            (BumpChoice::Custom, Some(version)) => version,
+            _ => self.effective_bump_str(),
+        }
     }
 
     fn effective_bump_str(&self) -> &'static str {
@@ -134,51 +191,6 @@ fn compute_display_rows(units: &[ReleaseUnitItem]) -> Vec<DisplayRow> {
     out
 }
 
-fn calculate_next_version(current: &str, recommendation: BumpRecommendation) -> String {
-    match recommendation {
-        BumpRecommendation::Major => calculate_major_version(current),
-        BumpRecommendation::Minor => calculate_minor_version(current),
-        BumpRecommendation::Patch => calculate_patch_version(current),
-        BumpRecommendation::None => current.to_string(),
-    }
-}
-
-fn calculate_major_version(current: &str) -> String {
-    if let Ok(mut v) = semver::Version::parse(current) {
-        v.major += 1;
-        v.minor = 0;
-        v.patch = 0;
-        v.pre = semver::Prerelease::EMPTY;
-        v.build = semver::BuildMetadata::EMPTY;
-        v.to_string()
-    } else {
-        format!("{}+1.0.0", current)
-    }
-}
-
-fn calculate_minor_version(current: &str) -> String {
-    if let Ok(mut v) = semver::Version::parse(current) {
-        v.minor += 1;
-        v.patch = 0;
-        v.pre = semver::Prerelease::EMPTY;
-        v.build = semver::BuildMetadata::EMPTY;
-        v.to_string()
-    } else {
-        format!("{}+0.1.0", current)
-    }
-}
-
-fn calculate_patch_version(current: &str) -> String {
-    if let Ok(mut v) = semver::Version::parse(current) {
-        v.patch += 1;
-        v.pre = semver::Prerelease::EMPTY;
-        v.build = semver::BuildMetadata::EMPTY;
-        v.to_string()
-    } else {
-        format!("{}+0.0.1", current)
-    }
-}
-
 struct WizardState {
     step: WizardStep,
     units: Vec<ReleaseUnitItem>,
@@ -193,6 +205,43 @@ struct WizardState {
     changelog_config: ChangelogConfiguration,
     bump_config: BumpConfiguration,
     changelog_scroll_offset: u16,
+    /// Cursor in the Step 2 commit list; `None` while the bump list has
+    /// focus.
+    commit_cursor: Option<usize>,
+    /// Commit opened with Enter, shown as a popup over Step 2.
+    commit_detail: Option<CommitDetail>,
+    commit_detail_scroll: u16,
+    repo_root: PathBuf,
+    /// Opened on the first detail request; the wizard otherwise never
+    /// touches git.
+    repo: Option<Repository>,
+    conflict_planner: ConflictPlanner,
+    /// Conflicts the current plan causes, each with the resolution the
+    /// user picked in the `DepConflicts` step.
+    dep_conflicts: Vec<(DepConflict, ConflictResolution)>,
+    conflict_list_state: ListState,
+    /// Units left out of the plan that depend on, or cascade from, one it
+    /// releases; listed in the confirmation step.
+    follow_ups: Vec<FollowUp>,
+    /// Open release PRs; with `--force` the plan may cover their units.
+    pending_releases: Vec<PendingRelease>,
+    /// How the plan differs from the open release PRs it prepares again;
+    /// listed in the confirmation step.
+    release_edits: Vec<ReleaseEdits>,
+    /// Version being typed for `BumpChoice::Custom`; `Some` while the
+    /// input is open.
+    custom_input: Option<String>,
+    /// Why the typed version was rejected.
+    custom_error: Option<String>,
+}
+
+/// Everything the commit detail popup shows.
+struct CommitDetail {
+    inspection: CommitInspection,
+    author: String,
+    attribution: String,
+    /// Set when the commit's scope is outside the unit's `scopes`.
+    scope_warning: Option<String>,
 }
 
 impl WizardState {
@@ -200,6 +249,7 @@ impl WizardState {
         projects: Vec<ReleaseUnitItem>,
         changelog_config: ChangelogConfiguration,
         bump_config: BumpConfiguration,
+        repo_root: PathBuf,
     ) -> Self {
         let mut unit_list_state = ListState::default();
         if !projects.is_empty() {
@@ -223,9 +273,83 @@ impl WizardState {
             changelog_config,
             bump_config,
             changelog_scroll_offset: 0,
+            commit_cursor: None,
+            commit_detail: None,
+            commit_detail_scroll: 0,
+            repo_root,
+            repo: None,
+            conflict_planner: ConflictPlanner::default(),
+            dep_conflicts: Vec::new(),
+            conflict_list_state: ListState::default(),
+            follow_ups: Vec::new(),
+            pending_releases: Vec::new(),
+            release_edits: Vec::new(),
+            custom_input: None,
+            custom_error: None,
         }
     }
 
+    /// Load the commit under the cursor from git and open the detail popup.
+    /// A commit that cannot be read shows the error in place of its body.
+    fn open_commit_detail(&mut self) {
+        let Some(cursor) = self.commit_cursor else {
+            return;
+        };
+        let Some((commit, unit)) = self
+            .get_current_project()
+            .and_then(|p| p.commits().get(cursor).map(|c| (c.clone(), p)))
+        else {
+            return;
+        };
+        let unit_name = unit.name().to_string();
+        let unit_prefix = unit.candidate.prefix.clone();
+        let scope_warning = commit_scopes::unknown_scope(&commit.message, &unit.candidate.scopes)
+            .map(|scope| {
+                format!(
+                    "scope `{scope}` is not one of {unit_name}'s scopes: {}",
+                    unit.candidate.scopes.join(", ")
+                )
+            });
+        let unit_names: Vec<String> = self.units.iter().map(|u| u.name().to_string()).collect();
+
+        if self.repo.is_none() {
+            self.repo = Repository::open(&self.repo_root).ok();
+        }
+        let inspection = match &self.repo {
+            Some(repo) => repo.inspect_commit(&commit.id),
+            None => Err(anyhow::anyhow!(
+                "cannot open the repository at {}",
+                self.repo_root.display()
+            )),
+        };
+        let inspection = inspection.unwrap_or_else(|e| CommitInspection {
+            id: commit.id.clone(),
+            summary: commit.message.clone(),
+            body: format!("(could not load commit details: {e})"),
+            trailers: Vec::new(),
+            changed_files: Vec::new(),
+        });
+
+        let attribution = attribution_reason(&inspection, &unit_name, &unit_prefix, &unit_names);
+        self.commit_detail = Some(CommitDetail {
+            author: format!(
+                "{} <{}>",
+                commit.author.name.as_deref().unwrap_or("unknown"),
+                commit.author.email.as_deref().unwrap_or("")
+            ),
+            inspection,
+            attribution,
+            scope_warning,
+        });
+        self.commit_detail_scroll = 0;
+    }
+
+    fn reset_commit_view(&mut self) {
+        self.commit_cursor = None;
+        self.commit_detail = None;
+        self.commit_detail_scroll = 0;
+    }
+
     fn toggle_markdown_view(&mut self) {
         self.changelog_toggle.toggle();
     }
@@ -260,22 +384,17 @@ impl WizardState {
         }
 
         let commits = project.commits().to_vec();
-        let current_version = project.current_version().to_string();
-        let chosen_bump = project.chosen_bump.unwrap_or(BumpChoice::Auto);
-        let suggested_bump = project.suggested_bump();
-
-        let new_version = match chosen_bump {
-            BumpChoice::Auto => calculate_next_version(&current_version, suggested_bump),
-            BumpChoice::Major => calculate_major_version(&current_version),
-            BumpChoice::Minor => calculate_minor_version(&current_version),
-            BumpChoice::Patch => calculate_patch_version(&current_version),
-        };
+        let new_version = project.next_version(project.effective_bump());
+
+        let git_config = GitConfig::from_user_config(&self.changelog_config);
+        let changelog_config = ChangelogConfig::from_user_config(
+            &self
+                .changelog_config
+                .for_project(project.name(), project.ecosystem().as_str()),
+        );
 
         let (tx, rx) = mpsc::channel();
         self.loading_receiver = Some(rx);
-
-        let git_config = GitConfig::from_user_config(&self.changelog_config);
-        let changelog_config = ChangelogConfig::from_user_config(&self.changelog_config);
         let bump_config = BumpConfig::from_user_config(&self.bump_config);
 
         thread::spawn(move || {
@@ -362,6 +481,67 @@ impl WizardState {
         }
     }
 
+    /// Recheck the plan against internal dependency requirements, keeping
+    /// the resolution already picked for a conflict that is still there,
+    /// and list the dependents it leaves out.
+    fn refresh_dep_conflicts(&mut self) {
+        let selected = self.selected_projects();
+        let bumps: Vec<_> = selected
+            .iter()
+            .map(|p| (p.candidate.ident, p.planned_bump()))
+            .collect();
+        let conflicts = self.conflict_planner.conflicts(&bumps);
+        self.follow_ups = self.conflict_planner.follow_ups(&bumps);
+        let previous = std::mem::take(&mut self.dep_conflicts);
+        self.dep_conflicts = conflicts
+            .into_iter()
+            .map(|c| {
+                let resolution = previous
+                    .iter()
+                    .find(|(p, _)| p.dependent == c.dependent && p.dependee == c.dependee)
+                    .map(|(_, r)| *r)
+                    .unwrap_or_default();
+                (c, resolution)
+            })
+            .collect();
+        self.conflict_list_state
+            .select((!self.dep_conflicts.is_empty()).then_some(0));
+    }
+
+    /// Compare the plan with the open release PRs it prepares again.
+    fn refresh_release_edits(&mut self) {
+        let versions: Vec<(&ReleaseUnitItem, String)> = self
+            .selected_projects()
+            .into_iter()
+            .filter(|p| {
+                !p.bump_skipped() && p.effective_bump_str() != "no bump" && !self.is_skipped(p)
+            })
+            .map(|p| (p, p.next_version(p.effective_bump())))
+            .collect();
+        let planned: Vec<PlannedUnit<'_>> = versions
+            .iter()
+            .map(|(p, version)| PlannedUnit {
+                name: p.name(),
+                version,
+                commits: p.commits(),
+            })
+            .collect();
+        self.release_edits = ReleaseEdits::against_pending(&self.pending_releases, &planned);
+    }
+
+    /// Whether a `SkipProject` resolution leaves `unit` out, directly or
+    /// through its group.
+    fn is_skipped(&self, unit: &ReleaseUnitItem) -> bool {
+        self.dep_conflicts
+            .iter()
+            .filter(|(_, r)| *r == ConflictResolution::SkipProject)
+            .filter_map(|(c, _)| self.units.iter().find(|u| u.candidate.ident == c.dependee))
+            .any(|skipped| {
+                skipped.candidate.ident == unit.candidate.ident
+                    || (skipped.group_id().is_some() && skipped.group_id() == unit.group_id())
+            })
+    }
+
     fn toggle_help(&mut self) {
         self.show_help = !self.show_help;
     }
@@ -370,6 +550,7 @@ impl WizardState {
         if self.loading_changelog {
             return false;
         }
+        self.reset_commit_view();
 
         match &self.step {
             WizardStep::ReleaseUnitSelection => {
@@ -382,20 +563,26 @@ impl WizardState {
                 true
             }
             WizardStep::UnitConfig { unit_index } => {
+                let unit_index = *unit_index;
                 if !self.show_changelog {
-                    if let Some(selected) = self.bump_list_state.selected() {
-                        let choice = BumpChoice::all()[selected];
+                    if let Some(choice) = self.highlighted_bump() {
                         // Group atomicity: changing one member's bump
                         // propagates to every sibling so the user can't
                         // accidentally desync the group through the UI.
                         // Validation at finalize is the safety net; this is
                         // the friendlier path where it just stays consistent.
+                        // Siblings that weren't bumped by hand can't take
+                        // `as-is` and keep their own choice.
                         if let Some(project) = self.get_current_project() {
                             let group_id = project.group_id().map(str::to_string);
+                            let custom_version = project.custom_version.clone();
                             if let Some(gid) = group_id {
                                 for p in &mut self.units {
-                                    if p.group_id() == Some(gid.as_str()) {
+                                    if p.group_id() == Some(gid.as_str())
+                                        && p.bump_choices().contains(&choice)
+                                    {
                                         p.chosen_bump = Some(choice);
+                                        p.custom_version = custom_version.clone();
                                     }
                                 }
                             } else if let Some(p) = self.get_current_project_mut() {
@@ -403,28 +590,47 @@ impl WizardState {
                             }
                         }
                     }
-                    self.loading_changelog = true;
-                    self.start_background_changelog_generation();
-                    true
+                    // A skipped unit has no changelog to preview; move on.
+                    if !self
+                        .get_current_project()
+                        .is_some_and(ReleaseUnitItem::bump_skipped)
+                    {
+                        self.loading_changelog = true;
+                        self.start_background_changelog_generation();
+                        return true;
+                    }
+                }
+                if unit_index + 1 < self.selected_count() {
+                    self.step = WizardStep::UnitConfig {
+                        unit_index: unit_index + 1,
+                    };
+                    self.show_changelog = false;
+                    self.bump_list_state.select(Some(0));
+                    self.changelog_scroll_offset = 0;
                 } else {
-                    if *unit_index + 1 < self.selected_count() {
-                        self.step = WizardStep::UnitConfig {
-                            unit_index: unit_index + 1,
-                        };
-                        self.show_changelog = false;
-                        self.bump_list_state.select(Some(0));
-                        self.changelog_scroll_offset = 0;
+                    // The conflicts step only shows up when the chosen
+                    // bumps break an internal dependency requirement.
+                    self.refresh_dep_conflicts();
+                    self.refresh_release_edits();
+                    self.step = if self.dep_conflicts.is_empty() {
+                        WizardStep::Confirmation
                     } else {
-                        self.step = WizardStep::Confirmation;
-                    }
-                    true
+                        WizardStep::DepConflicts
+                    };
                 }
+                true
+            }
+            WizardStep::DepConflicts => {
+                self.refresh_release_edits();
+                self.step = WizardStep::Confirmation;
+                true
             }
             WizardStep::Confirmation => false,
         }
     }
 
     fn prev_step(&mut self) -> bool {
+        self.reset_commit_view();
         match &self.step {
             WizardStep::ReleaseUnitSelection => false,
             WizardStep::UnitConfig { unit_index } => {
@@ -435,24 +641,84 @@ impl WizardState {
                     self.step = WizardStep::ReleaseUnitSelection;
                     true
                 } else {
-                    self.step = WizardStep::UnitConfig {
-                        unit_index: unit_index - 1,
-                    };
-                    self.show_changelog = true;
+                    self.revisit_unit(unit_index - 1);
                     true
                 }
             }
-            WizardStep::Confirmation => {
+            WizardStep::Confirmation if !self.dep_conflicts.is_empty() => {
+                self.step = WizardStep::DepConflicts;
+                true
+            }
+            WizardStep::DepConflicts | WizardStep::Confirmation => {
                 let last_idx = self.selected_count().saturating_sub(1);
-                self.step = WizardStep::UnitConfig {
-                    unit_index: last_idx,
-                };
-                self.show_changelog = true;
+                self.revisit_unit(last_idx);
                 true
             }
         }
     }
 
+    /// The choice under the cursor in the current unit's bump list.
+    fn highlighted_bump(&self) -> Option<BumpChoice> {
+        let selected = self.bump_list_state.selected()?;
+        let project = self.get_current_project()?;
+        project.bump_choices().get(selected).copied()
+    }
+
+    /// Keys while the custom version input is open. Enter accepts a valid
+    /// version and moves on like any other choice.
+    fn handle_key_custom_input(&mut self, key: KeyCode) -> bool {
+        let Some(input) = self.custom_input.as_mut() else {
+            return false;
+        };
+        match key {
+            KeyCode::Char(c) => input.push(c),
+            KeyCode::Backspace => {
+                input.pop();
+            }
+            KeyCode::Esc => {
+                self.custom_input = None;
+                self.custom_error = None;
+                return true;
+            }
+            KeyCode::Enter => {
+                let typed = input.clone();
+                let Some(project) = self.get_current_project() else {
+                    return false;
+                };
+                match validate_custom_version(&project.candidate, &typed) {
+                    Ok(version) => {
+                        if let Some(p) = self.get_current_project_mut() {
+                            p.custom_version = Some(version);
+                        }
+                        self.custom_input = None;
+                        self.custom_error = None;
+                        return self.next_step();
+                    }
+                    Err(e) => self.custom_error = Some(e.to_string()),
+                }
+                return false;
+            }
+            _ => return false,
+        }
+        self.custom_error = None;
+        false
+    }
+
+    /// Step back onto `unit_index`: its changelog preview, or its bump
+    /// list when it was skipped and so has no changelog.
+    fn revisit_unit(&mut self, unit_index: usize) {
+        self.step = WizardStep::UnitConfig { unit_index };
+        self.show_changelog = true;
+        if let Some(project) = self.get_current_project().filter(|p| p.bump_skipped()) {
+            let idx = project
+                .bump_choices()
+                .iter()
+                .position(|c| *c == BumpChoice::Skip);
+            self.show_changelog = false;
+            self.bump_list_state.select(idx);
+        }
+    }
+
     fn selected_projects(&self) -> Vec<&ReleaseUnitItem> {
         self.units.iter().filter(|p| p.selected).collect()
     }
@@ -505,7 +771,45 @@ impl WizardState {
     }
 
     fn handle_key_unit_config(&mut self, key: KeyCode) -> bool {
+        if self.commit_detail.is_some() {
+            match key {
+                KeyCode::Up => {
+                    self.commit_detail_scroll = self.commit_detail_scroll.saturating_sub(1)
+                }
+                KeyCode::Down => {
+                    self.commit_detail_scroll = self.commit_detail_scroll.saturating_add(1)
+                }
+                KeyCode::Enter | KeyCode::Esc | KeyCode::Backspace => self.commit_detail = None,
+                _ => {}
+            }
+            return false;
+        }
+
+        if let Some(cursor) = self.commit_cursor {
+            let n_commits = self
+                .get_current_project()
+                .map(|p| p.commits().len())
+                .unwrap_or(0);
+            match key {
+                KeyCode::Up => self.commit_cursor = Some(cursor.saturating_sub(1)),
+                KeyCode::Down if cursor + 1 < n_commits => self.commit_cursor = Some(cursor + 1),
+                KeyCode::Enter => self.open_commit_detail(),
+                KeyCode::Left | KeyCode::Esc | KeyCode::Tab => self.commit_cursor = None,
+                _ => {}
+            }
+            return false;
+        }
+
         match key {
+            KeyCode::Right if !self.show_changelog => {
+                let has_commits = self
+                    .get_current_project()
+                    .is_some_and(|p| !p.commits().is_empty());
+                if has_commits {
+                    self.commit_cursor = Some(0);
+                }
+                false
+            }
             KeyCode::Tab => {
                 self.show_changelog = !self.show_changelog;
                 if self.show_changelog {
@@ -518,7 +822,8 @@ impl WizardState {
                     }
                 } else if let Some(project) = self.get_current_project() {
                     if let Some(chosen) = project.chosen_bump {
-                        let idx = BumpChoice::all()
+                        let idx = project
+                            .bump_choices()
                             .iter()
                             .position(|s| *s == chosen)
                             .unwrap_or(0);
@@ -553,19 +858,60 @@ impl WizardState {
             }
             KeyCode::Down => {
                 if let Some(selected) = self.bump_list_state.selected() {
-                    let strategies = BumpChoice::all();
-                    if selected < strategies.len() - 1 {
+                    let strategies = self
+                        .get_current_project()
+                        .map(ReleaseUnitItem::bump_choices)
+                        .unwrap_or_default();
+                    if selected + 1 < strategies.len() {
                         self.bump_list_state.select(Some(selected + 1));
                     }
                 }
                 false
             }
+            KeyCode::Enter
+                if !self.show_changelog && self.highlighted_bump() == Some(BumpChoice::Custom) =>
+            {
+                let typed = self
+                    .get_current_project()
+                    .and_then(|p| p.custom_version.clone())
+                    .unwrap_or_default();
+                self.custom_input = Some(typed);
+                true
+            }
             KeyCode::Enter => self.next_step(),
             KeyCode::Backspace | KeyCode::Esc => self.prev_step(),
             _ => false,
         }
     }
 
+    fn handle_key_dep_conflicts(&mut self, key: KeyCode) -> bool {
+        let Some(selected) = self.conflict_list_state.selected() else {
+            return match key {
+                KeyCode::Enter => self.next_step(),
+                KeyCode::Backspace | KeyCode::Esc => self.prev_step(),
+                _ => false,
+            };
+        };
+        match key {
+            KeyCode::Up if selected > 0 => self.conflict_list_state.select(Some(selected - 1)),
+            KeyCode::Down if selected + 1 < self.dep_conflicts.len() => {
+                self.conflict_list_state.select(Some(selected + 1))
+            }
+            KeyCode::Right | KeyCode::Char(' ') => {
+                let resolution = &mut self.dep_conflicts[selected].1;
+                *resolution = resolution.next();
+            }
+            KeyCode::Left => {
+                let resolution = &mut self.dep_conflicts[selected].1;
+                *resolution = resolution.prev();
+            }
+            KeyCode::Enter => return self.next_step(),
+            KeyCode::Backspace | KeyCode::Esc => return self.prev_step(),
+            _ => {}
+        }
+        false
+    }
+
     fn handle_key_confirmation(&mut self, key: KeyCode) -> (bool, bool) {
         match key {
             KeyCode::Enter => (false, true),
@@ -575,20 +921,26 @@ impl WizardState {
     }
 }
 
-pub fn run_with_overrides_and_decisions(
+pub(super) fn run_with_overrides_and_decisions(
     project_overrides: Option<Vec<String>>,
+    project_list: Option<super::ProjectList>,
     decisions: Vec<crate::core::bump_source::BumpDecision>,
+    force: bool,
+    branch: Option<String>,
+    empty_release: Option<EmptyRelease>,
 ) -> Result<i32> {
     info!("starting interactive TUI wizard for release preparation");
 
     let mut sess = AppBuilder::new()?
         .fetch_tags_first(true)
+        .maintenance_branch(branch)
         .initialize()
         .context("could not initialize app and project graph")?;
     // Snapshot groups before ctx takes a mutable borrow on sess.
     let groups = sess.graph().groups().clone();
 
-    let mut ctx = PrepareContext::initialize(&mut sess, true)?;
+    let mut ctx = PrepareContext::initialize(&mut sess, true, force)?;
+    ctx.empty_release = empty_release;
     ctx.discover_projects()?;
 
     if !ctx.has_candidates() {
@@ -619,11 +971,22 @@ pub fn run_with_overrides_and_decisions(
         .collect();
 
     let mut projects = projects;
-    // Precedence: external decisions feed in first; explicit
-    // `--project name:bump` CLI overrides win on top. The wizard then
-    // shows the resulting `chosen_bump` so the user can still change it
-    // interactively before confirming.
+    // Precedence: external decisions feed in first, then the
+    // `--projects-from-file` list; explicit `--project name:bump` CLI
+    // overrides win on top. The wizard then shows the resulting
+    // `chosen_bump` so the user can still change it interactively before
+    // confirming.
     apply_decisions_to_items(&mut projects, &decisions)?;
+    if let Some(list) = &project_list {
+        let names: Vec<String> = projects.iter().map(|p| p.name().to_string()).collect();
+        list.check_names(&names)?;
+        for project in &mut projects {
+            if !list.contains(project.name()) {
+                project.chosen_bump = Some(BumpChoice::Skip);
+            }
+        }
+        apply_project_overrides_to_items(&mut projects, &list.overrides)?;
+    }
     if let Some(ref overrides) = project_overrides {
         apply_project_overrides_to_items(&mut projects, overrides)?;
     }
@@ -632,10 +995,13 @@ pub fn run_with_overrides_and_decisions(
         projects,
         ctx.changelog_config.clone(),
         ctx.bump_config.clone(),
+        ctx.resolve_workdir(RepoPathBuf::new(b"").as_ref()),
+        ctx.conflict_planner(),
+        ctx.pending_releases.clone(),
     )?;
 
-    let selected_items = match wizard_result {
-        Some(items) => items,
+    let (selected_items, resolutions) = match wizard_result {
+        Some(result) => result,
         None => {
             info!("release preparation cancelled by user");
             ctx.cleanup();
@@ -651,11 +1017,12 @@ pub fn run_with_overrides_and_decisions(
         return Ok(0);
     }
 
-    let selections: Vec<ReleaseUnitSelection> = selected_items
+    let mut selections: Vec<ReleaseUnitSelection> = selected_items
         .into_iter()
         .map(|item| ReleaseUnitSelection {
+            bump_choice: item.effective_bump(),
             candidate: item.candidate,
-            bump_choice: item.chosen_bump.unwrap_or(BumpChoice::Auto),
+            custom_version: item.custom_version,
             cached_changelog: item.cached_changelog,
         })
         .collect();
@@ -668,6 +1035,7 @@ pub fn run_with_overrides_and_decisions(
         ctx.cleanup();
         return Err(e);
     }
+    ctx.resolve_dep_conflicts(&mut selections, &resolutions);
 
     println!();
     let mut spinner = spinoff::Spinner::new(
@@ -714,18 +1082,27 @@ fn print_no_changes_message() {
     println!();
 }
 
+/// The units the user confirmed, and the resolution picked for each
+/// dependency conflict their bumps cause.
+type WizardOutcome = (Vec<ReleaseUnitItem>, Vec<(DepConflict, ConflictResolution)>);
+
 fn run_wizard_ui(
     projects: Vec<ReleaseUnitItem>,
     changelog_config: ChangelogConfiguration,
     bump_config: BumpConfiguration,
-) -> Result<Option<Vec<ReleaseUnitItem>>> {
+    repo_root: PathBuf,
+    conflict_planner: ConflictPlanner,
+    pending_releases: Vec<PendingRelease>,
+) -> Result<Option<WizardOutcome>> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let mut state = WizardState::new(projects, changelog_config, bump_config);
+    let mut state = WizardState::new(projects, changelog_config, bump_config, repo_root);
+    state.conflict_planner = conflict_planner;
+    state.pending_releases = pending_releases;
     let result = run_app(&mut terminal, &mut state);
 
     disable_raw_mode()?;
@@ -738,7 +1115,7 @@ fn run_wizard_ui(
 
     if result? {
         let selected = state.units.into_iter().filter(|p| p.selected).collect();
-        Ok(Some(selected))
+        Ok(Some((selected, state.dep_conflicts)))
     } else {
         Ok(None)
     }
@@ -749,7 +1126,10 @@ fn run_app(
     state: &mut WizardState,
 ) -> Result<bool> {
     loop {
-        terminal.draw(|f| render::ui(f, state))?;
+        terminal.draw(|f| {
+            render::ui(f, state);
+            theme::apply_color_mode(f);
+        })?;
 
         if state.is_loading() {
             state.check_loading_complete();
@@ -777,6 +1157,12 @@ fn run_app(
                 kind: KeyEventKind::Press,
                 ..
             }) => {
+                // Typed text goes to the input, not the global shortcuts.
+                if state.custom_input.is_some() {
+                    state.handle_key_custom_input(code);
+                    continue;
+                }
+
                 if code == KeyCode::Char('q') || code == KeyCode::Char('c') {
                     return Ok(false);
                 }
@@ -799,6 +1185,7 @@ fn run_app(
                 let result = match &state.step {
                     WizardStep::ReleaseUnitSelection => state.handle_key_unit_selection(code),
                     WizardStep::UnitConfig { .. } => state.handle_key_unit_config(code),
+                    WizardStep::DepConflicts => state.handle_key_dep_conflicts(code),
                     WizardStep::Confirmation => {
                         let (step_changed, confirmed) = state.handle_key_confirmation(code);
                         if confirmed {
@@ -829,6 +1216,42 @@ fn run_app(
 
 mod render;
 
+/// Why history analysis counted `commit` for the unit, checked in the same
+/// order as `Repository::analyze_histories`: a conventional-commit scope
+/// naming a unit wins, otherwise the files it touched.
+fn attribution_reason(
+    commit: &CommitInspection,
+    unit_name: &str,
+    unit_prefix: &str,
+    unit_names: &[String],
+) -> String {
+    if let Some(scope) = extract_scope(&commit.summary) {
+        if let Some(matched) = ScopeMatcher::default().find_matching_project(&scope, unit_names) {
+            if matched == unit_name {
+                return format!("scope `{scope}` matches {unit_name}");
+            }
+        }
+    }
+
+    let touched: Vec<String> = commit
+        .changed_files
+        .iter()
+        .map(|f| f.escaped())
+        .filter(|f| f.starts_with(unit_prefix))
+        .collect();
+    match touched.as_slice() {
+        [] => format!("changes files {unit_name}'s path rules include"),
+        _ if unit_prefix.is_empty() => {
+            format!(
+                "changes {} file(s) in the repository root unit",
+                touched.len()
+            )
+        }
+        [one] => format!("changes {one} under {unit_prefix}/"),
+        many => format!("changes {} files under {unit_prefix}/", many.len()),
+    }
+}
+
 fn parse_existing_changelog(path: &Path) -> Option<String> {
     let content = std::fs::read_to_string(path).ok()?;
 
@@ -945,6 +1368,8 @@ fn apply_project_overrides_to_items(
 mod tests {
     use super::*;
     use crate::core::bump::BumpRecommendation;
+    use crate::core::release_line::ReleaseLine;
+    use crate::core::version::Version;
     use crate::core::wire::known::Ecosystem;
 
     /// Build a minimal `ReleaseUnitItem` for layout tests. We don't need
@@ -957,13 +1382,20 @@ mod tests {
                 name: name.into(),
                 prefix: String::new(),
                 current_version: "0.1.0".into(),
+                version: Version::Semver(semver::Version::new(0, 1, 0)),
                 commits: Vec::new(),
                 commit_count: 0,
                 suggested_bump: BumpRecommendation::Patch,
+                bump_reasons: Vec::new(),
+                api_bump: None,
                 ecosystem: Ecosystem::classify("npm"),
+                manual_bump_from: None,
+                release_line: None,
+                scopes: Vec::new(),
             },
             selected: true,
             chosen_bump: None,
+            custom_version: None,
             cached_changelog: None,
             existing_changelog: String::new(),
             group_id: group_id.map(str::to_string),
@@ -1060,4 +1492,199 @@ mod tests {
         let rows = compute_display_rows(&[]);
         assert!(rows.is_empty());
     }
+
+    /// ←/→ cycle the resolution under the cursor; skipping a grouped
+    /// dependee leaves its whole group out, and Confirmation steps back
+    /// into the conflicts.
+    #[test]
+    fn hand_bumped_units_offer_and_default_to_as_is() {
+        let mut manual = item("core", None);
+        manual.candidate.manual_bump_from = Some("0.0.9".into());
+        assert_eq!(manual.bump_choices()[0], BumpChoice::AsIs);
+        assert_eq!(manual.effective_bump(), BumpChoice::AsIs);
+        assert_eq!(manual.effective_bump_str(), "manual");
+
+        let regular = item("app", None);
+        assert!(!regular.bump_choices().contains(&BumpChoice::AsIs));
+        assert_eq!(regular.effective_bump(), BumpChoice::Auto);
+    }
+
+    #[test]
+    fn skip_choice_moves_on_without_a_changelog() {
+        let cfg = crate::core::embed::EmbeddedConfig::parse().unwrap();
+        let units = vec![item("core", None), item("app", None)];
+        let mut state = WizardState::new(units, cfg.changelog, cfg.bump, PathBuf::new());
+        state.step = WizardStep::UnitConfig { unit_index: 0 };
+        let skip = BumpChoice::all().len() - 1;
+        state.bump_list_state.select(Some(skip));
+
+        assert!(state.next_step());
+        assert_eq!(state.step, WizardStep::UnitConfig { unit_index: 1 });
+        assert!(!state.loading_changelog);
+        assert!(state.units[0].bump_skipped());
+        assert_eq!(state.units[0].effective_bump_str(), "no bump");
+        assert!(!state.units[1].bump_skipped());
+
+        // Back on the skipped unit, its bump list shows the skip choice.
+        assert!(state.prev_step());
+        assert_eq!(state.step, WizardStep::UnitConfig { unit_index: 0 });
+        assert!(!state.show_changelog);
+        assert_eq!(state.bump_list_state.selected(), Some(skip));
+    }
+
+    #[test]
+    fn previews_bump_in_the_units_own_version_scheme() {
+        let mut unit = item("pyproj", None);
+        unit.candidate.current_version = "1.2".into();
+        unit.candidate.version = Version::Pep440("1.2".parse().unwrap());
+        assert_eq!(unit.next_version(BumpChoice::Auto), "1.2.1");
+        assert_eq!(unit.next_version(BumpChoice::Minor), "1.3.0");
+        assert_eq!(unit.next_version(BumpChoice::Major), "2.0.0");
+        assert_eq!(unit.next_version(BumpChoice::Skip), "1.2");
+
+        // No custom version typed yet: the preview stays put.
+        assert_eq!(unit.next_version(BumpChoice::Custom), "1.2");
+        unit.custom_version = Some("1.4.0".into());
+        assert_eq!(unit.next_version(BumpChoice::Custom), "1.4.0");
+    }
+
+    #[test]
+    fn custom_choice_takes_a_validated_version() {
+        let cfg = crate::core::embed::EmbeddedConfig::parse().unwrap();
+        let mut core = item("core", None);
+        core.cached_changelog = Some("## 1.0.0".into());
+        let mut state = WizardState::new(vec![core], cfg.changelog, cfg.bump, PathBuf::new());
+        state.step = WizardStep::UnitConfig { unit_index: 0 };
+        let custom = BumpChoice::all()
+            .iter()
+            .position(|c| *c == BumpChoice::Custom);
+        state.bump_list_state.select(custom);
+
+        state.handle_key_unit_config(KeyCode::Enter);
+        assert_eq!(state.custom_input.as_deref(), Some(""));
+
+        let type_text = |state: &mut WizardState, text: &str| {
+            while state.custom_input.as_ref().is_some_and(|i| !i.is_empty()) {
+                state.handle_key_custom_input(KeyCode::Backspace);
+            }
+            for c in text.chars() {
+                state.handle_key_custom_input(KeyCode::Char(c));
+            }
+            state.handle_key_custom_input(KeyCode::Enter);
+        };
+        type_text(&mut state, "0.0.9");
+        assert!(state.custom_error.is_some(), "not above 0.1.0");
+        type_text(&mut state, "next");
+        assert!(state.custom_error.is_some(), "not semver");
+        assert_eq!(state.units[0].custom_version, None);
+
+        type_text(&mut state, "v1.0.0");
+        assert_eq!(state.custom_input, None);
+        assert_eq!(state.custom_error, None);
+        assert_eq!(state.units[0].effective_bump(), BumpChoice::Custom);
+        assert_eq!(state.units[0].planned_bump(), "1.0.0");
+        assert!(state.show_changelog);
+    }
+
+    #[test]
+    fn warns_when_the_api_diff_requires_a_bigger_bump() {
+        let mut unit = item("core", None);
+        assert_eq!(unit.api_bump_warning(), None);
+        unit.candidate.api_bump = Some(BumpRecommendation::Patch);
+        assert_eq!(unit.api_bump_warning(), None);
+        unit.candidate.api_bump = Some(BumpRecommendation::Major);
+        assert_eq!(
+            unit.api_bump_warning().as_deref(),
+            Some("commits say patch, API diff says MAJOR")
+        );
+    }
+
+    #[test]
+    fn maintenance_branches_only_offer_patch_releases() {
+        let mut unit = item("core", None);
+        unit.candidate.current_version = "1.2.0".into();
+        unit.candidate.release_line = ReleaseLine::from_branch("release/1.x");
+        let choices = unit.bump_choices();
+        assert!(!choices.contains(&BumpChoice::Major));
+        assert!(!choices.contains(&BumpChoice::Minor));
+        assert!(choices.contains(&BumpChoice::Patch));
+
+        assert!(validate_custom_version(&unit.candidate, "1.3.0").is_err());
+        assert_eq!(
+            validate_custom_version(&unit.candidate, "1.2.4").unwrap(),
+            "1.2.4"
+        );
+    }
+
+    #[test]
+    fn dep_conflict_step_cycles_resolutions_and_skips_groups() {
+        let cfg = crate::core::embed::EmbeddedConfig::parse().unwrap();
+        let mut units = vec![
+            item("core", Some("bundle")),
+            item("core-wasm", Some("bundle")),
+            item("app", None),
+        ];
+        for (i, u) in units.iter_mut().enumerate() {
+            u.candidate.ident = i;
+        }
+        let mut state = WizardState::new(units, cfg.changelog, cfg.bump, PathBuf::new());
+        let conflict = DepConflict {
+            dependent: 2,
+            dependent_name: "app".into(),
+            dependee: 0,
+            dependee_name: "core".into(),
+            requirement: "=0.1.0".into(),
+            old_version: "0.1.0".into(),
+            new_version: "0.1.1".into(),
+            widened: ">=0.1.0, <0.2.0".into(),
+            pinned: "=0.1.1".into(),
+        };
+        state.dep_conflicts = vec![(conflict, ConflictResolution::WidenRange)];
+        state.conflict_list_state.select(Some(0));
+        state.step = WizardStep::DepConflicts;
+
+        state.handle_key_dep_conflicts(KeyCode::Right);
+        assert_eq!(state.dep_conflicts[0].1, ConflictResolution::PinNewVersion);
+        assert!(!state.is_skipped(&state.units[0]));
+
+        state.handle_key_dep_conflicts(KeyCode::Right);
+        assert_eq!(state.dep_conflicts[0].1, ConflictResolution::SkipProject);
+        assert!(state.is_skipped(&state.units[0]));
+        assert!(state.is_skipped(&state.units[1]));
+        assert!(!state.is_skipped(&state.units[2]));
+
+        assert!(state.handle_key_dep_conflicts(KeyCode::Enter));
+        assert_eq!(state.step, WizardStep::Confirmation);
+        assert!(state.prev_step());
+        assert_eq!(state.step, WizardStep::DepConflicts);
+    }
+
+    /// The detail popup explains attribution the way history analysis
+    /// decided it: scope first, then the files under the unit's prefix.
+    #[test]
+    fn attribution_reason_prefers_scope_over_paths() {
+        let names = vec!["core".to_string(), "web".to_string()];
+        let commit = |summary: &str, files: &[&str]| CommitInspection {
+            id: "0".repeat(40),
+            summary: summary.to_string(),
+            body: String::new(),
+            trailers: Vec::new(),
+            changed_files: files
+                .iter()
+                .map(|f| RepoPathBuf::new(f.as_bytes()))
+                .collect(),
+        };
+
+        let scoped = commit("fix(core): handle ✨ names", &["web/src/a.rs"]);
+        assert_eq!(
+            attribution_reason(&scoped, "core", "crates/core", &names),
+            "scope `core` matches core"
+        );
+
+        let by_path = commit("fix: typo", &["crates/core/src/lib.rs", "README.md"]);
+        assert_eq!(
+            attribution_reason(&by_path, "core", "crates/core", &names),
+            "changes crates/core/src/lib.rs under crates/core/"
+        );
+    }
 }
@@ -10,32 +10,38 @@ use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span, Text},
-    widgets::{Block, Borders, List, ListItem, Padding, Paragraph, Wrap},
+    widgets::{Block, Borders, Clear, List, ListItem, Padding, Paragraph, Wrap},
     Frame,
 };
 
 use crate::core::{
-    bump::BumpRecommendation,
+    bump::{BumpReason, BumpRecommendation},
     changelog::Commit,
     ui::{
-        markdown,
+        glyphs, markdown,
         release_unit_view::{
             BumpHint, ReleaseUnitView, RenderMode, ResolvedEntry, ViewContext, ViewLayout,
         },
+        text,
         utils::centered_rect,
     },
     wire::known::Ecosystem,
-    workflow::BumpChoice,
+    workflow::{BumpChoice, ConflictResolution},
 };
+use crate::utils::theme;
 
-use super::{
-    calculate_major_version, calculate_minor_version, calculate_next_version,
-    calculate_patch_version, WizardState, WizardStep,
-};
+use super::{WizardState, WizardStep};
+
+/// Commits listed under the detected bump in the detail panel.
+const MAX_BUMP_REASONS: usize = 5;
 
 pub(super) fn ui(f: &mut Frame, state: &mut WizardState) {
     render_step(f, f.area(), state);
 
+    if state.commit_detail.is_some() {
+        render_commit_detail(f, state);
+    }
+
     if state.show_help {
         render_help_popup(f, state);
     }
@@ -53,6 +59,7 @@ fn render_step(f: &mut Frame, area: Rect, state: &mut WizardState) {
                 render_project_bump_strategy(f, area, state);
             }
         }
+        WizardStep::DepConflicts => render_dep_conflicts(f, area, state),
         WizardStep::Confirmation => render_confirmation(f, area, state),
     }
 }
@@ -173,23 +180,33 @@ fn bump_hint_from(rec: BumpRecommendation) -> BumpHint {
 }
 
 fn render_project_bump_strategy(f: &mut Frame, area: Rect, state: &mut WizardState) {
-    let strategies = BumpChoice::all();
-
     let project = match state.get_current_project() {
         Some(p) => p,
         None => return,
     };
+    let strategies = project.bump_choices();
 
     let project_name = project.name().to_string();
     let current_version = project.current_version().to_string();
     let suggested_bump = project.suggested_bump();
+    let bump_reasons = project.bump_reasons().to_vec();
     let commits = project.commits().to_vec();
+    let unknown_scopes = project.unknown_scopes();
+    let api_bump_warning = project.api_bump_warning();
+    let next_versions: Vec<String> = strategies
+        .iter()
+        .map(|strategy| match strategy {
+            BumpChoice::Skip => "not released".to_string(),
+            BumpChoice::Custom if project.custom_version.is_none() => "type a version".to_string(),
+            _ => project.next_version(*strategy),
+        })
+        .collect();
 
     let selected_index = state.bump_list_state.selected().unwrap_or(0);
     let selected_strategy = strategies
         .get(selected_index)
         .copied()
-        .unwrap_or(BumpChoice::Auto);
+        .unwrap_or_else(|| project.effective_bump());
 
     let current_project_idx = match &state.step {
         WizardStep::UnitConfig { unit_index } => *unit_index + 1,
@@ -222,26 +239,36 @@ fn render_project_bump_strategy(f: &mut Frame, area: Rect, state: &mut WizardSta
         ])
         .split(inner_area);
 
-    let header_lines = vec![
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("📦 ", Style::default()),
-            Span::styled(
-                project_name.clone(),
-                Style::default()
-                    .fg(Color::White)
-                    .add_modifier(Modifier::BOLD),
-            ),
-            Span::styled(
-                format!("  v{}", current_version),
-                Style::default().fg(Color::Gray),
-            ),
-            Span::styled(
-                format!("  ({} commits)", commits.len()),
-                Style::default().fg(Color::Gray),
-            ),
-        ]),
+    let mut header_spans = vec![
+        Span::styled("📦 ", Style::default()),
+        Span::styled(
+            project_name.clone(),
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!("  v{}", current_version),
+            Style::default().fg(Color::Gray),
+        ),
+        Span::styled(
+            format!("  ({} commits)", commits.len()),
+            Style::default().fg(Color::Gray),
+        ),
     ];
+    if !unknown_scopes.is_empty() {
+        header_spans.push(Span::styled(
+            format!("  ⚠ unknown scopes: {}", unknown_scopes.join(", ")),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+    if let Some(warning) = api_bump_warning {
+        header_spans.push(Span::styled(
+            format!("  ⚠ {warning}"),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+    let header_lines = vec![Line::from(""), Line::from(header_spans)];
     let header = Paragraph::new(header_lines).alignment(ratatui::layout::Alignment::Center);
     f.render_widget(header, outer_chunks[0]);
 
@@ -255,20 +282,18 @@ fn render_project_bump_strategy(f: &mut Frame, area: Rect, state: &mut WizardSta
         .enumerate()
         .map(|(idx, strategy)| {
             let is_selected = idx == selected_index;
-            let next_ver = match strategy {
-                BumpChoice::Auto => calculate_next_version(&current_version, suggested_bump),
-                BumpChoice::Major => calculate_major_version(&current_version),
-                BumpChoice::Minor => calculate_minor_version(&current_version),
-                BumpChoice::Patch => calculate_patch_version(&current_version),
-            };
+            let next_ver = &next_versions[idx];
             let (icon, color) = match strategy {
                 BumpChoice::Auto => ("🔄", Color::Cyan),
-                BumpChoice::Major => ("🔴", Color::Red),
-                BumpChoice::Minor => ("🟡", Color::Yellow),
-                BumpChoice::Patch => ("🟢", Color::Green),
+                BumpChoice::Major => (glyphs::bump("MAJOR"), Color::Red),
+                BumpChoice::Minor => (glyphs::bump("MINOR"), Color::Yellow),
+                BumpChoice::Patch => (glyphs::bump("PATCH"), Color::Green),
+                BumpChoice::Custom => ("✏", Color::Blue),
+                BumpChoice::AsIs => ("✋", Color::Magenta),
+                BumpChoice::Skip => ("⏭", Color::DarkGray),
             };
             let lines = vec![Line::from(vec![
-                Span::styled(format!(" {} ", icon), Style::default()),
+                Span::styled(format!(" {}", text::fit(icon, 3)), Style::default()),
                 Span::styled(
                     strategy.as_str(),
                     if is_selected {
@@ -283,7 +308,7 @@ fn render_project_bump_strategy(f: &mut Frame, area: Rect, state: &mut WizardSta
                 ),
             ])];
             let style = if is_selected {
-                Style::default().bg(Color::Rgb(40, 40, 50))
+                theme::row_highlight()
             } else {
                 Style::default()
             };
@@ -319,12 +344,34 @@ fn render_project_bump_strategy(f: &mut Frame, area: Rect, state: &mut WizardSta
             )
             .wrap(Wrap { trim: true });
         f.render_widget(loading_panel, main_chunks[1]);
+    } else if let Some(input) = &state.custom_input {
+        let input_content = build_custom_input_panel(input, state.custom_error.as_deref());
+        let input_panel = Paragraph::new(input_content)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Blue))
+                    .title(Span::styled(
+                        " Custom Version ",
+                        Style::default().fg(Color::Blue),
+                    )),
+            )
+            .wrap(Wrap { trim: true });
+        f.render_widget(input_panel, main_chunks[1]);
     } else {
+        let next_version = strategies
+            .iter()
+            .position(|s| *s == selected_strategy)
+            .map_or_else(|| current_version.clone(), |i| next_versions[i].clone());
         let detail_content = build_detail_panel(
             &selected_strategy,
             &current_version,
+            next_version,
             suggested_bump,
+            &bump_reasons,
             &commits,
+            main_chunks[1].width.saturating_sub(2) as usize,
+            state.commit_cursor,
         );
 
         let detail_panel = Paragraph::new(detail_content)
@@ -358,6 +405,8 @@ fn render_project_bump_strategy(f: &mut Frame, area: Rect, state: &mut WizardSta
             Span::styled(" select  ", Style::default().fg(Color::Gray)),
             Span::styled("Tab", Style::default().fg(Color::Cyan)),
             Span::styled(" preview changelog  ", Style::default().fg(Color::Gray)),
+            Span::styled("→", Style::default().fg(Color::Cyan)),
+            Span::styled(" commits  ", Style::default().fg(Color::Gray)),
             Span::styled("Enter", Style::default().fg(Color::Green)),
             Span::styled(" next  ", Style::default().fg(Color::Gray)),
             Span::styled("Esc", Style::default().fg(Color::Yellow)),
@@ -419,21 +468,51 @@ fn build_loading_panel(state: &WizardState, commits: &[Commit]) -> Text<'static>
     Text::from(lines)
 }
 
+fn build_custom_input_panel(input: &str, error: Option<&str>) -> Text<'static> {
+    let mut lines: Vec<Line> = Vec::new();
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled("Version: ", Style::default().fg(Color::Gray)),
+        Span::styled(
+            format!("{input}▏"),
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        ),
+    ]));
+    lines.push(Line::from(""));
+    if let Some(error) = error {
+        lines.push(Line::from(Span::styled(
+            format!("✗ {error}"),
+            Style::default().fg(Color::Red),
+        )));
+        lines.push(Line::from(""));
+    }
+    lines.push(Line::from(Span::styled(
+        "   Enter to use this version, Esc to go back",
+        Style::default().fg(Color::Gray),
+    )));
+
+    Text::from(lines)
+}
+
+#[expect(
+    clippy::too_many_arguments,
+    reason = "the panel shows one field of the unit per argument"
+)]
 fn build_detail_panel(
     strategy: &BumpChoice,
     current_version: &str,
+    next_version: String,
     suggested_bump: BumpRecommendation,
+    bump_reasons: &[BumpReason],
     commits: &[Commit],
+    width: usize,
+    cursor: Option<usize>,
 ) -> Text<'static> {
     let mut lines: Vec<Line> = Vec::new();
 
-    let next_version = match strategy {
-        BumpChoice::Auto => calculate_next_version(current_version, suggested_bump),
-        BumpChoice::Major => calculate_major_version(current_version),
-        BumpChoice::Minor => calculate_minor_version(current_version),
-        BumpChoice::Patch => calculate_patch_version(current_version),
-    };
-
     lines.push(Line::from(vec![
         Span::styled("Version: ", Style::default().fg(Color::Gray)),
         Span::styled(
@@ -462,6 +541,24 @@ fn build_detail_panel(
                 Span::styled("Detected: ", Style::default().fg(Color::Gray)),
                 Span::styled(bump_name.to_string(), Style::default().fg(Color::Cyan)),
             ]));
+            for reason in bump_reasons.iter().take(MAX_BUMP_REASONS) {
+                lines.push(Line::from(vec![
+                    Span::styled(
+                        format!("  {} ", reason.commit),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Span::styled(
+                        text::truncate(&reason.subject, width.saturating_sub(10)).into_owned(),
+                        Style::default().fg(Color::Gray),
+                    ),
+                ]));
+            }
+            if bump_reasons.len() > MAX_BUMP_REASONS {
+                lines.push(Line::from(Span::styled(
+                    format!("  …and {} more", bump_reasons.len() - MAX_BUMP_REASONS),
+                    Style::default().fg(Color::DarkGray),
+                )));
+            }
         }
         BumpChoice::Major => {
             lines.push(Line::from(Span::styled(
@@ -493,6 +590,36 @@ fn build_detail_panel(
                 Style::default().fg(Color::Gray),
             )));
         }
+        BumpChoice::Custom => {
+            lines.push(Line::from(Span::styled(
+                "✎ Custom Version",
+                Style::default().fg(Color::Blue),
+            )));
+            lines.push(Line::from(Span::styled(
+                "  Releases exactly the version you type",
+                Style::default().fg(Color::Gray),
+            )));
+        }
+        BumpChoice::AsIs => {
+            lines.push(Line::from(Span::styled(
+                "✓ Manual Release",
+                Style::default().fg(Color::Magenta),
+            )));
+            lines.push(Line::from(Span::styled(
+                "  Version already bumped by hand; only the changelog and tag are created",
+                Style::default().fg(Color::Gray),
+            )));
+        }
+        BumpChoice::Skip => {
+            lines.push(Line::from(Span::styled(
+                "⏭ Skip This Project",
+                Style::default().fg(Color::Yellow),
+            )));
+            lines.push(Line::from(Span::styled(
+                "  Left out of this release; other selections are kept",
+                Style::default().fg(Color::Gray),
+            )));
+        }
     }
 
     lines.push(Line::from(""));
@@ -529,7 +656,10 @@ fn build_detail_panel(
     if revert_count > 0 {
         lines.push(Line::from(vec![
             Span::styled("  revert:    ", Style::default().fg(Color::Yellow)),
-            Span::styled(format!("{}", revert_count), Style::default().fg(Color::Yellow)),
+            Span::styled(
+                format!("{}", revert_count),
+                Style::default().fg(Color::Yellow),
+            ),
         ]));
     }
     if other_count > 0 {
@@ -545,13 +675,13 @@ fn build_detail_panel(
         Style::default().add_modifier(Modifier::BOLD),
     )));
 
-    for (i, commit) in commits.iter().take(8).enumerate() {
+    // Keep the cursor inside the 8-row window while browsing.
+    let window_start = cursor.map_or(0, |c| c.saturating_sub(7));
+    for (i, commit) in commits.iter().enumerate().skip(window_start).take(8) {
         let msg = &commit.message;
-        let truncated = if msg.len() > 50 {
-            format!("{}...", &msg[..47])
-        } else {
-            msg.clone()
-        };
+        // Leave room for the "  • " bullet column.
+        let truncated = text::truncate(msg, width.saturating_sub(4));
+        let is_cursor = cursor == Some(i);
 
         let color = if msg.starts_with("feat") {
             Color::Green
@@ -563,22 +693,35 @@ fn build_detail_panel(
             Color::Gray
         };
 
+        let (marker, style) = if is_cursor {
+            (
+                "▶",
+                Style::default().fg(color).add_modifier(Modifier::REVERSED),
+            )
+        } else {
+            ("•", Style::default().fg(color))
+        };
         lines.push(Line::from(Span::styled(
-            format!("  {} {}", if i < 9 { "•" } else { " " }, truncated),
-            Style::default().fg(color),
+            format!("  {} {}", marker, truncated),
+            style,
         )));
     }
 
-    if commits.len() > 8 {
+    let remaining = commits.len().saturating_sub(window_start + 8);
+    if remaining > 0 {
         lines.push(Line::from(Span::styled(
-            format!("  ... and {} more", commits.len() - 8),
+            format!("  ... and {} more", remaining),
             Style::default().fg(Color::Gray),
         )));
     }
 
     lines.push(Line::from(""));
     lines.push(Line::from(Span::styled(
-        "Press Tab to preview full changelog",
+        if cursor.is_some() {
+            "Press Enter for commit details, ← to go back"
+        } else {
+            "Press Tab to preview full changelog, → to browse commits"
+        },
         Style::default().fg(Color::Gray),
     )));
 
@@ -620,15 +763,9 @@ fn render_project_changelog(f: &mut Frame, area: Rect, state: &mut WizardState)
         None => return,
     };
 
-    let chosen_bump = current_project.chosen_bump.unwrap_or(BumpChoice::Auto);
+    let chosen_bump = current_project.effective_bump();
     let current_version = current_project.current_version();
-    let suggested_bump = current_project.suggested_bump();
-    let new_version = match chosen_bump {
-        BumpChoice::Auto => calculate_next_version(current_version, suggested_bump),
-        BumpChoice::Major => calculate_major_version(current_version),
-        BumpChoice::Minor => calculate_minor_version(current_version),
-        BumpChoice::Patch => calculate_patch_version(current_version),
-    };
+    let new_version = current_project.next_version(chosen_bump);
 
     let new_entry = current_project
         .cached_changelog
@@ -755,6 +892,128 @@ fn render_project_changelog(f: &mut Frame, area: Rect, state: &mut WizardState)
     f.render_widget(hints_para, chunks[3]);
 }
 
+fn render_dep_conflicts(f: &mut Frame, area: Rect, state: &mut WizardState) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .title(Span::styled(
+            " Step 3: Dependency Conflicts ",
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ));
+
+    let inner_area = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(4),
+            Constraint::Min(0),
+            Constraint::Length(2),
+        ])
+        .split(inner_area);
+
+    let header_lines = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("⚠️  ", Style::default()),
+            Span::styled(
+                "These internal requirements would reject the new versions",
+                Style::default().fg(Color::White),
+            ),
+        ]),
+        Line::from(Span::styled(
+            "   Choose how to resolve each one",
+            Style::default().fg(Color::Gray),
+        )),
+    ];
+    let header = Paragraph::new(header_lines).alignment(ratatui::layout::Alignment::Center);
+    f.render_widget(header, chunks[0]);
+
+    let selected_index = state.conflict_list_state.selected().unwrap_or(0);
+    let items: Vec<ListItem> = state
+        .dep_conflicts
+        .iter()
+        .enumerate()
+        .map(|(idx, (conflict, chosen))| {
+            let mut options = vec![Span::raw("    ")];
+            for option in ConflictResolution::all() {
+                let style = if option == *chosen {
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::DarkGray)
+                };
+                let label = if option == *chosen {
+                    format!("[{}]", option.label())
+                } else {
+                    format!(" {} ", option.label())
+                };
+                options.push(Span::styled(label, style));
+                options.push(Span::raw("  "));
+            }
+            let outcome = match conflict.requirement_for(*chosen) {
+                Some(requirement) => format!(
+                    "    → {} requires {} `{}`",
+                    conflict.dependent_name, conflict.dependee_name, requirement
+                ),
+                None => format!(
+                    "    → {} stays at {} and is left out of this release",
+                    conflict.dependee_name, conflict.old_version
+                ),
+            };
+            let lines = vec![
+                Line::from(vec![
+                    Span::styled(" ⚠ ", Style::default().fg(Color::Yellow)),
+                    Span::styled(conflict.describe(), Style::default().fg(Color::White)),
+                ]),
+                Line::from(options),
+                Line::from(Span::styled(outcome, Style::default().fg(Color::Gray))),
+                Line::from(""),
+            ];
+            let style = if idx == selected_index {
+                theme::row_highlight()
+            } else {
+                Style::default()
+            };
+            ListItem::new(lines).style(style)
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Gray))
+                .title(Span::styled(
+                    " Conflicts ",
+                    Style::default().fg(Color::White),
+                )),
+        )
+        .highlight_symbol("");
+    f.render_stateful_widget(list, chunks[1], &mut state.conflict_list_state);
+
+    let hints = Line::from(vec![
+        Span::styled("↑↓", Style::default().fg(Color::Cyan)),
+        Span::styled(" navigate  ", Style::default().fg(Color::Gray)),
+        Span::styled("←→", Style::default().fg(Color::Cyan)),
+        Span::styled(" resolution  ", Style::default().fg(Color::Gray)),
+        Span::styled("Enter", Style::default().fg(Color::Green)),
+        Span::styled(" continue  ", Style::default().fg(Color::Gray)),
+        Span::styled("Esc", Style::default().fg(Color::Yellow)),
+        Span::styled(" back  ", Style::default().fg(Color::Gray)),
+        Span::styled("?", Style::default().fg(Color::Yellow)),
+        Span::styled(" help  ", Style::default().fg(Color::Gray)),
+        Span::styled("q", Style::default().fg(Color::Red)),
+        Span::styled(" quit", Style::default().fg(Color::Gray)),
+    ]);
+    let hints_para = Paragraph::new(hints).alignment(ratatui::layout::Alignment::Center);
+    f.render_widget(hints_para, chunks[2]);
+}
+
 fn render_confirmation(f: &mut Frame, area: Rect, state: &WizardState) {
     let selected_projects = state.selected_projects();
 
@@ -814,13 +1073,36 @@ fn render_confirmation(f: &mut Frame, area: Rect, state: &WizardState) {
     ];
 
     for project in selected_projects.iter().take(10) {
-        let bump_text = project.effective_bump_str();
+        if state.is_skipped(project) {
+            project_lines.push(Line::from(vec![
+                Span::styled("   ⏭  ", Style::default().fg(Color::Yellow)),
+                Span::styled(project.name(), Style::default().fg(Color::Gray)),
+                Span::styled(
+                    " (skipped: dependency conflict)",
+                    Style::default().fg(Color::Yellow),
+                ),
+            ]));
+            continue;
+        }
+        if project.bump_skipped() {
+            project_lines.push(Line::from(vec![
+                Span::styled("   ⏭  ", Style::default().fg(Color::Yellow)),
+                Span::styled(project.name(), Style::default().fg(Color::Gray)),
+                Span::styled(" (skipped)", Style::default().fg(Color::Yellow)),
+            ]));
+            continue;
+        }
+        let bump_text = project.planned_bump();
         let bump_color = match bump_text {
             "MAJOR" => Color::Red,
             "MINOR" => Color::Yellow,
             "PATCH" => Color::Green,
             _ => Color::Cyan,
         };
+        let bump_label = match glyphs::bump(bump_text) {
+            "" => bump_text.to_string(),
+            glyph => format!("{glyph} {bump_text}"),
+        };
 
         project_lines.push(Line::from(vec![
             Span::styled("   ✅ ", Style::default().fg(Color::Green)),
@@ -832,7 +1114,7 @@ fn render_confirmation(f: &mut Frame, area: Rect, state: &WizardState) {
         ]));
         project_lines.push(Line::from(vec![
             Span::styled("      → ", Style::default().fg(Color::Gray)),
-            Span::styled(bump_text, Style::default().fg(bump_color)),
+            Span::styled(bump_label, Style::default().fg(bump_color)),
         ]));
     }
 
@@ -882,6 +1164,62 @@ fn render_confirmation(f: &mut Frame, area: Rect, state: &WizardState) {
         Span::styled("belaf/releases/*.json", Style::default().fg(Color::Gray)),
     ]));
 
+    if !state.follow_ups.is_empty() {
+        file_lines.push(Line::from(""));
+        file_lines.push(Line::from(vec![
+            Span::styled("🔗 ", Style::default()),
+            Span::styled("Not Released", Style::default().fg(Color::White)),
+        ]));
+        file_lines.push(Line::from(""));
+        for follow_up in state.follow_ups.iter().take(8) {
+            let (icon, color) = if follow_up.needs_release() {
+                ("   ⚠  ", Color::Yellow)
+            } else {
+                ("   ·  ", Color::Gray)
+            };
+            file_lines.push(Line::from(vec![
+                Span::styled(icon, Style::default().fg(color)),
+                Span::styled(follow_up.describe(), Style::default().fg(color)),
+            ]));
+        }
+        if state.follow_ups.len() > 8 {
+            file_lines.push(Line::from(Span::styled(
+                format!("   ... and {} more", state.follow_ups.len() - 8),
+                Style::default().fg(Color::Gray),
+            )));
+        }
+    }
+
+    for release_edits in &state.release_edits {
+        file_lines.push(Line::from(""));
+        file_lines.push(Line::from(vec![
+            Span::styled("✏️  ", Style::default()),
+            Span::styled(
+                format!("Since Release PR #{}", release_edits.number),
+                Style::default().fg(Color::White),
+            ),
+        ]));
+        file_lines.push(Line::from(""));
+        if release_edits.edits.is_empty() {
+            file_lines.push(Line::from(Span::styled(
+                "   ·  nothing changed",
+                Style::default().fg(Color::Gray),
+            )));
+        }
+        for edit in release_edits.edits.iter().take(8) {
+            file_lines.push(Line::from(vec![
+                Span::styled("   ·  ", Style::default().fg(Color::Cyan)),
+                Span::styled(edit.describe(), Style::default().fg(Color::Cyan)),
+            ]));
+        }
+        if release_edits.edits.len() > 8 {
+            file_lines.push(Line::from(Span::styled(
+                format!("   ... and {} more", release_edits.edits.len() - 8),
+                Style::default().fg(Color::Gray),
+            )));
+        }
+    }
+
     let file_block = Paragraph::new(file_lines).block(
         Block::default()
             .borders(Borders::ALL)
@@ -907,6 +1245,79 @@ fn render_confirmation(f: &mut Frame, area: Rect, state: &WizardState) {
     f.render_widget(hints_para, chunks[2]);
 }
 
+fn render_commit_detail(f: &mut Frame, state: &WizardState) {
+    let Some(detail) = &state.commit_detail else {
+        return;
+    };
+    let commit = &detail.inspection;
+    let area = centered_rect(80, 80, f.area());
+    let label = Style::default()
+        .fg(Color::Cyan)
+        .add_modifier(Modifier::BOLD);
+
+    let mut lines = vec![
+        Line::from(Span::styled(
+            commit.summary.clone(),
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(Span::styled(
+            format!("{} · {}", commit.id, detail.author),
+            Style::default().fg(Color::Gray),
+        )),
+        Line::from(""),
+    ];
+
+    if !commit.body.is_empty() {
+        lines.extend(commit.body.lines().map(|l| Line::from(l.to_string())));
+        lines.push(Line::from(""));
+    }
+
+    if !commit.trailers.is_empty() {
+        lines.push(Line::from(Span::styled("Trailers", label)));
+        for (key, value) in &commit.trailers {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {}: ", key), Style::default().fg(Color::Gray)),
+                Span::raw(value.clone()),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+
+    lines.push(Line::from(Span::styled(
+        format!("Changed files ({})", commit.changed_files.len()),
+        label,
+    )));
+    for file in &commit.changed_files {
+        lines.push(Line::from(format!("  {}", file.escaped())));
+    }
+    lines.push(Line::from(""));
+
+    lines.push(Line::from(Span::styled("Why it is listed", label)));
+    lines.push(Line::from(format!("  {}", detail.attribution)));
+    if let Some(warning) = &detail.scope_warning {
+        lines.push(Line::from(Span::styled(
+            format!("  ⚠ {warning}"),
+            Style::default().fg(Color::Yellow),
+        )));
+    }
+
+    let paragraph = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(Color::Cyan))
+                .title(" Commit (↑↓ scroll, Enter/Esc close) ")
+                .style(Style::default().bg(Color::Black)),
+        )
+        .wrap(Wrap { trim: false })
+        .scroll((state.commit_detail_scroll, 0));
+
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
+
 fn render_help_popup(f: &mut Frame, state: &WizardState) {
     let area = centered_rect(60, 70, f.area());
 
@@ -939,22 +1350,47 @@ fn render_help_popup(f: &mut Frame, state: &WizardState) {
                  • Auto: Use conventional commits analysis\n\
                  • Major: Breaking changes (x.0.0)\n\
                  • Minor: New features (0.x.0)\n\
-                 • Patch: Bug fixes (0.0.x)\n\n\
+                 • Patch: Bug fixes (0.0.x)\n\
+                 • Custom…: Type an exact version\n\
+                 • As-is: Release a version bumped by hand\n\
+                 • Skip: Leave this project out\n\n\
                  Each project can have its own bump strategy.\n\
                  The 'Auto' option uses the suggested bump\n\
                  based on your commit messages.\n\n\
                  • Press ↑/↓ to select a bump strategy\n\
+                 • Press → to browse commits, Enter on a\n\
+                   commit shows its body, trailers and files\n\
                  • Press Tab to preview the changelog\n\
                  • Press Enter to confirm and continue"
             }
         }
+        WizardStep::DepConflicts => {
+            "Dependency Conflicts Help\n\n\
+             Another project pins an internal dependency\n\
+             to a requirement its new version won't meet.\n\n\
+             • Widen range: accept the current version up\n\
+               to the next breaking release\n\
+             • Pin new version: require exactly the new one\n\
+             • Skip project: leave the dependency (and its\n\
+               group) out of this release\n\n\
+             • Press ↑/↓ to select a conflict\n\
+             • Press ←/→ or Space to change its resolution\n\
+             • Press Enter to continue"
+        }
         WizardStep::Confirmation => {
             "Confirmation Help\n\n\
              Review the changes that will be made:\n\
              • Version numbers in project files\n\
              • CHANGELOG.md entries\n\
              • Dependency version updates\n\n\
-             Each project shows its selected bump strategy.\n\n\
+             Each project shows its selected bump strategy.\n\
+             \"Not Released\" lists projects you left out\n\
+             that depend on a released one: ⚠ marks a\n\
+             requirement the new version breaks or a\n\
+             cascade_from bump that is due.\n\
+             \"Since Release PR #N\" lists what changed\n\
+             since that open release PR was prepared;\n\
+             the new PR's body repeats it.\n\n\
              Press Enter to apply all changes.\n\
              You will still need to commit and tag."
         }
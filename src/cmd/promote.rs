@@ -0,0 +1,27 @@
+//! `belaf promote` — release the stable version of a prerelease.
+//!
+//! Opens a release PR like `prepare --ci`, for the named units only and
+//! at the stable versions of their latest prerelease tags. See
+//! [`crate::core::workflow::PrepareContext::promote`].
+
+use anyhow::Result;
+use tracing::info;
+
+use crate::core::{
+    session::AppBuilder,
+    workflow::{preflight_github_access, PrepareContext},
+};
+
+pub fn run(release_units: Vec<String>, force: bool) -> Result<i32> {
+    info!(
+        "promoting prereleases with belaf version {}",
+        env!("CARGO_PKG_VERSION")
+    );
+
+    let mut sess = AppBuilder::new()?.fetch_tags_first(true).initialize()?;
+    preflight_github_access(&sess)?;
+
+    let ctx = PrepareContext::initialize(&mut sess, false, force)?;
+    ctx.promote(&release_units)?;
+    Ok(0)
+}
@@ -0,0 +1,803 @@
+//! `belaf release cut` and `belaf release publish` — ship a merged
+//! release in two steps, with QA in between.
+//!
+//! `cut` runs where `belaf tag` would: it creates the release's tags (see
+//! [`tag::create_tags`]), pushes them, and opens a draft GitHub release
+//! per tag with the artifacts of `--artifacts` attached. `publish` makes
+//! the drafts public and then does what `belaf tag` does after tagging:
+//! the `released` webhook, Jira and milestones. Both record where the
+//! release stands in the manifest, to be committed; see
+//! [`crate::core::publication`]. With `[release] check_registries`,
+//! both look the versions up on their package registries first; see
+//! [`crate::core::ecosystem::registry`]. Drafts are published in
+//! dependency order, waiting for the registry between them as
+//! `[release.publish]` says. With `[signing]`, `cut` also attaches a
+//! signed checksums file and a signature per artifact; see
+//! [`crate::core::signing`].
+//!
+//! `belaf release --snapshot` releases a snapshot of HEAD instead, with
+//! no manifest behind it; see [`crate::core::snapshot`].
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use owo_colors::OwoColorize;
+use serde::Serialize;
+
+use crate::cli::{ReleasePublishOutputFormat, ReleaseSnapshotOutputFormat};
+use crate::cmd::tag;
+use crate::core::{
+    audit::{self, AuditEntry},
+    deterministic,
+    ecosystem::registry::{Registry, RegistryClient},
+    git::repository::{
+        ChangeList, CommitId, DirtyRepositoryError, PathMatcher, RepoPath, RepoPathBuf,
+    },
+    github::rest::{self, NewRelease},
+    manifest::{Publication, PublicationRelease, PublicationState, ReleaseEntry, ReleaseManifest},
+    publication::{self, Outcome},
+    releases,
+    session::AppSession,
+    signing,
+    snapshot::{self, Channel},
+    workflow,
+};
+
+/// The first look for a release in `[release.publish] wait_for_registry`.
+const FIRST_POLL: Duration = Duration::from_secs(5);
+
+pub async fn cut(
+    release_id: String,
+    artifacts: Option<PathBuf>,
+    sign: bool,
+    dry_run: bool,
+) -> Result<i32> {
+    let sess = AppSession::initialize_default()?;
+    let (path, rel_path) = releases::find(&sess.repo, &release_id)?;
+    let manifest = releases::read(&path)?.manifest;
+    if let Some(existing) = &manifest.publication {
+        bail!(
+            "{rel_path} was already cut on {}; {}",
+            existing.cut_at,
+            match existing.state {
+                PublicationState::Cut => "publish it with `belaf release publish`",
+                PublicationState::Published => "it is published",
+            }
+        );
+    }
+    let sign = sign || sess.signs_tags();
+
+    let published = on_registries(&sess, &manifest).await?;
+    if !published.is_empty() {
+        bail!(
+            "these versions are already on their registries, which refuse to publish them again:\n  • {}",
+            published
+                .iter()
+                .map(|(r, registry)| format!("{} {} on {}", r.name, r.new_version, registry.label()))
+                .collect::<Vec<_>>()
+                .join("\n  • ")
+        );
+    }
+
+    // Read the artifacts first: a wrong directory shouldn't leave tags behind.
+    let mut staged = Vec::new();
+    for (tag_name, members) in tag::tags_of(&manifest) {
+        let files = match &artifacts {
+            Some(dir) => publication::collect_artifacts(dir, &members)?,
+            None => Vec::new(),
+        };
+        staged.push((tag_name.to_string(), members, files));
+    }
+
+    // Likewise the signatures: a missing tool or key fails here. They
+    // live in a private directory until uploaded.
+    let mut signatures = None;
+    if let Some(signing) = sess.config_signing() {
+        let root = sess.repo.resolve_workdir(&RepoPathBuf::new(b""));
+        let out = signatures.insert(
+            tempfile::tempdir().context("failed to create a directory for the signatures")?,
+        );
+        for (tag_name, _, files) in staged.iter_mut().filter(|(_, _, f)| !f.is_empty()) {
+            if dry_run {
+                println!(
+                    "{} {tag_name}: {} and {} signature(s) would be added, made with {}",
+                    "ℹ".cyan().bold(),
+                    signing::CHECKSUMS,
+                    files.len() + 1,
+                    signing.tool.as_str()
+                );
+                continue;
+            }
+            let signed = signing::sign_release(signing, &root, &out.path().join(&*tag_name), files)
+                .with_context(|| format!("failed to sign the artifacts of {tag_name}"))?;
+            files.extend(signed);
+            files.sort_by(|(_, a), (_, b)| a.name.cmp(&b.name));
+        }
+    }
+
+    tag::create_tags(&sess, &manifest, &rel_path, sign, dry_run)?;
+    if dry_run {
+        for (tag_name, _, files) in &staged {
+            println!(
+                "{} Draft release {tag_name} with {} artifact(s)",
+                "ℹ".cyan().bold(),
+                files.len()
+            );
+            for (_, artifact) in files {
+                println!("  {}  {}", artifact.sha256.dimmed(), artifact.name);
+            }
+        }
+        return Ok(0);
+    }
+
+    let tag_names: Vec<&str> = staged.iter().map(|(t, _, _)| t.as_str()).collect();
+    sess.repo.push_tags(&tag_names, None)?;
+    let (client, owner, repo) = rest::for_upstream(&sess.repo)?;
+    let head = sess.repo.head_commit_sha()?;
+
+    // A cut that failed halfway left drafts behind: finish those rather
+    // than drafting the same tag twice.
+    let mut drafts = Vec::new();
+    for (tag_name, members, files) in &staged {
+        let (draft, attached) = match client.find_draft_release(&owner, &repo, tag_name).await? {
+            Some(draft) => {
+                let attached = client.release_assets(&owner, &repo, draft.id).await?;
+                println!(
+                    "{} Reusing the draft of {tag_name}: {}",
+                    "•".dimmed(),
+                    draft.url
+                );
+                (draft, attached)
+            }
+            None => {
+                let name = members
+                    .iter()
+                    .map(|r| format!("{} {}", r.name, r.new_version))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let draft = client
+                    .create_draft_release(
+                        &owner,
+                        &repo,
+                        &NewRelease {
+                            tag_name,
+                            target: &head,
+                            name: &name,
+                            body: &tag::members_notes(members),
+                            prerelease: members.iter().any(|r| r.is_prerelease),
+                        },
+                    )
+                    .await?;
+                (draft, Vec::new())
+            }
+        };
+        for (file, artifact) in files {
+            if let Some(asset) = attached.iter().find(|a| a.name == artifact.name) {
+                if asset
+                    .sha256
+                    .as_ref()
+                    .is_some_and(|sha| *sha != artifact.sha256)
+                {
+                    bail!(
+                        "`{}` on {} differs from the one in the artifacts directory; delete it from the draft and cut again",
+                        artifact.name,
+                        draft.url
+                    );
+                }
+                continue;
+            }
+            let data =
+                fs::read(file).with_context(|| format!("failed to read `{}`", file.display()))?;
+            client
+                .upload_release_asset(&draft, &artifact.name, data)
+                .await
+                .with_context(|| format!("draft {} is incomplete", draft.url))?;
+        }
+        println!(
+            "{} Drafted {tag_name} with {} artifact(s): {}",
+            "✓".green().bold(),
+            files.len(),
+            draft.url
+        );
+        drafts.push(PublicationRelease {
+            tag_name: tag_name.clone(),
+            release_id: draft.id,
+            url: draft.url,
+            artifacts: files.iter().map(|(_, a)| a.clone()).collect(),
+        });
+    }
+
+    drop(signatures);
+
+    let mut cut = manifest.clone();
+    cut.publication = Some(Publication {
+        state: PublicationState::Cut,
+        cut_at: publication::timestamp(),
+        published_at: None,
+        releases: drafts,
+    });
+    cut.save_to_file(&path)
+        .with_context(|| format!("failed to write `{}`", path.display()))?;
+    let entry = AuditEntry::new(&sess.repo, "release cut")
+        .with_manifest(&manifest.manifest_id)
+        .with_tags(tag_names.iter().map(|t| t.to_string()).collect());
+    audit::append(&sess.repo, &entry)?;
+
+    println!("  Commit {rel_path} and, once QA signed off, run:");
+    println!("    belaf release publish {}", manifest.manifest_id);
+    Ok(0)
+}
+
+/// What became of one draft, for `--format json`.
+#[derive(Serialize)]
+struct DraftResult {
+    tag: String,
+    units: Vec<UnitResult>,
+    /// `published`, `failed`, `skipped` or, with `--dry-run`, `planned`.
+    status: &'static str,
+    url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    /// The tag whose draft this one waited for, when skipped.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    blocked_by: Option<String>,
+}
+
+#[derive(Serialize)]
+struct UnitResult {
+    name: String,
+    version: String,
+    on_registry: bool,
+}
+
+#[derive(Serialize)]
+struct PublishPayload {
+    release_id: String,
+    manifest: String,
+    dry_run: bool,
+    published: bool,
+    drafts: Vec<DraftResult>,
+}
+
+pub async fn publish(
+    release_id: String,
+    jobs: Option<usize>,
+    dry_run: bool,
+    format: Option<ReleasePublishOutputFormat>,
+) -> Result<i32> {
+    let json = format == Some(ReleasePublishOutputFormat::Json);
+    let sess = AppSession::initialize_default()?;
+    let (path, rel_path) = releases::find(&sess.repo, &release_id)?;
+    let mut manifest = releases::read(&path)?.manifest;
+    let Some(mut state) = manifest.publication.clone() else {
+        bail!("{rel_path} has not been cut; run `belaf release cut {release_id}` first");
+    };
+    if state.state == PublicationState::Published {
+        bail!(
+            "{rel_path} was already published on {}",
+            state.published_at.as_deref().unwrap_or("an unknown date")
+        );
+    }
+
+    sess.repo.check_upstream("publish releases")?;
+    let (client, owner, repo) = rest::for_upstream(&sess.repo)?;
+    let mut problems = Vec::new();
+    for release in &state.releases {
+        let assets = client
+            .release_assets(&owner, &repo, release.release_id)
+            .await?;
+        problems.extend(
+            publication::asset_problems(&release.artifacts, &assets)
+                .into_iter()
+                .map(|p| format!("{}: {p}", release.tag_name)),
+        );
+    }
+    if !problems.is_empty() {
+        bail!(
+            "the drafts no longer hold the artifacts recorded when they were cut:\n  • {}\n\n\
+             Nothing was published.",
+            problems.join("\n  • ")
+        );
+    }
+
+    // Under --format json, stdout is kept for the payload.
+    let note = |text: &str| {
+        if json {
+            eprint!("{text}");
+        } else {
+            print!("{text}");
+        }
+    };
+
+    // Left over from an earlier run that stopped short: the job that
+    // publishes to registries is told, so it can skip them.
+    let mut on_registry = Vec::new();
+    for (release, registry) in on_registries(&sess, &manifest).await? {
+        note(&format!(
+            "{} {} {} is already on {}\n",
+            "ℹ".cyan().bold(),
+            release.name,
+            release.new_version,
+            registry.label()
+        ));
+        on_registry.push(release.name.clone());
+    }
+
+    let members: Vec<Vec<&ReleaseEntry>> = state
+        .releases
+        .iter()
+        .map(|draft| {
+            manifest
+                .releases
+                .iter()
+                .filter(|r| r.tag_name == draft.tag_name)
+                .collect()
+        })
+        .collect();
+    let names: Vec<Vec<&str>> = members
+        .iter()
+        .map(|m| m.iter().map(|r| r.name.as_str()).collect())
+        .collect();
+    let graph = sess.graph();
+    let cfg = sess.config_publish();
+    let depends_on = |unit: &str, dep: &str| {
+        cfg.after
+            .get(unit)
+            .is_some_and(|after| after.iter().any(|a| a == dep))
+            || match (graph.lookup_ident(unit), graph.lookup_ident(dep)) {
+                (Some(unit), Some(dep)) => graph
+                    .lookup(unit)
+                    .internal_deps
+                    .iter()
+                    .any(|d| d.ident == dep),
+                _ => false,
+            }
+    };
+    let order = publication::publish_order(&names, depends_on)?;
+    let needs: Vec<Vec<usize>> = (0..names.len())
+        .map(|i| {
+            (0..names.len())
+                .filter(|&j| {
+                    j != i
+                        && names[i]
+                            .iter()
+                            .any(|unit| names[j].iter().any(|dep| depends_on(unit, dep)))
+                })
+                .collect()
+        })
+        .collect();
+    // Dependents are only published once their registry has this.
+    let waits = |i: usize| cfg.wait_for_registry && needs.iter().any(|n| n.contains(&i));
+
+    let registries = RegistryClient::new()?;
+    let total = order.len();
+    let mut finished = 0;
+    let outcomes = publication::run_in_order(
+        &order,
+        &needs,
+        jobs.unwrap_or(cfg.parallelism),
+        |i| {
+            let (draft, members) = (&state.releases[i], &members[i]);
+            let (client, owner, repo, registries) = (&client, &owner, &repo, &registries);
+            async move {
+                if dry_run {
+                    return Ok(draft.url.clone());
+                }
+                let url = client
+                    .publish_release(owner, repo, draft.release_id)
+                    .await?;
+                if waits(i) {
+                    for release in members {
+                        let Some(registry) = Registry::of(&release.ecosystem) else {
+                            continue;
+                        };
+                        registries
+                            .wait_for(
+                                registry,
+                                &release.name,
+                                &release.new_version,
+                                Duration::from_secs(cfg.timeout_secs),
+                                FIRST_POLL,
+                            )
+                            .await
+                            .context("published, but its dependents are held back")?;
+                    }
+                }
+                Ok(url)
+            }
+        },
+        |i, outcome| {
+            finished += 1;
+            if json {
+                return;
+            }
+            let tag_name = &state.releases[i].tag_name;
+            let step = format!("[{finished}/{total}]").dimmed().to_string();
+            match outcome {
+                Outcome::Done(url) if dry_run => println!(
+                    "{step} {} {tag_name} would be published: {url}{}",
+                    "ℹ".cyan().bold(),
+                    if waits(i) {
+                        ", then its registry waited for"
+                    } else {
+                        ""
+                    }
+                ),
+                Outcome::Done(url) => {
+                    println!("{step} {} Published {tag_name}: {url}", "✓".green().bold())
+                }
+                Outcome::Failed(e) => println!("{step} {} {tag_name}: {e:#}", "✗".red().bold()),
+                Outcome::Skipped(j) => println!(
+                    "{step} {} {tag_name} skipped: {} was not published",
+                    "•".dimmed(),
+                    state.releases[*j].tag_name
+                ),
+            }
+        },
+    )
+    .await;
+
+    let drafts: Vec<DraftResult> = outcomes
+        .iter()
+        .enumerate()
+        .map(|(i, outcome)| {
+            let draft = &state.releases[i];
+            let (status, error, blocked_by) = match outcome {
+                Outcome::Done(_) if dry_run => ("planned", None, None),
+                Outcome::Done(_) => ("published", None, None),
+                Outcome::Failed(e) => ("failed", Some(format!("{e:#}")), None),
+                Outcome::Skipped(j) => ("skipped", None, Some(state.releases[*j].tag_name.clone())),
+            };
+            DraftResult {
+                tag: draft.tag_name.clone(),
+                units: members[i]
+                    .iter()
+                    .map(|r| UnitResult {
+                        name: r.name.clone(),
+                        version: r.new_version.clone(),
+                        on_registry: on_registry.contains(&r.name),
+                    })
+                    .collect(),
+                status,
+                url: match outcome {
+                    Outcome::Done(url) => url.clone(),
+                    _ => draft.url.clone(),
+                },
+                error,
+                blocked_by,
+            }
+        })
+        .collect();
+    let missed = outcomes
+        .iter()
+        .filter(|o| !matches!(o, Outcome::Done(_)))
+        .count();
+
+    let tag_names: Vec<String> = order
+        .iter()
+        .map(|&i| state.releases[i].tag_name.clone())
+        .collect();
+    let mut out = String::new();
+    if dry_run {
+        tag::announce(&sess, &manifest, &tag_names, &on_registry, true, &mut out);
+    } else if missed == 0 {
+        state.state = PublicationState::Published;
+        state.published_at = Some(publication::timestamp());
+        manifest.publication = Some(state);
+        manifest
+            .save_to_file(&path)
+            .with_context(|| format!("failed to write `{}`", path.display()))?;
+        let entry = AuditEntry::new(&sess.repo, "release publish")
+            .with_manifest(&manifest.manifest_id)
+            .with_tags(tag_names.clone());
+        audit::append(&sess.repo, &entry)?;
+        tag::announce(&sess, &manifest, &tag_names, &on_registry, false, &mut out);
+    }
+    note(&out);
+
+    if json {
+        let payload = PublishPayload {
+            release_id: manifest.manifest_id.clone(),
+            manifest: rel_path.clone(),
+            dry_run,
+            published: !dry_run && missed == 0,
+            drafts,
+        };
+        println!("{}", serde_json::to_string_pretty(&payload)?);
+    }
+    if missed > 0 {
+        bail!(
+            "{missed} of {total} draft(s) weren't published; once the cause is fixed, \
+             `belaf release publish` publishes the rest"
+        );
+    }
+    if !dry_run && !json {
+        println!("  Commit {rel_path} to record the publication.");
+    }
+    Ok(0)
+}
+
+/// A unit's snapshot, for `--format json`.
+#[derive(Serialize)]
+struct SnapshotResult {
+    name: String,
+    ecosystem: String,
+    previous_version: String,
+    version: String,
+    /// `npm:next` when published there.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    channel: Option<String>,
+    /// Why it wasn't published.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    skipped: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tag: Option<String>,
+    /// The GitHub prerelease of `tag`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+}
+
+#[derive(Serialize)]
+struct SnapshotPayload {
+    commit: String,
+    /// The commit on top of `commit` with the snapshot versions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    snapshot_commit: Option<String>,
+    dry_run: bool,
+    snapshots: Vec<SnapshotResult>,
+}
+
+pub async fn snapshot(
+    label: String,
+    tag: bool,
+    dry_run: bool,
+    format: Option<ReleaseSnapshotOutputFormat>,
+) -> Result<i32> {
+    let json = format == Some(ReleaseSnapshotOutputFormat::Json);
+    let mut sess = AppSession::initialize_default()?;
+    let head = sess.repo.head_commit_sha()?;
+    let today = deterministic::now_utc().date();
+    let planned = snapshot::plan(&sess, &label, today, &head[..7])?;
+
+    let groups = sess.graph().groups();
+    let mut results = Vec::new();
+    for unit in &planned {
+        let version = unit.version.to_string();
+        let tag_name = if tag {
+            Some(snapshot::tag_name(&workflow::unit_tag_name(
+                &sess,
+                unit.ident,
+                &unit.name,
+                &unit.ecosystem,
+                &version,
+                groups,
+            )?))
+        } else {
+            None
+        };
+        let (channel, skipped) = match Channel::of(unit.ecosystem.as_str()) {
+            Channel::Npm => (Some(format!("npm:{}", snapshot::NPM_DIST_TAG)), None),
+            Channel::Unsupported(why) => (None, Some(why.to_string())),
+        };
+        results.push(SnapshotResult {
+            name: unit.name.clone(),
+            ecosystem: unit.ecosystem.as_str().to_string(),
+            previous_version: unit.previous_version.clone(),
+            version,
+            channel,
+            skipped,
+            tag: tag_name,
+            url: None,
+        });
+    }
+    if let Some(existing) = results
+        .iter()
+        .filter_map(|r| r.tag.as_deref())
+        .find(|t| sess.repo.tag_exists(t))
+    {
+        bail!("{existing} already exists; snapshots of one commit are tagged once a day");
+    }
+
+    let mut snapshot_commit = None;
+    if !dry_run && !planned.is_empty() {
+        let audit_log = PathMatcher::new_include(RepoPathBuf::new(audit::AUDIT_LOG.as_bytes()));
+        if let Some(dirty) = sess.repo.check_if_dirty(&[audit_log])? {
+            return Err(DirtyRepositoryError(dirty).into());
+        }
+        if tag {
+            sess.repo.check_upstream("push snapshot tags")?;
+        }
+        for unit in &planned {
+            sess.graph_mut().lookup_mut(unit.ident).version = unit.version.clone();
+        }
+        let changes = sess
+            .rewrite()
+            .context("failed to write the snapshot versions")?;
+        let built = build_snapshot(&sess, &planned, &results, &head, &changes);
+        sess.repo
+            .hard_reset_changes(&changes)
+            .context("failed to restore the manifests after the snapshot")?;
+        let commit = built?.to_string();
+
+        let mut pushed = Vec::new();
+        if tag {
+            let mut tag_names: Vec<&str> = Vec::new();
+            for result in &results {
+                let Some(tag_name) = result.tag.as_deref() else {
+                    continue;
+                };
+                if tag_names.contains(&tag_name) {
+                    continue;
+                }
+                let message = format!("{} {} (snapshot of {head})", result.name, result.version);
+                sess.repo
+                    .create_annotated_tag(tag_name, &commit, &message, sess.signs_tags())?;
+                tag_names.push(tag_name);
+            }
+            sess.repo.push_tags(&tag_names, None)?;
+            pushed = tag_names.iter().map(|t| t.to_string()).collect();
+
+            let (client, owner, repo) = rest::for_upstream(&sess.repo)?;
+            let mut urls: Vec<(String, String)> = Vec::new();
+            for tag_name in &tag_names {
+                let members: Vec<&SnapshotResult> = results
+                    .iter()
+                    .filter(|r| r.tag.as_deref() == Some(*tag_name))
+                    .collect();
+                let name = members
+                    .iter()
+                    .map(|r| format!("{} {}", r.name, r.version))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let draft = client
+                    .create_draft_release(
+                        &owner,
+                        &repo,
+                        &NewRelease {
+                            tag_name,
+                            target: &commit,
+                            name: &name,
+                            body: &format!(
+                                "Snapshot of {head}, with the changes since the last release."
+                            ),
+                            prerelease: true,
+                        },
+                    )
+                    .await?;
+                let url = client.publish_release(&owner, &repo, draft.id).await?;
+                urls.push((tag_name.to_string(), url));
+            }
+            for result in &mut results {
+                result.url = urls
+                    .iter()
+                    .find(|(t, _)| result.tag.as_deref() == Some(t.as_str()))
+                    .map(|(_, url)| url.clone());
+            }
+        }
+
+        let entry = AuditEntry::new(&sess.repo, "release --snapshot").with_tags(pushed);
+        audit::append(&sess.repo, &entry)?;
+        snapshot_commit = Some(commit);
+    }
+
+    if json {
+        let payload = SnapshotPayload {
+            commit: head,
+            snapshot_commit,
+            dry_run,
+            snapshots: results,
+        };
+        println!("{}", serde_json::to_string_pretty(&payload)?);
+        return Ok(0);
+    }
+    if results.is_empty() {
+        println!(
+            "{} No unit has changes since its last release",
+            "ℹ".cyan().bold()
+        );
+        return Ok(0);
+    }
+    for result in &results {
+        let mark = if dry_run {
+            "ℹ".cyan().bold().to_string()
+        } else {
+            "✓".green().bold().to_string()
+        };
+        println!(
+            "{mark} {} {} -> {}",
+            result.name, result.previous_version, result.version
+        );
+        let verb = if dry_run { "would be " } else { "" };
+        match (&result.channel, &result.skipped) {
+            (Some(channel), _) => println!("  {verb}published to {channel}"),
+            (None, Some(why)) => println!("  {} not published: {why}", "•".dimmed()),
+            (None, None) => {}
+        }
+        if let Some(tag_name) = &result.tag {
+            match &result.url {
+                Some(url) => println!("  tagged {tag_name}, prerelease {url}"),
+                None => println!("  {verb}tagged {tag_name}"),
+            }
+        }
+    }
+    if let Some(commit) = &snapshot_commit {
+        println!(
+            "  The versions are committed as {} on top of HEAD; check it out to build the snapshot.",
+            &commit[..8]
+        );
+    }
+    Ok(0)
+}
+
+/// Publish the snapshots that have a channel from the working tree, where
+/// `changes` wrote their versions, and commit those files on top of
+/// `head`. Returns the commit.
+fn build_snapshot(
+    sess: &AppSession,
+    planned: &[snapshot::Snapshot],
+    results: &[SnapshotResult],
+    head: &str,
+    changes: &ChangeList,
+) -> Result<CommitId> {
+    for (unit, result) in planned.iter().zip(results) {
+        if result.channel.is_none() {
+            continue;
+        }
+        let dir = sess
+            .repo
+            .resolve_workdir(sess.graph().lookup(unit.ident).prefix());
+        let output = Command::new("npm")
+            .args(["publish", "--tag", snapshot::NPM_DIST_TAG])
+            .current_dir(&dir)
+            .output()
+            .context("failed to run `npm publish`")?;
+        if !output.status.success() {
+            bail!(
+                "`npm publish` of {} {} failed:\n{}",
+                result.name,
+                result.version,
+                String::from_utf8_lossy(&output.stderr).trim_end()
+            );
+        }
+    }
+
+    let message = format!(
+        "chore(release): snapshot of {}\n\n{}\n",
+        &head[..7],
+        results
+            .iter()
+            .map(|r| format!("{} {}", r.name, r.version))
+            .collect::<Vec<_>>()
+            .join("\n")
+    );
+    let files: Vec<&RepoPath> = changes.paths().collect();
+    sess.repo.create_detached_commit(&message, &files)
+}
+
+/// The releases of `manifest` whose version is already on their package
+/// registry; none unless `[release] check_registries`.
+async fn on_registries<'m>(
+    sess: &AppSession,
+    manifest: &'m ReleaseManifest,
+) -> Result<Vec<(&'m ReleaseEntry, Registry)>> {
+    if !sess.check_registries() {
+        return Ok(Vec::new());
+    }
+    let client = RegistryClient::new()?;
+    let mut found = Vec::new();
+    for release in &manifest.releases {
+        let Some(registry) = Registry::of(&release.ecosystem) else {
+            continue;
+        };
+        if client
+            .has_version(registry, &release.name, &release.new_version)
+            .await?
+        {
+            found.push((release, registry));
+        }
+    }
+    Ok(found)
+}
@@ -8,17 +8,25 @@
 use anyhow::{anyhow, Result};
 
 const MANIFEST_SCHEMA: &str = include_str!("../../schemas/manifest.v1.schema.json");
+const GRAPH_SCHEMA: &str = include_str!("../../schemas/graph.v1.schema.json");
 
 /// One row of `belaf describe --json`'s `schemas` array. Kept here so
 /// the schema list has a single source of truth.
-pub const AVAILABLE_SCHEMAS: &[(&str, &str)] = &[(
-    "manifest",
-    "Belaf release manifest, v1 (JSON Schema Draft 2020-12)",
-)];
+pub const AVAILABLE_SCHEMAS: &[(&str, &str)] = &[
+    (
+        "manifest",
+        "Belaf release manifest, v1 (JSON Schema Draft 2020-12)",
+    ),
+    (
+        "graph",
+        "`belaf graph --format json` output, v1 (JSON Schema Draft 2020-12)",
+    ),
+];
 
 pub fn run(name: String) -> Result<i32> {
     let body = match name.as_str() {
         "manifest" => MANIFEST_SCHEMA,
+        "graph" => GRAPH_SCHEMA,
         other => {
             let known: Vec<&str> = AVAILABLE_SCHEMAS.iter().map(|(n, _)| *n).collect();
             return Err(anyhow!(
@@ -1,4 +1,7 @@
-use std::io;
+use std::{
+    collections::{BTreeMap, HashMap},
+    io,
+};
 
 use anyhow::{Context, Result};
 use crossterm::{
@@ -14,11 +17,29 @@ use ratatui::{
     widgets::{Block, Borders, Cell, Clear, Paragraph, Row},
     Terminal,
 };
-use tracing::info;
-
-use crate::cli::ReleaseOutputFormat;
+use tracing::{info, warn};
+
+use crate::cli::{ReleaseOutputFormat, StatusFilter};
+use crate::core::api_diff;
+use crate::core::bump::{self, BumpAnalysis, BumpConfig, BumpRecommendation};
+use crate::core::changelog::ChangelogConfig;
+use crate::core::commit_scopes::ScopeUsage;
+use crate::core::config::syntax::ApiDiffMode;
+use crate::core::git::repository::RepoHistory;
+use crate::core::github::codeowners::CodeOwners;
+use crate::core::github::pending::{find_pending_releases, PendingRelease};
+use crate::core::resolved_release_unit::ResolvedReleaseUnit;
+use crate::core::status_gates::{self, GateFailure, UnitState};
 use crate::core::ui::components::table::Table;
-use crate::core::{graph::GraphQueryBuilder, session::AppSession};
+use crate::core::ui::text;
+use crate::core::wire::known::Ecosystem;
+use crate::core::{
+    exit_code::ExitCode,
+    graph::GraphQueryBuilder,
+    session::{AppBuilder, AppSession},
+    workflow::changelog_path,
+};
+use crate::utils::theme;
 
 struct ReleaseUnitStatus {
     name: String,
@@ -312,7 +333,7 @@ impl TuiState {
                 let indicator = if is_selected { "▶ " } else { "  " };
 
                 let style = if is_selected {
-                    Style::default().bg(Color::Rgb(40, 40, 60)).fg(Color::White)
+                    theme::row_highlight().fg(Color::White)
                 } else {
                     Style::default().fg(Color::Gray)
                 };
@@ -371,6 +392,8 @@ impl TuiState {
             );
 
             let available_height = area.height.saturating_sub(3);
+            // Borders, the 5-cell `#` column and the column spacing.
+            let summary_width = area.width.saturating_sub(8) as usize;
             let visible_start = self.commit_scroll_offset;
             let visible_end = (visible_start + available_height as usize).min(unit.commits.len());
 
@@ -383,14 +406,16 @@ impl TuiState {
                 .map(|(idx, commit)| {
                     let is_current = idx == self.commit_scroll_offset;
                     let style = if is_current && self.selected_panel == SelectablePanel::Commits {
-                        Style::default().fg(Color::White)
+                        Style::default()
+                            .fg(Color::White)
+                            .add_modifier(Modifier::BOLD)
                     } else {
                         Style::default().fg(Color::Gray)
                     };
 
                     Row::new(vec![
                         Cell::from(format!(" {}", idx + 1)).style(Style::default().fg(Color::Gray)),
-                        Cell::from(commit.clone()).style(style),
+                        Cell::from(text::truncate(commit, summary_width).into_owned()).style(style),
                     ])
                 })
                 .collect();
@@ -502,6 +527,7 @@ impl TuiState {
 
 fn run_tui(sess: &AppSession, idents: &[usize]) -> Result<()> {
     let histories = sess.analyze_histories()?;
+
     let mut unit_data = Vec::new();
 
     for ident in idents {
@@ -540,7 +566,10 @@ fn run_tui(sess: &AppSession, idents: &[usize]) -> Result<()> {
     let mut state = TuiState::new(unit_data);
 
     loop {
-        terminal.draw(|frame| state.render(frame))?;
+        terminal.draw(|frame| {
+            state.render(frame);
+            theme::apply_color_mode(frame);
+        })?;
 
         if state.should_quit {
             break;
@@ -559,7 +588,12 @@ fn run_tui(sess: &AppSession, idents: &[usize]) -> Result<()> {
     Ok(())
 }
 
-pub fn run(format: Option<ReleaseOutputFormat>, ci: bool) -> Result<i32> {
+pub fn run(
+    format: Option<ReleaseOutputFormat>,
+    ci: bool,
+    filter: Option<StatusFilter>,
+    at: Option<String>,
+) -> Result<i32> {
     use crate::core::ui::utils::should_use_tui;
 
     info!(
@@ -567,7 +601,8 @@ pub fn run(format: Option<ReleaseOutputFormat>, ci: bool) -> Result<i32> {
         env!("CARGO_PKG_VERSION")
     );
 
-    let sess = AppSession::initialize_default()?;
+    let time_travel = at.is_some();
+    let sess = AppBuilder::read_only()?.at(at).initialize()?;
 
     let q = GraphQueryBuilder::default();
     let idents = sess
@@ -576,8 +611,23 @@ pub fn run(format: Option<ReleaseOutputFormat>, ci: bool) -> Result<i32> {
         .context("cannot get requested statuses")?;
 
     let histories = sess.analyze_histories()?;
+    let mut readiness = HashMap::new();
+    for ident in &idents {
+        readiness.insert(*ident, release_readiness(&sess, histories.lookup(*ident))?);
+    }
+    let idents: Vec<_> = idents
+        .into_iter()
+        .filter(|ident| match filter {
+            None => true,
+            Some(StatusFilter::Changed) => histories.lookup(*ident).n_commits() > 0,
+            Some(StatusFilter::Clean) => histories.lookup(*ident).n_commits() == 0,
+            Some(StatusFilter::Breaking) => {
+                readiness[ident].recommendation == BumpRecommendation::Major
+            }
+        })
+        .collect();
 
-    let use_tui = should_use_tui(ci, &format);
+    let use_tui = !time_travel && should_use_tui(ci, &format);
 
     let output_format = if ci {
         ReleaseOutputFormat::Json
@@ -590,14 +640,28 @@ pub fn run(format: Option<ReleaseOutputFormat>, ci: bool) -> Result<i32> {
         return Ok(0);
     }
 
+    // Open release PRs are today's, not the past commit's.
+    let pending = if time_travel {
+        None
+    } else {
+        find_pending_releases(&sess)
+    };
+    let as_of = sess.as_of().copied();
+    let mut gate_failures: Vec<GateFailure> = Vec::new();
+    let pending_for = |name: &str| -> Option<&PendingRelease> {
+        pending.as_deref()?.iter().find(|pr| pr.covers(name))
+    };
+
     match output_format {
         ReleaseOutputFormat::Json => {
             use serde_json::json;
 
             let mut projects = Vec::new();
+            let codeowners = CodeOwners::load(&sess.repo);
 
             for ident in &idents {
                 let unit = sess.graph().lookup(*ident);
+                let owners = codeowners.owners_of(unit.prefix());
                 let history = histories.lookup(*ident);
                 let n = history.n_commits();
                 let rel_info = history.release_info(&sess.repo)?;
@@ -607,14 +671,39 @@ pub fn run(format: Option<ReleaseOutputFormat>, ci: bool) -> Result<i32> {
                     let summary = sess.repo.get_commit_summary(*cid)?;
                     commits.push(summary);
                 }
+                let scopes = ScopeUsage::tally(
+                    commits.iter().map(String::as_str),
+                    sess.allowed_scopes(*ident),
+                );
+
+                if ci {
+                    gate_failures.extend(check_status_gates(
+                        &sess,
+                        unit,
+                        history,
+                        history
+                            .release_version()
+                            .map(ToString::to_string)
+                            .or_else(|| {
+                                rel_info
+                                    .lookup_project(unit)
+                                    .map(|info| info.version.to_string())
+                            }),
+                    )?);
+                }
 
-                let unit_data = if let Some(this_info) = rel_info.lookup_project(unit) {
+                let api_diff = api_diff_advice(&sess, *ident, unit, history)?;
+
+                let mut unit_data = if let Some(this_info) = rel_info.lookup_project(unit) {
                     json!({
                         "name": unit.user_facing_name,
                         "current_version": this_info.version.to_string(),
                         "commits_count": n,
                         "commits": commits,
                         "age": this_info.age,
+                        "release_pr": pending_for(&unit.user_facing_name).map(|pr| pr.number),
+                        "owners": owners,
+                        "scopes": scopes,
                     })
                 } else {
                     json!({
@@ -623,62 +712,328 @@ pub fn run(format: Option<ReleaseOutputFormat>, ci: bool) -> Result<i32> {
                         "commits_count": n,
                         "commits": commits,
                         "age": null,
+                        "release_pr": pending_for(&unit.user_facing_name).map(|pr| pr.number),
+                        "owners": owners,
+                        "scopes": scopes,
                     })
                 };
+                if let Some(truncation) = history.truncation() {
+                    unit_data["truncated"] = json!(truncation.to_string());
+                }
+                if as_of.is_some() {
+                    unit_data["version"] = json!(unit.version.to_string());
+                    unit_data["release_tag"] = json!(history.release_tag());
+                }
+                let analysis = &readiness[ident];
+                unit_data["bump"] = json!({
+                    "suggested": analysis.recommendation.as_str(),
+                    "reasons": analysis.drivers().collect::<Vec<_>>(),
+                });
+                if let Some((required, suggested)) = api_diff {
+                    unit_data["api_diff"] = json!({
+                        "required": required.as_str(),
+                        "commits": suggested.as_str(),
+                    });
+                }
 
                 projects.push(unit_data);
             }
 
-            let output = json!({
-                "projects": projects
+            let mut output = json!({
+                "projects": projects,
+                "release_prs": pending,
             });
+            if let Some(cid) = &as_of {
+                output["at"] = json!(cid.to_string());
+            }
+            if ci {
+                output["gates"] = json!({
+                    "passed": gate_failures.is_empty(),
+                    "failures": gate_failures,
+                });
+            }
 
             println!("{}", serde_json::to_string_pretty(&output)?);
         }
         _ => {
-            for ident in idents {
-                let unit = sess.graph().lookup(ident);
-                let history = histories.lookup(ident);
-                let n = history.n_commits();
-                let rel_info = history.release_info(&sess.repo)?;
-
-                if let Some(this_info) = rel_info.lookup_project(unit) {
-                    if this_info.age == 0 {
-                        if n == 0 {
-                            println!(
-                                "{}: no relevant commits since {}",
-                                unit.user_facing_name, this_info.version
-                            );
-                        } else {
-                            println!(
-                                "{}: {} relevant commit(s) since {}",
-                                unit.user_facing_name, n, this_info.version
-                            );
+            if let Some(cid) = as_of {
+                let sha = cid.to_string();
+                println!(
+                    "As of {} {}\n",
+                    &sha[..8],
+                    sess.repo.get_commit_summary(cid)?
+                );
+            }
+            let mut groups: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+            for ident in &idents {
+                let unit = sess.graph().lookup(*ident);
+                let ecosystem = unit
+                    .qualified_names()
+                    .get(1)
+                    .map(|s| Ecosystem::classify(s).display_name().to_string())
+                    .unwrap_or_default();
+                groups.entry(ecosystem).or_default().push(*ident);
+            }
+            let width = idents
+                .iter()
+                .map(|ident| sess.graph().lookup(*ident).user_facing_name.chars().count())
+                .max()
+                .unwrap_or(0);
+
+            for (ecosystem, mut members) in groups {
+                members.sort_by_key(|ident| {
+                    (
+                        readiness_rank(readiness[ident].recommendation),
+                        std::cmp::Reverse(histories.lookup(*ident).n_commits()),
+                        sess.graph().lookup(*ident).user_facing_name.clone(),
+                    )
+                });
+                println!("{ecosystem}");
+
+                for ident in members {
+                    let unit = sess.graph().lookup(ident);
+                    let history = histories.lookup(ident);
+                    let n = history.n_commits();
+                    let rel_info = history.release_info(&sess.repo)?;
+
+                    let since = match rel_info.lookup_project(unit) {
+                        Some(this_info) if this_info.age == 0 && n == 0 => {
+                            format!("no relevant commits since {}", this_info.version)
                         }
-                    } else {
+                        Some(this_info) if this_info.age == 0 => {
+                            format!("{} relevant commit(s) since {}", n, this_info.version)
+                        }
+                        Some(this_info) => format!(
+                            "no more than {} relevant commit(s) since {} (unable to track in detail)",
+                            n, this_info.version
+                        ),
+                        None => format!(
+                            "{} relevant commit(s) since start of history (no releases on record)",
+                            n
+                        ),
+                    };
+                    println!(
+                        "  {:<width$}  {:<8}  {}",
+                        unit.user_facing_name,
+                        readiness_label(readiness[&ident].recommendation),
+                        since
+                    );
+                    if let Some(line) = bump_reasons_line(&readiness[&ident]) {
+                        println!("      {line}");
+                    }
+                    if as_of.is_some() {
+                        match history.release_tag() {
+                            Some(tag) => println!("      at {}, released as {tag}", unit.version),
+                            None => println!("      at {}, no release tag yet", unit.version),
+                        }
+                    }
+
+                    if let Some(truncation) = history.truncation() {
                         println!(
-                            "{}: no more than {} relevant commit(s) since {} (unable to track in detail)",
-                            unit.user_facing_name, n, this_info.version
+                            "      analysis truncated ({truncation}); older commits were not considered"
                         );
                     }
-                } else {
-                    println!(
-                        "{}: {} relevant commit(s) since start of history (no releases on record)",
-                        unit.user_facing_name, n
-                    );
-                }
 
-                for (idx, cid) in history.commits().into_iter().enumerate() {
-                    let summary = sess.repo.get_commit_summary(*cid)?;
-                    println!("    {}. {}", idx + 1, summary);
-                }
+                    if let Some(pr) = pending_for(&unit.user_facing_name) {
+                        println!(
+                            "      release PR #{} open ({}, {})",
+                            pr.number,
+                            pr.checks_label(),
+                            pr.mergeable_label()
+                        );
+                    }
+
+                    let mut summaries = Vec::new();
+                    for (idx, cid) in history.commits().into_iter().enumerate() {
+                        let summary = sess.repo.get_commit_summary(*cid)?;
+                        println!("      {}. {}", idx + 1, summary);
+                        summaries.push(summary);
+                    }
 
-                if n > 0 {
-                    println!();
+                    let scopes = ScopeUsage::tally(
+                        summaries.iter().map(String::as_str),
+                        sess.allowed_scopes(ident),
+                    );
+                    if !scopes.scopes.is_empty() {
+                        println!("      scopes: {}", scopes.summary());
+                    }
+                    if !scopes.unknown.is_empty() {
+                        println!(
+                            "      not in the unit's scopes: {}",
+                            scopes.unknown.join(", ")
+                        );
+                    }
+                    if let Some((required, suggested)) =
+                        api_diff_advice(&sess, ident, unit, history)?
+                    {
+                        println!(
+                            "      API diff: {} (commits suggest {})",
+                            required.as_str(),
+                            suggested.as_str()
+                        );
+                    }
                 }
+                println!();
+            }
+
+            if let Some(pending) = pending.as_deref().filter(|p| !p.is_empty()) {
+                print_pending_releases(pending);
             }
         }
     }
 
+    if !gate_failures.is_empty() {
+        for failure in &gate_failures {
+            warn!(
+                "status gate `{}` failed for {}: {}",
+                failure.gate, failure.unit, failure.detail
+            );
+        }
+        return Ok(ExitCode::Precondition.into());
+    }
     Ok(0)
 }
+
+/// What the commits since the last release call for, unadjusted by
+/// `[bump]`: breaking changes, features, fixes, or nothing, and the
+/// commits behind it.
+fn release_readiness(sess: &AppSession, history: &RepoHistory) -> Result<BumpAnalysis> {
+    let commits = history
+        .commits()
+        .into_iter()
+        .map(|cid| sess.repo.get_commit_details(*cid))
+        .collect::<Result<Vec<_>, _>>()?;
+    bump::analyze_commits(&commits)
+}
+
+/// `suggests minor: 1a2b3c4, 2b3c4d5`, naming the commits behind the
+/// suggested bump; `None` when nothing calls for one.
+fn bump_reasons_line(analysis: &BumpAnalysis) -> Option<String> {
+    const MAX_IDS: usize = 5;
+    let drivers: Vec<_> = analysis.drivers().collect();
+    if drivers.is_empty() {
+        return None;
+    }
+    let mut ids: Vec<&str> = drivers
+        .iter()
+        .take(MAX_IDS)
+        .map(|reason| reason.commit.as_str())
+        .collect();
+    let more = drivers.len().saturating_sub(MAX_IDS);
+    let more = (more > 0).then(|| format!("+{more} more"));
+    ids.extend(more.as_deref());
+    Some(format!(
+        "suggests {}: {}",
+        analysis.recommendation.as_str(),
+        ids.join(", ")
+    ))
+}
+
+/// Most release-ready first.
+fn readiness_rank(readiness: BumpRecommendation) -> u8 {
+    match readiness {
+        BumpRecommendation::Major => 0,
+        BumpRecommendation::Minor => 1,
+        BumpRecommendation::Patch => 2,
+        BumpRecommendation::None => 3,
+    }
+}
+
+fn readiness_label(readiness: BumpRecommendation) -> &'static str {
+    match readiness {
+        BumpRecommendation::Major => "breaking",
+        BumpRecommendation::Minor => "features",
+        BumpRecommendation::Patch => "fixes",
+        BumpRecommendation::None => "-",
+    }
+}
+
+/// The bump the API diff requires for `unit` and the one its commits
+/// suggest, with `[checks] api_diff` enabled. `None` when the check is
+/// off, the unit isn't a released Rust crate, nothing changed since the
+/// release or the diff failed.
+fn api_diff_advice(
+    sess: &AppSession,
+    ident: usize,
+    unit: &ResolvedReleaseUnit,
+    history: &RepoHistory,
+) -> Result<Option<(BumpRecommendation, BumpRecommendation)>> {
+    if sess.checks().api_diff == ApiDiffMode::Off || history.n_commits() == 0 {
+        return Ok(None);
+    }
+    let Some(tag) = history.release_tag() else {
+        return Ok(None);
+    };
+    let required = match api_diff::check_unit(sess, ident, tag) {
+        Ok(Some(required)) => required,
+        Ok(None) => return Ok(None),
+        Err(e) => {
+            warn!("{}: skipping the API diff: {e:#}", unit.user_facing_name);
+            return Ok(None);
+        }
+    };
+
+    let commits = history
+        .commits()
+        .into_iter()
+        .map(|cid| sess.repo.get_commit_details(*cid))
+        .collect::<Result<Vec<_>, _>>()?;
+    let bump_config = BumpConfig::from_user_config(&sess.bump_config);
+    let current_version = unit.version.to_string();
+    let suggested = bump::analyze_commits(&commits)?
+        .recommendation
+        .apply_config(&bump_config, Some(&current_version));
+    let required = required.apply_config(&bump_config, Some(&current_version));
+    Ok(Some((required, suggested)))
+}
+
+/// The `[ci.status_gates]` that `unit`, last released as
+/// `released_version`, fails.
+fn check_status_gates(
+    sess: &AppSession,
+    unit: &ResolvedReleaseUnit,
+    history: &RepoHistory,
+    released_version: Option<String>,
+) -> Result<Vec<GateFailure>> {
+    let commits = history
+        .commits()
+        .into_iter()
+        .map(|cid| sess.repo.get_commit_details(*cid))
+        .collect::<Result<Vec<_>, _>>()?;
+    let changelog_config = ChangelogConfig::from_user_config(&sess.changelog_config);
+    let path = changelog_path(&changelog_config, &unit.prefix().escaped());
+    let changelog = sess.repo.read_to_string(path.as_ref()).ok();
+
+    let now = crate::core::deterministic::now_utc().unix_timestamp();
+    Ok(status_gates::check(
+        sess.status_gates(),
+        &UnitState {
+            name: &unit.user_facing_name,
+            commits: &commits,
+            released_version: released_version.as_deref(),
+            changelog: changelog.as_deref(),
+        },
+        now,
+    ))
+}
+
+fn print_pending_releases(pending: &[PendingRelease]) {
+    println!("Open release PRs:");
+    for pr in pending {
+        println!(
+            "  #{} {}",
+            pr.number,
+            pr.title.as_deref().unwrap_or(&pr.branch)
+        );
+        if let Some(url) = &pr.url {
+            println!("      {url}");
+        }
+        println!("      {}, {}", pr.checks_label(), pr.mergeable_label());
+        if !pr.groups.is_empty() {
+            println!("      groups: {}", pr.groups.join(", "));
+        }
+        if !pr.units.is_empty() {
+            println!("      units: {}", pr.units.join(", "));
+        }
+    }
+}
@@ -0,0 +1,402 @@
+//! `belaf tag` — create a merged release's tags locally.
+//!
+//! Run on the base branch after the release PR merged. `<release-id>` is
+//! the manifest id (any unique prefix of it will do) of a file in
+//! `belaf/releases/`. Each release's tag is created at `HEAD` as an
+//! annotated tag whose message is the release's changelog entry, so
+//! `git tag -l -n99` and tooling that reads tag messages see the release
+//! notes. Members of a group share one tag and one message. With
+//! `--sign`, or git's `tag.gpgSign`, the tags are signed like
+//! `git tag -s`; see
+//! [`crate::core::git::repository::Repository::create_annotated_tag`].
+//!
+//! Tags that already exist, e.g. because the GitHub App tagged the
+//! release first, are left alone. Nothing is pushed. Creating tags
+//! sends the `released` webhook; see [`crate::core::notify`]. With
+//! `[trackers.jira]`, each release's Jira fix version is created and
+//! set on the issues its commits mention; see [`crate::core::jira`].
+//! With `[release.milestones]`, each release's GitHub milestone is
+//! closed and its open issues move to the next one; see
+//! [`crate::core::github::client::roll_milestone`].
+//!
+//! When a release's unit has a `release_checklist`, the release PR must
+//! be merged with every step ticked before anything is tagged; see
+//! [`crate::core::checklist`]. Under `[release.approvers]` the release
+//! must be approved, its sign-offs backed by reviews of the release PR;
+//! see [`crate::core::approval`]. `--dry-run` skips both checks.
+
+use std::fmt::Write;
+
+use anyhow::{bail, Result};
+use owo_colors::OwoColorize;
+
+use crate::core::{
+    approval,
+    audit::{self, AuditEntry},
+    checklist,
+    config::syntax::{JiraConfiguration, MilestonesConfiguration, WebhookEvent},
+    github::{client as github, rest},
+    jira::{self, JiraClient},
+    manifest::{ReleaseEntry, ReleaseManifest},
+    notify, releases,
+    session::AppSession,
+};
+
+pub fn run(release_id: String, sign: bool, dry_run: bool) -> Result<i32> {
+    let sess = AppSession::initialize_default()?;
+    let (path, rel_path) = releases::find(&sess.repo, &release_id)?;
+    let manifest = releases::read(&path)?.manifest;
+    let sign = sign || sess.signs_tags();
+
+    let created = create_tags(&sess, &manifest, &rel_path, sign, dry_run)?;
+    if !created.is_empty() {
+        let entry = AuditEntry::new(&sess.repo, "tag")
+            .with_manifest(&manifest.manifest_id)
+            .with_tags(created.clone());
+        audit::append(&sess.repo, &entry)?;
+        println!("  Push them with: git push origin {}", created.join(" "));
+    }
+    let mut out = String::new();
+    announce(&sess, &manifest, &created, &[], dry_run, &mut out);
+    print!("{out}");
+    Ok(0)
+}
+
+/// The tags of `manifest` in order, each with the releases sharing it.
+pub(crate) fn tags_of(manifest: &ReleaseManifest) -> Vec<(&str, Vec<&ReleaseEntry>)> {
+    let mut tags: Vec<(&str, Vec<&ReleaseEntry>)> = Vec::new();
+    for release in &manifest.releases {
+        match tags.iter_mut().find(|(tag, _)| *tag == release.tag_name) {
+            Some((_, members)) => members.push(release),
+            None => tags.push((&release.tag_name, vec![release])),
+        }
+    }
+    tags
+}
+
+/// Create the missing tags of `manifest` at `HEAD`, once its checklist
+/// is done and it is approved. Returns the tags created.
+pub(crate) fn create_tags(
+    sess: &AppSession,
+    manifest: &ReleaseManifest,
+    rel_path: &str,
+    sign: bool,
+    dry_run: bool,
+) -> Result<Vec<String>> {
+    if !checklist::expected(sess, manifest).is_empty() && !dry_run {
+        let future = checklist::verify(sess, manifest, rel_path);
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => tokio::task::block_in_place(|| handle.block_on(future)),
+            Err(_) => tokio::runtime::Runtime::new()?.block_on(future),
+        }?;
+    }
+    let approvers = sess.config_approvers();
+    if approval::status(manifest.approval.as_ref(), approvers, None).required > 0 && !dry_run {
+        let future = approval::verify(&sess.repo, manifest, rel_path, approvers);
+        let status = match tokio::runtime::Handle::try_current() {
+            Ok(handle) => tokio::task::block_in_place(|| handle.block_on(future)),
+            Err(_) => tokio::runtime::Runtime::new()?.block_on(future),
+        }?;
+        if !status.is_approved() {
+            bail!(
+                "release {} has {} of {} required approval(s); run `belaf approve {} --check` for details",
+                manifest.manifest_id,
+                status.approved_by.len(),
+                status.required,
+                manifest.manifest_id
+            );
+        }
+    }
+
+    let tags = tags_of(manifest);
+    let mut created = Vec::new();
+    for (tag, members) in &tags {
+        if sess.repo.tag_exists(tag) {
+            println!("{} {tag} already exists, skipped", "•".dimmed());
+            continue;
+        }
+        let message = tag_message(members);
+        if dry_run {
+            println!("{} {tag}\n", "ℹ".cyan().bold());
+            println!("{message}\n");
+            continue;
+        }
+        sess.repo
+            .create_annotated_tag(tag, "HEAD", &message, sign)?;
+        println!(
+            "{} Tagged {tag}{}",
+            "✓".green().bold(),
+            if sign { " (signed)" } else { "" }
+        );
+        created.push(tag.to_string());
+    }
+    if tags.is_empty() {
+        println!("{} {rel_path} lists no releases", "ℹ".cyan().bold());
+    }
+    Ok(created)
+}
+
+/// Tell the world `tags` of `manifest` were released: the `released`
+/// webhook, then Jira and milestones as configured. The releases named
+/// in `on_registry` are flagged as already on their package registry.
+/// What happened is appended to `out`.
+pub(crate) fn announce(
+    sess: &AppSession,
+    manifest: &ReleaseManifest,
+    tags: &[String],
+    on_registry: &[String],
+    dry_run: bool,
+    out: &mut String,
+) {
+    if !tags.is_empty() {
+        let releases: Vec<serde_json::Value> = manifest
+            .releases
+            .iter()
+            .filter(|r| tags.contains(&r.tag_name))
+            .map(|r| {
+                serde_json::json!({
+                    "name": r.name,
+                    "previous_version": r.previous_version,
+                    "new_version": r.new_version,
+                    "tag": r.tag_name,
+                    "on_registry": on_registry.contains(&r.name),
+                })
+            })
+            .collect();
+        notify::emit(
+            sess.webhooks(),
+            WebhookEvent::Released,
+            serde_json::json!({
+                "manifest_id": manifest.manifest_id,
+                "tags": tags,
+                "releases": releases,
+            }),
+        );
+    }
+
+    if let Some(jira) = &sess.trackers().jira {
+        update_jira(sess, jira, &manifest.releases, dry_run, out);
+    }
+    if let Some(milestones) = sess.config_milestones() {
+        update_milestones(sess, milestones, &manifest.releases, dry_run, out);
+    }
+}
+
+/// Roll over the GitHub milestone of each of `releases`; see
+/// [`github::roll_milestone`]. Like Jira, problems are only reported.
+fn update_milestones(
+    sess: &AppSession,
+    cfg: &MilestonesConfiguration,
+    releases: &[ReleaseEntry],
+    dry_run: bool,
+    out: &mut String,
+) {
+    let mut titles: Vec<(String, Option<String>)> = Vec::new();
+    for release in releases {
+        let pair = github::milestone_titles(cfg, release);
+        if !titles.contains(&pair) {
+            titles.push(pair);
+        }
+    }
+
+    if dry_run {
+        for (title, next) in &titles {
+            let _ = writeln!(
+                out,
+                "{} Milestone `{title}` would be closed{}",
+                "ℹ".cyan().bold(),
+                next.as_ref()
+                    .map(|next| format!(", its open issues moved to `{next}`"))
+                    .unwrap_or_default()
+            );
+        }
+        return;
+    }
+
+    let (client, owner, repo) = match rest::for_upstream(&sess.repo) {
+        Ok(upstream) => upstream,
+        Err(e) => {
+            let _ = writeln!(out, "{} Milestones not updated: {e:#}", "⚠".yellow().bold());
+            return;
+        }
+    };
+    for (title, next) in &titles {
+        let _ = match github::roll_milestone(&client, &owner, &repo, title, next.as_deref()) {
+            Ok(Some(report)) => writeln!(
+                out,
+                "{} Milestone `{title}` closed{}",
+                "✓".green().bold(),
+                match next {
+                    Some(next) => format!(
+                        ", {} open issue(s) moved to `{next}`{}",
+                        report.moved,
+                        if report.created { " (created)" } else { "" }
+                    ),
+                    None => String::new(),
+                }
+            ),
+            Ok(None) => writeln!(
+                out,
+                "{} No open milestone `{title}` to close",
+                "ℹ".cyan().bold()
+            ),
+            Err(e) => writeln!(
+                out,
+                "{} Milestone `{title}` not closed: {e:#}",
+                "⚠".yellow().bold()
+            ),
+        };
+    }
+}
+
+/// Record `releases` in Jira; see [`jira`]. Problems are reported, not
+/// returned: the tags exist by now, and running `belaf tag` again
+/// retries.
+fn update_jira(
+    sess: &AppSession,
+    cfg: &JiraConfiguration,
+    releases: &[ReleaseEntry],
+    dry_run: bool,
+    out: &mut String,
+) {
+    let client = if dry_run {
+        None
+    } else {
+        match JiraClient::new(cfg) {
+            Ok(client) => Some(client),
+            Err(e) => {
+                let _ = writeln!(out, "{} Jira not updated: {e:#}", "⚠".yellow().bold());
+                return;
+            }
+        }
+    };
+
+    for release in releases {
+        let version = jira::version_name(cfg, release);
+        let messages: Vec<String> = release
+            .commits
+            .iter()
+            .filter_map(|sha| sess.repo.commit_message_of(sha))
+            .collect();
+        let issues = jira::issue_keys(&cfg.project, messages.iter().map(String::as_str));
+        let Some(client) = &client else {
+            let _ = writeln!(
+                out,
+                "{} Jira: fix version `{version}` for {}",
+                "ℹ".cyan().bold(),
+                if issues.is_empty() {
+                    "no issues".to_string()
+                } else {
+                    issues.join(", ")
+                }
+            );
+            continue;
+        };
+
+        match jira::sync_release(client, cfg, &version, &issues) {
+            Ok(report) => {
+                let _ = writeln!(
+                    out,
+                    "{} Jira fix version `{version}`{} on {} issue(s){}",
+                    "✓".green().bold(),
+                    if report.created { " created" } else { "" },
+                    report.updated.len(),
+                    match jira::transition_name(cfg) {
+                        Some(target) if !report.transitioned.is_empty() => {
+                            format!(", {} moved to {target}", report.transitioned.len())
+                        }
+                        _ => String::new(),
+                    }
+                );
+                for (issue, reason) in &report.failed {
+                    let _ = writeln!(out, "  {} {issue}: {reason}", "⚠".yellow().bold());
+                }
+            }
+            Err(e) => {
+                let _ = writeln!(
+                    out,
+                    "{} Jira fix version `{version}` not recorded: {e:#}",
+                    "⚠".yellow().bold()
+                );
+            }
+        }
+    }
+}
+
+/// The message of a tag shared by `members`: a subject naming each
+/// member and its version, then their changelog entries.
+fn tag_message(members: &[&ReleaseEntry]) -> String {
+    let subject = members
+        .iter()
+        .map(|r| format!("{} {}", r.name, r.new_version))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let notes = members_notes(members);
+    if notes.is_empty() {
+        subject
+    } else {
+        format!("{subject}\n\n{notes}")
+    }
+}
+
+/// The changelog entries of `members`, one after the other.
+pub(crate) fn members_notes(members: &[&ReleaseEntry]) -> String {
+    members
+        .iter()
+        .map(|release| release_notes(&release.changelog))
+        .filter(|entry| !entry.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// The entry in a rendered changelog, from its `## ` heading on (the
+/// template's title dropped), with runs of blank lines collapsed.
+fn release_notes(changelog: &str) -> String {
+    let start = changelog
+        .match_indices("## ")
+        .find(|(i, _)| *i == 0 || changelog.as_bytes()[i - 1] == b'\n')
+        .map_or(0, |(i, _)| i);
+    let mut notes = String::new();
+    for line in changelog[start..].lines().map(str::trim_end) {
+        if line.is_empty() && (notes.is_empty() || notes.ends_with("\n\n")) {
+            continue;
+        }
+        notes.push_str(line);
+        notes.push('\n');
+    }
+    notes.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn release(name: &str, changelog: &str) -> ReleaseEntry {
+        ReleaseEntry::new(
+            name.into(),
+            "cargo".into(),
+            "1.2.0".into(),
+            "1.3.0".into(),
+            "minor".into(),
+            changelog.into(),
+            String::new(),
+        )
+    }
+
+    #[test]
+    fn tag_messages_hold_every_members_changelog_entry() {
+        let api = release(
+            "api",
+            "# Changelog\n\n## [1.3.0] - 2026-10-16\n\n\n### Features\n\n- x\n\n\n",
+        );
+        let wasm = release("api-wasm", "");
+        assert_eq!(
+            tag_message(&[&api]),
+            "api 1.3.0\n\n## [1.3.0] - 2026-10-16\n\n### Features\n\n- x"
+        );
+        assert_eq!(
+            tag_message(&[&api, &wasm]),
+            "api 1.3.0, api-wasm 1.3.0\n\n## [1.3.0] - 2026-10-16\n\n### Features\n\n- x"
+        );
+    }
+}
@@ -0,0 +1,389 @@
+//! `belaf verify-tag` — check a release tag the way a consumer or an
+//! auditor would.
+//!
+//! The tag's release units are the ones whose tag format it matches, so
+//! group tags cover every member. For the commit the tag points at, it
+//! checks:
+//!
+//! * `signature`: the tag is signed and `git verify-tag` accepts it; see
+//!   [`crate::core::git::repository::Repository::verify_tag_signature`].
+//! * `version`: each unit's `Cargo.toml`, `package.json` or
+//!   `pyproject.toml` says the tag's version. Other ecosystems are
+//!   skipped.
+//! * `changelog`: each unit's changelog has an entry for the version.
+//! * `artifacts`, with `--artifacts DIR`: every file in `DIR` is one the
+//!   release recorded when it was cut, with the same SHA-256; see
+//!   [`publication::artifact_problems`].
+//!
+//! Snapshot tags are matched by the release tag inside their namespace
+//! (see [`snapshot::TAG_NAMESPACE`]), and have no changelog to check.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use owo_colors::OwoColorize;
+use serde::Serialize;
+
+use crate::cli::VerifyTagOutputFormat;
+use crate::core::{
+    changelog::ChangelogConfig,
+    exit_code::ExitCode,
+    git::repository::{CommitId, RepoPathBuf, TagSignature},
+    publication,
+    release_unit::VersionFieldSpec,
+    releases,
+    resolved_release_unit::ResolvedReleaseUnit,
+    session::AppSession,
+    snapshot, status_gates, version_field,
+    wire::known::Ecosystem,
+    workflow::changelog_path,
+};
+
+#[derive(Serialize)]
+struct Report {
+    tag: String,
+    commit: String,
+    /// False if any check is `error`.
+    ok: bool,
+    units: Vec<TaggedUnit>,
+    checks: Vec<Check>,
+}
+
+#[derive(Serialize)]
+struct TaggedUnit {
+    name: String,
+    version: String,
+}
+
+#[derive(Serialize)]
+struct Check {
+    name: &'static str,
+    /// `ok`, `error` or `skipped`.
+    status: &'static str,
+    summary: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    detail: Option<String>,
+}
+
+impl Check {
+    fn new(name: &'static str, status: &'static str, summary: impl Into<String>) -> Self {
+        Self {
+            name,
+            status,
+            summary: summary.into(),
+            detail: None,
+        }
+    }
+
+    fn with_detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+}
+
+pub fn run(
+    tag: String,
+    artifacts: Option<PathBuf>,
+    allow_unsigned: bool,
+    format: Option<VerifyTagOutputFormat>,
+) -> Result<i32> {
+    let sess = AppSession::initialize_default()?;
+    if !sess.repo.tag_exists(&tag) {
+        bail!("no tag `{tag}`; fetch tags first with `git fetch --tags`");
+    }
+    let cid = sess.repo.resolve_commitish(&tag)?;
+
+    let release_tag = snapshot::release_tag(&tag);
+    let graph = sess.graph();
+    let mut units = Vec::new();
+    for unit in graph.projects() {
+        let matcher = sess.tag_matcher(unit.ident())?;
+        if let Some(version) = matcher.match_version(release_tag.unwrap_or(&tag)) {
+            units.push((unit, version.to_string()));
+        }
+    }
+    if units.is_empty() {
+        bail!("`{tag}` is not a release tag of any release unit");
+    }
+
+    let mut checks = vec![check_signature(&sess, &tag, allow_unsigned)?];
+    for (unit, version) in &units {
+        checks.push(check_version(&sess, &cid, unit, version));
+        checks.push(match release_tag {
+            Some(_) => Check::new(
+                "changelog",
+                "skipped",
+                format!("{}: a snapshot, which has no entry", unit.user_facing_name),
+            ),
+            None => check_changelog(&sess, &cid, unit, version),
+        });
+    }
+    checks.push(match &artifacts {
+        Some(dir) => check_artifacts(&sess, &tag, dir)?,
+        None => Check::new("artifacts", "skipped", "not checked; pass --artifacts DIR"),
+    });
+
+    let report = Report {
+        ok: checks.iter().all(|c| c.status != "error"),
+        commit: cid.to_string(),
+        units: units
+            .iter()
+            .map(|(unit, version)| TaggedUnit {
+                name: unit.user_facing_name.clone(),
+                version: version.clone(),
+            })
+            .collect(),
+        tag,
+        checks,
+    };
+
+    if format == Some(VerifyTagOutputFormat::Json) {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        println!(
+            "{} {} {}",
+            report.tag.bold(),
+            (&report.commit[..8]).yellow(),
+            format!(
+                "({})",
+                report
+                    .units
+                    .iter()
+                    .map(|u| format!("{} {}", u.name, u.version))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+            .dimmed()
+        );
+        for check in &report.checks {
+            let icon = match check.status {
+                "ok" => "✓".green().bold().to_string(),
+                "error" => "✗".red().bold().to_string(),
+                _ => "·".dimmed().to_string(),
+            };
+            println!("  {icon} {:9}  {}", check.name, check.summary);
+            if let Some(detail) = &check.detail {
+                for line in detail.lines() {
+                    println!("      {}", line.dimmed());
+                }
+            }
+        }
+    }
+
+    Ok(if report.ok {
+        0
+    } else {
+        ExitCode::Precondition.into()
+    })
+}
+
+fn check_signature(sess: &AppSession, tag: &str, allow_unsigned: bool) -> Result<Check> {
+    let unsigned = |summary: &str| {
+        if allow_unsigned {
+            Check::new(
+                "signature",
+                "skipped",
+                format!("{summary}; allowed by --allow-unsigned"),
+            )
+        } else {
+            Check::new("signature", "error", summary)
+        }
+    };
+    Ok(match sess.repo.verify_tag_signature(tag)? {
+        TagSignature::Good(signer) => Check::new("signature", "ok", signer),
+        TagSignature::Bad(report) => {
+            Check::new("signature", "error", "the signature does not verify").with_detail(report)
+        }
+        TagSignature::Unsigned => unsigned("the tag is not signed"),
+        TagSignature::Lightweight => unsigned("a lightweight tag, which can't be signed"),
+    })
+}
+
+/// The file, read as `spec` says, that holds the version of units of
+/// `ecosystem`.
+fn version_file(ecosystem: &Ecosystem) -> Option<(&'static str, VersionFieldSpec)> {
+    match ecosystem.as_str() {
+        "cargo" => Some(("Cargo.toml", VersionFieldSpec::CargoToml)),
+        "npm" => Some(("package.json", VersionFieldSpec::NpmPackageJson)),
+        "pypa" => Some(("pyproject.toml", VersionFieldSpec::Pep621)),
+        _ => None,
+    }
+}
+
+fn check_version(
+    sess: &AppSession,
+    cid: &CommitId,
+    unit: &ResolvedReleaseUnit,
+    version: &str,
+) -> Check {
+    let ecosystem = unit
+        .qualified_names()
+        .get(1)
+        .map(|s| Ecosystem::classify(s))
+        .unwrap_or_else(|| Ecosystem::classify("cargo"));
+    let name = &unit.user_facing_name;
+    let Some((file, spec)) = version_file(&ecosystem) else {
+        return Check::new(
+            "version",
+            "skipped",
+            format!("{name}: not read for {}", ecosystem.display_name()),
+        );
+    };
+
+    let prefix = unit.prefix().escaped();
+    let mut paths = vec![join(&prefix, file)];
+    // Workspace members may inherit the version from the root.
+    if matches!(spec, VersionFieldSpec::CargoToml) && !prefix.is_empty() {
+        paths.push(file.to_string());
+    }
+    let mut problems = Vec::new();
+    for path in &paths {
+        match version_at(sess, cid, path, &spec) {
+            Ok(found) if found == version => {
+                return Check::new("version", "ok", format!("{name}: {path} says {found}"));
+            }
+            Ok(found) => {
+                return Check::new(
+                    "version",
+                    "error",
+                    format!("{name}: {path} says {found}, the tag {version}"),
+                );
+            }
+            Err(e) => problems.push(format!("{e:#}")),
+        }
+    }
+    Check::new(
+        "version",
+        "error",
+        format!("{name}: no version found at the tag"),
+    )
+    .with_detail(problems.join("\n"))
+}
+
+/// The version `spec` reads from the file at `path` as of `cid`.
+fn version_at(
+    sess: &AppSession,
+    cid: &CommitId,
+    path: &str,
+    spec: &VersionFieldSpec,
+) -> Result<String> {
+    let data = sess
+        .repo
+        .get_file_at_commit(cid, &RepoPathBuf::new(path.as_bytes()))?
+        .with_context(|| format!("`{path}` is not in the tagged commit"))?;
+    let content = String::from_utf8(data).with_context(|| format!("`{path}` is not UTF-8"))?;
+    version_field::read_str(spec, Path::new(path), &content)
+        .with_context(|| format!("failed to read the version in `{path}`"))
+}
+
+fn check_changelog(
+    sess: &AppSession,
+    cid: &CommitId,
+    unit: &ResolvedReleaseUnit,
+    version: &str,
+) -> Check {
+    let name = &unit.user_facing_name;
+    let changelog_config = ChangelogConfig::from_user_config(&sess.changelog_config);
+    let path = changelog_path(&changelog_config, &unit.prefix().escaped());
+    let text = match sess.repo.get_file_at_commit(cid, &path) {
+        Ok(Some(data)) => String::from_utf8_lossy(&data).into_owned(),
+        Ok(None) => {
+            return Check::new(
+                "changelog",
+                "error",
+                format!("{name}: {} is not in the tagged commit", path.escaped()),
+            )
+        }
+        Err(e) => {
+            return Check::new(
+                "changelog",
+                "error",
+                format!("{name}: failed to read {}", path.escaped()),
+            )
+            .with_detail(format!("{e:#}"))
+        }
+    };
+    if status_gates::has_entry_for(&text, version) {
+        Check::new(
+            "changelog",
+            "ok",
+            format!("{name}: {} has an entry for {version}", path.escaped()),
+        )
+    } else {
+        Check::new(
+            "changelog",
+            "error",
+            format!("{name}: {} has no entry for {version}", path.escaped()),
+        )
+    }
+}
+
+fn check_artifacts(sess: &AppSession, tag: &str, dir: &Path) -> Result<Check> {
+    let mut recorded = None;
+    for path in releases::list(&sess.repo)? {
+        let manifest = releases::read_in(&sess.repo, &path)?.manifest;
+        let release = manifest
+            .publication
+            .iter()
+            .flat_map(|p| &p.releases)
+            .find(|r| r.tag_name == tag);
+        if let Some(release) = release {
+            recorded = Some(release.artifacts.clone());
+            break;
+        }
+    }
+    let Some(recorded) = recorded else {
+        return Ok(Check::new(
+            "artifacts",
+            "error",
+            format!("no release in belaf/releases/ records the artifacts of {tag}"),
+        )
+        .with_detail("only releases shipped with `belaf release cut` record them"));
+    };
+
+    let entries =
+        fs::read_dir(dir).with_context(|| format!("failed to read `{}`", dir.display()))?;
+    let mut found = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path.is_file() {
+            found.push(publication::artifact(&path)?);
+        }
+    }
+    if found.is_empty() {
+        return Ok(Check::new(
+            "artifacts",
+            "error",
+            format!("no files in `{}`", dir.display()),
+        ));
+    }
+
+    let problems = publication::artifact_problems(&recorded, &found);
+    Ok(if problems.is_empty() {
+        Check::new(
+            "artifacts",
+            "ok",
+            format!(
+                "{} of {} recorded artifact(s) match",
+                found.len(),
+                recorded.len()
+            ),
+        )
+    } else {
+        Check::new(
+            "artifacts",
+            "error",
+            format!("{} file(s) don't match the release", problems.len()),
+        )
+        .with_detail(problems.join("\n"))
+    })
+}
+
+fn join(prefix: &str, file: &str) -> String {
+    if prefix.is_empty() {
+        file.to_string()
+    } else {
+        format!("{}/{file}", prefix.trim_end_matches('/'))
+    }
+}
@@ -0,0 +1,120 @@
+//! `belaf which-release` — the first release of each project that
+//! contains a commit.
+//!
+//! For every release unit (or just `<project>`), the unit's release tags
+//! are walked oldest version first and the first one `<commit>` is an
+//! ancestor of is reported; see
+//! [`crate::core::git::repository::Repository::find_earliest_release_containing`].
+//! Units none of whose tags contain the commit are either unreleased
+//! (the commit is on `HEAD`, so their next release ships it) or don't
+//! have it on this branch at all.
+
+use anyhow::{Context, Result};
+use owo_colors::OwoColorize;
+use serde::Serialize;
+
+use crate::cli::WhichReleaseOutputFormat;
+use crate::core::{
+    git::repository::ReleaseAvailability, resolved_release_unit::ResolvedReleaseUnit,
+    session::AppSession,
+};
+
+#[derive(Serialize)]
+struct Report {
+    commit: String,
+    summary: Option<String>,
+    projects: Vec<ProjectRelease>,
+}
+
+#[derive(Serialize)]
+struct ProjectRelease {
+    name: String,
+    /// `released`, `unreleased` or `not_on_branch`.
+    status: &'static str,
+    version: Option<String>,
+    tag: Option<String>,
+}
+
+pub fn run(
+    commit: String,
+    project: Option<String>,
+    format: Option<WhichReleaseOutputFormat>,
+) -> Result<i32> {
+    let sess = AppSession::initialize_default()?;
+    let cid = sess.repo.resolve_commitish(&commit)?;
+    let graph = sess.graph();
+
+    let mut units: Vec<&ResolvedReleaseUnit> = match &project {
+        Some(name) => {
+            let ident = graph.lookup_ident(name).with_context(|| {
+                let mut names: Vec<_> = graph
+                    .projects()
+                    .map(|u| u.user_facing_name.as_str())
+                    .collect();
+                names.sort_unstable();
+                format!("no release unit `{name}`; known: {}", names.join(", "))
+            })?;
+            vec![graph.lookup(ident)]
+        }
+        None => graph.projects().collect(),
+    };
+    units.sort_by(|a, b| a.user_facing_name.cmp(&b.user_facing_name));
+
+    let mut projects = Vec::new();
+    for unit in units {
+        let matcher = sess.tag_matcher(unit.ident())?;
+        let availability = sess
+            .repo
+            .find_earliest_release_containing(unit, &matcher, &cid)?;
+        let (status, version, tag) = match availability {
+            ReleaseAvailability::ExistingRelease(version, tag) => {
+                ("released", Some(version.to_string()), Some(tag))
+            }
+            ReleaseAvailability::NewRelease => ("unreleased", None, None),
+            ReleaseAvailability::NotAvailable => ("not_on_branch", None, None),
+        };
+        projects.push(ProjectRelease {
+            name: unit.user_facing_name.clone(),
+            status,
+            version,
+            tag,
+        });
+    }
+
+    let sha = cid.to_string();
+    let report = Report {
+        summary: sess.repo.commit_summary_of(&sha),
+        commit: sha,
+        projects,
+    };
+
+    if format == Some(WhichReleaseOutputFormat::Json) {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(0);
+    }
+
+    println!(
+        "{} {}",
+        (&report.commit[..8]).yellow(),
+        report.summary.as_deref().unwrap_or_default()
+    );
+    let width = report
+        .projects
+        .iter()
+        .map(|p| p.name.len())
+        .max()
+        .unwrap_or(0);
+    for p in &report.projects {
+        let found = match p.status {
+            "released" => format!(
+                "{} {}",
+                p.version.as_deref().unwrap_or_default().green().bold(),
+                format!("({})", p.tag.as_deref().unwrap_or_default()).dimmed()
+            ),
+            "unreleased" => "unreleased; the next release ships it".yellow().to_string(),
+            _ => "not on this branch".dimmed().to_string(),
+        };
+        println!("  {:width$}  {found}", p.name);
+    }
+    Ok(0)
+}
@@ -2,6 +2,7 @@ pub mod client;
 pub mod error;
 pub mod generated;
 pub mod oidc;
+pub mod retry;
 pub mod types;
 
 pub use client::ApiClient;
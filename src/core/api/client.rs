@@ -1,10 +1,15 @@
 use reqwest::{Client, Response, StatusCode};
 use serde::de::DeserializeOwned;
 use serde::Deserialize;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
+use tracing::warn;
 
 use super::error::ApiError;
 use super::oidc::fetch_actions_oidc_jwt;
+use super::retry::{header_u64, rate_limit_wait, RetryPolicy};
+use crate::core::github::forge;
+
 use super::types::{
     ApiCommit, ApiPullRequest, CheckInstallationResponse, CommitsResponse, CreatePullRequestParams,
     CreatePullRequestRequest, CreatePullRequestResponse, DeviceCodeRequest, DeviceCodeResponse,
@@ -26,10 +31,34 @@ const CLIENT_ID: &str = "belaf-cli";
 const DEVICE_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:device_code";
 const DEFAULT_TIMEOUT_SECS: u64 = 30;
 
-#[derive(Debug)]
+/// Rate-limit headers from the most recent API response. The belaf API
+/// forwards GitHub's `x-ratelimit-*` headers on proxied calls.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RateLimitInfo {
+    pub limit: Option<u64>,
+    pub remaining: Option<u64>,
+    /// Unix timestamp (seconds) at which the window resets.
+    pub reset_at: Option<u64>,
+}
+
+impl RateLimitInfo {
+    fn from_headers(headers: &reqwest::header::HeaderMap) -> Option<Self> {
+        let info = Self {
+            limit: header_u64(headers, "x-ratelimit-limit"),
+            remaining: header_u64(headers, "x-ratelimit-remaining"),
+            reset_at: header_u64(headers, "x-ratelimit-reset"),
+        };
+        (info != Self::default()).then_some(info)
+    }
+}
+
+/// Clones share the underlying connection pool and rate-limit state.
+#[derive(Clone, Debug)]
 pub struct ApiClient {
     client: Client,
     base_url: String,
+    retry: RetryPolicy,
+    rate_limit: Arc<Mutex<Option<RateLimitInfo>>>,
 }
 
 impl ApiClient {
@@ -52,7 +81,7 @@ impl ApiClient {
     /// Prefer this over `new()` when you need to handle client creation failures
     /// gracefully.
     pub fn try_new() -> Result<Self, ApiError> {
-        let client = Client::builder()
+        let client = crate::core::net::client_builder()?
             .timeout(Duration::from_secs(DEFAULT_TIMEOUT_SECS))
             .build()
             .map_err(|e| ApiError::ClientCreation(e.to_string()))?;
@@ -61,7 +90,12 @@ impl ApiClient {
 
         validate_api_url(&base_url)?;
 
-        Ok(Self { client, base_url })
+        Ok(Self {
+            client,
+            base_url,
+            retry: crate::core::net::retry_policy(),
+            rate_limit: Arc::default(),
+        })
     }
 
     /// Creates a new API client with a custom base URL.
@@ -69,7 +103,7 @@ impl ApiClient {
     /// Primarily used for testing with mock servers.
     #[cfg(test)]
     pub fn with_base_url(base_url: &str) -> Result<Self, ApiError> {
-        let client = Client::builder()
+        let client = crate::core::net::client_builder()?
             .timeout(Duration::from_secs(DEFAULT_TIMEOUT_SECS))
             .build()
             .map_err(|e| ApiError::ClientCreation(e.to_string()))?;
@@ -77,24 +111,91 @@ impl ApiClient {
         Ok(Self {
             client,
             base_url: base_url.to_string(),
+            retry: RetryPolicy {
+                base_delay: Duration::from_millis(1),
+                ..RetryPolicy::default()
+            },
+            rate_limit: Arc::default(),
         })
     }
 
+    /// Rate-limit headers seen on the most recent response, if the
+    /// server sent any.
+    pub fn rate_limit(&self) -> Option<RateLimitInfo> {
+        self.rate_limit.lock().ok().and_then(|g| g.clone())
+    }
+
+    /// Sends `request`, retrying rate-limited and transiently failing
+    /// calls according to the [`RetryPolicy`].
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<Response, ApiError> {
+        let request = forge::apply_headers(request).build()?;
+        let method = request.method().clone();
+        let mut attempt = 0;
+
+        loop {
+            // Bodies here are always buffered JSON, so cloning only fails
+            // for streaming bodies — send those once, without retries.
+            let Some(this_try) = request.try_clone() else {
+                return Ok(self.client.execute(request).await?);
+            };
+
+            let delay = match self.client.execute(this_try).await {
+                Ok(response) => {
+                    if let Some(info) = RateLimitInfo::from_headers(response.headers()) {
+                        if let Ok(mut slot) = self.rate_limit.lock() {
+                            *slot = Some(info);
+                        }
+                    }
+                    match self.retry.delay_for_response(
+                        &method,
+                        response.status(),
+                        response.headers(),
+                        attempt,
+                    ) {
+                        Some(delay) => {
+                            warn!(
+                                "{} {} returned {}; retrying in {:.1}s",
+                                method,
+                                request.url().path(),
+                                response.status(),
+                                delay.as_secs_f64()
+                            );
+                            delay
+                        }
+                        None => return Ok(response),
+                    }
+                }
+                Err(err) => match self.retry.delay_for_error(&method, &err, attempt) {
+                    Some(delay) => {
+                        warn!(
+                            "{} {} failed ({}); retrying in {:.1}s",
+                            method,
+                            request.url().path(),
+                            err,
+                            delay.as_secs_f64()
+                        );
+                        delay
+                    }
+                    None => return Err(err.into()),
+                },
+            };
+
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
     /// Handles common response processing: status code checking and JSON deserialization.
     async fn handle_response<T: DeserializeOwned>(response: Response) -> Result<T, ApiError> {
         if response.status() == StatusCode::UNAUTHORIZED {
             return Err(ApiError::Unauthorized);
         }
 
-        if response.status() == StatusCode::TOO_MANY_REQUESTS {
-            let retry_after = response
-                .headers()
-                .get("retry-after")
-                .and_then(|v| v.to_str().ok())
-                .and_then(|v| v.parse::<u64>().ok())
-                .unwrap_or(60);
+        // 429, and GitHub's 403-flavoured primary/secondary rate limits
+        // that were still in force after the retry budget ran out.
+        if let Some(wait) = rate_limit_wait(response.status(), response.headers()) {
             return Err(ApiError::RateLimited {
-                retry_after_secs: retry_after,
+                retry_after_secs: wait.map_or(60, |d| d.as_secs()),
             });
         }
 
@@ -140,15 +241,14 @@ impl ApiClient {
     /// Returns [`ApiError::ApiResponse`] if the API request fails, or
     /// [`ApiError::Request`] for network errors.
     pub async fn request_device_code(&self) -> Result<DeviceCodeResponse, ApiError> {
-        let response = self
+        let request = self
             .client
             .post(format!("{}/api/auth/device/code", self.base_url))
             .json(&DeviceCodeRequest {
                 client_id: CLIENT_ID.to_string(),
                 scope: "cli".to_string(),
-            })
-            .send()
-            .await?;
+            });
+        let response = self.send(request).await?;
 
         Self::handle_response(response).await
     }
@@ -164,16 +264,15 @@ impl ApiClient {
     /// - An access token on success
     /// - An error code like "authorization_pending" or "slow_down"
     pub async fn poll_for_token(&self, device_code: &str) -> Result<TokenPollResponse, ApiError> {
-        let response = self
+        let request = self
             .client
             .post(format!("{}/api/auth/device/token", self.base_url))
             .json(&TokenPollRequest {
                 client_id: CLIENT_ID.to_string(),
                 device_code: device_code.to_string(),
                 grant_type: DEVICE_GRANT_TYPE.to_string(),
-            })
-            .send()
-            .await?;
+            });
+        let response = self.send(request).await?;
 
         Ok(response.json().await?)
     }
@@ -189,12 +288,11 @@ impl ApiClient {
     /// The returned `StoredToken` should not be persisted to the keyring —
     /// it is short-lived and tied to the specific repo/run.
     pub async fn exchange_oidc_token(&self, oidc_jwt: String) -> Result<StoredToken, ApiError> {
-        let response = self
+        let request = self
             .client
             .post(format!("{}/api/cli/auth/oidc/exchange", self.base_url))
-            .json(&OidcExchangeRequest { token: oidc_jwt })
-            .send()
-            .await?;
+            .json(&OidcExchangeRequest { token: oidc_jwt });
+        let response = self.send(request).await?;
 
         let parsed: OidcExchangeResponse = Self::handle_response(response).await?;
 
@@ -246,13 +344,12 @@ impl ApiClient {
         token: &StoredToken,
         repo: &str,
     ) -> Result<CheckInstallationResponse, ApiError> {
-        let response = self
+        let request = self
             .client
             .get(format!("{}/api/cli/check-installation", self.base_url))
             .query(&[("repo", repo)])
-            .bearer_auth(&token.access_token)
-            .send()
-            .await?;
+            .bearer_auth(&token.access_token);
+        let response = self.send(request).await?;
 
         Self::handle_response(response).await
     }
@@ -263,12 +360,11 @@ impl ApiClient {
     ///
     /// Returns [`ApiError::Unauthorized`] if the token is invalid or expired.
     pub async fn get_user_info(&self, token: &StoredToken) -> Result<UserInfo, ApiError> {
-        let response = self
+        let request = self
             .client
             .get(format!("{}/api/cli/me", self.base_url))
-            .bearer_auth(&token.access_token)
-            .send()
-            .await?;
+            .bearer_auth(&token.access_token);
+        let response = self.send(request).await?;
 
         Self::handle_response(response).await
     }
@@ -298,12 +394,8 @@ impl ApiClient {
             url.push_str(&format!("&ref={}", r));
         }
 
-        let response = self
-            .client
-            .get(&url)
-            .bearer_auth(&token.access_token)
-            .send()
-            .await?;
+        let request = self.client.get(&url).bearer_auth(&token.access_token);
+        let response = self.send(request).await?;
 
         let result: CommitsResponse = Self::handle_response(response).await?;
         Ok(result.commits)
@@ -317,19 +409,42 @@ impl ApiClient {
         repo: &str,
         page: u32,
         per_page: u32,
+    ) -> Result<Vec<ApiPullRequest>, ApiError> {
+        self.list_pull_requests(token, owner, repo, "closed", page, per_page)
+            .await
+    }
+
+    /// Gets open pull requests for a repository with pagination, including
+    /// their head branch, check status and mergeability.
+    pub async fn get_open_pull_requests(
+        &self,
+        token: &StoredToken,
+        owner: &str,
+        repo: &str,
+        page: u32,
+        per_page: u32,
+    ) -> Result<Vec<ApiPullRequest>, ApiError> {
+        self.list_pull_requests(token, owner, repo, "open", page, per_page)
+            .await
+    }
+
+    async fn list_pull_requests(
+        &self,
+        token: &StoredToken,
+        owner: &str,
+        repo: &str,
+        state: &str,
+        page: u32,
+        per_page: u32,
     ) -> Result<Vec<ApiPullRequest>, ApiError> {
         let per_page = per_page.min(100);
         let url = format!(
-            "{}/api/cli/repos/{}/{}/pulls?state=closed&per_page={}&page={}",
-            self.base_url, owner, repo, per_page, page
+            "{}/api/cli/repos/{}/{}/pulls?state={}&per_page={}&page={}",
+            self.base_url, owner, repo, state, per_page, page
         );
 
-        let response = self
-            .client
-            .get(&url)
-            .bearer_auth(&token.access_token)
-            .send()
-            .await?;
+        let request = self.client.get(&url).bearer_auth(&token.access_token);
+        let response = self.send(request).await?;
 
         let result: PullRequestsResponse = Self::handle_response(response).await?;
         Ok(result.pull_requests)
@@ -340,7 +455,7 @@ impl ApiClient {
         &self,
         params: CreatePullRequestParams<'_>,
     ) -> Result<CreatePullRequestResponse, ApiError> {
-        let response = self
+        let request = self
             .client
             .post(format!(
                 "{}/api/cli/repos/{}/{}/pulls",
@@ -352,9 +467,10 @@ impl ApiClient {
                 head: params.head.to_string(),
                 base: params.base.to_string(),
                 body: Some(params.body.to_string()),
-            })
-            .send()
-            .await?;
+                reviewers: params.reviewers.to_vec(),
+                team_reviewers: params.team_reviewers.to_vec(),
+            });
+        let response = self.send(request).await?;
 
         Self::handle_response(response).await
     }
@@ -369,15 +485,14 @@ impl ApiClient {
         owner: &str,
         repo: &str,
     ) -> Result<GitCredentialsResponse, ApiError> {
-        let response = self
+        let request = self
             .client
             .get(format!(
                 "{}/api/cli/repos/{}/{}/git/credentials",
                 self.base_url, owner, repo
             ))
-            .bearer_auth(&token.access_token)
-            .send()
-            .await?;
+            .bearer_auth(&token.access_token);
+        let response = self.send(request).await?;
 
         Self::handle_response(response).await
     }
@@ -394,16 +509,15 @@ impl ApiClient {
         repo: &str,
         uncovered_paths: Vec<String>,
     ) -> Result<(), ApiError> {
-        let response = self
+        let request = self
             .client
             .post(format!(
                 "{}/api/cli/repos/{}/{}/drift",
                 self.base_url, owner, repo
             ))
             .bearer_auth(&token.access_token)
-            .json(&serde_json::json!({ "uncovered_paths": uncovered_paths }))
-            .send()
-            .await?;
+            .json(&serde_json::json!({ "uncovered_paths": uncovered_paths }));
+        let response = self.send(request).await?;
 
         if !response.status().is_success() {
             let status = response.status().as_u16();
@@ -370,3 +370,110 @@ async fn test_exchange_oidc_token_401_on_bad_jwt() {
         .expect_err("401 should fail");
     assert!(matches!(err, ApiError::Unauthorized));
 }
+
+fn user_info_body() -> serde_json::Value {
+    serde_json::json!({
+        "id": "user-123",
+        "username": "testuser",
+        "name": "Test User",
+        "email": "test@example.com"
+    })
+}
+
+#[tokio::test]
+async fn test_get_retries_transient_server_error() {
+    let mock_server = MockServer::start().await;
+    let token = create_test_token();
+
+    Mock::given(method("GET"))
+        .and(path("/api/cli/me"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(2)
+        .with_priority(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/cli/me"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(user_info_body()))
+        .mount(&mock_server)
+        .await;
+
+    let client = ApiClient::with_base_url(&mock_server.uri()).unwrap();
+    let user = client
+        .get_user_info(&token)
+        .await
+        .expect("retried to success");
+    assert_eq!(user.id, "user-123");
+}
+
+#[tokio::test]
+async fn test_post_is_not_retried_on_server_error() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/api/auth/device/code"))
+        .respond_with(ResponseTemplate::new(502))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = ApiClient::with_base_url(&mock_server.uri()).unwrap();
+    let err = client.request_device_code().await.unwrap_err();
+    assert!(matches!(err, ApiError::ApiResponse { status: 502, .. }));
+}
+
+#[tokio::test]
+async fn test_secondary_rate_limit_is_retried_after_delay() {
+    let mock_server = MockServer::start().await;
+    let token = create_test_token();
+
+    Mock::given(method("GET"))
+        .and(path("/api/cli/me"))
+        .respond_with(ResponseTemplate::new(403).insert_header("retry-after", "0"))
+        .up_to_n_times(1)
+        .with_priority(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/cli/me"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("x-ratelimit-limit", "5000")
+                .insert_header("x-ratelimit-remaining", "4998")
+                .insert_header("x-ratelimit-reset", "1760000000")
+                .set_body_json(user_info_body()),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let client = ApiClient::with_base_url(&mock_server.uri()).unwrap();
+    client
+        .get_user_info(&token)
+        .await
+        .expect("retried to success");
+
+    let info = client.rate_limit().expect("rate limit headers recorded");
+    assert_eq!(info.remaining, Some(4998));
+    assert_eq!(info.limit, Some(5000));
+}
+
+#[tokio::test]
+async fn test_exhausted_rate_limit_surfaces_as_rate_limited() {
+    let mock_server = MockServer::start().await;
+    let token = create_test_token();
+
+    Mock::given(method("GET"))
+        .and(path("/api/cli/me"))
+        .respond_with(
+            ResponseTemplate::new(403)
+                .insert_header("x-ratelimit-remaining", "0")
+                .insert_header("x-ratelimit-reset", "4102444800"),
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let client = ApiClient::with_base_url(&mock_server.uri()).unwrap();
+    let err = client.get_user_info(&token).await.unwrap_err();
+    assert!(matches!(err, ApiError::RateLimited { .. }));
+}
@@ -0,0 +1,217 @@
+//! Retry policy for belaf API calls.
+//!
+//! GitHub traffic is proxied through the belaf API, so GitHub's rate
+//! limits surface here: `429`, or `403` carrying either a `Retry-After`
+//! header (secondary rate limit) or `x-ratelimit-remaining: 0` plus an
+//! `x-ratelimit-reset` epoch (primary rate limit). Those are retried for
+//! every method, since the server rejected the request before acting on
+//! it. Transient `5xx` responses and connection failures are retried
+//! only for idempotent methods — replaying a `POST` that timed out
+//! could open the release PR twice.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use reqwest::{header::HeaderMap, Method, StatusCode};
+
+use crate::core::config::syntax::NetworkConfiguration;
+
+/// How often, and how long, to retry a failed API call.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt. `0` disables retrying.
+    pub max_retries: u32,
+    /// Delay before the first retry; doubles on every further attempt.
+    pub base_delay: Duration,
+    /// Upper bound for any single wait. A rate limit that resets later
+    /// than this is reported immediately instead of stalling the run.
+    pub max_wait: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(500),
+            max_wait: Duration::from_secs(60),
+        }
+    }
+}
+
+impl RetryPolicy {
+    pub fn from_config(cfg: &NetworkConfiguration) -> Self {
+        let defaults = Self::default();
+        Self {
+            max_retries: cfg.max_retries.unwrap_or(defaults.max_retries),
+            base_delay: defaults.base_delay,
+            max_wait: cfg
+                .max_retry_wait_secs
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.max_wait),
+        }
+    }
+
+    /// Exponential backoff for retry number `attempt` (0-based), with up
+    /// to 25% jitter so parallel CI jobs don't retry in lockstep.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let exp = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_wait);
+        let jitter_ms = (exp.as_millis() / 4) as u64;
+        if jitter_ms == 0 {
+            return exp;
+        }
+        (exp + Duration::from_millis(rand::random_range(0..=jitter_ms))).min(self.max_wait)
+    }
+
+    /// Decide whether a response warrants another attempt, and after
+    /// how long. `None` means hand the response to the caller as-is.
+    pub fn delay_for_response(
+        &self,
+        method: &Method,
+        status: StatusCode,
+        headers: &HeaderMap,
+        attempt: u32,
+    ) -> Option<Duration> {
+        if attempt >= self.max_retries {
+            return None;
+        }
+
+        let wait = if let Some(wait) = rate_limit_wait(status, headers) {
+            wait.unwrap_or_else(|| self.backoff(attempt))
+        } else if status.is_server_error() && is_idempotent(method) {
+            self.backoff(attempt)
+        } else {
+            return None;
+        };
+
+        (wait <= self.max_wait).then_some(wait)
+    }
+
+    /// Same as [`Self::delay_for_response`] for requests that never got
+    /// a response (connect failures, timeouts).
+    pub fn delay_for_error(
+        &self,
+        method: &Method,
+        err: &reqwest::Error,
+        attempt: u32,
+    ) -> Option<Duration> {
+        if attempt >= self.max_retries || !is_idempotent(method) {
+            return None;
+        }
+        (err.is_connect() || err.is_timeout()).then(|| self.backoff(attempt))
+    }
+}
+
+fn is_idempotent(method: &Method) -> bool {
+    matches!(
+        *method,
+        Method::GET | Method::HEAD | Method::OPTIONS | Method::PUT | Method::DELETE
+    )
+}
+
+/// `Some(_)` when the response is a rate-limit rejection. The inner
+/// value is the server-advertised wait, if it gave one.
+pub fn rate_limit_wait(status: StatusCode, headers: &HeaderMap) -> Option<Option<Duration>> {
+    let retry_after = header_u64(headers, "retry-after").map(Duration::from_secs);
+    let exhausted = header_u64(headers, "x-ratelimit-remaining") == Some(0);
+
+    match status {
+        StatusCode::TOO_MANY_REQUESTS => Some(retry_after.or_else(|| reset_wait(headers))),
+        StatusCode::FORBIDDEN if retry_after.is_some() => Some(retry_after),
+        StatusCode::FORBIDDEN if exhausted => Some(reset_wait(headers)),
+        _ => None,
+    }
+}
+
+fn reset_wait(headers: &HeaderMap) -> Option<Duration> {
+    let reset = header_u64(headers, "x-ratelimit-reset")?;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).ok()?.as_secs();
+    Some(Duration::from_secs(reset.saturating_sub(now)))
+}
+
+pub(crate) fn header_u64(headers: &HeaderMap, name: &str) -> Option<u64> {
+    headers.get(name)?.to_str().ok()?.trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (k, v) in pairs {
+            map.insert(*k, HeaderValue::from_str(v).unwrap());
+        }
+        map
+    }
+
+    #[test]
+    fn backoff_grows_and_is_capped() {
+        let policy = RetryPolicy {
+            max_retries: 10,
+            base_delay: Duration::from_millis(100),
+            max_wait: Duration::from_secs(1),
+        };
+        assert!(policy.backoff(0) >= Duration::from_millis(100));
+        assert!(policy.backoff(2) >= Duration::from_millis(400));
+        assert_eq!(policy.backoff(8), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn server_errors_retry_only_idempotent_methods() {
+        let policy = RetryPolicy::default();
+        let h = HeaderMap::new();
+        let status = StatusCode::BAD_GATEWAY;
+        assert!(policy
+            .delay_for_response(&Method::GET, status, &h, 0)
+            .is_some());
+        assert!(policy
+            .delay_for_response(&Method::POST, status, &h, 0)
+            .is_none());
+    }
+
+    #[test]
+    fn secondary_rate_limit_honours_retry_after() {
+        let policy = RetryPolicy::default();
+        let h = headers(&[("retry-after", "7")]);
+        assert_eq!(
+            policy.delay_for_response(&Method::POST, StatusCode::FORBIDDEN, &h, 0),
+            Some(Duration::from_secs(7))
+        );
+    }
+
+    #[test]
+    fn plain_forbidden_is_not_retried() {
+        let policy = RetryPolicy::default();
+        assert!(policy
+            .delay_for_response(&Method::GET, StatusCode::FORBIDDEN, &HeaderMap::new(), 0)
+            .is_none());
+    }
+
+    #[test]
+    fn rate_limit_beyond_max_wait_is_not_retried() {
+        let policy = RetryPolicy::default();
+        let h = headers(&[("retry-after", "3600")]);
+        assert!(policy
+            .delay_for_response(&Method::GET, StatusCode::TOO_MANY_REQUESTS, &h, 0)
+            .is_none());
+    }
+
+    #[test]
+    fn retries_stop_after_max_retries() {
+        let policy = RetryPolicy {
+            max_retries: 2,
+            ..Default::default()
+        };
+        let h = HeaderMap::new();
+        let status = StatusCode::SERVICE_UNAVAILABLE;
+        assert!(policy
+            .delay_for_response(&Method::GET, status, &h, 1)
+            .is_some());
+        assert!(policy
+            .delay_for_response(&Method::GET, status, &h, 2)
+            .is_none());
+    }
+}
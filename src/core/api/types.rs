@@ -151,4 +151,7 @@ pub struct CreatePullRequestParams<'a> {
     pub head: &'a str,
     pub base: &'a str,
     pub body: &'a str,
+    /// Logins and team slugs to request reviews from.
+    pub reviewers: &'a [String],
+    pub team_reviewers: &'a [String],
 }
@@ -0,0 +1,138 @@
+//! `[checks] api_diff` — the bump a Rust crate's public API requires.
+//!
+//! Commit messages say what the authors meant to change; a removed
+//! public function in a commit labelled `fix:` still breaks downstream
+//! crates. With the check enabled, belaf runs `cargo semver-checks`
+//! between each crate's last release tag and `HEAD` and shows the
+//! verdict next to the commit-based suggestion in `belaf status` and the
+//! prepare wizard ("commits say minor, API diff says MAJOR"). In
+//! `enforce` mode `prepare` also refuses a release smaller than the API
+//! diff requires.
+//!
+//! The diff needs `cargo-semver-checks` on `PATH`; without it, or when
+//! the analysis fails, belaf warns and falls back to the commits alone.
+
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::LazyLock;
+
+use anyhow::{bail, Result};
+use regex::Regex;
+
+use crate::core::{
+    bump::BumpRecommendation, git::repository::RepoPathBuf, resolved_release_unit::ReleaseUnitId,
+    session::AppSession, wire::known::KnownEcosystem,
+};
+
+/// `Summary semver requires new major version: 1 major and 0 minor checks failed`
+static CHECKS_FAILED: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(\d+) major and (\d+) minor checks? failed").expect("valid regex")
+});
+
+/// `Summary semver requires new minor version: …`, for output without counts.
+static REQUIRES: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"requires new (major|minor) version").expect("valid regex"));
+
+/// The bump the API changes of `ident` since `baseline_rev` require.
+/// `None` for units that aren't Cargo crates.
+pub fn check_unit(
+    sess: &AppSession,
+    ident: ReleaseUnitId,
+    baseline_rev: &str,
+) -> Result<Option<BumpRecommendation>> {
+    let unit = sess.graph().lookup(ident);
+    let qnames = unit.qualified_names();
+    if qnames.get(1).and_then(|e| KnownEcosystem::from_wire(e)) != Some(KnownEcosystem::Cargo) {
+        return Ok(None);
+    }
+    let workdir = sess.repo.resolve_workdir(&RepoPathBuf::new(b""));
+    let manifest = sess.repo.resolve_workdir(&RepoPathBuf::new(
+        format!("{}Cargo.toml", unit.prefix().escaped()).as_bytes(),
+    ));
+    required_bump(&workdir, &manifest, &qnames[0], baseline_rev).map(Some)
+}
+
+/// The bump `cargo semver-checks` says the API changes of `package`
+/// since `baseline_rev` require. `manifest` is the crate's `Cargo.toml`
+/// and `workdir` the repository root. The verdict is raw: a breaking
+/// change to a 0.x crate is reported as [`BumpRecommendation::Major`];
+/// callers apply the `[bump]` configuration like they do for commits.
+pub fn required_bump(
+    workdir: &Path,
+    manifest: &Path,
+    package: &str,
+    baseline_rev: &str,
+) -> Result<BumpRecommendation> {
+    let output = Command::new("cargo")
+        .args(["semver-checks", "check-release", "--manifest-path"])
+        .arg(manifest)
+        .args(["--package", package, "--baseline-rev", baseline_rev])
+        .current_dir(workdir)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| anyhow::anyhow!("failed to run `cargo semver-checks`: {e}"))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if stderr.contains("no such command: `semver-checks`") {
+        bail!("`cargo semver-checks` is not installed; run `cargo install cargo-semver-checks`");
+    }
+    match parse_verdict(&format!("{stdout}\n{stderr}"), output.status.success()) {
+        Some(bump) => Ok(bump),
+        None => bail!(
+            "`cargo semver-checks` failed for `{package}`: {}",
+            stderr.trim().lines().last().unwrap_or("no output")
+        ),
+    }
+}
+
+/// Read the verdict from `cargo semver-checks` output. A run that
+/// succeeded without failed checks requires no more than a patch release;
+/// `None` when a failed run doesn't say why.
+fn parse_verdict(output: &str, success: bool) -> Option<BumpRecommendation> {
+    if let Some(caps) = CHECKS_FAILED.captures(output) {
+        let major: u32 = caps[1].parse().ok()?;
+        let minor: u32 = caps[2].parse().ok()?;
+        return Some(if major > 0 {
+            BumpRecommendation::Major
+        } else if minor > 0 {
+            BumpRecommendation::Minor
+        } else {
+            BumpRecommendation::Patch
+        });
+    }
+    if let Some(caps) = REQUIRES.captures(output) {
+        return BumpRecommendation::from_string(&caps[1]);
+    }
+    success.then_some(BumpRecommendation::Patch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failed_checks_decide_the_bump() {
+        let major = "     Checked [   0.012s] 97 checks: 95 pass, 2 fail, 0 warn, 0 skip\n\
+                     \x20    Summary semver requires new major version: 1 major and 1 minor checks failed\n";
+        assert_eq!(parse_verdict(major, false), Some(BumpRecommendation::Major));
+
+        let minor =
+            "     Summary semver requires new minor version: 0 major and 1 minor check failed\n";
+        assert_eq!(parse_verdict(minor, false), Some(BumpRecommendation::Minor));
+
+        let legacy = "Final [0.1s] semver requires new major version: 2 checks failed";
+        assert_eq!(
+            parse_verdict(legacy, false),
+            Some(BumpRecommendation::Major)
+        );
+    }
+
+    #[test]
+    fn clean_runs_need_a_patch_and_unexplained_failures_nothing() {
+        let clean = "     Checked [   0.010s] 97 checks: 97 pass, 0 skip\n\
+                     \x20    Summary no semver update required\n";
+        assert_eq!(parse_verdict(clean, true), Some(BumpRecommendation::Patch));
+        assert_eq!(parse_verdict("error: could not compile `api`", false), None);
+    }
+}
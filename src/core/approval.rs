@@ -0,0 +1,313 @@
+//! Release approval gate (`[release.approvers]`).
+//!
+//! With approvers configured, `belaf prepare` writes an `approval` block
+//! into the release manifest naming who prepared the release. Each
+//! approver then runs `belaf approve <release-id>` on the release branch,
+//! which appends their authenticated GitHub login, and CI runs
+//! `belaf approve <release-id> --check` as a required check on the
+//! release PR so it can't merge — and the GitHub App can't tag — until
+//! enough approvers have signed off.
+//!
+//! Sign-offs are always re-checked against the configuration: a login
+//! that isn't listed, or that prepared the release, never counts, and
+//! the configured `required` can only raise the number the manifest
+//! asks for.
+//!
+//! Anyone who can push to the release branch can write a sign-off into
+//! the manifest, so [`verify`] also checks each one against the release
+//! PR on the forge: it counts only when that login's latest review
+//! approves the PR, and whoever opened the PR or authored its release
+//! commit counts as its preparer whatever `requested_by` says. `belaf
+//! approve --check` and `belaf tag` both verify.
+
+use anyhow::{anyhow, bail, Context, Result};
+
+use crate::core::api::{ApiClient, ApiError};
+use crate::core::auth::token::load_or_exchange_token;
+use crate::core::config::syntax::ApproversConfiguration;
+use crate::core::deterministic;
+use crate::core::git::repository::Repository;
+use crate::core::github::rest::{self, ReleasePullRequest};
+use crate::core::manifest::{Approval, ReleaseManifest, Signoff};
+
+/// Reject configurations no release could ever satisfy.
+pub fn validate(cfg: &ApproversConfiguration) -> Result<()> {
+    if cfg.users.is_empty() {
+        bail!("`users` must list at least one GitHub login");
+    }
+    if cfg.required == 0 {
+        bail!("`required` must be at least 1");
+    }
+    if cfg.required > cfg.users.len() as u64 {
+        bail!(
+            "`required` is {} but only {} approver(s) are listed",
+            cfg.required,
+            cfg.users.len()
+        );
+    }
+    Ok(())
+}
+
+/// The block `prepare` writes into a new manifest.
+pub fn requirement(cfg: &ApproversConfiguration, requested_by: Option<String>) -> Approval {
+    Approval {
+        required: cfg.required,
+        requested_by,
+        approvals: Vec::new(),
+    }
+}
+
+/// GitHub logins are case-insensitive and often written with an `@`.
+fn same_login(a: &str, b: &str) -> bool {
+    a.trim_start_matches('@')
+        .eq_ignore_ascii_case(b.trim_start_matches('@'))
+}
+
+fn is_approver(cfg: &ApproversConfiguration, login: &str) -> bool {
+    cfg.users.iter().any(|u| same_login(u, login))
+}
+
+/// Record `login`'s sign-off on `approval`.
+pub fn sign(approval: &mut Approval, cfg: &ApproversConfiguration, login: &str) -> Result<()> {
+    if !is_approver(cfg, login) {
+        bail!(
+            "`{login}` is not listed in [release.approvers] users ({})",
+            cfg.users.join(", ")
+        );
+    }
+    if approval
+        .requested_by
+        .as_deref()
+        .is_some_and(|r| same_login(r, login))
+    {
+        bail!("`{login}` prepared this release and cannot approve it");
+    }
+    if approval
+        .approvals
+        .iter()
+        .any(|s| same_login(&s.approver, login))
+    {
+        bail!("`{login}` has already approved this release");
+    }
+
+    let now = deterministic::now_utc();
+    let approved_at = now
+        .format(&time::format_description::well_known::Rfc3339)
+        .unwrap_or_else(|_| now.to_string());
+    approval.approvals.push(Signoff {
+        approver: login.trim_start_matches('@').to_owned(),
+        approved_at,
+    });
+    Ok(())
+}
+
+/// Where a release stands against the gate.
+#[derive(Debug, PartialEq, Eq, serde::Serialize)]
+pub struct ApprovalStatus {
+    pub required: u64,
+    /// Sign-offs that count, in the order they were given.
+    pub approved_by: Vec<String>,
+    /// Sign-offs that don't count, with the reason.
+    pub rejected: Vec<String>,
+    /// The release PR the sign-offs were checked against; `None` when
+    /// only the manifest was read.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pull_request: Option<String>,
+}
+
+impl ApprovalStatus {
+    pub fn is_approved(&self) -> bool {
+        self.approved_by.len() as u64 >= self.required
+    }
+}
+
+/// Evaluate a manifest's `approval` block against the configured gate.
+/// Either side may be missing: a manifest prepared before the gate was
+/// configured still needs `cfg.required` approvals, and one prepared
+/// under a gate since removed keeps what it asked for. With `pull`, the
+/// release PR, sign-offs are checked against its reviews too.
+pub fn status(
+    approval: Option<&Approval>,
+    cfg: Option<&ApproversConfiguration>,
+    pull: Option<&ReleasePullRequest>,
+) -> ApprovalStatus {
+    let required = approval
+        .map(|a| a.required)
+        .max(cfg.map(|c| c.required))
+        .unwrap_or(0);
+    let mut status = ApprovalStatus {
+        required,
+        approved_by: Vec::new(),
+        rejected: Vec::new(),
+        pull_request: pull.map(|p| p.url.clone()),
+    };
+    let Some(approval) = approval else {
+        return status;
+    };
+
+    for s in &approval.approvals {
+        let login = s.approver.as_str();
+        let reason = if cfg.is_some_and(|c| !is_approver(c, login)) {
+            Some("not a configured approver")
+        } else if approval
+            .requested_by
+            .as_deref()
+            .is_some_and(|r| same_login(r, login))
+            || pull.is_some_and(|p| p.prepared_by.iter().any(|r| same_login(r, login)))
+        {
+            Some("prepared the release")
+        } else if status.approved_by.iter().any(|a| same_login(a, login)) {
+            Some("duplicate sign-off")
+        } else if pull.is_some_and(|p| !p.approved_by.iter().any(|a| same_login(a, login))) {
+            Some("no approving review on the release PR")
+        } else {
+            None
+        };
+        match reason {
+            Some(reason) => status.rejected.push(format!("{login}: {reason}")),
+            None => status.approved_by.push(login.to_owned()),
+        }
+    }
+    status
+}
+
+/// [`status`] of `manifest`, stored at `manifest_path`, with its sign-offs
+/// checked against the reviews of the release PR that added it. Fails
+/// when the PR can't be read: an unverified gate must not pass.
+pub async fn verify(
+    repo: &Repository,
+    manifest: &ReleaseManifest,
+    manifest_path: &str,
+    cfg: Option<&ApproversConfiguration>,
+) -> Result<ApprovalStatus> {
+    let context = "sign-offs are checked against the release PR's reviews";
+    let (client, owner, name) = rest::for_upstream(repo).context(context)?;
+    let pull = client
+        .release_pull_request(&owner, &name, manifest_path)
+        .await
+        .context(context)?
+        .ok_or_else(|| {
+            anyhow!("no release PR adds `{manifest_path}`, so its sign-offs can't be checked")
+        })?;
+    Ok(status(manifest.approval.as_ref(), cfg, Some(&pull)))
+}
+
+/// GitHub login of the authenticated belaf user (keyring token, or the
+/// GitHub Actions OIDC exchange in CI).
+pub async fn current_login() -> Result<String> {
+    let client = ApiClient::try_new()?;
+    let token = load_or_exchange_token(&client)
+        .await?
+        .filter(|t| !t.is_expired())
+        .ok_or(ApiError::Unauthorized)?;
+    let user = client
+        .get_user_info(&token)
+        .await
+        .context("failed to look up the authenticated user")?;
+    user.username
+        .ok_or_else(|| anyhow!("the belaf API reported no GitHub login for this account"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cfg(users: &[&str], required: u64) -> ApproversConfiguration {
+        ApproversConfiguration {
+            users: users.iter().map(|u| u.to_string()).collect(),
+            required,
+        }
+    }
+
+    #[test]
+    fn validate_rejects_unsatisfiable_gates() {
+        assert!(validate(&cfg(&["alice"], 1)).is_ok());
+        assert!(validate(&cfg(&[], 1)).is_err());
+        assert!(validate(&cfg(&["alice"], 0)).is_err());
+        assert!(validate(&cfg(&["alice"], 2)).is_err());
+    }
+
+    #[test]
+    fn preparer_cannot_approve_own_release() {
+        let c = cfg(&["@alice", "bob"], 1);
+        let mut a = requirement(&c, Some("Alice".to_string()));
+
+        assert!(sign(&mut a, &c, "alice").is_err());
+        assert!(sign(&mut a, &c, "mallory").is_err());
+        assert!(!status(Some(&a), Some(&c), None).is_approved());
+
+        sign(&mut a, &c, "bob").expect("bob may approve");
+        assert!(sign(&mut a, &c, "BOB").is_err(), "no double sign-off");
+        let st = status(Some(&a), Some(&c), None);
+        assert!(st.is_approved());
+        assert_eq!(st.approved_by, ["bob"]);
+    }
+
+    #[test]
+    fn status_rechecks_hand_edited_signoffs() {
+        let c = cfg(&["alice", "bob", "carol"], 2);
+        let a = Approval {
+            required: 1,
+            requested_by: Some("alice".to_string()),
+            approvals: ["alice", "mallory", "bob"]
+                .iter()
+                .map(|l| Signoff {
+                    approver: l.to_string(),
+                    approved_at: "2026-01-01T00:00:00Z".to_string(),
+                })
+                .collect(),
+        };
+        let st = status(Some(&a), Some(&c), None);
+        assert_eq!(st.required, 2, "config raises the manifest's requirement");
+        assert_eq!(st.approved_by, ["bob"]);
+        assert_eq!(st.rejected.len(), 2);
+        assert!(!st.is_approved());
+    }
+
+    #[test]
+    fn no_gate_means_approved() {
+        assert!(status(None, None, None).is_approved());
+        assert!(!status(None, Some(&cfg(&["alice"], 1)), None).is_approved());
+    }
+
+    #[test]
+    fn signoffs_need_an_approving_review() {
+        let c = cfg(&["alice", "bob", "carol", "dave"], 2);
+        // A hand-written manifest: nobody is named as the preparer.
+        let a = Approval {
+            required: 2,
+            requested_by: None,
+            approvals: ["alice", "bob", "carol"]
+                .iter()
+                .map(|l| Signoff {
+                    approver: l.to_string(),
+                    approved_at: "2026-01-01T00:00:00Z".to_string(),
+                })
+                .collect(),
+        };
+        assert!(status(Some(&a), Some(&c), None).is_approved());
+
+        let pull = ReleasePullRequest {
+            number: 7,
+            url: "https://github.com/acme/widgets/pull/7".to_string(),
+            prepared_by: vec!["belaf[bot]".to_string(), "Alice".to_string()],
+            approved_by: vec!["alice".to_string(), "bob".to_string(), "dave".to_string()],
+            body: String::new(),
+            merged: false,
+        };
+        let st = status(Some(&a), Some(&c), Some(&pull));
+        assert_eq!(st.approved_by, ["bob"]);
+        assert_eq!(
+            st.rejected,
+            [
+                "alice: prepared the release",
+                "carol: no approving review on the release PR"
+            ]
+        );
+        assert!(
+            !st.is_approved(),
+            "dave approved on GitHub but never signed off"
+        );
+        assert_eq!(st.pull_request.as_deref(), Some(pull.url.as_str()));
+    }
+}
@@ -0,0 +1,135 @@
+//! Append-only audit log of the operations that change a repository.
+//!
+//! `init`, `prepare`, `approve`, `tag`, `release cut`, `release
+//! publish` and `release --snapshot` each append one JSON line to `belaf/audit.jsonl` in the
+//! working tree, next to the files they wrote, so the entry is committed
+//! together with them: `prepare` puts it into the release commit, the
+//! others leave it for the commit the user makes afterwards. The commit that added a line is therefore the
+//! operation's resulting commit, and `belaf audit show` reads it back
+//! with `git blame` instead of storing it.
+
+use std::fs::OpenOptions;
+use std::io::Write;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+use crate::core::deterministic;
+use crate::core::git::repository::{CommitId, RepoPathBuf, Repository};
+
+/// Where the log lives, relative to the repository root.
+pub const AUDIT_LOG: &str = "belaf/audit.jsonl";
+
+/// One line of the log.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct AuditEntry {
+    /// RFC 3339, UTC.
+    pub timestamp: String,
+    /// The git identity the operation ran as, `Name <email>`.
+    pub user: String,
+    /// Subcommand, e.g. `prepare`.
+    pub command: String,
+    /// Command-line arguments after the program name.
+    pub args: Vec<String>,
+    /// Branch the operation committed to, when not the current one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    /// Release manifest id the operation created or changed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub manifest: Option<String>,
+    /// Tags the operation creates, or that merging its release PR will.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+impl AuditEntry {
+    /// An entry for `command`, run now with this process's arguments.
+    pub fn new(repo: &Repository, command: &str) -> Self {
+        let now = deterministic::now_utc();
+        let timestamp = now
+            .format(&time::format_description::well_known::Rfc3339)
+            .unwrap_or_else(|_| now.to_string());
+        let user = repo
+            .get_signature()
+            .map(|sig| {
+                format!(
+                    "{} <{}>",
+                    sig.name().unwrap_or("unknown"),
+                    sig.email().unwrap_or("unknown")
+                )
+            })
+            .unwrap_or_else(|_| "unknown".to_string());
+        Self {
+            timestamp,
+            user,
+            command: command.to_string(),
+            args: std::env::args().skip(1).collect(),
+            branch: None,
+            manifest: None,
+            tags: Vec::new(),
+        }
+    }
+
+    pub fn with_branch(mut self, branch: impl Into<String>) -> Self {
+        self.branch = Some(branch.into());
+        self
+    }
+
+    pub fn with_manifest(mut self, manifest_id: impl Into<String>) -> Self {
+        self.manifest = Some(manifest_id.into());
+        self
+    }
+
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
+}
+
+/// Append `entry` to the log, creating it if needed. Returns the log's
+/// repository path for callers that commit it.
+pub fn append(repo: &Repository, entry: &AuditEntry) -> Result<RepoPathBuf> {
+    let path = RepoPathBuf::new(AUDIT_LOG.as_bytes());
+    let abs = repo.resolve_workdir(&path);
+    if let Some(parent) = abs.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut line = serde_json::to_string(entry)?;
+    line.push('\n');
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&abs)
+        .and_then(|mut f| f.write_all(line.as_bytes()))
+        .with_context(|| format!("failed to append to the audit log `{}`", abs.display()))?;
+    Ok(path)
+}
+
+/// Every entry in the log, oldest first, with the commit that added it
+/// (`None` while it is only in the working tree). Lines that don't parse
+/// are skipped with a warning.
+pub fn read(repo: &Repository) -> Result<Vec<(AuditEntry, Option<CommitId>)>> {
+    let path = RepoPathBuf::new(AUDIT_LOG.as_bytes());
+    let abs = repo.resolve_workdir(&path);
+    let content = match std::fs::read_to_string(&abs) {
+        Ok(c) => c,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(e).with_context(|| format!("failed to read `{}`", abs.display()));
+        }
+    };
+    let commits = repo.line_commits(&path)?;
+
+    let mut entries = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str(line) {
+            Ok(entry) => entries.push((entry, commits.get(i).copied())),
+            Err(e) => warn!("skipping malformed line {} of {AUDIT_LOG}: {e}", i + 1),
+        }
+    }
+    Ok(entries)
+}
@@ -0,0 +1,254 @@
+//! GitHub personal access tokens, instead of the belaf GitHub App.
+//!
+//! Where the App can't be installed, `belaf auth login --with-token`
+//! takes a fine-grained personal access token, pasted at the prompt or
+//! piped on stdin, and checks right away what it may do on the
+//! repository of the current checkout:
+//!
+//! - read it (`Metadata: Read-only`, which every token has);
+//! - push the release branch (`Contents: Read and write`);
+//! - open the release pull request (`Pull requests: Read and write`).
+//!
+//! The write checks send requests GitHub turns down as invalid (`422`)
+//! when the token may write and as forbidden (`403`) when it may not, so
+//! nothing is created. What the token may do is stored with it, per
+//! repository. On a repository it was checked against, `prepare` pushes
+//! and opens the pull request with the token, and stops before touching
+//! the working tree when it lacks a permission, naming it. Anywhere else
+//! the App is used as before.
+
+use std::collections::BTreeMap;
+
+use anyhow::{bail, Result};
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use time::{macros::format_description, OffsetDateTime, PrimitiveDateTime};
+
+use crate::core::auth::permissions::{AccessError, Permission};
+use crate::core::auth::token::load_pat;
+use crate::core::github::rest::RestClient;
+
+/// Header carrying the expiry of a fine-grained token.
+const EXPIRATION_HEADER: &str = "github-authentication-token-expiration";
+
+/// What a token may do on one repository.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Capabilities {
+    pub read: bool,
+    pub push: bool,
+    pub pull_requests: bool,
+}
+
+impl Capabilities {
+    /// Whether the token holds `permission`. `Workflows` can't be checked
+    /// without pushing; a rejected push names it instead.
+    pub fn allows(&self, permission: Permission) -> bool {
+        match permission {
+            Permission::Contents => self.push,
+            Permission::PullRequests => self.pull_requests,
+            Permission::Workflows => true,
+        }
+    }
+
+    /// Fail with the first of `required` the token lacks on `repo`.
+    pub fn require(&self, repo: &str, required: &[Permission]) -> Result<()> {
+        match required.iter().find(|p| !self.allows(**p)) {
+            Some(&permission) => Err(AccessError::TokenLacksPermission {
+                repo: repo.to_string(),
+                permission,
+            }
+            .into()),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A personal access token and what it was found to allow.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StoredPat {
+    pub token: String,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub expires_at: Option<OffsetDateTime>,
+    /// Keyed by `owner/repo`.
+    #[serde(default)]
+    pub repos: BTreeMap<String, Capabilities>,
+}
+
+impl StoredPat {
+    pub fn is_expired(&self) -> bool {
+        self.expires_at
+            .is_some_and(|at| at <= OffsetDateTime::now_utc())
+    }
+}
+
+/// What [`validate`] found out about a token.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Validation {
+    pub capabilities: Capabilities,
+    pub expires_at: Option<OffsetDateTime>,
+}
+
+/// How to create a token that is enough for `owner/repo` on the forge
+/// at `web_url`.
+pub fn guidance(web_url: &str, owner: &str, repo: &str) -> String {
+    format!(
+        "Create a fine-grained token at {web_url}/settings/personal-access-tokens/new\n\
+         with resource owner `{owner}`, access to `{owner}/{repo}` only, and these\n\
+         repository permissions:\n  \
+         • {}\n  \
+         • {}\n\
+         Add `{}` if releases touch `.github/workflows/`.",
+        Permission::Contents.token_label(),
+        Permission::PullRequests.token_label(),
+        Permission::Workflows.token_label(),
+    )
+}
+
+/// Check what the token of `client` may do on `owner/repo`.
+pub async fn validate(client: &RestClient, owner: &str, repo: &str) -> Result<Validation> {
+    let full = format!("{owner}/{repo}");
+    let response = client
+        .send(Method::GET, &format!("/repos/{full}"), None)
+        .await?;
+    match response.status {
+        200 => {}
+        401 => bail!("GitHub rejected the token: it is mistyped, expired or revoked"),
+        403 | 404 => bail!("the token can't read {full}; give it access to the repository"),
+        status => bail!("GitHub API error ({status}): {}", response.message()),
+    }
+    let expires_at = response
+        .headers
+        .get(EXPIRATION_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(parse_expiration);
+
+    let push = may_write(client, &format!("/repos/{full}/git/refs")).await?;
+    let pull_requests = may_write(client, &format!("/repos/{full}/pulls")).await?;
+    Ok(Validation {
+        capabilities: Capabilities {
+            read: true,
+            push,
+            pull_requests,
+        },
+        expires_at,
+    })
+}
+
+/// Post an empty body to `path`: GitHub checks the permission before
+/// the body, so `422` means the token may write there.
+async fn may_write(client: &RestClient, path: &str) -> Result<bool> {
+    let response = client
+        .send(Method::POST, path, Some(&serde_json::json!({})))
+        .await?;
+    match response.status {
+        422 => Ok(true),
+        403 | 404 => Ok(false),
+        status => bail!("GitHub API error ({status}): {}", response.message()),
+    }
+}
+
+/// `2026-11-01 00:00:00 UTC`, as GitHub sends it.
+fn parse_expiration(value: &str) -> Option<OffsetDateTime> {
+    let value = value.trim().strip_suffix(" UTC")?;
+    PrimitiveDateTime::parse(
+        value,
+        format_description!("[year]-[month]-[day] [hour]:[minute]:[second]"),
+    )
+    .ok()
+    .map(PrimitiveDateTime::assume_utc)
+}
+
+/// The stored token and what it may do on `owner/repo`, if the active
+/// profile holds one that was checked against it.
+pub fn for_repo(owner: &str, repo: &str) -> Result<Option<(StoredPat, Capabilities)>> {
+    let Some(pat) = load_pat()? else {
+        return Ok(None);
+    };
+    let Some(&capabilities) = pat.repos.get(&format!("{owner}/{repo}")) else {
+        return Ok(None);
+    };
+    if pat.is_expired() {
+        bail!(
+            "the personal access token for {owner}/{repo} has expired; create a new one and run `belaf auth login --with-token`"
+        );
+    }
+    Ok(Some((pat, capabilities)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{bearer_token, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    async fn server(refs: u16, pulls: u16) -> MockServer {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/acme/widgets"))
+            .and(bearer_token("github_pat_test"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header(EXPIRATION_HEADER, "2030-11-01 00:00:00 UTC")
+                    .set_body_json(serde_json::json!({ "full_name": "acme/widgets" })),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/repos/acme/widgets/git/refs"))
+            .respond_with(ResponseTemplate::new(refs))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/repos/acme/widgets/pulls"))
+            .respond_with(ResponseTemplate::new(pulls))
+            .mount(&server)
+            .await;
+        server
+    }
+
+    #[tokio::test]
+    async fn validation_finds_what_the_token_may_do() {
+        let server = server(422, 403).await;
+        let client = RestClient::with_api_url(&server.uri(), "github_pat_test").unwrap();
+
+        let found = validate(&client, "acme", "widgets").await.unwrap();
+        assert_eq!(
+            found.capabilities,
+            Capabilities {
+                read: true,
+                push: true,
+                pull_requests: false,
+            }
+        );
+        assert_eq!(found.expires_at.unwrap().year(), 2030);
+
+        let err = found
+            .capabilities
+            .require(
+                "acme/widgets",
+                &[Permission::Contents, Permission::PullRequests],
+            )
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("`Pull requests: Read and write`"),
+            "{err}"
+        );
+        let hint = err.downcast_ref::<AccessError>().unwrap().hint();
+        assert!(hint.contains("belaf auth login --with-token"), "{hint}");
+    }
+
+    #[tokio::test]
+    async fn rejected_tokens_fail_validation() {
+        let server = server(422, 422).await;
+        let client = RestClient::with_api_url(&server.uri(), "github_pat_wrong").unwrap();
+        let err = validate(&client, "acme", "widgets").await.unwrap_err();
+        assert!(err.to_string().contains("can't read acme/widgets"), "{err}");
+    }
+
+    #[test]
+    fn expiration_header_parses() {
+        let at = parse_expiration("2026-11-01 12:30:00 UTC").unwrap();
+        assert_eq!((at.year(), at.hour(), at.minute()), (2026, 12, 30));
+        assert_eq!(parse_expiration("never"), None);
+    }
+}
@@ -0,0 +1,223 @@
+//! What belaf needs from GitHub for each operation, and the pre-flight
+//! that checks it before `prepare` touches the working tree.
+//!
+//! Usually belaf holds no GitHub token of its own: the belaf GitHub App
+//! acts on the repository, and the CLI's API token only proves who is
+//! asking. So "scopes" here are the App installation's repository
+//! permissions, or those of a personal access token stored with
+//! `belaf auth login --with-token` (see [`crate::core::auth::pat`]).
+//! GitHub answers a missing permission with a bare `403`, and a repo the
+//! installation cannot see with `404`; [`explain_api_error`] turns both
+//! into an [`AccessError`] naming the permission and the fix.
+
+use thiserror::Error as ThisError;
+
+use crate::core::api::{ApiClient, ApiError, StoredToken};
+
+/// A repository permission of the belaf GitHub App installation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Permission {
+    /// `contents: write` — push the release branch.
+    Contents,
+    /// `pull_requests: write` — open the release PR.
+    PullRequests,
+    /// `workflows: write` — required by GitHub for any push that touches
+    /// `.github/workflows/`.
+    Workflows,
+}
+
+impl Permission {
+    /// The permission as GitHub's App settings page spells it.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Contents => "contents: write",
+            Self::PullRequests => "pull_requests: write",
+            Self::Workflows => "workflows: write",
+        }
+    }
+
+    /// The permission as the fine-grained personal access token form
+    /// spells it.
+    pub fn token_label(&self) -> &'static str {
+        match self {
+            Self::Contents => "Contents: Read and write",
+            Self::PullRequests => "Pull requests: Read and write",
+            Self::Workflows => "Workflows: Read and write",
+        }
+    }
+
+    /// What belaf was trying to do when it needed this permission.
+    pub fn operation(&self) -> &'static str {
+        match self {
+            Self::Contents => "push the release branch",
+            Self::PullRequests => "open the release pull request",
+            Self::Workflows => "push changes under `.github/workflows/`",
+        }
+    }
+}
+
+/// Everything `belaf prepare` needs to go from release commit to PR.
+pub const PREPARE_PERMISSIONS: &[Permission] = &[Permission::Contents, Permission::PullRequests];
+
+#[derive(Debug, ThisError)]
+#[non_exhaustive]
+pub enum AccessError {
+    #[error("the belaf GitHub App is not installed on {repo}")]
+    NotInstalled {
+        repo: String,
+        install_url: Option<String>,
+    },
+
+    #[error("the belaf GitHub App cannot see {repo} — it is not installed there, or the repository was excluded from the installation")]
+    RepositoryNotAccessible { repo: String },
+
+    #[error("the belaf GitHub App lacks `{}` on {repo}, which it needs to {}", .permission.label(), .permission.operation())]
+    MissingPermission {
+        repo: String,
+        permission: Permission,
+    },
+
+    #[error("the personal access token lacks `{}` on {repo}, which belaf needs to {}", .permission.token_label(), .permission.operation())]
+    TokenLacksPermission {
+        repo: String,
+        permission: Permission,
+    },
+}
+
+impl AccessError {
+    /// The `help:` line shown under the error.
+    pub fn hint(&self) -> String {
+        match self {
+            Self::NotInstalled {
+                install_url: Some(url),
+                ..
+            } => format!("install the App at {url}, then re-run `belaf install` to re-login"),
+            Self::NotInstalled { .. } | Self::RepositoryNotAccessible { .. } => {
+                "run `belaf install` to install the App on this repository and re-login".to_string()
+            }
+            Self::MissingPermission { permission, .. } => format!(
+                "grant `{}` in the belaf GitHub App's installation settings, then run `belaf install` to re-login",
+                permission.label()
+            ),
+            Self::TokenLacksPermission { repo, permission } => format!(
+                "give the token `{}` on {repo}, then run `belaf auth login --with-token` again",
+                permission.token_label()
+            ),
+        }
+    }
+}
+
+/// Verify the App is installed on `owner/repo` before doing any work.
+/// Individual permissions cannot be queried up front; they are checked
+/// when the call that needs them answers `403` (see
+/// [`explain_api_error`]).
+pub async fn preflight(
+    client: &ApiClient,
+    token: &StoredToken,
+    owner: &str,
+    repo: &str,
+) -> Result<(), anyhow::Error> {
+    let full = format!("{owner}/{repo}");
+    let status = client.check_installation(token, &full).await?;
+    if !status.installed {
+        return Err(AccessError::NotInstalled {
+            repo: full,
+            install_url: status.install_url,
+        }
+        .into());
+    }
+    Ok(())
+}
+
+/// Translate an API failure from a call that needed `permission` into
+/// an actionable error. Anything that isn't a `403`/`404` passes
+/// through unchanged.
+pub fn explain_api_error(
+    err: ApiError,
+    permission: Permission,
+    owner: &str,
+    repo: &str,
+) -> anyhow::Error {
+    let repo = format!("{owner}/{repo}");
+    match err {
+        ApiError::ApiResponse { status: 403, .. } => {
+            AccessError::MissingPermission { repo, permission }.into()
+        }
+        ApiError::ApiResponse { status: 404, .. } => {
+            AccessError::RepositoryNotAccessible { repo }.into()
+        }
+        other => other.into(),
+    }
+}
+
+/// GitHub refuses pushes that add or modify workflow files unless the
+/// pusher holds `workflows: write`, which the belaf App's push tokens
+/// never carry. Spot that in a failed push so the user gets the real
+/// reason instead of a generic transport error.
+pub fn is_workflow_push_rejection(message: &str) -> bool {
+    let lower = message.to_ascii_lowercase();
+    lower.contains("workflow") && (lower.contains("permission") || lower.contains("scope"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn forbidden_maps_to_missing_permission() {
+        let err = explain_api_error(
+            ApiError::ApiResponse {
+                status: 403,
+                message: "Resource not accessible by integration".into(),
+            },
+            Permission::PullRequests,
+            "acme",
+            "widgets",
+        );
+        let access = err.downcast_ref::<AccessError>().unwrap();
+        assert!(matches!(
+            access,
+            AccessError::MissingPermission {
+                permission: Permission::PullRequests,
+                ..
+            }
+        ));
+        assert!(err.to_string().contains("pull_requests: write"));
+        assert!(access.hint().contains("belaf install"));
+    }
+
+    #[test]
+    fn not_found_maps_to_repository_not_accessible() {
+        let err = explain_api_error(
+            ApiError::ApiResponse {
+                status: 404,
+                message: "Not Found".into(),
+            },
+            Permission::Contents,
+            "acme",
+            "widgets",
+        );
+        assert!(matches!(
+            err.downcast_ref::<AccessError>(),
+            Some(AccessError::RepositoryNotAccessible { .. })
+        ));
+    }
+
+    #[test]
+    fn other_errors_pass_through() {
+        let err = explain_api_error(ApiError::Unauthorized, Permission::Contents, "a", "b");
+        assert!(matches!(
+            err.downcast_ref::<ApiError>(),
+            Some(ApiError::Unauthorized)
+        ));
+    }
+
+    #[test]
+    fn detects_workflow_push_rejection() {
+        assert!(is_workflow_push_rejection(
+            "refusing to allow a GitHub App to create or update workflow \
+             `.github/workflows/ci.yml` without `workflows` permission"
+        ));
+        assert!(!is_workflow_push_rejection("connection reset by peer"));
+    }
+}
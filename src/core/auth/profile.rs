@@ -0,0 +1,323 @@
+//! Named auth profiles, for people who log in with more than one GitHub
+//! account (say `work` and `oss`).
+//!
+//! Each profile keeps its own token in the OS keyring (see
+//! [`crate::core::auth::token`]); the unnamed `default` profile uses the
+//! entry belaf has always used, so existing logins keep working. The list
+//! of profiles and the repositories they are matched against live in
+//! `profiles.toml` in the user's config directory, written by
+//! `belaf auth login --profile <name> --match <owner/repo>`.
+//!
+//! The profile in effect is, in order: `--profile` / `BELAF_PROFILE`,
+//! `[auth] profile` in `belaf/config.toml`, the first profile whose
+//! `match` patterns cover the upstream remote, and finally `default`.
+
+use std::{
+    collections::BTreeMap,
+    fmt,
+    path::{Path, PathBuf},
+    sync::RwLock,
+};
+
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::{
+    core::{
+        config::{peek_section, syntax::AuthConfiguration},
+        git::url::parse_github_url,
+    },
+    error::{CliError, Result},
+};
+
+/// The profile used when nothing selects another one.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Where the active profile came from, for `belaf auth status`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProfileSource {
+    /// `--profile` or `BELAF_PROFILE`.
+    Explicit,
+    /// `[auth] profile` in the repository config.
+    Config,
+    /// A `match` pattern in `profiles.toml` covered the upstream remote.
+    Remote,
+    Default,
+}
+
+impl fmt::Display for ProfileSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Explicit => "selected with --profile",
+            Self::Config => "from [auth] profile",
+            Self::Remote => "matched the upstream remote",
+            Self::Default => "default",
+        })
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct Selection {
+    explicit: Option<String>,
+    configured: Option<String>,
+    matched: Option<String>,
+}
+
+static SELECTION: RwLock<Selection> = RwLock::new(Selection {
+    explicit: None,
+    configured: None,
+    matched: None,
+});
+
+/// Profile names double as keyring keys and TOML table names, so keep
+/// them to something unsurprising in both.
+pub fn validate_name(name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(CliError::TokenStorage(format!(
+            "invalid profile name `{name}`: use letters, digits, `-` and `_`"
+        )))
+    }
+}
+
+/// Pin the profile for this process (`--profile` / `BELAF_PROFILE`);
+/// `None` unpins it.
+pub fn select(name: Option<&str>) -> Result<()> {
+    if let Some(name) = name {
+        validate_name(name)?;
+    }
+    if let Ok(mut sel) = SELECTION.write() {
+        sel.explicit = name.map(str::to_string);
+    }
+    Ok(())
+}
+
+/// Apply a repository's `[auth]` table and upstream remote. Called when a
+/// session loads its config; an explicit selection still wins.
+pub fn configure(cfg: &AuthConfiguration, upstream_url: Option<&str>) {
+    let matched = upstream_url.and_then(|url| match ProfileRegistry::load() {
+        Ok(registry) => registry.match_remote(url).map(str::to_string),
+        Err(e) => {
+            debug!("ignoring unreadable profile registry: {e}");
+            None
+        }
+    });
+
+    if let Ok(mut sel) = SELECTION.write() {
+        sel.configured = cfg.profile.clone();
+        sel.matched = matched;
+    }
+}
+
+/// Best-effort selection for commands that never open a session
+/// (`belaf install`, `belaf auth …`): peek at `[auth]` in
+/// `belaf/config.toml` and the `origin` remote of the repository
+/// containing the current directory.
+pub fn configure_from_env() {
+    let Ok(repo) = git2::Repository::open_from_env() else {
+        configure(&AuthConfiguration::default(), None);
+        return;
+    };
+    let cfg = peek_section::<AuthConfiguration>("auth")
+        .map(|(cfg, _)| cfg)
+        .unwrap_or_default();
+    let url = repo
+        .find_remote("origin")
+        .ok()
+        .and_then(|r| r.url().map(str::to_string));
+
+    configure(&cfg, url.as_deref());
+}
+
+/// The profile in effect and why.
+pub fn active() -> (String, ProfileSource) {
+    let sel = SELECTION.read().map(|s| s.clone()).unwrap_or_default();
+    if let Some(name) = sel.explicit {
+        (name, ProfileSource::Explicit)
+    } else if let Some(name) = sel.configured {
+        (name, ProfileSource::Config)
+    } else if let Some(name) = sel.matched {
+        (name, ProfileSource::Remote)
+    } else {
+        (DEFAULT_PROFILE.to_string(), ProfileSource::Default)
+    }
+}
+
+/// Name of the profile in effect.
+pub fn active_name() -> String {
+    active().0
+}
+
+/// belaf's per-user config directory (`~/.config/belaf` on Linux).
+pub fn user_config_dir() -> Option<PathBuf> {
+    directories::ProjectDirs::from("", "", "belaf")
+        .map(|d| d.config_dir().to_path_buf())
+        .or_else(|| dirs::config_dir().map(|d| d.join("belaf")))
+}
+
+/// One `[profiles.<name>]` table of `profiles.toml`.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct ProfileEntry {
+    /// `owner/repo` globs; `*` matches within one path segment.
+    #[serde(default, rename = "match", skip_serializing_if = "Vec::is_empty")]
+    pub remotes: Vec<String>,
+}
+
+/// The user-level list of profiles. Tokens themselves are never written
+/// here.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProfileRegistry {
+    #[serde(default)]
+    pub profiles: BTreeMap<String, ProfileEntry>,
+}
+
+impl ProfileRegistry {
+    /// `profiles.toml` in the platform config directory.
+    pub fn path() -> Option<PathBuf> {
+        user_config_dir().map(|d| d.join("profiles.toml"))
+    }
+
+    pub fn load() -> Result<Self> {
+        match Self::path() {
+            Some(path) => Self::load_from(&path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    pub fn load_from(path: &Path) -> Result<Self> {
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(e.into()),
+        };
+        toml::from_str(&text)
+            .map_err(|e| CliError::TokenStorage(format!("cannot parse `{}`: {e}", path.display())))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = Self::path().ok_or_else(|| {
+            CliError::TokenStorage("cannot determine the user config directory".to_string())
+        })?;
+        self.save_to(&path)
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let text = toml::to_string_pretty(self)
+            .map_err(|e| CliError::TokenStorage(format!("cannot serialize profiles: {e}")))?;
+        std::fs::write(path, text)?;
+        Ok(())
+    }
+
+    /// Record `name`, adding any new `match` patterns.
+    pub fn add(&mut self, name: &str, remotes: &[String]) {
+        let entry = self.profiles.entry(name.to_string()).or_default();
+        for remote in remotes {
+            if !entry.remotes.contains(remote) {
+                entry.remotes.push(remote.clone());
+            }
+        }
+    }
+
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.profiles.remove(name).is_some()
+    }
+
+    /// The first profile (by name) with a pattern covering `url`.
+    pub fn match_remote(&self, url: &str) -> Option<&str> {
+        let (owner, repo) = parse_github_url(url).ok()?;
+        let slug = format!("{owner}/{repo}");
+        self.profiles
+            .iter()
+            .find(|(_, entry)| entry.remotes.iter().any(|p| glob_match(p, &slug)))
+            .map(|(name, _)| name.as_str())
+    }
+}
+
+/// `owner/repo` glob match, case-insensitive like GitHub itself. `*`
+/// never crosses a `/`.
+fn glob_match(pattern: &str, slug: &str) -> bool {
+    let pattern = pattern.trim().trim_end_matches(".git").to_ascii_lowercase();
+    let slug = slug.to_ascii_lowercase();
+    let pat: Vec<&str> = pattern.split('/').collect();
+    let seg: Vec<&str> = slug.split('/').collect();
+    pat.len() == seg.len() && pat.iter().zip(&seg).all(|(p, s)| segment_match(p, s))
+}
+
+fn segment_match(pattern: &str, text: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == text,
+        Some((prefix, rest)) => {
+            let Some(text) = text.strip_prefix(prefix) else {
+                return false;
+            };
+            (0..=text.len())
+                .filter(|&i| text.is_char_boundary(i))
+                .any(|i| segment_match(rest, &text[i..]))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glob_matches_within_segments() {
+        assert!(glob_match("acme/*", "acme/widgets"));
+        assert!(glob_match("ACME/widgets", "acme/Widgets"));
+        assert!(glob_match("acme-*/*-api", "acme-eu/billing-api"));
+        assert!(!glob_match("acme/*", "acme-eu/widgets"));
+        assert!(!glob_match("*", "acme/widgets"));
+    }
+
+    #[test]
+    fn registry_matches_remote_urls() {
+        let mut registry = ProfileRegistry::default();
+        registry.add("work", &["acme/*".to_string()]);
+        registry.add("oss", &["me/*".to_string()]);
+
+        assert_eq!(
+            registry.match_remote("git@github.com:acme/widgets.git"),
+            Some("work")
+        );
+        assert_eq!(
+            registry.match_remote("https://github.com/me/dotfiles"),
+            Some("oss")
+        );
+        assert_eq!(registry.match_remote("https://github.com/other/x"), None);
+    }
+
+    #[test]
+    fn registry_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("belaf").join("profiles.toml");
+
+        let mut registry = ProfileRegistry::default();
+        registry.add("work", &["acme/*".to_string()]);
+        registry.add("work", &["acme/*".to_string(), "acme-eu/*".to_string()]);
+        registry.add("oss", &[]);
+        registry.save_to(&path).unwrap();
+
+        let loaded = ProfileRegistry::load_from(&path).unwrap();
+        assert_eq!(loaded.profiles["work"].remotes, ["acme/*", "acme-eu/*"]);
+        assert!(loaded.profiles["oss"].remotes.is_empty());
+    }
+
+    #[test]
+    fn rejects_odd_profile_names() {
+        assert!(validate_name("work").is_ok());
+        assert!(validate_name("oss_2").is_ok());
+        assert!(validate_name("").is_err());
+        assert!(validate_name("a:b").is_err());
+    }
+}
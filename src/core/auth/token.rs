@@ -1,50 +1,238 @@
+//! Persistence of belaf API tokens, one per auth profile.
+//!
+//! Tokens live in the OS credential store — macOS Keychain, Windows
+//! Credential Manager, or the Secret Service (libsecret / KWallet) on
+//! Linux. Headless machines often have no Secret Service running; there
+//! the token falls back to `credentials/<profile>.json` in the user config
+//! directory, created readable by the owner only. `BELAF_TOKEN_STORE=file`
+//! forces the file store. A token found in a file while the keychain
+//! works is moved into the keychain on first load.
+//!
+//! A GitHub personal access token stored by `belaf auth login
+//! --with-token` (see [`crate::core::auth::pat`]) is kept the same way,
+//! next to the profile's API token.
+
+use std::path::{Path, PathBuf};
+
+use serde::{de::DeserializeOwned, Serialize};
+
 use crate::core::api::oidc::is_actions_oidc_available;
 use crate::core::api::{ApiClient, StoredToken};
+use crate::core::auth::pat::StoredPat;
+use crate::core::auth::profile::{active_name, user_config_dir, DEFAULT_PROFILE};
 use crate::error::{CliError, Result};
 use keyring::Entry;
-use tracing::{info, warn};
+use tracing::{debug, info, warn};
 
 const SERVICE_NAME: &str = "belaf";
 const TOKEN_KEY: &str = "api-token";
+const PAT_KEY: &str = "github-pat";
+
+/// Environment variable choosing the token store: `keychain` (default)
+/// or `file`.
+pub const TOKEN_STORE_ENV: &str = "BELAF_TOKEN_STORE";
 
 fn is_keyring_disabled() -> bool {
     std::env::var("BELAF_NO_KEYRING").is_ok()
 }
 
+fn is_file_store_forced() -> bool {
+    std::env::var(TOKEN_STORE_ENV).is_ok_and(|v| v.eq_ignore_ascii_case("file"))
+}
+
+/// The keychain itself is missing or locked, as opposed to the entry
+/// being absent or malformed. These are the cases the file store covers.
+fn is_keychain_unavailable(e: &keyring::Error) -> bool {
+    matches!(
+        e,
+        keyring::Error::PlatformFailure(_) | keyring::Error::NoStorageAccess(_)
+    )
+}
+
+/// Which of a profile's secrets is meant.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Slot {
+    /// The belaf API token.
+    ApiToken,
+    /// A GitHub personal access token.
+    Pat,
+}
+
+/// Keyring entry for a profile. `default` keeps the original key so
+/// logins from before profiles existed still load.
+fn slot_key(slot: Slot, profile: &str) -> String {
+    let key = match slot {
+        Slot::ApiToken => TOKEN_KEY,
+        Slot::Pat => PAT_KEY,
+    };
+    if profile == DEFAULT_PROFILE {
+        key.to_string()
+    } else {
+        format!("{key}:{profile}")
+    }
+}
+
+fn entry_for(slot: Slot, profile: &str) -> Result<Entry> {
+    Entry::new(SERVICE_NAME, &slot_key(slot, profile))
+        .map_err(|e| CliError::TokenStorage(format!("Failed to create keyring entry: {}", e)))
+}
+
+/// Fallback location of a profile's token.
+pub fn token_file_path(profile: &str) -> Option<PathBuf> {
+    slot_file_path(Slot::ApiToken, profile)
+}
+
+fn slot_file_path(slot: Slot, profile: &str) -> Option<PathBuf> {
+    let name = match slot {
+        Slot::ApiToken => format!("{profile}.json"),
+        Slot::Pat => format!("{profile}.pat.json"),
+    };
+    user_config_dir().map(|d| d.join("credentials").join(name))
+}
+
+fn serialize<T: Serialize>(token: &T) -> Result<String> {
+    serde_json::to_string(token)
+        .map_err(|e| CliError::TokenStorage(format!("Failed to serialize token: {}", e)))
+}
+
+fn deserialize<T: DeserializeOwned>(json: &str) -> Result<T> {
+    serde_json::from_str(json)
+        .map_err(|e| CliError::TokenStorage(format!("Failed to parse token: {}", e)))
+}
+
+fn write_token_file<T: Serialize>(path: &Path, token: &T) -> Result<()> {
+    use std::io::Write;
+
+    let json = serialize(token)?;
+    if let Some(parent) = path.parent() {
+        let mut builder = std::fs::DirBuilder::new();
+        builder.recursive(true);
+        #[cfg(unix)]
+        std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+        builder.create(parent)?;
+    }
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options.open(path)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+    }
+    file.write_all(json.as_bytes())?;
+    Ok(())
+}
+
+fn read_secret_file<T: DeserializeOwned>(path: &Path) -> Result<Option<T>> {
+    match std::fs::read_to_string(path) {
+        Ok(json) => deserialize(&json).map(Some),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn remove_token_file(path: &Path) -> Result<()> {
+    match std::fs::remove_file(path) {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn require_token_file(slot: Slot, profile: &str) -> Result<PathBuf> {
+    slot_file_path(slot, profile).ok_or_else(|| {
+        CliError::TokenStorage("cannot determine the user config directory".to_string())
+    })
+}
+
+/// Save the token of the active profile.
 pub fn save_token(token: &StoredToken) -> Result<()> {
+    save_profile_token(&active_name(), token)
+}
+
+pub fn save_profile_token(profile: &str, token: &StoredToken) -> Result<()> {
+    save_secret(Slot::ApiToken, profile, token)
+}
+
+/// Save the personal access token of the active profile.
+pub fn save_pat(pat: &StoredPat) -> Result<()> {
+    save_secret(Slot::Pat, &active_name(), pat)
+}
+
+fn save_secret<T: Serialize>(slot: Slot, profile: &str, token: &T) -> Result<()> {
     if is_keyring_disabled() {
         warn!("BELAF_NO_KEYRING is set - token will not be persisted");
         return Ok(());
     }
 
-    let entry = Entry::new(SERVICE_NAME, TOKEN_KEY)
-        .map_err(|e| CliError::TokenStorage(format!("Failed to create keyring entry: {}", e)))?;
+    if is_file_store_forced() {
+        return write_token_file(&require_token_file(slot, profile)?, token);
+    }
+
+    match entry_for(slot, profile)?.set_password(&serialize(token)?) {
+        Ok(()) => match slot_file_path(slot, profile) {
+            Some(path) => remove_token_file(&path),
+            None => Ok(()),
+        },
+        Err(e) if is_keychain_unavailable(&e) => {
+            let path = require_token_file(slot, profile)?;
+            warn!(
+                "OS keychain unavailable ({e}); storing token in `{}` instead",
+                path.display()
+            );
+            write_token_file(&path, token)
+        }
+        Err(e) => Err(CliError::TokenStorage(format!(
+            "Failed to save token: {}",
+            e
+        ))),
+    }
+}
 
-    let json = serde_json::to_string(token)
-        .map_err(|e| CliError::TokenStorage(format!("Failed to serialize token: {}", e)))?;
+/// Load the token of the active profile.
+pub fn load_token() -> Result<Option<StoredToken>> {
+    load_profile_token(&active_name())
+}
 
-    entry
-        .set_password(&json)
-        .map_err(|e| CliError::TokenStorage(format!("Failed to save token: {}", e)))?;
+pub fn load_profile_token(profile: &str) -> Result<Option<StoredToken>> {
+    load_secret(Slot::ApiToken, profile)
+}
 
-    Ok(())
+/// Load the personal access token of the active profile.
+pub fn load_pat() -> Result<Option<StoredPat>> {
+    load_secret(Slot::Pat, &active_name())
 }
 
-pub fn load_token() -> Result<Option<StoredToken>> {
+fn load_secret<T: Serialize + DeserializeOwned>(slot: Slot, profile: &str) -> Result<Option<T>> {
     if is_keyring_disabled() {
         return Ok(None);
     }
 
-    let entry = Entry::new(SERVICE_NAME, TOKEN_KEY)
-        .map_err(|e| CliError::TokenStorage(format!("Failed to create keyring entry: {}", e)))?;
+    let path = slot_file_path(slot, profile);
+    if is_file_store_forced() {
+        return path.map_or(Ok(None), |p| read_secret_file(&p));
+    }
 
+    let entry = entry_for(slot, profile)?;
     match entry.get_password() {
-        Ok(json) => {
-            let token: StoredToken = serde_json::from_str(&json)
-                .map_err(|e| CliError::TokenStorage(format!("Failed to parse token: {}", e)))?;
+        Ok(json) => deserialize(&json).map(Some),
+        Err(keyring::Error::NoEntry) => {
+            let Some(path) = path else {
+                return Ok(None);
+            };
+            let Some(token) = read_secret_file(&path)? else {
+                return Ok(None);
+            };
+            migrate_to_keychain(&entry, &path, &token);
             Ok(Some(token))
         }
-        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) if is_keychain_unavailable(&e) => {
+            debug!("OS keychain unavailable ({e}); falling back to the token file");
+            path.map_or(Ok(None), |p| read_secret_file(&p))
+        }
         Err(e) => Err(CliError::TokenStorage(format!(
             "Failed to load token: {}",
             e
@@ -52,10 +240,29 @@ pub fn load_token() -> Result<Option<StoredToken>> {
     }
 }
 
+/// Move a file-stored token into the keychain now that one is reachable.
+/// Failure leaves the file in place; the token still works either way.
+fn migrate_to_keychain<T: Serialize>(entry: &Entry, path: &Path, token: &T) {
+    let moved = serialize(token)
+        .ok()
+        .is_some_and(|json| entry.set_password(&json).is_ok());
+    if !moved {
+        return;
+    }
+    match remove_token_file(path) {
+        Ok(()) => info!("moved token from `{}` into the OS keychain", path.display()),
+        Err(e) => warn!(
+            "token copied into the OS keychain, but `{}` could not be removed: {e}",
+            path.display()
+        ),
+    }
+}
+
 /// Loads a token for outbound `/api/cli/*` calls, with an OIDC fallback for CI.
 ///
 /// Resolution order:
-/// 1. Existing token from the OS keyring (interactive `belaf install` path).
+/// 1. Existing token of the active profile from the OS keyring or its file
+///    fallback (interactive `belaf install` / `belaf auth login` path).
 /// 2. If `ACTIONS_ID_TOKEN_REQUEST_*` env vars are set (GitHub Actions runner
 ///    with `permissions: id-token: write`), mint an OIDC JWT and exchange it
 ///    via `POST /api/cli/auth/oidc/exchange`. The result is **not** persisted
@@ -84,16 +291,95 @@ pub async fn load_or_exchange_token(client: &ApiClient) -> Result<Option<StoredT
     }
 }
 
+/// Delete the token of the active profile.
 pub fn delete_token() -> Result<()> {
-    let entry = Entry::new(SERVICE_NAME, TOKEN_KEY)
-        .map_err(|e| CliError::TokenStorage(format!("Failed to create keyring entry: {}", e)))?;
+    delete_profile_token(&active_name())
+}
+
+/// Remove a profile's token from the keychain and the file fallback.
+pub fn delete_profile_token(profile: &str) -> Result<()> {
+    delete_secret(Slot::ApiToken, profile)
+}
+
+/// Delete the personal access token of the active profile.
+pub fn delete_pat() -> Result<()> {
+    delete_secret(Slot::Pat, &active_name())
+}
 
-    match entry.delete_credential() {
+fn delete_secret(slot: Slot, profile: &str) -> Result<()> {
+    if let Some(path) = slot_file_path(slot, profile) {
+        remove_token_file(&path)?;
+    }
+    if is_file_store_forced() {
+        return Ok(());
+    }
+
+    match entry_for(slot, profile)?.delete_credential() {
         Ok(()) => Ok(()),
         Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) if is_keychain_unavailable(&e) => Ok(()),
         Err(e) => Err(CliError::TokenStorage(format!(
             "Failed to delete token: {}",
             e
         ))),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token_key(profile: &str) -> String {
+        slot_key(Slot::ApiToken, profile)
+    }
+
+    fn read_token_file(path: &Path) -> Result<Option<StoredToken>> {
+        read_secret_file(path)
+    }
+
+    #[test]
+    fn default_profile_keeps_legacy_key() {
+        assert_eq!(token_key(DEFAULT_PROFILE), "api-token");
+        assert_eq!(token_key("work"), "api-token:work");
+    }
+
+    #[test]
+    fn personal_access_tokens_have_their_own_slot() {
+        assert_eq!(slot_key(Slot::Pat, DEFAULT_PROFILE), "github-pat");
+        assert_eq!(slot_key(Slot::Pat, "work"), "github-pat:work");
+        let file = slot_file_path(Slot::Pat, "work");
+        assert_ne!(file, token_file_path("work"));
+        assert!(file.is_none_or(|f| f.ends_with("credentials/work.pat.json")));
+    }
+
+    #[test]
+    fn token_file_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("credentials").join("work.json");
+
+        assert!(read_token_file(&path).unwrap().is_none());
+
+        let token = StoredToken::new("secret".to_string(), Some(3600));
+        write_token_file(&path, &token).unwrap();
+        let loaded = read_token_file(&path).unwrap().unwrap();
+        assert_eq!(loaded.access_token, "secret");
+        assert_eq!(loaded.expires_at, token.expires_at);
+
+        remove_token_file(&path).unwrap();
+        remove_token_file(&path).unwrap();
+        assert!(read_token_file(&path).unwrap().is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn token_file_is_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("credentials").join("default.json");
+        write_token_file(&path, &StoredToken::new("secret".to_string(), None)).unwrap();
+
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+}
@@ -115,9 +115,52 @@ impl BumpRecommendation {
     }
 }
 
+/// What a commit contributes to the bump.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    Breaking,
+    Feature,
+    Fix,
+    Revert,
+}
+
+impl ChangeKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Breaking => "breaking",
+            Self::Feature => "feature",
+            Self::Fix => "fix",
+            Self::Revert => "revert",
+        }
+    }
+
+    pub fn bump(&self) -> BumpRecommendation {
+        match self {
+            Self::Breaking => BumpRecommendation::Major,
+            Self::Feature => BumpRecommendation::Minor,
+            Self::Fix | Self::Revert => BumpRecommendation::Patch,
+        }
+    }
+}
+
+/// A commit that calls for a bump, and why.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BumpReason {
+    /// Abbreviated commit id; empty for bare messages.
+    pub commit: String,
+    pub kind: ChangeKind,
+    /// First line of the commit message.
+    pub subject: String,
+}
+
+/// The bump a set of commits calls for, the commits behind it and a
+/// tally by type.
 #[derive(Debug)]
-pub struct CommitAnalysis {
+pub struct BumpAnalysis {
     pub recommendation: BumpRecommendation,
+    /// Every breaking, feature, fix and revert commit, oldest first.
+    pub reasons: Vec<BumpReason>,
     pub total_commits: usize,
     pub feat_count: usize,
     pub fix_count: usize,
@@ -126,10 +169,11 @@ pub struct CommitAnalysis {
     pub other_count: usize,
 }
 
-impl Default for CommitAnalysis {
+impl Default for BumpAnalysis {
     fn default() -> Self {
         Self {
             recommendation: BumpRecommendation::None,
+            reasons: Vec::new(),
             total_commits: 0,
             feat_count: 0,
             fix_count: 0,
@@ -140,7 +184,15 @@ impl Default for CommitAnalysis {
     }
 }
 
-impl CommitAnalysis {
+impl BumpAnalysis {
+    /// The commits that decided the recommendation: those calling for
+    /// exactly that bump.
+    pub fn drivers(&self) -> impl Iterator<Item = &BumpReason> {
+        self.reasons
+            .iter()
+            .filter(|reason| reason.kind.bump() == self.recommendation)
+    }
+
     pub fn summary(&self) -> String {
         if self.total_commits == 0 {
             return "No commits to analyze".to_string();
@@ -181,8 +233,8 @@ impl CommitAnalysis {
     }
 }
 
-pub fn analyze_commits(commits: &[Commit]) -> Result<CommitAnalysis> {
-    let mut analysis = CommitAnalysis {
+pub fn analyze_commits(commits: &[Commit]) -> Result<BumpAnalysis> {
+    let mut analysis = BumpAnalysis {
         recommendation: BumpRecommendation::None,
         total_commits: commits.len(),
         ..Default::default()
@@ -191,19 +243,19 @@ pub fn analyze_commits(commits: &[Commit]) -> Result<CommitAnalysis> {
     for commit in commits {
         let commit_result = git_conventional::Commit::parse(&commit.message);
 
-        let recommendation = match commit_result {
+        let kind = match commit_result {
             Ok(conv) => {
                 if conv.breaking() {
                     analysis.breaking_count += 1;
-                    BumpRecommendation::Major
+                    Some(ChangeKind::Breaking)
                 } else {
                     let commit_type = conv.type_();
                     if commit_type == Type::FEAT {
                         analysis.feat_count += 1;
-                        BumpRecommendation::Minor
+                        Some(ChangeKind::Feature)
                     } else if commit_type == Type::FIX || commit_type == Type::PERF {
                         analysis.fix_count += 1;
-                        BumpRecommendation::Patch
+                        Some(ChangeKind::Fix)
                     } else if commit_type == Type::REVERT {
                         // `revert:` is part of the Conventional Commits
                         // spec. Treat it like a fix — undoing a prior
@@ -213,10 +265,10 @@ pub fn analyze_commits(commits: &[Commit]) -> Result<CommitAnalysis> {
                         // footer) is caught by the `conv.breaking()`
                         // branch above and lifted to major.
                         analysis.revert_count += 1;
-                        BumpRecommendation::Patch
+                        Some(ChangeKind::Revert)
                     } else {
                         analysis.other_count += 1;
-                        BumpRecommendation::None
+                        None
                     }
                 }
             }
@@ -229,15 +281,27 @@ pub fn analyze_commits(commits: &[Commit]) -> Result<CommitAnalysis> {
                 // and the result needs to ship as a new release.
                 if is_git_autogenerated_revert(&commit.message) {
                     analysis.revert_count += 1;
-                    BumpRecommendation::Patch
+                    Some(ChangeKind::Revert)
                 } else {
                     analysis.other_count += 1;
-                    BumpRecommendation::None
+                    None
                 }
             }
         };
 
-        analysis.recommendation = analysis.recommendation.merge(recommendation);
+        if let Some(kind) = kind {
+            analysis.recommendation = analysis.recommendation.merge(kind.bump());
+            analysis.reasons.push(BumpReason {
+                commit: commit.id.chars().take(7).collect(),
+                kind,
+                subject: commit
+                    .message
+                    .lines()
+                    .next()
+                    .unwrap_or_default()
+                    .to_string(),
+            });
+        }
     }
 
     Ok(analysis)
@@ -253,7 +317,7 @@ fn is_git_autogenerated_revert(message: &str) -> bool {
     first_line.starts_with("Revert \"") && first_line.ends_with('"')
 }
 
-pub fn analyze_commit_messages(messages: &[String]) -> Result<CommitAnalysis> {
+pub fn analyze_commit_messages(messages: &[String]) -> Result<BumpAnalysis> {
     let commits: Vec<Commit> = messages
         .iter()
         .map(|msg| Commit {
@@ -497,6 +561,46 @@ mod tests {
         assert_eq!(analysis.recommendation, BumpRecommendation::Minor);
     }
 
+    #[test]
+    fn test_analysis_names_the_commits_behind_the_bump() {
+        let commits: Vec<Commit> = [
+            ("1a2b3c4d5e", "fix: fix bug"),
+            ("2b3c4d5e6f", "feat(api): add streaming\n\nLong body."),
+            ("3c4d5e6f70", "docs: update"),
+            ("4d5e6f7081", "feat: add retries"),
+        ]
+        .into_iter()
+        .map(|(id, message)| Commit {
+            id: id.to_string(),
+            message: message.to_string(),
+            ..Default::default()
+        })
+        .collect();
+        let analysis = analyze_commits(&commits).unwrap();
+        assert_eq!(analysis.reasons.len(), 3);
+        let drivers: Vec<_> = analysis.drivers().collect();
+        assert_eq!(
+            drivers,
+            [
+                &BumpReason {
+                    commit: "2b3c4d5".to_string(),
+                    kind: ChangeKind::Feature,
+                    subject: "feat(api): add streaming".to_string(),
+                },
+                &BumpReason {
+                    commit: "4d5e6f7".to_string(),
+                    kind: ChangeKind::Feature,
+                    subject: "feat: add retries".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            analyze_commits(&[]).unwrap().drivers().count(),
+            0,
+            "no commits, no reasons"
+        );
+    }
+
     #[test]
     fn test_extract_scope() {
         assert_eq!(
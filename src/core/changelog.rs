@@ -1,21 +1,32 @@
+mod authors;
+mod cache;
 mod command;
 mod commit;
 mod config;
 mod contributor;
+mod dependencies;
 mod error;
 mod generator;
 mod github;
+mod links;
 mod release;
 mod remote;
 mod statistics;
 mod template;
 
+pub use authors::AuthorUsernames;
+pub use cache::{select_disabled as select_cache_disabled, EntryCache};
 pub use commit::{Commit, Footer, Link, Range, Signature};
-pub use config::{ChangelogConfig, CommitParser, GitConfig, LinkParser, TextProcessor};
+pub use config::{
+    context_variable_error, ChangelogConfig, CommitParser, FormatCommand, GitConfig, LinkParser,
+    TextProcessor,
+};
 pub use contributor::RemoteContributor;
+pub use dependencies::{updates_between as dependency_updates, DependencyUpdate};
 pub use error::{Error, Result};
 pub use generator::{Changelog, RemoteConfig};
 pub use github::GitHubClient;
+pub use links::{LinkForge, LinkStyle, RepoLinks};
 pub use release::{Release, Releases};
 pub use remote::{RemoteCommit, RemoteMetadata, RemotePullRequest, RemoteReleaseMetadata};
 pub use statistics::{LinkCount, Statistics};
@@ -0,0 +1,170 @@
+//! Forge usernames for commit authors.
+//!
+//! Git records an author's name and email, not their forge account, so
+//! a template can't mention `@handle` from git data alone. Before the
+//! changelog renders, each commit's `author.username` is filled in from,
+//! in order:
+//!
+//! 1. a noreply address, which names the account outright:
+//!    `12345+octocat@users.noreply.github.com` (also on GitHub
+//!    Enterprise hosts) or `12345-octocat@users.noreply.gitlab.com`;
+//! 2. the login the forge reports for the commit itself, which the
+//!    GitHub metadata pass stores as `remote.username`;
+//! 3. what earlier runs learnt from (2): every email the forge linked
+//!    to a login is remembered in `.git/belaf/author-usernames.json`,
+//!    so commits the forge hasn't seen yet (or a run without a login)
+//!    still resolve.
+//!
+//! Authors none of these know keep `username` unset.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use super::commit::Commit;
+
+/// Email → forge username, as learnt from the forge.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct AuthorUsernames {
+    by_email: BTreeMap<String, String>,
+    changed: bool,
+}
+
+impl AuthorUsernames {
+    /// Cache of learnt usernames, relative to the git directory.
+    pub const CACHE_FILE: &str = "belaf/author-usernames.json";
+
+    /// The cache at `path`; empty when it is missing or unreadable.
+    pub fn load(path: &Path) -> Self {
+        let by_email = fs::read_to_string(path)
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+        Self {
+            by_email,
+            changed: false,
+        }
+    }
+
+    /// Write the cache to `path` if anything new was learnt. Failing to
+    /// is only logged: the cache is an optimisation.
+    pub fn save(&self, path: &Path) {
+        if !self.changed {
+            return;
+        }
+        let result = path
+            .parent()
+            .map_or(Ok(()), fs::create_dir_all)
+            .and_then(|()| {
+                let json = serde_json::to_string_pretty(&self.by_email)?;
+                fs::write(path, json)
+            });
+        if let Err(e) = result {
+            log::warn!("failed to write `{}`: {e}", path.display());
+        }
+    }
+
+    /// Remember the login the forge reported for each of `commits`.
+    pub fn learn(&mut self, commits: &[Commit]) {
+        for commit in commits {
+            let (Some(email), Some(username)) = (
+                commit.author.email.as_deref(),
+                commit.remote.as_ref().and_then(|r| r.username.as_deref()),
+            ) else {
+                continue;
+            };
+            let previous = self
+                .by_email
+                .insert(email.to_lowercase(), username.to_string());
+            self.changed |= previous.as_deref() != Some(username);
+        }
+    }
+
+    /// Set `author.username` on each of `commits` that can be resolved.
+    pub fn apply(&self, commits: &mut [Commit]) {
+        for commit in commits {
+            let from_forge = commit.remote.as_ref().and_then(|r| r.username.clone());
+            commit.author.username = commit
+                .author
+                .email
+                .as_deref()
+                .and_then(noreply_username)
+                .or(from_forge)
+                .or_else(|| {
+                    let email = commit.author.email.as_deref()?.to_lowercase();
+                    self.by_email.get(&email).cloned()
+                });
+        }
+    }
+}
+
+/// The account a forge noreply address belongs to.
+fn noreply_username(email: &str) -> Option<String> {
+    let (local, domain) = email.rsplit_once('@')?;
+    let domain = domain.to_ascii_lowercase();
+    let username = if domain == "users.noreply.gitlab.com" {
+        // `<id>-<username>`
+        local.split_once('-').map_or(local, |(_, name)| name)
+    } else if domain.starts_with("users.noreply.") {
+        // `<id>+<login>`, or just `<login>` for older accounts.
+        local.split_once('+').map_or(local, |(_, name)| name)
+    } else {
+        return None;
+    };
+    (!username.is_empty()).then(|| username.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::changelog::RemoteContributor;
+
+    fn commit(email: &str, login: Option<&str>) -> Commit {
+        let mut commit = Commit::new("abc".into(), "fix: x".into());
+        commit.author.email = Some(email.into());
+        commit.remote = login.map(|login| RemoteContributor {
+            username: Some(login.into()),
+            ..Default::default()
+        });
+        commit
+    }
+
+    #[test]
+    fn noreply_addresses_name_the_account() {
+        assert_eq!(
+            noreply_username("583231+octocat@users.noreply.github.com").as_deref(),
+            Some("octocat")
+        );
+        assert_eq!(
+            noreply_username("octocat@users.noreply.ghe.example.com").as_deref(),
+            Some("octocat")
+        );
+        assert_eq!(
+            noreply_username("1234-jdoe@users.noreply.gitlab.com").as_deref(),
+            Some("jdoe")
+        );
+        assert_eq!(noreply_username("jane@example.com"), None);
+    }
+
+    #[test]
+    fn usernames_learnt_from_the_forge_resolve_later_commits() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let cache = dir.path().join(AuthorUsernames::CACHE_FILE);
+
+        let mut known = AuthorUsernames::load(&cache);
+        let mut commits = vec![commit("Jane@Example.com", Some("jdoe"))];
+        known.learn(&commits);
+        known.apply(&mut commits);
+        assert_eq!(commits[0].author.username.as_deref(), Some("jdoe"));
+        known.save(&cache);
+
+        let known = AuthorUsernames::load(&cache);
+        let mut commits = vec![
+            commit("jane@example.com", None),
+            commit("bob@example.com", None),
+        ];
+        known.apply(&mut commits);
+        assert_eq!(commits[0].author.username.as_deref(), Some("jdoe"));
+        assert_eq!(commits[1].author.username, None);
+    }
+}
@@ -0,0 +1,136 @@
+//! Rendered changelog entries, kept between runs.
+//!
+//! Rendering an entry is cheap, but `translate_command` and
+//! `format_command` run external programs that may be slow or cost
+//! money per call. Running `belaf prepare` or `belaf changelog` again
+//! over the same commits would pay for them again to get the same text,
+//! so every finished entry is stored under
+//! `belaf/.cache/changelog/<project>/<key>.md`.
+//!
+//! The key hashes everything the entry is made of: the belaf version,
+//! the release (its commits, version, tags and date, but not the time of
+//! day), the template context and the resolved `[changelog]` section,
+//! templates and commands included. A new commit, a changed template or
+//! another day makes a new key; stale files are never read again.
+//!
+//! `--no-cache` renders every entry afresh. A cache that can't be read
+//! or written is only logged, and repositories without a `belaf/`
+//! directory get no cache at all.
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::PathBuf,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use sha2::{Digest, Sha256};
+
+use super::config::ChangelogConfig;
+use super::release::Release;
+use crate::core::git::repository::Repository;
+use crate::core::release_unit::discovery_cache::CACHE_DIR;
+
+static DISABLED: AtomicBool = AtomicBool::new(false);
+
+/// Turn the cache off for this process (`--no-cache`), or back on.
+pub fn select_disabled(disabled: bool) {
+    DISABLED.store(disabled, Ordering::Relaxed);
+}
+
+/// The cached entries of one project.
+#[derive(Clone, Debug)]
+pub struct EntryCache {
+    dir: PathBuf,
+}
+
+impl EntryCache {
+    /// The cache of `project` in `repo`, or `None` under `--no-cache` or
+    /// before `belaf init`.
+    pub fn for_repo(repo: &Repository, project: &str) -> Option<Self> {
+        if DISABLED.load(Ordering::Relaxed) {
+            return None;
+        }
+        let config_dir = repo.resolve_config_dir();
+        // Scoped npm names contain a `/`.
+        let project = project.replace(['/', '\\'], "_");
+        config_dir
+            .is_dir()
+            .then(|| Self::at(config_dir.join(CACHE_DIR).join("changelog").join(project)))
+    }
+
+    fn at(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// The key of the entry rendered from `release`; `None` if any part
+    /// of it can't be serialised.
+    pub fn key(
+        release: &Release,
+        context: &HashMap<String, serde_json::Value>,
+        config: &ChangelogConfig,
+    ) -> Option<String> {
+        let mut release = serde_json::to_value(release).ok()?;
+        if let Some(fields) = release.as_object_mut() {
+            fields.remove("timestamp");
+        }
+        let mut hasher = Sha256::new();
+        for part in [
+            serde_json::Value::String(env!("CARGO_PKG_VERSION").to_string()),
+            release,
+            serde_json::to_value(context).ok()?,
+            serde_json::to_value(config).ok()?,
+        ] {
+            hasher.update(part.to_string());
+            hasher.update([0]);
+        }
+        Some(format!("{:x}", hasher.finalize()))
+    }
+
+    pub fn get(&self, key: &str) -> Option<String> {
+        fs::read_to_string(self.dir.join(format!("{key}.md"))).ok()
+    }
+
+    pub fn put(&self, key: &str, entry: &str) {
+        let path = self.dir.join(format!("{key}.md"));
+        if let Err(e) = fs::create_dir_all(&self.dir).and_then(|()| fs::write(&path, entry)) {
+            log::debug!(
+                "failed to cache changelog entry at `{}`: {e}",
+                path.display()
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::changelog::Commit;
+
+    fn release(id: &str, timestamp: i64) -> Release {
+        Release {
+            version: Some("1.1.0".to_string()),
+            commits: vec![Commit::new(id.to_string(), "feat: add export".to_string())],
+            timestamp: Some(timestamp),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn keys_follow_the_commits_not_the_clock() {
+        let defaults = crate::core::embed::EmbeddedConfig::parse().unwrap();
+        let config = ChangelogConfig::from_user_config(&defaults.changelog);
+        let context = HashMap::new();
+        let key = |r: &Release| EntryCache::key(r, &context, &config).unwrap();
+
+        assert_eq!(key(&release("abc1234", 1)), key(&release("abc1234", 2)));
+        assert_ne!(key(&release("abc1234", 1)), key(&release("def5678", 1)));
+
+        let dir = tempfile::tempdir().unwrap();
+        let cache = EntryCache::at(dir.path().join("api"));
+        let k = key(&release("abc1234", 1));
+        assert_eq!(cache.get(&k), None);
+        cache.put(&k, "## 1.1.0\n");
+        assert_eq!(cache.get(&k).as_deref(), Some("## 1.1.0\n"));
+    }
+}
@@ -30,9 +30,11 @@ pub fn run(command: &str, input: Option<String>, envs: Vec<(&str, &str)>) -> Res
             .take()
             .ok_or_else(|| Error::CommandError("stdin is not captured".to_string()))?;
         thread::spawn(move || {
-            stdin
-                .write_all(input.as_bytes())
-                .expect("Failed to write to stdin");
+            // A command may exit without reading its input; its exit
+            // status is what gets reported, not the broken pipe.
+            if let Err(e) = stdin.write_all(input.as_bytes()) {
+                log::trace!("failed to write to stdin: {}", e);
+            }
         });
     }
 
@@ -69,6 +69,10 @@ pub struct Signature {
     pub name: Option<String>,
     pub email: Option<String>,
     pub timestamp: i64,
+    /// Forge account of the author, resolved by
+    /// [`super::authors::AuthorUsernames`].
+    #[serde(default)]
+    pub username: Option<String>,
 }
 
 impl<'a> From<CommitSignature<'a>> for Signature {
@@ -77,6 +81,7 @@ impl<'a> From<CommitSignature<'a>> for Signature {
             name: signature.name().map(String::from),
             email: signature.email().map(String::from),
             timestamp: signature.when().seconds(),
+            username: None,
         }
     }
 }
@@ -113,6 +118,10 @@ pub struct Commit {
     pub extra: Option<Value>,
     pub remote: Option<RemoteContributor>,
     pub raw_message: Option<String>,
+    /// Files the commit changed relative to its first parent, for
+    /// [`CommitParser::paths`].
+    #[serde(default)]
+    pub paths: Vec<String>,
 }
 
 impl From<String> for Commit {
@@ -234,6 +243,14 @@ impl Commit {
             ) {
                 regex_checks.extend(footers.iter().map(|f| (footer_regex, f.value.clone())));
             }
+            if let Some(author_regex) = parser.author.as_ref() {
+                regex_checks.extend(
+                    [&self.author.name, &self.author.email]
+                        .into_iter()
+                        .flatten()
+                        .map(|s| (author_regex, s.clone())),
+                );
+            }
             if let (Some(field_name), Some(pattern_regex)) =
                 (parser.field.as_ref(), parser.pattern.as_ref())
             {
@@ -303,10 +320,23 @@ impl Commit {
                         self.group = parser.group.clone().map(regex_replace);
                         self.scope = parser.scope.clone().map(regex_replace);
                         self.default_scope.clone_from(&parser.default_scope);
-                        return Ok(self);
+                        return Ok(self.group_from_scope(parser));
                     }
                 }
             }
+            let touches_paths = self
+                .paths
+                .iter()
+                .any(|path| parser.paths.iter().any(|glob| glob.matches(path)));
+            if touches_paths {
+                if self.skip_commit(parser, protect_breaking) {
+                    return Err(Error::GroupError(String::from("Skipping commit")));
+                }
+                self.group.clone_from(&parser.group);
+                self.scope.clone_from(&parser.scope);
+                self.default_scope.clone_from(&parser.default_scope);
+                return Ok(self.group_from_scope(parser));
+            }
         }
         if filter {
             Err(Error::GroupError(String::from(
@@ -317,6 +347,22 @@ impl Commit {
         }
     }
 
+    /// With `group_from_scope`, the group `parser` just assigned is
+    /// replaced by the commit's scope, if it has one.
+    fn group_from_scope(mut self, parser: &CommitParser) -> Self {
+        if parser.group_from_scope {
+            let scope = self
+                .scope
+                .clone()
+                .or_else(|| self.conv.as_ref().and_then(|c| c.scope.clone()))
+                .or_else(|| self.default_scope.clone());
+            if scope.is_some() {
+                self.group = scope;
+            }
+        }
+        self
+    }
+
     pub fn parse_links(mut self, parsers: &[LinkParser]) -> Self {
         for parser in parsers {
             let regex = &parser.pattern;
@@ -394,6 +440,7 @@ impl Serialize for Commit {
             commit.serialize_field("remote", remote)?;
         }
         commit.serialize_field("raw_message", &self.raw_message())?;
+        commit.serialize_field("paths", &self.paths)?;
         commit.end()
     }
 }
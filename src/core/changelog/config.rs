@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 use glob::Pattern;
@@ -5,7 +6,8 @@ use regex::Regex;
 use serde::{Deserialize, Serialize};
 
 use super::command;
-use super::error::Result;
+use super::error::{Error, Result};
+use super::links::LinkStyle;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GitConfig {
@@ -39,6 +41,8 @@ pub struct GitConfig {
     pub topo_order_commits: bool,
     pub sort_commits: String,
     #[serde(default)]
+    pub dedupe_commits: bool,
+    #[serde(default)]
     pub limit_commits: Option<usize>,
     #[serde(default)]
     pub recurse_submodules: Option<bool>,
@@ -98,6 +102,130 @@ impl TextProcessor {
     }
 }
 
+/// External formatters run over every rendered release entry, in order.
+/// Each command gets the entry on stdin; its stdout replaces the entry.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct FormatCommand {
+    pub commands: Vec<String>,
+    #[serde(default)]
+    pub fail_on_error: bool,
+}
+
+impl FormatCommand {
+    pub fn from_config(cfg: &crate::core::config::syntax::FormatCommandConfig) -> Self {
+        Self {
+            commands: cfg.commands.clone(),
+            fail_on_error: cfg.fail_on_error,
+        }
+    }
+
+    /// Pipe `entry` through every command. A failing command — or one
+    /// that prints nothing — leaves the entry as it was and only warns,
+    /// unless `fail_on_error` is set. The entry's trailing whitespace is
+    /// kept so formatters that normalise the final newline don't glue
+    /// releases together.
+    pub fn apply(&self, entry: String) -> Result<String> {
+        let trailing = &entry[entry.trim_end().len()..];
+        let trailing = trailing.to_string();
+        let mut formatted = entry;
+
+        for cmd in &self.commands {
+            let result = command::run(cmd, Some(formatted.clone()), Vec::new()).and_then(|out| {
+                if out.trim().is_empty() {
+                    Err(Error::CommandError("produced no output".to_string()))
+                } else {
+                    Ok(out)
+                }
+            });
+            match result {
+                Ok(out) => formatted = format!("{}{trailing}", out.trim_end()),
+                Err(e) if self.fail_on_error => {
+                    return Err(Error::CommandError(format!(
+                        "changelog format command `{cmd}` failed: {e}"
+                    )));
+                }
+                Err(e) => log::warn!("changelog format command `{cmd}` failed, skipping it: {e}"),
+            }
+        }
+
+        Ok(formatted)
+    }
+}
+
+/// Another changelog rendered from the same commits, such as a
+/// translation; see [`ChangelogConfig::for_output`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangelogOutput {
+    pub language: String,
+    pub output: PathBuf,
+    pub header: Option<String>,
+    pub body: Option<String>,
+    pub footer: Option<String>,
+    pub translate_command: Option<String>,
+}
+
+impl ChangelogOutput {
+    pub fn from_config(cfg: &crate::core::config::syntax::ChangelogOutputConfig) -> Self {
+        Self {
+            language: cfg.language.clone(),
+            output: PathBuf::from(&cfg.output),
+            header: cfg.header.clone(),
+            body: cfg.body.clone(),
+            footer: cfg.footer.clone(),
+            translate_command: cfg.translate_command.clone(),
+        }
+    }
+}
+
+/// Names templates already see — the release's own fields and what
+/// belaf adds — which `[changelog.context_commands]` can't redefine.
+pub const RESERVED_CONTEXT_VARIABLES: &[&str] = &[
+    "version",
+    "message",
+    "commits",
+    "commit_id",
+    "timestamp",
+    "previous",
+    "repository",
+    "compare_url",
+    "previous_version",
+    "previous_tag",
+    "release_date",
+    "commit_range",
+    "submodule_commits",
+    "statistics",
+    "extra",
+    "github",
+    "emoji_groups",
+    "group_emojis",
+    "group_order",
+    "include_breaking_section",
+    "group_by_scope",
+    "include_contributors",
+    "include_statistics",
+    "include_dependencies",
+    "dependencies",
+    "links",
+    "remote",
+];
+
+/// Why `name` can't be a `[changelog.context_commands]` variable, if it
+/// can't: templates only reach identifiers, and built-in names are taken.
+pub fn context_variable_error(name: &str) -> Option<&'static str> {
+    let mut chars = name.chars();
+    let identifier = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !identifier {
+        Some("is not a template identifier")
+    } else if RESERVED_CONTEXT_VARIABLES.contains(&name) {
+        Some("is a built-in template variable")
+    } else {
+        None
+    }
+}
+
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct CommitParser {
     pub sha: Option<String>,
@@ -107,6 +235,8 @@ pub struct CommitParser {
     pub body: Option<Regex>,
     #[serde(with = "serde_regex", default)]
     pub footer: Option<Regex>,
+    #[serde(with = "serde_regex", default)]
+    pub author: Option<Regex>,
     pub group: Option<String>,
     pub default_scope: Option<String>,
     pub scope: Option<String>,
@@ -114,6 +244,10 @@ pub struct CommitParser {
     pub field: Option<String>,
     #[serde(with = "serde_regex", default)]
     pub pattern: Option<Regex>,
+    #[serde(with = "serde_pattern", default)]
+    pub paths: Vec<Pattern>,
+    #[serde(default)]
+    pub group_from_scope: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -134,13 +268,34 @@ pub struct ChangelogConfig {
     pub render_always: bool,
     #[serde(default)]
     pub postprocessors: Vec<TextProcessor>,
+    #[serde(default)]
+    pub format_command: Option<FormatCommand>,
+    #[serde(default)]
+    pub link_style: LinkStyle,
+    #[serde(default)]
+    pub context_commands: BTreeMap<String, String>,
     pub output: Option<PathBuf>,
+    #[serde(default)]
+    pub outputs: Vec<ChangelogOutput>,
+    /// Language tag of the entries; set for `[[changelog.outputs]]`.
+    #[serde(default)]
+    pub language: Option<String>,
+    #[serde(default)]
+    pub translate_command: Option<String>,
     pub include_breaking_section: bool,
+    #[serde(default)]
+    pub group_by_scope: bool,
     pub include_contributors: bool,
     pub include_statistics: bool,
+    #[serde(default)]
+    pub include_dependencies: bool,
     pub emoji_groups: bool,
     #[serde(default)]
     pub group_emojis: std::collections::HashMap<String, String>,
+    #[serde(default)]
+    pub group_order: Vec<String>,
+    #[serde(default)]
+    pub max_entry_size: Option<usize>,
 }
 
 impl CommitParser {
@@ -150,12 +305,19 @@ impl CommitParser {
             message: cfg.message.as_ref().and_then(|p| Regex::new(p).ok()),
             body: cfg.body.as_ref().and_then(|p| Regex::new(p).ok()),
             footer: cfg.footer.as_ref().and_then(|p| Regex::new(p).ok()),
+            author: cfg.author.as_ref().and_then(|p| Regex::new(p).ok()),
             group: cfg.group.clone(),
             default_scope: cfg.default_scope.clone(),
             scope: cfg.scope.clone(),
             skip: cfg.skip,
             field: None,
             pattern: None,
+            paths: cfg
+                .paths
+                .iter()
+                .filter_map(|p| Pattern::new(p).ok())
+                .collect(),
+            group_from_scope: cfg.group_from_scope,
         })
     }
 }
@@ -199,15 +361,95 @@ impl ChangelogConfig {
             trim: user_cfg.trim,
             render_always: false,
             postprocessors,
+            format_command: user_cfg
+                .format_command
+                .as_ref()
+                .map(FormatCommand::from_config),
+            link_style: user_cfg
+                .link_style
+                .as_ref()
+                .map(LinkStyle::from_config)
+                .unwrap_or_default(),
+            context_commands: user_cfg.context_commands.clone(),
             output: Some(PathBuf::from(&user_cfg.output)),
+            outputs: user_cfg
+                .outputs
+                .iter()
+                .map(ChangelogOutput::from_config)
+                .collect(),
+            language: None,
+            translate_command: None,
             include_breaking_section: user_cfg.include_breaking_section,
+            group_by_scope: user_cfg.group_by_scope,
             include_contributors: user_cfg.include_contributors,
             include_statistics: user_cfg.include_statistics,
+            include_dependencies: user_cfg.include_dependencies,
             emoji_groups: user_cfg.emoji_groups,
             group_emojis: user_cfg.group_emojis.clone(),
+            group_order: user_cfg.group_order.clone(),
+            max_entry_size: user_cfg.max_entry_size,
         }
     }
 
+    /// Run every `[changelog.context_commands]` command from the working
+    /// directory. Unlike a format command, a failing one is an error: a
+    /// release note with a blank build number is worse than none.
+    pub fn resolve_context(&self) -> Result<BTreeMap<String, String>> {
+        self.context_commands
+            .iter()
+            .map(|(name, cmd)| {
+                let out = command::run(cmd, None, Vec::new()).map_err(|e| {
+                    Error::CommandError(format!(
+                        "changelog context command `{name}` (`{cmd}`) failed: {e}"
+                    ))
+                })?;
+                Ok((name.clone(), out.trim().to_string()))
+            })
+            .collect()
+    }
+
+    /// The configuration `output` is rendered with: this one, with the
+    /// output's file, language and translate command, and its templates
+    /// where it sets them.
+    pub fn for_output(&self, output: &ChangelogOutput) -> Self {
+        Self {
+            header: output.header.clone().or_else(|| self.header.clone()),
+            body: output.body.clone().unwrap_or_else(|| self.body.clone()),
+            footer: output.footer.clone().or_else(|| self.footer.clone()),
+            output: Some(output.output.clone()),
+            outputs: Vec::new(),
+            language: Some(output.language.clone()),
+            translate_command: output.translate_command.clone(),
+            ..self.clone()
+        }
+    }
+
+    /// Pipe `entry` through the translate command, if there is one, with
+    /// `BELAF_LANGUAGE` set to the language tag. Unlike a format command,
+    /// a failing one is an error: an untranslated entry in a translated
+    /// changelog is worse than none.
+    pub fn translate(&self, entry: String) -> Result<String> {
+        let Some(cmd) = &self.translate_command else {
+            return Ok(entry);
+        };
+        let language = self.language.as_deref().unwrap_or_default();
+        let trailing = entry[entry.trim_end().len()..].to_string();
+        let out = command::run(cmd, Some(entry), vec![("BELAF_LANGUAGE", language)])
+            .and_then(|out| {
+                if out.trim().is_empty() {
+                    Err(Error::CommandError("produced no output".to_string()))
+                } else {
+                    Ok(out)
+                }
+            })
+            .map_err(|e| {
+                Error::CommandError(format!(
+                    "changelog translate command `{cmd}` ({language}) failed: {e}"
+                ))
+            })?;
+        Ok(format!("{}{trailing}", out.trim_end()))
+    }
+
     pub fn get_emoji(&self, group: &str) -> Option<&str> {
         self.group_emojis
             .iter()
@@ -244,6 +486,7 @@ impl GitConfig {
             filter_unconventional: user_cfg.filter_unconventional,
             filter_commits: user_cfg.filter_commits,
             sort_commits: user_cfg.sort_commits.clone(),
+            dedupe_commits: user_cfg.dedupe_commits,
             limit_commits: user_cfg.limit_commits,
             tag_pattern: user_cfg
                 .tag_pattern
@@ -0,0 +1,249 @@
+//! Upgraded external dependencies, read from lockfiles.
+//!
+//! With `[changelog] include_dependencies = true`, each entry gets the
+//! dependencies whose locked version changed since the previous release
+//! tag. The lockfiles are the `Cargo.lock`, `package-lock.json` and
+//! `poetry.lock` nearest to the unit: those in its directory, or else in
+//! the closest directory above it that has any, up to the repository
+//! root. Each is read as committed at the tag and at `HEAD`.
+//!
+//! Only packages locked on both sides are listed: added and removed
+//! dependencies show up in the manifest's own diff. Workspace members
+//! (Cargo packages without a `source`, linked npm packages) are the
+//! repository's own code and are left out. A package locked at several
+//! versions is compared by its newest one. Templates see the list as
+//! `dependencies`, by lockfile and then by name.
+
+use std::collections::BTreeMap;
+
+use anyhow::Context;
+use serde::Serialize;
+
+use crate::core::errors::Result;
+use crate::core::git::repository::{CommitId, RepoPathBuf, Repository};
+
+/// The lockfiles read, in the order their updates are listed.
+pub const LOCKFILES: &[&str] = &["Cargo.lock", "package-lock.json", "poetry.lock"];
+
+/// One dependency locked at another version than at the last release.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct DependencyUpdate {
+    pub name: String,
+    pub from: String,
+    pub to: String,
+    /// File name of the lockfile, such as `Cargo.lock`.
+    pub lockfile: String,
+}
+
+/// The updates between `from` and `to` in the lockfiles nearest to the
+/// unit at `prefix`.
+pub fn updates_between(
+    repo: &Repository,
+    prefix: &str,
+    from: CommitId,
+    to: CommitId,
+) -> Result<Vec<DependencyUpdate>> {
+    let mut dir = prefix.trim_matches('/').to_string();
+    loop {
+        let mut found = false;
+        let mut updates = Vec::new();
+        for lockfile in LOCKFILES {
+            let path = if dir.is_empty() {
+                RepoPathBuf::new(lockfile.as_bytes())
+            } else {
+                RepoPathBuf::new(format!("{dir}/{lockfile}").as_bytes())
+            };
+            let before = repo.get_file_at_commit(&from, &path)?;
+            let after = repo.get_file_at_commit(&to, &path)?;
+            found |= before.is_some() || after.is_some();
+            let (Some(before), Some(after)) = (before, after) else {
+                continue;
+            };
+            let read = |data: &[u8]| {
+                locked_versions(lockfile, data)
+                    .with_context(|| format!("failed to read `{}`", path.escaped()))
+            };
+            updates.extend(diff(lockfile, &read(&before)?, &read(&after)?));
+        }
+        if found || dir.is_empty() {
+            return Ok(updates);
+        }
+        dir = dir
+            .rsplit_once('/')
+            .map_or("", |(parent, _)| parent)
+            .to_string();
+    }
+}
+
+/// Name → locked versions of the external packages in `data`.
+fn locked_versions(lockfile: &str, data: &[u8]) -> Result<BTreeMap<String, Vec<String>>> {
+    let text = std::str::from_utf8(data).context("lockfile is not UTF-8")?;
+    let mut versions: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let mut lock = |name: &str, version: &str| {
+        versions
+            .entry(name.to_string())
+            .or_default()
+            .push(version.to_string());
+    };
+    match lockfile {
+        "package-lock.json" => {
+            let doc: serde_json::Value = serde_json::from_str(text)?;
+            if let Some(packages) = doc["packages"].as_object() {
+                for (key, package) in packages {
+                    let Some((_, name)) = key.rsplit_once("node_modules/") else {
+                        continue;
+                    };
+                    if package["link"].as_bool() == Some(true) {
+                        continue;
+                    }
+                    if let Some(version) = package["version"].as_str() {
+                        lock(name, version);
+                    }
+                }
+            } else if let Some(dependencies) = doc["dependencies"].as_object() {
+                for (name, package) in dependencies {
+                    if let Some(version) = package["version"].as_str() {
+                        lock(name, version);
+                    }
+                }
+            }
+        }
+        _ => {
+            let doc: toml::Table = toml::from_str(text)?;
+            let packages = doc.get("package").and_then(|p| p.as_array());
+            for package in packages.into_iter().flatten() {
+                let field = |key: &str| package.get(key).and_then(|v| v.as_str());
+                // Cargo workspace and path members have no source.
+                if lockfile == "Cargo.lock" && field("source").is_none() {
+                    continue;
+                }
+                if let (Some(name), Some(version)) = (field("name"), field("version")) {
+                    lock(name, version);
+                }
+            }
+        }
+    }
+    Ok(versions)
+}
+
+/// The packages whose newest locked version differs between `before`
+/// and `after`.
+fn diff(
+    lockfile: &str,
+    before: &BTreeMap<String, Vec<String>>,
+    after: &BTreeMap<String, Vec<String>>,
+) -> Vec<DependencyUpdate> {
+    before
+        .iter()
+        .filter_map(|(name, old)| {
+            let from = newest(old)?;
+            let to = newest(after.get(name)?)?;
+            (from != to).then(|| DependencyUpdate {
+                name: name.clone(),
+                from: from.to_string(),
+                to: to.to_string(),
+                lockfile: lockfile.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// The newest of `versions`; ones that aren't semver sort below the
+/// rest, by text.
+fn newest(versions: &[String]) -> Option<&str> {
+    versions
+        .iter()
+        .max_by_key(|v| (semver::Version::parse(v).ok(), v.as_str()))
+        .map(String::as_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn updates(lockfile: &str, before: &str, after: &str) -> Vec<(String, String, String)> {
+        let before = locked_versions(lockfile, before.as_bytes()).unwrap();
+        let after = locked_versions(lockfile, after.as_bytes()).unwrap();
+        diff(lockfile, &before, &after)
+            .into_iter()
+            .map(|u| (u.name, u.from, u.to))
+            .collect()
+    }
+
+    fn update(name: &str, from: &str, to: &str) -> (String, String, String) {
+        (name.into(), from.into(), to.into())
+    }
+
+    #[test]
+    fn cargo_lock_skips_workspace_members() {
+        let lock = |serde: &str, own: &str| {
+            format!(
+                "version = 4\n\n\
+                 [[package]]\nname = \"api\"\nversion = \"{own}\"\n\n\
+                 [[package]]\nname = \"serde\"\nversion = \"{serde}\"\n\
+                 source = \"registry+https://github.com/rust-lang/crates.io-index\"\n\n\
+                 [[package]]\nname = \"log\"\nversion = \"0.4.22\"\n\
+                 source = \"registry+https://github.com/rust-lang/crates.io-index\"\n"
+            )
+        };
+        assert_eq!(
+            updates(
+                "Cargo.lock",
+                &lock("1.0.200", "0.1.0"),
+                &lock("1.0.210", "0.2.0")
+            ),
+            vec![update("serde", "1.0.200", "1.0.210")]
+        );
+    }
+
+    #[test]
+    fn package_lock_reads_both_layouts() {
+        let v3 = |react: &str| {
+            format!(
+                r#"{{"lockfileVersion": 3, "packages": {{
+                    "": {{"name": "web", "version": "1.0.0"}},
+                    "node_modules/react": {{"version": "{react}"}},
+                    "node_modules/@acme/ui": {{"link": true}},
+                    "node_modules/a/node_modules/@types/node": {{"version": "20.1.0"}}
+                }}}}"#
+            )
+        };
+        assert_eq!(
+            updates("package-lock.json", &v3("18.2.0"), &v3("18.3.1")),
+            vec![update("react", "18.2.0", "18.3.1")]
+        );
+
+        let v1 = |react: &str| {
+            format!(
+                r#"{{"lockfileVersion": 1, "dependencies": {{"react": {{"version": "{react}"}}}}}}"#
+            )
+        };
+        assert_eq!(
+            updates("package-lock.json", &v1("16.14.0"), &v1("17.0.2")),
+            vec![update("react", "16.14.0", "17.0.2")]
+        );
+    }
+
+    #[test]
+    fn poetry_lock_compares_the_newest_version() {
+        let lock = |requests: &str| {
+            format!(
+                "[[package]]\nname = \"requests\"\nversion = \"{requests}\"\n\n\
+                 [[package]]\nname = \"urllib3\"\nversion = \"1.26.18\"\n\n\
+                 [[package]]\nname = \"urllib3\"\nversion = \"2.2.1\"\n"
+            )
+        };
+        assert_eq!(
+            updates("poetry.lock", &lock("2.31.0"), &lock("2.32.3")),
+            vec![update("requests", "2.31.0", "2.32.3")]
+        );
+        assert_eq!(
+            updates(
+                "poetry.lock",
+                &lock("2.31.0"),
+                "[[package]]\nname = \"idna\"\nversion = \"3.7\"\n"
+            ),
+            vec![]
+        );
+    }
+}
@@ -1,20 +1,23 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::io::Write;
-use std::time::{SystemTime, UNIX_EPOCH};
 
 use serde::Serialize;
 
+use super::authors::AuthorUsernames;
+use super::cache::EntryCache;
 use super::commit::Commit;
 
 const SHORT_SHA_LENGTH: usize = 7;
-use super::config::{ChangelogConfig, GitConfig};
+use super::config::{ChangelogConfig, ChangelogOutput, GitConfig};
 use super::error::{Error, Result};
 use super::github::GitHubClient;
+use super::links::RepoLinks;
 use super::release::{Release, Releases};
 use super::remote::RemoteMetadata;
 use super::template::Template;
 use crate::core::api::StoredToken;
 use crate::core::bump::BumpConfig;
+use crate::core::deterministic;
 
 #[derive(Debug, Clone, Serialize)]
 pub struct RemoteConfig {
@@ -34,6 +37,7 @@ pub struct Changelog {
     additional_context: HashMap<String, serde_json::Value>,
     remote: Option<RemoteConfig>,
     github_token: Option<StoredToken>,
+    entry_cache: Option<EntryCache>,
 }
 
 impl Changelog {
@@ -43,9 +47,11 @@ impl Changelog {
         changelog_config: ChangelogConfig,
         bump_config: BumpConfig,
     ) -> Result<Self> {
-        let trim = changelog_config.trim;
         let mut additional_context = HashMap::new();
 
+        for (name, value) in changelog_config.resolve_context()? {
+            additional_context.insert(name, serde_json::Value::String(value));
+        }
         additional_context.insert(
             "emoji_groups".to_string(),
             serde_json::to_value(changelog_config.emoji_groups)?,
@@ -54,10 +60,18 @@ impl Changelog {
             "group_emojis".to_string(),
             serde_json::to_value(&changelog_config.group_emojis)?,
         );
+        additional_context.insert(
+            "group_order".to_string(),
+            serde_json::to_value(&changelog_config.group_order)?,
+        );
         additional_context.insert(
             "include_breaking_section".to_string(),
             serde_json::to_value(changelog_config.include_breaking_section)?,
         );
+        additional_context.insert(
+            "group_by_scope".to_string(),
+            serde_json::to_value(changelog_config.group_by_scope)?,
+        );
         additional_context.insert(
             "include_contributors".to_string(),
             serde_json::to_value(changelog_config.include_contributors)?,
@@ -66,24 +80,26 @@ impl Changelog {
             "include_statistics".to_string(),
             serde_json::to_value(changelog_config.include_statistics)?,
         );
+        additional_context.insert(
+            "include_dependencies".to_string(),
+            serde_json::to_value(changelog_config.include_dependencies)?,
+        );
+        additional_context.insert("dependencies".to_string(), serde_json::json!([]));
+        additional_context.insert("links".to_string(), RepoLinks::default().template_context());
 
+        let (header_template, body_template, footer_template) = templates(&changelog_config)?;
         Ok(Self {
             releases,
-            header_template: match &changelog_config.header {
-                Some(header) => Some(Template::new("header", header.to_string(), trim)?),
-                None => None,
-            },
-            body_template: Template::new("body", changelog_config.body.clone(), trim)?,
-            footer_template: match &changelog_config.footer {
-                Some(footer) => Some(Template::new("footer", footer.to_string(), trim)?),
-                None => None,
-            },
+            header_template,
+            body_template,
+            footer_template,
             git_config,
             changelog_config,
             bump_config,
             additional_context,
             remote: None,
             github_token: None,
+            entry_cache: None,
         })
     }
 
@@ -106,6 +122,44 @@ impl Changelog {
         self
     }
 
+    /// Link into the repository `links` describes, and to the compare
+    /// view between `tags` (previous, new) when given.
+    pub fn with_links(mut self, links: &RepoLinks, tags: Option<(&str, &str)>) -> Self {
+        for release in &mut self.releases {
+            release.repository = Some(links.base_url().to_string());
+            release.compare_url = tags.map(|(from, to)| links.compare_url(from, to));
+        }
+        self.additional_context
+            .insert("links".to_string(), links.template_context());
+        self
+    }
+
+    /// This changelog, with its commits as processed, rendered for one
+    /// of `[[changelog.outputs]]`; see [`ChangelogConfig::for_output`].
+    pub fn for_output(&self, output: &ChangelogOutput) -> Result<Self> {
+        let changelog_config = self.changelog_config.for_output(output);
+        let (header_template, body_template, footer_template) = templates(&changelog_config)?;
+        Ok(Self {
+            releases: self.releases.clone(),
+            git_config: self.git_config.clone(),
+            changelog_config,
+            bump_config: self.bump_config.clone(),
+            header_template,
+            body_template,
+            footer_template,
+            additional_context: self.additional_context.clone(),
+            remote: self.remote.clone(),
+            github_token: self.github_token.clone(),
+            entry_cache: self.entry_cache.clone(),
+        })
+    }
+
+    /// Reuse entries rendered by earlier runs; see [`EntryCache`].
+    pub fn with_entry_cache(mut self, cache: EntryCache) -> Self {
+        self.entry_cache = Some(cache);
+        self
+    }
+
     pub fn with_github_token(mut self, token: StoredToken) -> Self {
         self.github_token = Some(token);
         self
@@ -232,6 +286,35 @@ impl Changelog {
             }
         }
 
+        if git_config.dedupe_commits {
+            // Keep the oldest copy of a change — the original rather than
+            // the cherry-pick. Topological order lists the newest first.
+            let newest_first = git_config.topo_order_commits
+                || git_config.sort_commits.eq_ignore_ascii_case("newest");
+            let oldest_first: Vec<usize> = if newest_first {
+                (0..commits.len()).rev().collect()
+            } else {
+                (0..commits.len()).collect()
+            };
+            let mut seen = HashSet::new();
+            let duplicates: HashSet<usize> = oldest_first
+                .into_iter()
+                .filter(|i| !seen.insert(dedupe_key(&commits[*i])))
+                .collect();
+            let mut index = 0;
+            commits.retain(|commit| {
+                let duplicate = duplicates.contains(&index);
+                index += 1;
+                if duplicate {
+                    log::debug!(
+                        "Dropping duplicate commit {}",
+                        &commit.id[..SHORT_SHA_LENGTH.min(commit.id.len())]
+                    );
+                }
+                !duplicate
+            });
+        }
+
         if let Some(limit) = git_config.limit_commits {
             commits.truncate(limit);
         }
@@ -382,6 +465,15 @@ impl Changelog {
         Ok(())
     }
 
+    /// Fill in `author.username` of every commit, and remember the
+    /// usernames the GitHub metadata pass found; see [`AuthorUsernames`].
+    pub fn resolve_author_usernames(&mut self, known: &mut AuthorUsernames) {
+        for release in &mut self.releases {
+            known.learn(&release.commits);
+            known.apply(&mut release.commits);
+        }
+    }
+
     pub fn bump_version(&mut self) -> Result<Option<String>> {
         if let Some(ref mut last_release) = self.releases.iter_mut().next() {
             if last_release.version.is_none() {
@@ -389,18 +481,52 @@ impl Changelog {
                     last_release.calculate_next_version_with_config(&self.bump_config)?;
                 log::debug!("Bumping the version to {next_version}");
                 last_release.version = Some(next_version.to_string());
-                last_release.timestamp = Some(
-                    SystemTime::now()
-                        .duration_since(UNIX_EPOCH)
-                        .map_err(|e| Error::ChangelogError(e.to_string()))?
-                        .as_secs() as i64,
-                );
+                last_release.timestamp = Some(deterministic::now_utc().unix_timestamp());
                 return Ok(Some(next_version));
             }
         }
         Ok(None)
     }
 
+    /// The rendered release entries, without header and footer.
+    pub fn render_entries(&self) -> Result<String> {
+        let postprocessors = &self.changelog_config.postprocessors;
+        let mut entries = String::new();
+        for release in &self.releases {
+            let key = self.entry_cache.as_ref().and_then(|_| {
+                EntryCache::key(release, &self.additional_context, &self.changelog_config)
+            });
+            let cached = self
+                .entry_cache
+                .as_ref()
+                .zip(key.as_deref())
+                .and_then(|(cache, key)| cache.get(key));
+            if let Some(entry) = cached {
+                log::debug!(
+                    "reusing the cached changelog entry {}",
+                    key.unwrap_or_default()
+                );
+                entries.push_str(&entry);
+                continue;
+            }
+
+            let mut entry = self.body_template.render(
+                release,
+                Some(&self.additional_context),
+                postprocessors,
+            )?;
+            entry = self.changelog_config.translate(entry)?;
+            if let Some(format_command) = &self.changelog_config.format_command {
+                entry = format_command.apply(entry)?;
+            }
+            if let (Some(cache), Some(key)) = (&self.entry_cache, &key) {
+                cache.put(key, &entry);
+            }
+            entries.push_str(&entry);
+        }
+        Ok(entries)
+    }
+
     pub fn generate<W: Write + ?Sized>(&self, out: &mut W) -> Result<()> {
         log::debug!("Generating changelog");
         let postprocessors = self.changelog_config.postprocessors.clone();
@@ -424,20 +550,10 @@ impl Changelog {
             }
         }
 
-        for release in &self.releases {
-            let write_result = write!(
-                out,
-                "{}",
-                self.body_template.render(
-                    release,
-                    Some(&self.additional_context),
-                    &postprocessors
-                )?
-            );
-            if let Err(e) = write_result {
-                if e.kind() != std::io::ErrorKind::BrokenPipe {
-                    return Err(e.into());
-                }
+        let entries = self.render_entries()?;
+        if let Err(e) = write!(out, "{entries}") {
+            if e.kind() != std::io::ErrorKind::BrokenPipe {
+                return Err(e.into());
             }
         }
 
@@ -482,3 +598,48 @@ impl Changelog {
         Ok(())
     }
 }
+
+/// The header, body and footer templates of `config`.
+fn templates(config: &ChangelogConfig) -> Result<(Option<Template>, Template, Option<Template>)> {
+    let trim = config.trim;
+    let order = &config.group_order;
+    let header = match &config.header {
+        Some(header) => {
+            Some(Template::new("header", header.to_string(), trim)?.with_group_order(order))
+        }
+        None => None,
+    };
+    let body = Template::new("body", config.body.clone(), trim)?.with_group_order(order);
+    let footer = match &config.footer {
+        Some(footer) => {
+            Some(Template::new("footer", footer.to_string(), trim)?.with_group_order(order))
+        }
+        None => None,
+    };
+    Ok((header, body, footer))
+}
+
+/// What makes two commits the same change for `dedupe_commits`: their
+/// `Change-Id` trailer, or else their type, scope and subject.
+fn dedupe_key(commit: &Commit) -> (String, Option<String>, String) {
+    let Some(conv) = &commit.conv else {
+        let subject = commit.message.lines().next().unwrap_or_default().trim();
+        return (String::new(), None, subject.to_owned());
+    };
+    if let Some(change_id) = conv
+        .footers
+        .iter()
+        .find(|f| f.token.eq_ignore_ascii_case("Change-Id"))
+    {
+        return (
+            "Change-Id".to_owned(),
+            None,
+            change_id.value.trim().to_owned(),
+        );
+    }
+    (
+        conv.type_.clone(),
+        conv.scope.clone(),
+        conv.description.trim().to_owned(),
+    )
+}
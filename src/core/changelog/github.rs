@@ -56,7 +56,7 @@ impl GitHubClient {
         Ok(Self {
             owner,
             repo,
-            client: ApiClient::new(),
+            client: ApiClient::try_new()?,
             token,
         })
     }
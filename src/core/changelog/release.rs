@@ -24,6 +24,19 @@ pub struct Release {
     pub timestamp: Option<i64>,
     pub previous: Option<Box<Release>>,
     pub repository: Option<String>,
+    /// The forge's compare view between the previous and this release's
+    /// tag, when both are known.
+    #[serde(rename = "compare_url")]
+    pub compare_url: Option<String>,
+    /// The version this release follows, when it was tagged.
+    #[serde(rename = "previous_version")]
+    pub previous_version: Option<String>,
+    /// The tag of that version.
+    #[serde(rename = "previous_tag")]
+    pub previous_tag: Option<String>,
+    /// The day of the release, as `YYYY-MM-DD`.
+    #[serde(rename = "release_date")]
+    pub release_date: Option<String>,
     #[serde(rename = "commit_range")]
     pub commit_range: Option<Range>,
     #[serde(rename = "submodule_commits")]
@@ -1,5 +1,7 @@
 use std::collections::{HashMap, HashSet};
 use std::error::Error as ErrorImpl;
+use std::sync::{mpsc, Arc};
+use std::time::Duration;
 
 use regex::Regex;
 use serde::Serialize;
@@ -8,36 +10,46 @@ use tera::{ast, Context as TeraContext, Result as TeraResult, Tera, Value};
 use super::config::TextProcessor;
 use super::error::{Error, Result};
 
+/// How long a template may take to render. Tera can't be interrupted, so
+/// a template that loops over a huge range would otherwise hang
+/// `prepare`.
+const RENDER_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The most a template may render: far more than any changelog entry.
+const MAX_RENDERED_BYTES: usize = 4 * 1024 * 1024;
+
 #[derive(Debug)]
 pub struct Template {
     name: String,
-    tera: Tera,
+    /// The source as configured, for pointing at lines in errors.
+    content: String,
+    tera: Arc<Tera>,
     pub variables: Vec<String>,
 }
 
 impl Template {
-    pub fn new(name: &str, mut content: String, trim: bool) -> Result<Self> {
-        if trim {
-            content = content
+    pub fn new(name: &str, content: String, trim: bool) -> Result<Self> {
+        let source = if trim {
+            content
                 .lines()
                 .map(|v| v.trim())
                 .collect::<Vec<&str>>()
-                .join("\n");
-        }
+                .join("\n")
+        } else {
+            content.clone()
+        };
         let mut tera = Tera::default();
-        if let Err(e) = tera.add_raw_template(name, &content) {
-            let content_snippet = content.lines().take(3).collect::<Vec<_>>().join("\n");
-            return if let Some(error_source) = e.source() {
-                Err(Error::TemplateParseError(format!(
-                    "Template '{}' failed to parse: {}\nContent snippet:\n{}",
-                    name, error_source, content_snippet
-                )))
-            } else {
-                Err(Error::TemplateParseError(format!(
-                    "Template '{}' failed to parse: {}\nContent snippet:\n{}",
-                    name, e, content_snippet
-                )))
-            };
+        if let Err(e) = tera.add_raw_template(name, &source) {
+            let cause = e
+                .source()
+                .map_or_else(|| e.to_string(), ToString::to_string);
+            let position = parse_position(&cause)
+                .map(|(line, column)| format!(" at line {line}, column {column}"))
+                .unwrap_or_default();
+            return Err(Error::TemplateParseError(format!(
+                "the `{name}` template failed to parse{position}:\n{}",
+                cause.trim_end()
+            )));
         }
 
         tera.register_filter("upper_first", Self::upper_first_filter);
@@ -47,11 +59,28 @@ impl Template {
 
         Ok(Self {
             name: name.to_string(),
+            content,
             variables: Self::get_template_variables(name, &tera)?,
-            tera,
+            tera: Arc::new(tera),
         })
     }
 
+    /// Register the `ordered_groups` filter: `commits | ordered_groups`
+    /// is a list of `{name, commits}` sections, one per commit group that
+    /// has commits, in `order` (matched case-insensitively) and then
+    /// alphabetically. Commits without a group are left out, as
+    /// `group_by` leaves them out.
+    pub fn with_group_order(mut self, order: &[String]) -> Self {
+        let order: Vec<String> = order.iter().map(|g| g.to_lowercase()).collect();
+        Arc::make_mut(&mut self.tera).register_filter(
+            "ordered_groups",
+            move |value: &Value, _: &HashMap<String, Value>| -> TeraResult<Value> {
+                ordered_groups(value, &order)
+            },
+        );
+        self
+    }
+
     fn upper_first_filter(value: &Value, _: &HashMap<String, Value>) -> TeraResult<Value> {
         let mut s = tera::try_get_value!("upper_first_filter", "value", String, value);
         let mut c = s.chars();
@@ -211,6 +240,9 @@ impl Template {
             .any(|var| self.variables.iter().any(|v| v.starts_with(var)))
     }
 
+    /// Render with `context` and `additional_context` as variables, on a
+    /// thread of its own so a runaway template fails after
+    /// [`RENDER_TIMEOUT`] instead of hanging.
     pub fn render<C: Serialize, T: Serialize, S: Into<String> + Clone>(
         &self,
         context: &C,
@@ -223,33 +255,190 @@ impl Template {
                 context.insert(key.clone(), &value);
             }
         }
-        match self.tera.render(&self.name, &context) {
-            Ok(mut v) => {
-                for postprocessor in postprocessors {
-                    postprocessor.replace(&mut v, vec![])?;
-                }
-                Ok(v)
+
+        let tera = Arc::clone(&self.tera);
+        let name = self.name.clone();
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let result = tera.render(&name, &context).map_err(|e| (e, context));
+            let _ = tx.send(result);
+        });
+        let mut rendered = match rx.recv_timeout(RENDER_TIMEOUT) {
+            Ok(Ok(rendered)) => rendered,
+            Ok(Err((e, context))) => return Err(self.render_error(&e, context)),
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                return Err(Error::TemplateRenderError(format!(
+                    "the `{}` template did not finish rendering within {}s; \
+                     look for loops over large ranges or recursive macros",
+                    self.name,
+                    RENDER_TIMEOUT.as_secs()
+                )));
             }
-            Err(e) => {
-                if let Some(source1) = e.source() {
-                    if let Some(source2) = source1.source() {
-                        Err(Error::TemplateRenderError(format!(
-                            "Template '{}' render failed: {}: {}",
-                            self.name, source1, source2
-                        )))
-                    } else {
-                        Err(Error::TemplateRenderError(format!(
-                            "Template '{}' render failed: {}",
-                            self.name, source1
-                        )))
-                    }
-                } else {
-                    Err(Error::TemplateRenderError(format!(
-                        "Template '{}' render failed: {}",
-                        self.name, e
-                    )))
-                }
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                return Err(Error::TemplateRenderError(format!(
+                    "rendering the `{}` template panicked",
+                    self.name
+                )));
             }
+        };
+        if rendered.len() > MAX_RENDERED_BYTES {
+            return Err(Error::TemplateRenderError(format!(
+                "the `{}` template rendered {} bytes, more than the {} allowed",
+                self.name,
+                rendered.len(),
+                MAX_RENDERED_BYTES
+            )));
+        }
+
+        for postprocessor in postprocessors {
+            postprocessor.replace(&mut rendered, vec![])?;
+        }
+        Ok(rendered)
+    }
+
+    /// `e` with the line it most likely comes from and the variables the
+    /// template could have used. Tera reports no positions when
+    /// rendering, so the line is the first one naming the variable or
+    /// filter the error quotes.
+    fn render_error(&self, e: &tera::Error, context: TeraContext) -> Error {
+        let mut causes = vec![e.to_string()];
+        let mut source = e.source();
+        while let Some(cause) = source {
+            causes.push(cause.to_string());
+            source = cause.source();
+        }
+        // The first cause only says which template failed.
+        let detail = match causes.as_slice() {
+            [only] => only.clone(),
+            [_, rest @ ..] => rest.join(": "),
+            [] => String::new(),
+        };
+        let mut message = format!("the `{}` template failed to render: {detail}", self.name);
+
+        let quoted = causes
+            .iter()
+            .rev()
+            .find_map(|cause| cause.split('`').nth(1))
+            .map(|quoted| quoted.split(['.', '[']).next().unwrap_or(quoted));
+        let located = quoted.and_then(|needle| {
+            self.content
+                .lines()
+                .position(|line| line.contains(needle))
+                .map(|index| index + 1)
+        });
+        if let Some(snippet) = located.and_then(|line| snippet(&self.content, line)) {
+            message.push_str(&format!("\n{snippet}"));
+        }
+
+        let mut variables: Vec<String> = match context.into_json() {
+            Value::Object(map) => map.into_iter().map(|(key, _)| key).collect(),
+            _ => Vec::new(),
+        };
+        variables.sort();
+        message.push_str(&format!("\n\nvariables: {}", variables.join(", ")));
+        Error::TemplateRenderError(message)
+    }
+}
+
+/// Line and column of a Tera parse error, from its ` --> line:column`.
+fn parse_position(cause: &str) -> Option<(usize, usize)> {
+    let position = cause
+        .lines()
+        .find_map(|l| l.trim_start().strip_prefix("--> "))?;
+    let (line, column) = position.trim().split_once(':')?;
+    Some((line.parse().ok()?, column.parse().ok()?))
+}
+
+/// Line `line` (1-based) of `content` with the one before and after it,
+/// numbered, the line itself marked.
+fn snippet(content: &str, line: usize) -> Option<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    if line == 0 || line > lines.len() {
+        return None;
+    }
+    let first = line.saturating_sub(2);
+    let last = (line + 1).min(lines.len());
+    Some(
+        (first..last)
+            .map(|i| {
+                let marker = if i + 1 == line { '>' } else { ' ' };
+                format!("{marker} {:>4} | {}", i + 1, lines[i])
+            })
+            .collect::<Vec<_>>()
+            .join("\n"),
+    )
+}
+
+fn ordered_groups(value: &Value, order: &[String]) -> TeraResult<Value> {
+    let commits = tera::try_get_value!("ordered_groups", "value", Vec<Value>, value);
+    let mut groups: Vec<(String, Vec<Value>)> = Vec::new();
+    for commit in commits {
+        let Some(group) = commit.get("group").and_then(Value::as_str) else {
+            continue;
+        };
+        match groups.iter_mut().find(|(name, _)| name == group) {
+            Some((_, members)) => members.push(commit),
+            None => groups.push((group.to_owned(), vec![commit])),
         }
     }
+    groups.sort_by_cached_key(|(name, _)| {
+        let rank = order
+            .iter()
+            .position(|g| *g == name.to_lowercase())
+            .unwrap_or(order.len());
+        (rank, name.clone())
+    });
+    Ok(Value::Array(
+        groups
+            .into_iter()
+            .map(|(name, commits)| serde_json::json!({ "name": name, "commits": commits }))
+            .collect(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(content: &str) -> Result<String> {
+        let context = serde_json::json!({ "version": "1.2.0" });
+        Template::new("body", content.to_string(), false)?.render(
+            &context,
+            None::<&HashMap<String, Value>>,
+            &[],
+        )
+    }
+
+    #[test]
+    fn parse_errors_name_the_position() {
+        let err = render("## {{ version }}\n- {{ version  }\n").unwrap_err();
+        let message = err.to_string();
+        assert!(
+            message.contains("the `body` template failed to parse at line 2, column 15"),
+            "{message}"
+        );
+        assert!(message.contains("- {{ version  }"), "{message}");
+    }
+
+    #[test]
+    fn render_errors_point_at_the_line_and_list_the_variables() {
+        let err = render("## {{ version }}\n\n{{ previous.version }}\n").unwrap_err();
+        let message = err.to_string();
+        assert!(
+            message.contains("`previous.version` not found"),
+            "{message}"
+        );
+        assert!(
+            message.contains(">    3 | {{ previous.version }}"),
+            "{message}"
+        );
+        assert!(message.contains("variables: version"), "{message}");
+    }
+
+    #[test]
+    fn output_is_capped() {
+        assert_eq!(render("{{ version }}").unwrap(), "1.2.0");
+        let err = render("{% for i in range(end=1100000) %}abcd{% endfor %}").unwrap_err();
+        assert!(err.to_string().contains("more than the"), "{err}");
+    }
 }
@@ -0,0 +1,203 @@
+//! Manual release steps (`[release_unit.<name>] release_checklist`).
+//!
+//! Some releases need a human to do something belaf can't: bump a
+//! dashboard, warn a customer, run a migration. Listing those steps in
+//! `release_checklist` makes `belaf prepare` render them as a task list
+//! in the release PR body, one unchecked box per step. Reviewers tick
+//! the boxes on the PR as they go, and `belaf tag` reads the PR back
+//! through the GitHub API and refuses to tag until it is merged with
+//! every step ticked.
+//!
+//! The PR is the one that adds the manifest, found the way sign-offs
+//! find it; see [`rest::RestClient::release_pull_request`]. The steps
+//! expected are the released units' `release_checklist` entries; each
+//! must be in the section under [`HEADING`] as a ticked box, so deleting
+//! a step, or the whole section, doesn't get a release past the check.
+//! Task lists elsewhere in the body, e.g. in a changelog, don't count.
+
+use anyhow::{anyhow, bail, Context, Result};
+
+use crate::core::{
+    github::rest, manifest::ReleaseManifest, session::AppSession, workflow::SelectedReleaseUnit,
+};
+
+/// Heading of the checklist section in release PR bodies.
+pub const HEADING: &str = "### ☑️ Release Checklist";
+
+/// The checklist section for `projects`, or `None` when none has steps.
+pub fn render(projects: &[SelectedReleaseUnit]) -> Option<String> {
+    if projects.iter().all(|p| p.checklist.is_empty()) {
+        return None;
+    }
+    let mut section = format!("{HEADING}\n\n");
+    for project in projects {
+        for step in &project.checklist {
+            section.push_str(&format!("- [ ] {}\n", entry(&project.name, step)));
+        }
+    }
+    section.push_str("\n`belaf tag` won't tag this release until every box is ticked.\n");
+    Some(section)
+}
+
+/// How `step` of unit `name` reads in the checklist section.
+fn entry(name: &str, step: &str) -> String {
+    format!("**{name}**: {step}")
+}
+
+/// The checklist entries the releases of `manifest` call for, from
+/// their units' `release_checklist`.
+pub fn expected(sess: &AppSession, manifest: &ReleaseManifest) -> Vec<String> {
+    manifest
+        .releases
+        .iter()
+        .flat_map(|release| {
+            sess.resolved_release_units()
+                .iter()
+                .filter(|r| r.unit.name == release.name)
+                .flat_map(|r| r.unit.release_checklist.iter())
+                .map(|step| entry(&release.name, step))
+        })
+        .collect()
+}
+
+/// The entries of `expected` that `body`'s checklist section doesn't
+/// have as a ticked box: unticked, edited or deleted ones alike.
+pub fn unticked(body: &str, expected: &[String]) -> Vec<String> {
+    let ticked: Vec<&str> = body
+        .lines()
+        .skip_while(|line| line.trim() != HEADING)
+        .skip(1)
+        .take_while(|line| {
+            let line = line.trim();
+            !line.starts_with('#') && line != "---"
+        })
+        .filter_map(|line| {
+            let line = line.trim();
+            line.strip_prefix("- [x]")
+                .or_else(|| line.strip_prefix("- [X]"))
+        })
+        .map(str::trim)
+        .collect();
+    expected
+        .iter()
+        .filter(|step| !ticked.contains(&step.as_str()))
+        .cloned()
+        .collect()
+}
+
+/// Check that the release PR adding `manifest_path` is merged, with
+/// every step of `manifest`'s checklist ticked.
+pub async fn verify(
+    sess: &AppSession,
+    manifest: &ReleaseManifest,
+    manifest_path: &str,
+) -> Result<()> {
+    let context = "the release checklist is read from the release PR";
+    let (client, owner, name) = rest::for_upstream(&sess.repo).context(context)?;
+    let pr = client
+        .release_pull_request(&owner, &name, manifest_path)
+        .await
+        .context(context)?
+        .ok_or_else(|| {
+            anyhow!(
+                "no release PR adds `{manifest_path}`, so its release checklist can't be checked"
+            )
+        })?;
+
+    let url = &pr.url;
+    if !pr.merged {
+        bail!("{url} is not merged, so `{manifest_path}` is not released");
+    }
+    let open = unticked(&pr.body, &expected(sess, manifest));
+    if !open.is_empty() {
+        bail!(
+            "the release checklist of {url} has {} unticked step(s):\n{}",
+            open.len(),
+            open.iter()
+                .map(|step| format!("  - {step}"))
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::wire::known::Ecosystem;
+
+    fn unit(name: &str, checklist: &[&str]) -> SelectedReleaseUnit {
+        SelectedReleaseUnit {
+            ident: 0,
+            name: name.to_string(),
+            prefix: String::new(),
+            old_version: "1.0.0".to_string(),
+            new_version: "1.1.0".to_string(),
+            bump_type: "minor".to_string(),
+            commits: vec![],
+            ecosystem: Ecosystem::classify("cargo"),
+            cached_changelog: None,
+            owners: vec![],
+            promoted_changelog: None,
+            checklist: checklist.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn renders_unchecked_steps_per_unit() {
+        assert_eq!(render(&[unit("api", &[])]), None);
+
+        let section = render(&[
+            unit("api", &["Run the migration", "Update the status page"]),
+            unit("web", &[]),
+        ])
+        .unwrap();
+        assert!(section.starts_with(HEADING), "{section}");
+        assert!(section.contains("- [ ] **api**: Run the migration\n"));
+        assert!(section.contains("- [ ] **api**: Update the status page\n"));
+        let expected = [
+            "**api**: Run the migration".to_string(),
+            "**api**: Update the status page".to_string(),
+        ];
+        assert_eq!(unticked(&section, &expected), expected);
+        let ticked = section.replace("- [ ]", "- [x]");
+        assert!(unticked(&ticked, &expected).is_empty());
+    }
+
+    #[test]
+    fn only_reads_the_checklist_section() {
+        let body = format!(
+            "## 🚀 Release Preparation\n\n- [ ] not a step\n\n{HEADING}\n\n\
+             - [x] **api**: Run the migration\n\
+             - [X] **api**: Warn support\n\
+             - [ ] **api**: Update the status page\n\n\
+             ### 📋 Release Manifest\n\n- [x] **api**: Update the status page\n"
+        );
+        let expected = [
+            "**api**: Run the migration".to_string(),
+            "**api**: Warn support".to_string(),
+            "**api**: Update the status page".to_string(),
+        ];
+        assert_eq!(
+            unticked(&body, &expected),
+            ["**api**: Update the status page"]
+        );
+    }
+
+    #[test]
+    fn deleted_steps_and_sections_count_as_unticked() {
+        let expected = [
+            "**api**: Run the migration".to_string(),
+            "**api**: Warn support".to_string(),
+        ];
+        let body = format!("{HEADING}\n\n- [x] **api**: Run the migration\n");
+        assert_eq!(unticked(&body, &expected), ["**api**: Warn support"]);
+        let body = format!("{HEADING}\n\n- [x] **api**: Run the migration, mostly\n");
+        assert_eq!(unticked(&body, &expected), expected);
+        assert_eq!(
+            unticked("no checklist here\n- [x] step", &expected),
+            expected
+        );
+    }
+}
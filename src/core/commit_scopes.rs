@@ -0,0 +1,112 @@
+//! Per-unit commit scope taxonomies.
+//!
+//! `scopes = ["http", "db"]` on a `[release_unit.<name>]` block lists the
+//! conventional-commit scopes the unit's commits may use. Scopes steer
+//! attribution, so a typo such as `feat(htp): …` quietly falls back to
+//! path matching and the taxonomy drifts. Nothing is rejected: history
+//! analysis warns, the prepare wizard flags the commits, `belaf status`
+//! reports how often each scope is used and `belaf lint-commits` lists
+//! the offending commits. Commits without a scope, and ones that aren't
+//! conventional, are never checked.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::core::bump::extract_scope;
+
+/// The scope of `message` if it isn't one of `allowed`. Scopes compare
+/// case-insensitively, as in attribution; an empty `allowed` accepts any.
+pub fn unknown_scope(message: &str, allowed: &[String]) -> Option<String> {
+    if allowed.is_empty() {
+        return None;
+    }
+    let scope = extract_scope(message)?;
+    (!allowed.iter().any(|a| a.eq_ignore_ascii_case(&scope))).then_some(scope)
+}
+
+/// How often each scope appears among a unit's commits.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct ScopeUsage {
+    /// Commits per lowercased scope, known or not.
+    pub scopes: BTreeMap<String, usize>,
+    /// Commits without a scope, conventional or not.
+    pub unscoped: usize,
+    /// The used scopes outside the unit's taxonomy, sorted.
+    pub unknown: Vec<String>,
+}
+
+impl ScopeUsage {
+    pub fn tally<'a>(messages: impl IntoIterator<Item = &'a str>, allowed: &[String]) -> Self {
+        let mut usage = Self::default();
+        for message in messages {
+            match extract_scope(message) {
+                Some(scope) => *usage.scopes.entry(scope.to_lowercase()).or_default() += 1,
+                None => usage.unscoped += 1,
+            }
+        }
+        if !allowed.is_empty() {
+            usage.unknown = usage
+                .scopes
+                .keys()
+                .filter(|s| !allowed.iter().any(|a| a.eq_ignore_ascii_case(s)))
+                .cloned()
+                .collect();
+        }
+        usage
+    }
+
+    /// `http 3, db 1, 2 unscoped`, most used first.
+    pub fn summary(&self) -> String {
+        let mut counts: Vec<(&String, &usize)> = self.scopes.iter().collect();
+        counts.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+        let mut parts: Vec<String> = counts
+            .into_iter()
+            .map(|(scope, n)| format!("{scope} {n}"))
+            .collect();
+        if self.unscoped > 0 {
+            parts.push(format!("{} unscoped", self.unscoped));
+        }
+        parts.join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn allowed() -> Vec<String> {
+        vec!["http".into(), "DB".into()]
+    }
+
+    #[test]
+    fn only_scopes_outside_the_taxonomy_are_unknown() {
+        assert_eq!(
+            unknown_scope("feat(htp): add client", &allowed()),
+            Some("htp".into())
+        );
+        assert_eq!(unknown_scope("fix(db): close pool", &allowed()), None);
+        assert_eq!(unknown_scope("fix(HTTP): retry", &allowed()), None);
+        assert_eq!(unknown_scope("fix: no scope", &allowed()), None);
+        assert_eq!(unknown_scope("Update README", &allowed()), None);
+        assert_eq!(unknown_scope("feat(htp): add client", &[]), None);
+    }
+
+    #[test]
+    fn usage_counts_every_scope_and_flags_unknown_ones() {
+        let messages = [
+            "feat(http): add client",
+            "fix(HTTP): retry",
+            "fix(htp): typo",
+            "chore: bump deps",
+            "Merge branch 'main'",
+        ];
+        let usage = ScopeUsage::tally(messages, &allowed());
+        assert_eq!(usage.scopes.get("http"), Some(&2));
+        assert_eq!(usage.unscoped, 2);
+        assert_eq!(usage.unknown, vec!["htp".to_string()]);
+        assert_eq!(usage.summary(), "http 2, htp 1, 2 unscoped");
+
+        assert!(ScopeUsage::tally(messages, &[]).unknown.is_empty());
+    }
+}
@@ -1,7 +1,10 @@
-use std::path::Path;
+pub mod migrations;
+
+use std::path::{Path, PathBuf};
 
 use crate::atry;
-use crate::core::errors::{Error, Result};
+use crate::core::errors::{AnnotatedReport, Error, Result};
+use crate::core::git::repository::{RepoPathBuf, Repository};
 
 pub mod syntax {
     use serde::{Deserialize, Serialize};
@@ -16,6 +19,12 @@ pub mod syntax {
     #[derive(Clone, Debug, Deserialize, Serialize)]
     #[serde(deny_unknown_fields)]
     pub struct ReleaseConfiguration {
+        /// Repository-relative config fragments merged under this file;
+        /// see [`super::ConfigurationFile::get`]. Only read from
+        /// `belaf/config.toml` itself.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub include: Vec<String>,
+
         pub repo: RepoConfiguration,
 
         pub changelog: ChangelogConfiguration,
@@ -24,6 +33,56 @@ pub mod syntax {
 
         pub commit_attribution: CommitAttributionConfiguration,
 
+        /// `[git]` — how belaf drives git when it writes the release
+        /// commit. Optional; omitted sections fall back to the defaults.
+        #[serde(default)]
+        pub git: GitConfiguration,
+
+        /// `[network]` — trust roots for outbound HTTPS. Optional.
+        #[serde(default)]
+        pub network: NetworkConfiguration,
+
+        /// `[auth]` — which login profile this repository uses. Optional.
+        #[serde(default)]
+        pub auth: AuthConfiguration,
+
+        /// `[forge]` — GitHub Enterprise Server endpoints. Optional;
+        /// github.com when omitted.
+        #[serde(default)]
+        pub forge: ForgeConfiguration,
+
+        /// `[release]` — release policy. Optional; no gate when omitted.
+        #[serde(default)]
+        pub release: ReleasePolicyConfiguration,
+
+        /// `[dashboard]` — the menu `belaf` shows without a subcommand.
+        /// Optional.
+        #[serde(default)]
+        pub dashboard: DashboardConfiguration,
+
+        /// `[ci]` — how CI-mode commands judge the repository. Optional.
+        #[serde(default)]
+        pub ci: CiConfiguration,
+
+        /// `[checks]` — opt-in checks run before a release. Optional.
+        #[serde(default)]
+        pub checks: ChecksConfiguration,
+
+        /// `[webhooks]` — endpoints notified of release lifecycle
+        /// events. Optional; nothing is sent when omitted.
+        #[serde(default)]
+        pub webhooks: WebhooksConfiguration,
+
+        /// `[trackers]` — issue trackers updated when a release is
+        /// tagged. Optional.
+        #[serde(default)]
+        pub trackers: TrackersConfiguration,
+
+        /// `[signing]` — signatures for release artifacts. Optional;
+        /// nothing is signed when omitted.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub signing: Option<SigningConfiguration>,
+
         /// `[group.<id>]` — bundles projects that release together with
         /// synchronised versions. Named-entry form only; the parser
         /// rejects an array-of-tables `[[group]]` shape.
@@ -39,6 +98,20 @@ pub mod syntax {
         #[serde(default, rename = "bump_source", skip_serializing_if = "Vec::is_empty")]
         pub bump_sources: Vec<BumpSourceConfig>,
 
+        /// `[packaging.<unit>]` — package manager manifests `belaf
+        /// package` regenerates for a released command-line tool.
+        #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+        pub packaging: HashMap<String, PackagingConfig>,
+
+        /// `[submodule.<name>]` — git submodules `belaf prepare` moves to
+        /// their newest release.
+        #[serde(
+            default,
+            rename = "submodule",
+            skip_serializing_if = "HashMap::is_empty"
+        )]
+        pub submodules: HashMap<String, SubmoduleConfig>,
+
         /// `[release_unit.<name>]` — named-entry release units. Each
         /// entry is either explicit (no `glob` field) or glob-form
         /// (with `glob` set, expanding at resolve-time into N units
@@ -91,6 +164,111 @@ pub mod syntax {
         pub tag_format: Option<String>,
     }
 
+    /// `[packaging.<unit>]` named-entry — the TOML key is the release
+    /// unit. See [`crate::core::packaging`].
+    ///
+    /// ```toml
+    /// [packaging.mycli]
+    /// description = "Does things"
+    /// license = "MIT"
+    ///
+    /// [packaging.mycli.homebrew]
+    /// repo = "acme/homebrew-tap"
+    /// ```
+    #[derive(Clone, Debug, Default, Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct PackagingConfig {
+        /// Name of the installed executable; the unit's name when omitted.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub binary: Option<String>,
+
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub description: Option<String>,
+
+        /// Defaults to the repository's web page.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub homepage: Option<String>,
+
+        /// SPDX identifier.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub license: Option<String>,
+
+        /// Download URL of an archive, with `{tag}`, `{version}` and
+        /// `{file}` substituted. Defaults to the release asset on the
+        /// forge.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub url: Option<String>,
+
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub homebrew: Option<PackageRepoConfig>,
+
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub scoop: Option<PackageRepoConfig>,
+
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub aur: Option<PackageRepoConfig>,
+    }
+
+    /// `[packaging.<unit>.homebrew|scoop|aur]` — the repository a
+    /// manifest is committed to.
+    #[derive(Clone, Debug, Default, Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct PackageRepoConfig {
+        /// `owner/name` of the tap, bucket or AUR mirror on the forge.
+        pub repo: String,
+
+        /// Formula, app or `pkgname`; the binary's name (with `-bin`
+        /// for the AUR) when omitted.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub name: Option<String>,
+
+        /// File to write, e.g. `Formula/<name>.rb` (the default).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub path: Option<String>,
+
+        /// Branch to commit to; the repository's default when omitted.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub branch: Option<String>,
+    }
+
+    /// `[submodule.<name>]` named-entry — a git submodule pinned at a
+    /// release of its own repository. See
+    /// [`crate::core::rewriters::submodule_pin`].
+    ///
+    /// ```toml
+    /// [submodule.core]
+    /// path = "vendor/core"
+    /// version_files = [
+    ///     { path = "versions.env", regex_pattern = "(?m)^CORE=(.+)$", regex_replace = "CORE={version}" },
+    /// ]
+    /// ```
+    #[derive(Clone, Debug, Default, Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct SubmoduleConfig {
+        /// Repository-relative path of the submodule.
+        pub path: String,
+
+        /// Tags of the submodule that mark its releases; `v{version}`
+        /// when omitted.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub tag_format: Option<String>,
+
+        /// Files of this repository that name the pinned version.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub version_files: Vec<VersionReferenceConfig>,
+    }
+
+    /// One entry of `[submodule.<name>] version_files`: the version is
+    /// the only capture group of `regex_pattern`, written back through
+    /// `regex_replace` with `{version}` substituted.
+    #[derive(Clone, Debug, Default, Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct VersionReferenceConfig {
+        pub path: String,
+        pub regex_pattern: String,
+        pub regex_replace: String,
+    }
+
     /// `[[bump_source]]` table: a subprocess belaf runs by default to
     /// gather externally-computed bump decisions (e.g. `graphql-inspector
     /// diff`). `cmd` is required; `release_unit` / `group` are pure
@@ -143,9 +321,21 @@ pub mod syntax {
 
         pub sort_commits: String,
 
+        /// Keep one entry per `Change-Id` trailer, or per type, scope and
+        /// subject, within a release — a change cherry-picked between
+        /// branches otherwise shows up twice.
+        #[serde(default)]
+        pub dedupe_commits: bool,
+
         #[serde(default)]
         pub limit_commits: Option<usize>,
 
+        /// Bytes of a release's entry put into the release PR body and
+        /// the release notes; longer entries are cut at a line and link
+        /// to the full changelog file.
+        #[serde(default)]
+        pub max_entry_size: Option<usize>,
+
         #[serde(default)]
         pub tag_pattern: Option<String>,
 
@@ -167,16 +357,107 @@ pub mod syntax {
         #[serde(default)]
         pub postprocessors: Vec<TextProcessorConfig>,
 
+        #[serde(default)]
+        pub format_command: Option<FormatCommandConfig>,
+
+        #[serde(default)]
+        pub link_style: Option<LinkStyleConfig>,
+
+        /// Template variables set to the trimmed stdout of a shell command.
+        #[serde(default)]
+        pub context_commands: std::collections::BTreeMap<String, String>,
+
+        /// Further changelogs rendered from the same commits, e.g. one
+        /// per language.
+        #[serde(default)]
+        pub outputs: Vec<ChangelogOutputConfig>,
+
         pub include_breaking_section: bool,
 
+        /// Sub-group each type section by commit scope. Templates see it
+        /// as `group_by_scope`.
+        #[serde(default)]
+        pub group_by_scope: bool,
+
         pub include_contributors: bool,
 
         pub include_statistics: bool,
 
+        /// List dependencies upgraded in the unit's lockfiles since the
+        /// last release. Templates see it as `include_dependencies`, and
+        /// the upgrades as `dependencies`.
+        #[serde(default)]
+        pub include_dependencies: bool,
+
         pub emoji_groups: bool,
 
         #[serde(default)]
         pub group_emojis: std::collections::HashMap<String, String>,
+
+        /// Order of the commit groups in an entry; groups not listed
+        /// follow alphabetically. Templates see it as `group_order`, and
+        /// the `ordered_groups` filter sorts by it.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub group_order: Vec<String>,
+
+        /// `[changelog.ecosystems.<ecosystem>]`: emoji settings for the
+        /// units of one ecosystem (`npm`, `cargo`, …).
+        #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+        pub ecosystems: HashMap<String, GroupEmojiOverrides>,
+
+        /// `[changelog.projects.<name>]`: emoji settings for one unit,
+        /// over those of its ecosystem.
+        #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+        pub projects: HashMap<String, GroupEmojiOverrides>,
+    }
+
+    impl ChangelogConfiguration {
+        /// The configuration a unit's changelog is rendered with:
+        /// `emoji_groups` and `group_emojis` as overridden for its
+        /// ecosystem, then for the unit itself. Emoji keys match
+        /// case-insensitively: `features` sets the emoji of `Features`.
+        pub fn for_project(&self, name: &str, ecosystem: &str) -> Self {
+            let mut merged = self.clone();
+            let layers = [self.ecosystems.get(ecosystem), self.projects.get(name)];
+            for overrides in layers.into_iter().flatten() {
+                if let Some(emoji_groups) = overrides.emoji_groups {
+                    merged.emoji_groups = emoji_groups;
+                }
+                for (group, emoji) in &overrides.group_emojis {
+                    let key = merged
+                        .group_emojis
+                        .keys()
+                        .find(|existing| existing.eq_ignore_ascii_case(group))
+                        .cloned()
+                        .unwrap_or_else(|| group.clone());
+                    merged.group_emojis.insert(key, emoji.clone());
+                }
+            }
+            merged
+        }
+    }
+
+    /// `[changelog.ecosystems.<ecosystem>]` and
+    /// `[changelog.projects.<name>]` tables.
+    ///
+    /// ```toml
+    /// [changelog.ecosystems.npm.group_emojis]
+    /// "Features" = "✨"
+    /// "Bug Fixes" = "🐛"
+    ///
+    /// [changelog.projects.docs-site]
+    /// emoji_groups = false
+    /// ```
+    #[derive(Clone, Debug, Default, Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct GroupEmojiOverrides {
+        /// Replaces `[changelog] emoji_groups`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub emoji_groups: Option<bool>,
+
+        /// Merged over `[changelog.group_emojis]`, group by group.
+        #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+        pub group_emojis: HashMap<String, String>,
     }
 
     #[derive(Clone, Debug, Default, Deserialize, Serialize)]
@@ -190,6 +471,11 @@ pub mod syntax {
         #[serde(default)]
         pub footer: Option<String>,
 
+        /// Matched against the author's name and email, e.g.
+        /// `^dependabot` or `renovate`.
+        #[serde(default)]
+        pub author: Option<String>,
+
         #[serde(default)]
         pub group: Option<String>,
 
@@ -201,6 +487,16 @@ pub mod syntax {
 
         #[serde(default)]
         pub skip: Option<bool>,
+
+        /// Globs matched against the files a commit changed, such as
+        /// `docs/**`; any changed file matching any glob is a match.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub paths: Vec<String>,
+
+        /// Name the group after the commit's scope, or use `group` when
+        /// it has none.
+        #[serde(default)]
+        pub group_from_scope: bool,
     }
 
     #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -221,6 +517,60 @@ pub mod syntax {
         pub replace: Option<String>,
     }
 
+    /// External formatters (`prettier`, `vale`, …) each generated
+    /// changelog entry is piped through before it is written.
+    #[derive(Clone, Debug, Default, Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct FormatCommandConfig {
+        #[serde(default)]
+        pub commands: Vec<String>,
+
+        #[serde(default)]
+        pub fail_on_error: bool,
+    }
+
+    /// One `[[changelog.outputs]]` entry: another changelog of each
+    /// unit, such as a translation, written next to `output`.
+    #[derive(Clone, Debug, Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct ChangelogOutputConfig {
+        /// Language tag, e.g. `ja` or `pt-BR`.
+        pub language: String,
+
+        /// File name in each unit's directory, like `[changelog] output`.
+        pub output: String,
+
+        /// Templates for this changelog; unset ones are `[changelog]`'s.
+        #[serde(default)]
+        pub header: Option<String>,
+
+        #[serde(default)]
+        pub body: Option<String>,
+
+        #[serde(default)]
+        pub footer: Option<String>,
+
+        /// Shell command each rendered entry is piped through, e.g. a
+        /// machine-translation script; its stdout replaces the entry.
+        #[serde(default)]
+        pub translate_command: Option<String>,
+    }
+
+    /// How commit, pull request and compare links in changelog entries
+    /// are built; see [`crate::core::changelog::LinkStyle`].
+    #[derive(Clone, Debug, Default, Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct LinkStyleConfig {
+        /// `github`, `gitlab` or `gitea`; detected from the host if unset.
+        #[serde(default)]
+        pub forge: Option<String>,
+
+        /// The repository's web page, when the upstream remote doesn't
+        /// point at it (mirrors, custom domains).
+        #[serde(default)]
+        pub base_url: Option<String>,
+    }
+
     #[derive(Clone, Debug, Deserialize, Serialize)]
     pub struct CommitAttributionConfiguration {
         pub strategy: String,
@@ -242,11 +592,465 @@ pub mod syntax {
         pub analysis: AnalysisConfig,
     }
 
+    /// `[git]` table.
+    #[derive(Clone, Debug, Default, Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct GitConfiguration {
+        /// Run the repository's `pre-commit` and `commit-msg` hooks
+        /// (honouring `core.hooksPath`) on the release commit. libgit2
+        /// never runs hooks on its own, so the default behaves like
+        /// `git commit --no-verify`.
+        #[serde(default)]
+        pub run_hooks: bool,
+
+        /// Which transport pushes the release branch. See [`GitBackend`].
+        #[serde(default)]
+        pub backend: GitBackend,
+
+        /// One release commit, or one per release unit. See
+        /// [`ReleaseCommits`].
+        #[serde(default)]
+        pub release_commits: ReleaseCommits,
+
+        /// Branch releases are prepared for when HEAD is detached, as CI
+        /// checks it out; `GITHUB_REF` decides when unset.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub base_branch: Option<String>,
+    }
+
+    /// `[git] backend` — how belaf talks to the remote.
+    #[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+    #[serde(rename_all = "lowercase")]
+    pub enum GitBackend {
+        /// Push through libgit2; if that fails, retry with the `git`
+        /// executable so credential helpers and SSO setups still work.
+        #[default]
+        Auto,
+        /// libgit2 only (SSH agent or the belaf-issued token).
+        Libgit2,
+        /// Always shell out to `git push`, using whatever credentials
+        /// plain git is configured with.
+        Cli,
+    }
+
+    /// `[git] release_commits` — how a release's changes are committed.
+    #[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+    #[serde(rename_all = "snake_case")]
+    pub enum ReleaseCommits {
+        /// One commit for everything the release changes.
+        #[default]
+        Single,
+        /// A `chore(release): <unit> v<version>` commit per release unit
+        /// holding the files under that unit, then one for the release
+        /// manifest and whatever no single unit owns, such as lockfiles.
+        PerUnit,
+    }
+
+    /// `[network]` table. Proxies are not configured here — belaf honours
+    /// the standard `HTTPS_PROXY` / `NO_PROXY` environment variables.
+    #[derive(Clone, Debug, Default, Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct NetworkConfiguration {
+        /// PEM file with extra root certificates to trust (corporate
+        /// TLS-intercepting proxies, internal CAs). Relative paths are
+        /// resolved against the repository root.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub ca_bundle: Option<String>,
+
+        /// Retries for rate-limited or transiently failing API calls
+        /// (default 3; `0` disables retrying).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub max_retries: Option<u32>,
+
+        /// Longest single wait, in seconds, belaf accepts before a retry
+        /// (default 60). Rate limits resetting later fail immediately.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub max_retry_wait_secs: Option<u64>,
+    }
+
+    /// `[auth]` table.
+    #[derive(Clone, Debug, Default, Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct AuthConfiguration {
+        /// Auth profile (see `belaf auth login --profile`) whose token
+        /// this repository uses. `--profile` / `BELAF_PROFILE` override it.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub profile: Option<String>,
+    }
+
+    /// `[forge]` table.
+    #[derive(Clone, Debug, Default, Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct ForgeConfiguration {
+        /// REST endpoint of a GitHub Enterprise Server install, e.g.
+        /// `https://ghe.example.com/api/v3`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub api_url: Option<String>,
+
+        /// GraphQL endpoint; derived from `api_url` when omitted.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub graphql_url: Option<String>,
+    }
+
+    /// `[dashboard]` table.
+    #[derive(Clone, Debug, Default, Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct DashboardConfiguration {
+        /// Menu entries, in display order. Entries left out are hidden
+        /// and their keys disabled; all of them show when omitted.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub actions: Option<Vec<DashboardItem>>,
+
+        /// Number of recent releases listed (default 5; `0` hides the
+        /// list).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub recent_releases: Option<usize>,
+    }
+
+    /// `[dashboard] actions` entry.
+    #[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+    #[serde(rename_all = "snake_case")]
+    pub enum DashboardItem {
+        Prepare,
+        Status,
+        Graph,
+        Changelog,
+        Init,
+        Web,
+        Help,
+        Quit,
+    }
+
+    /// `[release]` table.
+    #[derive(Clone, Debug, Default, Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct ReleasePolicyConfiguration {
+        /// Sign-off required before a prepared release may ship. See
+        /// [`crate::core::approval`].
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub approvers: Option<ApproversConfiguration>,
+
+        /// GitHub milestones rolled over by `belaf tag`. See
+        /// [`crate::core::github::client::roll_milestone`].
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub milestones: Option<MilestonesConfiguration>,
+
+        /// Look each release up on its package registry before
+        /// `belaf release cut` and `publish`. See
+        /// [`crate::core::ecosystem::registry`].
+        #[serde(default)]
+        pub check_registries: bool,
+
+        /// Order and pacing of `belaf release publish`. See
+        /// [`crate::core::publication::publish_order`].
+        #[serde(default)]
+        pub publish: PublishConfiguration,
+    }
+
+    /// `[release.approvers]` table.
+    ///
+    /// ```toml
+    /// [release.approvers]
+    /// users = ["alice", "bob"]
+    /// required = 1
+    /// ```
+    #[derive(Clone, Debug, Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct ApproversConfiguration {
+        /// GitHub logins allowed to approve a release.
+        pub users: Vec<String>,
+
+        /// Distinct approvals needed (default 1). Whoever prepared the
+        /// release never counts, so 1 already is a two-person rule.
+        #[serde(default = "default_required_approvals")]
+        pub required: u64,
+    }
+
+    fn default_required_approvals() -> u64 {
+        1
+    }
+
+    /// `[release.milestones]` table.
+    ///
+    /// ```toml
+    /// [release.milestones]
+    /// enabled = true
+    /// title_format = "v{version}"
+    /// ```
+    #[derive(Clone, Debug, Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct MilestonesConfiguration {
+        #[serde(default)]
+        pub enabled: bool,
+
+        /// Milestone title, with `{name}` and `{version}` substituted
+        /// (default `{version}`).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub title_format: Option<String>,
+    }
+
+    /// `[release.publish]` table.
+    ///
+    /// ```toml
+    /// [release.publish]
+    /// wait_for_registry = true
+    /// timeout_secs = 900
+    /// parallelism = 8
+    /// after = { docs = ["api"] }
+    /// ```
+    #[derive(Clone, Debug, Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct PublishConfiguration {
+        /// Before publishing a release's dependents, wait until its
+        /// registry lists it.
+        #[serde(default)]
+        pub wait_for_registry: bool,
+
+        /// How long to wait for one release (default 600).
+        #[serde(default = "default_publish_timeout")]
+        pub timeout_secs: u64,
+
+        /// Drafts published at the same time (default 4).
+        #[serde(default = "default_publish_parallelism")]
+        pub parallelism: usize,
+
+        /// Units to publish only after the listed ones, on top of the
+        /// dependency graph.
+        #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+        pub after: HashMap<String, Vec<String>>,
+    }
+
+    impl Default for PublishConfiguration {
+        fn default() -> Self {
+            Self {
+                wait_for_registry: false,
+                timeout_secs: default_publish_timeout(),
+                parallelism: default_publish_parallelism(),
+                after: HashMap::new(),
+            }
+        }
+    }
+
+    fn default_publish_timeout() -> u64 {
+        600
+    }
+
+    fn default_publish_parallelism() -> usize {
+        4
+    }
+
+    /// `[ci]` table.
+    #[derive(Clone, Debug, Default, Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct CiConfiguration {
+        /// Conditions under which `belaf status --ci` fails. See
+        /// [`crate::core::status_gates`].
+        #[serde(default)]
+        pub status_gates: StatusGatesConfiguration,
+    }
+
+    /// `[ci.status_gates]` table. Every gate is off unless set.
+    ///
+    /// ```toml
+    /// [ci.status_gates]
+    /// max_breaking_age_days = 14
+    /// changelog_drift = true
+    /// ```
+    #[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+    #[serde(deny_unknown_fields)]
+    pub struct StatusGatesConfiguration {
+        /// Fail when a unit has had an unreleased breaking change for
+        /// longer than this many days.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub max_breaking_age_days: Option<u64>,
+
+        /// Fail when a unit has had any unreleased commit for longer
+        /// than this many days.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub max_unreleased_age_days: Option<u64>,
+
+        /// Fail when a released unit's changelog has no entry for its
+        /// current version.
+        #[serde(default)]
+        pub changelog_drift: bool,
+    }
+
+    /// `[checks]` table. Every check is off unless enabled.
+    #[derive(Clone, Debug, Default, Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct ChecksConfiguration {
+        /// Refuse to prepare a release whose manifest lacks the metadata
+        /// its registry needs to publish it. See
+        /// [`crate::core::publish_checks`].
+        #[serde(default)]
+        pub publish_metadata: bool,
+
+        /// Compare each Rust crate's public API against its last release
+        /// with `cargo semver-checks`. See [`crate::core::api_diff`].
+        #[serde(default)]
+        pub api_diff: ApiDiffMode,
+    }
+
+    /// `[checks] api_diff` — what the API diff of a Rust crate does.
+    #[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+    #[serde(rename_all = "snake_case")]
+    pub enum ApiDiffMode {
+        /// Don't run `cargo semver-checks`.
+        #[default]
+        Off,
+        /// Show the bump the API diff requires next to the one the
+        /// commits suggest, in `status` and the wizard.
+        Advise,
+        /// Also refuse to prepare a release smaller than the API diff
+        /// requires.
+        Enforce,
+    }
+
+    /// `[webhooks]` table. See [`crate::core::notify`].
+    ///
+    /// ```toml
+    /// [webhooks]
+    /// urls = ["https://deploy.example.com/hooks/belaf"]
+    /// events = ["released", "publish_failed"]
+    /// ```
+    #[derive(Clone, Debug, Default, Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct WebhooksConfiguration {
+        /// Endpoints every event is POSTed to.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pub urls: Vec<String>,
+
+        /// Events to send; all of them when omitted.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub events: Option<Vec<WebhookEvent>>,
+
+        /// Environment variable holding the HMAC secret payloads are
+        /// signed with (default `BELAF_WEBHOOK_SECRET`).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub secret_env: Option<String>,
+    }
+
+    /// `[webhooks] events` entry.
+    #[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+    #[serde(rename_all = "snake_case")]
+    pub enum WebhookEvent {
+        /// `belaf prepare` started writing a release.
+        PrepareStarted,
+        /// The release PR was opened.
+        PrOpened,
+        /// `belaf tag` created a merged release's tags.
+        Released,
+        /// `belaf package` could not publish a package manifest.
+        PublishFailed,
+    }
+
+    /// `[trackers]` table.
+    #[derive(Clone, Debug, Default, Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct TrackersConfiguration {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub jira: Option<JiraConfiguration>,
+    }
+
+    /// `[signing]` table. See [`crate::core::signing`].
+    ///
+    /// ```toml
+    /// [signing]
+    /// tool = "minisign"
+    /// key = "keys/release.key"
+    /// tags = true
+    /// ```
+    #[derive(Clone, Debug, Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct SigningConfiguration {
+        pub tool: SigningTool,
+
+        /// Key file, or for cosign an `env://` or KMS URI. cosign signs
+        /// keyless without one.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub key: Option<String>,
+
+        /// Sign tags like `git tag -s`, as if `--sign` was given.
+        #[serde(default)]
+        pub tags: bool,
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+    #[serde(rename_all = "lowercase")]
+    pub enum SigningTool {
+        Cosign,
+        Minisign,
+    }
+
+    impl SigningTool {
+        pub fn as_str(self) -> &'static str {
+            match self {
+                Self::Cosign => "cosign",
+                Self::Minisign => "minisign",
+            }
+        }
+    }
+
+    /// `[trackers.jira]` table. See [`crate::core::jira`].
+    ///
+    /// ```toml
+    /// [trackers.jira]
+    /// url = "https://acme.atlassian.net"
+    /// project = "PROJ"
+    /// email = "release-bot@acme.com"
+    /// ```
+    #[derive(Clone, Debug, Deserialize, Serialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct JiraConfiguration {
+        /// Base URL of the Jira site.
+        pub url: String,
+
+        /// Key of the project fix versions are created in. Only issues
+        /// of this project are updated.
+        pub project: String,
+
+        /// Account email for Jira Cloud's basic auth. Without it the
+        /// token is sent as a bearer token (a Server/Data Center PAT).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub email: Option<String>,
+
+        /// Environment variable holding the API token (default
+        /// `BELAF_JIRA_TOKEN`).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub token_env: Option<String>,
+
+        /// Fix version name, with `{name}`, `{version}` and `{tag}`
+        /// substituted (default `{name} {version}`).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub version_format: Option<String>,
+
+        /// Transition applied to the issues (default `Done`); `""`
+        /// leaves their status alone.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub transition: Option<String>,
+    }
+
     #[derive(Clone, Debug, Deserialize, Serialize)]
     pub struct AnalysisConfig {
         pub commit_cache_size: usize,
 
         pub tree_cache_size: usize,
+
+        /// Follow units whose directory was moved: history analysis
+        /// detects renames and also attributes older commits under the
+        /// old location. Off by default; rename detection makes every
+        /// diff more expensive.
+        #[serde(default)]
+        pub follow_renames: bool,
+
+        /// Visit at most this many commits per unit, see
+        /// [`crate::core::history_bounds`].
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub max_commits: Option<std::num::NonZeroUsize>,
+
+        /// Ignore commits made before this day (`YYYY-MM-DD`, UTC).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pub since_date: Option<String>,
     }
 }
 
@@ -260,20 +1064,69 @@ pub struct NamedReleaseUnitConfig {
 
 #[derive(Clone, Debug)]
 pub struct ConfigurationFile {
+    pub include: Vec<String>,
+    /// The files merged into this configuration besides the built-in
+    /// defaults, lowest precedence first; see [`ConfigurationFile::get`].
+    pub sources: Vec<PathBuf>,
     pub repo: syntax::RepoConfiguration,
     pub changelog: syntax::ChangelogConfiguration,
     pub bump: syntax::BumpConfiguration,
     pub commit_attribution: syntax::CommitAttributionConfiguration,
+    pub git: syntax::GitConfiguration,
+    pub network: syntax::NetworkConfiguration,
+    pub auth: syntax::AuthConfiguration,
+    pub forge: syntax::ForgeConfiguration,
+    pub release: syntax::ReleasePolicyConfiguration,
+    pub dashboard: syntax::DashboardConfiguration,
+    pub ci: syntax::CiConfiguration,
+    pub checks: syntax::ChecksConfiguration,
+    pub webhooks: syntax::WebhooksConfiguration,
+    pub trackers: syntax::TrackersConfiguration,
+    pub signing: Option<syntax::SigningConfiguration>,
     pub groups: Vec<syntax::ResolvedGroupConfig>,
     pub bump_sources: Vec<syntax::BumpSourceConfig>,
+    pub packaging: std::collections::HashMap<String, syntax::PackagingConfig>,
+    pub submodules: std::collections::HashMap<String, syntax::SubmoduleConfig>,
     pub release_units: Vec<NamedReleaseUnitConfig>,
     pub ignore_paths: crate::core::release_unit::syntax::IgnorePathsConfig,
     pub allow_uncovered: crate::core::release_unit::syntax::AllowUncoveredConfig,
     pub ecosystems: crate::core::release_unit::syntax::EcosystemsConfig,
 }
 
+/// A per-project config file, committed next to a project's manifest.
+pub const PROJECT_CONFIG_FILE: &str = "belaf.toml";
+
 impl ConfigurationFile {
+    /// Load `path` (normally `belaf/config.toml`) with every layer it
+    /// pulls in. From lowest to highest precedence:
+    ///
+    /// 1. the built-in defaults;
+    /// 2. per-project `belaf.toml` files in the repository index, in path
+    ///    order;
+    /// 3. the fragments `include` lists, in the order listed;
+    /// 4. `path` itself.
+    ///
+    /// Tables merge key by key, so layers can each add their own
+    /// `[release_unit.<name>]` entries; a value set in two layers (an
+    /// array included) is taken whole from the higher one. Paths in
+    /// every layer are repository-relative, where the repository root is
+    /// the directory containing `belaf/`.
     pub fn get<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::load(path.as_ref(), &Worktree)
+    }
+
+    /// [`Self::get`] for the `belaf/config.toml` of `repo`, with every
+    /// layer read the way [`Repository::open_file`] reads: from the tree
+    /// of the commit the repository is read as of, if any.
+    pub fn get_in(repo: &Repository) -> Result<Self> {
+        let path = repo.resolve_config_dir().join("config.toml");
+        if repo.as_of().is_none() {
+            return Self::get(path);
+        }
+        Self::load(&path, &Tree(repo))
+    }
+
+    fn load(path: &Path, files: &dyn Layers) -> Result<Self> {
         let embedded_config_str = super::embed::EmbeddedConfig::get_config_string()?;
 
         let mut builder = config::Config::builder().add_source(config::File::from_str(
@@ -281,15 +1134,42 @@ impl ConfigurationFile {
             config::FileFormat::Toml,
         ));
 
-        if path.as_ref().exists() {
-            builder = builder.add_source(config::File::from(path.as_ref()));
+        let sources = if files.is_file(path) {
+            layers(path, files)?
+        } else {
+            Vec::new()
+        };
+        for source in &sources {
+            builder = files.add_source(builder, source)?;
         }
 
         let cfg: syntax::ReleaseConfiguration = builder
             .build()
             .map_err(|e| Error::new(e).context("failed to build configuration"))?
             .try_deserialize()
-            .map_err(|e| Error::new(e).context("failed to deserialize configuration"))?;
+            .map_err(|e| {
+                let mut ar = AnnotatedReport::default();
+                ar.set_message("failed to deserialize configuration".to_string());
+                for source in &sources {
+                    let text = files.read(source).unwrap_or_default();
+                    for m in migrations::pending(&text) {
+                        ar.add_note(if source == path {
+                            format!(
+                                "`{}` was replaced by `{}`; run `belaf migrate` to rewrite the file",
+                                m.old, m.new
+                            )
+                        } else {
+                            format!(
+                                "`{}` in `{}` was replaced by `{}`",
+                                m.old,
+                                source.display(),
+                                m.new
+                            )
+                        });
+                    }
+                }
+                Error::new(e).context(ar)
+            })?;
 
         // Promote the HashMap keys into runtime-adjacent shapes with a
         // stable iteration order. Sort by name for deterministic
@@ -313,12 +1193,27 @@ impl ConfigurationFile {
         release_units.sort_by(|a, b| a.name.cmp(&b.name));
 
         Ok(ConfigurationFile {
+            include: cfg.include,
+            sources,
             repo: cfg.repo,
             changelog: cfg.changelog,
             bump: cfg.bump,
             commit_attribution: cfg.commit_attribution,
+            git: cfg.git,
+            network: cfg.network,
+            auth: cfg.auth,
+            forge: cfg.forge,
+            release: cfg.release,
+            dashboard: cfg.dashboard,
+            ci: cfg.ci,
+            checks: cfg.checks,
+            webhooks: cfg.webhooks,
+            trackers: cfg.trackers,
+            signing: cfg.signing,
             groups,
             bump_sources: cfg.bump_sources,
+            packaging: cfg.packaging,
+            submodules: cfg.submodules,
             release_units,
             ignore_paths: cfg.ignore_paths,
             allow_uncovered: cfg.allow_uncovered,
@@ -347,12 +1242,26 @@ impl ConfigurationFile {
                 .map(|u| (u.name, u.config))
                 .collect();
         let cfg = syntax::ReleaseConfiguration {
+            include: self.include,
             repo: self.repo,
             changelog: self.changelog,
             bump: self.bump,
             commit_attribution: self.commit_attribution,
+            git: self.git,
+            network: self.network,
+            auth: self.auth,
+            forge: self.forge,
+            release: self.release,
+            dashboard: self.dashboard,
+            ci: self.ci,
+            checks: self.checks,
+            webhooks: self.webhooks,
+            trackers: self.trackers,
+            signing: self.signing,
             groups,
             bump_sources: self.bump_sources,
+            packaging: self.packaging,
+            submodules: self.submodules,
             release_units,
             ignore_paths: self.ignore_paths,
             allow_uncovered: self.allow_uncovered,
@@ -364,3 +1273,197 @@ impl ConfigurationFile {
         ))
     }
 }
+
+/// Where [`ConfigurationFile`] reads its layers from.
+trait Layers {
+    fn read(&self, path: &Path) -> std::io::Result<String>;
+
+    fn is_file(&self, path: &Path) -> bool;
+
+    fn add_source(
+        &self,
+        builder: config::ConfigBuilder<config::builder::DefaultState>,
+        path: &Path,
+    ) -> Result<config::ConfigBuilder<config::builder::DefaultState>>;
+
+    /// The [`PROJECT_CONFIG_FILE`]s of the repository at `root`, below
+    /// the root itself, in path order.
+    fn project_configs(&self, root: &Path) -> Vec<PathBuf>;
+}
+
+/// The filesystem, for [`ConfigurationFile::get`].
+struct Worktree;
+
+impl Layers for Worktree {
+    fn read(&self, path: &Path) -> std::io::Result<String> {
+        std::fs::read_to_string(path)
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        path.is_file()
+    }
+
+    fn add_source(
+        &self,
+        builder: config::ConfigBuilder<config::builder::DefaultState>,
+        path: &Path,
+    ) -> Result<config::ConfigBuilder<config::builder::DefaultState>> {
+        Ok(builder.add_source(config::File::from(path)))
+    }
+
+    fn project_configs(&self, root: &Path) -> Vec<PathBuf> {
+        project_configs(root)
+    }
+}
+
+/// A repository read as of a commit, for [`ConfigurationFile::get_in`].
+struct Tree<'a>(&'a Repository);
+
+impl Tree<'_> {
+    /// `path`, made by [`Repository::resolve_workdir`], as a repository
+    /// path again.
+    fn repo_path(&self, path: &Path) -> RepoPathBuf {
+        let root = self.0.resolve_workdir(&RepoPathBuf::new(b""));
+        let rel: PathBuf = path
+            .strip_prefix(&root)
+            .unwrap_or(path)
+            .components()
+            .filter(|c| matches!(c, std::path::Component::Normal(_)))
+            .collect();
+        RepoPathBuf::new(rel.to_string_lossy().as_bytes())
+    }
+}
+
+impl Layers for Tree<'_> {
+    fn read(&self, path: &Path) -> std::io::Result<String> {
+        self.0.read_to_string(&self.repo_path(path))
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        self.0.is_file(&self.repo_path(path))
+    }
+
+    fn add_source(
+        &self,
+        builder: config::ConfigBuilder<config::builder::DefaultState>,
+        path: &Path,
+    ) -> Result<config::ConfigBuilder<config::builder::DefaultState>> {
+        let text = atry!(
+            self.read(path);
+            ["could not read `{}`", path.display()]
+        );
+        Ok(builder.add_source(config::File::from_str(&text, config::FileFormat::Toml)))
+    }
+
+    fn project_configs(&self, root: &Path) -> Vec<PathBuf> {
+        let suffix = format!("/{PROJECT_CONFIG_FILE}");
+        let mut found = Vec::new();
+        let _ = self.0.scan_paths(|p| {
+            if p.escaped().ends_with(&suffix) {
+                found.push(root.join(p.as_path()));
+            }
+            Ok(())
+        });
+        found.sort();
+        found
+    }
+}
+
+/// The files [`ConfigurationFile::get`] merges for the config at `path`,
+/// lowest precedence first, ending with `path`.
+fn layers(path: &Path, files: &dyn Layers) -> Result<Vec<PathBuf>> {
+    let root = match path.parent().and_then(Path::parent) {
+        Some(root) if !root.as_os_str().is_empty() => root,
+        _ => Path::new("."),
+    };
+    let include = atry!(
+        read_include(path, files);
+        ["could not read `{}`", path.display()]
+    );
+
+    let mut included = Vec::new();
+    for entry in &include {
+        let fragment = Path::new(entry);
+        if fragment.is_absolute() {
+            return Err(Error::msg(format!(
+                "`include` entry `{entry}` in `{}` must be relative to the repository root",
+                path.display()
+            )));
+        }
+        let fragment = root.join(fragment);
+        if !files.is_file(&fragment) {
+            return Err(Error::msg(format!(
+                "`include` entry `{entry}` in `{}` does not exist",
+                path.display()
+            )));
+        }
+        included.push(fragment);
+    }
+
+    let mut layers: Vec<PathBuf> = files
+        .project_configs(root)
+        .into_iter()
+        .filter(|p| !included.contains(p))
+        .collect();
+    layers.append(&mut included);
+    for layer in &layers {
+        let nested = atry!(
+            read_include(layer, files);
+            ["could not read `{}`", layer.display()]
+        );
+        if !nested.is_empty() {
+            return Err(Error::msg(format!(
+                "`{}` sets `include`, which only `{}` may",
+                layer.display(),
+                path.display()
+            )));
+        }
+    }
+    layers.push(path.to_path_buf());
+    Ok(layers)
+}
+
+/// The top-level `include` array of the TOML file at `path`.
+fn read_include(path: &Path, files: &dyn Layers) -> Result<Vec<String>> {
+    #[derive(serde::Deserialize)]
+    struct Include {
+        #[serde(default)]
+        include: Vec<String>,
+    }
+    let text = files.read(path)?;
+    let parsed: Include = toml::from_str(&text)?;
+    Ok(parsed.include)
+}
+
+/// The [`PROJECT_CONFIG_FILE`]s in the index of the repository at `root`,
+/// below the root itself. Nothing when `root` isn't a repository.
+fn project_configs(root: &Path) -> Vec<PathBuf> {
+    let Ok(index) = git2::Repository::open(root).and_then(|repo| repo.index()) else {
+        return Vec::new();
+    };
+    let suffix = format!("/{PROJECT_CONFIG_FILE}");
+    let mut found: Vec<PathBuf> = index
+        .iter()
+        .filter_map(|entry| {
+            let path = std::str::from_utf8(&entry.path).ok()?;
+            path.ends_with(&suffix).then(|| root.join(path))
+        })
+        .filter(|path| path.is_file())
+        .collect();
+    found.sort();
+    found
+}
+
+/// Read a single table of `belaf/config.toml` in the repository containing
+/// the current directory, for settings needed before (or without) an
+/// [`crate::core::session::AppSession`] — `belaf install`, the update
+/// check. Returns the table and the working-tree root. Any problem yields
+/// `None`; full validation happens when a session loads the file.
+pub fn peek_section<T: serde::de::DeserializeOwned>(name: &str) -> Option<(T, PathBuf)> {
+    let repo = git2::Repository::open_from_env().ok()?;
+    let workdir = repo.workdir()?.to_path_buf();
+    let text = std::fs::read_to_string(workdir.join("belaf").join("config.toml")).ok()?;
+    let table: toml::Table = toml::from_str(&text).ok()?;
+    let section = table.get(name)?.clone().try_into().ok()?;
+    Some((section, workdir))
+}
@@ -0,0 +1,221 @@
+//! Rewriting `belaf/config.toml` files written for an older belaf.
+//!
+//! When a config shape is retired, its replacement gets a
+//! [`ConfigMigration`] here. `belaf migrate` applies every migration to
+//! the file with `toml_edit`, so comments, key order and formatting
+//! survive, and [`crate::core::config::ConfigurationFile::get`] suggests
+//! running it when the file fails to load because of an old shape.
+//!
+//! The shapes retired so far are the pre-1.0 array-of-tables forms
+//! `[[release_unit]]`, `[[release_unit_glob]]` and `[[group]]`, which
+//! became the named-entry tables `[release_unit.<name>]` and
+//! `[group.<id>]`.
+
+use toml_edit::{DocumentMut, Item, Table};
+
+/// One retired config shape and how to rewrite it.
+pub struct ConfigMigration {
+    /// The retired shape, as users wrote it.
+    pub old: &'static str,
+    /// What replaces it.
+    pub new: &'static str,
+    /// Rewrites `doc`, returning one line per change.
+    apply: fn(&mut DocumentMut) -> Vec<String>,
+}
+
+pub const MIGRATIONS: &[ConfigMigration] = &[
+    ConfigMigration {
+        old: "[[release_unit]]",
+        new: "[release_unit.<name>]",
+        apply: release_unit_array,
+    },
+    ConfigMigration {
+        old: "[[release_unit_glob]]",
+        new: "[release_unit.<key>] with `glob`",
+        apply: release_unit_glob_array,
+    },
+    ConfigMigration {
+        old: "[[group]]",
+        new: "[group.<id>]",
+        apply: group_array,
+    },
+];
+
+/// A config file rewritten by [`migrate`].
+#[derive(Debug)]
+pub struct MigratedConfig {
+    pub text: String,
+    /// What was rewritten, one line per table.
+    pub changes: Vec<String>,
+}
+
+/// Apply every migration to the config `text`. `None` when the file
+/// uses no retired shape.
+pub fn migrate(text: &str) -> Result<Option<MigratedConfig>, toml_edit::TomlError> {
+    let mut doc: DocumentMut = text.parse()?;
+    let changes: Vec<String> = MIGRATIONS
+        .iter()
+        .flat_map(|m| (m.apply)(&mut doc))
+        .collect();
+    if changes.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(MigratedConfig {
+        text: doc.to_string(),
+        changes,
+    }))
+}
+
+/// The migrations the config `text` needs. Empty when it is current or
+/// doesn't parse as TOML at all.
+pub fn pending(text: &str) -> Vec<&'static ConfigMigration> {
+    let Ok(mut doc) = text.parse::<DocumentMut>() else {
+        return Vec::new();
+    };
+    MIGRATIONS
+        .iter()
+        .filter(|m| !(m.apply)(&mut doc).is_empty())
+        .collect()
+}
+
+/// `[[release_unit]]` entries named by their `name` key.
+fn release_unit_array(doc: &mut DocumentMut) -> Vec<String> {
+    lift_array(doc, "release_unit", "release_unit", |entry| {
+        if entry.contains_key("glob") {
+            return glob_key(entry);
+        }
+        let name = entry.remove("name")?;
+        name.as_str().map(str::to_string)
+    })
+}
+
+/// `[[release_unit_glob]]` entries, which kept `name` as the template
+/// for the units they expand to.
+fn release_unit_glob_array(doc: &mut DocumentMut) -> Vec<String> {
+    lift_array(doc, "release_unit_glob", "release_unit", glob_key)
+}
+
+/// `[[group]]` entries identified by their `id` key.
+fn group_array(doc: &mut DocumentMut) -> Vec<String> {
+    lift_array(doc, "group", "group", |entry| {
+        let id = entry.remove("id")?;
+        id.as_str().map(str::to_string)
+    })
+}
+
+/// Move each table of the array `from` into the table `to`, under the
+/// key `key_of` returns. Entries without a key are numbered, and
+/// clashing keys get a suffix.
+fn lift_array(
+    doc: &mut DocumentMut,
+    from: &str,
+    to: &str,
+    key_of: fn(&mut Table) -> Option<String>,
+) -> Vec<String> {
+    if !doc.get(from).is_some_and(Item::is_array_of_tables) {
+        return Vec::new();
+    }
+    let Some(Item::ArrayOfTables(entries)) = doc.remove(from) else {
+        return Vec::new();
+    };
+    if !doc.contains_key(to) {
+        let mut parent = Table::new();
+        parent.set_implicit(true);
+        doc.insert(to, Item::Table(parent));
+    }
+    let Some(parent) = doc.get_mut(to).and_then(Item::as_table_mut) else {
+        // Something else already lives under the new key; leave the
+        // entries for the user to sort out.
+        doc.insert(from, Item::ArrayOfTables(entries));
+        return Vec::new();
+    };
+
+    let mut changes = Vec::new();
+    for (idx, mut entry) in entries.into_iter().enumerate() {
+        let base = key_of(&mut entry).unwrap_or_else(|| format!("{to}-{}", idx + 1));
+        let mut key = base.clone();
+        let mut n = 2;
+        while parent.contains_key(&key) {
+            key = format!("{base}-{n}");
+            n += 1;
+        }
+        changes.push(format!("[[{from}]] #{} → [{to}.{key}]", idx + 1));
+        parent.insert(&key, Item::Table(entry));
+    }
+    changes
+}
+
+/// A key for a glob-form unit, from its glob: `crates/*` → `crates`.
+fn glob_key(entry: &mut Table) -> Option<String> {
+    let glob = entry.get("glob")?.as_str()?;
+    let key: String = glob
+        .split(|c: char| !c.is_ascii_alphanumeric() && c != '-' && c != '_')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    (!key.is_empty()).then_some(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OLD: &str = r#"# Release units
+[[release_unit]]
+name = "core"
+ecosystem = "cargo"
+# pinned by the app
+tag_format = "core-v{version}"
+
+[[release_unit_glob]]
+glob = "crates/*"
+name = "{basename}"
+ecosystem = "cargo"
+
+[[group]]
+id = "schema"
+members = ["core"]
+"#;
+
+    #[test]
+    fn array_of_tables_become_named_entries() {
+        let migrated = migrate(OLD).unwrap().unwrap();
+        assert_eq!(
+            migrated.text,
+            r#"# Release units
+[release_unit.core]
+ecosystem = "cargo"
+# pinned by the app
+tag_format = "core-v{version}"
+
+[release_unit.crates]
+glob = "crates/*"
+name = "{basename}"
+ecosystem = "cargo"
+
+[group.schema]
+members = ["core"]
+"#
+        );
+        assert_eq!(
+            migrated.changes,
+            vec![
+                "[[release_unit]] #1 → [release_unit.core]",
+                "[[release_unit_glob]] #1 → [release_unit.crates]",
+                "[[group]] #1 → [group.schema]",
+            ]
+        );
+        assert!(migrate(&migrated.text).unwrap().is_none());
+    }
+
+    #[test]
+    fn pending_names_the_retired_shapes() {
+        let olds: Vec<&str> = pending(OLD).iter().map(|m| m.old).collect();
+        assert_eq!(
+            olds,
+            vec!["[[release_unit]]", "[[release_unit_glob]]", "[[group]]"]
+        );
+        assert!(pending("[group.schema]\nmembers = []\n").is_empty());
+        assert!(pending("not = [toml").is_empty());
+    }
+}
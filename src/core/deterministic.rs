@@ -0,0 +1,81 @@
+//! `BELAF_DETERMINISTIC=1`: the same repository gives the same output.
+//!
+//! Commands stamp their output with the current time (changelog dates,
+//! manifest `created_at`, release branch names) and with random ids
+//! (release branch suffixes, manifest ids), and draw spinners on a
+//! terminal. Snapshot tests of that output, ours and those of tools built
+//! on belaf, need it to stay put between runs. With the variable set:
+//!
+//! - [`now_utc`] is [`FIXED_NOW`] instead of the wall clock;
+//! - [`uuid_v4`] and [`uuid_v7`] come from a fixed seed, so every call
+//!   returns the same id;
+//! - spinners, progress bars and the update check stay off.
+//!
+//! Commit dates still come from the repository; set `GIT_AUTHOR_DATE`
+//! and `GIT_COMMITTER_DATE` when creating the commits to pin those too.
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use time::{macros::datetime, OffsetDateTime};
+use uuid::Uuid;
+
+/// The environment variable turning the mode on.
+pub const ENV: &str = "BELAF_DETERMINISTIC";
+
+/// What the clock reads in deterministic mode.
+pub const FIXED_NOW: OffsetDateTime = datetime!(2024-01-01 00:00:00 UTC);
+
+const SEED: u64 = 0x62_65_6c_61_66;
+
+/// Whether `BELAF_DETERMINISTIC` is set to anything but empty or `0`.
+pub fn enabled() -> bool {
+    std::env::var_os(ENV).is_some_and(|v| !v.is_empty() && v != "0")
+}
+
+/// The current time, or [`FIXED_NOW`].
+pub fn now_utc() -> OffsetDateTime {
+    if enabled() {
+        FIXED_NOW
+    } else {
+        OffsetDateTime::now_utc()
+    }
+}
+
+/// A random UUID, or the same seeded one on every call.
+pub fn uuid_v4() -> Uuid {
+    if enabled() {
+        uuid::Builder::from_random_bytes(seeded()).into_uuid()
+    } else {
+        Uuid::new_v4()
+    }
+}
+
+/// A time-ordered UUID, or one made from [`FIXED_NOW`] and the seed.
+pub fn uuid_v7() -> Uuid {
+    if enabled() {
+        let millis = (FIXED_NOW.unix_timestamp_nanos() / 1_000_000) as u64;
+        uuid::Builder::from_unix_timestamp_millis(millis, &seeded()).into_uuid()
+    } else {
+        Uuid::now_v7()
+    }
+}
+
+fn seeded<const N: usize>() -> [u8; N] {
+    StdRng::seed_from_u64(SEED).random()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seeded_ids_are_stable_and_well_formed() {
+        let v4 = uuid::Builder::from_random_bytes(seeded()).into_uuid();
+        assert_eq!(v4, uuid::Builder::from_random_bytes(seeded()).into_uuid());
+        assert_eq!(v4.get_version_num(), 4);
+
+        let millis = (FIXED_NOW.unix_timestamp_nanos() / 1_000_000) as u64;
+        let v7 = uuid::Builder::from_unix_timestamp_millis(millis, &seeded()).into_uuid();
+        assert_eq!(v7.get_version_num(), 7);
+        assert!(v7.to_string().starts_with("018cc251-f400-7"), "{v7}");
+    }
+}
@@ -14,7 +14,8 @@ use tracing::info;
 
 use crate::core::{
     ecosystem::format_handler::{
-        is_path_inside_any, DiscoveredUnit, FormatHandler, RawInternalDep, WorkspaceDiscoverer,
+        is_path_inside_any, DeclaredDep, DiscoveredUnit, FormatHandler, RawInternalDep,
+        SingleUnitBuilder, WorkspaceDiscoverer,
     },
     errors::Result,
     git::repository::{ChangeList, RepoPath, RepoPathBuf, Repository},
@@ -24,7 +25,7 @@ use crate::core::{
     session::AppSession,
     version::Version,
 };
-use crate::utils::file_io::read_config_file;
+use crate::utils::file_io::{read_config_file, read_repo_config_file};
 use crate::utils::theme::PhaseSpinner;
 
 /// Stateless cargo `FormatHandler`. The struct exists only as a
@@ -49,12 +50,11 @@ impl CargoLoader {
         repo: &Repository,
         toml_repopath: &RepoPathBuf,
     ) -> Result<Option<DiscoveredUnit>> {
-        let toml_abs = repo.resolve_workdir(toml_repopath);
-        if !toml_abs.exists() {
+        if !repo.is_file(toml_repopath) {
             return Ok(None);
         }
 
-        let Ok(content) = read_config_file(&toml_abs) else {
+        let Ok(content) = read_repo_config_file(repo, toml_repopath) else {
             return Ok(None);
         };
         let Ok(doc) = content.parse::<DocumentMut>() else {
@@ -79,18 +79,26 @@ impl CargoLoader {
             return Ok(None);
         };
 
+        Ok(Some(Self::single_unit(
+            toml_repopath,
+            name,
+            Version::Semver(version),
+        )))
+    }
+
+    fn single_unit(toml_repopath: &RepoPath, name: String, version: Version) -> DiscoveredUnit {
         let (prefix, _) = toml_repopath.split_basename();
-        let manifest = toml_repopath.clone();
-        Ok(Some(DiscoveredUnit {
+        let manifest = toml_repopath.to_owned();
+        DiscoveredUnit {
             qnames: vec![name, "cargo".to_owned()],
-            version: Version::Semver(version),
+            version,
             prefix: prefix.to_owned(),
             anchor_manifest: manifest.clone(),
             rewriter_factories: vec![Box::new(move |id| {
                 Box::new(CargoRewriter::new(id, manifest))
             })],
             internal_deps: Vec::new(),
-        }))
+        }
     }
 
     fn is_workspace_project(&self, doc: &DocumentMut) -> bool {
@@ -370,6 +378,47 @@ impl FormatHandler for CargoLoader {
     ) -> Result<Option<DiscoveredUnit>> {
         self.direct_parse_unit(repo, &manifest_path.to_owned())
     }
+
+    fn single_unit_builder(&self) -> Option<SingleUnitBuilder> {
+        Some(Self::single_unit)
+    }
+
+    fn declared_deps(&self, content: &str) -> Result<Vec<DeclaredDep>> {
+        let doc: DocumentMut = content
+            .parse()
+            .map_err(|e| anyhow!("parse Cargo.toml: {e}"))?;
+        let Some(deps) = doc.get("dependencies").and_then(|v| v.as_table_like()) else {
+            return Ok(Vec::new());
+        };
+
+        let mut declared = Vec::new();
+        for (key, item) in deps.iter() {
+            let (name, requirement) = if let Some(req) = item.as_str() {
+                (key, req)
+            } else if let Some(spec) = item.as_table_like() {
+                let name = spec.get("package").and_then(|v| v.as_str()).unwrap_or(key);
+                // Path, git and workspace-inherited deps carry no
+                // requirement of their own; say where they come from.
+                let requirement = spec
+                    .get("version")
+                    .and_then(|v| v.as_str())
+                    .or_else(|| {
+                        ["workspace", "path", "git"]
+                            .into_iter()
+                            .find(|k| spec.contains_key(k))
+                    })
+                    .unwrap_or("*");
+                (name, requirement)
+            } else {
+                continue;
+            };
+            declared.push(DeclaredDep {
+                name: name.to_owned(),
+                requirement: requirement.to_owned(),
+            });
+        }
+        Ok(declared)
+    }
 }
 
 /// Workspace walker for cargo: claims any Cargo.toml that has a
@@ -388,8 +437,7 @@ impl WorkspaceDiscoverer for CargoWorkspaceDiscoverer {
         if basename.as_ref() != b"Cargo.toml" {
             return false;
         }
-        let abs = repo.resolve_workdir(manifest_path);
-        let Ok(content) = read_config_file(&abs) else {
+        let Ok(content) = read_repo_config_file(repo, manifest_path) else {
             return false;
         };
         let Ok(doc) = content.parse::<DocumentMut>() else {
@@ -399,6 +447,12 @@ impl WorkspaceDiscoverer for CargoWorkspaceDiscoverer {
     }
 
     fn discover(&self, repo: &Repository, root_path: &RepoPath) -> Result<Vec<DiscoveredUnit>> {
+        // `cargo metadata` needs the workspace checked out; when reading
+        // a commit's tree the members are parsed one by one instead.
+        if repo.as_of().is_some() {
+            return Ok(Vec::new());
+        }
+
         let workspace_root = repo.resolve_workdir(root_path);
         let mut cmd = MetadataCommand::new();
         cmd.manifest_path(&workspace_root);
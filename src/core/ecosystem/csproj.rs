@@ -51,7 +51,7 @@ impl CsProjLoader {
         for vdproj in vdproj_files.drain(..) {
             let p = repo.resolve_workdir(&vdproj);
             let f = atry!(
-                File::open(&p);
+                repo.open_file(&vdproj);
                 ["failed to open file `{}`", p.display()]
             );
             let reader = BufReader::new(f);
@@ -138,10 +138,10 @@ impl CsProjLoader {
             // Parse the .csproj XML
 
             let p = repo.resolve_workdir(csproj);
-            let mut xml = atry!(
-                Reader::from_file(&p);
+            let mut xml = Reader::from_reader(BufReader::new(atry!(
+                repo.open_file(csproj);
                 ["unable to open `{}` for reading", p.display()]
-            );
+            )));
             let mut buf = Vec::new();
             let mut guid = None;
             let mut name = None;
@@ -306,7 +306,7 @@ impl CsProjLoader {
 
             {
                 let f = atry!(
-                    File::open(&p);
+                    repo.open_file(assembly_info);
                     ["failed to open file `{}`", p.display()]
                 );
                 let reader = BufReader::new(f);
@@ -468,7 +468,7 @@ impl FormatHandler for CsProjLoader {
         let mut dirs_of_interest: HashMap<RepoPathBuf, super::csproj::DirData> = HashMap::new();
         let mut props_dir = dirname.to_owned();
         props_dir.push("Properties/AssemblyInfo.cs");
-        let assembly_info = if repo.resolve_workdir(&props_dir).exists() {
+        let assembly_info = if repo.is_file(&props_dir) {
             Some(props_dir)
         } else {
             None
@@ -9,7 +9,7 @@ use crate::utils::file_io::check_file_size;
 use crate::{
     atry,
     core::{
-        ecosystem::format_handler::{DiscoveredUnit, FormatHandler},
+        ecosystem::format_handler::{DiscoveredUnit, FormatHandler, SingleUnitBuilder},
         errors::Result,
         git::repository::{ChangeList, RepoPath, RepoPathBuf, Repository},
         release_unit::VersionFieldSpec,
@@ -56,6 +56,22 @@ impl ElixirLoader {
         }
         None
     }
+
+    fn single_unit(manifest_path: &RepoPath, app_name: String, version: Version) -> DiscoveredUnit {
+        let (prefix, _) = manifest_path.split_basename();
+        let manifest = manifest_path.to_owned();
+        let manifest_for_rw = manifest.clone();
+        DiscoveredUnit {
+            qnames: vec![app_name, "elixir".to_owned()],
+            version,
+            prefix: prefix.to_owned(),
+            anchor_manifest: manifest,
+            rewriter_factories: vec![Box::new(move |id| {
+                Box::new(MixExsRewriter::new(id, manifest_for_rw))
+            })],
+            internal_deps: Vec::new(),
+        }
+    }
 }
 
 impl FormatHandler for ElixirLoader {
@@ -99,7 +115,7 @@ impl FormatHandler for ElixirLoader {
         let fs_path = repo.resolve_workdir(manifest_path);
         let mut contents = String::new();
         let mut f = atry!(
-            File::open(&fs_path);
+            repo.open_file(manifest_path);
             ["failed to open mix.exs file `{}`", fs_path.display()]
         );
         atry!(
@@ -128,19 +144,11 @@ impl FormatHandler for ElixirLoader {
             Err(_) => Version::Semver(semver::Version::new(0, 1, 0)),
         };
 
-        let (prefix, _) = manifest_path.split_basename();
-        let manifest = manifest_path.to_owned();
-        let manifest_for_rw = manifest.clone();
-        Ok(Some(DiscoveredUnit {
-            qnames: vec![app_name, "elixir".to_owned()],
-            version,
-            prefix: prefix.to_owned(),
-            anchor_manifest: manifest,
-            rewriter_factories: vec![Box::new(move |id| {
-                Box::new(MixExsRewriter::new(id, manifest_for_rw))
-            })],
-            internal_deps: Vec::new(),
-        }))
+        Ok(Some(Self::single_unit(manifest_path, app_name, version)))
+    }
+
+    fn single_unit_builder(&self) -> Option<SingleUnitBuilder> {
+        Some(Self::single_unit)
     }
 }
 
@@ -19,6 +19,8 @@
 //!   if one claims it, otherwise to the matching `FormatHandler`'s
 //!   default single-file discovery.
 
+use serde::Serialize;
+
 use crate::core::{
     errors::Result,
     git::repository::{RepoPath, RepoPathBuf, Repository},
@@ -92,6 +94,25 @@ pub trait FormatHandler: Send + Sync + std::fmt::Debug {
         repo: &Repository,
         manifest_path: &RepoPath,
     ) -> Result<Option<DiscoveredUnit>>;
+
+    /// Constructor for the unit `discover_single` emits, given its
+    /// native name and version, for handlers whose single-package
+    /// units depend on nothing but the manifest itself. Returning it
+    /// lets the orchestrator cache their results across runs (see
+    /// [`crate::core::release_unit::discovery_cache`]). The default
+    /// `None` opts out — pypa and csproj read sibling files.
+    fn single_unit_builder(&self) -> Option<SingleUnitBuilder> {
+        None
+    }
+
+    /// Direct dependencies declared in one canonical manifest's
+    /// content, as written (name plus requirement text). Pure, like
+    /// [`FormatHandler::parse_version`]; internal deps are included
+    /// and left to the caller to filter. Only used for display
+    /// (`belaf graph --external`), so the default reports none.
+    fn declared_deps(&self, _content: &str) -> Result<Vec<DeclaredDep>> {
+        Ok(Vec::new())
+    }
 }
 
 // ---------------------------------------------------------------------------
@@ -123,6 +144,10 @@ pub trait WorkspaceDiscoverer: Send + Sync + std::fmt::Debug {
 /// Build a [`Rewriter`] for a unit once its `ReleaseUnitId` is known.
 pub type RewriterFactory = Box<dyn FnOnce(ReleaseUnitId) -> Box<dyn Rewriter> + Send>;
 
+/// See [`FormatHandler::single_unit_builder`]. Arguments are the
+/// manifest path, the package-manager-native name and the version.
+pub type SingleUnitBuilder = fn(&RepoPath, String, Version) -> DiscoveredUnit;
+
 pub struct DiscoveredUnit {
     /// Qualified names; `qnames[0]` is the package-manager-native
     /// name, `qnames[1]` is the ecosystem (must equal the
@@ -159,6 +184,16 @@ impl std::fmt::Debug for DiscoveredUnit {
     }
 }
 
+/// A dependency as declared in a manifest — see
+/// [`FormatHandler::declared_deps`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct DeclaredDep {
+    pub name: String,
+    /// The requirement text as written (`^1.0`, `>=2,<3`, `v0.4.1`);
+    /// `*` when the manifest gives none.
+    pub requirement: String,
+}
+
 #[derive(Debug, Clone)]
 pub struct RawInternalDep {
     pub target_package_name: String,
@@ -8,7 +8,9 @@ use crate::utils::file_io::check_file_size;
 use crate::{
     atry,
     core::{
-        ecosystem::format_handler::{DiscoveredUnit, FormatHandler},
+        ecosystem::format_handler::{
+            DeclaredDep, DiscoveredUnit, FormatHandler, SingleUnitBuilder,
+        },
         errors::Result,
         git::repository::{ChangeList, RepoPath, RepoPathBuf, Repository},
         release_unit::VersionFieldSpec,
@@ -32,6 +34,57 @@ fn extract_module_name(content: &str) -> Option<String> {
     None
 }
 
+/// `require` directives of a go.mod, single-line and block form.
+/// Indirect requirements are skipped: they aren't the module's own.
+fn extract_requires(content: &str) -> Vec<DeclaredDep> {
+    let mut requires = Vec::new();
+    let mut in_block = false;
+    for line in content.lines() {
+        let trimmed = line.trim();
+        let spec = if in_block {
+            if trimmed == ")" {
+                in_block = false;
+                continue;
+            }
+            trimmed
+        } else if trimmed == "require (" || trimmed == "require(" {
+            in_block = true;
+            continue;
+        } else if let Some(rest) = trimmed.strip_prefix("require ") {
+            rest.trim()
+        } else {
+            continue;
+        };
+        if spec.contains("// indirect") {
+            continue;
+        }
+        let mut parts = spec.split_whitespace();
+        if let (Some(name), Some(version)) = (parts.next(), parts.next()) {
+            requires.push(DeclaredDep {
+                name: name.to_owned(),
+                requirement: version.to_owned(),
+            });
+        }
+    }
+    requires
+}
+
+fn single_unit(manifest_path: &RepoPath, module_name: String, version: Version) -> DiscoveredUnit {
+    let (prefix, _) = manifest_path.split_basename();
+    let manifest = manifest_path.to_owned();
+    let manifest_for_rw = manifest.clone();
+    DiscoveredUnit {
+        qnames: vec![module_name, "go".to_owned()],
+        version,
+        prefix: prefix.to_owned(),
+        anchor_manifest: manifest,
+        rewriter_factories: vec![Box::new(move |id| {
+            Box::new(GoModRewriter::new(id, manifest_for_rw))
+        })],
+        internal_deps: Vec::new(),
+    }
+}
+
 impl FormatHandler for GoLoader {
     fn name(&self) -> &'static str {
         "go"
@@ -81,7 +134,7 @@ impl FormatHandler for GoLoader {
     ) -> Result<Option<DiscoveredUnit>> {
         let fs_path = repo.resolve_workdir(manifest_path);
         let f = atry!(
-            File::open(&fs_path);
+            repo.open_file(manifest_path);
             ["failed to open go.mod file `{}`", fs_path.display()]
         );
         atry!(
@@ -99,19 +152,19 @@ impl FormatHandler for GoLoader {
             ["failed to parse module name from `{}`", fs_path.display()]
         );
 
-        let (prefix, _) = manifest_path.split_basename();
-        let manifest = manifest_path.to_owned();
-        let manifest_for_rw = manifest.clone();
-        Ok(Some(DiscoveredUnit {
-            qnames: vec![module_name, "go".to_owned()],
-            version: Version::Semver(semver::Version::new(0, 0, 0)),
-            prefix: prefix.to_owned(),
-            anchor_manifest: manifest,
-            rewriter_factories: vec![Box::new(move |id| {
-                Box::new(GoModRewriter::new(id, manifest_for_rw))
-            })],
-            internal_deps: Vec::new(),
-        }))
+        Ok(Some(single_unit(
+            manifest_path,
+            module_name,
+            Version::Semver(semver::Version::new(0, 0, 0)),
+        )))
+    }
+
+    fn single_unit_builder(&self) -> Option<SingleUnitBuilder> {
+        Some(single_unit)
+    }
+
+    fn declared_deps(&self, content: &str) -> Result<Vec<DeclaredDep>> {
+        Ok(extract_requires(content))
     }
 }
 
@@ -218,4 +271,21 @@ mod tests {
 
         assert_eq!(module_name, Some("example.com/myproject".to_string()));
     }
+
+    #[test]
+    fn test_extract_requires_skips_indirect() {
+        let content = "module example.com/app\n\ngo 1.21\n\nrequire golang.org/x/sync v0.7.0\n\nrequire (\n\tgithub.com/spf13/cobra v1.8.0\n\tgithub.com/inconshreveable/mousetrap v1.1.0 // indirect\n)\n";
+        let requires: Vec<_> = super::extract_requires(content)
+            .into_iter()
+            .map(|d| (d.name, d.requirement))
+            .collect();
+
+        assert_eq!(
+            requires,
+            vec![
+                ("golang.org/x/sync".to_string(), "v0.7.0".to_string()),
+                ("github.com/spf13/cobra".to_string(), "v1.8.0".to_string()),
+            ]
+        );
+    }
 }
@@ -44,7 +44,7 @@ use crate::{
     atry,
     core::{
         ecosystem::format_handler::{
-            DiscoveredUnit, FormatHandler, RawInternalDep, WorkspaceDiscoverer,
+            DeclaredDep, DiscoveredUnit, FormatHandler, RawInternalDep, WorkspaceDiscoverer,
         },
         errors::Result,
         git::repository::{RepoPath, RepoPathBuf, Repository},
@@ -123,8 +123,7 @@ impl FormatHandler for MavenLoader {
         // Single-pom fallback: no parent-chain inheritance, no
         // property substitution. Used when the WorkspaceDiscoverer
         // didn't already claim this pom.
-        let fs_path = repo.resolve_workdir(manifest_path);
-        let pom = ParsedPom::from_file(manifest_path, &fs_path)?;
+        let pom = ParsedPom::from_repo(repo, manifest_path)?;
         let group_id = pom
             .group_id
             .clone()
@@ -150,6 +149,21 @@ impl FormatHandler for MavenLoader {
             internal_deps: Vec::new(),
         }))
     }
+
+    fn declared_deps(&self, content: &str) -> Result<Vec<DeclaredDep>> {
+        // Includes `<dependencyManagement>` pins; property references
+        // in versions are reported as written.
+        let buf = RepoPathBuf::new(b"pom.xml");
+        let parsed = ParsedPom::from_str(buf.as_ref(), std::path::Path::new("pom.xml"), content)?;
+        Ok(parsed
+            .dependencies
+            .into_iter()
+            .map(|d| DeclaredDep {
+                name: format!("{}:{}", d.group_id, d.artifact_id),
+                requirement: d.version.unwrap_or_else(|| "*".to_owned()),
+            })
+            .collect())
+    }
 }
 
 /// Workspace walker for maven: claims any `pom.xml`. Walks the full
@@ -185,9 +199,8 @@ impl WorkspaceDiscoverer for MavenWorkspaceDiscoverer {
 
         let mut parsed: Vec<ParsedPom> = Vec::with_capacity(pom_paths.len());
         for repo_path in &pom_paths {
-            let fs_path = repo.resolve_workdir(repo_path);
             let pom = atry!(
-                ParsedPom::from_file(repo_path, &fs_path);
+                ParsedPom::from_repo(repo, repo_path);
                 ["failed to parse Maven POM `{}`", repo_path.escaped()]
             );
             parsed.push(pom);
@@ -6,7 +6,7 @@
 //! Plus parent-cycle detection via Tarjan-SCC on the parent-graph
 //! built from the parsed POMs.
 
-use std::{collections::HashMap, fs::File, io::Read, path::PathBuf};
+use std::{collections::HashMap, path::PathBuf};
 
 use anyhow::anyhow;
 use petgraph::{algo::tarjan_scc, graph::DiGraph};
@@ -16,7 +16,7 @@ use crate::{
     atry,
     core::{
         errors::Result,
-        git::repository::{RepoPath, RepoPathBuf},
+        git::repository::{RepoPath, RepoPathBuf, Repository},
     },
 };
 
@@ -57,13 +57,13 @@ pub(super) struct DepRef {
 }
 
 impl ParsedPom {
-    pub(super) fn from_file(repo_path: &RepoPath, fs_path: &std::path::Path) -> Result<Self> {
-        let mut content = String::new();
-        atry!(
-            File::open(fs_path).and_then(|mut f| f.read_to_string(&mut content));
+    pub(super) fn from_repo(repo: &Repository, repo_path: &RepoPath) -> Result<Self> {
+        let fs_path = repo.resolve_workdir(repo_path);
+        let content = atry!(
+            repo.read_to_string(repo_path);
             ["failed to read Maven POM `{}`", fs_path.display()]
         );
-        Self::from_str(repo_path, fs_path, &content)
+        Self::from_str(repo_path, &fs_path, &content)
     }
 
     pub(super) fn from_str(
@@ -23,7 +23,8 @@ use crate::{
     atry,
     core::{
         ecosystem::format_handler::{
-            DiscoveredUnit, FormatHandler, RawInternalDep, WorkspaceDiscoverer,
+            DeclaredDep, DiscoveredUnit, FormatHandler, RawInternalDep, SingleUnitBuilder,
+            WorkspaceDiscoverer,
         },
         errors::Result,
         git::repository::{ChangeList, RepoPath, RepoPathBuf, Repository},
@@ -62,7 +63,7 @@ impl NpmLoader {
     ) -> Result<Option<(DiscoveredUnit, PackageLoadData)>> {
         let path = repo.resolve_workdir(repopath);
         let f = atry!(
-            File::open(&path);
+            repo.open_file(repopath);
             ["failed to open repository file `{}`", path.display()]
         );
         atry!(
@@ -106,18 +107,7 @@ impl NpmLoader {
              version_str, path.display()]
         );
 
-        let (dirname, _) = repopath.split_basename();
-        let json_path = repopath.clone();
-        let unit = DiscoveredUnit {
-            qnames: vec![name.clone(), "npm".to_owned()],
-            version: Version::Semver(version),
-            prefix: dirname.to_owned(),
-            anchor_manifest: repopath.clone(),
-            rewriter_factories: vec![Box::new(move |id| {
-                Box::new(PackageJsonRewriter::new(id, json_path))
-            })],
-            internal_deps: Vec::new(),
-        };
+        let unit = Self::single_unit(repopath, name.clone(), Version::Semver(version));
         let load = PackageLoadData {
             package_name: name,
             json_path: repopath.clone(),
@@ -125,6 +115,21 @@ impl NpmLoader {
         };
         Ok(Some((unit, load)))
     }
+
+    fn single_unit(repopath: &RepoPath, name: String, version: Version) -> DiscoveredUnit {
+        let (dirname, _) = repopath.split_basename();
+        let json_path = repopath.to_owned();
+        DiscoveredUnit {
+            qnames: vec![name, "npm".to_owned()],
+            version,
+            prefix: dirname.to_owned(),
+            anchor_manifest: repopath.to_owned(),
+            rewriter_factories: vec![Box::new(move |id| {
+                Box::new(PackageJsonRewriter::new(id, json_path))
+            })],
+            internal_deps: Vec::new(),
+        }
+    }
 }
 
 impl FormatHandler for NpmLoader {
@@ -178,6 +183,25 @@ impl FormatHandler for NpmLoader {
             None => Ok(None),
         }
     }
+
+    fn single_unit_builder(&self) -> Option<SingleUnitBuilder> {
+        Some(Self::single_unit)
+    }
+
+    fn declared_deps(&self, content: &str) -> Result<Vec<DeclaredDep>> {
+        let pkg: serde_json::Map<String, serde_json::Value> =
+            serde_json::from_str(content).map_err(|e| anyhow!("parse package.json: {e}"))?;
+        let Some(deps) = pkg.get("dependencies").and_then(|v| v.as_object()) else {
+            return Ok(Vec::new());
+        };
+        Ok(deps
+            .iter()
+            .map(|(name, spec)| DeclaredDep {
+                name: name.clone(),
+                requirement: spec.as_str().unwrap_or("*").to_owned(),
+            })
+            .collect())
+    }
 }
 
 /// Workspace walker for npm: claims any `package.json` carrying a
@@ -195,8 +219,7 @@ impl WorkspaceDiscoverer for NpmWorkspaceDiscoverer {
         if basename.as_ref() != b"package.json" {
             return false;
         }
-        let abs = repo.resolve_workdir(manifest_path);
-        let Ok(content) = std::fs::read_to_string(&abs) else {
+        let Ok(content) = repo.read_to_string(manifest_path) else {
             return false;
         };
         let Ok(pkg) = serde_json::from_str::<serde_json::Value>(&content) else {
@@ -210,8 +233,7 @@ impl WorkspaceDiscoverer for NpmWorkspaceDiscoverer {
         // member's package.json, wire internal deps. Top-level
         // package.json itself is treated as a unit only if it has a
         // `version` + content key (parse_one handles the filter).
-        let root_abs = repo.resolve_workdir(root_path);
-        let root_content = match std::fs::read_to_string(&root_abs) {
+        let root_content = match repo.read_to_string(root_path) {
             Ok(c) => c,
             Err(_) => return Ok(Vec::new()),
         };
@@ -20,7 +20,7 @@ use crate::utils::file_io::check_file_size;
 use crate::{
     a_ok_or, atry,
     core::{
-        ecosystem::format_handler::{DiscoveredUnit, FormatHandler, RawInternalDep},
+        ecosystem::format_handler::{DeclaredDep, DiscoveredUnit, FormatHandler, RawInternalDep},
         errors::{Error, Result},
         git::repository::{ChangeList, RepoPath, RepoPathBuf, Repository},
         release_unit::VersionFieldSpec,
@@ -71,7 +71,7 @@ impl PypaLoader {
 
             let config = {
                 let toml_path = repo.resolve_workdir(&toml_repopath);
-                let f = match File::open(&toml_path) {
+                let f = match repo.open_file(&toml_repopath) {
                     Ok(f) => Some(f),
                     Err(e) => {
                         if e.kind() == std::io::ErrorKind::NotFound {
@@ -122,11 +122,11 @@ impl PypaLoader {
             // Parse setup.cfg for metadata if available.
 
             {
-                let mut cfg_path = dirname.clone();
-                cfg_path.push("setup.cfg");
-                let cfg_path = repo.resolve_workdir(&cfg_path);
+                let mut cfg_repopath = dirname.clone();
+                cfg_repopath.push("setup.cfg");
+                let cfg_path = repo.resolve_workdir(&cfg_repopath);
 
-                let f = match File::open(&cfg_path) {
+                let f = match repo.open_file(&cfg_repopath) {
                     Ok(f) => Some(f),
                     Err(e) => {
                         if e.kind() == std::io::ErrorKind::NotFound {
@@ -197,11 +197,11 @@ impl PypaLoader {
             // Finally, how about setup.py?
 
             {
-                let mut setup_path = dirname.clone();
-                setup_path.push("setup.py");
-                let setup_path = repo.resolve_workdir(&setup_path);
+                let mut setup_repopath = dirname.clone();
+                setup_repopath.push("setup.py");
+                let setup_path = repo.resolve_workdir(&setup_repopath);
 
-                let f = match File::open(&setup_path) {
+                let f = match repo.open_file(&setup_repopath) {
                     Ok(f) => Some(f),
                     Err(e) => {
                         if e.kind() == std::io::ErrorKind::NotFound {
@@ -261,12 +261,12 @@ impl PypaLoader {
 
             if !main_version_in_setup && py_version_file.is_some() {
                 let main_version_file = py_version_file.as_deref().unwrap();
-                let mut version_path = dirname.clone();
-                version_path.push(main_version_file);
-                let version_path = repo.resolve_workdir(&version_path);
+                let mut version_repopath = dirname.clone();
+                version_repopath.push(main_version_file);
+                let version_path = repo.resolve_workdir(&version_repopath);
 
                 let f = atry!(
-                    File::open(&version_path);
+                    repo.open_file(&version_repopath);
                     ["failed to open file `{}`", version_path.display()]
                 );
 
@@ -489,6 +489,47 @@ impl FormatHandler for PypaLoader {
         let mut units = self.build_units(repo, dirs)?;
         Ok(units.pop())
     }
+
+    fn declared_deps(&self, content: &str) -> Result<Vec<DeclaredDep>> {
+        // Only PEP 621 metadata is declarative; setup.py / setup.cfg
+        // requirements aren't read.
+        let Ok(parsed) = toml::from_str::<PyProjectFile>(content) else {
+            return Ok(Vec::new());
+        };
+        Ok(parsed
+            .project
+            .map(|p| p.dependencies)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|spec| parse_pep508_requirement(spec))
+            .collect())
+    }
+}
+
+/// Split a PEP 508 requirement (`requests[socks]>=2.31; python_version
+/// < "3.12"`) into its distribution name and version specifier.
+fn parse_pep508_requirement(spec: &str) -> Option<DeclaredDep> {
+    let spec = spec.split(';').next().unwrap_or_default().trim();
+    let name_end = spec
+        .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+        .unwrap_or(spec.len());
+    let name = &spec[..name_end];
+    if name.is_empty() {
+        return None;
+    }
+    let mut rest = spec[name_end..].trim_start();
+    if rest.starts_with('[') {
+        rest = rest.find(']').map_or("", |i| rest[i + 1..].trim_start());
+    }
+    let requirement = rest.trim_start_matches('(').trim_end_matches(')').trim();
+    Some(DeclaredDep {
+        name: name.to_owned(),
+        requirement: if requirement.is_empty() {
+            "*".to_owned()
+        } else {
+            requirement.to_owned()
+        },
+    })
 }
 
 fn scan_rewritten_file(
@@ -499,7 +540,7 @@ fn scan_rewritten_file(
     let file_path = repo.resolve_workdir(path);
 
     let f = atry!(
-        File::open(&file_path);
+        repo.open_file(path);
         ["failed to open file `{}` for reading", file_path.display()]
     );
     let reader = BufReader::new(f);
@@ -722,6 +763,9 @@ impl PyProjectFile {
 struct PyProjectMetadata {
     pub name: Option<String>,
     pub version: Option<String>,
+    /// PEP 508 requirement strings.
+    #[serde(default)]
+    pub dependencies: Vec<String>,
 }
 
 /// `pyproject.toml` section `tool` deserialization container.
@@ -1128,4 +1172,18 @@ version = {attr = "package.__version__"}
         assert!(double.contains('"'));
         assert!(single.contains('\''));
     }
+
+    #[test]
+    fn test_parse_pep508_requirement() {
+        let dep = |spec| {
+            let d = super::parse_pep508_requirement(spec).expect("requirement parses");
+            (d.name, d.requirement)
+        };
+        assert_eq!(dep("requests>=2.31"), ("requests".into(), ">=2.31".into()));
+        assert_eq!(
+            dep("httpx[http2] (>=0.27,<1); python_version >= '3.9'"),
+            ("httpx".into(), ">=0.27,<1".into())
+        );
+        assert_eq!(dep("attrs"), ("attrs".into(), "*".into()));
+    }
 }
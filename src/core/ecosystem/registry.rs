@@ -0,0 +1,223 @@
+//! Whether a version is already on its package registry.
+//!
+//! Registries refuse to publish a version twice. With `[release]
+//! check_registries = true`, `belaf release cut` therefore refuses a
+//! release whose version crates.io, npm or PyPI already has, before
+//! anything is tagged, and `belaf release publish` flags those in the
+//! `released` webhook so the job publishing to registries can skip them
+//! when it runs again. Releases of other ecosystems aren't looked up.
+//!
+//! `belaf release publish` can also wait for a release to show up on its
+//! registry before publishing its dependents; see [`RegistryClient::wait_for`].
+//!
+//! Only the public registries are asked, anonymously: a package on a
+//! private registry is simply never found.
+
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context};
+use reqwest::StatusCode;
+use serde_json::Value;
+
+use crate::core::errors::Result;
+use crate::core::wire::known::{Ecosystem, KnownEcosystem};
+
+const TIMEOUT_SECS: u64 = 15;
+/// The longest pause between two looks in [`RegistryClient::wait_for`].
+const MAX_POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// A public package registry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Registry {
+    CratesIo,
+    Npm,
+    PyPI,
+}
+
+impl Registry {
+    /// The registry releases of `ecosystem` are published to, if any is
+    /// looked up.
+    pub fn of(ecosystem: &Ecosystem) -> Option<Self> {
+        match ecosystem {
+            Ecosystem::Known(KnownEcosystem::Cargo) => Some(Self::CratesIo),
+            Ecosystem::Known(KnownEcosystem::Npm) => Some(Self::Npm),
+            Ecosystem::Known(KnownEcosystem::Pypa) => Some(Self::PyPI),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::CratesIo => "crates.io",
+            Self::Npm => "npm",
+            Self::PyPI => "PyPI",
+        }
+    }
+
+    fn default_url(self) -> &'static str {
+        match self {
+            Self::CratesIo => "https://crates.io",
+            Self::Npm => "https://registry.npmjs.org",
+            Self::PyPI => "https://pypi.org",
+        }
+    }
+}
+
+/// Asks registries about versions.
+pub struct RegistryClient {
+    http: reqwest::Client,
+    url: Option<String>,
+}
+
+impl RegistryClient {
+    pub fn new() -> Result<Self> {
+        let http = crate::core::net::client_builder()?
+            .timeout(Duration::from_secs(TIMEOUT_SECS))
+            .user_agent(concat!("belaf/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .context("failed to create HTTP client")?;
+        Ok(Self { http, url: None })
+    }
+
+    /// A client asking the server at `url` in place of every registry.
+    pub fn with_url(url: &str) -> Result<Self> {
+        Ok(Self {
+            url: Some(url.trim_end_matches('/').to_string()),
+            ..Self::new()?
+        })
+    }
+
+    /// Whether `registry` has `version` of package `name`.
+    pub async fn has_version(&self, registry: Registry, name: &str, version: &str) -> Result<bool> {
+        let root = self.url.as_deref().unwrap_or(registry.default_url());
+        let url = match registry {
+            Registry::CratesIo => format!("{root}/api/v1/crates/{name}/{version}"),
+            // Scoped names keep their `@` but escape the `/`.
+            Registry::Npm => format!("{root}/{}", name.replace('/', "%2F")),
+            Registry::PyPI => format!("{root}/pypi/{name}/{version}/json"),
+        };
+        let response = self
+            .http
+            .get(&url)
+            .header(
+                "Accept",
+                "application/vnd.npm.install-v1+json, application/json",
+            )
+            .send()
+            .await
+            .with_context(|| format!("failed to reach {}", registry.label()))?;
+        match response.status() {
+            StatusCode::OK => {}
+            StatusCode::NOT_FOUND => return Ok(false),
+            status => bail!(
+                "{} answered {status} for {name} {version}",
+                registry.label()
+            ),
+        }
+        if registry != Registry::Npm {
+            return Ok(true);
+        }
+        let packument: Value = response
+            .json()
+            .await
+            .with_context(|| format!("npm returned an unreadable document for {name}"))?;
+        Ok(packument["versions"].get(version).is_some())
+    }
+
+    /// Look for `version` of `name` on `registry` until it is there,
+    /// first after `interval` and then twice as long each time, up to a
+    /// minute apart. Fails once `timeout` has passed.
+    pub async fn wait_for(
+        &self,
+        registry: Registry,
+        name: &str,
+        version: &str,
+        timeout: Duration,
+        mut interval: Duration,
+    ) -> Result<()> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            if self.has_version(registry, name, version).await? {
+                return Ok(());
+            }
+            let left = deadline.saturating_duration_since(Instant::now());
+            if left.is_zero() {
+                bail!(
+                    "{} did not list {name} {version} within {}s",
+                    registry.label(),
+                    timeout.as_secs()
+                );
+            }
+            tokio::time::sleep(interval.min(left)).await;
+            interval = (interval * 2).min(MAX_POLL_INTERVAL);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn each_registry_is_asked_its_own_way() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/crates/serde/1.0.0"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/@acme%2Fui"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(json!({ "versions": { "1.0.0": {} } })),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/pypi/requests/9.9.9/json"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+
+        let client = RegistryClient::with_url(&server.uri()).unwrap();
+        let has = |registry, name, version| client.has_version(registry, name, version);
+        assert!(has(Registry::CratesIo, "serde", "1.0.0").await.unwrap());
+        assert!(!has(Registry::CratesIo, "serde", "9.9.9").await.unwrap());
+        assert!(has(Registry::Npm, "@acme/ui", "1.0.0").await.unwrap());
+        assert!(!has(Registry::Npm, "@acme/ui", "1.1.0").await.unwrap());
+        assert!(!has(Registry::PyPI, "requests", "9.9.9").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn waiting_ends_once_the_version_shows() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/crates/core/1.1.0"))
+            .respond_with(ResponseTemplate::new(404))
+            .up_to_n_times(2)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/api/v1/crates/core/1.1.0"))
+            .respond_with(ResponseTemplate::new(200))
+            .mount(&server)
+            .await;
+
+        let client = RegistryClient::with_url(&server.uri()).unwrap();
+        let ms = Duration::from_millis;
+        client
+            .wait_for(Registry::CratesIo, "core", "1.1.0", ms(2000), ms(10))
+            .await
+            .unwrap();
+        let err = client
+            .wait_for(Registry::CratesIo, "core", "1.2.0", ms(50), ms(10))
+            .await
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("crates.io did not list core 1.2.0"));
+    }
+}
@@ -1,8 +1,5 @@
 use anyhow::anyhow;
-use std::{
-    fs::File,
-    io::{BufRead, BufReader},
-};
+use std::io::{BufRead, BufReader};
 
 // Swift Package Manager does not store version in Package.swift.
 // Versions are derived from git tags (e.g., v1.0.0), similar to Go modules.
@@ -12,7 +9,7 @@ use crate::utils::file_io::check_file_size;
 use crate::{
     atry,
     core::{
-        ecosystem::format_handler::{DiscoveredUnit, FormatHandler},
+        ecosystem::format_handler::{DiscoveredUnit, FormatHandler, SingleUnitBuilder},
         errors::Result,
         git::repository::{ChangeList, RepoPath, RepoPathBuf, Repository},
         release_unit::VersionFieldSpec,
@@ -80,7 +77,7 @@ impl FormatHandler for SwiftLoader {
     ) -> Result<Option<DiscoveredUnit>> {
         let fs_path = repo.resolve_workdir(manifest_path);
         let f = atry!(
-            File::open(&fs_path);
+            repo.open_file(manifest_path);
             ["failed to open Package.swift file `{}`", fs_path.display()]
         );
         atry!(
@@ -100,15 +97,27 @@ impl FormatHandler for SwiftLoader {
             package_name.ok_or_else(|| anyhow!("no package name declaration found"));
             ["failed to parse package name from `{}`", fs_path.display()]
         );
-        let (prefix, _) = manifest_path.split_basename();
-        Ok(Some(DiscoveredUnit {
-            qnames: vec![package_name, "swift".to_owned()],
-            version: Version::Semver(semver::Version::new(0, 0, 0)),
-            prefix: prefix.to_owned(),
-            anchor_manifest: manifest_path.to_owned(),
-            rewriter_factories: vec![Box::new(|_id| Box::new(SwiftNoOpRewriter))],
-            internal_deps: Vec::new(),
-        }))
+        Ok(Some(single_unit(
+            manifest_path,
+            package_name,
+            Version::Semver(semver::Version::new(0, 0, 0)),
+        )))
+    }
+
+    fn single_unit_builder(&self) -> Option<SingleUnitBuilder> {
+        Some(single_unit)
+    }
+}
+
+fn single_unit(manifest_path: &RepoPath, package_name: String, version: Version) -> DiscoveredUnit {
+    let (prefix, _) = manifest_path.split_basename();
+    DiscoveredUnit {
+        qnames: vec![package_name, "swift".to_owned()],
+        version,
+        prefix: prefix.to_owned(),
+        anchor_manifest: manifest_path.to_owned(),
+        rewriter_factories: vec![Box::new(|_id| Box::new(SwiftNoOpRewriter))],
+        internal_deps: Vec::new(),
     }
 }
 
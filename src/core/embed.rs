@@ -50,12 +50,27 @@ impl EmbeddedConfig {
         release_units.sort_by(|a, b| a.name.cmp(&b.name));
 
         Ok(ConfigurationFile {
+            include: cfg.include,
+            sources: Vec::new(),
             repo: cfg.repo,
             changelog: cfg.changelog,
             bump: cfg.bump,
             commit_attribution: cfg.commit_attribution,
+            git: cfg.git,
+            network: cfg.network,
+            auth: cfg.auth,
+            forge: cfg.forge,
+            release: cfg.release,
+            dashboard: cfg.dashboard,
+            ci: cfg.ci,
+            checks: cfg.checks,
+            webhooks: cfg.webhooks,
+            trackers: cfg.trackers,
+            signing: cfg.signing,
             groups,
             bump_sources: cfg.bump_sources,
+            packaging: cfg.packaging,
+            submodules: cfg.submodules,
             release_units,
             ignore_paths: cfg.ignore_paths,
             allow_uncovered: cfg.allow_uncovered,
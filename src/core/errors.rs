@@ -13,6 +13,11 @@
 //!   becomes context lines; `AnnotatedReport.notes` and downcasts of typed
 //!   errors (`ApiError`, `DirtyRepositoryError`, etc.) become `help:`
 //!   groups underneath.
+//! * `ErrorCode` + `classify(&Error)`: the stable code (and exit code)
+//!   for a failure, derived from the same typed errors, and the one-line
+//!   JSON form `display_diagnostic` prints under `--format json`.
+//! * `record_error` / `recent_errors`: the last few diagnostics, kept in
+//!   the user cache directory for `belaf env`.
 
 use std::io::{stderr, IsTerminal};
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -20,6 +25,8 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use annotate_snippets::{Group, Level, Renderer};
 use thiserror::Error as ThisError;
 
+use crate::core::exit_code::ExitCode;
+
 /// Global "no color" override. `main` sets this to `true` when the user
 /// passes `--no-color`. `display_diagnostic` honors it alongside the
 /// standard `NO_COLOR` env var.
@@ -29,6 +36,15 @@ pub fn set_no_color(value: bool) {
     FORCE_NO_COLOR.store(value, Ordering::Relaxed);
 }
 
+/// Global "JSON errors" switch. `main` sets this when the command was
+/// invoked with `--format json` (or its equivalent), so the failure
+/// comes out as JSON on stderr like the success payload on stdout.
+static JSON_OUTPUT: AtomicBool = AtomicBool::new(false);
+
+pub fn set_json_output(value: bool) {
+    JSON_OUTPUT.store(value, Ordering::Relaxed);
+}
+
 pub use anyhow::Error;
 pub type Result<T> = std::result::Result<T, Error>;
 
@@ -101,6 +117,181 @@ macro_rules! a_ok_or {
     }};
 }
 
+// ---------------------------------------------------------------------------
+// Error codes
+// ---------------------------------------------------------------------------
+
+/// Stable, machine-readable classification of a failure. Reported as
+/// `code` in the `--format json` error payload and mapped onto an
+/// [`ExitCode`], so scripts can tell a dirty tree from a missing upstream
+/// from an auth failure without parsing messages. Labels are public
+/// contract just like exit codes: add variants, never rename them.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ErrorCode {
+    /// Anything not recognised below.
+    Internal,
+    /// Not inside a Git working tree.
+    NotARepository,
+    /// The repository is bare.
+    BareRepository,
+    /// The working tree has changes and the command needs it clean.
+    DirtyRepository,
+    /// No remote could be chosen as the upstream.
+    NoUpstream,
+    /// The upstream remote is not one of `[repo] upstream_urls`.
+    UnexpectedUpstream,
+    /// No valid belaf credentials, or the API rejected them.
+    AuthRequired,
+    /// The GitHub App is not installed on the repository, can't see it,
+    /// or lacks a permission.
+    AccessDenied,
+    /// The account's plan does not allow another repository.
+    PlanLimit,
+    /// `belaf/config.toml` is invalid.
+    ConfigInvalid,
+    /// The belaf API or GitHub rate-limited the request.
+    RateLimited,
+    /// A network call failed or returned an error response.
+    Network,
+}
+
+impl ErrorCode {
+    /// Stable string label, the `code` of the JSON error payload.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Internal => "internal",
+            Self::NotARepository => "not_a_repository",
+            Self::BareRepository => "bare_repository",
+            Self::DirtyRepository => "dirty_repository",
+            Self::NoUpstream => "no_upstream",
+            Self::UnexpectedUpstream => "unexpected_upstream",
+            Self::AuthRequired => "auth_required",
+            Self::AccessDenied => "access_denied",
+            Self::PlanLimit => "plan_limit",
+            Self::ConfigInvalid => "config_invalid",
+            Self::RateLimited => "rate_limited",
+            Self::Network => "network",
+        }
+    }
+
+    /// The process exit code a failure of this kind produces.
+    pub fn exit_code(self) -> ExitCode {
+        match self {
+            Self::Internal => ExitCode::Generic,
+            Self::NotARepository
+            | Self::BareRepository
+            | Self::DirtyRepository
+            | Self::NoUpstream
+            | Self::UnexpectedUpstream
+            | Self::AuthRequired
+            | Self::AccessDenied
+            | Self::PlanLimit => ExitCode::Precondition,
+            Self::ConfigInvalid => ExitCode::ConfigInvalid,
+            Self::RateLimited | Self::Network => ExitCode::Network,
+        }
+    }
+
+    /// One-line description for `belaf describe`.
+    pub fn description(self) -> &'static str {
+        match self {
+            Self::Internal => "Unclassified error.",
+            Self::NotARepository => "Not inside a Git working tree.",
+            Self::BareRepository => "The repository is bare.",
+            Self::DirtyRepository => "The working tree has uncommitted changes.",
+            Self::NoUpstream => "No Git remote could be identified as the upstream.",
+            Self::UnexpectedUpstream => "The upstream remote is not one of `[repo] upstream_urls`.",
+            Self::AuthRequired => "Not authenticated, or the credentials were rejected.",
+            Self::AccessDenied => {
+                "The GitHub App is missing on the repository or lacks a permission."
+            }
+            Self::PlanLimit => "The plan's repository limit is reached.",
+            Self::ConfigInvalid => "`belaf/config.toml` is invalid.",
+            Self::RateLimited => "Rate-limited by the belaf API or GitHub.",
+            Self::Network => "A network call failed or returned an error.",
+        }
+    }
+
+    /// Every variant, for documentation generators.
+    pub fn all() -> &'static [ErrorCode] {
+        &[
+            Self::Internal,
+            Self::NotARepository,
+            Self::BareRepository,
+            Self::DirtyRepository,
+            Self::NoUpstream,
+            Self::UnexpectedUpstream,
+            Self::AuthRequired,
+            Self::AccessDenied,
+            Self::PlanLimit,
+            Self::ConfigInvalid,
+            Self::RateLimited,
+            Self::Network,
+        ]
+    }
+}
+
+/// Context marking an error as caused by `belaf/config.toml`, so that
+/// [`classify`] reports `config_invalid` whatever the underlying parse or
+/// validation error was. Displays as its message.
+#[derive(Debug, ThisError)]
+#[error("{0}")]
+pub struct ConfigError(pub String);
+
+/// Find a `T` anywhere in `error`: among the anyhow contexts (only
+/// reachable through `Error::downcast_ref`) or in the `source()` chain.
+fn find<T: std::error::Error + Send + Sync + 'static>(error: &Error) -> Option<&T> {
+    error
+        .downcast_ref::<T>()
+        .or_else(|| error.chain().find_map(|layer| layer.downcast_ref::<T>()))
+}
+
+/// Classify `error` by the typed errors it carries. Errors that are
+/// still plain strings come out as [`ErrorCode::Internal`].
+pub fn classify(error: &Error) -> ErrorCode {
+    use crate::core::api::ApiError;
+    use crate::core::auth::permissions::AccessError;
+    use crate::core::git::repository::{
+        BareRepositoryError, DirtyRepositoryError, NoUpstreamError, UnexpectedUpstreamError,
+    };
+
+    if find::<ConfigError>(error).is_some() {
+        return ErrorCode::ConfigInvalid;
+    }
+    if find::<DirtyRepositoryError>(error).is_some() {
+        return ErrorCode::DirtyRepository;
+    }
+    if find::<BareRepositoryError>(error).is_some() {
+        return ErrorCode::BareRepository;
+    }
+    if find::<NoUpstreamError>(error).is_some() {
+        return ErrorCode::NoUpstream;
+    }
+    if find::<UnexpectedUpstreamError>(error).is_some() {
+        return ErrorCode::UnexpectedUpstream;
+    }
+    if find::<AccessError>(error).is_some() {
+        return ErrorCode::AccessDenied;
+    }
+    if let Some(api) = find::<ApiError>(error) {
+        return match api {
+            ApiError::Unauthorized
+            | ApiError::DeviceCodeExpired
+            | ApiError::DeviceCodeDenied
+            | ApiError::TokenStorage(_) => ErrorCode::AuthRequired,
+            ApiError::RateLimited { .. } | ApiError::SlowDown => ErrorCode::RateLimited,
+            ApiError::LimitExceeded { .. } => ErrorCode::PlanLimit,
+            ApiError::ClientCreation(_) | ApiError::InvalidConfiguration(_) => ErrorCode::Internal,
+            _ => ErrorCode::Network,
+        };
+    }
+    if let Some(git) = find::<git2::Error>(error) {
+        if git.class() == git2::ErrorClass::Repository && git.code() == git2::ErrorCode::NotFound {
+            return ErrorCode::NotARepository;
+        }
+    }
+    ErrorCode::Internal
+}
+
 // ---------------------------------------------------------------------------
 // Diagnostic rendering
 // ---------------------------------------------------------------------------
@@ -110,6 +301,10 @@ macro_rules! a_ok_or {
 /// Color is decided by `use_color()`: respects `NO_COLOR`, the `--no-color`
 /// flag (via `owo_colors`), and stderr-is-a-TTY.
 pub fn display_diagnostic(error: &Error) {
+    if JSON_OUTPUT.load(Ordering::Relaxed) {
+        eprintln!("{}", render_json(error));
+        return;
+    }
     let rendered = render_diagnostic(error, use_color());
     eprintln!();
     eprintln!("{}", rendered);
@@ -122,11 +317,47 @@ pub fn report(r: Result<i32>) -> i32 {
         Ok(c) => c,
         Err(e) => {
             display_diagnostic(&e);
-            1
+            classify(&e).exit_code().into()
         }
     }
 }
 
+/// The `--format json` form of a diagnostic: one line, so a script can
+/// read stderr's last line and parse it.
+///
+/// ```json
+/// {"error":{"code":"dirty_repository","exit_code":4,"message":"...","causes":[],"hints":["..."]}}
+/// ```
+pub fn render_json(error: &Error) -> String {
+    #[derive(serde::Serialize)]
+    struct Payload {
+        error: JsonError,
+    }
+
+    #[derive(serde::Serialize)]
+    struct JsonError {
+        code: &'static str,
+        exit_code: i32,
+        message: String,
+        causes: Vec<String>,
+        hints: Vec<String>,
+    }
+
+    let code = classify(error);
+    let mut hints = collect_notes(error);
+    hints.extend(derive_typed_hints(error));
+    let payload = Payload {
+        error: JsonError {
+            code: code.label(),
+            exit_code: code.exit_code().into(),
+            message: error.to_string(),
+            causes: error.chain().skip(1).map(|c| c.to_string()).collect(),
+            hints,
+        },
+    };
+    serde_json::to_string(&payload).unwrap_or_default()
+}
+
 /// Plain (no-color) renderer for tests and snapshot assertions.
 #[doc(hidden)]
 pub fn display_diagnostic_to_string(error: &Error) -> String {
@@ -197,7 +428,7 @@ fn use_color() -> bool {
     if std::env::var_os("NO_COLOR").is_some() {
         return false;
     }
-    stderr().is_terminal()
+    !super::output::is_capturing() && stderr().is_terminal()
 }
 
 /// Walk every layer of the error (including the outermost anyhow context,
@@ -205,7 +436,13 @@ fn use_color() -> bool {
 /// every recognised typed error along the way.
 fn derive_typed_hints(error: &Error) -> Vec<String> {
     use crate::core::api::ApiError;
-    use crate::core::git::repository::{BareRepositoryError, DirtyRepositoryError};
+    use crate::core::auth::permissions::AccessError;
+    use crate::core::git::repository::{
+        BareRepositoryError, DirtyRepositoryError, UnexpectedUpstreamError,
+    };
+
+    const BARE_REPOSITORY_HINT: &str = "this command needs a working tree; in a bare repository only `status`, `graph`, `history` and `changelog --preview` run";
+    const UNEXPECTED_UPSTREAM_HINT: &str = "point the upstream remote at a repository in `[repo] upstream_urls`, add its URL there, or pass `--i-know-what-im-doing`";
 
     fn hint_for_api(api: &ApiError) -> Option<String> {
         match api {
@@ -242,11 +479,17 @@ fn derive_typed_hints(error: &Error) -> Vec<String> {
             hints.push(h);
         }
     }
+    if let Some(access) = error.downcast_ref::<AccessError>() {
+        hints.push(access.hint());
+    }
     if error.downcast_ref::<DirtyRepositoryError>().is_some() {
         hints.push("commit or stash your changes, or pass `--force` to override".to_string());
     }
     if error.downcast_ref::<BareRepositoryError>().is_some() {
-        hints.push("belaf must run inside a working tree, not a bare repository".to_string());
+        hints.push(BARE_REPOSITORY_HINT.to_string());
+    }
+    if error.downcast_ref::<UnexpectedUpstreamError>().is_some() {
+        hints.push(UNEXPECTED_UPSTREAM_HINT.to_string());
     }
 
     // Plus every layer in the standard source() chain.
@@ -260,7 +503,13 @@ fn derive_typed_hints(error: &Error) -> Vec<String> {
             hints.push("commit or stash your changes, or pass `--force` to override".to_string());
         }
         if layer.downcast_ref::<BareRepositoryError>().is_some() {
-            hints.push("belaf must run inside a working tree, not a bare repository".to_string());
+            hints.push(BARE_REPOSITORY_HINT.to_string());
+        }
+        if layer.downcast_ref::<UnexpectedUpstreamError>().is_some() {
+            hints.push(UNEXPECTED_UPSTREAM_HINT.to_string());
+        }
+        if let Some(access) = layer.downcast_ref::<AccessError>() {
+            hints.push(access.hint());
         }
     }
 
@@ -269,3 +518,72 @@ fn derive_typed_hints(error: &Error) -> Vec<String> {
     hints.dedup();
     hints
 }
+
+// ---------------------------------------------------------------------------
+// Recent errors
+// ---------------------------------------------------------------------------
+
+/// How many failed runs [`record_error`] keeps.
+const RECENT_ERRORS_KEPT: usize = 5;
+
+/// A failed run, kept in the user cache directory so `belaf env` can
+/// include it in a bug report.
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct RecordedError {
+    /// RFC 3339, UTC.
+    pub timestamp: String,
+    /// Command-line arguments after the program name.
+    pub args: Vec<String>,
+    /// The diagnostic as printed, without color.
+    pub diagnostic: String,
+}
+
+fn recent_errors_path() -> Option<std::path::PathBuf> {
+    directories::ProjectDirs::from("", "", "belaf")
+        .map(|d| d.cache_dir().to_path_buf())
+        .or_else(|| dirs::cache_dir().map(|d| d.join("belaf")))
+        .map(|d| d.join("errors.jsonl"))
+}
+
+/// Remember `error` from the run of `belaf <args>` for `belaf env`,
+/// dropping all but the last few. Best-effort: an unwritable cache
+/// directory is not worth a second error on top of the one being
+/// reported.
+pub fn record_error(error: &Error, args: &[String]) {
+    let Some(path) = recent_errors_path() else {
+        return;
+    };
+    let now = time::OffsetDateTime::now_utc();
+    let mut kept = recent_errors();
+    kept.push(RecordedError {
+        timestamp: now
+            .format(&time::format_description::well_known::Rfc3339)
+            .unwrap_or_else(|_| now.to_string()),
+        args: args.to_vec(),
+        diagnostic: render_diagnostic(error, false),
+    });
+
+    let start = kept.len().saturating_sub(RECENT_ERRORS_KEPT);
+    let content: String = kept[start..]
+        .iter()
+        .filter_map(|e| serde_json::to_string(e).ok())
+        .map(|line| line + "\n")
+        .collect();
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    let _ = std::fs::write(path, content);
+}
+
+/// The failed runs [`record_error`] kept, oldest first.
+pub fn recent_errors() -> Vec<RecordedError> {
+    recent_errors_path()
+        .and_then(|p| std::fs::read_to_string(p).ok())
+        .map(|content| {
+            content
+                .lines()
+                .filter_map(|l| serde_json::from_str(l).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
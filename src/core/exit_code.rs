@@ -2,6 +2,9 @@
 //! emitted by `belaf describe --json` so AI agents can branch on them
 //! without parsing stderr.
 //!
+//! Failures get theirs from the [`crate::core::errors::ErrorCode`] they
+//! classify as.
+//!
 //! The set is intentionally small. Add a new variant only when an
 //! existing one is genuinely wrong, and never repurpose a number.
 
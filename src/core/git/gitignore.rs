@@ -5,6 +5,7 @@ use std::path::Path;
 const GITIGNORE_ENTRIES: &str = r#"
 # Belaf
 belaf/.branches
+belaf/.cache
 "#;
 
 pub fn update(project_root: &Path) -> Result<()> {
@@ -0,0 +1,163 @@
+//! Client-side git hooks for commits belaf writes itself.
+//!
+//! libgit2 never runs hooks, so a release commit created through
+//! [`crate::core::git::repository::Repository::create_commit`] silently
+//! bypasses husky / pre-commit / lefthook setups. When `[git] run_hooks`
+//! is enabled we shell out to the same hook scripts `git commit` would
+//! run, resolving the directory the same way git does: `core.hooksPath`
+//! (relative paths are relative to the working tree) with a fallback to
+//! `$GIT_DIR/hooks`.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{anyhow, bail, Context};
+use tracing::{debug, info};
+
+use crate::core::errors::Result;
+
+/// Resolve the directory git would look in for hook scripts.
+pub fn hooks_dir(repo: &git2::Repository) -> PathBuf {
+    let configured = repo
+        .config()
+        .ok()
+        .and_then(|cfg| cfg.get_path("core.hooksPath").ok());
+
+    match configured {
+        Some(p) if p.is_absolute() => p,
+        Some(p) => repo.workdir().unwrap_or_else(|| repo.path()).join(p),
+        None => repo.path().join("hooks"),
+    }
+}
+
+/// Locate an executable hook named `name`, if the repository has one.
+/// Non-executable files are ignored, mirroring git's own behaviour
+/// (git prints a hint and carries on).
+pub fn find_hook(repo: &git2::Repository, name: &str) -> Option<PathBuf> {
+    let path = hooks_dir(repo).join(name);
+    if !path.is_file() {
+        return None;
+    }
+
+    if is_executable(&path) {
+        Some(path)
+    } else {
+        debug!("ignoring hook `{}`: file is not executable", path.display());
+        None
+    }
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    std::fs::metadata(path)
+        .map(|m| m.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_executable(_path: &Path) -> bool {
+    true
+}
+
+/// Run the hook `name` with `args` from the working-tree root. Returns
+/// `Ok(false)` when no such hook exists, `Ok(true)` when it ran and
+/// exited zero, and an error carrying the hook's output otherwise.
+pub fn run_hook(repo: &git2::Repository, name: &str, args: &[&str]) -> Result<bool> {
+    let Some(hook) = find_hook(repo, name) else {
+        return Ok(false);
+    };
+
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| anyhow!("cannot run `{}` hook in a bare repository", name))?;
+
+    info!("running {} hook: {}", name, hook.display());
+
+    let output = hook_command(&hook)
+        .args(args)
+        .current_dir(workdir)
+        .env("GIT_DIR", repo.path())
+        .env("GIT_INDEX_FILE", repo.path().join("index"))
+        .output()
+        .with_context(|| format!("failed to spawn `{}` hook `{}`", name, hook.display()))?;
+
+    if !output.status.success() {
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!(
+            "`{}` hook rejected the release commit ({}). stdout: <{}>, stderr: <{}>. \
+             Fix the reported problem, or set `run_hooks = false` under `[git]` in \
+             belaf/config.toml to skip hooks for release commits.",
+            name,
+            output.status,
+            stdout.trim(),
+            stderr.trim()
+        );
+    }
+
+    Ok(true)
+}
+
+#[cfg(unix)]
+fn hook_command(hook: &Path) -> Command {
+    Command::new(hook)
+}
+
+/// Hooks are usually POSIX shell scripts; on Windows run them through
+/// the `sh` that ships with Git for Windows, like `git` itself does.
+#[cfg(not(unix))]
+fn hook_command(hook: &Path) -> Command {
+    let mut cmd = Command::new("sh");
+    cmd.arg(hook);
+    cmd
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn init_repo() -> (tempfile::TempDir, git2::Repository) {
+        let dir = tempfile::TempDir::new().unwrap();
+        let repo = git2::Repository::init(dir.path()).unwrap();
+        (dir, repo)
+    }
+
+    #[test]
+    fn hooks_dir_defaults_to_git_dir() {
+        let (_dir, repo) = init_repo();
+        assert_eq!(hooks_dir(&repo), repo.path().join("hooks"));
+    }
+
+    #[test]
+    fn hooks_dir_honours_relative_hooks_path() {
+        let (_dir, repo) = init_repo();
+        repo.config()
+            .unwrap()
+            .set_str("core.hooksPath", ".husky")
+            .unwrap();
+        assert_eq!(hooks_dir(&repo), repo.workdir().unwrap().join(".husky"));
+    }
+
+    #[test]
+    fn missing_hook_is_not_an_error() {
+        let (_dir, repo) = init_repo();
+        assert!(!run_hook(&repo, "pre-commit", &[]).unwrap());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn failing_hook_surfaces_output() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let (_dir, repo) = init_repo();
+        let hook = hooks_dir(&repo).join("pre-commit");
+        std::fs::create_dir_all(hook.parent().unwrap()).unwrap();
+        std::fs::write(&hook, "#!/bin/sh\necho lint failed >&2\nexit 1\n").unwrap();
+        std::fs::set_permissions(&hook, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let err = run_hook(&repo, "pre-commit", &[]).unwrap_err().to_string();
+        assert!(err.contains("lint failed"), "got: {err}");
+    }
+}
@@ -4,32 +4,75 @@
 //! State of the backing version control repository.
 
 use anyhow::{anyhow, bail, Context};
+use once_cell::unsync::OnceCell;
 use ref_cast::RefCast;
 use serde::{Deserialize, Serialize};
 
 use std::{
     fs::File,
-    io::{BufRead, BufReader},
+    io::{BufRead, BufReader, Read},
     path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
 };
 use thiserror::Error as ThisError;
 use tracing::{info, warn};
-use uuid::Uuid;
 
 use crate::{
     atry,
     core::{
         bump::{extract_scope, ScopeMatcher},
-        config::syntax::RepoConfiguration,
+        config::syntax::{GitBackend, GitConfiguration, ReleaseCommits, RepoConfiguration},
+        deterministic,
         errors::Result,
+        history_bounds::{HistoryBounds, Truncation},
+        project_trailers,
+        release_line::ReleaseLine,
         resolved_release_unit::{DepRequirement, ResolvedReleaseUnit},
+        snapshot,
         tag_format::TagMatcher,
         version::Version,
     },
 };
 
+/// `Name <email> <time> <offset>`, as git writes a signature into a
+/// commit or tag object.
+fn format_signature(sig: &git2::Signature<'_>) -> String {
+    let when = sig.when();
+    let offset = when.offset_minutes();
+    let sign = if offset < 0 { '-' } else { '+' };
+    format!(
+        "{} <{}> {} {sign}{:02}{:02}",
+        String::from_utf8_lossy(sig.name_bytes()),
+        String::from_utf8_lossy(sig.email_bytes()),
+        when.seconds(),
+        offset.abs() / 60,
+        offset.abs() % 60
+    )
+}
+
+/// Credentials for talking to a remote: the SSH agent, or `git_token`
+/// over HTTPS as GitHub App installation tokens expect it.
+pub(crate) fn remote_callbacks(git_token: Option<&str>) -> git2::RemoteCallbacks<'static> {
+    let token = git_token.map(str::to_owned);
+    let mut callbacks = git2::RemoteCallbacks::new();
+    callbacks.credentials(move |_url, username_from_url, allowed_types| {
+        if allowed_types.contains(git2::CredentialType::SSH_KEY) {
+            git2::Cred::ssh_key_from_agent(username_from_url.unwrap_or("git"))
+        } else if allowed_types.contains(git2::CredentialType::USER_PASS_PLAINTEXT) {
+            if let Some(ref token) = token {
+                git2::Cred::userpass_plaintext("x-access-token", token)
+            } else {
+                git2::Cred::default()
+            }
+        } else {
+            git2::Cred::default()
+        }
+    });
+    callbacks
+}
+
 /// Opaque type representing a commit in the repository.
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub struct CommitId(git2::Oid);
 
 impl std::fmt::Display for CommitId {
@@ -44,6 +87,33 @@ impl std::fmt::Display for CommitId {
 #[error("cannot operate on a bare repository")]
 pub struct BareRepositoryError;
 
+/// An error returned when none of the repository's remotes can be identified
+/// as the upstream.
+#[derive(Debug, ThisError)]
+#[error("cannot identify the upstream Git remote")]
+pub struct NoUpstreamError;
+
+/// An error returned when the upstream remote is not one of `[repo]
+/// upstream_urls`, e.g. in a clone whose `origin` points at a fork, so a
+/// push or pull request would land in the wrong repository.
+#[derive(Debug, ThisError)]
+#[error(
+    "the upstream remote `{url}` is not one of `[repo] upstream_urls`, so belaf won't {operation}"
+)]
+pub struct UnexpectedUpstreamError {
+    pub url: String,
+    pub operation: String,
+}
+
+/// Set by `--i-know-what-im-doing`.
+static ANY_UPSTREAM: AtomicBool = AtomicBool::new(false);
+
+/// Let [`Repository::check_upstream`] pass whatever the upstream is, for
+/// this process.
+pub fn allow_any_upstream(allow: bool) {
+    ANY_UPSTREAM.store(allow, Ordering::Relaxed);
+}
+
 /// An error returned when the backing repository is "dirty", i.e. there are
 /// modified files, and this has situation has been deemed unacceptable. The
 /// inner value is one of the culprit paths.
@@ -74,8 +144,31 @@ pub struct Repository {
     /// The name of the "upstream" remote.
     upstream_name: String,
 
+    /// `[repo] upstream_urls`: the remotes pushes may go to.
+    upstream_urls: Vec<String>,
+
     /// Analysis configuration for LRU cache sizes.
     analysis_config: crate::core::config::syntax::AnalysisConfig,
+
+    /// `[git]` settings that control how belaf writes commits.
+    git_config: GitConfiguration,
+
+    /// Directory selected with `--scope`, see [`crate::core::scope`].
+    scope: Option<RepoPathBuf>,
+
+    /// Version line of the maintenance branch being released, see
+    /// [`crate::core::release_line`].
+    release_line: Option<ReleaseLine>,
+
+    /// The commit the repository is read as of, see [`Self::set_as_of`].
+    as_of: Option<git2::Oid>,
+
+    /// Attribute commits by their `Belaf-Projects` trailer first, see
+    /// [`crate::core::project_trailers`].
+    project_trailers: bool,
+
+    /// The `.mailmap`, loaded on first use by [`Self::get_commit_details`].
+    mailmap: OnceCell<git2::Mailmap>,
 }
 
 impl Repository {
@@ -100,10 +193,57 @@ impl Repository {
         Ok(Repository {
             repo,
             upstream_name,
+            upstream_urls: Vec::new(),
             analysis_config: crate::core::config::syntax::AnalysisConfig {
                 commit_cache_size: 512,
                 tree_cache_size: 3,
+                follow_renames: false,
+                max_commits: None,
+                since_date: None,
             },
+            git_config: GitConfiguration::default(),
+            scope: None,
+            release_line: None,
+            as_of: None,
+            project_trailers: false,
+            mailmap: OnceCell::new(),
+        })
+    }
+
+    /// Like [`Self::open_from_env`], for commands that only read. A bare
+    /// repository is read as of its `HEAD` commit, see [`Self::set_as_of`].
+    pub fn open_from_env_read_only() -> Result<Repository> {
+        let git = git2::Repository::open_from_env()?;
+        if !git.is_bare() {
+            return Self::open_from_env();
+        }
+        let head = git
+            .head()
+            .and_then(|head| head.peel_to_commit())
+            .context("the bare repository has no commit at HEAD")?
+            .id();
+        info!(
+            "bare repository: reading the tree of HEAD ({})",
+            &head.to_string()[..8]
+        );
+
+        Ok(Repository {
+            repo: git,
+            upstream_name: "origin".to_owned(),
+            upstream_urls: Vec::new(),
+            analysis_config: crate::core::config::syntax::AnalysisConfig {
+                commit_cache_size: 512,
+                tree_cache_size: 3,
+                follow_renames: false,
+                max_commits: None,
+                since_date: None,
+            },
+            git_config: GitConfiguration::default(),
+            scope: None,
+            release_line: None,
+            as_of: Some(head),
+            project_trailers: false,
+            mailmap: OnceCell::new(),
         })
     }
 
@@ -130,6 +270,9 @@ impl Repository {
             crate::core::config::syntax::AnalysisConfig {
                 commit_cache_size: 512,
                 tree_cache_size: 3,
+                follow_renames: false,
+                max_commits: None,
+                since_date: None,
             },
         )
     }
@@ -150,7 +293,14 @@ impl Repository {
         Ok(Repository {
             repo,
             upstream_name: upstream_name.to_owned(),
+            upstream_urls: Vec::new(),
             analysis_config,
+            git_config: GitConfiguration::default(),
+            scope: None,
+            release_line: None,
+            as_of: None,
+            project_trailers: false,
+            mailmap: OnceCell::new(),
         })
     }
 
@@ -200,10 +350,12 @@ impl Repository {
                 }
             }
 
-            let (name, url) = info.ok_or_else(|| anyhow!("no usable remotes in the Git repo"))?;
+            let (name, url) = info
+                .ok_or(NoUpstreamError)
+                .context("no usable remotes in the Git repo")?;
 
             if n_remotes > 1 && name != "origin" {
-                bail!("no way to choose among multiple Git remotes");
+                return Err(NoUpstreamError).context("no way to choose among multiple Git remotes");
             }
 
             info!("using Git remote `{}` as the upstream", name);
@@ -268,13 +420,91 @@ impl Repository {
         } else if saw_origin {
             "origin".to_owned()
         } else {
-            bail!("cannot identify the upstream Git remote");
+            return Err(NoUpstreamError.into());
         };
 
+        crate::core::history_bounds::HistoryBounds::new(&cfg.analysis)?;
         self.analysis_config = cfg.analysis;
+        self.upstream_urls = cfg.upstream_urls;
         Ok(())
     }
 
+    /// Refuse to `operation` (push, open a pull request) when the
+    /// upstream remote is not one of `[repo] upstream_urls`. https, ssh
+    /// and scp-style spellings of the same repository are the same. An
+    /// empty list, and `--i-know-what-im-doing`, let anything through.
+    pub fn check_upstream(&self, operation: &str) -> Result<()> {
+        if self.upstream_urls.is_empty() || ANY_UPSTREAM.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        let url = self.upstream_url()?;
+        let id = repository_id(&url);
+        if self.upstream_urls.iter().any(|u| repository_id(u) == id) {
+            return Ok(());
+        }
+        Err(UnexpectedUpstreamError {
+            url,
+            operation: operation.to_string(),
+        }
+        .into())
+    }
+
+    /// Apply the `[git]` section of the config file.
+    pub fn apply_git_config(&mut self, cfg: GitConfiguration) {
+        self.git_config = cfg;
+    }
+
+    /// Honour `Belaf-Projects` trailers in history analysis, per
+    /// `[commit_attribution] strategy`.
+    pub fn set_project_trailers(&mut self, enabled: bool) {
+        self.project_trailers = enabled;
+    }
+
+    /// Whether history analysis honours `Belaf-Projects` trailers.
+    pub fn project_trailers(&self) -> bool {
+        self.project_trailers
+    }
+
+    /// `[git] release_commits`.
+    pub fn release_commits(&self) -> ReleaseCommits {
+        self.git_config.release_commits
+    }
+
+    /// Restrict discovery and history analysis to `scope`.
+    pub fn set_scope(&mut self, scope: RepoPathBuf) {
+        self.scope = Some(scope);
+    }
+
+    /// The directory selected with `--scope`, if any.
+    pub fn scope(&self) -> Option<&RepoPath> {
+        self.scope.as_deref()
+    }
+
+    /// Only consider release tags on `line`.
+    pub fn set_release_line(&mut self, line: ReleaseLine) {
+        self.release_line = Some(line);
+    }
+
+    /// The maintenance branch's version line, if one was selected.
+    pub fn release_line(&self) -> Option<ReleaseLine> {
+        self.release_line
+    }
+
+    /// Read the repository as it was at `commit`: files come from its
+    /// tree instead of the working tree (see [`Self::open_file`]), and
+    /// only release tags on its history count, the ones a release made
+    /// then could have seen. Nothing is checked out. See `belaf status
+    /// --at`.
+    pub fn set_as_of(&mut self, commit: CommitId) {
+        self.as_of = Some(commit.0);
+    }
+
+    /// The commit set with [`Self::set_as_of`], or `HEAD` of a bare
+    /// repository.
+    pub fn as_of(&self) -> Option<CommitId> {
+        self.as_of.map(CommitId)
+    }
+
     /// Get the URL of the upstream repository.
     pub fn upstream_url(&self) -> Result<String> {
         let upstream = self.repo.find_remote(&self.upstream_name)?;
@@ -308,6 +538,50 @@ impl Repository {
         })
     }
 
+    /// The full SHA of the commit `HEAD` points at.
+    pub fn head_commit_sha(&self) -> Result<String> {
+        Ok(self.repo.head()?.peel_to_commit()?.id().to_string())
+    }
+
+    /// The summary line of commit `sha`, or `None` when it isn't in the
+    /// local repository (e.g. in a shallow clone).
+    pub fn commit_summary_of(&self, sha: &str) -> Option<String> {
+        let oid = git2::Oid::from_str(sha).ok()?;
+        let commit = self.repo.find_commit(oid).ok()?;
+        commit.summary().map(str::to_owned)
+    }
+
+    /// The full message of commit `sha`; see [`Self::commit_summary_of`].
+    pub fn commit_message_of(&self, sha: &str) -> Option<String> {
+        let oid = git2::Oid::from_str(sha).ok()?;
+        let commit = self.repo.find_commit(oid).ok()?;
+        commit.message().map(str::to_owned)
+    }
+
+    /// The branch a release from a detached HEAD is for: `[git]
+    /// base_branch`, else the branch `GITHUB_REF` names. `None` when
+    /// neither says, or `GITHUB_REF` is a tag or pull request ref.
+    pub fn detached_base_branch(&self) -> Option<String> {
+        if let Some(branch) = &self.git_config.base_branch {
+            return Some(branch.clone());
+        }
+        crate::core::env::maybe_var("GITHUB_REF")
+            .ok()
+            .flatten()
+            .and_then(|r| r.strip_prefix("refs/heads/").map(str::to_owned))
+    }
+
+    /// Resolve a commit-ish (a SHA, a unique prefix of one, a branch, a
+    /// tag, `HEAD~2`, ...) to the commit it names.
+    pub fn resolve_commitish(&self, rev: &str) -> Result<CommitId> {
+        let commit = self
+            .repo
+            .revparse_single(rev)
+            .and_then(|obj| obj.peel_to_commit())
+            .with_context(|| format!("`{rev}` does not name a commit"))?;
+        Ok(CommitId(commit.id()))
+    }
+
     /// Parse a textual reference to a commit within the repository.
     pub fn parse_history_ref<T: AsRef<str>>(&self, text: T) -> Result<ParsedHistoryRef> {
         let text = text.as_ref();
@@ -385,17 +659,131 @@ impl Repository {
     }
 
     /// Resolve a `RepoPath` repository path to a filesystem path in the working
-    /// directory.
+    /// directory. A bare repository has none, so there the path stays
+    /// relative; it only names the file in messages, as files are read
+    /// with [`Self::open_file`].
     pub fn resolve_workdir(&self, p: &RepoPath) -> PathBuf {
-        let mut fullpath = self
-            .repo
-            .workdir()
-            .expect("BUG: workdir() should never be None as bare repos are rejected at open()")
-            .to_owned();
+        let mut fullpath = self.repo.workdir().unwrap_or(Path::new("")).to_owned();
         fullpath.push(p.as_path());
         fullpath
     }
 
+    /// Open the file at `p` for reading: from the working tree, or from
+    /// the tree of the commit the repository is read as of.
+    pub fn open_file(&self, p: &RepoPath) -> std::io::Result<RepoFile> {
+        match self.as_of {
+            None => File::open(self.resolve_workdir(p)).map(RepoFile::Worktree),
+            Some(_) => match self.tree_entry(p)? {
+                Some(entry) if entry.kind() == Some(git2::ObjectType::Blob) => {
+                    let blob = self
+                        .repo
+                        .find_blob(entry.id())
+                        .map_err(std::io::Error::other)?;
+                    Ok(RepoFile::Blob(std::io::Cursor::new(
+                        blob.content().to_vec(),
+                    )))
+                }
+                Some(_) => Err(std::io::Error::other(format!(
+                    "`{}` is not a file",
+                    p.escaped()
+                ))),
+                None => Err(std::io::ErrorKind::NotFound.into()),
+            },
+        }
+    }
+
+    /// The text of the file at `p`, read as [`Self::open_file`] does.
+    pub fn read_to_string(&self, p: &RepoPath) -> std::io::Result<String> {
+        let mut text = String::new();
+        self.open_file(p)?.read_to_string(&mut text)?;
+        Ok(text)
+    }
+
+    /// Whether `p` is a file, in the working tree or the tree read.
+    pub fn is_file(&self, p: &RepoPath) -> bool {
+        match self.as_of {
+            None => self.resolve_workdir(p).is_file(),
+            Some(_) => matches!(
+                self.tree_entry(p),
+                Ok(Some(entry)) if entry.kind() == Some(git2::ObjectType::Blob)
+            ),
+        }
+    }
+
+    /// Whether `p` is a directory, in the working tree or the tree read.
+    pub fn is_dir(&self, p: &RepoPath) -> bool {
+        match self.as_of {
+            None => self.resolve_workdir(p).is_dir(),
+            Some(_) => {
+                p.is_empty()
+                    || matches!(
+                        self.tree_entry(p),
+                        Ok(Some(entry)) if entry.kind() == Some(git2::ObjectType::Tree)
+                    )
+            }
+        }
+    }
+
+    /// The entries of directory `p`, as repository paths in no
+    /// particular order.
+    pub fn list_dir(&self, p: &RepoPath) -> std::io::Result<Vec<RepoPathBuf>> {
+        let child = |name: &[u8]| {
+            let mut path = p.to_owned();
+            path.push(name);
+            path
+        };
+        let Some(oid) = self.as_of else {
+            return std::fs::read_dir(self.resolve_workdir(p))?
+                .map(|entry| Ok(child(entry?.file_name().as_encoded_bytes())))
+                .collect();
+        };
+        let tree = if p.is_empty() {
+            self.tree_of(oid)?
+        } else {
+            match self.tree_entry(p)? {
+                Some(entry) if entry.kind() == Some(git2::ObjectType::Tree) => self
+                    .repo
+                    .find_tree(entry.id())
+                    .map_err(std::io::Error::other)?,
+                _ => return Err(std::io::ErrorKind::NotFound.into()),
+            }
+        };
+        Ok(tree.iter().map(|entry| child(entry.name_bytes())).collect())
+    }
+
+    /// The blob id of the file at `p`, as git would hash it. `None` if
+    /// there is no such file.
+    pub fn blob_id(&self, p: &RepoPath) -> Option<git2::Oid> {
+        match self.as_of {
+            None => git2::Oid::hash_file(git2::ObjectType::Blob, self.resolve_workdir(p)).ok(),
+            Some(_) => self
+                .tree_entry(p)
+                .ok()
+                .flatten()
+                .filter(|entry| entry.kind() == Some(git2::ObjectType::Blob))
+                .map(|entry| entry.id()),
+        }
+    }
+
+    fn tree_of(&self, commit: git2::Oid) -> std::io::Result<git2::Tree<'_>> {
+        self.repo
+            .find_commit(commit)
+            .and_then(|commit| commit.tree())
+            .map_err(std::io::Error::other)
+    }
+
+    /// The entry at `p` of the tree read, if there is one.
+    fn tree_entry(&self, p: &RepoPath) -> std::io::Result<Option<git2::TreeEntry<'static>>> {
+        let Some(oid) = self.as_of else {
+            return Ok(None);
+        };
+        match self.tree_of(oid)?.get_path(p.pop_sep().as_path()) {
+            Ok(entry) => Ok(Some(entry.to_owned())),
+            Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+            Err(e) => Err(std::io::Error::other(e)),
+        }
+    }
+
     /// Resolve the path to the per-repository configuration directory.
     pub fn resolve_config_dir(&self) -> PathBuf {
         self.resolve_workdir(RepoPath::new(b"belaf"))
@@ -422,7 +810,8 @@ impl Repository {
         RepoPathBuf::from_path(rel)
     }
 
-    /// Scan the paths in the repository index.
+    /// Scan the paths in the repository index, or in the tree of the
+    /// commit the repository is read as of.
     pub fn scan_paths<F>(&self, mut f: F) -> Result<()>
     where
         F: FnMut(&RepoPath) -> Result<()>,
@@ -436,17 +825,36 @@ impl Repository {
         Ok(index.len())
     }
 
+    /// The paths of the repository index or, when reading as of a
+    /// commit, of its tree.
+    fn index_paths(&self) -> Result<Vec<Vec<u8>>> {
+        let Some(oid) = self.as_of else {
+            return Ok(self.repo.index()?.iter().map(|entry| entry.path).collect());
+        };
+        let mut paths = Vec::new();
+        self.tree_of(oid)?
+            .walk(git2::TreeWalkMode::PreOrder, |root, entry| {
+                if entry.kind() != Some(git2::ObjectType::Tree) {
+                    let mut path = root.as_bytes().to_vec();
+                    path.extend_from_slice(entry.name_bytes());
+                    paths.push(path);
+                }
+                git2::TreeWalkResult::Ok
+            })?;
+        Ok(paths)
+    }
+
     /// Scan the paths in the repository index with progress information.
     /// The callback receives: (path, current_index, total_count)
     pub fn scan_paths_with_progress<F>(&self, mut f: F) -> Result<()>
     where
         F: FnMut(&RepoPath, usize, usize) -> Result<()>,
     {
-        let index = self.repo.index()?;
-        let total = index.len();
+        let paths = self.index_paths()?;
+        let total = paths.len();
 
-        for (i, entry) in index.iter().enumerate() {
-            let p = RepoPath::new(&entry.path);
+        for (i, path) in paths.iter().enumerate() {
+            let p = RepoPath::new(path);
             atry!(
                 f(p, i, total);
                 ["encountered a problem while scanning repository entry `{}`", p.escaped()]
@@ -532,6 +940,146 @@ impl Repository {
         Ok(Some(blob.content().to_owned()))
     }
 
+    /// The commit that introduced each line of `path` as committed at
+    /// HEAD, in line order. Empty if the file isn't committed; lines that
+    /// only exist in the working tree have no entry.
+    pub fn line_commits(&self, path: &RepoPath) -> Result<Vec<CommitId>> {
+        let blame = match self.repo.blame_file(path.as_path(), None) {
+            Ok(b) => b,
+            Err(e) if e.code() == git2::ErrorCode::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let mut commits = Vec::new();
+        for hunk in blame.iter() {
+            let cid = CommitId(hunk.final_commit_id());
+            commits.extend(std::iter::repeat_n(cid, hunk.lines_in_hunk()));
+        }
+        Ok(commits)
+    }
+
+    /// Files under `dir` that the tip commit of the upstream remote-tracking
+    /// branch `branch` added, relative to its first parent, as
+    /// `(file name, content)`. Empty if the branch has not been fetched.
+    /// Release branches carry exactly one commit, so this yields the
+    /// release manifest it introduced.
+    pub fn files_added_on_upstream_branch(
+        &self,
+        branch: &str,
+        dir: &str,
+    ) -> Result<Vec<(String, Vec<u8>)>> {
+        let refname = format!("refs/remotes/{}/{}", self.upstream_name, branch);
+        let commit = match self.repo.find_reference(&refname) {
+            Ok(r) => r.peel_to_commit()?,
+            Err(e) if e.code() == git2::ErrorCode::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let subtree = |commit: &git2::Commit<'_>| -> Result<Option<git2::Tree<'_>>> {
+            match commit.tree()?.get_path(Path::new(dir)) {
+                Ok(entry) => Ok(entry.to_object(&self.repo)?.into_tree().ok()),
+                Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+                Err(e) => Err(e.into()),
+            }
+        };
+
+        let Some(tree) = subtree(&commit)? else {
+            return Ok(Vec::new());
+        };
+        let parent_tree = match commit.parent(0) {
+            Ok(parent) => subtree(&parent)?,
+            Err(_) => None,
+        };
+
+        let mut added = Vec::new();
+        for entry in tree.iter() {
+            let Some(name) = entry.name() else {
+                continue;
+            };
+            if parent_tree
+                .as_ref()
+                .is_some_and(|t| t.get_name(name).is_some())
+            {
+                continue;
+            }
+            if let Ok(blob) = entry.to_object(&self.repo)?.into_blob() {
+                added.push((name.to_owned(), blob.content().to_owned()));
+            }
+        }
+        Ok(added)
+    }
+
+    /// Paths touched by the commits on HEAD since it diverged from `base`
+    /// (any revspec, e.g. `origin/main`), along with that merge base.
+    ///
+    /// Commits are diffed against their first parent the same way
+    /// [`Self::analyze_histories`] attributes them, merges included only
+    /// through the commits they bring in. Uncommitted changes don't count.
+    pub fn paths_changed_since(&self, base: &str) -> Result<(CommitId, Vec<RepoPathBuf>)> {
+        let (merge_base, walk) = self.walk_since(base)?;
+
+        let mut dopts = git2::DiffOptions::new();
+        dopts.include_typechange(true);
+
+        let mut paths = std::collections::BTreeSet::new();
+        for oid in walk {
+            let commit = self.repo.find_commit(oid?)?;
+            if commit.parent_count() > 1 {
+                continue;
+            }
+            let parent_tree = match commit.parent(0) {
+                Ok(parent) => Some(parent.tree()?),
+                Err(_) => None,
+            };
+            let diff = self.repo.diff_tree_to_tree(
+                parent_tree.as_ref(),
+                Some(&commit.tree()?),
+                Some(&mut dopts),
+            )?;
+            for delta in diff.deltas() {
+                for file in &[delta.old_file(), delta.new_file()] {
+                    if let Some(path_bytes) = file.path_bytes() {
+                        paths.insert(path_bytes.to_vec());
+                    }
+                }
+            }
+        }
+
+        Ok((
+            CommitId(merge_base),
+            paths.iter().map(|p| RepoPathBuf::new(p)).collect(),
+        ))
+    }
+
+    /// The commits on HEAD since it diverged from `base`, newest first,
+    /// along with that merge base; see [`Self::paths_changed_since`].
+    pub fn commits_since(&self, base: &str) -> Result<(CommitId, Vec<CommitId>)> {
+        let (merge_base, walk) = self.walk_since(base)?;
+        let commits = walk
+            .map(|oid| oid.map(CommitId))
+            .collect::<std::result::Result<_, _>>()?;
+        Ok((CommitId(merge_base), commits))
+    }
+
+    /// The merge base of HEAD and `base`, and a walk over the commits
+    /// between the two.
+    fn walk_since(&self, base: &str) -> Result<(git2::Oid, git2::Revwalk<'_>)> {
+        let base_commit = self
+            .repo
+            .revparse_single(base)
+            .and_then(|obj| obj.peel_to_commit())
+            .with_context(|| format!("cannot resolve base ref `{base}`"))?;
+        let head = self.repo.head()?.peel_to_commit()?;
+        let merge_base = self
+            .repo
+            .merge_base(base_commit.id(), head.id())
+            .with_context(|| format!("`{base}` and HEAD have no common ancestor"))?;
+
+        let mut walk = self.repo.revwalk()?;
+        walk.push(head.id())?;
+        walk.hide(merge_base)?;
+        Ok((merge_base, walk))
+    }
+
     /// Get a ReleaseCommitInfo corresponding to the project's history before
     /// Belaf. Always empty in 3.0 — the per-project release history is
     /// derived from git tags + the `belaf-baseline` tag.
@@ -554,6 +1102,10 @@ impl Repository {
     ///
     /// For multi-project repos, only matches prefixed tags to avoid ambiguity.
     ///
+    /// With a [`ReleaseLine`] selected, tags for versions off the line
+    /// are ignored, so a `release/1.x` branch finds the latest `1.x`
+    /// release even after `2.0.0` has been tagged.
+    ///
     /// Returns the commit OID, tag name, and parsed version of the
     /// latest matching tag, sorted by semantic version (highest first).
     ///
@@ -572,14 +1124,35 @@ impl Repository {
         &self,
         matcher: &TagMatcher,
     ) -> Result<Option<(git2::Oid, String, semver::Version)>> {
+        let mut matching_tags = self.release_tags_for_project(matcher)?;
+        matching_tags.sort_by(|a, b| b.2.cmp(&a.2));
+        Ok(matching_tags.into_iter().next())
+    }
+
+    /// Every tag `matcher` recognises, within the release line if one is
+    /// set: the commit it points at, its name and its version. Unordered.
+    fn release_tags_for_project(
+        &self,
+        matcher: &TagMatcher,
+    ) -> Result<Vec<(git2::Oid, String, semver::Version)>> {
         let tags = self.repo.tag_names(None)?;
 
         let mut matching_tags: Vec<(git2::Oid, String, semver::Version)> = Vec::new();
 
         for tag_name in tags.iter().flatten() {
+            // A snapshot's tag names a version that was never released.
+            if snapshot::release_tag(tag_name).is_some() {
+                continue;
+            }
             let Some(version) = matcher.match_version(tag_name) else {
                 continue;
             };
+            if self
+                .release_line
+                .is_some_and(|line| !line.contains(&version))
+            {
+                continue;
+            }
             let Ok(tag_ref) = self.repo.find_reference(&format!("refs/tags/{}", tag_name)) else {
                 continue;
             };
@@ -590,15 +1163,18 @@ impl Repository {
             } else {
                 continue;
             };
+            if let Some(at) = self.as_of {
+                let Ok(tagged) = tag_ref.peel_to_commit() else {
+                    continue;
+                };
+                if tagged.id() != at && !self.repo.graph_descendant_of(at, tagged.id())? {
+                    continue;
+                }
+            }
             matching_tags.push((oid, tag_name.to_string(), version));
         }
 
-        if matching_tags.is_empty() {
-            return Ok(None);
-        }
-
-        matching_tags.sort_by(|a, b| b.2.cmp(&a.2));
-        Ok(matching_tags.into_iter().next())
+        Ok(matching_tags)
     }
 
     /// Parse a semantic version from a tag name.
@@ -633,7 +1209,7 @@ impl Repository {
         let tags = self.repo.tag_names(None)?;
         let re = regex::Regex::new(r"\d+\.\d+\.\d+").expect("BUG: literal regex compiles");
         for tag_name in tags.iter().flatten() {
-            if re.is_match(tag_name) {
+            if re.is_match(tag_name) && snapshot::release_tag(tag_name).is_none() {
                 return Ok(true);
             }
         }
@@ -660,6 +1236,139 @@ impl Repository {
         self.repo.find_reference(&refs_tag).is_ok()
     }
 
+    /// Names of every tag in the local repository.
+    pub fn tag_names(&self) -> Result<Vec<String>> {
+        let tags = self.repo.tag_names(None)?;
+        Ok(tags.iter().flatten().map(str::to_owned).collect())
+    }
+
+    /// The submodules of the working tree, as `.gitmodules` lists them.
+    pub fn submodules(&self) -> Result<Vec<Submodule>> {
+        let mut submodules = Vec::new();
+        for sub in self.repo.submodules()? {
+            let path = sub
+                .path()
+                .to_str()
+                .ok_or_else(|| anyhow!("submodule path `{}` is not UTF-8", sub.path().display()))?;
+            submodules.push(Submodule {
+                name: sub.name().unwrap_or(path).to_owned(),
+                path: RepoPathBuf::new(path.as_bytes()),
+                url: sub.url().map(str::to_owned),
+                pinned: sub.index_id().map(CommitId),
+            });
+        }
+        Ok(submodules)
+    }
+
+    fn open_submodule(&self, path: &RepoPath) -> Result<git2::Repository> {
+        git2::Repository::open(self.resolve_workdir(path)).with_context(|| {
+            format!(
+                "submodule `{}` is not checked out; run `git submodule update --init`",
+                path.escaped()
+            )
+        })
+    }
+
+    /// The tags `matcher` recognises in the checkout of the submodule at
+    /// `path`, newest version first. Only tags the checkout already has
+    /// are seen; nothing is fetched.
+    pub fn submodule_release_tags(
+        &self,
+        path: &RepoPath,
+        matcher: &TagMatcher,
+    ) -> Result<Vec<SubmoduleTag>> {
+        let sub = self.open_submodule(path)?;
+        let mut tags = Vec::new();
+        for name in sub.tag_names(None)?.iter().flatten() {
+            let Some(version) = matcher.match_version(name) else {
+                continue;
+            };
+            let Ok(commit) = sub
+                .find_reference(&format!("refs/tags/{name}"))
+                .and_then(|r| r.peel_to_commit())
+            else {
+                continue;
+            };
+            tags.push(SubmoduleTag {
+                name: name.to_owned(),
+                version,
+                commit: CommitId(commit.id()),
+            });
+        }
+        tags.sort_by(|a, b| b.version.cmp(&a.version));
+        Ok(tags)
+    }
+
+    /// Whether `commit` is `ancestor` or a descendant of it, in the
+    /// submodule at `path`.
+    pub fn submodule_contains(
+        &self,
+        path: &RepoPath,
+        commit: CommitId,
+        ancestor: CommitId,
+    ) -> Result<bool> {
+        let sub = self.open_submodule(path)?;
+        Ok(commit == ancestor || sub.graph_descendant_of(commit.0, ancestor.0)?)
+    }
+
+    /// Check out `commit` in the submodule at `path` with a detached
+    /// HEAD, so that the superproject sees it moved there.
+    pub fn checkout_submodule(&self, path: &RepoPath, commit: CommitId) -> Result<()> {
+        let sub = self.open_submodule(path)?;
+        let target = sub.find_commit(commit.0)?;
+        sub.checkout_tree(
+            target.as_object(),
+            Some(git2::build::CheckoutBuilder::new().safe()),
+        )
+        .with_context(|| {
+            format!(
+                "failed to check out {commit} in submodule `{}`",
+                path.escaped()
+            )
+        })?;
+        sub.set_head_detached(commit.0)?;
+        Ok(())
+    }
+
+    /// Names of every tag on the upstream remote, as `git ls-remote
+    /// --tags` lists them. Unlike [`Self::fetch_tags`] this changes no
+    /// local refs, so it also sees tags pushed since the last fetch
+    /// without overwriting anything. Credentials are those of
+    /// [`remote_callbacks`].
+    pub fn remote_tag_names(&self, git_token: Option<&str>) -> Result<Vec<String>> {
+        let mut remote = self
+            .repo
+            .find_remote(&self.upstream_name)
+            .with_context(|| format!("cannot find upstream remote `{}`", self.upstream_name))?;
+
+        let connection = remote
+            .connect_auth(
+                git2::Direction::Fetch,
+                Some(remote_callbacks(git_token)),
+                None,
+            )
+            .with_context(|| {
+                format!(
+                    "failed to list tags on `{}` — check network connectivity and \
+                     that you have credentials for the remote (SSH agent or git \
+                     token). Set BELAF_NO_FETCH=1 to skip.",
+                    self.upstream_name
+                )
+            })?;
+
+        let mut names: Vec<String> = connection
+            .list()?
+            .iter()
+            .filter_map(|head| head.name().strip_prefix("refs/tags/"))
+            // Annotated tags are listed twice; `^{}` is the peeled commit.
+            .filter(|name| !name.ends_with("^{}"))
+            .map(str::to_owned)
+            .collect();
+        names.sort();
+        names.dedup();
+        Ok(names)
+    }
+
     pub fn create_baseline_tag(&self) -> Result<()> {
         let head = self.repo.head()?;
         let target_oid = head.target().context("HEAD has no target")?;
@@ -681,6 +1390,142 @@ impl Repository {
         }
     }
 
+    /// Create the annotated tag `name` on `target` (any revision git
+    /// understands) with `message`. Fails if the tag exists.
+    ///
+    /// With `sign` the tag is signed like `git tag -s`: the tag object is
+    /// passed to `gpg.program` (default `gpg`) with `user.signingkey`, or
+    /// the tagger's email, as the key, and the signature is appended to
+    /// the message. Only OpenPGP signing (`gpg.format = openpgp`) is
+    /// supported.
+    pub fn create_annotated_tag(
+        &self,
+        name: &str,
+        target: &str,
+        message: &str,
+        sign: bool,
+    ) -> Result<()> {
+        let object = self
+            .repo
+            .revparse_single(target)
+            .with_context(|| format!("cannot resolve `{target}` to tag it as `{name}`"))?;
+        let tagger = self.get_signature()?;
+        let mut message = format!("{}\n", message.trim_end());
+
+        if sign {
+            let kind = object.kind().map(|k| k.str()).unwrap_or("commit");
+            let payload = format!(
+                "object {}\ntype {kind}\ntag {name}\ntagger {}\n\n{message}",
+                object.id(),
+                format_signature(&tagger)
+            );
+            message.push_str(&self.gpg_sign(&payload, &tagger)?);
+        }
+
+        self.repo
+            .tag(name, &object, &tagger, &message, false)
+            .with_context(|| format!("failed to create tag `{name}`"))?;
+        info!("created annotated tag `{name}`");
+        Ok(())
+    }
+
+    /// Whether `tag.gpgSign` asks for signed tags.
+    pub fn signs_tags(&self) -> bool {
+        self.repo
+            .config()
+            .and_then(|c| c.get_bool("tag.gpgSign"))
+            .unwrap_or(false)
+    }
+
+    /// Whether the tag `name` is signed and, if so, whether `git
+    /// verify-tag` accepts the signature. git picks the tool from the
+    /// signature, so OpenPGP, SSH and X.509 signatures all work as
+    /// configured for git.
+    pub fn verify_tag_signature(&self, name: &str) -> Result<TagSignature> {
+        let reference = self
+            .repo
+            .find_reference(&format!("refs/tags/{name}"))
+            .with_context(|| format!("no tag `{name}`"))?;
+        let Ok(tag) = reference.peel_to_tag() else {
+            return Ok(TagSignature::Lightweight);
+        };
+        if !String::from_utf8_lossy(tag.message_bytes().unwrap_or_default()).contains("-----BEGIN ")
+        {
+            return Ok(TagSignature::Unsigned);
+        }
+
+        let workdir = self
+            .repo
+            .workdir()
+            .ok_or_else(|| anyhow!("cannot verify tags in a bare repository"))?;
+        let output = std::process::Command::new("git")
+            .args(["verify-tag", name])
+            .current_dir(workdir)
+            .output()
+            .context("failed to invoke `git verify-tag` — is git installed and on PATH?")?;
+        let report = String::from_utf8_lossy(&output.stderr).trim().to_owned();
+        Ok(if output.status.success() {
+            TagSignature::Good(
+                report
+                    .lines()
+                    .find(|l| l.contains("Good "))
+                    .map(|l| l.trim_start_matches("gpg: ").to_owned())
+                    .unwrap_or(report),
+            )
+        } else {
+            TagSignature::Bad(report)
+        })
+    }
+
+    /// A detached, armored signature of `payload`; see
+    /// [`Self::create_annotated_tag`].
+    fn gpg_sign(&self, payload: &str, tagger: &git2::Signature<'_>) -> Result<String> {
+        use std::io::Write;
+
+        let config = self.repo.config()?;
+        let format = config.get_string("gpg.format").unwrap_or_default();
+        if !format.is_empty() && format != "openpgp" {
+            bail!("signing tags with `gpg.format = {format}` is not supported; use openpgp");
+        }
+        let program = config
+            .get_string("gpg.openpgp.program")
+            .or_else(|_| config.get_string("gpg.program"))
+            .unwrap_or_else(|_| "gpg".to_owned());
+        let key = config
+            .get_string("user.signingkey")
+            .ok()
+            .or_else(|| tagger.email().map(str::to_owned))
+            .context("no `user.signingkey` configured to sign the tag with")?;
+
+        let mut child = std::process::Command::new(&program)
+            .args(["--status-fd=2", "-bsau", &key])
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn()
+            .with_context(|| format!("failed to run `{program}` to sign the tag"))?;
+        child
+            .stdin
+            .take()
+            .context("BUG: the signing program's stdin is piped")?
+            .write_all(payload.as_bytes())?;
+        let output = child.wait_with_output()?;
+        let signature = String::from_utf8_lossy(&output.stdout).into_owned();
+        if !output.status.success() || !signature.contains("-----BEGIN PGP SIGNATURE-----") {
+            bail!(
+                "`{program}` failed to sign the tag with key `{key}`: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(signature)
+    }
+
+    /// Stage `files` and commit them on top of HEAD.
+    ///
+    /// With `[git] run_hooks = true` the repository's `pre-commit` and
+    /// `commit-msg` hooks run around the commit exactly as `git commit`
+    /// would run them (see [`crate::core::git::hooks`]); otherwise the
+    /// commit is written like `git commit --no-verify`.
     pub fn create_commit(&self, message: &str, files: &[&RepoPath]) -> Result<()> {
         let mut index = self.repo.index()?;
 
@@ -689,6 +1534,26 @@ impl Repository {
         }
 
         index.write()?;
+
+        let mut message = message.to_owned();
+
+        if self.git_config.run_hooks {
+            crate::core::git::hooks::run_hook(&self.repo, "pre-commit", &[])?;
+
+            // pre-commit hooks (lint-staged, formatters) may re-stage
+            // files, so pick up whatever they left in the on-disk index.
+            index.read(true)?;
+
+            let msg_path = self.repo.path().join("COMMIT_EDITMSG");
+            std::fs::write(&msg_path, &message)
+                .with_context(|| format!("failed to write `{}`", msg_path.display()))?;
+            let msg_arg = msg_path.to_string_lossy().into_owned();
+            if crate::core::git::hooks::run_hook(&self.repo, "commit-msg", &[&msg_arg])? {
+                message = std::fs::read_to_string(&msg_path)
+                    .with_context(|| format!("failed to read `{}`", msg_path.display()))?;
+            }
+        }
+
         let tree_id = index.write_tree()?;
         let tree = self.repo.find_tree(tree_id)?;
 
@@ -699,7 +1564,7 @@ impl Repository {
             Some("HEAD"),
             &signature,
             &signature,
-            message,
+            &message,
             &tree,
             &[&parent_commit],
         )?;
@@ -708,6 +1573,52 @@ impl Repository {
         Ok(())
     }
 
+    /// Commit the working tree's `files` on top of HEAD without touching
+    /// the index or moving any branch, e.g. for a snapshot. The commit is
+    /// only reachable through what the caller points at it, such as a tag.
+    pub fn create_detached_commit(&self, message: &str, files: &[&RepoPath]) -> Result<CommitId> {
+        let parent_commit = self.repo.head()?.peel_to_commit()?;
+        let mut index = git2::Index::new()?;
+        index.read_tree(&parent_commit.tree()?)?;
+
+        for file in files {
+            let path = self.resolve_workdir(file);
+            let data = std::fs::read(&path)
+                .with_context(|| format!("failed to read `{}`", path.display()))?;
+            let mode = index
+                .get_path(std::path::Path::new(std::str::from_utf8(&file.0)?), 0)
+                .map_or(0o100644, |entry| entry.mode);
+            let entry = git2::IndexEntry {
+                ctime: git2::IndexTime::new(0, 0),
+                mtime: git2::IndexTime::new(0, 0),
+                dev: 0,
+                ino: 0,
+                mode,
+                uid: 0,
+                gid: 0,
+                file_size: data.len() as u32,
+                id: self.repo.blob(&data)?,
+                flags: 0,
+                flags_extended: 0,
+                path: file.0.to_vec(),
+            };
+            index.add(&entry)?;
+        }
+
+        let tree = self.repo.find_tree(index.write_tree_to(&self.repo)?)?;
+        let signature = self.repo.signature()?;
+        let oid = self.repo.commit(
+            None,
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &[&parent_commit],
+        )?;
+        info!("created detached commit {}: {}", oid, message);
+        Ok(CommitId(oid))
+    }
+
     /// Figure out which commits in the history affect each project since its
     /// last release.
     ///
@@ -733,6 +1644,7 @@ impl Repository {
             RepoHistory {
                 commits: Vec::new(),
                 boundary: None,
+                truncation: None,
             };
             projects.len()
         ];
@@ -742,9 +1654,7 @@ impl Repository {
 
         for (i, unit) in projects.iter().enumerate() {
             let matcher = &matchers[i];
-            if let Some((tag_oid, tag_name, version)) =
-                self.find_latest_tag_for_project(matcher)?
-            {
+            if let Some((tag_oid, tag_name, version)) = self.find_latest_tag_for_project(matcher)? {
                 info!(
                     "found release tag for {}: {} (v{}) via template `{}`",
                     unit.user_facing_name,
@@ -778,7 +1688,8 @@ impl Repository {
                      but this repo already has version-shaped tags. \
                      Refusing to analyze the full history — that would over-count old commits and inflate the bump. \
                      Likely causes: (1) the project's `tag_format` in `belaf/config.toml` doesn't match how previous tags were written; \
-                     (2) the project is genuinely new — in that case, create a baseline with `git tag belaf-baseline <commit>` to mark the starting point. \
+                     (2) the project is genuinely new — in that case, create a baseline with `git tag belaf-baseline <commit>` to mark the starting point; \
+                     (3) the project was renamed — list its old names in `previous_names = [\"...\"]` on its `[release_unit.<name>]` block. \
                      Override-only path: set `tag_format = \"...\"` on the `[release_unit.<name>]` block to match the existing tag shape.",
                     name = unit.user_facing_name,
                     tmpl = matcher.template(),
@@ -796,27 +1707,66 @@ impl Repository {
         let tree_cache_size = std::num::NonZeroUsize::new(self.analysis_config.tree_cache_size)
             .unwrap_or(std::num::NonZeroUsize::new(3).expect("BUG: 3 is non-zero"));
 
+        let bounds = HistoryBounds::new(&self.analysis_config)?;
+
         let mut commit_data = lru::LruCache::new(commit_cache_size);
         let mut trees = lru::LruCache::new(tree_cache_size);
 
         let mut dopts = git2::DiffOptions::new();
         dopts.include_typechange(true);
 
+        // Under `--scope` the graph only holds the scoped units, so the
+        // diffs can skip every path none of them owns. Not when following
+        // renames: a move into a unit has its other half outside it.
+        let follow_renames = self.analysis_config.follow_renames;
+        if self.scope.is_some()
+            && !follow_renames
+            && projects.iter().all(|p| !p.prefix().is_empty())
+        {
+            for unit in projects {
+                dopts.pathspec(unit.prefix().pop_sep().as_ref().to_vec());
+            }
+        }
+
         let project_names: Vec<String> = projects
             .iter()
             .map(|p| p.user_facing_name.clone())
             .collect();
         let scope_matcher = ScopeMatcher::default();
 
+        // Each unit's directory, plus the ones it was moved from that the
+        // walk has found so far; see `moved_prefixes`. A unit at the root
+        // owns every path anyway.
+        let mut prefixes: Vec<Vec<Vec<u8>>> = projects
+            .iter()
+            .map(|p| {
+                let prefix = p.prefix().as_ref();
+                if prefix.is_empty() {
+                    Vec::new()
+                } else {
+                    vec![prefix.to_vec()]
+                }
+            })
+            .collect();
+
         // note that we don't "know" that unit_idx = project.ident
         for unit_idx in 0..projects.len() {
             let mut walk = self.repo.revwalk()?;
-            walk.push_head()?;
+            match self.as_of {
+                Some(oid) => walk.push(oid)?,
+                None => walk.push_head()?,
+            }
 
             if let Some(boundary_commit) = histories[unit_idx].boundary_commit() {
                 walk.hide(boundary_commit.0)?;
             }
 
+            // `since_date` stops at the first older commit, so that had
+            // better come after all the newer ones.
+            if bounds.since.is_some() {
+                walk.set_sorting(git2::Sort::TIME)?;
+            }
+
             // Walk through the history, finding relevant commits. The full
             // codepath loads up trees for each commit and its parents, computes
             // the diff, and compares that against the path-matchers for each
@@ -824,9 +1774,21 @@ impl Repository {
             // intention is that the LRU caches will make it so that little
             // redundant work is performed.
 
-            for maybe_oid in walk {
+            for (visited, maybe_oid) in walk.enumerate() {
                 let oid = maybe_oid?;
 
+                if bounds != HistoryBounds::default() {
+                    let time = self.repo.find_commit(oid)?.time().seconds();
+                    if let Some(truncation) = bounds.stop_before(visited, time) {
+                        info!(
+                            "history of {} is truncated: {}",
+                            projects[unit_idx].user_facing_name, truncation
+                        );
+                        histories[unit_idx].truncation = Some(truncation);
+                        break;
+                    }
+                }
+
                 // Hopefully this commit is already in the cache, but if not ...
                 if !commit_data.contains(&oid) {
                     // Get the two relevant trees and compute their diff. We have to
@@ -855,11 +1817,23 @@ impl Repository {
                         (Some(ptid), Some(parent_tree))
                     };
 
-                    let diff = self.repo.diff_tree_to_tree(
+                    let mut diff = self.repo.diff_tree_to_tree(
                         maybe_parent_tree.as_ref(),
                         Some(&cur_tree),
                         Some(&mut dopts),
                     )?;
+                    if follow_renames {
+                        diff.find_similar(Some(git2::DiffFindOptions::new().renames(true)))?;
+                        for (idx, old_prefix) in moved_prefixes(&diff, &cur_tree, &prefixes) {
+                            info!(
+                                "{} was moved from `{}` in commit {}",
+                                projects[idx].user_facing_name,
+                                escape_pathlike(&old_prefix),
+                                oid
+                            );
+                            prefixes[idx].push(old_prefix);
+                        }
+                    }
 
                     trees.put(ctid, cur_tree);
                     if let (Some(ptid), Some(pt)) = (maybe_ptid, maybe_parent_tree) {
@@ -869,31 +1843,44 @@ impl Repository {
                     let mut hit_buf = vec![false; projects.len()];
 
                     if commit.parent_count() < 2 {
+                        let trailer_matched = self.project_trailers
+                            && project_trailers::attribute(
+                                commit.message().unwrap_or_default(),
+                                &project_names,
+                                &mut hit_buf,
+                            );
                         let mut scope_matched = false;
 
-                        if let Some(summary) = commit.summary() {
-                            if let Some(scope) = extract_scope(summary) {
-                                if let Some(matched_name) =
-                                    scope_matcher.find_matching_project(&scope, &project_names)
-                                {
-                                    for (idx, unit) in projects.iter().enumerate() {
-                                        if &unit.user_facing_name == matched_name {
-                                            hit_buf[idx] = true;
-                                            scope_matched = true;
-                                            break;
+                        if !trailer_matched {
+                            if let Some(summary) = commit.summary() {
+                                if let Some(scope) = extract_scope(summary) {
+                                    if let Some(matched_name) =
+                                        scope_matcher.find_matching_project(&scope, &project_names)
+                                    {
+                                        for (idx, unit) in projects.iter().enumerate() {
+                                            if &unit.user_facing_name == matched_name {
+                                                hit_buf[idx] = true;
+                                                scope_matched = true;
+                                                break;
+                                            }
                                         }
                                     }
                                 }
                             }
                         }
 
-                        if !scope_matched {
+                        if !trailer_matched && !scope_matched {
                             for delta in diff.deltas() {
                                 for file in &[delta.old_file(), delta.new_file()] {
                                     if let Some(path_bytes) = file.path_bytes() {
                                         let path = RepoPath::new(path_bytes);
                                         for (idx, unit) in projects.iter().enumerate() {
-                                            if unit.repo_paths.repo_path_matches(path) {
+                                            if unit.repo_paths.repo_path_matches(path)
+                                                || prefixes[idx]
+                                                    .iter()
+                                                    .skip(1)
+                                                    .any(|old| path.starts_with(old))
+                                            {
                                                 hit_buf[idx] = true;
                                             }
                                         }
@@ -931,9 +1918,77 @@ impl Repository {
     }
 
     /// Get full commit details including author and committer information.
+    /// Names and emails go through the repository's `.mailmap` (and git's
+    /// `mailmap.file`/`mailmap.blob`), so one person committing under
+    /// several identities shows up once.
     pub fn get_commit_details(&self, cid: CommitId) -> Result<crate::core::changelog::Commit> {
         let commit = self.repo.find_commit(cid.0)?;
-        Ok(crate::core::changelog::Commit::from(&commit))
+        let mut details = crate::core::changelog::Commit::from(&commit);
+        let mailmap = self.mailmap.get_or_try_init(|| self.repo.mailmap())?;
+        details.author = commit.author_with_mailmap(mailmap)?.into();
+        details.committer = commit.committer_with_mailmap(mailmap)?.into();
+        details.paths = self
+            .changed_files(&commit)?
+            .iter()
+            .map(|path| path.escaped())
+            .collect();
+        Ok(details)
+    }
+
+    /// The files `commit` changed relative to its first parent.
+    fn changed_files(&self, commit: &git2::Commit<'_>) -> Result<Vec<RepoPathBuf>> {
+        let tree = commit.tree()?;
+        let parent_tree = match commit.parent_count() {
+            0 => None,
+            _ => Some(commit.parent(0)?.tree()?),
+        };
+        let diff = self
+            .repo
+            .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)?;
+        Ok(diff
+            .deltas()
+            .filter_map(|d| d.new_file().path_bytes().or(d.old_file().path_bytes()))
+            .map(RepoPathBuf::new)
+            .collect())
+    }
+
+    /// Everything a detail view shows about one commit: the body without
+    /// its trailer block, the trailers, and the files it changed relative
+    /// to its first parent. Loaded on demand — history analysis only keeps
+    /// commit ids around.
+    pub fn inspect_commit(&self, id: &str) -> Result<CommitInspection> {
+        let oid = git2::Oid::from_str(id).with_context(|| format!("invalid commit id `{id}`"))?;
+        let commit = self.repo.find_commit(oid)?;
+        let message = String::from_utf8_lossy(commit.message_bytes()).into_owned();
+
+        let trailers: Vec<(String, String)> = git2::message_trailers_strs(&message)
+            .map(|t| {
+                t.iter()
+                    .map(|(k, v)| (k.to_owned(), v.to_owned()))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut paragraphs: Vec<&str> = message
+            .trim()
+            .split("\n\n")
+            .skip(1)
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .collect();
+        if !trailers.is_empty() {
+            paragraphs.pop();
+        }
+
+        let changed_files = self.changed_files(&commit)?;
+
+        Ok(CommitInspection {
+            id: oid.to_string(),
+            summary: commit.summary().unwrap_or_default().to_owned(),
+            body: paragraphs.join("\n\n"),
+            trailers,
+            changed_files,
+        })
     }
 
     /// Update the specified files in the working tree to reset them to what
@@ -995,6 +2050,69 @@ impl Repository {
         Ok(())
     }
 
+    pub fn branch_exists(&self, name: &str) -> bool {
+        self.repo.find_branch(name, git2::BranchType::Local).is_ok()
+    }
+
+    /// The repository's `.git` directory (the worktree's own, in a linked
+    /// worktree), for state that must not show up in the working tree.
+    pub fn git_dir(&self) -> &Path {
+        self.repo.path()
+    }
+
+    /// Every modified, staged or untracked path in the working tree.
+    pub fn changed_paths(&self) -> Result<Vec<RepoPathBuf>> {
+        let mut opts = git2::StatusOptions::new();
+        opts.include_untracked(true);
+        opts.recurse_untracked_dirs(true);
+        opts.include_ignored(false);
+
+        Ok(self
+            .repo
+            .statuses(Some(&mut opts))?
+            .iter()
+            .filter(|entry| entry.status() != git2::Status::CURRENT)
+            .map(|entry| RepoPathBuf::new(entry.path_bytes()))
+            .collect())
+    }
+
+    /// Reset every changed path except those in `keep` to HEAD: staged
+    /// changes are unstaged, modified files checked out again and files
+    /// HEAD doesn't have deleted. Returns the paths that were reset.
+    pub fn discard_changes(&self, keep: &[RepoPathBuf]) -> Result<Vec<RepoPathBuf>> {
+        let head = self.repo.head()?.peel_to_commit()?;
+        let head_tree = head.tree()?;
+        let discarded: Vec<RepoPathBuf> = self
+            .changed_paths()?
+            .into_iter()
+            .filter(|path| !keep.contains(path))
+            .collect();
+        if discarded.is_empty() {
+            return Ok(discarded);
+        }
+
+        let pathspecs: Vec<&Path> = discarded.iter().map(|p| p.as_path()).collect();
+        self.repo.reset_default(Some(head.as_object()), pathspecs)?;
+
+        let mut tracked = ChangeList::default();
+        for path in &discarded {
+            if head_tree.get_path(path.as_path()).is_ok() {
+                tracked.add_path(path);
+            } else {
+                let file = self.resolve_workdir(path);
+                match std::fs::remove_file(&file) {
+                    Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                        return Err(e)
+                            .with_context(|| format!("failed to delete `{}`", file.display()));
+                    }
+                    _ => {}
+                }
+            }
+        }
+        self.hard_reset_changes(&tracked)?;
+        Ok(discarded)
+    }
+
     /// Fetch tags from the configured upstream remote.
     ///
     /// Why this exists: tag-reading helpers like
@@ -1006,35 +2124,16 @@ impl Repository {
     ///
     /// Uses refspec `+refs/tags/*:refs/tags/*` (force) so that
     /// re-tagged versions (rare but legal) overwrite the local copy
-    /// instead of erroring out. Mirrors the credential pattern from
-    /// [`Self::push_branch`].
+    /// instead of erroring out. Credentials are those of
+    /// [`remote_callbacks`].
     pub fn fetch_tags(&self, git_token: Option<&str>) -> Result<()> {
         let mut remote = self
             .repo
             .find_remote(&self.upstream_name)
-            .with_context(|| {
-                format!("cannot find upstream remote `{}`", self.upstream_name)
-            })?;
-
-        let token_for_closure = git_token.map(str::to_owned);
-
-        let mut callbacks = git2::RemoteCallbacks::new();
-        callbacks.credentials(move |_url, username_from_url, allowed_types| {
-            if allowed_types.contains(git2::CredentialType::SSH_KEY) {
-                git2::Cred::ssh_key_from_agent(username_from_url.unwrap_or("git"))
-            } else if allowed_types.contains(git2::CredentialType::USER_PASS_PLAINTEXT) {
-                if let Some(ref token) = token_for_closure {
-                    git2::Cred::userpass_plaintext("x-access-token", token)
-                } else {
-                    git2::Cred::default()
-                }
-            } else {
-                git2::Cred::default()
-            }
-        });
+            .with_context(|| format!("cannot find upstream remote `{}`", self.upstream_name))?;
 
         let mut fetch_options = git2::FetchOptions::new();
-        fetch_options.remote_callbacks(callbacks);
+        fetch_options.remote_callbacks(remote_callbacks(git_token));
         fetch_options.download_tags(git2::AutotagOption::All);
 
         remote
@@ -1057,48 +2156,142 @@ impl Repository {
         Ok(())
     }
 
+    /// Push `branch_name` to the upstream remote.
+    ///
+    /// The transport follows `[git] backend`: libgit2 (SSH agent or the
+    /// belaf-issued `git_token`), the `git` executable, or — the default —
+    /// libgit2 first with a fallback to `git push`. The fallback exists
+    /// because libgit2 knows nothing about credential managers (macOS
+    /// keychain, Git Credential Manager, SSO-backed HTTPS helpers), so a
+    /// push that plain git would accept can fail here.
     pub fn push_branch(&self, branch_name: &str, git_token: Option<&str>) -> Result<()> {
-        let mut remote = self.repo.find_remote(&self.upstream_name)?;
+        self.check_upstream("push the release branch")?;
         let refspec = format!("refs/heads/{}:refs/heads/{}", branch_name, branch_name);
+        self.push_refspec(&refspec, git_token)?;
+        info!("pushed branch {} to {}", branch_name, self.upstream_name);
+        Ok(())
+    }
 
-        let token_for_closure = git_token.map(|s| s.to_string());
+    /// Push the tags `names` to the upstream remote, the same way as
+    /// [`Self::push_branch`].
+    pub fn push_tags(&self, names: &[&str], git_token: Option<&str>) -> Result<()> {
+        self.check_upstream("push release tags")?;
+        for name in names {
+            self.push_refspec(&format!("refs/tags/{name}:refs/tags/{name}"), git_token)?;
+            info!("pushed tag {} to {}", name, self.upstream_name);
+        }
+        Ok(())
+    }
 
-        let mut callbacks = git2::RemoteCallbacks::new();
-        callbacks.credentials(move |_url, username_from_url, allowed_types| {
-            if allowed_types.contains(git2::CredentialType::SSH_KEY) {
-                git2::Cred::ssh_key_from_agent(username_from_url.unwrap_or("git"))
-            } else if allowed_types.contains(git2::CredentialType::USER_PASS_PLAINTEXT) {
-                if let Some(ref token) = token_for_closure {
-                    git2::Cred::userpass_plaintext("x-access-token", token)
-                } else {
-                    git2::Cred::default()
+    fn push_refspec(&self, refspec: &str, git_token: Option<&str>) -> Result<()> {
+        match self.git_config.backend {
+            GitBackend::Libgit2 => self.push_libgit2(refspec, git_token),
+            GitBackend::Cli => self.push_cli(refspec),
+            GitBackend::Auto => {
+                if let Err(err) = self.push_libgit2(refspec, git_token) {
+                    warn!(
+                        "libgit2 push to {} failed ({}); retrying with the git CLI",
+                        self.upstream_name, err
+                    );
+                    self.push_cli(refspec)?;
                 }
-            } else {
-                git2::Cred::default()
+                Ok(())
             }
-        });
+        }
+    }
+
+    fn push_libgit2(&self, refspec: &str, git_token: Option<&str>) -> Result<()> {
+        let mut remote = self.repo.find_remote(&self.upstream_name)?;
 
         let mut push_options = git2::PushOptions::new();
-        push_options.remote_callbacks(callbacks);
+        push_options.remote_callbacks(remote_callbacks(git_token));
+
+        remote.push(&[refspec], Some(&mut push_options))?;
+        Ok(())
+    }
+
+    /// Push by running `git push` in the working tree, so the user's own
+    /// git configuration (credential helpers, `insteadOf` rewrites, SSH
+    /// config) applies.
+    fn push_cli(&self, refspec: &str) -> Result<()> {
+        let workdir = self
+            .repo
+            .workdir()
+            .ok_or_else(|| anyhow!("cannot push from a bare repository"))?;
+
+        let output = std::process::Command::new("git")
+            .args(["push", "--porcelain", &self.upstream_name, refspec])
+            .current_dir(workdir)
+            .output()
+            .context("failed to invoke `git push` — is git installed and on PATH?")?;
+
+        if !output.status.success() {
+            bail!(
+                "`git push {} {}` failed ({}): {}",
+                self.upstream_name,
+                refspec,
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
 
-        remote.push(&[&refspec], Some(&mut push_options))?;
-        info!("pushed branch {} to {}", branch_name, self.upstream_name);
         Ok(())
     }
 
     pub fn generate_release_branch_name() -> String {
-        let now = time::OffsetDateTime::now_utc();
+        let now = deterministic::now_utc();
         let formatted =
             time::format_description::parse("[year][month][day]-[hour][minute][second]")
                 .ok()
                 .and_then(|format| now.format(&format).ok())
                 .unwrap_or_else(|| now.unix_timestamp().to_string());
-        let suffix = &Uuid::new_v4().to_string()[..8];
+        let suffix = &deterministic::uuid_v4().to_string()[..8];
 
         format!("release/{}-{}", formatted, suffix)
     }
 }
 
+/// A file opened with [`Repository::open_file`].
+#[derive(Debug)]
+pub enum RepoFile {
+    /// A file in the working tree.
+    Worktree(File),
+    /// The content of a blob of the commit read.
+    Blob(std::io::Cursor<Vec<u8>>),
+}
+
+impl RepoFile {
+    /// The file's size in bytes.
+    pub fn size(&self) -> std::io::Result<u64> {
+        match self {
+            RepoFile::Worktree(f) => Ok(f.metadata()?.len()),
+            RepoFile::Blob(data) => Ok(data.get_ref().len() as u64),
+        }
+    }
+}
+
+impl Read for RepoFile {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self {
+            RepoFile::Worktree(f) => f.read(buf),
+            RepoFile::Blob(data) => data.read(buf),
+        }
+    }
+}
+
+/// What [`Repository::verify_tag_signature`] found.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TagSignature {
+    /// A lightweight tag, which can't carry a signature.
+    Lightweight,
+    /// An annotated tag without a signature.
+    Unsigned,
+    /// A signature git verified, with what the tool said of the signer.
+    Good(String),
+    /// A signature git rejected, with the tool's complaint.
+    Bad(String),
+}
+
 /// Describes the availability of a given commit in the release of a project.
 /// Note that because different projects are released at different times, the
 /// availability for the same commit might vary depending on which project we're
@@ -1106,8 +2299,8 @@ impl Repository {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum ReleaseAvailability {
     /// The commit has already been released. The earliest release containing it
-    /// has the given version.
-    ExistingRelease(Version),
+    /// has the given version and tag.
+    ExistingRelease(Version, String),
 
     /// The commit has not been released, but is an ancestor of HEAD, so it
     /// would be available if a new release of the target project were to be
@@ -1126,18 +2319,19 @@ impl Repository {
         matcher: &TagMatcher,
         cid: &CommitId,
     ) -> Result<ReleaseAvailability> {
-        if let Some((tag_oid, _tag_name, version)) =
-            self.find_latest_tag_for_project(matcher)?
-        {
-            if self.repo.graph_descendant_of(tag_oid, cid.0)? || tag_oid == cid.0 {
+        let mut tags = self.release_tags_for_project(matcher)?;
+        tags.sort_by(|a, b| a.2.cmp(&b.2));
+        for (tag_oid, tag_name, version) in tags {
+            if tag_oid == cid.0 || self.repo.graph_descendant_of(tag_oid, cid.0)? {
                 let v = Version::parse_like(&unit.version, version.to_string())?;
-                return Ok(ReleaseAvailability::ExistingRelease(v));
+                return Ok(ReleaseAvailability::ExistingRelease(v, tag_name));
             }
         }
 
-        let head_ref = self.repo.head()?;
-        let head_commit = head_ref.peel_to_commit()?;
-        let head_id = head_commit.id();
+        let head_id = match self.as_of {
+            Some(oid) => oid,
+            None => self.repo.head()?.peel_to_commit()?.id(),
+        };
 
         if head_id == cid.0 || self.repo.graph_descendant_of(head_id, cid.0)? {
             Ok(ReleaseAvailability::NewRelease)
@@ -1220,6 +2414,35 @@ impl ChangeList {
     }
 }
 
+/// A submodule of the working tree, see [`Repository::submodules`].
+#[derive(Clone, Debug)]
+pub struct Submodule {
+    pub name: String,
+    pub path: RepoPathBuf,
+    pub url: Option<String>,
+    /// The commit the superproject's index pins it at.
+    pub pinned: Option<CommitId>,
+}
+
+/// A release tag of a submodule, see
+/// [`Repository::submodule_release_tags`].
+#[derive(Clone, Debug)]
+pub struct SubmoduleTag {
+    pub name: String,
+    pub version: semver::Version,
+    pub commit: CommitId,
+}
+
+/// Detail of a single commit, see [`Repository::inspect_commit`].
+#[derive(Clone, Debug)]
+pub struct CommitInspection {
+    pub id: String,
+    pub summary: String,
+    pub body: String,
+    pub trailers: Vec<(String, String)>,
+    pub changed_files: Vec<RepoPathBuf>,
+}
+
 #[derive(Clone, Debug)]
 pub enum HistoryBoundary {
     ReleaseTag {
@@ -1236,6 +2459,7 @@ pub enum HistoryBoundary {
 pub struct RepoHistory {
     commits: Vec<CommitId>,
     boundary: Option<HistoryBoundary>,
+    truncation: Option<Truncation>,
 }
 
 impl RepoHistory {
@@ -1254,6 +2478,20 @@ impl RepoHistory {
         }
     }
 
+    /// Why the walk stopped before reaching the boundary, if it did; see
+    /// [`crate::core::history_bounds`].
+    pub fn truncation(&self) -> Option<Truncation> {
+        self.truncation
+    }
+
+    /// Name of the release tag the history starts after, if any.
+    pub fn release_tag(&self) -> Option<&str> {
+        match &self.boundary {
+            Some(HistoryBoundary::ReleaseTag { tag_name, .. }) => Some(tag_name),
+            _ => None,
+        }
+    }
+
     pub fn has_release_tag(&self) -> bool {
         matches!(&self.boundary, Some(HistoryBoundary::ReleaseTag { .. }))
     }
@@ -1685,6 +2923,47 @@ impl std::ops::Deref for RepoPathBuf {
     }
 }
 
+/// Directories `diff` (with renames detected) moved into a unit, as
+/// `(unit index, old prefix)`: a file renamed into one of the unit's
+/// `prefixes` keeps its path relative to it, and the directory it left
+/// is gone from `tree`, the commit's tree. Moving a single file into a
+/// unit from a directory that stays doesn't count.
+fn moved_prefixes(
+    diff: &git2::Diff<'_>,
+    tree: &git2::Tree<'_>,
+    prefixes: &[Vec<Vec<u8>>],
+) -> Vec<(usize, Vec<u8>)> {
+    let mut moved: Vec<(usize, Vec<u8>)> = Vec::new();
+    for delta in diff.deltas() {
+        if delta.status() != git2::Delta::Renamed {
+            continue;
+        }
+        let (Some(old), Some(new)) = (delta.old_file().path_bytes(), delta.new_file().path_bytes())
+        else {
+            continue;
+        };
+        for (idx, known) in prefixes.iter().enumerate() {
+            let Some(old_prefix) = known
+                .iter()
+                .find_map(|prefix| old.strip_suffix(new.strip_prefix(prefix.as_slice())?))
+            else {
+                continue;
+            };
+            if !old_prefix.ends_with(b"/")
+                || known.iter().any(|k| k == old_prefix)
+                || moved.iter().any(|(i, p)| *i == idx && p == old_prefix)
+                || tree
+                    .get_path(bytes2path(&old_prefix[..old_prefix.len() - 1]))
+                    .is_ok()
+            {
+                continue;
+            }
+            moved.push((idx, old_prefix.to_vec()));
+        }
+    }
+    moved
+}
+
 /// Convert an arbitrary byte slice to something printable.
 ///
 /// If the bytes can be interpreted as UTF-8, their Unicode stringification will
@@ -1737,6 +3016,21 @@ pub fn escape_pathlike(b: &[u8]) -> String {
     }
 }
 
+/// `host/owner/repo` of a remote URL, lower-cased, without scheme,
+/// credentials, port or `.git`; local paths are kept as they are.
+fn repository_id(url: &str) -> String {
+    let url = url.trim().trim_end_matches('/');
+    let url = url.strip_suffix(".git").unwrap_or(url);
+    let rest = match url.split_once("://") {
+        Some((_, rest)) => rest.to_string(),
+        None => url.replacen(':', "/", 1),
+    };
+    let (authority, path) = rest.split_once('/').unwrap_or((&rest, ""));
+    let host = authority.rsplit_once('@').map_or(authority, |(_, h)| h);
+    let host = host.split(':').next().unwrap_or_default();
+    format!("{host}/{path}").to_ascii_lowercase()
+}
+
 #[cfg(test)]
 #[path = "repository_tests.rs"]
 mod repository_tests;
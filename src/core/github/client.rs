@@ -1,11 +1,30 @@
-use anyhow::{anyhow, Context};
-use tracing::info;
+//! The GitHub side of a release: pull requests through the belaf API,
+//! and milestones through the REST API.
+//!
+//! With `[release.milestones] enabled = true`, `belaf tag` rolls over
+//! the milestone of each release it tags (see [`roll_milestone`]): the
+//! open milestone titled after the released version is closed, the one
+//! of the next minor version is created unless it exists, and the issues
+//! and pull requests still open in the closed one move to it. Running it
+//! again changes nothing once the milestone is closed.
+
+use anyhow::{anyhow, bail, Context};
+use reqwest::Method;
+use serde_json::{json, Value};
+use tracing::{debug, info};
 
 use crate::core::api::{ApiClient, CreatePullRequestParams, StoredToken};
+use crate::core::auth::permissions::{explain_api_error, preflight, Permission};
 use crate::core::auth::token::load_token;
+use crate::core::config::syntax::MilestonesConfiguration;
 use crate::core::errors::Result;
+use crate::core::github::{forge, rest::RestClient};
+use crate::core::manifest::ReleaseEntry;
 use crate::core::session::AppSession;
 
+const DEFAULT_MILESTONE_FORMAT: &str = "{version}";
+const PER_PAGE: usize = 100;
+
 pub struct GitHubInformation {
     owner: String,
     repo: String,
@@ -35,13 +54,40 @@ impl GitHubInformation {
         Ok(GitHubInformation {
             owner,
             repo,
-            api_client: ApiClient::new(),
+            api_client: ApiClient::try_new()?,
             token,
         })
     }
 
-    pub fn new_with_scopes(sess: &AppSession, _required_scopes: &[&str]) -> Result<Self> {
-        Self::new(sess)
+    /// Like [`Self::new`], but first confirms the belaf GitHub App is
+    /// installed on the repository. `required` documents what the caller
+    /// is about to do; individual permissions are only verifiable once
+    /// the call that needs them answers, so a `403` there is reported
+    /// against the matching [`Permission`].
+    pub fn new_with_scopes(sess: &AppSession, required: &[Permission]) -> Result<Self> {
+        let info = Self::new(sess)?;
+        debug!(
+            "checking App installation on {}/{} (needs: {})",
+            info.owner,
+            info.repo,
+            required
+                .iter()
+                .map(|p| p.label())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+
+        let future = preflight(&info.api_client, &info.token, &info.owner, &info.repo);
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => tokio::task::block_in_place(|| handle.block_on(future)),
+            Err(_) => {
+                let rt =
+                    tokio::runtime::Runtime::new().context("failed to create async runtime")?;
+                rt.block_on(future)
+            }
+        }?;
+
+        Ok(info)
     }
 
     pub fn create_pull_request(
@@ -50,6 +96,8 @@ impl GitHubInformation {
         base: &str,
         title: &str,
         body: &str,
+        reviewers: &[String],
+        team_reviewers: &[String],
     ) -> Result<String> {
         let owner = self.owner.clone();
         let repo = self.repo.clone();
@@ -58,6 +106,8 @@ impl GitHubInformation {
         let head = head.to_string();
         let base = base.to_string();
         let body = body.to_string();
+        let reviewers = reviewers.to_vec();
+        let team_reviewers = team_reviewers.to_vec();
         let api_client = self.api_client.clone();
 
         let future = async move {
@@ -69,6 +119,8 @@ impl GitHubInformation {
                 head: &head,
                 base: &base,
                 body: &body,
+                reviewers: &reviewers,
+                team_reviewers: &team_reviewers,
             };
 
             let pr = api_client
@@ -81,6 +133,9 @@ impl GitHubInformation {
                     } => {
                         anyhow::anyhow!("pull request creation failed: {}", message)
                     }
+                    crate::core::api::ApiError::ApiResponse {
+                        status: 403 | 404, ..
+                    } => explain_api_error(e, Permission::PullRequests, &owner, &repo),
                     crate::core::api::ApiError::ApiResponse { status, message } => {
                         anyhow::anyhow!("GitHub API error ({}): {}", status, message)
                     }
@@ -102,28 +157,290 @@ impl GitHubInformation {
     }
 }
 
-impl Clone for ApiClient {
-    fn clone(&self) -> Self {
-        Self::new()
+/// `(owner, repo)` of a remote on the configured forge (github.com or
+/// the `[forge]` GitHub Enterprise host).
+pub fn parse_github_url(url: &str) -> Result<(String, String)> {
+    forge::current().parse_remote(url)
+}
+
+/// Check `[release.milestones]`: the title has to name the version.
+pub fn validate_milestones(cfg: &MilestonesConfiguration) -> Result<()> {
+    if let Some(format) = &cfg.title_format {
+        if !format.contains("{version}") {
+            bail!("title_format `{format}` must contain `{{version}}`");
+        }
     }
+    Ok(())
 }
 
-pub fn parse_github_url(url: &str) -> Result<(String, String)> {
-    if let Some(rest) = url.strip_prefix("git@github.com:") {
-        let repo = rest.trim_end_matches(".git");
-        let parts: Vec<&str> = repo.split('/').collect();
-        if parts.len() == 2 {
-            return Ok((parts[0].to_string(), parts[1].to_string()));
+/// The milestone of `release`, and the one of the next minor version
+/// after it; `None` for the latter when the version isn't semver.
+pub fn milestone_titles(
+    cfg: &MilestonesConfiguration,
+    release: &ReleaseEntry,
+) -> (String, Option<String>) {
+    let title = |version: &str| {
+        cfg.title_format
+            .as_deref()
+            .unwrap_or(DEFAULT_MILESTONE_FORMAT)
+            .replace("{name}", &release.name)
+            .replace("{version}", version)
+    };
+    let next = semver::Version::parse(&release.new_version)
+        .ok()
+        .map(|v| title(&semver::Version::new(v.major, v.minor + 1, 0).to_string()));
+    (title(&release.new_version), next)
+}
+
+/// What [`roll_milestone`] did.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MilestoneReport {
+    /// Whether the next milestone had to be created.
+    pub created: bool,
+    /// Open issues and pull requests moved to it.
+    pub moved: usize,
+}
+
+/// Close the open milestone titled `title` in `owner/repo`, moving its
+/// open issues and pull requests to the milestone `next`, created as
+/// needed. `None` when there is no such open milestone.
+pub fn roll_milestone(
+    client: &RestClient,
+    owner: &str,
+    repo: &str,
+    title: &str,
+    next: Option<&str>,
+) -> Result<Option<MilestoneReport>> {
+    let future = roll_milestone_async(client, owner, repo, title, next);
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) => tokio::task::block_in_place(|| handle.block_on(future)),
+        Err(_) => tokio::runtime::Runtime::new()
+            .context("failed to create async runtime")?
+            .block_on(future),
+    }
+}
+
+async fn roll_milestone_async(
+    client: &RestClient,
+    owner: &str,
+    repo: &str,
+    title: &str,
+    next: Option<&str>,
+) -> Result<Option<MilestoneReport>> {
+    let milestones = list(
+        client,
+        &format!("/repos/{owner}/{repo}/milestones?state=open"),
+    )
+    .await?;
+    let number_of = |title: &str| {
+        milestones
+            .iter()
+            .find(|m| m["title"].as_str() == Some(title))
+            .and_then(|m| m["number"].as_u64())
+    };
+    let Some(closing) = number_of(title) else {
+        return Ok(None);
+    };
+
+    let mut report = MilestoneReport::default();
+    if let Some(next) = next {
+        let next_number = match number_of(next) {
+            Some(number) => number,
+            None => {
+                let response = client
+                    .send(
+                        Method::POST,
+                        &format!("/repos/{owner}/{repo}/milestones"),
+                        Some(&json!({ "title": next })),
+                    )
+                    .await?;
+                check(
+                    response.status,
+                    201,
+                    &format!("create milestone `{next}`"),
+                    &response.body,
+                )?;
+                report.created = true;
+                response.body["number"].as_u64().ok_or_else(|| {
+                    anyhow!("GitHub did not return the number of milestone `{next}`")
+                })?
+            }
+        };
+
+        let open = list(
+            client,
+            &format!("/repos/{owner}/{repo}/issues?milestone={closing}&state=open"),
+        )
+        .await?;
+        for issue in open.iter().filter_map(|i| i["number"].as_u64()) {
+            let response = client
+                .send(
+                    Method::PATCH,
+                    &format!("/repos/{owner}/{repo}/issues/{issue}"),
+                    Some(&json!({ "milestone": next_number })),
+                )
+                .await?;
+            check(
+                response.status,
+                200,
+                &format!("move #{issue} to `{next}`"),
+                &response.body,
+            )?;
+            report.moved += 1;
         }
     }
 
-    if let Some(rest) = url.strip_prefix("https://github.com/") {
-        let repo = rest.trim_end_matches(".git");
-        let parts: Vec<&str> = repo.split('/').collect();
-        if parts.len() >= 2 {
-            return Ok((parts[0].to_string(), parts[1].to_string()));
+    let response = client
+        .send(
+            Method::PATCH,
+            &format!("/repos/{owner}/{repo}/milestones/{closing}"),
+            Some(&json!({ "state": "closed" })),
+        )
+        .await?;
+    check(
+        response.status,
+        200,
+        &format!("close milestone `{title}`"),
+        &response.body,
+    )?;
+    Ok(Some(report))
+}
+
+/// Every item of the list at `path`, page by page.
+async fn list(client: &RestClient, path: &str) -> Result<Vec<Value>> {
+    let mut items = Vec::new();
+    for page in 1.. {
+        let response = client
+            .send(
+                Method::GET,
+                &format!("{path}&per_page={PER_PAGE}&page={page}"),
+                None,
+            )
+            .await?;
+        check(
+            response.status,
+            200,
+            &format!("list `{path}`"),
+            &response.body,
+        )?;
+        let batch = response.body.as_array().cloned().unwrap_or_default();
+        let last = batch.len() < PER_PAGE;
+        items.extend(batch);
+        if last {
+            break;
+        }
+    }
+    Ok(items)
+}
+
+fn check(status: u16, expected: u16, what: &str, body: &Value) -> Result<()> {
+    match status {
+        s if s == expected => Ok(()),
+        403 | 404 => bail!(
+            "failed to {what}: the token may not manage milestones; it needs `Issues: Read and write`"
+        ),
+        s => bail!(
+            "failed to {what} ({s}): {}",
+            body["message"].as_str().unwrap_or_default()
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_json, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn cfg(title_format: Option<&str>) -> MilestonesConfiguration {
+        MilestonesConfiguration {
+            enabled: true,
+            title_format: title_format.map(String::from),
         }
     }
 
-    Err(anyhow!("Could not parse GitHub URL: {}", url))
+    fn release(version: &str) -> ReleaseEntry {
+        ReleaseEntry::new(
+            "api".into(),
+            "cargo".into(),
+            "1.2.0".into(),
+            version.into(),
+            "minor".into(),
+            String::new(),
+            String::new(),
+        )
+    }
+
+    #[test]
+    fn milestones_are_titled_after_the_version() {
+        assert_eq!(
+            milestone_titles(&cfg(None), &release("1.3.0")),
+            ("1.3.0".to_string(), Some("1.4.0".to_string()))
+        );
+        assert_eq!(
+            milestone_titles(&cfg(Some("{name} v{version}")), &release("1.3.2")),
+            ("api v1.3.2".to_string(), Some("api v1.4.0".to_string()))
+        );
+        assert_eq!(milestone_titles(&cfg(None), &release("2024.1")).1, None);
+        assert!(validate_milestones(&cfg(Some("next"))).is_err());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn the_released_milestone_is_closed_and_its_issues_move_on() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/acme/widgets/milestones"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                { "number": 3, "title": "1.3.0" },
+                { "number": 9, "title": "2.0.0" },
+            ])))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/repos/acme/widgets/milestones"))
+            .and(body_json(json!({ "title": "1.4.0" })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(json!({ "number": 4 })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/acme/widgets/issues"))
+            .and(query_param("milestone", "3"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!([{ "number": 11 }, { "number": 12 }])),
+            )
+            .mount(&server)
+            .await;
+        for issue in [11, 12] {
+            Mock::given(method("PATCH"))
+                .and(path(format!("/repos/acme/widgets/issues/{issue}")))
+                .and(body_json(json!({ "milestone": 4 })))
+                .respond_with(ResponseTemplate::new(200))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("PATCH"))
+            .and(path("/repos/acme/widgets/milestones/3"))
+            .and(body_json(json!({ "state": "closed" })))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = RestClient::with_api_url(&server.uri(), "t").unwrap();
+        let report = roll_milestone(&client, "acme", "widgets", "1.3.0", Some("1.4.0")).unwrap();
+        assert_eq!(
+            report,
+            Some(MilestoneReport {
+                created: true,
+                moved: 2,
+            })
+        );
+        assert_eq!(
+            roll_milestone(&client, "acme", "widgets", "1.5.0", Some("1.6.0")).unwrap(),
+            None
+        );
+    }
 }
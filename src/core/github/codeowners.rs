@@ -0,0 +1,221 @@
+//! Release-unit owners from the repository's `CODEOWNERS` file.
+//!
+//! The file is looked up where GitHub looks for it — `.github/`, the
+//! repository root, then `docs/` — and the first one found is used.
+//! Patterns follow GitHub's rules: gitignore-style globs, the last
+//! matching line wins, and a trailing `/*` covers only the files
+//! directly in that directory.
+//!
+//! A unit's owners are the owners of the files directly in its
+//! directory, so a rule such as `*.md @docs` that only claims some
+//! files doesn't make `@docs` the owner of every unit.
+
+use regex::Regex;
+use tracing::{debug, warn};
+
+use crate::core::git::repository::{RepoPath, RepoPathBuf, Repository};
+
+/// Where GitHub looks for the file, in order of precedence.
+pub const LOCATIONS: &[&str] = &[".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"];
+
+/// Stand-in file name for "a file directly in the unit's directory".
+/// A NUL can't occur in a real path, so only patterns that match every
+/// file name (`*`, `?*`) match it.
+const ANY_FILE: &str = "\0";
+
+/// One line of a `CODEOWNERS` file.
+#[derive(Debug)]
+pub struct Rule {
+    pub pattern: String,
+    /// `@user`, `@org/team` or e-mail entries, as written. Empty when
+    /// the line deliberately leaves its paths unowned.
+    pub owners: Vec<String>,
+    regex: Regex,
+}
+
+#[derive(Debug, Default)]
+pub struct CodeOwners {
+    path: Option<RepoPathBuf>,
+    rules: Vec<Rule>,
+}
+
+impl CodeOwners {
+    /// Load the repository's `CODEOWNERS`, as [`Repository::open_file`]
+    /// reads it. A missing or unreadable file yields no rules; it never
+    /// fails the command that asked.
+    pub fn load(repo: &Repository) -> Self {
+        for location in LOCATIONS {
+            let path = RepoPathBuf::new(location.as_bytes());
+            if !repo.is_file(&path) {
+                continue;
+            }
+            let abs = repo.resolve_workdir(&path);
+            return match repo.read_to_string(&path) {
+                Ok(content) => CodeOwners {
+                    path: Some(path),
+                    ..Self::parse(&content)
+                },
+                Err(e) => {
+                    warn!("cannot read `{}`: {e}", abs.display());
+                    Self::default()
+                }
+            };
+        }
+        Self::default()
+    }
+
+    pub fn parse(content: &str) -> Self {
+        let mut rules = Vec::new();
+        for line in content.lines() {
+            let mut fields = line
+                .split_whitespace()
+                .take_while(|field| !field.starts_with('#'));
+            let Some(pattern) = fields.next() else {
+                continue;
+            };
+            match pattern_regex(pattern) {
+                Ok(regex) => rules.push(Rule {
+                    pattern: pattern.to_owned(),
+                    owners: fields.map(str::to_owned).collect(),
+                    regex,
+                }),
+                Err(e) => debug!("skipping CODEOWNERS pattern `{pattern}`: {e}"),
+            }
+        }
+        CodeOwners { path: None, rules }
+    }
+
+    /// The file the rules came from, if one was found.
+    pub fn path(&self) -> Option<&RepoPath> {
+        self.path.as_deref()
+    }
+
+    /// The rule deciding who owns the directory `prefix` (a unit
+    /// prefix: empty for the root, otherwise ending in `/`).
+    pub fn rule_for(&self, prefix: &RepoPath) -> Option<&Rule> {
+        let probe = format!("{}{ANY_FILE}", String::from_utf8_lossy(prefix.as_ref()));
+        self.rules.iter().rev().find(|r| r.regex.is_match(&probe))
+    }
+
+    pub fn owners_of(&self, prefix: &RepoPath) -> &[String] {
+        self.rule_for(prefix).map_or(&[], |r| &r.owners)
+    }
+}
+
+/// Split owners into the `(reviewers, team_reviewers)` GitHub's review
+/// request takes: logins and team slugs. E-mail owners can't be
+/// requested and are dropped.
+pub fn review_requests<'a>(
+    owners: impl IntoIterator<Item = &'a String>,
+) -> (Vec<String>, Vec<String>) {
+    let mut reviewers = Vec::new();
+    let mut teams = Vec::new();
+    for owner in owners {
+        let Some(handle) = owner.strip_prefix('@') else {
+            continue;
+        };
+        let (list, name) = match handle.split_once('/') {
+            Some((_org, team)) => (&mut teams, team),
+            None => (&mut reviewers, handle),
+        };
+        if !list.iter().any(|n: &String| n == name) {
+            list.push(name.to_owned());
+        }
+    }
+    (reviewers, teams)
+}
+
+/// Translate a gitignore-style pattern into a regex over repo paths.
+fn pattern_regex(pattern: &str) -> Result<Regex, regex::Error> {
+    let body = pattern.trim_start_matches('/').trim_end_matches('/');
+    // A slash anywhere but at the end pins the pattern to the root.
+    let anchored = pattern.starts_with('/') || body.contains('/');
+    // GitHub, unlike gitignore, doesn't let `dir/*` reach into subdirectories.
+    let descends = !body.ends_with("/*");
+
+    let mut re = String::from("^");
+    if !anchored {
+        re.push_str("(?:.*/)?");
+    }
+    let mut chars = body.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    re.push_str("(?:.*/)?");
+                } else {
+                    re.push_str(".*");
+                }
+            }
+            '*' => re.push_str("[^/]*"),
+            '?' => re.push_str("[^/]"),
+            _ => re.push_str(&regex::escape(c.encode_utf8(&mut [0; 4]))),
+        }
+    }
+    if descends {
+        re.push_str("(?:/.*)?");
+    }
+    re.push('$');
+    Regex::new(&re)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FILE: &str = "\
+# Default owners
+*                 @acme/maintainers
+/crates/core/     @alice @acme/core   # the engine
+apps/             @bob
+docs/*            docs@example.com
+*.md              @writers
+/vendor/
+";
+
+    fn owners(prefix: &str) -> Vec<String> {
+        CodeOwners::parse(FILE)
+            .owners_of(&RepoPathBuf::new(prefix.as_bytes()))
+            .to_vec()
+    }
+
+    #[test]
+    fn last_matching_rule_wins() {
+        assert_eq!(owners(""), ["@acme/maintainers"]);
+        assert_eq!(owners("crates/core/"), ["@alice", "@acme/core"]);
+        assert_eq!(owners("crates/core/benches/"), ["@alice", "@acme/core"]);
+        assert_eq!(owners("crates/other/"), ["@acme/maintainers"]);
+    }
+
+    #[test]
+    fn unanchored_directories_match_at_any_depth() {
+        assert_eq!(owners("apps/web/"), ["@bob"]);
+        assert_eq!(owners("services/apps/"), ["@bob"]);
+    }
+
+    #[test]
+    fn trailing_star_does_not_descend() {
+        assert_eq!(owners("docs/"), ["docs@example.com"]);
+        assert_eq!(owners("docs/site/"), ["@acme/maintainers"]);
+    }
+
+    #[test]
+    fn ownerless_rule_clears_owners() {
+        assert!(owners("vendor/lib/").is_empty());
+    }
+
+    #[test]
+    fn review_requests_split_users_and_teams() {
+        let all = [
+            "@alice".to_owned(),
+            "@acme/core".to_owned(),
+            "docs@example.com".to_owned(),
+            "@alice".to_owned(),
+        ];
+        let (users, teams) = review_requests(&all);
+        assert_eq!(users, ["alice"]);
+        assert_eq!(teams, ["core"]);
+    }
+}
@@ -0,0 +1,266 @@
+//! Which GitHub the repository lives on: github.com, or a GitHub
+//! Enterprise Server (GHES) install configured through `[forge]`.
+//!
+//! The CLI itself calls the GitHub API only with a personal access token
+//! (see [`super::rest`]); otherwise the belaf API does. For an
+//! enterprise forge the endpoints are forwarded to it on every request
+//! (see [`apply_headers`]) so it can route to the right instance; locally
+//! the forge decides which remotes count as GitHub and how web links
+//! (compare URLs, repository pages) are spelled. Certificates signed by a
+//! corporate CA are trusted via `[network] ca_bundle`.
+
+use std::sync::RwLock;
+
+use anyhow::{anyhow, bail, Result};
+use tracing::debug;
+
+use crate::core::config::{peek_section, syntax::ForgeConfiguration};
+use crate::core::git::url::parse_github_url;
+
+/// Header carrying the forge's REST endpoint to the belaf API.
+pub const API_URL_HEADER: &str = "x-belaf-forge-api-url";
+
+/// Header carrying the forge's GraphQL endpoint to the belaf API.
+pub const GRAPHQL_URL_HEADER: &str = "x-belaf-forge-graphql-url";
+
+const GITHUB_WEB_URL: &str = "https://github.com";
+const GITHUB_API_URL: &str = "https://api.github.com";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Forge {
+    web_url: String,
+    api_url: String,
+    graphql_url: String,
+}
+
+static FORGE: RwLock<Option<Forge>> = RwLock::new(None);
+
+impl Forge {
+    pub fn github_com() -> Self {
+        Forge {
+            web_url: GITHUB_WEB_URL.to_string(),
+            api_url: GITHUB_API_URL.to_string(),
+            graphql_url: format!("{GITHUB_API_URL}/graphql"),
+        }
+    }
+
+    /// Build the forge from `[forge]`. GHES serves REST under
+    /// `https://<host>/api/v3` and GraphQL under `https://<host>/api/graphql`;
+    /// the web URL and a missing `graphql_url` are derived from `api_url`.
+    pub fn from_config(cfg: &ForgeConfiguration) -> Result<Self> {
+        let Some(api_url) = cfg.api_url.as_deref() else {
+            if cfg.graphql_url.is_some() {
+                bail!("[forge] graphql_url requires api_url to be set as well");
+            }
+            return Ok(Self::github_com());
+        };
+
+        let api_url = validate_url("api_url", api_url)?;
+        let web_url = web_url_for(&api_url)?;
+        let graphql_url = match cfg.graphql_url.as_deref() {
+            Some(url) => validate_url("graphql_url", url)?,
+            None if api_url == GITHUB_API_URL => format!("{GITHUB_API_URL}/graphql"),
+            None => format!("{web_url}/api/graphql"),
+        };
+
+        Ok(Forge {
+            web_url,
+            api_url,
+            graphql_url,
+        })
+    }
+
+    pub fn api_url(&self) -> &str {
+        &self.api_url
+    }
+
+    pub fn graphql_url(&self) -> &str {
+        &self.graphql_url
+    }
+
+    pub fn web_url(&self) -> &str {
+        &self.web_url
+    }
+
+    /// Host name the forge's git remotes use.
+    pub fn host(&self) -> &str {
+        host_of(&self.web_url).unwrap_or_default()
+    }
+
+    pub fn is_enterprise(&self) -> bool {
+        self.web_url != GITHUB_WEB_URL
+    }
+
+    /// `(owner, repo)` of a remote on this forge. Remotes on any other host
+    /// are rejected so a GitLab or mirror remote is never mistaken for one.
+    pub fn parse_remote(&self, url: &str) -> Result<(String, String)> {
+        let host = host_of(url).ok_or_else(|| anyhow!("Could not parse GitHub URL: {}", url))?;
+        if !host.eq_ignore_ascii_case(self.host()) {
+            bail!(
+                "remote `{}` is not on {} (configure `[forge] api_url` for GitHub Enterprise)",
+                url,
+                self.host()
+            );
+        }
+        parse_github_url(url)
+    }
+
+    /// Web page of `owner/repo`, the base for compare and PR links.
+    pub fn repo_url(&self, owner: &str, repo: &str) -> String {
+        format!("{}/{}/{}", self.web_url, owner, repo)
+    }
+
+    /// [`Self::repo_url`] for a remote, if it lives on this forge.
+    pub fn repo_url_for_remote(&self, url: &str) -> Option<String> {
+        let (owner, repo) = self.parse_remote(url).ok()?;
+        Some(self.repo_url(&owner, &repo))
+    }
+}
+
+fn validate_url(key: &str, url: &str) -> Result<String> {
+    let url = url.trim().trim_end_matches('/');
+    if !url.starts_with("https://") || host_of(url).is_none_or(str::is_empty) {
+        bail!("[forge] {key} must be an https:// URL, got `{url}`");
+    }
+    Ok(url.to_string())
+}
+
+fn web_url_for(api_url: &str) -> Result<String> {
+    if api_url == GITHUB_API_URL {
+        return Ok(GITHUB_WEB_URL.to_string());
+    }
+    if let Some(web) = api_url.strip_suffix("/api/v3") {
+        return Ok(web.to_string());
+    }
+    // GHES with subdomain isolation may also be reached as `api.<host>`.
+    if let Some(host) = api_url.strip_prefix("https://api.") {
+        if !host.contains('/') {
+            return Ok(format!("https://{host}"));
+        }
+    }
+    bail!("[forge] api_url `{api_url}` does not look like a GitHub Enterprise API URL (expected `https://<host>/api/v3`)")
+}
+
+/// Host of an https, ssh or scp-style (`git@host:owner/repo`) URL.
+fn host_of(url: &str) -> Option<&str> {
+    let rest = match url.split_once("://") {
+        Some((_, rest)) => rest,
+        None if url.contains(':') => url,
+        None => return None,
+    };
+    let rest = rest.rsplit_once('@').map_or(rest, |(_, r)| r);
+    let end = rest.find([':', '/']).unwrap_or(rest.len());
+    Some(&rest[..end])
+}
+
+/// Install the forge for this process.
+pub fn configure(cfg: &ForgeConfiguration) -> Result<()> {
+    let forge = Forge::from_config(cfg)?;
+    if let Ok(mut slot) = FORGE.write() {
+        *slot = Some(forge);
+    }
+    Ok(())
+}
+
+/// Best-effort early configuration from `[forge]` of the current
+/// repository, for commands that never open a session (`belaf install`,
+/// `belaf auth …`). Invalid settings are reported once the session loads.
+pub fn configure_from_env() {
+    let Some((cfg, _)) = peek_section::<ForgeConfiguration>("forge") else {
+        if let Ok(mut slot) = FORGE.write() {
+            *slot = None;
+        }
+        return;
+    };
+    if let Err(e) = configure(&cfg) {
+        debug!("ignoring [forge] until the config is loaded: {e}");
+    }
+}
+
+/// The forge in effect; github.com unless `[forge]` says otherwise.
+pub fn current() -> Forge {
+    FORGE
+        .read()
+        .ok()
+        .and_then(|slot| slot.clone())
+        .unwrap_or_else(Forge::github_com)
+}
+
+/// Tell the belaf API which GitHub to talk to. A no-op for github.com,
+/// so requests look exactly as before for most users.
+pub fn apply_headers(request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+    let forge = current();
+    if !forge.is_enterprise() {
+        return request;
+    }
+    request
+        .header(API_URL_HEADER, forge.api_url())
+        .header(GRAPHQL_URL_HEADER, forge.graphql_url())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ghes(api_url: &str) -> ForgeConfiguration {
+        ForgeConfiguration {
+            api_url: Some(api_url.to_string()),
+            graphql_url: None,
+        }
+    }
+
+    #[test]
+    fn defaults_to_github_com() {
+        let forge = Forge::from_config(&ForgeConfiguration::default()).unwrap();
+        assert_eq!(forge, Forge::github_com());
+        assert!(!forge.is_enterprise());
+        assert_eq!(forge.host(), "github.com");
+    }
+
+    #[test]
+    fn derives_ghes_endpoints_from_api_url() {
+        let forge = Forge::from_config(&ghes("https://ghe.corp.example/api/v3/")).unwrap();
+        assert!(forge.is_enterprise());
+        assert_eq!(forge.web_url(), "https://ghe.corp.example");
+        assert_eq!(forge.api_url(), "https://ghe.corp.example/api/v3");
+        assert_eq!(forge.graphql_url(), "https://ghe.corp.example/api/graphql");
+        assert_eq!(forge.host(), "ghe.corp.example");
+
+        let forge = Forge::from_config(&ForgeConfiguration {
+            api_url: Some("https://ghe.corp.example/api/v3".into()),
+            graphql_url: Some("https://graphql.corp.example".into()),
+        })
+        .unwrap();
+        assert_eq!(forge.graphql_url(), "https://graphql.corp.example");
+    }
+
+    #[test]
+    fn rejects_unusable_config() {
+        assert!(Forge::from_config(&ghes("http://ghe.corp/api/v3")).is_err());
+        assert!(Forge::from_config(&ghes("https://ghe.corp/rest")).is_err());
+        assert!(Forge::from_config(&ForgeConfiguration {
+            api_url: None,
+            graphql_url: Some("https://ghe.corp/api/graphql".into()),
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn parses_remotes_on_the_forge_only() {
+        let forge = Forge::from_config(&ghes("https://ghe.corp/api/v3")).unwrap();
+        assert_eq!(
+            forge.parse_remote("git@ghe.corp:acme/widgets.git").unwrap(),
+            ("acme".to_string(), "widgets".to_string())
+        );
+        assert_eq!(
+            forge.repo_url_for_remote("ssh://git@ghe.corp:2222/acme/widgets.git"),
+            Some("https://ghe.corp/acme/widgets".to_string())
+        );
+        assert!(forge
+            .parse_remote("https://github.com/acme/widgets")
+            .is_err());
+        assert!(Forge::github_com()
+            .parse_remote("git@ghe.corp:acme/widgets.git")
+            .is_err());
+    }
+}
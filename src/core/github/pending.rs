@@ -0,0 +1,232 @@
+//! Release pull requests that are open but not merged yet.
+//!
+//! Every release PR belaf opens comes from a `release/<timestamp>-<id>`
+//! branch whose single commit adds one manifest under `belaf/releases/`.
+//! The belaf API reports the forge-side state of those PRs (URL, check
+//! runs, mergeability); the manifest, read from the remote-tracking
+//! branch, says which release units and groups each one covers. When the
+//! branch has not been fetched, the units are recovered from the PR title.
+
+use serde::Serialize;
+use tracing::{debug, warn};
+
+use crate::core::{
+    api::{ApiClient, ApiError, ApiPullRequest, StoredToken},
+    auth::token::load_or_exchange_token,
+    git::repository::Repository,
+    github::forge,
+    manifest::MANIFEST_DIR,
+    releases,
+    session::AppSession,
+};
+
+/// Branch prefix of release PRs (see
+/// [`Repository::generate_release_branch_name`]).
+pub const RELEASE_BRANCH_PREFIX: &str = "release/";
+
+const PER_PAGE: u32 = 100;
+const MAX_PAGES: u32 = 10;
+
+/// One open release PR.
+#[derive(Clone, Debug, Serialize)]
+pub struct PendingRelease {
+    pub number: i64,
+    pub title: Option<String>,
+    pub url: Option<String>,
+    pub branch: String,
+    /// Combined check-run state: `success`, `failure`, `pending`, or
+    /// `None` when no checks ran.
+    pub checks: Option<String>,
+    /// `None` while GitHub is still computing mergeability.
+    pub mergeable: Option<bool>,
+    pub units: Vec<String>,
+    pub groups: Vec<String>,
+    /// The releases its manifest plans; empty when the branch has not
+    /// been fetched.
+    #[serde(skip)]
+    pub planned: Vec<PlannedRelease>,
+}
+
+/// One release of an open release PR, as its manifest records it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlannedRelease {
+    pub name: String,
+    pub new_version: String,
+    /// Ids of the commits it ships; `None` for a manifest written before
+    /// belaf recorded them.
+    pub commits: Option<Vec<String>>,
+}
+
+impl PendingRelease {
+    fn from_pull_request(repo: &Repository, pr: ApiPullRequest) -> Self {
+        let branch = pr.head_ref.unwrap_or_default();
+        let (mut units, mut groups, mut planned) = (Vec::new(), Vec::new(), Vec::new());
+
+        match repo.files_added_on_upstream_branch(&branch, MANIFEST_DIR) {
+            Ok(files) => {
+                for (name, content) in files {
+                    let parsed = std::str::from_utf8(&content)
+                        .ok()
+                        .and_then(|json| releases::parse(json).ok());
+                    let Some(manifest) = parsed else {
+                        debug!("ignoring unreadable manifest `{name}` on `{branch}`");
+                        continue;
+                    };
+                    for release in manifest.releases {
+                        groups.extend(release.group_id);
+                        planned.push(PlannedRelease {
+                            name: release.name.clone(),
+                            new_version: release.new_version,
+                            commits: release.commit_range.is_some().then_some(release.commits),
+                        });
+                        units.push(release.name);
+                    }
+                }
+            }
+            Err(e) => debug!("cannot read manifests of `{branch}`: {e:#}"),
+        }
+
+        if units.is_empty() {
+            units = pr
+                .title
+                .as_deref()
+                .map(units_from_title)
+                .unwrap_or_default();
+        }
+        units.sort();
+        units.dedup();
+        groups.sort();
+        groups.dedup();
+
+        PendingRelease {
+            number: pr.number,
+            title: pr.title,
+            url: pr.html_url,
+            branch,
+            checks: pr.checks_status,
+            mergeable: pr.mergeable,
+            units,
+            groups,
+            planned,
+        }
+    }
+
+    /// Whether this PR releases the unit called `name`.
+    pub fn covers(&self, name: &str) -> bool {
+        self.units.iter().any(|u| u == name)
+    }
+
+    pub fn checks_label(&self) -> &'static str {
+        match self.checks.as_deref() {
+            Some("success") => "checks passing",
+            Some("failure") => "checks failing",
+            Some("pending") => "checks running",
+            Some(_) => "checks unknown",
+            None => "no checks",
+        }
+    }
+
+    pub fn mergeable_label(&self) -> &'static str {
+        match self.mergeable {
+            Some(true) => "mergeable",
+            Some(false) => "has conflicts",
+            None => "mergeability pending",
+        }
+    }
+}
+
+/// Unit names from a title made by [`crate::core::github::pr::generate_pr_title`],
+/// e.g. `chore(release): core v1.0.0, utils v2.1.0`. The `N packages`
+/// form names nothing.
+fn units_from_title(title: &str) -> Vec<String> {
+    let Some(list) = title.strip_prefix("chore(release): ") else {
+        return Vec::new();
+    };
+    list.split(", ")
+        .filter_map(|item| item.rsplit_once(" v").map(|(name, _)| name.to_string()))
+        .collect()
+}
+
+/// All open PRs whose head is a belaf release branch.
+pub async fn list_open_release_prs(
+    client: &ApiClient,
+    token: &StoredToken,
+    owner: &str,
+    repo: &str,
+) -> Result<Vec<ApiPullRequest>, ApiError> {
+    let mut found = Vec::new();
+    for page in 1..=MAX_PAGES {
+        let prs = client
+            .get_open_pull_requests(token, owner, repo, page, PER_PAGE)
+            .await?;
+        let last_page = prs.len() < PER_PAGE as usize;
+        found.extend(prs.into_iter().filter(|pr| {
+            pr.head_ref
+                .as_deref()
+                .is_some_and(|r| r.starts_with(RELEASE_BRANCH_PREFIX))
+        }));
+        if last_page {
+            break;
+        }
+    }
+    Ok(found)
+}
+
+/// Open release PRs on the session's upstream. `None` means the forge
+/// could not be asked — no GitHub remote, not logged in, or the API
+/// failed — which callers must treat as "unknown", not "none pending".
+pub fn find_pending_releases(sess: &AppSession) -> Option<Vec<PendingRelease>> {
+    let upstream_url = sess.repo.upstream_url().ok()?;
+    let (owner, repo) = forge::current().parse_remote(&upstream_url).ok()?;
+
+    let future = async {
+        let api_client = ApiClient::try_new()?;
+        let Some(token) = load_or_exchange_token(&api_client).await? else {
+            return Ok::<_, anyhow::Error>(None);
+        };
+        let prs = list_open_release_prs(&api_client, &token, &owner, &repo).await?;
+        Ok(Some(prs))
+    };
+
+    let result = match tokio::runtime::Handle::try_current() {
+        Ok(handle) => tokio::task::block_in_place(|| handle.block_on(future)),
+        Err(_) => tokio::runtime::Runtime::new()
+            .map_err(anyhow::Error::from)
+            .and_then(|rt| rt.block_on(future)),
+    };
+
+    match result {
+        Ok(Some(prs)) => Some(
+            prs.into_iter()
+                .map(|pr| PendingRelease::from_pull_request(&sess.repo, pr))
+                .collect(),
+        ),
+        Ok(None) => {
+            debug!("not logged in; skipping the open release PR lookup");
+            None
+        }
+        Err(e) => {
+            warn!("could not list open release PRs: {e:#}");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn units_from_generated_titles() {
+        assert_eq!(
+            units_from_title("chore(release): my-crate v1.2.0"),
+            ["my-crate"]
+        );
+        assert_eq!(
+            units_from_title("chore(release): @acme/core v1.0.0, utils v2.1.0-rc.1"),
+            ["@acme/core", "utils"]
+        );
+        assert!(units_from_title("chore(release): 5 packages").is_empty());
+        assert!(units_from_title("Bump serde").is_empty());
+    }
+}
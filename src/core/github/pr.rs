@@ -1,7 +1,9 @@
 //! Pull Request content generation for release PRs.
 //!
 //! Generates formatted PR titles and bodies for release pull requests,
-//! including version tables, ecosystem badges, and changelog summaries.
+//! including version tables, ecosystem badges, and changelog summaries,
+//! and the preview comment and labels `belaf comment` and `belaf label`
+//! keep on feature PRs.
 //!
 //! # Generated PR Format
 //!
@@ -35,9 +37,26 @@
 //! |---------|-----------|---------|------|
 //! | **my-crate** | 🦀 Rust | `1.0.0` → `1.1.0` | 🟡 MINOR |
 //!
+//! ### 👥 Owners
+//! - **my-crate**: @acme/core
+//!
+//! ### ⚠️ Breaking Changes
+//! - **my-crate**: drop the v1 API
+//!
+//!   Clients must call `/v2` instead.
+//!
 //! ### 📝 Changelogs
 //! [changelog content here]
 //!
+//! ### ✏️ Edit Log
+//! Prepared again while #12 was open. Since then:
+//!
+//! - **my-crate**: `1.1.0` → `1.2.0`
+//!   - new: feat: add retries
+//!
+//! ### ☑️ Release Checklist
+//! - [ ] **my-crate**: Update the status page
+//!
 //! ### 📋 Release Manifest
 //! 📄 `belaf/releases/release-20250605-123456.json`
 //!
@@ -49,7 +68,13 @@
 
 use std::collections::HashMap;
 
-use crate::core::workflow::SelectedReleaseUnit;
+use crate::core::{
+    changelog::Commit,
+    checklist,
+    github::rest::Label,
+    pr_impact::UnitImpact,
+    workflow::{ReleaseEdits, SelectedReleaseUnit},
+};
 
 const MAX_PROJECTS_IN_TITLE: usize = 3;
 
@@ -80,9 +105,14 @@ pub fn generate_pr_title(projects: &[SelectedReleaseUnit]) -> String {
 /// # Sections
 ///
 /// 1. **Packages table** - Shows each package with ecosystem badge, version diff, and bump badge
-/// 2. **Changelogs** - Inline for single package, collapsible `<details>` for multiple
-/// 3. **Manifest link** - Points to `belaf/releases/{filename}.json`
-/// 4. **Next steps** - Documents GitHub App automation
+/// 2. **Owners** - `CODEOWNERS` owners per package, only when any package has some
+/// 3. **Breaking changes** - The `BREAKING CHANGE:` footers of the packages' commits,
+///    only when there are some
+/// 4. **Changelogs** - Inline for single package, collapsible `<details>` for multiple
+/// 5. **Release checklist** - The packages' `release_checklist` steps as a task list,
+///    only when there are some; see [`checklist`]
+/// 6. **Manifest link** - Points to `belaf/releases/{filename}.json`
+/// 7. **Next steps** - Documents GitHub App automation
 ///
 /// # Badge Examples
 ///
@@ -93,6 +123,7 @@ pub fn generate_pr_body(
     projects: &[SelectedReleaseUnit],
     manifest_filename: &str,
     changelog_contents: &HashMap<String, String>,
+    edits: &[ReleaseEdits],
 ) -> String {
     let mut body = String::new();
 
@@ -114,6 +145,37 @@ pub fn generate_pr_body(
         ));
     }
 
+    if projects.iter().any(|p| !p.owners.is_empty()) {
+        body.push_str("\n### 👥 Owners\n\n");
+        for project in projects.iter().filter(|p| !p.owners.is_empty()) {
+            body.push_str(&format!(
+                "- **{}**: {}\n",
+                project.name,
+                project.owners.join(" ")
+            ));
+        }
+    }
+
+    let breaking: Vec<(&str, BreakingChange)> = projects
+        .iter()
+        .flat_map(|p| breaking_changes(&p.commits).map(move |c| (p.name.as_str(), c)))
+        .collect();
+    if !breaking.is_empty() {
+        body.push_str("\n### ⚠️ Breaking Changes\n\n");
+        for (name, change) in &breaking {
+            body.push_str(&format!("- **{}**: {}\n", name, change.subject));
+            body.push('\n');
+            for line in change.description.lines() {
+                if line.trim().is_empty() {
+                    body.push('\n');
+                } else {
+                    body.push_str(&format!("  {}\n", line));
+                }
+            }
+            body.push('\n');
+        }
+    }
+
     body.push_str("\n### 📝 Changelogs\n\n");
 
     if projects.len() == 1 {
@@ -135,6 +197,19 @@ pub fn generate_pr_body(
         }
     }
 
+    if !edits.is_empty() {
+        body.push_str("### ✏️ Edit Log\n\n");
+        for release_edits in edits {
+            body.push_str(&release_edits.to_markdown());
+            body.push('\n');
+        }
+    }
+
+    if let Some(section) = checklist::render(projects) {
+        body.push_str(&section);
+        body.push('\n');
+    }
+
     body.push_str("### 📋 Release Manifest\n\n");
     body.push_str(&format!("📄 `belaf/releases/{}`\n\n", manifest_filename));
 
@@ -148,6 +223,153 @@ pub fn generate_pr_body(
     body
 }
 
+/// Marks the preview comment, so `belaf comment` edits it instead of
+/// posting another.
+pub const PREVIEW_MARKER: &str = "<!-- belaf:pr-preview -->";
+
+/// Label of feature PRs with a breaking change.
+pub const BREAKING_LABEL: &str = "breaking";
+
+/// Generates the preview comment for a feature PR whose head is `head`.
+///
+/// # Output Example
+///
+/// ```text
+/// <!-- belaf:pr-preview -->
+/// ## 🔮 Release Preview
+///
+/// Merging this PR adds to the next release of:
+///
+/// | Package | Ecosystem | Version | Bump |
+/// |---------|-----------|---------|------|
+/// | **my-crate** | 🦀 Rust | `1.0.0` → `1.1.0` | 🟡 MINOR |
+///
+/// ### 📝 Changelog
+/// [changelog entry of the PR's commits]
+/// ```
+pub fn generate_preview_comment(units: &[UnitImpact], head: &str) -> String {
+    let mut body = String::new();
+
+    body.push_str(PREVIEW_MARKER);
+    body.push_str("\n## 🔮 Release Preview\n\n");
+
+    if units.is_empty() {
+        body.push_str("This PR changes no release unit.\n");
+    } else {
+        body.push_str("Merging this PR adds to the next release of:\n\n");
+        body.push_str("| Package | Ecosystem | Version | Bump |\n");
+        body.push_str("|---------|-----------|---------|------|\n");
+        for unit in units {
+            let version = match &unit.next_version {
+                Some(next) => format!("`{}` → `{}`", unit.version, next),
+                None => format!("`{}`", unit.version),
+            };
+            body.push_str(&format!(
+                "| **{}** | {} | {} | {} |\n",
+                unit.name,
+                ecosystem_badge(unit.ecosystem.display_name()),
+                version,
+                bump_badge(&unit.bump_type)
+            ));
+        }
+
+        let entries: Vec<(&UnitImpact, &String)> = units
+            .iter()
+            .filter_map(|u| u.changelog.as_ref().map(|c| (u, c)))
+            .collect();
+        if !entries.is_empty() {
+            body.push_str("\n### 📝 Changelog\n\n");
+        }
+        if let [(_, changelog)] = entries[..] {
+            body.push_str(changelog);
+            body.push('\n');
+        } else {
+            for (unit, changelog) in entries {
+                body.push_str(&format!(
+                    "<details>\n<summary><strong>{}</strong></summary>\n\n",
+                    unit.name
+                ));
+                body.push_str(changelog);
+                body.push_str("\n</details>\n\n");
+            }
+        }
+    }
+
+    body.push_str(&format!(
+        "\n---\n\n<sub>Updated by `belaf comment` for {}.</sub>\n",
+        &head[..head.len().min(8)]
+    ));
+    body
+}
+
+/// Generates the labels of a feature PR: `area:<unit>` for each unit it
+/// changes, `semver:<bump>` for the largest bump among them, and
+/// [`BREAKING_LABEL`] when any of its commits is a breaking change.
+///
+/// # Output Example
+///
+/// `area:api`, `area:web`, `semver:minor`
+pub fn generate_pr_labels(units: &[UnitImpact]) -> Vec<Label> {
+    let mut labels: Vec<Label> = units
+        .iter()
+        .map(|unit| Label {
+            name: format!("area:{}", unit.name),
+            color: "c5def5".to_string(),
+            description: format!("Changes {}", unit.name),
+        })
+        .collect();
+
+    let bump = ["major", "minor", "patch"]
+        .into_iter()
+        .find(|level| units.iter().any(|u| u.bump_type == *level));
+    if let Some(bump) = bump {
+        labels.push(Label {
+            name: format!("semver:{bump}"),
+            color: match bump {
+                "major" => "d73a4a",
+                "minor" => "fbca04",
+                _ => "0e8a16",
+            }
+            .to_string(),
+            description: format!("Calls for a {bump} release"),
+        });
+    }
+    if units.iter().any(|u| u.breaking) {
+        labels.push(Label {
+            name: BREAKING_LABEL.to_string(),
+            color: "b60205".to_string(),
+            description: "Contains a breaking change".to_string(),
+        });
+    }
+    labels
+}
+
+/// Whether `belaf label` put `name` on PRs, and takes it off again when
+/// it no longer applies.
+pub fn is_generated_label(name: &str) -> bool {
+    name.starts_with("area:") || name.starts_with("semver:") || name == BREAKING_LABEL
+}
+
+/// A `BREAKING CHANGE:` footer and the subject of its commit.
+struct BreakingChange {
+    subject: String,
+    description: String,
+}
+
+/// The `BREAKING CHANGE:` footers of `commits`. Commits marked breaking
+/// only by `!` carry no description beyond their subject, which the
+/// changelog already shows.
+fn breaking_changes(commits: &[Commit]) -> impl Iterator<Item = BreakingChange> + '_ {
+    commits.iter().filter_map(|commit| {
+        let conv = git_conventional::Commit::parse(&commit.message).ok()?;
+        let footer = conv.footers().iter().find(|f| f.breaking())?;
+        Some(BreakingChange {
+            subject: conv.description().to_owned(),
+            description: footer.value().trim().to_owned(),
+        })
+    })
+}
+
 fn ecosystem_badge(ecosystem: &str) -> String {
     match ecosystem {
         "Rust" => "🦀 Rust".to_string(),
@@ -165,6 +387,8 @@ fn bump_badge(bump_type: &str) -> String {
         "major" => "🔴 **MAJOR**".to_string(),
         "minor" => "🟡 MINOR".to_string(),
         "patch" => "🟢 patch".to_string(),
+        "manual" => "✋ manual".to_string(),
+        "promote" => "🎓 promote".to_string(),
         _ => bump_type.to_string(),
     }
 }
@@ -185,6 +409,9 @@ mod tests {
             commits: vec![],
             ecosystem: Ecosystem::classify("cargo"),
             cached_changelog: None,
+            owners: vec![],
+            promoted_changelog: None,
+            checklist: vec![],
         }
     }
 
@@ -233,7 +460,7 @@ mod tests {
     fn test_pr_body_contains_packages_table() {
         let projects = vec![make_project("test-crate", "1.0.0", "2.0.0", "major")];
         let changelog_contents = HashMap::new();
-        let body = generate_pr_body(&projects, "release-test.json", &changelog_contents);
+        let body = generate_pr_body(&projects, "release-test.json", &changelog_contents, &[]);
 
         assert!(body.contains("## 🚀 Release Preparation"));
         assert!(body.contains("### 📦 Packages"));
@@ -246,7 +473,12 @@ mod tests {
     #[test]
     fn test_pr_body_contains_manifest_link() {
         let projects = vec![make_project("test", "1.0.0", "1.0.1", "patch")];
-        let body = generate_pr_body(&projects, "release-20250101-abc123.json", &HashMap::new());
+        let body = generate_pr_body(
+            &projects,
+            "release-20250101-abc123.json",
+            &HashMap::new(),
+            &[],
+        );
 
         assert!(body.contains("### 📋 Release Manifest"));
         assert!(body.contains("📄 `belaf/releases/release-20250101-abc123.json`"));
@@ -255,7 +487,7 @@ mod tests {
     #[test]
     fn test_pr_body_contains_next_steps() {
         let projects = vec![make_project("test", "1.0.0", "1.0.1", "patch")];
-        let body = generate_pr_body(&projects, "release.json", &HashMap::new());
+        let body = generate_pr_body(&projects, "release.json", &HashMap::new(), &[]);
 
         assert!(body.contains("### ✅ Next Steps"));
         assert!(body.contains("belaf GitHub App"));
@@ -271,7 +503,7 @@ mod tests {
             "my-crate".to_string(),
             "## Features\n- Added new feature".to_string(),
         );
-        let body = generate_pr_body(&projects, "release.json", &changelog_contents);
+        let body = generate_pr_body(&projects, "release.json", &changelog_contents, &[]);
 
         assert!(body.contains("### 📝 Changelogs"));
         assert!(body.contains("## Features"));
@@ -288,7 +520,7 @@ mod tests {
         let mut changelog_contents = HashMap::new();
         changelog_contents.insert("core".to_string(), "Core changes".to_string());
         changelog_contents.insert("utils".to_string(), "Utils fixes".to_string());
-        let body = generate_pr_body(&projects, "release.json", &changelog_contents);
+        let body = generate_pr_body(&projects, "release.json", &changelog_contents, &[]);
 
         assert!(body.contains("<details>"));
         assert!(body.contains("<summary><strong>core</strong>"));
@@ -296,6 +528,160 @@ mod tests {
         assert!(body.contains("</details>"));
     }
 
+    #[test]
+    fn test_pr_body_lists_owners() {
+        let mut core = make_project("core", "1.0.0", "1.1.0", "minor");
+        core.owners = vec!["@alice".to_string(), "@acme/core".to_string()];
+        let utils = make_project("utils", "2.0.0", "2.0.1", "patch");
+        let body = generate_pr_body(&[core, utils], "release.json", &HashMap::new(), &[]);
+
+        assert!(body.contains("### 👥 Owners"));
+        assert!(body.contains("- **core**: @alice @acme/core"));
+        assert!(!body.contains("- **utils**"));
+
+        let body = generate_pr_body(
+            &[make_project("utils", "2.0.0", "2.0.1", "patch")],
+            "release.json",
+            &HashMap::new(),
+            &[],
+        );
+        assert!(!body.contains("### 👥 Owners"));
+    }
+
+    #[test]
+    fn test_pr_body_carries_the_release_checklist() {
+        let mut api = make_project("api", "1.0.0", "1.1.0", "minor");
+        api.checklist = vec!["Run the migration".to_string()];
+        let body = generate_pr_body(&[api], "release.json", &HashMap::new(), &[]);
+
+        let checklist = body.find(checklist::HEADING).unwrap();
+        assert!(checklist < body.find("### 📋 Release Manifest").unwrap());
+        assert!(body.contains("- [ ] **api**: Run the migration"));
+        let step = ["**api**: Run the migration".to_string()];
+        assert_eq!(checklist::unticked(&body, &step), step);
+
+        let body = generate_pr_body(
+            &[make_project("api", "1.0.0", "1.1.0", "minor")],
+            "release.json",
+            &HashMap::new(),
+            &[],
+        );
+        assert!(!body.contains(checklist::HEADING));
+    }
+
+    #[test]
+    fn test_pr_body_lists_breaking_change_descriptions() {
+        let mut core = make_project("core", "1.0.0", "2.0.0", "major");
+        core.commits = vec![
+            Commit::new(
+                "a".repeat(40),
+                "feat(api)!: drop the v1 API\n\nBREAKING CHANGE: clients must call `/v2`.\n\nSee the migration guide."
+                    .to_string(),
+            ),
+            Commit::new("b".repeat(40), "feat!: rename the config file".to_string()),
+            Commit::new("c".repeat(40), "fix: typo".to_string()),
+        ];
+        let body = generate_pr_body(&[core], "release.json", &HashMap::new(), &[]);
+
+        assert!(body.contains("### ⚠️ Breaking Changes"));
+        assert!(body.contains(
+            "- **core**: drop the v1 API\n\n  clients must call `/v2`.\n\n  See the migration guide.\n"
+        ));
+        assert!(!body.contains("rename the config file"));
+
+        let body = generate_pr_body(
+            &[make_project("utils", "2.0.0", "2.0.1", "patch")],
+            "release.json",
+            &HashMap::new(),
+            &[],
+        );
+        assert!(!body.contains("### ⚠️ Breaking Changes"));
+    }
+
+    #[test]
+    fn test_pr_body_carries_the_edit_log() {
+        use crate::core::workflow::UnitEdit;
+
+        let projects = vec![make_project("core", "1.0.0", "1.2.0", "minor")];
+        let edits = ReleaseEdits {
+            number: 12,
+            url: None,
+            edits: vec![UnitEdit::Changed {
+                name: "core".to_string(),
+                from: "1.1.0".to_string(),
+                to: "1.2.0".to_string(),
+                new_commits: vec!["feat: add retries".to_string()],
+            }],
+        };
+        let body = generate_pr_body(&projects, "release.json", &HashMap::new(), &[edits]);
+
+        assert!(body.contains(
+            "### ✏️ Edit Log\n\nPrepared again while #12 was open. Since then:\n\n\
+             - **core**: `1.1.0` → `1.2.0`\n  - new: feat: add retries\n"
+        ));
+        let body = generate_pr_body(&projects, "release.json", &HashMap::new(), &[]);
+        assert!(!body.contains("Edit Log"));
+    }
+
+    fn make_preview(name: &str, next: Option<&str>, changelog: Option<&str>) -> UnitImpact {
+        UnitImpact {
+            name: name.to_string(),
+            ecosystem: Ecosystem::classify("cargo"),
+            version: "1.0.0".to_string(),
+            next_version: next.map(String::from),
+            bump_type: if next.is_some() { "minor" } else { "no bump" }.to_string(),
+            breaking: false,
+            changelog: changelog.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_preview_comment_lists_affected_units() {
+        let units = vec![
+            make_preview("core", Some("1.1.0"), Some("### Features\n- add retries")),
+            make_preview("docs", None, None),
+        ];
+        let body = generate_preview_comment(&units, "0123456789abcdef");
+        assert!(body.starts_with(PREVIEW_MARKER));
+        assert!(body.contains("| **core** | Rust (Cargo) | `1.0.0` → `1.1.0` | 🟡 MINOR |"));
+        assert!(body.contains("| **docs** | Rust (Cargo) | `1.0.0` | no bump |"));
+        assert!(body.contains("### 📝 Changelog\n\n### Features\n- add retries\n"));
+        assert!(!body.contains("<details>"));
+        assert!(body.contains("for 01234567."));
+    }
+
+    #[test]
+    fn test_preview_comment_without_units() {
+        let body = generate_preview_comment(&[], "0123456789abcdef");
+        assert!(body.contains("This PR changes no release unit."));
+        assert!(!body.contains("| Package |"));
+    }
+
+    #[test]
+    fn test_pr_labels_name_units_and_the_largest_bump() {
+        let mut units = vec![
+            make_preview("api", Some("1.1.0"), None),
+            make_preview("docs", None, None),
+        ];
+        let names = |units: &[UnitImpact]| -> Vec<String> {
+            generate_pr_labels(units)
+                .into_iter()
+                .map(|l| l.name)
+                .collect()
+        };
+        assert_eq!(names(&units), ["area:api", "area:docs", "semver:minor"]);
+
+        units[1].bump_type = "major".to_string();
+        units[1].breaking = true;
+        assert_eq!(
+            names(&units),
+            ["area:api", "area:docs", "semver:major", "breaking"]
+        );
+        assert!(names(&units).iter().all(|n| is_generated_label(n)));
+        assert!(!is_generated_label("needs review"));
+        assert!(generate_pr_labels(&[]).is_empty());
+    }
+
     #[test]
     fn test_ecosystem_badges() {
         assert_eq!(ecosystem_badge("Rust"), "🦀 Rust");
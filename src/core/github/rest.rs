@@ -0,0 +1,1038 @@
+//! Calls to the GitHub REST API made with a personal access token.
+//!
+//! Everything else goes through the belaf API (see [`super::forge`]).
+//! With a token stored by `belaf auth login --with-token`, the token is
+//! checked against the repository here, and `prepare` opens the release
+//! pull request with it directly; see [`crate::core::auth::pat`].
+//! `belaf comment` and `belaf label` update feature pull requests here
+//! too, with that token or the one CI provides; see [`for_upstream`].
+//! `belaf release` manages draft releases and their assets here as well.
+
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context, Result};
+use reqwest::{header::HeaderMap, Method};
+use serde_json::{json, Value};
+use tracing::{info, warn};
+
+use crate::core::auth::pat;
+use crate::core::auth::permissions::{AccessError, Permission};
+use crate::core::env;
+use crate::core::git::{repository::Repository, url::parse_github_url};
+use crate::core::github::{forge, pending::RELEASE_BRANCH_PREFIX};
+
+const TIMEOUT_SECS: u64 = 30;
+/// Pages of 100 read from a list endpoint before giving up.
+const LIST_PAGES: u32 = 10;
+
+/// A REST client for the configured forge, authenticated with a token.
+pub struct RestClient {
+    http: reqwest::Client,
+    api_url: String,
+    token: String,
+}
+
+/// A label to put on a pull request; see [`RestClient::sync_labels`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Label {
+    pub name: String,
+    /// Hex color, without the `#`, used when the label is created.
+    pub color: String,
+    pub description: String,
+}
+
+/// A release to create as a draft; see [`RestClient::create_draft_release`].
+#[derive(Clone, Debug)]
+pub struct NewRelease<'a> {
+    pub tag_name: &'a str,
+    /// Commit the tag is created at, should it not exist on the forge.
+    pub target: &'a str,
+    pub name: &'a str,
+    pub body: &'a str,
+    pub prerelease: bool,
+}
+
+/// A draft release created by [`RestClient::create_draft_release`].
+#[derive(Clone, Debug)]
+pub struct DraftRelease {
+    pub id: u64,
+    /// Web page of the release.
+    pub url: String,
+    /// Where its assets are uploaded, without the URI template part.
+    pub upload_url: String,
+}
+
+/// A file attached to a release.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReleaseAsset {
+    pub name: String,
+    pub size: u64,
+    /// Hex SHA-256, when the forge reports a digest.
+    pub sha256: Option<String>,
+}
+
+/// The pull request that added a release manifest, with who prepared it
+/// and who approved it on the forge; see
+/// [`RestClient::release_pull_request`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReleasePullRequest {
+    pub number: u64,
+    /// Web page of the pull request.
+    pub url: String,
+    /// Logins that prepared the release: whoever opened the pull request
+    /// and the author of its first commit, the release commit.
+    pub prepared_by: Vec<String>,
+    /// Logins whose latest review approves the pull request.
+    pub approved_by: Vec<String>,
+    pub body: String,
+    pub merged: bool,
+}
+
+/// A client for the upstream repository of `repo`, with its owner and
+/// name. It is authenticated with `GITHUB_TOKEN`, as CI provides it, or
+/// else the personal access token stored for the repository.
+pub fn for_upstream(repo: &Repository) -> Result<(RestClient, String, String)> {
+    let (owner, name) = parse_github_url(&repo.upstream_url()?)?;
+    let token = match env::maybe_var("GITHUB_TOKEN")? {
+        Some(token) => token,
+        None => pat::for_repo(&owner, &name)?
+            .map(|(pat, _)| pat.token)
+            .ok_or_else(|| {
+                anyhow!(
+                    "no token for the GitHub API; set GITHUB_TOKEN or run `belaf auth login --with-token`"
+                )
+            })?,
+    };
+    Ok((RestClient::new(&token)?, owner, name))
+}
+
+/// Status, headers and JSON body (`null` if there was none) of a call.
+#[derive(Debug)]
+pub struct RestResponse {
+    pub status: u16,
+    pub headers: HeaderMap,
+    pub body: Value,
+}
+
+impl RestResponse {
+    /// GitHub's `message` for a failed call.
+    pub fn message(&self) -> &str {
+        self.body["message"].as_str().unwrap_or_default()
+    }
+}
+
+impl RestClient {
+    pub fn new(token: &str) -> Result<Self> {
+        Self::with_api_url(forge::current().api_url(), token)
+    }
+
+    pub fn with_api_url(api_url: &str, token: &str) -> Result<Self> {
+        let http = crate::core::net::client_builder()?
+            .timeout(Duration::from_secs(TIMEOUT_SECS))
+            .build()
+            .context("failed to create HTTP client")?;
+        Ok(Self {
+            http,
+            api_url: api_url.trim_end_matches('/').to_string(),
+            token: token.to_string(),
+        })
+    }
+
+    /// `method` on `path` (below the API root), with `body` as JSON.
+    pub async fn send(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<&Value>,
+    ) -> Result<RestResponse> {
+        let mut request = self.request(method, &format!("{}{path}", self.api_url));
+        if let Some(body) = body {
+            request = request.json(body);
+        }
+        let response = request
+            .send()
+            .await
+            .with_context(|| format!("failed to reach {}", self.api_url))?;
+        Self::read(response).await
+    }
+
+    fn request(&self, method: Method, url: &str) -> reqwest::RequestBuilder {
+        self.http
+            .request(method, url)
+            .bearer_auth(&self.token)
+            .header("Accept", "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .header("User-Agent", "belaf-cli")
+    }
+
+    async fn read(response: reqwest::Response) -> Result<RestResponse> {
+        let status = response.status().as_u16();
+        let headers = response.headers().clone();
+        let body = response.json().await.unwrap_or(Value::Null);
+        Ok(RestResponse {
+            status,
+            headers,
+            body,
+        })
+    }
+
+    /// Open a pull request of `head` into `base` and ask for reviews.
+    /// Returns the pull request's web page.
+    #[expect(
+        clippy::too_many_arguments,
+        reason = "mirrors GitHubInformation::create_pull_request"
+    )]
+    pub async fn create_pull_request(
+        &self,
+        owner: &str,
+        repo: &str,
+        head: &str,
+        base: &str,
+        title: &str,
+        body: &str,
+        reviewers: &[String],
+        team_reviewers: &[String],
+    ) -> Result<String> {
+        let response = self
+            .send(
+                Method::POST,
+                &format!("/repos/{owner}/{repo}/pulls"),
+                Some(&json!({ "title": title, "head": head, "base": base, "body": body })),
+            )
+            .await?;
+        match response.status {
+            201 => {}
+            403 | 404 => {
+                return Err(AccessError::TokenLacksPermission {
+                    repo: format!("{owner}/{repo}"),
+                    permission: Permission::PullRequests,
+                }
+                .into())
+            }
+            422 => bail!("pull request creation failed: {}", response.message()),
+            status => bail!("GitHub API error ({status}): {}", response.message()),
+        }
+
+        let url = response.body["html_url"]
+            .as_str()
+            .ok_or_else(|| anyhow!("GitHub did not return the pull request's URL"))?
+            .to_string();
+        info!("created pull request: {url}");
+
+        if !reviewers.is_empty() || !team_reviewers.is_empty() {
+            let number = response.body["number"].as_u64().unwrap_or_default();
+            let requested = self
+                .send(
+                    Method::POST,
+                    &format!("/repos/{owner}/{repo}/pulls/{number}/requested_reviewers"),
+                    Some(&json!({ "reviewers": reviewers, "team_reviewers": team_reviewers })),
+                )
+                .await;
+            match requested {
+                Ok(r) if r.status == 201 => {}
+                Ok(r) => warn!("could not request reviews on {url}: {}", r.message()),
+                Err(e) => warn!("could not request reviews on {url}: {e:#}"),
+            }
+        }
+        Ok(url)
+    }
+
+    /// Post `body` as a comment on issue or pull request `number`, or
+    /// edit the earlier comment containing `marker` in place. Returns the
+    /// comment's web page.
+    pub async fn upsert_comment(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        marker: &str,
+        body: &str,
+    ) -> Result<String> {
+        let mut existing = None;
+        for page in 1.. {
+            let response = self
+                .send(
+                    Method::GET,
+                    &format!(
+                        "/repos/{owner}/{repo}/issues/{number}/comments?per_page=100&page={page}"
+                    ),
+                    None,
+                )
+                .await?;
+            match response.status {
+                200 => {}
+                404 => {
+                    bail!("{owner}/{repo} has no pull request #{number}, or the token can't see it")
+                }
+                status => bail!("GitHub API error ({status}): {}", response.message()),
+            }
+            let comments = response.body.as_array().cloned().unwrap_or_default();
+            existing = comments
+                .iter()
+                .find(|c| c["body"].as_str().is_some_and(|b| b.contains(marker)))
+                .and_then(|c| c["id"].as_u64());
+            if existing.is_some() || comments.len() < 100 {
+                break;
+            }
+        }
+
+        let response = match existing {
+            Some(id) => {
+                self.send(
+                    Method::PATCH,
+                    &format!("/repos/{owner}/{repo}/issues/comments/{id}"),
+                    Some(&json!({ "body": body })),
+                )
+                .await?
+            }
+            None => {
+                self.send(
+                    Method::POST,
+                    &format!("/repos/{owner}/{repo}/issues/{number}/comments"),
+                    Some(&json!({ "body": body })),
+                )
+                .await?
+            }
+        };
+        match response.status {
+            200 | 201 => {}
+            403 => bail!(
+                "the token may not comment on {owner}/{repo}; it needs `Pull requests: Read and write`"
+            ),
+            status => bail!("GitHub API error ({status}): {}", response.message()),
+        }
+        response.body["html_url"]
+            .as_str()
+            .map(str::to_owned)
+            .ok_or_else(|| anyhow!("GitHub did not return the comment's URL"))
+    }
+
+    /// Create a draft release. Nobody but the repository's writers sees
+    /// it until [`Self::publish_release`].
+    pub async fn create_draft_release(
+        &self,
+        owner: &str,
+        repo: &str,
+        release: &NewRelease<'_>,
+    ) -> Result<DraftRelease> {
+        let response = self
+            .send(
+                Method::POST,
+                &format!("/repos/{owner}/{repo}/releases"),
+                Some(&json!({
+                    "tag_name": release.tag_name,
+                    "target_commitish": release.target,
+                    "name": release.name,
+                    "body": release.body,
+                    "draft": true,
+                    "prerelease": release.prerelease,
+                })),
+            )
+            .await?;
+        match response.status {
+            201 => {}
+            403 | 404 => {
+                return Err(AccessError::TokenLacksPermission {
+                    repo: format!("{owner}/{repo}"),
+                    permission: Permission::Contents,
+                }
+                .into())
+            }
+            status => bail!(
+                "failed to create the release of {} ({status}): {}",
+                release.tag_name,
+                response.message()
+            ),
+        }
+        Self::draft_release(&response.body)
+    }
+
+    /// The draft release of `tag_name`, if there is one. GitHub looks up
+    /// releases by tag only once they are published, so this pages
+    /// through the list.
+    pub async fn find_draft_release(
+        &self,
+        owner: &str,
+        repo: &str,
+        tag_name: &str,
+    ) -> Result<Option<DraftRelease>> {
+        for page in 1.. {
+            let response = self
+                .send(
+                    Method::GET,
+                    &format!("/repos/{owner}/{repo}/releases?per_page=100&page={page}"),
+                    None,
+                )
+                .await?;
+            match response.status {
+                200 => {}
+                status => bail!("GitHub API error ({status}): {}", response.message()),
+            }
+            let batch = response.body.as_array().cloned().unwrap_or_default();
+            let found = batch.iter().find(|release| {
+                release["draft"].as_bool() == Some(true)
+                    && release["tag_name"].as_str() == Some(tag_name)
+            });
+            if let Some(release) = found {
+                return Ok(Some(Self::draft_release(release)?));
+            }
+            if batch.len() < 100 {
+                break;
+            }
+        }
+        Ok(None)
+    }
+
+    fn draft_release(release: &serde_json::Value) -> Result<DraftRelease> {
+        let field = |key: &str| {
+            release[key]
+                .as_str()
+                .map(str::to_owned)
+                .ok_or_else(|| anyhow!("GitHub did not return the release's `{key}`"))
+        };
+        let upload_url = field("upload_url")?;
+        Ok(DraftRelease {
+            id: release["id"]
+                .as_u64()
+                .ok_or_else(|| anyhow!("GitHub did not return the release's id"))?,
+            url: field("html_url")?,
+            upload_url: upload_url
+                .split_once('{')
+                .map_or(upload_url.as_str(), |(url, _)| url)
+                .to_string(),
+        })
+    }
+
+    /// Attach `data` to `release` as the asset `name`.
+    pub async fn upload_release_asset(
+        &self,
+        release: &DraftRelease,
+        name: &str,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        let response = self
+            .request(
+                Method::POST,
+                &format!("{}?name={}", release.upload_url, encode_path_segment(name)),
+            )
+            .header("Content-Type", "application/octet-stream")
+            .body(data)
+            .send()
+            .await
+            .with_context(|| format!("failed to upload `{name}`"))?;
+        let response = Self::read(response).await?;
+        match response.status {
+            201 => Ok(()),
+            422 => bail!("`{name}` is already attached to {}", release.url),
+            status => bail!(
+                "failed to upload `{name}` ({status}): {}",
+                response.message()
+            ),
+        }
+    }
+
+    /// The assets of release `id`.
+    pub async fn release_assets(
+        &self,
+        owner: &str,
+        repo: &str,
+        id: u64,
+    ) -> Result<Vec<ReleaseAsset>> {
+        let mut assets = Vec::new();
+        for page in 1.. {
+            let response = self
+                .send(
+                    Method::GET,
+                    &format!("/repos/{owner}/{repo}/releases/{id}/assets?per_page=100&page={page}"),
+                    None,
+                )
+                .await?;
+            match response.status {
+                200 => {}
+                404 => bail!("{owner}/{repo} has no release {id}, or the token can't see it"),
+                status => bail!("GitHub API error ({status}): {}", response.message()),
+            }
+            let batch = response.body.as_array().cloned().unwrap_or_default();
+            assets.extend(batch.iter().map(|asset| {
+                ReleaseAsset {
+                    name: asset["name"].as_str().unwrap_or_default().to_string(),
+                    size: asset["size"].as_u64().unwrap_or_default(),
+                    sha256: asset["digest"]
+                        .as_str()
+                        .and_then(|d| d.strip_prefix("sha256:"))
+                        .map(str::to_owned),
+                }
+            }));
+            if batch.len() < 100 {
+                break;
+            }
+        }
+        Ok(assets)
+    }
+
+    /// Make draft release `id` public. Returns its web page.
+    pub async fn publish_release(&self, owner: &str, repo: &str, id: u64) -> Result<String> {
+        let response = self
+            .send(
+                Method::PATCH,
+                &format!("/repos/{owner}/{repo}/releases/{id}"),
+                Some(&json!({ "draft": false })),
+            )
+            .await?;
+        match response.status {
+            200 => {}
+            403 => {
+                return Err(AccessError::TokenLacksPermission {
+                    repo: format!("{owner}/{repo}"),
+                    permission: Permission::Contents,
+                }
+                .into())
+            }
+            404 => bail!("{owner}/{repo} has no release {id}, or the token can't see it"),
+            status => bail!(
+                "failed to publish release {id} ({status}): {}",
+                response.message()
+            ),
+        }
+        response.body["html_url"]
+            .as_str()
+            .map(str::to_owned)
+            .ok_or_else(|| anyhow!("GitHub did not return the release's URL"))
+    }
+
+    /// Put `labels` on issue or pull request `number`, creating those the
+    /// repository lacks, and take off the ones `managed` claims that are
+    /// no longer among them. Returns the names added and removed.
+    pub async fn sync_labels(
+        &self,
+        owner: &str,
+        repo: &str,
+        number: u64,
+        labels: &[Label],
+        managed: impl Fn(&str) -> bool,
+    ) -> Result<(Vec<String>, Vec<String>)> {
+        let response = self
+            .send(
+                Method::GET,
+                &format!("/repos/{owner}/{repo}/issues/{number}/labels?per_page=100"),
+                None,
+            )
+            .await?;
+        match response.status {
+            200 => {}
+            404 => bail!("{owner}/{repo} has no pull request #{number}, or the token can't see it"),
+            status => bail!("GitHub API error ({status}): {}", response.message()),
+        }
+        let current: Vec<String> = response
+            .body
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|l| l["name"].as_str().map(str::to_owned))
+            .collect();
+
+        let added: Vec<String> = labels
+            .iter()
+            .filter(|l| !current.contains(&l.name))
+            .map(|l| l.name.clone())
+            .collect();
+        let removed: Vec<String> = current
+            .into_iter()
+            .filter(|name| managed(name) && !labels.iter().any(|l| &l.name == name))
+            .collect();
+
+        for label in labels.iter().filter(|l| added.contains(&l.name)) {
+            self.ensure_label(owner, repo, label).await?;
+        }
+        if !added.is_empty() {
+            let response = self
+                .send(
+                    Method::POST,
+                    &format!("/repos/{owner}/{repo}/issues/{number}/labels"),
+                    Some(&json!({ "labels": added })),
+                )
+                .await?;
+            if response.status != 200 {
+                bail!(
+                    "failed to label #{number} ({}): {}",
+                    response.status,
+                    response.message()
+                );
+            }
+        }
+        for name in &removed {
+            let response = self
+                .send(
+                    Method::DELETE,
+                    &format!(
+                        "/repos/{owner}/{repo}/issues/{number}/labels/{}",
+                        encode_path_segment(name)
+                    ),
+                    None,
+                )
+                .await?;
+            match response.status {
+                200 | 404 => {}
+                status => bail!(
+                    "failed to remove label `{name}` from #{number} ({status}): {}",
+                    response.message()
+                ),
+            }
+        }
+        Ok((added, removed))
+    }
+
+    /// The release pull request, open or closed, that adds the manifest at
+    /// `manifest_path`. Candidates are pull requests from a release branch
+    /// whose body names the manifest, as `prepare` writes it; the one
+    /// that really adds the file is taken.
+    pub async fn release_pull_request(
+        &self,
+        owner: &str,
+        repo: &str,
+        manifest_path: &str,
+    ) -> Result<Option<ReleasePullRequest>> {
+        let pulls = self
+            .list(&format!(
+                "/repos/{owner}/{repo}/pulls?state=all&sort=created&direction=desc"
+            ))
+            .await?;
+        for pull in pulls {
+            let is_candidate = pull["head"]["ref"]
+                .as_str()
+                .is_some_and(|r| r.starts_with(RELEASE_BRANCH_PREFIX))
+                && pull["body"]
+                    .as_str()
+                    .is_some_and(|body| body.contains(manifest_path));
+            let Some(number) = pull["number"].as_u64().filter(|_| is_candidate) else {
+                continue;
+            };
+            let files = self
+                .list(&format!("/repos/{owner}/{repo}/pulls/{number}/files"))
+                .await?;
+            if !files
+                .iter()
+                .any(|f| f["filename"].as_str() == Some(manifest_path) && f["status"] == "added")
+            {
+                continue;
+            }
+
+            let mut prepared_by: Vec<String> = pull["user"]["login"]
+                .as_str()
+                .map(str::to_owned)
+                .into_iter()
+                .collect();
+            let commits = self
+                .list(&format!("/repos/{owner}/{repo}/pulls/{number}/commits"))
+                .await?;
+            if let Some(login) = commits.first().and_then(|c| c["author"]["login"].as_str()) {
+                prepared_by.push(login.to_owned());
+            }
+
+            let reviews = self
+                .list(&format!("/repos/{owner}/{repo}/pulls/{number}/reviews"))
+                .await?;
+            return Ok(Some(ReleasePullRequest {
+                number,
+                url: pull["html_url"].as_str().unwrap_or_default().to_string(),
+                prepared_by,
+                approved_by: approvals(&reviews),
+                body: pull["body"].as_str().unwrap_or_default().to_string(),
+                merged: !pull["merged_at"].is_null(),
+            }));
+        }
+        Ok(None)
+    }
+
+    /// Every item of the list at `path`, page by page.
+    async fn list(&self, path: &str) -> Result<Vec<Value>> {
+        let separator = if path.contains('?') { '&' } else { '?' };
+        let mut items = Vec::new();
+        for page in 1..=LIST_PAGES {
+            let response = self
+                .send(
+                    Method::GET,
+                    &format!("{path}{separator}per_page=100&page={page}"),
+                    None,
+                )
+                .await?;
+            match response.status {
+                200 => {}
+                403 | 404 => bail!("the token can't read `{path}`; it needs `Pull requests: Read`"),
+                status => bail!("GitHub API error ({status}): {}", response.message()),
+            }
+            let batch = response.body.as_array().cloned().unwrap_or_default();
+            let last = batch.len() < 100;
+            items.extend(batch);
+            if last {
+                break;
+            }
+        }
+        Ok(items)
+    }
+
+    /// Create `label` in the repository unless it exists.
+    async fn ensure_label(&self, owner: &str, repo: &str, label: &Label) -> Result<()> {
+        let response = self
+            .send(
+                Method::GET,
+                &format!(
+                    "/repos/{owner}/{repo}/labels/{}",
+                    encode_path_segment(&label.name)
+                ),
+                None,
+            )
+            .await?;
+        if response.status == 200 {
+            return Ok(());
+        }
+        let response = self
+            .send(
+                Method::POST,
+                &format!("/repos/{owner}/{repo}/labels"),
+                Some(&json!({
+                    "name": label.name,
+                    "color": label.color,
+                    "description": label.description,
+                })),
+            )
+            .await?;
+        match response.status {
+            // 422: created meanwhile, by a concurrent run.
+            201 | 422 => Ok(()),
+            403 => bail!(
+                "the token may not create labels on {owner}/{repo}; it needs `Issues: Read and write`"
+            ),
+            status => bail!(
+                "failed to create label `{}` ({status}): {}",
+                label.name,
+                response.message()
+            ),
+        }
+    }
+}
+
+/// Logins whose latest review, in the order GitHub lists them, approves.
+/// Comments leave an earlier verdict standing; a dismissal or a request
+/// for changes withdraws an approval.
+fn approvals(reviews: &[Value]) -> Vec<String> {
+    let mut verdicts: Vec<(String, bool)> = Vec::new();
+    for review in reviews {
+        let (Some(login), Some(state)) =
+            (review["user"]["login"].as_str(), review["state"].as_str())
+        else {
+            continue;
+        };
+        let approves = match state {
+            "APPROVED" => true,
+            "CHANGES_REQUESTED" | "DISMISSED" => false,
+            _ => continue,
+        };
+        match verdicts
+            .iter_mut()
+            .find(|(l, _)| l.eq_ignore_ascii_case(login))
+        {
+            Some(verdict) => verdict.1 = approves,
+            None => verdicts.push((login.to_owned(), approves)),
+        }
+    }
+    verdicts
+        .into_iter()
+        .filter_map(|(login, approves)| approves.then_some(login))
+        .collect()
+}
+
+/// `segment` percent-encoded for use in a URL path; label names may
+/// contain spaces, `:` and `/`.
+fn encode_path_segment(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{b:02X}"),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_json, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const MARKER: &str = "<!-- belaf:test -->";
+
+    async fn server(comments: Value) -> MockServer {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/acme/widgets/issues/7/comments"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(comments))
+            .mount(&server)
+            .await;
+        server
+    }
+
+    #[tokio::test]
+    async fn a_new_comment_is_posted() {
+        let server = server(json!([{ "id": 1, "body": "LGTM" }])).await;
+        Mock::given(method("POST"))
+            .and(path("/repos/acme/widgets/issues/7/comments"))
+            .and(body_json(json!({ "body": "preview" })))
+            .respond_with(
+                ResponseTemplate::new(201).set_body_json(json!({ "html_url": "https://x/c/2" })),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = RestClient::with_api_url(&server.uri(), "t").unwrap();
+        let url = client
+            .upsert_comment("acme", "widgets", 7, MARKER, "preview")
+            .await
+            .unwrap();
+        assert_eq!(url, "https://x/c/2");
+    }
+
+    #[tokio::test]
+    async fn the_marked_comment_is_edited_in_place() {
+        let server = server(json!([
+            { "id": 1, "body": "LGTM" },
+            { "id": 5, "body": format!("{MARKER}\nold preview") },
+        ]))
+        .await;
+        Mock::given(method("PATCH"))
+            .and(path("/repos/acme/widgets/issues/comments/5"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(json!({ "html_url": "https://x/c/5" })),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = RestClient::with_api_url(&server.uri(), "t").unwrap();
+        let url = client
+            .upsert_comment("acme", "widgets", 7, MARKER, "new preview")
+            .await
+            .unwrap();
+        assert_eq!(url, "https://x/c/5");
+    }
+
+    fn label(name: &str) -> Label {
+        Label {
+            name: name.to_string(),
+            color: "ededed".to_string(),
+            description: String::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn labels_are_created_added_and_removed() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/acme/widgets/issues/7/labels"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                { "name": "area:api" },
+                { "name": "semver:patch" },
+                { "name": "needs review" },
+            ])))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/acme/widgets/labels/semver%3Aminor"))
+            .respond_with(ResponseTemplate::new(404))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/repos/acme/widgets/labels"))
+            .and(body_json(
+                json!({ "name": "semver:minor", "color": "ededed", "description": "" }),
+            ))
+            .respond_with(ResponseTemplate::new(201))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/repos/acme/widgets/issues/7/labels"))
+            .and(body_json(json!({ "labels": ["semver:minor"] })))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/repos/acme/widgets/issues/7/labels/semver%3Apatch"))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = RestClient::with_api_url(&server.uri(), "t").unwrap();
+        let (added, removed) = client
+            .sync_labels(
+                "acme",
+                "widgets",
+                7,
+                &[label("area:api"), label("semver:minor")],
+                |name| name.starts_with("area:") || name.starts_with("semver:"),
+            )
+            .await
+            .unwrap();
+        assert_eq!(added, ["semver:minor"]);
+        assert_eq!(removed, ["semver:patch"]);
+    }
+
+    #[tokio::test]
+    async fn a_draft_release_is_staged_and_published() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/repos/acme/widgets/releases"))
+            .and(body_json(json!({
+                "tag_name": "v1.1.0",
+                "target_commitish": "abc123",
+                "name": "widgets 1.1.0",
+                "body": "## 1.1.0",
+                "draft": true,
+                "prerelease": false,
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+                "id": 42,
+                "html_url": "https://x/releases/42",
+                "upload_url": format!("{}/uploads/42/assets{{?name,label}}", server.uri()),
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/uploads/42/assets"))
+            .and(query_param("name", "widgets.tar.gz"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(json!({ "id": 7 })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/acme/widgets/releases/42/assets"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                { "name": "widgets.tar.gz", "size": 3, "digest": "sha256:abcd" },
+            ])))
+            .mount(&server)
+            .await;
+        Mock::given(method("PATCH"))
+            .and(path("/repos/acme/widgets/releases/42"))
+            .and(body_json(json!({ "draft": false })))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({ "html_url": "https://x/releases/tag/v1.1.0" })),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = RestClient::with_api_url(&server.uri(), "t").unwrap();
+        let draft = client
+            .create_draft_release(
+                "acme",
+                "widgets",
+                &NewRelease {
+                    tag_name: "v1.1.0",
+                    target: "abc123",
+                    name: "widgets 1.1.0",
+                    body: "## 1.1.0",
+                    prerelease: false,
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(draft.id, 42);
+        client
+            .upload_release_asset(&draft, "widgets.tar.gz", b"abc".to_vec())
+            .await
+            .unwrap();
+        assert_eq!(
+            client.release_assets("acme", "widgets", 42).await.unwrap(),
+            [ReleaseAsset {
+                name: "widgets.tar.gz".to_string(),
+                size: 3,
+                sha256: Some("abcd".to_string()),
+            }]
+        );
+        assert_eq!(
+            client.publish_release("acme", "widgets", 42).await.unwrap(),
+            "https://x/releases/tag/v1.1.0"
+        );
+    }
+
+    #[tokio::test]
+    async fn the_release_pull_request_carries_its_reviews() {
+        let manifest = "belaf/releases/0192f3a1.json";
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/acme/widgets/pulls"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                // Names the manifest, but doesn't add it.
+                { "number": 9, "head": { "ref": "release/forged" },
+                  "body": manifest, "user": { "login": "mallory" } },
+                { "number": 8, "head": { "ref": "feature" }, "body": manifest },
+                { "number": 7, "head": { "ref": "release/20260101" },
+                  "body": format!("Manifest: `{manifest}`"),
+                  "user": { "login": "belaf[bot]" },
+                  "html_url": "https://x/pull/7",
+                  "merged_at": "2026-01-02T00:00:00Z" },
+            ])))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/acme/widgets/pulls/9/files"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                { "filename": manifest, "status": "modified" },
+            ])))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/acme/widgets/pulls/7/files"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                { "filename": "Cargo.toml", "status": "modified" },
+                { "filename": manifest, "status": "added" },
+            ])))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/acme/widgets/pulls/7/commits"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                { "author": { "login": "alice" } },
+                { "author": { "login": "bob" } },
+            ])))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/acme/widgets/pulls/7/reviews"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                { "user": { "login": "bob" }, "state": "APPROVED" },
+                { "user": { "login": "carol" }, "state": "APPROVED" },
+                { "user": { "login": "carol" }, "state": "COMMENTED" },
+                { "user": { "login": "dave" }, "state": "APPROVED" },
+                { "user": { "login": "dave" }, "state": "DISMISSED" },
+                { "user": { "login": "erin" }, "state": "CHANGES_REQUESTED" },
+            ])))
+            .mount(&server)
+            .await;
+
+        let client = RestClient::with_api_url(&server.uri(), "t").unwrap();
+        let pull = client
+            .release_pull_request("acme", "widgets", manifest)
+            .await
+            .unwrap()
+            .expect("#7 adds the manifest");
+        assert_eq!(
+            pull,
+            ReleasePullRequest {
+                number: 7,
+                url: "https://x/pull/7".to_string(),
+                prepared_by: vec!["belaf[bot]".to_string(), "alice".to_string()],
+                approved_by: vec!["bob".to_string(), "carol".to_string()],
+                body: format!("Manifest: `{manifest}`"),
+                merged: true,
+            }
+        );
+    }
+}
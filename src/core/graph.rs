@@ -9,16 +9,18 @@
 //! depend on each other. In the general case, these intra-repository
 //! dependencies have the structure of a directed acyclic graph (DAG).
 
+use anyhow::bail;
 use petgraph::{
     algo::toposort,
     graph::{DefaultIx, DiGraph, NodeIndex},
 };
 use std::collections::{HashMap, HashSet};
+use tracing::debug;
 
 use crate::core::{
     config::syntax::ResolvedGroupConfig,
     errors::Result,
-    git::repository::{RepoHistory, Repository},
+    git::repository::{RepoHistory, RepoPathBuf, Repository},
     group::{Group, GroupId, GroupSet},
     resolved_release_unit::{
         DepRequirement, Dependency, DependencyBuilder, DependencyTarget, ReleaseUnitId,
@@ -102,6 +104,30 @@ impl ReleaseUnitGraph {
         }
     }
 
+    /// `seeds` plus every project that depends on one of them, directly
+    /// or transitively, in topologically sorted order.
+    pub fn with_dependents(&self, seeds: &[ReleaseUnitId]) -> Vec<ReleaseUnitId> {
+        let mut reached: HashSet<ReleaseUnitId> = seeds.iter().copied().collect();
+
+        // Dependencies sort before their dependents, so one pass sees
+        // every dependency's membership before the project itself.
+        for id in &self.toposorted_ids {
+            if self.projects[*id]
+                .internal_deps
+                .iter()
+                .any(|dep| reached.contains(&dep.ident))
+            {
+                reached.insert(*id);
+            }
+        }
+
+        self.toposorted_ids
+            .iter()
+            .copied()
+            .filter(|id| reached.contains(id))
+            .collect()
+    }
+
     pub fn query(&self, query: GraphQueryBuilder) -> Result<Vec<ReleaseUnitId>> {
         let mut matched_idents = Vec::new();
         let mut seen_ids = HashSet::new();
@@ -221,6 +247,10 @@ pub struct ReleaseUnitGraphBuilder {
 
     /// The `petgraph` state expressing the project graph.
     graph: DiGraph<ReleaseUnitId, ()>,
+
+    /// Directory selected with `--scope`. Units outside it were never
+    /// added, so references to them are expected rather than errors.
+    scope: Option<RepoPathBuf>,
 }
 
 impl ReleaseUnitGraphBuilder {
@@ -229,9 +259,15 @@ impl ReleaseUnitGraphBuilder {
             projects: Vec::new(),
             node_ixs: Vec::new(),
             graph: DiGraph::default(),
+            scope: None,
         }
     }
 
+    /// Mark the graph as covering only the units under `scope`.
+    pub(crate) fn set_scope(&mut self, scope: RepoPathBuf) {
+        self.scope = Some(scope);
+    }
+
     /// Register a new project with the graph and return its
     /// identifier.
     pub fn add_project(&mut self, qnames: Vec<String>) -> ReleaseUnitId {
@@ -441,6 +477,15 @@ impl ReleaseUnitGraphBuilder {
             for dep in unit_builder.internal_deps.drain(..) {
                 let dep_ident = match dep.target {
                     DependencyTarget::Ident(id) => id,
+                    DependencyTarget::Text(ref dep_name) if self.scope.is_some() => {
+                        // Out-of-scope units are not loaded; their version
+                        // constraints are left as they are.
+                        let Some(id) = name_to_id.get(dep_name) else {
+                            debug!("ignoring dependency of `{name}` on out-of-scope `{dep_name}`");
+                            continue;
+                        };
+                        *id
+                    }
                     DependencyTarget::Text(ref dep_name) => *a_ok_or!(
                         name_to_id.get(dep_name);
                         ["project `{}` states a dependency on an unrecognized project name: `{}`",
@@ -476,6 +521,24 @@ impl ReleaseUnitGraphBuilder {
                 GroupId::new(&gc.id);
                 ["invalid `[group.{}]` id in belaf/config.toml", gc.id]
             );
+            if let Some(scope) = &self.scope {
+                let in_scope = gc
+                    .members
+                    .iter()
+                    .filter(|m| name_to_id.contains_key(*m))
+                    .count();
+                if in_scope == 0 {
+                    continue;
+                }
+                if in_scope < gc.members.len() {
+                    bail!(
+                        "group `{}` has members outside --scope `{}`; a group releases together, so widen the scope to cover all of {}",
+                        id,
+                        scope.escaped(),
+                        gc.members.join(", ")
+                    );
+                }
+            }
             let mut members = Vec::with_capacity(gc.members.len());
             for member_name in &gc.members {
                 let pid = a_ok_or!(
@@ -1321,4 +1384,33 @@ mod tests {
             _ => panic!("Expected Manual requirement"),
         }
     }
+
+    #[test]
+    fn with_dependents_follows_transitive_dependers() {
+        use crate::core::resolved_release_unit::{DepRequirement, DependencyTarget};
+
+        let mut graph = ReleaseUnitGraphBuilder::new();
+
+        // app -> service -> core; tool is unrelated.
+        let core = create_test_project(&mut graph, "core");
+        let service = create_test_project(&mut graph, "service");
+        let app = create_test_project(&mut graph, "app");
+        let tool = create_test_project(&mut graph, "tool");
+
+        for (depender, dependee) in [(service, core), (app, service)] {
+            graph.add_dependency(
+                depender,
+                DependencyTarget::Ident(dependee),
+                "0.0.0-dev.0".to_string(),
+                DepRequirement::Manual("^0.1".to_string()),
+            );
+        }
+
+        let graph = graph.complete_loading().expect("BUG: graph should load");
+
+        assert_eq!(graph.with_dependents(&[core]), vec![core, service, app]);
+        assert_eq!(graph.with_dependents(&[service]), vec![service, app]);
+        assert_eq!(graph.with_dependents(&[tool]), vec![tool]);
+        assert!(graph.with_dependents(&[]).is_empty());
+    }
 }
@@ -0,0 +1,121 @@
+//! Bounds on how far back history analysis walks.
+//!
+//! A unit without a release tag or baseline has every commit since the
+//! start of the repository analysed, which on a large repository takes a
+//! while. Two `[repo.analysis]` settings stop each unit's walk early:
+//!
+//! - `max_commits`: visit at most this many commits, newest first;
+//! - `since_date`: stop at the first commit made before this day
+//!   (`YYYY-MM-DD`, UTC).
+//!
+//! `--max-depth <N>` replaces `max_commits` for one invocation. A walk
+//! that was cut short is reported by `belaf status`: the commits it
+//! skipped may still be relevant, so its counts are lower bounds.
+
+use std::{fmt, num::NonZeroUsize, sync::RwLock};
+
+use anyhow::{Context, Result};
+use time::{macros::format_description, Date, OffsetDateTime};
+
+use crate::core::config::syntax::AnalysisConfig;
+
+static MAX_DEPTH: RwLock<Option<NonZeroUsize>> = RwLock::new(None);
+
+/// Override `[repo.analysis] max_commits` for this process; `None`
+/// drops the override.
+pub fn select_max_depth(depth: Option<NonZeroUsize>) {
+    if let Ok(mut slot) = MAX_DEPTH.write() {
+        *slot = depth;
+    }
+}
+
+/// The depth passed to `--max-depth`, if any.
+pub fn selected_max_depth() -> Option<NonZeroUsize> {
+    MAX_DEPTH.read().ok().and_then(|slot| *slot)
+}
+
+/// How far each unit's history walk may go.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HistoryBounds {
+    pub max_commits: Option<NonZeroUsize>,
+    pub since: Option<Date>,
+}
+
+impl HistoryBounds {
+    /// The bounds `cfg` sets, with `--max-depth` taking precedence over
+    /// `max_commits`.
+    pub fn new(cfg: &AnalysisConfig) -> Result<Self> {
+        let since = cfg
+            .since_date
+            .as_deref()
+            .map(|date| {
+                Date::parse(date, format_description!("[year]-[month]-[day]"))
+                    .with_context(|| format!("`since_date = \"{date}\"` is not a YYYY-MM-DD date"))
+            })
+            .transpose()?;
+        Ok(Self {
+            max_commits: selected_max_depth().or(cfg.max_commits),
+            since,
+        })
+    }
+
+    /// Why the walk must stop before a commit made at `commit_time`
+    /// (seconds since the epoch), having visited `visited` commits.
+    pub fn stop_before(&self, visited: usize, commit_time: i64) -> Option<Truncation> {
+        if let Some(max) = self.max_commits.filter(|max| visited >= max.get()) {
+            return Some(Truncation::MaxCommits(max));
+        }
+        let since = self.since?;
+        let start = since.midnight().assume_utc();
+        OffsetDateTime::from_unix_timestamp(commit_time)
+            .is_ok_and(|time| time < start)
+            .then_some(Truncation::Since(since))
+    }
+}
+
+/// Why a history walk stopped before reaching its boundary.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Truncation {
+    MaxCommits(NonZeroUsize),
+    Since(Date),
+}
+
+impl fmt::Display for Truncation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MaxCommits(max) => write!(f, "stopped after {max} commits"),
+            Self::Since(date) => write!(f, "stopped at commits made before {date}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::date;
+
+    #[test]
+    fn walks_stop_at_the_first_bound_reached() {
+        let bounds = HistoryBounds {
+            max_commits: NonZeroUsize::new(2),
+            since: Some(date!(2024 - 03 - 01)),
+        };
+        let march = date!(2024 - 03 - 01)
+            .midnight()
+            .assume_utc()
+            .unix_timestamp();
+        assert_eq!(bounds.stop_before(1, march), None);
+        assert_eq!(
+            bounds.stop_before(1, march - 1),
+            Some(Truncation::Since(date!(2024 - 03 - 01)))
+        );
+        assert_eq!(
+            bounds
+                .stop_before(2, march)
+                .map(|t| t.to_string())
+                .as_deref(),
+            Some("stopped after 2 commits")
+        );
+        assert_eq!(HistoryBounds::default().stop_before(10_000, 0), None);
+    }
+}
@@ -0,0 +1,445 @@
+//! Jira fix versions for tagged releases.
+//!
+//! With `[trackers.jira]` configured, `belaf tag` brings Jira up to date
+//! for each release of the manifest:
+//!
+//! 1. the release's fix version (`{name} {version}` unless
+//!    `version_format` says otherwise) is created in `project` and
+//!    marked released today, unless it exists already;
+//! 2. the issues of `project` the release's commits mention (`PROJ-123`
+//!    anywhere in a commit message) get the fix version;
+//! 3. those issues are moved through the `Done` transition (or the one
+//!    `transition` names), when their workflow offers it.
+//!
+//! Every step is idempotent, so running `belaf tag` again after a
+//! failure finishes the job. Requests go to the REST API v2, which Jira
+//! Cloud, Server and Data Center all serve. The API token is read from
+//! `BELAF_JIRA_TOKEN` (or the variable `token_env` names) and sent with
+//! `email` as basic auth, or alone as a bearer token.
+
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use reqwest::{header::ACCEPT, Method, RequestBuilder, Response};
+use serde::Deserialize;
+use serde_json::json;
+use time::macros::format_description;
+
+use crate::core::{config::syntax::JiraConfiguration, deterministic, manifest::ReleaseEntry, net};
+
+/// Environment variable holding the API token, unless `[trackers.jira]
+/// token_env` names another.
+pub const TOKEN_ENV: &str = "BELAF_JIRA_TOKEN";
+
+const DEFAULT_VERSION_FORMAT: &str = "{name} {version}";
+const DEFAULT_TRANSITION: &str = "Done";
+const TIMEOUT_SECS: u64 = 30;
+
+/// Whether `key` looks like a Jira project key: `PROJ`, `WEB2`.
+pub fn is_project_key(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars.next().is_some_and(|c| c.is_ascii_uppercase())
+        && chars.all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// Keys of `project`'s issues mentioned in `messages`, in issue-number
+/// order and without duplicates.
+pub fn issue_keys<'a>(project: &str, messages: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let prefix = format!("{project}-");
+    let mut numbers: Vec<u64> = Vec::new();
+    for message in messages {
+        for (start, _) in message.match_indices(&prefix) {
+            let before = message[..start].chars().next_back();
+            if before.is_some_and(|c| c.is_ascii_alphanumeric()) {
+                continue;
+            }
+            let rest = &message[start + prefix.len()..];
+            let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+            let after = rest[digits..].chars().next();
+            if after.is_some_and(|c| c.is_ascii_alphanumeric()) {
+                continue;
+            }
+            if let Ok(number) = rest[..digits].parse::<u64>() {
+                numbers.extend((number > 0).then_some(number));
+            }
+        }
+    }
+    numbers.sort_unstable();
+    numbers.dedup();
+    numbers
+        .into_iter()
+        .map(|n| format!("{prefix}{n}"))
+        .collect()
+}
+
+/// The fix version `release` is recorded as.
+pub fn version_name(cfg: &JiraConfiguration, release: &ReleaseEntry) -> String {
+    cfg.version_format
+        .as_deref()
+        .unwrap_or(DEFAULT_VERSION_FORMAT)
+        .replace("{name}", &release.name)
+        .replace("{version}", &release.new_version)
+        .replace("{tag}", &release.tag_name)
+}
+
+/// The transition issues are moved through, if any.
+pub fn transition_name(cfg: &JiraConfiguration) -> Option<&str> {
+    match cfg.transition.as_deref() {
+        Some("") => None,
+        Some(name) => Some(name),
+        None => Some(DEFAULT_TRANSITION),
+    }
+}
+
+/// What [`sync_release`] changed in Jira.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SyncReport {
+    /// Whether the fix version was created rather than found.
+    pub created: bool,
+    /// Issues the fix version was added to.
+    pub updated: Vec<String>,
+    /// Issues moved through the transition.
+    pub transitioned: Vec<String>,
+    /// Issues that could not be updated, with the reason.
+    pub failed: Vec<(String, String)>,
+}
+
+pub struct JiraClient {
+    client: reqwest::Client,
+    base_url: String,
+    email: Option<String>,
+    token: String,
+}
+
+#[derive(Deserialize)]
+struct Version {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct Transitions {
+    transitions: Vec<Transition>,
+}
+
+#[derive(Deserialize)]
+struct Transition {
+    id: String,
+    name: String,
+    to: Option<Status>,
+}
+
+#[derive(Deserialize)]
+struct Status {
+    name: String,
+}
+
+impl JiraClient {
+    pub fn new(cfg: &JiraConfiguration) -> Result<Self> {
+        let token_env = cfg.token_env.as_deref().unwrap_or(TOKEN_ENV);
+        let token = std::env::var(token_env)
+            .ok()
+            .filter(|t| !t.is_empty())
+            .with_context(|| format!("{token_env} is not set; it must hold a Jira API token"))?;
+        let client = net::client_builder()?
+            .timeout(Duration::from_secs(TIMEOUT_SECS))
+            .build()?;
+        Ok(Self {
+            client,
+            base_url: cfg.url.trim_end_matches('/').to_string(),
+            email: cfg.email.clone(),
+            token,
+        })
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let request = self
+            .client
+            .request(method, format!("{}/rest/api/2/{path}", self.base_url))
+            .header(ACCEPT, "application/json");
+        match &self.email {
+            Some(email) => request.basic_auth(email, Some(&self.token)),
+            None => request.bearer_auth(&self.token),
+        }
+    }
+
+    async fn send(&self, request: RequestBuilder, what: &str) -> Result<Response> {
+        let response = request
+            .send()
+            .await
+            .with_context(|| format!("failed to {what}"))?;
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+        let body = response.text().await.unwrap_or_default();
+        bail!("failed to {what}: HTTP {status}{}", error_messages(&body));
+    }
+
+    /// Create the released fix version `name` in `project` unless it
+    /// exists. Returns whether it was created.
+    async fn ensure_version(&self, project: &str, name: &str) -> Result<bool> {
+        let versions: Vec<Version> = self
+            .send(
+                self.request(Method::GET, &format!("project/{project}/versions")),
+                &format!("list the versions of Jira project {project}"),
+            )
+            .await?
+            .json()
+            .await?;
+        if versions.iter().any(|v| v.name == name) {
+            return Ok(false);
+        }
+        let today = deterministic::now_utc()
+            .format(format_description!("[year]-[month]-[day]"))
+            .unwrap_or_default();
+        self.send(
+            self.request(Method::POST, "version").json(&json!({
+                "name": name,
+                "project": project,
+                "released": true,
+                "releaseDate": today,
+            })),
+            &format!("create Jira version `{name}`"),
+        )
+        .await?;
+        Ok(true)
+    }
+
+    async fn add_fix_version(&self, issue: &str, name: &str) -> Result<()> {
+        self.send(
+            self.request(Method::PUT, &format!("issue/{issue}"))
+                .json(&json!({
+                    "update": { "fixVersions": [{ "add": { "name": name } }] },
+                })),
+            &format!("set the fix version of {issue}"),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Move `issue` through the transition named `target` (or leading to
+    /// a status named `target`). Returns `false` when the issue's
+    /// workflow doesn't offer it, e.g. because the issue is done already.
+    async fn transition(&self, issue: &str, target: &str) -> Result<bool> {
+        let path = format!("issue/{issue}/transitions");
+        let available: Transitions = self
+            .send(
+                self.request(Method::GET, &path),
+                &format!("list the transitions of {issue}"),
+            )
+            .await?
+            .json()
+            .await?;
+        let Some(transition) = available.transitions.iter().find(|t| {
+            t.name.eq_ignore_ascii_case(target)
+                || t.to
+                    .as_ref()
+                    .is_some_and(|s| s.name.eq_ignore_ascii_case(target))
+        }) else {
+            return Ok(false);
+        };
+        self.send(
+            self.request(Method::POST, &path)
+                .json(&json!({ "transition": { "id": transition.id } })),
+            &format!("move {issue} to {target}"),
+        )
+        .await?;
+        Ok(true)
+    }
+
+    async fn sync(
+        &self,
+        cfg: &JiraConfiguration,
+        version: &str,
+        issues: &[String],
+    ) -> Result<SyncReport> {
+        let mut report = SyncReport {
+            created: self.ensure_version(&cfg.project, version).await?,
+            ..SyncReport::default()
+        };
+        for issue in issues {
+            if let Err(e) = self.add_fix_version(issue, version).await {
+                report.failed.push((issue.clone(), format!("{e:#}")));
+                continue;
+            }
+            report.updated.push(issue.clone());
+            let Some(target) = transition_name(cfg) else {
+                continue;
+            };
+            match self.transition(issue, target).await {
+                Ok(true) => report.transitioned.push(issue.clone()),
+                Ok(false) => {}
+                Err(e) => report.failed.push((issue.clone(), format!("{e:#}"))),
+            }
+        }
+        Ok(report)
+    }
+}
+
+/// Create or find the fix version `version`, add it to `issues` and move
+/// them through the transition. Fails only when the version can't be
+/// had; per-issue problems are listed in the report.
+pub fn sync_release(
+    client: &JiraClient,
+    cfg: &JiraConfiguration,
+    version: &str,
+    issues: &[String],
+) -> Result<SyncReport> {
+    let future = client.sync(cfg, version, issues);
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) => tokio::task::block_in_place(|| handle.block_on(future)),
+        Err(_) => tokio::runtime::Runtime::new()
+            .context("failed to create async runtime")?
+            .block_on(future),
+    }
+}
+
+/// Jira's `{"errorMessages": [...], "errors": {...}}` error body as
+/// `: message; field: message`, or nothing.
+fn error_messages(body: &str) -> String {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(body) else {
+        return String::new();
+    };
+    let mut messages: Vec<String> = value["errorMessages"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|m| m.as_str().map(str::to_owned))
+        .collect();
+    if let Some(errors) = value["errors"].as_object() {
+        for (field, message) in errors {
+            messages.push(format!("{field}: {}", message.as_str().unwrap_or_default()));
+        }
+    }
+    if messages.is_empty() {
+        String::new()
+    } else {
+        format!(": {}", messages.join("; "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{basic_auth, body_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn config(url: &str) -> JiraConfiguration {
+        JiraConfiguration {
+            url: url.to_string(),
+            project: "PROJ".into(),
+            email: Some("bot@acme.com".into()),
+            token_env: None,
+            version_format: None,
+            transition: None,
+        }
+    }
+
+    #[test]
+    fn issue_keys_are_found_in_commit_messages() {
+        let messages = [
+            "fix(api): handle empty pages (PROJ-12)",
+            "feat: export\n\nCloses PROJ-3, refs PROJ-12 and OTHER-4",
+            "chore: not XPROJ-5, PROJ-6x or PROJ-0",
+        ];
+        assert_eq!(issue_keys("PROJ", messages), vec!["PROJ-3", "PROJ-12"]);
+        assert!(is_project_key("WEB2"));
+        assert!(!is_project_key("proj"));
+    }
+
+    #[test]
+    fn version_names_follow_the_format() {
+        let release = ReleaseEntry::new(
+            "api".into(),
+            "cargo".into(),
+            "1.2.0".into(),
+            "1.3.0".into(),
+            "minor".into(),
+            String::new(),
+            "crates/api".into(),
+        );
+        let mut cfg = config("https://jira.example.com");
+        assert_eq!(version_name(&cfg, &release), "api 1.3.0");
+        cfg.version_format = Some("{tag}".into());
+        assert_eq!(version_name(&cfg, &release), "crates/api/v1.3.0");
+    }
+
+    #[tokio::test]
+    async fn sync_creates_the_version_and_moves_the_issues() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/rest/api/2/project/PROJ/versions"))
+            .and(basic_auth("bot@acme.com", "t0ken"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(json!([{ "name": "api 1.2.0" }])),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/rest/api/2/version"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(json!({ "id": "10" })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        for issue in ["PROJ-3", "PROJ-12"] {
+            Mock::given(method("PUT"))
+                .and(path(format!("/rest/api/2/issue/{issue}")))
+                .and(body_json(json!({
+                    "update": { "fixVersions": [{ "add": { "name": "api 1.3.0" } }] },
+                })))
+                .respond_with(ResponseTemplate::new(204))
+                .mount(&server)
+                .await;
+        }
+        Mock::given(method("GET"))
+            .and(path("/rest/api/2/issue/PROJ-3/transitions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "transitions": [
+                    { "id": "21", "name": "Start", "to": { "name": "In Progress" } },
+                    { "id": "31", "name": "Finish", "to": { "name": "Done" } },
+                ],
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/rest/api/2/issue/PROJ-3/transitions"))
+            .and(body_json(json!({ "transition": { "id": "31" } })))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/rest/api/2/issue/PROJ-12/transitions"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "transitions": [] })))
+            .mount(&server)
+            .await;
+
+        let cfg = config(&server.uri());
+        let client = JiraClient {
+            client: reqwest::Client::new(),
+            base_url: server.uri(),
+            email: cfg.email.clone(),
+            token: "t0ken".into(),
+        };
+        let issues = vec!["PROJ-3".to_string(), "PROJ-12".to_string()];
+        let report = client.sync(&cfg, "api 1.3.0", &issues).await.unwrap();
+        assert_eq!(
+            report,
+            SyncReport {
+                created: true,
+                updated: issues.clone(),
+                transitioned: vec!["PROJ-3".into()],
+                failed: Vec::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn jira_error_bodies_are_summarised() {
+        assert_eq!(
+            error_messages(r#"{"errorMessages":["Issue does not exist"],"errors":{}}"#),
+            ": Issue does not exist"
+        );
+        assert_eq!(error_messages("<html>"), "");
+    }
+}
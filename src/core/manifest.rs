@@ -11,7 +11,8 @@
 //! `typify` in `build.rs` and live in [`crate::core::wire::codegen`].
 //! This module re-exports the ergonomic domain types from
 //! [`crate::core::wire::domain`] under the historical names
-//! (`ReleaseManifest`, `ReleaseEntry`).
+//! (`ReleaseManifest`, `ReleaseEntry`). Manifests on disk are read
+//! through [`crate::core::releases`], which upgrades older versions.
 
 use std::fs;
 use std::path::Path;
@@ -19,8 +20,9 @@ use std::path::Path;
 use serde_json::Map;
 
 pub use crate::core::wire::domain::{
-    Group, Manifest as ReleaseManifest, ManifestParseError, Release as ReleaseEntry,
-    ReleaseStatistics, SCHEMA_VERSION,
+    Approval, Artifact, Group, Manifest as ReleaseManifest, ManifestParseError, Publication,
+    PublicationRelease, PublicationState, Release as ReleaseEntry, ReleaseStatistics, Signoff,
+    SCHEMA_VERSION,
 };
 use crate::core::wire::known::{BumpType, Ecosystem};
 
@@ -89,6 +91,8 @@ impl ReleaseEntry {
             tag_name,
             previous_tag,
             compare_url: None,
+            commit_range: None,
+            commits: Vec::new(),
             is_prerelease,
             changelog,
             contributors: Vec::new(),
@@ -140,6 +144,15 @@ impl ReleaseEntry {
         self
     }
 
+    /// Record the commits this release ships: `range` is
+    /// `<previous_tag>..<sha>` (or `<sha>` for a first release) and
+    /// `commits` the unit's commits in it.
+    pub fn with_commits(mut self, range: impl Into<String>, commits: Vec<String>) -> Self {
+        self.commit_range = Some(range.into());
+        self.commits = commits;
+        self
+    }
+
     pub fn with_group_id(mut self, group_id: impl Into<String>) -> Self {
         self.group_id = Some(group_id.into());
         self
@@ -0,0 +1,204 @@
+//! Shared HTTP client construction.
+//!
+//! Every outbound HTTP client belaf builds — the belaf API client (which
+//! also backs the GitHub integration and the device-flow login), the
+//! Actions OIDC exchange, `belaf doctor`'s health probe and the update
+//! check — is created through this module so network settings apply
+//! uniformly.
+//!
+//! Proxies come from the environment: both reqwest and ureq honour
+//! `HTTPS_PROXY` / `HTTP_PROXY` / `ALL_PROXY` and `NO_PROXY` on their
+//! own, so the only job here is to never switch that off. Extra trust
+//! roots come from `[network] ca_bundle` in `belaf/config.toml`, or the
+//! `BELAF_CA_BUNDLE` environment variable, which wins over the config
+//! file. The bundle is *added* to the platform trust store for reqwest
+//! clients; ureq cannot merge roots, so the update check trusts the
+//! bundle alone.
+
+use std::{
+    path::{Path, PathBuf},
+    sync::RwLock,
+};
+
+use tracing::{debug, warn};
+
+use crate::core::{
+    api::{retry::RetryPolicy, ApiError},
+    config::{peek_section, syntax::NetworkConfiguration},
+};
+
+/// Environment variable overriding `[network] ca_bundle`.
+pub const CA_BUNDLE_ENV: &str = "BELAF_CA_BUNDLE";
+
+#[derive(Clone, Debug)]
+struct Settings {
+    ca_bundle: Option<PathBuf>,
+    retry: RetryPolicy,
+}
+
+static SETTINGS: RwLock<Option<Settings>> = RwLock::new(None);
+
+/// Install the `[network]` section of a loaded config. `repo_root`
+/// anchors a relative `ca_bundle` path.
+pub fn configure(cfg: &NetworkConfiguration, repo_root: &Path) {
+    let settings = Settings {
+        ca_bundle: cfg.ca_bundle.as_ref().map(|p| repo_root.join(p)),
+        retry: RetryPolicy::from_config(cfg),
+    };
+    if let Ok(mut slot) = SETTINGS.write() {
+        *slot = Some(settings);
+    }
+}
+
+fn settings() -> Option<Settings> {
+    SETTINGS.read().ok().and_then(|slot| slot.clone())
+}
+
+/// Best-effort early configuration from the repository containing the
+/// current directory. Commands such as `belaf install` and the update
+/// check never open an [`crate::core::session::AppSession`], so this
+/// peeks at just the `[network]` table of `belaf/config.toml`. Any
+/// problem is ignored here; full config validation happens when a
+/// session loads the file.
+pub fn configure_from_env() {
+    match peek_section::<NetworkConfiguration>("network") {
+        Some((network, workdir)) => configure(&network, &workdir),
+        None => {
+            debug!("no usable [network] section in belaf/config.toml; using defaults");
+            if let Ok(mut slot) = SETTINGS.write() {
+                *slot = None;
+            }
+        }
+    }
+}
+
+/// The CA bundle in effect, if any.
+pub fn ca_bundle_path() -> Option<PathBuf> {
+    if let Some(p) = std::env::var_os(CA_BUNDLE_ENV).filter(|v| !v.is_empty()) {
+        return Some(PathBuf::from(p));
+    }
+    settings().and_then(|s| s.ca_bundle)
+}
+
+/// Retry policy for belaf API calls (`[network] max_retries` /
+/// `max_retry_wait_secs`).
+pub fn retry_policy() -> RetryPolicy {
+    settings().map(|s| s.retry).unwrap_or_default()
+}
+
+fn read_ca_bundle(path: &Path) -> Result<Vec<u8>, ApiError> {
+    std::fs::read(path).map_err(|e| {
+        ApiError::InvalidConfiguration(format!("cannot read CA bundle `{}`: {e}", path.display()))
+    })
+}
+
+/// A `reqwest` client builder with belaf's network settings applied.
+/// Callers add their own timeouts and then `build()`.
+pub fn client_builder() -> Result<reqwest::ClientBuilder, ApiError> {
+    client_builder_with(ca_bundle_path().as_deref())
+}
+
+fn client_builder_with(ca_bundle: Option<&Path>) -> Result<reqwest::ClientBuilder, ApiError> {
+    let builder = reqwest::Client::builder();
+
+    let Some(path) = ca_bundle else {
+        return Ok(builder);
+    };
+
+    let pem = read_ca_bundle(path)?;
+    let certs = reqwest::Certificate::from_pem_bundle(&pem).map_err(|e| {
+        ApiError::InvalidConfiguration(format!(
+            "CA bundle `{}` is not valid PEM: {e}",
+            path.display()
+        ))
+    })?;
+    if certs.is_empty() {
+        return Err(ApiError::InvalidConfiguration(format!(
+            "CA bundle `{}` contains no certificates",
+            path.display()
+        )));
+    }
+
+    debug!(
+        "trusting {} extra root(s) from `{}`",
+        certs.len(),
+        path.display()
+    );
+    Ok(builder.tls_certs_merge(certs))
+}
+
+/// A `ureq` agent for the blocking update check. Never fails: a broken
+/// CA bundle is logged and the default roots are used, since the update
+/// check is advisory.
+pub fn ureq_agent() -> ureq::Agent {
+    let config = ureq::Agent::config_builder();
+
+    let Some(path) = ca_bundle_path() else {
+        return config.build().into();
+    };
+
+    let certs = read_ca_bundle(&path).map(|pem| {
+        ureq::tls::parse_pem(&pem)
+            .filter_map(|item| match item {
+                Ok(ureq::tls::PemItem::Certificate(cert)) => Some(cert.to_owned()),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+    });
+
+    match certs {
+        Ok(certs) if !certs.is_empty() => {
+            let tls = ureq::tls::TlsConfig::builder()
+                .root_certs(ureq::tls::RootCerts::new_with_certs(&certs))
+                .build();
+            config.tls_config(tls).build().into()
+        }
+        Ok(_) => {
+            warn!("CA bundle `{}` contains no certificates", path.display());
+            config.build().into()
+        }
+        Err(e) => {
+            warn!("{e}");
+            config.build().into()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn configure_resolves_relative_bundle_against_repo_root() {
+        configure(
+            &NetworkConfiguration {
+                ca_bundle: Some("certs/corp.pem".into()),
+                ..Default::default()
+            },
+            Path::new("/work/repo"),
+        );
+        let configured = settings().and_then(|s| s.ca_bundle);
+        configure(&NetworkConfiguration::default(), Path::new("/"));
+        assert_eq!(configured, Some(PathBuf::from("/work/repo/certs/corp.pem")));
+    }
+
+    #[test]
+    fn client_builder_rejects_missing_bundle() {
+        let err = client_builder_with(Some(Path::new("/nonexistent/belaf-ca.pem"))).unwrap_err();
+        assert!(err.to_string().contains("belaf-ca.pem"), "got: {err}");
+    }
+
+    #[test]
+    fn client_builder_rejects_bundle_without_certificates() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("empty.pem");
+        std::fs::write(&path, "not a certificate\n").unwrap();
+        let err = client_builder_with(Some(&path)).unwrap_err();
+        assert!(err.to_string().contains("no certificates"), "got: {err}");
+    }
+
+    #[test]
+    fn client_builder_without_bundle_builds() {
+        assert!(client_builder_with(None).unwrap().build().is_ok());
+    }
+}
@@ -0,0 +1,270 @@
+//! Webhooks: signed JSON notifications of release lifecycle events.
+//!
+//! With `[webhooks] urls` set, belaf POSTs a JSON document to each URL
+//! when something happens to a release:
+//!
+//! - `prepare_started` — `belaf prepare` started writing a release;
+//! - `pr_opened` — its release PR was opened;
+//! - `released` — `belaf tag` created a merged release's tags;
+//! - `publish_failed` — `belaf package` could not publish a manifest.
+//!
+//! The body is `{"event", "delivery", "created_at", "data"}`, where
+//! `data` depends on the event. Requests carry the event name in
+//! `X-Belaf-Event`, the delivery id in `X-Belaf-Delivery` and an
+//! HMAC-SHA256 of the body in `X-Belaf-Signature-256: sha256=<hex>`,
+//! keyed with the secret in `BELAF_WEBHOOK_SECRET` (or the variable
+//! `secret_env` names). Nothing is sent without a secret.
+//!
+//! Connection failures, `429` and `5xx` responses are retried per
+//! `[network] max_retries`, with the same delivery id, so receivers can
+//! drop duplicates. A webhook that still fails is logged as a warning:
+//! notifications never fail the command that sends them.
+
+use std::time::Duration;
+
+use hmac::{Hmac, Mac};
+use reqwest::header::CONTENT_TYPE;
+use serde::Serialize;
+use serde_json::Value;
+use sha2::Sha256;
+use time::format_description::well_known::Rfc3339;
+use tracing::{debug, warn};
+
+use crate::core::{
+    api::{
+        retry::{rate_limit_wait, RetryPolicy},
+        ApiError,
+    },
+    config::syntax::{WebhookEvent, WebhooksConfiguration},
+    deterministic, net,
+};
+
+/// Environment variable holding the signing secret, unless `[webhooks]
+/// secret_env` names another.
+pub const SECRET_ENV: &str = "BELAF_WEBHOOK_SECRET";
+
+const TIMEOUT_SECS: u64 = 10;
+
+impl WebhookEvent {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            WebhookEvent::PrepareStarted => "prepare_started",
+            WebhookEvent::PrOpened => "pr_opened",
+            WebhookEvent::Released => "released",
+            WebhookEvent::PublishFailed => "publish_failed",
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Payload<'a> {
+    event: WebhookEvent,
+    delivery: &'a str,
+    created_at: String,
+    data: &'a Value,
+}
+
+/// Send `event` with `data` to every configured URL, if `[webhooks]`
+/// wants the event. Blocks until each delivery succeeded or gave up.
+pub fn emit(cfg: &WebhooksConfiguration, event: WebhookEvent, data: Value) {
+    if cfg.urls.is_empty()
+        || cfg
+            .events
+            .as_ref()
+            .is_some_and(|events| !events.contains(&event))
+    {
+        return;
+    }
+    let secret_env = cfg.secret_env.as_deref().unwrap_or(SECRET_ENV);
+    let Some(secret) = std::env::var(secret_env).ok().filter(|s| !s.is_empty()) else {
+        warn!(
+            "{secret_env} is not set; not sending the {} webhook",
+            event.as_str()
+        );
+        return;
+    };
+
+    let retry = net::retry_policy();
+    let future = deliver_all(&cfg.urls, event, &data, secret.as_bytes(), &retry);
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) => tokio::task::block_in_place(|| handle.block_on(future)),
+        Err(_) => match tokio::runtime::Runtime::new() {
+            Ok(rt) => rt.block_on(future),
+            Err(e) => warn!("cannot send the {} webhook: {e}", event.as_str()),
+        },
+    }
+}
+
+async fn deliver_all(
+    urls: &[String],
+    event: WebhookEvent,
+    data: &Value,
+    secret: &[u8],
+    retry: &RetryPolicy,
+) {
+    let delivery = deterministic::uuid_v4().to_string();
+    let payload = Payload {
+        event,
+        delivery: &delivery,
+        created_at: deterministic::now_utc()
+            .format(&Rfc3339)
+            .unwrap_or_default(),
+        data,
+    };
+    let body = match serde_json::to_vec(&payload) {
+        Ok(body) => body,
+        Err(e) => return warn!("cannot serialize the {} webhook: {e}", event.as_str()),
+    };
+    let client = match net::client_builder().and_then(|b| {
+        b.timeout(Duration::from_secs(TIMEOUT_SECS))
+            .build()
+            .map_err(ApiError::from)
+    }) {
+        Ok(client) => client,
+        Err(e) => return warn!("cannot send the {} webhook: {e}", event.as_str()),
+    };
+
+    let request = Request {
+        event,
+        delivery: &delivery,
+        signature: &signature(secret, &body),
+        body: &body,
+    };
+    for url in urls {
+        match deliver(&client, url, &request, retry).await {
+            Ok(()) => debug!("sent the {} webhook to {url}", event.as_str()),
+            Err(failure) => warn!("the {} webhook to {url} failed: {failure}", event.as_str()),
+        }
+    }
+}
+
+struct Request<'a> {
+    event: WebhookEvent,
+    delivery: &'a str,
+    signature: &'a str,
+    body: &'a [u8],
+}
+
+/// POST `request` to `url`, retrying what [`RetryPolicy`] would retry
+/// for an idempotent call: the delivery id makes replays detectable.
+async fn deliver(
+    client: &reqwest::Client,
+    url: &str,
+    request: &Request<'_>,
+    retry: &RetryPolicy,
+) -> Result<(), String> {
+    let mut attempt = 0;
+    loop {
+        let result = client
+            .post(url)
+            .header(CONTENT_TYPE, "application/json")
+            .header("X-Belaf-Event", request.event.as_str())
+            .header("X-Belaf-Delivery", request.delivery)
+            .header("X-Belaf-Signature-256", request.signature)
+            .body(request.body.to_vec())
+            .send()
+            .await;
+        let (failure, wait) = match result {
+            Ok(resp) if resp.status().is_success() => return Ok(()),
+            Ok(resp) => {
+                let status = resp.status();
+                let wait = match rate_limit_wait(status, resp.headers()) {
+                    Some(wait) => Some(wait.unwrap_or_else(|| retry.backoff(attempt))),
+                    None => status.is_server_error().then(|| retry.backoff(attempt)),
+                };
+                (format!("HTTP {status}"), wait)
+            }
+            Err(e) => {
+                let wait = (e.is_connect() || e.is_timeout()).then(|| retry.backoff(attempt));
+                (e.to_string(), wait)
+            }
+        };
+        match wait {
+            Some(wait) if attempt < retry.max_retries && wait <= retry.max_wait => {
+                debug!("webhook to {url} failed ({failure}); retrying in {wait:?}");
+                tokio::time::sleep(wait).await;
+                attempt += 1;
+            }
+            _ => return Err(failure),
+        }
+    }
+}
+
+/// The `X-Belaf-Signature-256` value of `body`.
+pub fn signature(secret: &[u8], body: &[u8]) -> String {
+    format!("sha256={}", hmac_sha256(secret, body))
+}
+
+/// Hex HMAC-SHA256 (RFC 2104) of `message` keyed with `key`.
+fn hmac_sha256(key: &[u8], message: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC takes keys of any length");
+    mac.update(message);
+    format!("{:x}", mac.finalize().into_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    fn hmac_matches_rfc_4231() {
+        assert_eq!(
+            hmac_sha256(b"Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        assert_eq!(
+            hmac_sha256(
+                &[0xaa; 131],
+                b"Test Using Larger Than Block-Size Key - Hash Key First"
+            ),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+    }
+
+    #[tokio::test]
+    async fn deliveries_are_signed_and_retried() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/hook"))
+            .and(header("X-Belaf-Event", "released"))
+            .respond_with(ResponseTemplate::new(204))
+            .mount(&server)
+            .await;
+
+        let retry = RetryPolicy {
+            base_delay: Duration::from_millis(1),
+            ..RetryPolicy::default()
+        };
+        let data = serde_json::json!({ "tags": ["v1.2.0"] });
+        deliver_all(
+            &[format!("{}/hook", server.uri())],
+            WebhookEvent::Released,
+            &data,
+            b"s3cret",
+            &retry,
+        )
+        .await;
+
+        let requests = server.received_requests().await.unwrap();
+        assert_eq!(requests.len(), 2, "the 503 is retried once");
+        let last = &requests[1];
+        let payload: Value = serde_json::from_slice(&last.body).unwrap();
+        assert_eq!(payload["event"], "released");
+        assert_eq!(payload["data"], data);
+        assert_eq!(
+            last.headers["X-Belaf-Delivery"],
+            requests[0].headers["X-Belaf-Delivery"]
+        );
+        assert_eq!(
+            last.headers["X-Belaf-Signature-256"],
+            signature(b"s3cret", &last.body).as_str()
+        );
+    }
+}
@@ -0,0 +1,105 @@
+//! Where commands print.
+//!
+//! Everything belaf prints goes through `print!`, `println!`, `eprint!`
+//! and `eprintln!`, which the crate root redefines to call [`stdout`]
+//! and [`stderr`] here. Normally those write to the process's streams
+//! like the standard macros. While [`capture`] runs a closure they
+//! collect into buffers instead, which is how
+//! [`crate::in_process::run`] hands a command's output back to its
+//! caller without spawning the binary.
+//!
+//! The terminal UIs draw on the real terminal and are never captured;
+//! [`is_capturing`] makes [`crate::core::ui::utils::is_interactive_terminal`]
+//! report `false` so commands fall back to plain output.
+
+use std::{
+    fmt,
+    io::Write,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+};
+
+static CAPTURING: AtomicBool = AtomicBool::new(false);
+static CAPTURED: Mutex<Option<Captured>> = Mutex::new(None);
+
+/// What a captured closure printed.
+#[derive(Clone, Debug, Default)]
+pub struct Captured {
+    pub stdout: Vec<u8>,
+    pub stderr: Vec<u8>,
+}
+
+/// Print to stdout, or into the capture buffer.
+pub fn stdout(args: fmt::Arguments<'_>) {
+    if !write_captured(args, |c| &mut c.stdout) {
+        std::print!("{args}");
+    }
+}
+
+/// Print to stderr, or into the capture buffer.
+pub fn stderr(args: fmt::Arguments<'_>) {
+    if !write_captured(args, |c| &mut c.stderr) {
+        std::eprint!("{args}");
+    }
+}
+
+fn write_captured(args: fmt::Arguments<'_>, stream: fn(&mut Captured) -> &mut Vec<u8>) -> bool {
+    if !CAPTURING.load(Ordering::Relaxed) {
+        return false;
+    }
+    let Ok(mut slot) = CAPTURED.lock() else {
+        return false;
+    };
+    match slot.as_mut() {
+        Some(captured) => stream(captured).write_fmt(args).is_ok(),
+        None => false,
+    }
+}
+
+/// Whether output is being captured right now.
+pub fn is_capturing() -> bool {
+    CAPTURING.load(Ordering::Relaxed)
+}
+
+/// Run `f`, collecting everything printed meanwhile, from any thread.
+/// Callers serialise captures; a nested capture would steal the outer
+/// one's output.
+pub fn capture<R>(f: impl FnOnce() -> R) -> (R, Captured) {
+    struct Reset;
+    impl Drop for Reset {
+        fn drop(&mut self) {
+            CAPTURING.store(false, Ordering::Relaxed);
+        }
+    }
+
+    if let Ok(mut slot) = CAPTURED.lock() {
+        *slot = Some(Captured::default());
+    }
+    CAPTURING.store(true, Ordering::Relaxed);
+    let reset = Reset;
+    let result = f();
+    drop(reset);
+    let captured = CAPTURED
+        .lock()
+        .ok()
+        .and_then(|mut slot| slot.take())
+        .unwrap_or_default();
+    (result, captured)
+}
+
+/// An [`std::io::Write`] for [`stderr`], to point loggers at.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Stderr;
+
+impl std::io::Write for Stderr {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        stderr(format_args!("{}", String::from_utf8_lossy(buf)));
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        std::io::stderr().flush()
+    }
+}
@@ -0,0 +1,593 @@
+//! Package manager manifests for released command-line tools:
+//! `belaf package <unit>`.
+//!
+//! A unit with a `[packaging.<unit>]` table ships prebuilt binaries, and
+//! each release regenerates the Homebrew formula, Scoop manifest and AUR
+//! `PKGBUILD` that install them. The step runs in CI once the release is
+//! tagged and its archives are built: belaf hashes the archives in
+//! `--artifacts`, renders each configured manifest for the tagged version
+//! and commits it to the tap, bucket or AUR mirror repository, pushing
+//! with the same short-lived credentials the belaf API issues for release
+//! branches.
+//!
+//! Archives are matched to platforms by the Rust target triple in their
+//! file name (`mycli-aarch64-apple-darwin.tar.gz`), which is how
+//! cargo-dist, `cross` and most release workflows name them.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use sha2::{Digest, Sha256};
+
+use crate::core::{
+    config::syntax::{PackageRepoConfig, PackagingConfig},
+    git::repository::remote_callbacks,
+};
+
+/// Reject a `[packaging.<unit>]` table that couldn't publish anything.
+pub fn validate(cfg: &PackagingConfig) -> Result<()> {
+    let managers = PackageManager::configured(cfg);
+    if managers.is_empty() {
+        bail!("configure at least one of `homebrew`, `scoop` or `aur`");
+    }
+    for (manager, repo) in managers {
+        let valid = repo.repo.split_once('/').is_some_and(|(owner, name)| {
+            !owner.is_empty() && !name.is_empty() && !name.contains('/')
+        });
+        if !valid {
+            bail!(
+                "{} repo `{}` must be written as `owner/name`",
+                manager.label(),
+                repo.repo
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Where a manifest is published.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PackageManager {
+    Homebrew,
+    Scoop,
+    Aur,
+}
+
+impl PackageManager {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Homebrew => "Homebrew",
+            Self::Scoop => "Scoop",
+            Self::Aur => "AUR",
+        }
+    }
+
+    /// The configured managers, in a fixed order.
+    pub fn configured(cfg: &PackagingConfig) -> Vec<(Self, &PackageRepoConfig)> {
+        [
+            (Self::Homebrew, cfg.homebrew.as_ref()),
+            (Self::Scoop, cfg.scoop.as_ref()),
+            (Self::Aur, cfg.aur.as_ref()),
+        ]
+        .into_iter()
+        .filter_map(|(manager, repo)| Some((manager, repo?)))
+        .collect()
+    }
+
+    /// Package name when the config doesn't set one: the binary, with
+    /// the AUR's `-bin` suffix for prebuilt packages.
+    fn default_name(self, binary: &str) -> String {
+        match self {
+            Self::Aur => format!("{binary}-bin"),
+            Self::Homebrew | Self::Scoop => binary.to_string(),
+        }
+    }
+
+    /// File written in the package repository when the config doesn't
+    /// set one.
+    fn default_path(self, name: &str) -> String {
+        match self {
+            Self::Homebrew => format!("Formula/{name}.rb"),
+            Self::Scoop => format!("bucket/{name}.json"),
+            Self::Aur => "PKGBUILD".to_string(),
+        }
+    }
+}
+
+/// A platform a prebuilt archive runs on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Platform {
+    MacosArm,
+    MacosIntel,
+    LinuxArm,
+    LinuxIntel,
+    WindowsIntel,
+    WindowsArm,
+}
+
+impl Platform {
+    const ALL: [Self; 6] = [
+        Self::MacosArm,
+        Self::MacosIntel,
+        Self::LinuxArm,
+        Self::LinuxIntel,
+        Self::WindowsIntel,
+        Self::WindowsArm,
+    ];
+
+    /// The target-triple prefix archives for this platform are named with.
+    fn triple(self) -> &'static str {
+        match self {
+            Self::MacosArm => "aarch64-apple-darwin",
+            Self::MacosIntel => "x86_64-apple-darwin",
+            Self::LinuxArm => "aarch64-unknown-linux",
+            Self::LinuxIntel => "x86_64-unknown-linux",
+            Self::WindowsIntel => "x86_64-pc-windows",
+            Self::WindowsArm => "aarch64-pc-windows",
+        }
+    }
+
+    fn from_file_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|p| name.contains(p.triple()))
+    }
+}
+
+const ARCHIVE_EXTENSIONS: &[&str] = &[".tar.gz", ".tgz", ".tar.xz", ".tar.zst", ".zip"];
+
+/// A prebuilt archive of the release.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Artifact {
+    pub platform: Platform,
+    pub file: String,
+    pub url: String,
+    pub sha256: String,
+}
+
+/// Hash the archives in `dir` and work out their download URLs from
+/// `url_template` (`{tag}`, `{version}` and `{file}` are substituted).
+/// With several archives for one platform (gnu and musl builds, say)
+/// the first by file name wins.
+pub fn collect_artifacts(
+    dir: &Path,
+    url_template: &str,
+    tag: &str,
+    version: &str,
+) -> Result<Vec<Artifact>> {
+    let mut files: Vec<String> = fs::read_dir(dir)
+        .with_context(|| format!("failed to list artifacts in `{}`", dir.display()))?
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter(|name| ARCHIVE_EXTENSIONS.iter().any(|ext| name.ends_with(ext)))
+        .collect();
+    files.sort();
+
+    let mut artifacts: Vec<Artifact> = Vec::new();
+    for file in files {
+        let Some(platform) = Platform::from_file_name(&file) else {
+            continue;
+        };
+        if artifacts.iter().any(|a| a.platform == platform) {
+            continue;
+        }
+        let bytes = fs::read(dir.join(&file))
+            .with_context(|| format!("failed to read `{}`", dir.join(&file).display()))?;
+        let sha256 = format!("{:x}", Sha256::digest(&bytes));
+        let url = url_template
+            .replace("{tag}", tag)
+            .replace("{version}", version)
+            .replace("{file}", &file);
+        artifacts.push(Artifact {
+            platform,
+            file,
+            url,
+            sha256,
+        });
+    }
+    artifacts.sort_by_key(|a| a.platform);
+    Ok(artifacts)
+}
+
+/// What the manifests describe.
+#[derive(Clone, Debug)]
+pub struct Package {
+    pub binary: String,
+    pub version: String,
+    pub description: String,
+    pub homepage: String,
+    pub license: Option<String>,
+    pub artifacts: Vec<Artifact>,
+}
+
+impl Package {
+    fn artifact(&self, platform: Platform) -> Option<&Artifact> {
+        self.artifacts.iter().find(|a| a.platform == platform)
+    }
+}
+
+/// A rendered manifest and where it goes.
+#[derive(Clone, Debug)]
+pub struct PackageFile {
+    pub manager: PackageManager,
+    /// `owner/name` of the package repository.
+    pub repo: String,
+    pub branch: Option<String>,
+    pub path: String,
+    pub content: String,
+}
+
+/// Render `manager`'s manifest for `package`, as configured by `repo`.
+pub fn render(
+    manager: PackageManager,
+    repo: &PackageRepoConfig,
+    package: &Package,
+) -> Result<PackageFile> {
+    let name = repo
+        .name
+        .clone()
+        .unwrap_or_else(|| manager.default_name(&package.binary));
+    let content = match manager {
+        PackageManager::Homebrew => homebrew_formula(&name, package),
+        PackageManager::Scoop => scoop_manifest(package),
+        PackageManager::Aur => pkgbuild(&name, package),
+    }?;
+    Ok(PackageFile {
+        manager,
+        repo: repo.repo.clone(),
+        branch: repo.branch.clone(),
+        path: repo
+            .path
+            .clone()
+            .unwrap_or_else(|| manager.default_path(&name)),
+        content,
+    })
+}
+
+/// Homebrew class names are the formula name in CamelCase: `my-cli` → `MyCli`.
+fn formula_class(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+fn homebrew_formula(name: &str, package: &Package) -> Result<String> {
+    let os_blocks = [
+        ("on_macos", Platform::MacosArm, Platform::MacosIntel),
+        ("on_linux", Platform::LinuxArm, Platform::LinuxIntel),
+    ];
+    if !os_blocks.iter().any(|(_, arm, intel)| {
+        package
+            .artifact(*arm)
+            .or(package.artifact(*intel))
+            .is_some()
+    }) {
+        bail!("no macOS or Linux archives to put in a Homebrew formula");
+    }
+
+    let mut out = String::new();
+    writeln!(out, "class {} < Formula", formula_class(name))?;
+    writeln!(out, "  desc {:?}", package.description)?;
+    writeln!(out, "  homepage {:?}", package.homepage)?;
+    writeln!(out, "  version {:?}", package.version)?;
+    if let Some(license) = &package.license {
+        writeln!(out, "  license {license:?}")?;
+    }
+    for (block, arm, intel) in os_blocks {
+        let arches: Vec<(&str, &Artifact)> = [("on_arm", arm), ("on_intel", intel)]
+            .into_iter()
+            .filter_map(|(arch, platform)| Some((arch, package.artifact(platform)?)))
+            .collect();
+        if arches.is_empty() {
+            continue;
+        }
+        writeln!(out, "\n  {block} do")?;
+        for (arch, artifact) in arches {
+            writeln!(out, "    {arch} do")?;
+            writeln!(out, "      url {:?}", artifact.url)?;
+            writeln!(out, "      sha256 {:?}", artifact.sha256)?;
+            writeln!(out, "    end")?;
+        }
+        writeln!(out, "  end")?;
+    }
+    writeln!(out, "\n  def install")?;
+    writeln!(out, "    bin.install {:?}", package.binary)?;
+    writeln!(out, "  end")?;
+    writeln!(out, "\n  test do")?;
+    writeln!(
+        out,
+        "    system \"#{{bin}}/{}\", \"--version\"",
+        package.binary
+    )?;
+    writeln!(out, "  end")?;
+    writeln!(out, "end")?;
+    Ok(out)
+}
+
+fn scoop_manifest(package: &Package) -> Result<String> {
+    let mut architecture = serde_json::Map::new();
+    for (arch, platform) in [
+        ("64bit", Platform::WindowsIntel),
+        ("arm64", Platform::WindowsArm),
+    ] {
+        if let Some(artifact) = package.artifact(platform) {
+            architecture.insert(
+                arch.to_string(),
+                serde_json::json!({ "url": artifact.url, "hash": artifact.sha256 }),
+            );
+        }
+    }
+    if architecture.is_empty() {
+        bail!("no Windows archives to put in a Scoop manifest");
+    }
+
+    let mut manifest = serde_json::json!({
+        "version": package.version,
+        "description": package.description,
+        "homepage": package.homepage,
+    });
+    if let Some(license) = &package.license {
+        manifest["license"] = license.clone().into();
+    }
+    manifest["architecture"] = architecture.into();
+    manifest["bin"] = format!("{}.exe", package.binary).into();
+    Ok(serde_json::to_string_pretty(&manifest)? + "\n")
+}
+
+fn pkgbuild(name: &str, package: &Package) -> Result<String> {
+    let arches: Vec<(&str, &Artifact)> = [
+        ("x86_64", Platform::LinuxIntel),
+        ("aarch64", Platform::LinuxArm),
+    ]
+    .into_iter()
+    .filter_map(|(arch, platform)| Some((arch, package.artifact(platform)?)))
+    .collect();
+    if arches.is_empty() {
+        bail!("no Linux archives to put in a PKGBUILD");
+    }
+    // pkgver may not contain hyphens; prereleases keep their suffix.
+    let pkgver = package.version.replace('-', "_");
+    let binary = &package.binary;
+
+    let mut out = String::new();
+    writeln!(
+        out,
+        "# Generated by `belaf package`; edits are overwritten on the next release."
+    )?;
+    writeln!(out, "pkgname={name}")?;
+    writeln!(out, "pkgver={pkgver}")?;
+    writeln!(out, "pkgrel=1")?;
+    writeln!(out, "pkgdesc={}", shell_quote(&package.description))?;
+    let arch_list: Vec<String> = arches.iter().map(|(a, _)| format!("'{a}'")).collect();
+    writeln!(out, "arch=({})", arch_list.join(" "))?;
+    writeln!(out, "url={}", shell_quote(&package.homepage))?;
+    if let Some(license) = &package.license {
+        writeln!(out, "license=({})", shell_quote(license))?;
+    }
+    if name != binary.as_str() {
+        writeln!(out, "provides=('{binary}')")?;
+        writeln!(out, "conflicts=('{binary}')")?;
+    }
+    for (arch, artifact) in &arches {
+        let local = format!("{name}-{pkgver}-{arch}-{}", artifact.file);
+        writeln!(
+            out,
+            "source_{arch}=({})",
+            shell_quote(&format!("{local}::{}", artifact.url))
+        )?;
+        writeln!(out, "sha256sums_{arch}=('{}')", artifact.sha256)?;
+    }
+    writeln!(out, "\npackage() {{")?;
+    writeln!(
+        out,
+        "  install -Dm755 \"$(find . -type f -name {binary} | head -n1)\" \"$pkgdir/usr/bin/{binary}\""
+    )?;
+    writeln!(out, "}}")?;
+    Ok(out)
+}
+
+fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+/// Commit `file` to a clone of `remote_url` and push it, authenticating
+/// with `token` over HTTPS or the SSH agent. Returns `false`, without
+/// committing, when the file already has that content.
+pub fn publish(
+    remote_url: &str,
+    token: Option<&str>,
+    file: &PackageFile,
+    message: &str,
+) -> Result<bool> {
+    let dir = tempfile::tempdir().context("failed to create a temporary directory")?;
+    publish_in(dir.path(), remote_url, token, file, message)
+}
+
+fn publish_in(
+    dir: &Path,
+    remote_url: &str,
+    token: Option<&str>,
+    file: &PackageFile,
+    message: &str,
+) -> Result<bool> {
+    let mut fetch = git2::FetchOptions::new();
+    fetch.remote_callbacks(remote_callbacks(token));
+    let mut builder = git2::build::RepoBuilder::new();
+    builder.fetch_options(fetch);
+    if let Some(branch) = &file.branch {
+        builder.branch(branch);
+    }
+    let repo = builder
+        .clone(remote_url, dir)
+        .with_context(|| format!("failed to clone `{}`", file.repo))?;
+
+    let target = dir.join(&file.path);
+    if fs::read_to_string(&target).is_ok_and(|existing| existing == file.content) {
+        return Ok(false);
+    }
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(&target, &file.content)
+        .with_context(|| format!("failed to write `{}`", target.display()))?;
+
+    let mut index = repo.index()?;
+    index.add_path(Path::new(&file.path))?;
+    index.write()?;
+    let tree = repo.find_tree(index.write_tree()?)?;
+    let signature = repo
+        .signature()
+        .or_else(|_| git2::Signature::now("belaf", "belaf@devnull"))?;
+    let head = repo.head()?;
+    let branch = head
+        .shorthand()
+        .context("the package repository has no checked-out branch")?
+        .to_string();
+    let parent = head.peel_to_commit()?;
+    repo.commit(
+        Some("HEAD"),
+        &signature,
+        &signature,
+        message,
+        &tree,
+        &[&parent],
+    )?;
+
+    let mut push = git2::PushOptions::new();
+    push.remote_callbacks(remote_callbacks(token));
+    repo.find_remote("origin")?
+        .push(
+            &[format!("refs/heads/{branch}:refs/heads/{branch}")],
+            Some(&mut push),
+        )
+        .with_context(|| format!("failed to push to `{}`", file.repo))?;
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn artifact(platform: Platform, file: &str) -> Artifact {
+        Artifact {
+            platform,
+            file: file.to_string(),
+            url: format!("https://example.com/v1.2.0/{file}"),
+            sha256: "ab".repeat(32),
+        }
+    }
+
+    fn package() -> Package {
+        Package {
+            binary: "my-cli".into(),
+            version: "1.2.0".into(),
+            description: "Does things".into(),
+            homepage: "https://github.com/acme/my-cli".into(),
+            license: Some("MIT".into()),
+            artifacts: vec![
+                artifact(Platform::MacosArm, "my-cli-aarch64-apple-darwin.tar.gz"),
+                artifact(
+                    Platform::LinuxIntel,
+                    "my-cli-x86_64-unknown-linux-gnu.tar.gz",
+                ),
+                artifact(Platform::WindowsIntel, "my-cli-x86_64-pc-windows-msvc.zip"),
+            ],
+        }
+    }
+
+    fn repo_config() -> PackageRepoConfig {
+        PackageRepoConfig {
+            repo: "acme/tap".into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn homebrew_formula_has_a_block_per_platform() {
+        let file = render(PackageManager::Homebrew, &repo_config(), &package()).unwrap();
+        assert_eq!(file.path, "Formula/my-cli.rb");
+        let hash = "ab".repeat(32);
+        assert_eq!(
+            file.content,
+            format!(
+                "class MyCli < Formula\n  desc \"Does things\"\n  homepage \"https://github.com/acme/my-cli\"\n  version \"1.2.0\"\n  license \"MIT\"\n\n  on_macos do\n    on_arm do\n      url \"https://example.com/v1.2.0/my-cli-aarch64-apple-darwin.tar.gz\"\n      sha256 \"{hash}\"\n    end\n  end\n\n  on_linux do\n    on_intel do\n      url \"https://example.com/v1.2.0/my-cli-x86_64-unknown-linux-gnu.tar.gz\"\n      sha256 \"{hash}\"\n    end\n  end\n\n  def install\n    bin.install \"my-cli\"\n  end\n\n  test do\n    system \"#{{bin}}/my-cli\", \"--version\"\n  end\nend\n"
+            )
+        );
+    }
+
+    #[test]
+    fn scoop_and_aur_only_take_their_platforms() {
+        let scoop = render(PackageManager::Scoop, &repo_config(), &package()).unwrap();
+        assert_eq!(scoop.path, "bucket/my-cli.json");
+        let manifest: serde_json::Value = serde_json::from_str(&scoop.content).unwrap();
+        assert_eq!(manifest["bin"], "my-cli.exe");
+        assert_eq!(
+            manifest["architecture"]["64bit"]["url"],
+            "https://example.com/v1.2.0/my-cli-x86_64-pc-windows-msvc.zip"
+        );
+        assert!(manifest["architecture"].get("arm64").is_none());
+
+        let mut prerelease = package();
+        prerelease.version = "1.2.0-rc.1".into();
+        let aur = render(PackageManager::Aur, &repo_config(), &prerelease).unwrap();
+        assert_eq!(aur.path, "PKGBUILD");
+        assert!(aur
+            .content
+            .contains("pkgname=my-cli-bin\npkgver=1.2.0_rc.1\n"));
+        assert!(aur.content.contains("arch=('x86_64')\n"));
+        assert!(aur.content.contains("provides=('my-cli')\n"));
+        assert!(!aur.content.contains("apple-darwin"));
+
+        let mut mac_only = package();
+        mac_only.artifacts.truncate(1);
+        assert!(render(PackageManager::Scoop, &repo_config(), &mac_only).is_err());
+    }
+
+    #[test]
+    fn artifacts_are_matched_by_target_triple() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in [
+            "my-cli-x86_64-unknown-linux-gnu.tar.gz",
+            "my-cli-x86_64-unknown-linux-musl.tar.gz",
+            "my-cli-aarch64-apple-darwin.tar.xz",
+            "my-cli-aarch64-apple-darwin.tar.xz.sha256",
+            "my-cli-riscv64gc-unknown-linux-gnu.tar.gz",
+        ] {
+            fs::write(dir.path().join(name), name).unwrap();
+        }
+        let artifacts = collect_artifacts(
+            dir.path(),
+            "https://dl.example.com/{tag}/{file}?v={version}",
+            "my-cli-v1.2.0",
+            "1.2.0",
+        )
+        .unwrap();
+
+        let files: Vec<&str> = artifacts.iter().map(|a| a.file.as_str()).collect();
+        assert_eq!(
+            files,
+            vec![
+                "my-cli-aarch64-apple-darwin.tar.xz",
+                "my-cli-x86_64-unknown-linux-gnu.tar.gz",
+            ]
+        );
+        assert_eq!(
+            artifacts[1].url,
+            "https://dl.example.com/my-cli-v1.2.0/my-cli-x86_64-unknown-linux-gnu.tar.gz?v=1.2.0"
+        );
+        assert_eq!(
+            artifacts[1].sha256,
+            format!(
+                "{:x}",
+                Sha256::digest(b"my-cli-x86_64-unknown-linux-gnu.tar.gz")
+            )
+        );
+        assert_eq!(formula_class("my_cli2"), "MyCli2");
+    }
+}
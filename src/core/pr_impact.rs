@@ -0,0 +1,145 @@
+//! What a feature pull request adds to the next release of each unit.
+//!
+//! The pull request is taken to be the commits on HEAD since it diverged
+//! from a base ref. Of those, each unit gets the ones history analysis
+//! attributes to it, so path matching, trailers and ignored paths apply
+//! as they do for a release. `belaf comment` renders the result as a
+//! preview on the pull request, and `belaf label` labels it by.
+
+use std::collections::HashSet;
+
+use anyhow::{Context, Result};
+
+use crate::core::{
+    bump::{self, BumpConfig},
+    changelog::{ChangelogConfig, Commit, GitConfig},
+    session::AppSession,
+    wire::known::Ecosystem,
+    workflow::{generate_and_write_project_changelog, ChangelogGenerationParams},
+};
+
+/// What the pull request's commits add to the next release of one unit.
+#[derive(Clone, Debug)]
+pub struct UnitImpact {
+    pub name: String,
+    pub ecosystem: Ecosystem,
+    pub version: String,
+    /// `None` when the commits call for no bump.
+    pub next_version: Option<String>,
+    pub bump_type: String,
+    /// Whether any of the commits is a breaking change.
+    pub breaking: bool,
+    /// The changelog entry of the commits, if any is user-facing and it
+    /// was asked for.
+    pub changelog: Option<String>,
+}
+
+/// The units the commits on HEAD since `base` change, in dependency
+/// order. Changelog entries are rendered only `with_changelogs`.
+pub fn analyze(sess: &AppSession, base: &str, with_changelogs: bool) -> Result<Vec<UnitImpact>> {
+    let (_, range) = sess.repo.commits_since(base)?;
+    let range: HashSet<_> = range.into_iter().collect();
+
+    let histories = sess
+        .analyze_histories()
+        .context("failed to analyze project histories")?;
+    let git_config = GitConfig::from_user_config(&sess.changelog_config);
+    let bump_config = BumpConfig::from_user_config(&sess.bump_config);
+
+    let graph = sess.graph();
+    let mut units = Vec::new();
+    for ident in graph.toposorted() {
+        let unit = graph.lookup(ident);
+        let commits: Vec<Commit> = histories
+            .lookup(ident)
+            .commits()
+            .into_iter()
+            .filter(|cid| range.contains(cid))
+            .filter_map(|cid| sess.repo.get_commit_details(*cid).ok())
+            .collect();
+        if commits.is_empty() {
+            continue;
+        }
+
+        let version = unit.version.to_string();
+        let analysis = bump::analyze_commits(&commits)
+            .with_context(|| format!("failed to analyze commits for {}", unit.user_facing_name))?;
+        let bump_type = analysis
+            .recommendation
+            .apply_config(&bump_config, Some(&version))
+            .as_str()
+            .to_string();
+        let next_version = match bump_type.as_str() {
+            "no bump" => None,
+            bump => Some(
+                unit.version
+                    .bumped(bump)
+                    .with_context(|| {
+                        format!("failed to apply version bump to {}", unit.user_facing_name)
+                    })?
+                    .to_string(),
+            ),
+        };
+
+        let ecosystem = unit
+            .qualified_names()
+            .get(1)
+            .map(|s| Ecosystem::classify(s))
+            .unwrap_or_else(|| Ecosystem::classify("cargo"));
+        let changelog = if with_changelogs {
+            let changelog_config = ChangelogConfig::from_user_config(
+                &sess
+                    .changelog_config
+                    .for_project(&unit.user_facing_name, ecosystem.as_str()),
+            );
+            let prefix = unit.prefix().escaped();
+            let result = generate_and_write_project_changelog(&ChangelogGenerationParams {
+                repo: &sess.repo,
+                project_name: &unit.user_facing_name,
+                prefix: &prefix,
+                version: next_version.as_deref(),
+                commits: &commits,
+                git_config: &git_config,
+                changelog_config: &changelog_config,
+                bump_config: &bump_config,
+                write_to_file: false,
+                custom_output_path: None,
+                github_owner: None,
+                github_repo: None,
+                github_token: None,
+                previous_version: None,
+                tags: None,
+                replace_unreleased: false,
+            })?;
+            result
+                .has_user_changes
+                .then(|| entry_of(&result.content).trim_end().to_string())
+        } else {
+            None
+        };
+
+        units.push(UnitImpact {
+            name: unit.user_facing_name.clone(),
+            ecosystem,
+            version,
+            next_version,
+            bump_type,
+            breaking: analysis.breaking_count > 0,
+            changelog,
+        });
+    }
+    Ok(units)
+}
+
+/// `changelog` from its first entry (`## ` heading) on, without the
+/// file's header.
+fn entry_of(changelog: &str) -> &str {
+    let mut offset = 0;
+    for line in changelog.split_inclusive('\n') {
+        if line.starts_with("## ") {
+            return &changelog[offset..];
+        }
+        offset += line.len();
+    }
+    changelog
+}
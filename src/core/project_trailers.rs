@@ -0,0 +1,82 @@
+//! Commit trailers that name the release units a commit belongs to.
+//!
+//! With `[commit_attribution] strategy = "trailer_first"`, a commit can
+//! say which units it affects:
+//!
+//! ```text
+//! fix: share the retry helper
+//!
+//! Belaf-Projects: api, web
+//! ```
+//!
+//! Such a commit is attributed to exactly the units listed, whatever its
+//! scope and the paths it touches. Names compare case-insensitively with
+//! the units' names; a trailer may be repeated. Commits without the
+//! trailer, or whose trailer names no known unit, fall back to the scope
+//! and path inference every strategy uses. `belaf lint-commits` reports
+//! names that match no unit.
+
+/// The trailer key.
+pub const TRAILER: &str = "Belaf-Projects";
+
+/// The `[commit_attribution] strategy` that honours [`TRAILER`].
+pub const STRATEGY: &str = "trailer_first";
+
+/// The unit names `message`'s [`TRAILER`]s list, in order.
+pub fn declared_projects(message: &str) -> Vec<String> {
+    let Ok(trailers) = git2::message_trailers_strs(message) else {
+        return Vec::new();
+    };
+    trailers
+        .iter()
+        .filter(|(key, _)| key.eq_ignore_ascii_case(TRAILER))
+        .flat_map(|(_, value)| value.split(','))
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::to_owned)
+        .collect()
+}
+
+/// Mark in `hits` each of `project_names` that `message` declares.
+/// Returns whether any was: if not, the commit's attribution is left to
+/// inference.
+pub fn attribute(message: &str, project_names: &[String], hits: &mut [bool]) -> bool {
+    let mut matched = false;
+    for name in declared_projects(message) {
+        if let Some(idx) = project_names
+            .iter()
+            .position(|p| p.eq_ignore_ascii_case(&name))
+        {
+            hits[idx] = true;
+            matched = true;
+        }
+    }
+    matched
+}
+
+/// The names `message` declares that aren't among `project_names`.
+pub fn unknown_projects(message: &str, project_names: &[String]) -> Vec<String> {
+    declared_projects(message)
+        .into_iter()
+        .filter(|name| !project_names.iter().any(|p| p.eq_ignore_ascii_case(name)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trailers_list_projects() {
+        let message = "fix: share the retry helper\n\nBody text.\n\n\
+                       Belaf-Projects: api, Web\nbelaf-projects: cli\nSigned-off-by: A <a@b.c>\n";
+        assert_eq!(declared_projects(message), vec!["api", "Web", "cli"]);
+        assert!(declared_projects("fix: x\n\nBelaf-Projects mentioned in prose").is_empty());
+
+        let names = vec!["api".to_owned(), "web".to_owned(), "docs".to_owned()];
+        let mut hits = vec![false; 3];
+        assert!(attribute(message, &names, &mut hits));
+        assert_eq!(hits, vec![true, true, false]);
+        assert_eq!(unknown_projects(message, &names), vec!["cli"]);
+    }
+}
@@ -0,0 +1,431 @@
+//! Two-phase releases: cut, then publish.
+//!
+//! `belaf release cut` tags a merged release, pushes the tags and opens a
+//! draft GitHub release for each, with the build's artifacts attached.
+//! Their size and SHA-256 are recorded in the manifest's `publication`
+//! block, together with the drafts. `belaf release publish` makes the
+//! drafts public once QA signed off, after checking that the assets on
+//! each draft are still the ones recorded. The `released` webhook, Jira
+//! and milestones only see the release at that point; registries publish
+//! from the published GitHub release, as for any other.
+//!
+//! Artifacts are read from one directory: files directly in it belong to
+//! every release, files in `<dir>/<unit>/` only to that unit's.
+//!
+//! Drafts are published dependencies first (see [`publish_order`]), so a
+//! registry publish triggered by a release finds the versions it needs.
+//! With `[release.publish] wait_for_registry`, `publish` also waits for
+//! each release to show on its registry before publishing its dependents.
+//! Drafts that don't depend on each other are published concurrently, up
+//! to `parallelism` at a time; see [`run_in_order`].
+
+use std::fs;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context};
+use futures::stream::{FuturesUnordered, StreamExt};
+use sha2::{Digest, Sha256};
+
+use crate::core::deterministic;
+use crate::core::errors::Result;
+use crate::core::github::rest::ReleaseAsset;
+use crate::core::manifest::{Artifact, ReleaseEntry};
+
+/// The artifacts in `dir` for the release shared by `members`, by name.
+pub fn collect_artifacts(
+    dir: &Path,
+    members: &[&ReleaseEntry],
+) -> Result<Vec<(PathBuf, Artifact)>> {
+    let mut dirs = vec![dir.to_path_buf()];
+    dirs.extend(members.iter().map(|r| dir.join(&r.name)));
+
+    let mut artifacts: Vec<(PathBuf, Artifact)> = Vec::new();
+    for dir in dirs.iter().filter(|d| d.is_dir()) {
+        let entries =
+            fs::read_dir(dir).with_context(|| format!("failed to read `{}`", dir.display()))?;
+        for entry in entries {
+            let path = entry?.path();
+            if !path.is_file() {
+                continue;
+            }
+            let artifact = artifact(&path)?;
+            if let Some((other, _)) = artifacts.iter().find(|(_, a)| a.name == artifact.name) {
+                bail!(
+                    "`{}` and `{}` would both be uploaded as `{}`",
+                    other.display(),
+                    path.display(),
+                    artifact.name
+                );
+            }
+            artifacts.push((path, artifact));
+        }
+    }
+    artifacts.sort_by(|(_, a), (_, b)| a.name.cmp(&b.name));
+    Ok(artifacts)
+}
+
+/// Name, size and SHA-256 of the file at `path`.
+pub fn artifact(path: &Path) -> Result<Artifact> {
+    let data = fs::read(path).with_context(|| format!("failed to read `{}`", path.display()))?;
+    let name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .with_context(|| format!("`{}` has no UTF-8 file name", path.display()))?;
+    Ok(Artifact {
+        name: name.to_string(),
+        size: data.len() as u64,
+        sha256: format!("{:x}", Sha256::digest(&data)),
+    })
+}
+
+/// How the assets on a draft differ from the artifacts recorded when it
+/// was cut. A digest is only compared when the forge reports one.
+pub fn asset_problems(recorded: &[Artifact], uploaded: &[ReleaseAsset]) -> Vec<String> {
+    let mut problems = Vec::new();
+    for artifact in recorded {
+        match uploaded.iter().find(|a| a.name == artifact.name) {
+            None => problems.push(format!("`{}` is missing", artifact.name)),
+            Some(asset) if asset.size != artifact.size => problems.push(format!(
+                "`{}` is {} bytes, {} when cut",
+                artifact.name, asset.size, artifact.size
+            )),
+            Some(ReleaseAsset {
+                sha256: Some(sha256),
+                ..
+            }) if *sha256 != artifact.sha256 => problems.push(format!(
+                "`{}` has SHA-256 {sha256}, {} when cut",
+                artifact.name, artifact.sha256
+            )),
+            Some(_) => {}
+        }
+    }
+    for asset in uploaded {
+        if !recorded.iter().any(|a| a.name == asset.name) {
+            problems.push(format!("`{}` was added after the cut", asset.name));
+        }
+    }
+    problems
+}
+
+/// How downloaded `files` differ from the artifacts recorded when their
+/// release was cut. Recorded artifacts that weren't downloaded are fine.
+pub fn artifact_problems(recorded: &[Artifact], files: &[Artifact]) -> Vec<String> {
+    let mut problems = Vec::new();
+    for file in files {
+        match recorded.iter().find(|a| a.name == file.name) {
+            None => problems.push(format!("`{}` is not an artifact of the release", file.name)),
+            Some(artifact) if artifact.sha256 != file.sha256 => problems.push(format!(
+                "`{}` has SHA-256 {}, the release {}",
+                file.name, file.sha256, artifact.sha256
+            )),
+            Some(_) => {}
+        }
+    }
+    problems
+}
+
+/// The order to publish `tags` in, each given as the units it releases.
+/// A tag comes after every tag releasing a unit one of its own units
+/// `depends_on`; otherwise the given order is kept. Returns indices
+/// into `tags`.
+pub fn publish_order(
+    tags: &[Vec<&str>],
+    depends_on: impl Fn(&str, &str) -> bool,
+) -> Result<Vec<usize>> {
+    let needs = |i: usize, j: usize| {
+        i != j
+            && tags[i]
+                .iter()
+                .any(|unit| tags[j].iter().any(|dep| depends_on(unit, dep)))
+    };
+
+    let mut order = Vec::with_capacity(tags.len());
+    while order.len() < tags.len() {
+        let next = (0..tags.len()).find(|&i| {
+            !order.contains(&i) && (0..tags.len()).all(|j| order.contains(&j) || !needs(i, j))
+        });
+        let Some(next) = next else {
+            let stuck: Vec<String> = (0..tags.len())
+                .filter(|i| !order.contains(i))
+                .flat_map(|i| tags[i].iter().map(|u| u.to_string()))
+                .collect();
+            bail!(
+                "{} depend on each other; nothing can be published first",
+                stuck.join(", ")
+            );
+        };
+        order.push(next);
+    }
+    Ok(order)
+}
+
+/// How one job of [`run_in_order`] ended.
+#[derive(Debug)]
+pub enum Outcome<T> {
+    Done(T),
+    Failed(anyhow::Error),
+    /// Not run: the job at this index, which it needed, did not succeed.
+    Skipped(usize),
+}
+
+/// Run `job` for each index of `needs`, each only after the jobs it
+/// `needs` succeeded and at most `parallelism` at a time. Jobs start in
+/// the position they have in `order` (see [`publish_order`]). A failed
+/// job doesn't stop the others, but the jobs needing it are skipped.
+/// `progress` sees each outcome as it comes.
+pub async fn run_in_order<T, F, Fut>(
+    order: &[usize],
+    needs: &[Vec<usize>],
+    parallelism: usize,
+    job: F,
+    mut progress: impl FnMut(usize, &Outcome<T>),
+) -> Vec<Outcome<T>>
+where
+    F: Fn(usize) -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let mut outcomes: Vec<Option<Outcome<T>>> = needs.iter().map(|_| None).collect();
+    let mut started = vec![false; needs.len()];
+    let mut running = FuturesUnordered::new();
+    loop {
+        // Skipping one job may block another later in `order`, which
+        // depends on it; repeat until nothing changes.
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for &i in order {
+                if started[i] {
+                    continue;
+                }
+                let blocker = needs[i].iter().find(|&&j| {
+                    matches!(outcomes[j], Some(Outcome::Failed(_) | Outcome::Skipped(_)))
+                });
+                if let Some(&j) = blocker {
+                    started[i] = true;
+                    let outcome = Outcome::Skipped(j);
+                    progress(i, &outcome);
+                    outcomes[i] = Some(outcome);
+                    changed = true;
+                }
+            }
+        }
+        for &i in order {
+            if running.len() >= parallelism.max(1) {
+                break;
+            }
+            let ready = needs[i]
+                .iter()
+                .all(|&j| matches!(outcomes[j], Some(Outcome::Done(_))));
+            if !started[i] && ready {
+                started[i] = true;
+                let run = job(i);
+                running.push(async move { (i, run.await) });
+            }
+        }
+
+        let Some((i, result)) = running.next().await else {
+            break;
+        };
+        let outcome = match result {
+            Ok(value) => Outcome::Done(value),
+            Err(e) => Outcome::Failed(e),
+        };
+        progress(i, &outcome);
+        outcomes[i] = Some(outcome);
+    }
+    outcomes
+        .into_iter()
+        .map(|o| o.expect("BUG: every job runs or is skipped"))
+        .collect()
+}
+
+/// The current time, as the manifest records it.
+pub fn timestamp() -> String {
+    let now = deterministic::now_utc();
+    now.format(&time::format_description::well_known::Rfc3339)
+        .unwrap_or_else(|_| now.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn release(name: &str) -> ReleaseEntry {
+        ReleaseEntry::new(
+            name.into(),
+            "cargo".into(),
+            "1.0.0".into(),
+            "1.1.0".into(),
+            "minor".into(),
+            String::new(),
+            String::new(),
+        )
+    }
+
+    #[test]
+    fn artifacts_are_shared_or_per_unit() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("SHA256SUMS"), "sums\n").unwrap();
+        fs::create_dir_all(dir.path().join("api")).unwrap();
+        fs::write(dir.path().join("api/api.tar.gz"), "api").unwrap();
+        fs::create_dir_all(dir.path().join("web")).unwrap();
+        fs::write(dir.path().join("web/web.tar.gz"), "web").unwrap();
+
+        let (api, web) = (release("api"), release("web"));
+        let names = |members: &[&ReleaseEntry]| {
+            collect_artifacts(dir.path(), members)
+                .unwrap()
+                .into_iter()
+                .map(|(_, a)| a.name)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(&[&api]), ["SHA256SUMS", "api.tar.gz"]);
+        assert_eq!(
+            names(&[&api, &web]),
+            ["SHA256SUMS", "api.tar.gz", "web.tar.gz"]
+        );
+
+        fs::write(dir.path().join("web/SHA256SUMS"), "other\n").unwrap();
+        assert!(collect_artifacts(dir.path(), &[&web]).is_err());
+    }
+
+    #[test]
+    fn dependencies_are_published_first() {
+        let tags = vec![
+            vec!["cli"],
+            vec!["core", "core-wasm"],
+            vec!["docs"],
+            vec!["lib"],
+        ];
+        let depends_on = |unit: &str, dep: &str| {
+            matches!(
+                (unit, dep),
+                ("cli", "lib") | ("lib", "core") | ("docs", "cli")
+            )
+        };
+        assert_eq!(publish_order(&tags, depends_on).unwrap(), [1, 3, 0, 2]);
+        assert_eq!(publish_order(&tags, |_, _| false).unwrap(), [0, 1, 2, 3]);
+
+        let cycle = |unit: &str, dep: &str| matches!((unit, dep), ("cli", "lib") | ("lib", "cli"));
+        let err = publish_order(&tags, cycle).unwrap_err().to_string();
+        assert!(err.starts_with("cli, lib depend on each other"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn independent_jobs_run_side_by_side() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        // 0 and 1 are independent; 2 needs 1, which fails; 3 needs 2.
+        let needs = vec![vec![], vec![], vec![1], vec![2]];
+        let running = AtomicUsize::new(0);
+        let most = AtomicUsize::new(0);
+        let mut seen = Vec::new();
+        let outcomes = run_in_order(
+            &[0, 1, 2, 3],
+            &needs,
+            2,
+            |i| {
+                let (running, most) = (&running, &most);
+                async move {
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    most.fetch_max(now, Ordering::SeqCst);
+                    tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+                    running.fetch_sub(1, Ordering::SeqCst);
+                    if i == 1 {
+                        bail!("registry down");
+                    }
+                    Ok(i * 10)
+                }
+            },
+            |i, _| seen.push(i),
+        )
+        .await;
+
+        assert_eq!(most.load(Ordering::SeqCst), 2);
+        assert!(matches!(outcomes[0], Outcome::Done(0)));
+        assert!(matches!(&outcomes[1], Outcome::Failed(e) if e.to_string() == "registry down"));
+        assert!(matches!(outcomes[2], Outcome::Skipped(1)));
+        assert!(matches!(outcomes[3], Outcome::Skipped(2)));
+        seen.sort();
+        assert_eq!(seen, [0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn changed_assets_are_reported() {
+        let recorded = vec![
+            Artifact {
+                name: "a.tar.gz".into(),
+                size: 3,
+                sha256: "ab".repeat(32),
+            },
+            Artifact {
+                name: "b.tar.gz".into(),
+                size: 5,
+                sha256: "cd".repeat(32),
+            },
+        ];
+        let asset = |name: &str, size: u64, sha256: Option<String>| ReleaseAsset {
+            name: name.into(),
+            size,
+            sha256,
+        };
+        assert!(asset_problems(
+            &recorded,
+            &[
+                asset("a.tar.gz", 3, Some("ab".repeat(32))),
+                asset("b.tar.gz", 5, None),
+            ]
+        )
+        .is_empty());
+        assert_eq!(
+            asset_problems(
+                &recorded,
+                &[
+                    asset("a.tar.gz", 3, Some("ef".repeat(32))),
+                    asset("c.tar.gz", 1, None),
+                ]
+            ),
+            [
+                format!(
+                    "`a.tar.gz` has SHA-256 {}, {} when cut",
+                    "ef".repeat(32),
+                    "ab".repeat(32)
+                ),
+                "`b.tar.gz` is missing".to_string(),
+                "`c.tar.gz` was added after the cut".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn downloads_are_checked_against_the_recorded_artifacts() {
+        let artifact = |name: &str, sha256: String| Artifact {
+            name: name.into(),
+            size: 3,
+            sha256,
+        };
+        let recorded = vec![
+            artifact("a.tar.gz", "ab".repeat(32)),
+            artifact("b.tar.gz", "cd".repeat(32)),
+        ];
+        assert!(artifact_problems(&recorded, &[artifact("a.tar.gz", "ab".repeat(32))]).is_empty());
+        assert_eq!(
+            artifact_problems(
+                &recorded,
+                &[
+                    artifact("b.tar.gz", "ef".repeat(32)),
+                    artifact("c.tar.gz", "ab".repeat(32)),
+                ]
+            ),
+            [
+                format!(
+                    "`b.tar.gz` has SHA-256 {}, the release {}",
+                    "ef".repeat(32),
+                    "cd".repeat(32)
+                ),
+                "`c.tar.gz` is not an artifact of the release".to_string(),
+            ]
+        );
+    }
+}
@@ -0,0 +1,251 @@
+//! `[checks] publish_metadata` — registry metadata a release needs.
+//!
+//! crates.io refuses a crate without a license, npm happily publishes a
+//! package without `files` and ships the whole directory, and PyPI pages
+//! without classifiers are hard to find. These only surface after the
+//! release PR merged and the publish job ran. With the check enabled,
+//! `prepare` refuses to release a unit whose manifest lacks the fields
+//! below, and `belaf doctor` lists the gaps for every unit:
+//!
+//! - Cargo: `license` (or `license-file`), `description`, `repository`
+//! - npm: `license`, `main` (or `exports`), `files`
+//! - PyPI: `license` (or `license-files`), `classifiers`
+//!
+//! Units that aren't published — `publish = false` crates, `"private":
+//! true` packages — and other ecosystems are never checked. A field
+//! inherited from the workspace (`license.workspace = true`) or listed
+//! in PEP 621 `dynamic` counts as present.
+
+use anyhow::{Context, Result};
+
+use crate::core::{
+    git::repository::RepoPathBuf, release_unit::VersionSource,
+    resolved_release_unit::ReleaseUnitId, session::AppSession, wire::known::KnownEcosystem,
+};
+
+/// A field missing from a unit's manifest.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MetadataProblem {
+    pub unit: String,
+    /// Repo-relative path of the manifest.
+    pub manifest: String,
+    pub field: &'static str,
+    /// What to add, and why the registry cares.
+    pub hint: &'static str,
+}
+
+impl MetadataProblem {
+    /// One line for error messages, e.g. ``api: `Cargo.toml` has no
+    /// `license` — …``.
+    pub fn describe(&self) -> String {
+        format!(
+            "{}: `{}` has no `{}` — {}",
+            self.unit, self.manifest, self.field, self.hint
+        )
+    }
+}
+
+struct Missing {
+    field: &'static str,
+    hint: &'static str,
+}
+
+const CARGO_LICENSE: Missing = Missing {
+    field: "license",
+    hint: "add an SPDX `license` (or `license-file`) to [package]; crates.io rejects crates without one",
+};
+const CARGO_DESCRIPTION: Missing = Missing {
+    field: "description",
+    hint: "add a one-line `description` to [package]; crates.io rejects crates without one",
+};
+const CARGO_REPOSITORY: Missing = Missing {
+    field: "repository",
+    hint: "add the `repository` URL to [package] so crates.io links to the source",
+};
+const NPM_LICENSE: Missing = Missing {
+    field: "license",
+    hint: "add an SPDX `license`; npm shows unlicensed packages as proprietary",
+};
+const NPM_MAIN: Missing = Missing {
+    field: "main",
+    hint: "add `main` (or `exports`) pointing at the package entry point",
+};
+const NPM_FILES: Missing = Missing {
+    field: "files",
+    hint: "add a `files` allowlist; without one npm publishes the whole directory",
+};
+const PYPA_LICENSE: Missing = Missing {
+    field: "license",
+    hint: "add `license` (or `license-files`) to [project]",
+};
+const PYPA_CLASSIFIERS: Missing = Missing {
+    field: "classifiers",
+    hint: "add trove `classifiers` to [project] so PyPI can index the package",
+};
+
+/// The publish metadata `ident`'s manifest lacks. Empty for ecosystems
+/// without checks, units that aren't published and units whose manifest
+/// isn't where expected.
+pub fn check_unit(sess: &AppSession, ident: ReleaseUnitId) -> Result<Vec<MetadataProblem>> {
+    let unit = sess.graph().lookup(ident);
+    let Some(ecosystem) = unit
+        .qualified_names()
+        .get(1)
+        .and_then(|e| KnownEcosystem::from_wire(e))
+    else {
+        return Ok(Vec::new());
+    };
+    let check: fn(&str) -> Result<Vec<Missing>> = match ecosystem {
+        KnownEcosystem::Cargo => missing_cargo,
+        KnownEcosystem::Npm => missing_npm,
+        KnownEcosystem::Pypa => missing_pypa,
+        _ => return Ok(Vec::new()),
+    };
+
+    let file = ecosystem.version_file();
+    let manifest = sess
+        .resolved_release_units()
+        .iter()
+        .find(|r| r.unit.name == unit.user_facing_name)
+        .and_then(|r| match &r.unit.source {
+            VersionSource::Manifests(files) => files
+                .iter()
+                .map(|f| f.path.escaped())
+                .find(|p| p == file || p.ends_with(&format!("/{file}"))),
+            VersionSource::External(_) => None,
+        })
+        .unwrap_or_else(|| format!("{}{file}", unit.prefix().escaped()));
+
+    let path = RepoPathBuf::new(manifest.as_bytes());
+    let Ok(text) = std::fs::read_to_string(sess.repo.resolve_workdir(&path)) else {
+        return Ok(Vec::new());
+    };
+    let missing = check(&text).with_context(|| format!("failed to parse `{manifest}`"))?;
+    Ok(missing
+        .into_iter()
+        .map(|m| MetadataProblem {
+            unit: unit.user_facing_name.clone(),
+            manifest: manifest.clone(),
+            field: m.field,
+            hint: m.hint,
+        })
+        .collect())
+}
+
+fn missing_cargo(text: &str) -> Result<Vec<Missing>> {
+    let doc: toml::Table = toml::from_str(text)?;
+    let Some(package) = doc.get("package").and_then(|p| p.as_table()) else {
+        return Ok(Vec::new());
+    };
+    let unpublished = match package.get("publish") {
+        Some(toml::Value::Boolean(publish)) => !publish,
+        Some(toml::Value::Array(registries)) => registries.is_empty(),
+        _ => false,
+    };
+    if unpublished {
+        return Ok(Vec::new());
+    }
+
+    let has = |key: &str| package.contains_key(key);
+    let mut missing = Vec::new();
+    if !has("license") && !has("license-file") {
+        missing.push(CARGO_LICENSE);
+    }
+    if !has("description") {
+        missing.push(CARGO_DESCRIPTION);
+    }
+    if !has("repository") {
+        missing.push(CARGO_REPOSITORY);
+    }
+    Ok(missing)
+}
+
+fn missing_npm(text: &str) -> Result<Vec<Missing>> {
+    let package: serde_json::Value = serde_json::from_str(text)?;
+    if package["private"] == serde_json::Value::Bool(true) {
+        return Ok(Vec::new());
+    }
+
+    let has = |key: &str| !package[key].is_null();
+    let mut missing = Vec::new();
+    if !has("license") {
+        missing.push(NPM_LICENSE);
+    }
+    if !has("main") && !has("exports") {
+        missing.push(NPM_MAIN);
+    }
+    if !has("files") {
+        missing.push(NPM_FILES);
+    }
+    Ok(missing)
+}
+
+fn missing_pypa(text: &str) -> Result<Vec<Missing>> {
+    let doc: toml::Table = toml::from_str(text)?;
+    let Some(project) = doc.get("project").and_then(|p| p.as_table()) else {
+        return Ok(Vec::new());
+    };
+    let dynamic: Vec<&str> = project
+        .get("dynamic")
+        .and_then(|d| d.as_array())
+        .map(|d| d.iter().filter_map(|v| v.as_str()).collect())
+        .unwrap_or_default();
+
+    let has = |key: &str| project.contains_key(key) || dynamic.contains(&key);
+    let mut missing = Vec::new();
+    if !has("license") && !has("license-files") {
+        missing.push(PYPA_LICENSE);
+    }
+    if !has("classifiers") {
+        missing.push(PYPA_CLASSIFIERS);
+    }
+    Ok(missing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(missing: Result<Vec<Missing>>) -> Vec<&'static str> {
+        missing.unwrap().iter().map(|m| m.field).collect()
+    }
+
+    #[test]
+    fn crates_need_license_description_and_repository() {
+        let bare = "[package]\nname = \"api\"\nversion = \"1.0.0\"\n";
+        assert_eq!(
+            fields(missing_cargo(bare)),
+            ["license", "description", "repository"]
+        );
+
+        let complete = "[package]\nname = \"api\"\nlicense-file = \"LICENSE\"\n\
+                        description.workspace = true\nrepository = \"https://x\"\n";
+        assert!(fields(missing_cargo(complete)).is_empty());
+
+        let unpublished = "[package]\nname = \"api\"\npublish = false\n";
+        assert!(fields(missing_cargo(unpublished)).is_empty());
+        let workspace = "[workspace]\nmembers = [\"crates/*\"]\n";
+        assert!(fields(missing_cargo(workspace)).is_empty());
+    }
+
+    #[test]
+    fn npm_packages_need_license_entry_point_and_files() {
+        assert_eq!(
+            fields(missing_npm(r#"{"name": "web", "version": "1.0.0"}"#)),
+            ["license", "main", "files"]
+        );
+        let complete =
+            r#"{"name": "web", "license": "MIT", "exports": "./index.js", "files": ["dist"]}"#;
+        assert!(fields(missing_npm(complete)).is_empty());
+        assert!(fields(missing_npm(r#"{"name": "web", "private": true}"#)).is_empty());
+    }
+
+    #[test]
+    fn python_projects_need_license_and_classifiers() {
+        let bare = "[project]\nname = \"sdk\"\nversion = \"1.0.0\"\n";
+        assert_eq!(fields(missing_pypa(bare)), ["license", "classifiers"]);
+
+        let dynamic = "[project]\nname = \"sdk\"\nlicense = \"MIT\"\ndynamic = [\"classifiers\"]\n";
+        assert!(fields(missing_pypa(dynamic)).is_empty());
+    }
+}
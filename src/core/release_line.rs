@@ -0,0 +1,79 @@
+//! Maintenance branches: `belaf prepare --branch release/1.x`.
+//!
+//! A maintenance branch carries patch releases for an older line of
+//! versions while the default branch moves on. The line is read from the
+//! branch name — `release/1.x` is every `1.*` version, `release/1.4.x`
+//! every `1.4.*` — and, once selected, the repository only considers
+//! release tags on that line (see
+//! [`crate::core::git::repository::Repository::find_latest_tag_for_project`]),
+//! so history is analyzed from the last `1.x` release rather than the
+//! newest `2.x` one. Releases from the branch are patch releases that
+//! keep the unit's major and minor version.
+
+use std::fmt;
+
+/// The versions a maintenance branch releases.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReleaseLine {
+    pub major: u64,
+    /// Set for branches like `release/1.4.x`.
+    pub minor: Option<u64>,
+}
+
+impl ReleaseLine {
+    /// The line named by the last path segment of `branch`: `1.x`,
+    /// `1.4.x` or `v1.x`. `None` for other branch names.
+    pub fn from_branch(branch: &str) -> Option<Self> {
+        let segment = branch.rsplit('/').next()?;
+        let segment = segment.strip_prefix('v').unwrap_or(segment);
+        let parts: Vec<&str> = segment.split('.').collect();
+        let number = |s: &str| s.parse::<u64>().ok();
+        match parts.as_slice() {
+            [major, "x"] => Some(Self {
+                major: number(major)?,
+                minor: None,
+            }),
+            [major, minor, "x"] => Some(Self {
+                major: number(major)?,
+                minor: Some(number(minor)?),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Whether `version` is on this line.
+    pub fn contains(&self, version: &semver::Version) -> bool {
+        version.major == self.major && self.minor.is_none_or(|minor| version.minor == minor)
+    }
+}
+
+impl fmt::Display for ReleaseLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.minor {
+            Some(minor) => write!(f, "{}.{minor}.x", self.major),
+            None => write!(f, "{}.x", self.major),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lines_are_read_from_the_branch_name() {
+        let line = ReleaseLine::from_branch("release/1.x").unwrap();
+        assert_eq!(line.to_string(), "1.x");
+        assert!(line.contains(&semver::Version::parse("1.9.3").unwrap()));
+        assert!(!line.contains(&semver::Version::parse("2.0.0").unwrap()));
+
+        let line = ReleaseLine::from_branch("maint/v2.4.x").unwrap();
+        assert_eq!(line.to_string(), "2.4.x");
+        assert!(line.contains(&semver::Version::parse("2.4.1-rc.1").unwrap()));
+        assert!(!line.contains(&semver::Version::parse("2.5.0").unwrap()));
+
+        for branch in ["main", "release/next", "release/1.2", "release/x.x"] {
+            assert_eq!(ReleaseLine::from_branch(branch), None, "{branch}");
+        }
+    }
+}
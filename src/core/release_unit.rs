@@ -16,12 +16,14 @@
 use std::collections::HashMap;
 
 use crate::core::git::repository::RepoPathBuf;
+use crate::core::rewriters::dep_requirement::DepRequirementStrategy;
 use crate::core::wire::known::Ecosystem;
 
 pub mod bundle;
 pub mod cascade;
 pub mod detector;
 pub mod discovery;
+pub mod discovery_cache;
 pub mod resolver;
 pub mod shape;
 pub mod syntax;
@@ -57,6 +59,11 @@ pub struct ReleaseUnit {
     /// .tag_format` > ecosystem default.
     pub tag_format: Option<String>,
 
+    /// Former names of a renamed unit. Tag lookup also accepts tags
+    /// rendered with any of these as `{name}`, so release history
+    /// carries over the rename.
+    pub previous_names: Vec<String>,
+
     /// Visibility — whether this unit appears in TUI lists, the emitted
     /// manifest, and gets a git tag.
     pub visibility: Visibility,
@@ -64,6 +71,17 @@ pub struct ReleaseUnit {
     /// Optional cascade rule — bump this unit when a named upstream
     /// unit bumps. SDKs use this to follow a schema unit.
     pub cascade_from: Option<CascadeRule>,
+
+    /// How this unit's requirements on released internal dependencies
+    /// are rewritten. `None` defers to `[ecosystems.<name>]`.
+    pub dep_requirement: Option<DepRequirementStrategy>,
+
+    /// Conventional-commit scopes the unit's commits may use; see
+    /// [`crate::core::commit_scopes`]. Empty allows any.
+    pub scopes: Vec<String>,
+
+    /// Manual release steps; see [`crate::core::checklist`].
+    pub release_checklist: Vec<String>,
 }
 
 // ---------------------------------------------------------------------------
@@ -29,6 +29,16 @@ pub enum BumpKind {
 }
 
 impl BumpKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::NoBump => "no bump",
+            Self::Prerelease => "prerelease",
+            Self::Patch => "patch",
+            Self::Minor => "minor",
+            Self::Major => "major",
+        }
+    }
+
     fn rank(self) -> u8 {
         match self {
             Self::NoBump => 0,
@@ -50,7 +60,7 @@ impl BumpKind {
 
 /// Apply a [`CascadeBumpStrategy`] given the source unit's actual
 /// bump.
-fn cascaded(strategy: CascadeBumpStrategy, source: BumpKind) -> BumpKind {
+pub fn cascaded(strategy: CascadeBumpStrategy, source: BumpKind) -> BumpKind {
     if matches!(source, BumpKind::NoBump) {
         // Source didn't bump → cascade does nothing.
         return BumpKind::NoBump;
@@ -198,11 +208,15 @@ mod tests {
                 }]),
                 satellites: vec![],
                 tag_format: None,
+                previous_names: vec![],
                 visibility: Visibility::Public,
                 cascade_from: cascade.map(|(src, bump)| CascadeRule {
                     source: src.to_string(),
                     bump,
                 }),
+                dep_requirement: None,
+                scopes: vec![],
+                release_checklist: vec![],
             },
             origin: ResolveOrigin::Explicit { config_index: 0 },
         }
@@ -60,16 +60,16 @@ impl DetectionReport {
 
     /// Bundles + Hints (which decorate Standalones) count toward the
     /// release-unit candidate count; ExternallyManaged + bare-repo
-    /// hints (`SingleProject`, `NestedMonorepo`) do not.
+    /// hints (`SingleProject`, `NestedMonorepo`, `Submodule`) do not.
     pub fn count_release_unit_candidates(&self) -> usize {
         self.matches
             .iter()
             .filter(|m| match &m.shape {
                 DetectedShape::Bundle(_) => true,
                 DetectedShape::Hint(HintKind::SdkCascade | HintKind::NpmWorkspace) => true,
-                DetectedShape::Hint(HintKind::SingleProject { .. } | HintKind::NestedMonorepo) => {
-                    false
-                }
+                DetectedShape::Hint(
+                    HintKind::SingleProject { .. } | HintKind::NestedMonorepo | HintKind::Submodule,
+                ) => false,
                 DetectedShape::ExternallyManaged(_) => false,
             })
             .count()
@@ -140,6 +140,7 @@ fn drift_shape_label(s: &DetectedShape) -> String {
                 format!("single-project repo ({ecosystem})")
             }
             HintKind::NestedMonorepo => "nested submodule with its own monorepo".to_string(),
+            HintKind::Submodule => "submodule released from its own repository".to_string(),
         },
         DetectedShape::ExternallyManaged(e) => match e {
             ExtKind::MobileIos => "iOS app — recommend Bitrise/fastlane".to_string(),
@@ -252,14 +253,16 @@ pub fn detect_drift_from_report(
 }
 
 /// Whether a detector hit should ever surface as a drift error.
-/// Repo-shape Hints (`SingleProject`, `NestedMonorepo`) describe the
+/// Repo-shape Hints (`SingleProject`, `NestedMonorepo`, `Submodule`) describe the
 /// repo as a whole, not a missed bundle; they are wizard-only
 /// signals. Everything else (bundles, sdk-cascade hints, npm-workspace
 /// hints, externally-managed paths) does signal drift if uncovered.
 fn is_drift_signal(shape: &DetectedShape) -> bool {
     !matches!(
         shape,
-        DetectedShape::Hint(HintKind::SingleProject { .. } | HintKind::NestedMonorepo)
+        DetectedShape::Hint(
+            HintKind::SingleProject { .. } | HintKind::NestedMonorepo | HintKind::Submodule
+        )
     )
 }
 
@@ -286,6 +289,7 @@ pub fn detect_all(repo: &Repository) -> DetectionReport {
     matches.extend(scanners::sdk_cascade_members(&workdir));
     matches.extend(scanners::single_project_repo(&workdir));
     matches.extend(scanners::nested_monorepo(&workdir));
+    matches.extend(scanners::submodules(&workdir));
 
     DetectionReport { matches }
 }
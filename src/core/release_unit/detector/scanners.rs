@@ -233,11 +233,53 @@ fn has_nested_manifest(workdir: &Path) -> bool {
 // Nested monorepo — Hint
 
 pub(super) fn nested_monorepo(workdir: &Path) -> Vec<DetectorMatch> {
-    let gitmodules = workdir.join(".gitmodules");
-    if !gitmodules.is_file() {
-        return Vec::new();
+    let mut out = Vec::new();
+    for sub in submodule_checkouts(workdir) {
+        let has_belaf_config = sub.join("belaf").join("config.toml").is_file();
+        let manifest_count = count_manifests(&sub);
+
+        if has_belaf_config || manifest_count >= 2 {
+            if let Some(repopath) = relative_repopath(workdir, &sub) {
+                out.push(DetectorMatch {
+                    shape: DetectedShape::Hint(HintKind::NestedMonorepo),
+                    path: repopath,
+                    note: Some(if has_belaf_config {
+                        "submodule has its own belaf/config.toml".to_string()
+                    } else {
+                        "submodule holds multiple manifests".to_string()
+                    }),
+                });
+            }
+        }
     }
-    let content = match std::fs::read_to_string(&gitmodules) {
+    out
+}
+
+// Pinned submodule — Hint
+
+/// Every checked-out submodule that is not a nested monorepo: one
+/// component, released from its own repository.
+pub(super) fn submodules(workdir: &Path) -> Vec<DetectorMatch> {
+    let mut out = Vec::new();
+    for sub in submodule_checkouts(workdir) {
+        if sub.join("belaf").join("config.toml").is_file() || count_manifests(&sub) >= 2 {
+            continue;
+        }
+        if let Some(repopath) = relative_repopath(workdir, &sub) {
+            out.push(DetectorMatch {
+                shape: DetectedShape::Hint(HintKind::Submodule),
+                path: repopath,
+                note: None,
+            });
+        }
+    }
+    out
+}
+
+/// Directories of the submodules `.gitmodules` lists that are checked
+/// out.
+fn submodule_checkouts(workdir: &Path) -> Vec<PathBuf> {
+    let content = match std::fs::read_to_string(workdir.join(".gitmodules")) {
         Ok(c) => c,
         Err(_) => return Vec::new(),
     };
@@ -252,41 +294,29 @@ pub(super) fn nested_monorepo(workdir: &Path) -> Vec<DetectorMatch> {
                 continue;
             }
             let sub = workdir.join(value);
-            if !sub.is_dir() {
-                continue;
-            }
-            let has_belaf_config = sub.join("belaf").join("config.toml").is_file();
-            let manifest_count = [
-                "Cargo.toml",
-                "package.json",
-                "pyproject.toml",
-                "go.mod",
-                "pom.xml",
-                "Package.swift",
-                "mix.exs",
-            ]
-            .iter()
-            .filter(|f| sub.join(*f).is_file())
-            .count();
-
-            if has_belaf_config || manifest_count >= 2 {
-                if let Some(repopath) = relative_repopath(workdir, &sub) {
-                    out.push(DetectorMatch {
-                        shape: DetectedShape::Hint(HintKind::NestedMonorepo),
-                        path: repopath,
-                        note: Some(if has_belaf_config {
-                            "submodule has its own belaf/config.toml".to_string()
-                        } else {
-                            "submodule holds multiple manifests".to_string()
-                        }),
-                    });
-                }
+            if sub.is_dir() {
+                out.push(sub);
             }
         }
     }
     out
 }
 
+fn count_manifests(dir: &Path) -> usize {
+    [
+        "Cargo.toml",
+        "package.json",
+        "pyproject.toml",
+        "go.mod",
+        "pom.xml",
+        "Package.swift",
+        "mix.exs",
+    ]
+    .iter()
+    .filter(|f| dir.join(*f).is_file())
+    .count()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -490,5 +520,10 @@ mod tests {
         );
         let matches = nested_monorepo(t.path());
         assert!(matches.is_empty());
+
+        let matches = submodules(t.path());
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].shape, DetectedShape::Hint(HintKind::Submodule));
+        assert_eq!(matches[0].path.escaped(), "plain");
     }
 }
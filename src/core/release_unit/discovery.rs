@@ -10,6 +10,9 @@
 //! orchestrator (auto-discovered units) and the explicit
 //! `[release_unit.X]` resolver. The session then merges both into
 //! the graph.
+//!
+//! Per-manifest results are memoised across runs by
+//! [`super::discovery_cache`].
 
 use std::collections::HashSet;
 
@@ -19,11 +22,18 @@ use crate::core::{
     },
     errors::Result,
     git::repository::{RepoPathBuf, Repository},
+    scope,
 };
 
+use super::discovery_cache::DiscoveryCache;
+
 /// Walk the repo for every unconfigured manifest. `configured_skip_paths`
 /// is the union of every `[release_unit.X]` block's manifest-parent +
 /// satellites + `[ignore_paths]`.
+///
+/// Under `--scope` ([`Repository::scope`]) only manifests in the scoped
+/// directory or in a parent of it are read, and only units anchored
+/// inside the directory are returned.
 pub fn discover_implicit_release_units(
     repo: &Repository,
     handlers: &FormatHandlerRegistry,
@@ -35,13 +45,18 @@ pub fn discover_implicit_release_units(
     // one call (cargo metadata enumerates every workspace member from
     // one Cargo.toml).
     let mut paths: Vec<RepoPathBuf> = Vec::new();
+    let scope = repo.scope();
     repo.scan_paths(|p| {
+        if scope.is_some_and(|s| !scope::may_declare_members(s, p)) {
+            return Ok(());
+        }
         if !is_path_inside_any(p, configured_skip_paths) {
             paths.push(p.to_owned());
         }
         Ok(())
     })?;
 
+    let mut cache = DiscoveryCache::load(repo, handlers, discoverers);
     let mut units: Vec<DiscoveredUnit> = Vec::new();
     let mut consumed: HashSet<RepoPathBuf> = HashSet::new();
 
@@ -54,16 +69,13 @@ pub fn discover_implicit_release_units(
         if consumed.contains(path) {
             continue;
         }
-        for ws in discoverers.discoverers() {
-            if ws.claims(repo, path) {
-                let new_units = ws.discover(repo, path)?;
-                consumed.insert(path.clone());
-                for u in &new_units {
-                    consumed.insert(u.anchor_manifest.clone());
-                }
-                units.extend(new_units);
-                break;
+        if let Some(ws) = cache.workspace_root_of(repo, discoverers, path) {
+            let new_units = ws.discover(repo, path)?;
+            consumed.insert(path.clone());
+            for u in &new_units {
+                consumed.insert(u.anchor_manifest.clone());
             }
+            units.extend(new_units);
         }
     }
 
@@ -85,11 +97,16 @@ pub fn discover_implicit_release_units(
         if claimed_dirs.contains(&parent_buf) {
             continue;
         }
-        if let Some(unit) = handler.discover_single(repo, path)? {
+        if let Some(unit) = cache.discover_single(repo, handler, path)? {
             claimed_dirs.insert(parent_buf);
             units.push(unit);
         }
     }
 
+    cache.save(scope.is_none());
+
+    if let Some(scope) = scope {
+        units.retain(|u| scope::contains(scope, &u.anchor_manifest));
+    }
     Ok(units)
 }
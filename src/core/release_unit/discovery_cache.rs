@@ -0,0 +1,263 @@
+//! On-disk cache of manifest parses, so discovery on a large monorepo
+//! doesn't reread every unchanged `Cargo.toml` / `package.json`.
+//!
+//! The cache lives at `belaf/.cache/projects.bin`. Each entry is keyed
+//! by the manifest's repo path and records the git blob id of the
+//! content it was computed from, so editing a manifest invalidates its
+//! entry without any bookkeeping. Two facts are cached per manifest:
+//!
+//! - which [`WorkspaceDiscoverer`] claims it as a workspace root, and
+//! - what [`FormatHandler::discover_single`] returned for it, for the
+//!   handlers that offer a [`FormatHandler::single_unit_builder`].
+//!
+//! Workspace discovery itself always runs: `cargo metadata` and the
+//! npm/maven walkers read many files and resolve dependency
+//! requirements against history.
+//!
+//! The file is stamped with the belaf version and the registered
+//! handler names; a different binary discards it wholesale. A cache
+//! that can't be read or written is only logged — it never fails a run.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    io::Write,
+    path::PathBuf,
+};
+
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::core::{
+    ecosystem::format_handler::{
+        parse_version_string, DiscoveredUnit, FormatHandler, FormatHandlerRegistry,
+        WorkspaceDiscoverer, WorkspaceDiscovererRegistry,
+    },
+    errors::Result,
+    git::repository::{RepoPath, Repository},
+};
+
+/// Directory under `belaf/` holding local, never-committed state.
+pub const CACHE_DIR: &str = ".cache";
+
+const CACHE_FILE: &str = "projects.bin";
+
+/// Bump when the layout of [`CacheFile`] changes.
+const FORMAT: u32 = 1;
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct CacheFile {
+    format: u32,
+    stamp: String,
+    entries: HashMap<Vec<u8>, Entry>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+struct Entry {
+    blob: Vec<u8>,
+    /// `None` until the workspace discoverers have been asked; then the
+    /// name of the one that claimed the manifest, if any.
+    workspace_root_of: Option<Option<String>>,
+    /// `None` until `discover_single` has run; then its result.
+    single: Option<Option<CachedUnit>>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct CachedUnit {
+    name: String,
+    version: String,
+}
+
+pub struct DiscoveryCache {
+    path: Option<PathBuf>,
+    stamp: String,
+    entries: HashMap<Vec<u8>, Entry>,
+    /// Paths whose blob id has been checked against the files read
+    /// during this run.
+    checked: HashSet<Vec<u8>>,
+    dirty: bool,
+}
+
+impl DiscoveryCache {
+    /// Load the cache of `repo`. Repositories without a `belaf/`
+    /// directory get an in-memory cache that is never written, so
+    /// discovery before `belaf init` leaves the tree untouched; so do
+    /// repositories read as of a commit, which have no working tree to
+    /// keep it in.
+    pub fn load(
+        repo: &Repository,
+        handlers: &FormatHandlerRegistry,
+        discoverers: &WorkspaceDiscovererRegistry,
+    ) -> Self {
+        let config_dir = repo.resolve_config_dir();
+        let path = (repo.as_of().is_none() && config_dir.is_dir())
+            .then(|| config_dir.join(CACHE_DIR).join(CACHE_FILE));
+        let stamp = format!(
+            "{} {} {}",
+            env!("CARGO_PKG_VERSION"),
+            handlers.names().join(","),
+            discoverers
+                .discoverers()
+                .map(|d| d.name())
+                .collect::<Vec<_>>()
+                .join(","),
+        );
+
+        let entries = path
+            .as_ref()
+            .and_then(|p| fs::read(p).ok())
+            .and_then(|bytes| match postcard::from_bytes::<CacheFile>(&bytes) {
+                Ok(file) => Some(file),
+                Err(e) => {
+                    debug!("ignoring unreadable discovery cache: {e}");
+                    None
+                }
+            })
+            .filter(|file| file.format == FORMAT && file.stamp == stamp)
+            .map(|file| file.entries)
+            .unwrap_or_default();
+
+        DiscoveryCache {
+            path,
+            stamp,
+            entries,
+            checked: HashSet::new(),
+            dirty: false,
+        }
+    }
+
+    /// The entry for `path`, reset if the file changed since it was
+    /// cached. `None` if the file can't be hashed (e.g. it was deleted
+    /// from the working tree but is still in the index).
+    fn entry(&mut self, repo: &Repository, path: &RepoPath) -> Option<&mut Entry> {
+        let key = path.as_ref().to_vec();
+        if !self.checked.contains(&key) {
+            let blob = match repo.blob_id(path) {
+                Some(oid) => oid.as_bytes().to_vec(),
+                None => {
+                    self.entries.remove(&key);
+                    return None;
+                }
+            };
+            if self.entries.get(&key).is_none_or(|e| e.blob != blob) {
+                self.entries.insert(
+                    key.clone(),
+                    Entry {
+                        blob,
+                        ..Entry::default()
+                    },
+                );
+                self.dirty = true;
+            }
+            self.checked.insert(key.clone());
+        }
+        self.entries.get_mut(&key)
+    }
+
+    /// The workspace discoverer claiming `path` as a workspace root.
+    pub fn workspace_root_of<'a>(
+        &mut self,
+        repo: &Repository,
+        discoverers: &'a WorkspaceDiscovererRegistry,
+        path: &RepoPath,
+    ) -> Option<&'a dyn WorkspaceDiscoverer> {
+        let claim = || discoverers.discoverers().find(|ws| ws.claims(repo, path));
+        let Some(entry) = self.entry(repo, path) else {
+            return claim();
+        };
+        if let Some(name) = &entry.workspace_root_of {
+            let name = name.as_deref()?;
+            return discoverers.discoverers().find(|ws| ws.name() == name);
+        }
+
+        let found = claim();
+        entry.workspace_root_of = Some(found.map(|ws| ws.name().to_owned()));
+        self.dirty = true;
+        found
+    }
+
+    /// [`FormatHandler::discover_single`], answered from the cache when
+    /// the handler allows it and the manifest is unchanged.
+    pub fn discover_single(
+        &mut self,
+        repo: &Repository,
+        handler: &dyn FormatHandler,
+        path: &RepoPath,
+    ) -> Result<Option<DiscoveredUnit>> {
+        let Some(builder) = handler.single_unit_builder() else {
+            return handler.discover_single(repo, path);
+        };
+        let Some(entry) = self.entry(repo, path) else {
+            return handler.discover_single(repo, path);
+        };
+
+        match &entry.single {
+            Some(None) => return Ok(None),
+            Some(Some(cached)) => {
+                if let Ok(version) = parse_version_string(&cached.version, handler.name()) {
+                    return Ok(Some(builder(path, cached.name.clone(), version)));
+                }
+            }
+            None => {}
+        }
+
+        let unit = handler.discover_single(repo, path)?;
+        entry.single = Some(unit.as_ref().map(|u| CachedUnit {
+            name: u.qnames[0].clone(),
+            version: u.version.to_string(),
+        }));
+        self.dirty = true;
+        Ok(unit)
+    }
+
+    /// Write the cache back if anything changed. With `prune`, entries
+    /// for manifests not seen during this run are dropped; pass `false`
+    /// when only part of the repo was scanned (`--scope`).
+    pub fn save(mut self, prune: bool) {
+        let Some(path) = self.path.take() else {
+            return;
+        };
+        if prune {
+            let before = self.entries.len();
+            self.entries.retain(|k, _| self.checked.contains(k));
+            self.dirty |= self.entries.len() != before;
+        }
+        if !self.dirty {
+            return;
+        }
+
+        let file = CacheFile {
+            format: FORMAT,
+            stamp: self.stamp,
+            entries: self.entries,
+        };
+        if let Err(e) = write_cache(&path, &file) {
+            debug!(
+                "could not write discovery cache `{}`: {e:#}",
+                path.display()
+            );
+        }
+    }
+}
+
+fn write_cache(path: &std::path::Path, file: &CacheFile) -> Result<()> {
+    let bytes = postcard::to_stdvec(file)?;
+    let Some(dir) = path.parent() else {
+        return Ok(());
+    };
+    fs::create_dir_all(dir)?;
+
+    // Keep the directory out of `git status` (and so out of belaf's
+    // own dirty-tree check) even where `.gitignore` wasn't updated.
+    let ignore = dir.join(".gitignore");
+    if !ignore.exists() {
+        fs::write(&ignore, "*\n")?;
+    }
+
+    let af = atomicwrites::AtomicFile::new(path, atomicwrites::OverwriteBehavior::AllowOverwrite);
+    match af.write(|f| f.write_all(&bytes)) {
+        Err(atomicwrites::Error::Internal(e)) => Err(e.into()),
+        Err(atomicwrites::Error::User(e)) => Err(e.into()),
+        Ok(()) => Ok(()),
+    }
+}
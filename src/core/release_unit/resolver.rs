@@ -15,6 +15,7 @@ use std::path::Path;
 use crate::core::config::NamedReleaseUnitConfig;
 use crate::core::ecosystem::format_handler::DiscoveredUnit;
 use crate::core::git::repository::{RepoPathBuf, Repository};
+use crate::core::rewriters::dep_requirement::DepRequirementStrategy;
 use crate::core::wire::known::Ecosystem;
 
 use super::syntax::{CascadeRuleConfig, ManifestFileConfig, ManifestList, ReleaseUnitConfig};
@@ -43,9 +44,13 @@ pub struct PartialOverrideSpec {
     pub name: String,
     pub config_index: usize,
     pub tag_format: Option<String>,
+    pub previous_names: Vec<String>,
     pub visibility: Option<Visibility>,
     pub satellites: Vec<RepoPathBuf>,
     pub cascade_from: Option<CascadeRule>,
+    pub dep_requirement: Option<DepRequirementStrategy>,
+    pub scopes: Vec<String>,
+    pub release_checklist: Vec<String>,
 }
 
 /// Public API: resolve the parsed config into a list of
@@ -221,11 +226,16 @@ fn validate_partial_override(
         .iter()
         .map(|s| parse_repo_path(name, s))
         .collect::<Result<Vec<_>, _>>()?;
+    let dep_requirement = parse_dep_requirement(name, cfg.dep_requirement.as_deref())?;
 
     let has_any_override = cfg.tag_format.is_some()
+        || !cfg.previous_names.is_empty()
         || cfg.visibility.is_some()
         || !cfg.satellites.is_empty()
-        || cfg.cascade_from.is_some();
+        || cfg.cascade_from.is_some()
+        || dep_requirement.is_some()
+        || !cfg.scopes.is_empty()
+        || !cfg.release_checklist.is_empty();
     if !has_any_override {
         return Err(ResolverError::PartialOverrideEmpty {
             unit: name.to_string(),
@@ -236,12 +246,16 @@ fn validate_partial_override(
         name: name.to_string(),
         config_index,
         tag_format: cfg.tag_format.clone(),
+        previous_names: cfg.previous_names.clone(),
         // `visibility: Option` so default-vs-set is distinguishable.
         // `parse_visibility` returns `Visibility::default()` when raw is
         // None, which we map back to `None` here.
         visibility: cfg.visibility.as_deref().map(|_| visibility),
         satellites,
         cascade_from,
+        dep_requirement,
+        scopes: cfg.scopes.clone(),
+        release_checklist: cfg.release_checklist.clone(),
     })
 }
 
@@ -300,8 +314,12 @@ pub fn resolve_partial_against_discovered(
             source: VersionSource::Manifests(vec![manifest]),
             satellites: spec.satellites.clone(),
             tag_format: spec.tag_format.clone(),
+            previous_names: spec.previous_names.clone(),
             visibility: spec.visibility.unwrap_or_default(),
             cascade_from: spec.cascade_from.clone(),
+            dep_requirement: spec.dep_requirement,
+            scopes: spec.scopes.clone(),
+            release_checklist: spec.release_checklist.clone(),
         };
 
         out.push(ResolvedReleaseUnit {
@@ -410,6 +428,7 @@ fn convert_explicit(
         Some(c) => Some(parse_cascade_rule(name, c)?),
         None => None,
     };
+    let dep_requirement = parse_dep_requirement(name, cfg.dep_requirement.as_deref())?;
 
     Ok(ReleaseUnit {
         name: name.to_string(),
@@ -417,8 +436,12 @@ fn convert_explicit(
         source,
         satellites,
         tag_format: cfg.tag_format.clone(),
+        previous_names: cfg.previous_names.clone(),
         visibility,
         cascade_from,
+        dep_requirement,
+        scopes: cfg.scopes.clone(),
+        release_checklist: cfg.release_checklist.clone(),
     })
 }
 
@@ -445,6 +468,12 @@ fn expand_glob(
             config_key: config_key.to_string(),
         });
     }
+    // One list of former names cannot belong to every expanded unit.
+    if !cfg.previous_names.is_empty() {
+        return Err(ResolverError::GlobUnitHasPreviousNames {
+            config_key: config_key.to_string(),
+        });
+    }
     let templates: &Vec<String> = match &cfg.manifests {
         Some(ManifestList::Templates(t)) => t,
         Some(ManifestList::Explicit(_)) => {
@@ -470,45 +499,8 @@ fn expand_glob(
     // braces would silently miss).
     validate_template_vars_known(glob_idx, glob_pattern)?;
 
-    let workdir_repopath = RepoPathBuf::new(b"");
-    let workdir = repo
-        .resolve_workdir(&workdir_repopath)
-        .canonicalize()
-        .map_err(|e| ResolverError::InvalidPath {
-            unit: format!("[release_unit.{config_key}]"),
-            path: glob_pattern.clone(),
-            reason: format!("repo workdir canonicalize failed: {e}"),
-        })?;
-
-    let pattern_abs = workdir.join(glob_pattern);
-    let pattern_str = pattern_abs.to_string_lossy().to_string();
-
     let mut units = Vec::new();
-    let entries = match glob::glob(&pattern_str) {
-        Ok(e) => e,
-        Err(err) => {
-            return Err(ResolverError::InvalidPath {
-                unit: format!("[release_unit.{config_key}]"),
-                path: glob_pattern.clone(),
-                reason: format!("invalid glob pattern: {err}"),
-            });
-        }
-    };
-
-    for entry in entries.flatten() {
-        if !entry.is_dir() {
-            // Glob form expands to directories only.
-            continue;
-        }
-
-        let matched_repopath =
-            repo.convert_path(&entry)
-                .map_err(|e| ResolverError::InvalidPath {
-                    unit: format!("[release_unit.{config_key}]"),
-                    path: entry.display().to_string(),
-                    reason: format!("convert_path: {e}"),
-                })?;
-
+    for matched_repopath in glob_dirs(repo, config_key, glob_pattern)? {
         let ctx = TemplateCtx::from_matched_path(&matched_repopath);
 
         let unit_name = substitute(glob_idx, name_template, &ctx)?;
@@ -569,6 +561,7 @@ fn expand_glob(
             Some(c) => Some(parse_cascade_rule(&unit_name, c)?),
             None => None,
         };
+        let dep_requirement = parse_dep_requirement(&unit_name, cfg.dep_requirement.as_deref())?;
 
         let unit = ReleaseUnit {
             name: unit_name,
@@ -576,8 +569,12 @@ fn expand_glob(
             source: VersionSource::Manifests(manifests),
             satellites,
             tag_format: cfg.tag_format.clone(),
+            previous_names: Vec::new(),
             visibility,
             cascade_from,
+            dep_requirement,
+            scopes: cfg.scopes.clone(),
+            release_checklist: cfg.release_checklist.clone(),
         };
 
         units.push(ResolvedReleaseUnit {
@@ -678,6 +675,70 @@ fn validate_template_vars_known(glob_idx: usize, raw: &str) -> Result<(), Resolv
 // Manifest path resolution & ManifestFile construction
 // ===========================================================================
 
+/// The directories `glob_pattern` matches, in path order. Files don't
+/// count: the glob form expands to directories only.
+fn glob_dirs(
+    repo: &Repository,
+    config_key: &str,
+    glob_pattern: &str,
+) -> Result<Vec<RepoPathBuf>, ResolverError> {
+    let invalid = |reason: String| ResolverError::InvalidPath {
+        unit: format!("[release_unit.{config_key}]"),
+        path: glob_pattern.to_string(),
+        reason,
+    };
+
+    // Read as of a commit, the directories are those of its tree.
+    if repo.as_of().is_some() {
+        let pattern = glob::Pattern::new(glob_pattern)
+            .map_err(|err| invalid(format!("invalid glob pattern: {err}")))?;
+        let options = glob::MatchOptions {
+            require_literal_separator: true,
+            ..glob::MatchOptions::new()
+        };
+        let mut dirs = std::collections::BTreeSet::new();
+        repo.scan_paths(|p| {
+            let mut dir = p.split_basename().0.pop_sep();
+            while !dir.is_empty() {
+                if pattern.matches_path_with(dir.as_path(), options) {
+                    dirs.insert(dir.as_ref().to_vec());
+                }
+                dir = dir.split_basename().0.pop_sep();
+            }
+            Ok(())
+        })
+        .map_err(|e| invalid(format!("could not list the tree: {e}")))?;
+        return Ok(dirs.iter().map(|d| RepoPathBuf::new(d)).collect());
+    }
+
+    let workdir_repopath = RepoPathBuf::new(b"");
+    let workdir = repo
+        .resolve_workdir(&workdir_repopath)
+        .canonicalize()
+        .map_err(|e| invalid(format!("repo workdir canonicalize failed: {e}")))?;
+
+    let pattern_abs = workdir.join(glob_pattern);
+    let pattern_str = pattern_abs.to_string_lossy().to_string();
+    let entries =
+        glob::glob(&pattern_str).map_err(|err| invalid(format!("invalid glob pattern: {err}")))?;
+
+    let mut dirs = Vec::new();
+    for entry in entries.flatten() {
+        if !entry.is_dir() {
+            continue;
+        }
+        dirs.push(
+            repo.convert_path(&entry)
+                .map_err(|e| ResolverError::InvalidPath {
+                    unit: format!("[release_unit.{config_key}]"),
+                    path: entry.display().to_string(),
+                    reason: format!("convert_path: {e}"),
+                })?,
+        );
+    }
+    Ok(dirs)
+}
+
 fn pick_first_existing(
     unit_name: &str,
     primary: &[String],
@@ -689,8 +750,7 @@ fn pick_first_existing(
         for p in paths {
             tried.push(p.clone());
             let buf = RepoPathBuf::new(p.as_bytes());
-            let abs = repo.resolve_workdir(&buf);
-            if abs.exists() {
+            if repo.is_file(&buf) || repo.is_dir(&buf) {
                 return Ok(p.clone());
             }
         }
@@ -712,14 +772,11 @@ fn build_manifests(
     for m in cfg_manifests {
         let path = parse_repo_path(unit_name, &m.path)?;
 
-        if require_existence {
-            let abs = repo.resolve_workdir(&path);
-            if !abs.exists() {
-                return Err(ResolverError::PathDoesNotExist {
-                    unit: unit_name.to_string(),
-                    path: m.path.clone(),
-                });
-            }
+        if require_existence && !repo.is_file(&path) && !repo.is_dir(&path) {
+            return Err(ResolverError::PathDoesNotExist {
+                unit: unit_name.to_string(),
+                path: m.path.clone(),
+            });
         }
 
         let manifest_eco = match &m.ecosystem {
@@ -907,6 +964,21 @@ fn parse_visibility(unit_name: &str, raw: Option<&str>) -> Result<Visibility, Re
     }
 }
 
+fn parse_dep_requirement(
+    unit_name: &str,
+    raw: Option<&str>,
+) -> Result<Option<DepRequirementStrategy>, ResolverError> {
+    raw.map(|s| {
+        DepRequirementStrategy::from_wire(s).ok_or_else(|| ResolverError::UnknownEnumValue {
+            unit: unit_name.to_string(),
+            field: "dep_requirement",
+            value: s.to_string(),
+            allowed: DepRequirementStrategy::ALLOWED,
+        })
+    })
+    .transpose()
+}
+
 fn parse_cascade_rule(
     unit_name: &str,
     c: &CascadeRuleConfig,
@@ -1145,6 +1217,17 @@ mod tests {
         assert_eq!(err.rule(), "unknown_enum_value");
     }
 
+    #[test]
+    fn parse_dep_requirement_known_and_unknown() {
+        assert_eq!(parse_dep_requirement("x", None).unwrap(), None);
+        assert_eq!(
+            parse_dep_requirement("x", Some("minor-pinned")).unwrap(),
+            Some(DepRequirementStrategy::MinorPinned)
+        );
+        let err = parse_dep_requirement("x", Some("loose")).unwrap_err();
+        assert_eq!(err.rule(), "unknown_enum_value");
+    }
+
     #[test]
     fn parse_cascade_strategy_all_keys() {
         let cases = [
@@ -1196,8 +1279,12 @@ mod tests {
                 }]),
                 satellites: vec![],
                 tag_format: None,
+                previous_names: vec![],
                 visibility: Visibility::Public,
                 cascade_from: None,
+                dep_requirement: None,
+                scopes: vec![],
+                release_checklist: vec![],
             },
             origin: ResolveOrigin::Explicit { config_index: 0 },
         };
@@ -1222,8 +1309,12 @@ mod tests {
                 }]),
                 satellites: vec![],
                 tag_format: None,
+                previous_names: vec![],
                 visibility: Visibility::Public,
                 cascade_from: None,
+                dep_requirement: None,
+                scopes: vec![],
+                release_checklist: vec![],
             },
             origin: ResolveOrigin::Explicit { config_index: 0 },
         };
@@ -1245,8 +1336,12 @@ mod tests {
                 source: VersionSource::Manifests(vec![]),
                 satellites: vec![],
                 tag_format: None,
+                previous_names: vec![],
                 visibility: Visibility::Public,
                 cascade_from: None,
+                dep_requirement: None,
+                scopes: vec![],
+                release_checklist: vec![],
             },
             origin: ResolveOrigin::Explicit { config_index: 0 },
         };
@@ -1264,7 +1359,11 @@ mod tests {
                 source: VersionSource::Manifests(vec![]),
                 satellites: vec![],
                 tag_format: None,
+                previous_names: vec![],
                 visibility: Visibility::Public,
+                dep_requirement: None,
+                scopes: vec![],
+                release_checklist: vec![],
                 cascade_from: Some(CascadeRule {
                     source: "ghost-schema".into(),
                     bump: CascadeBumpStrategy::FloorMinor,
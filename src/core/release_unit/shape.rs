@@ -12,7 +12,7 @@
 //!   and hide their inner manifests in the wizard.
 //! - **Hint**: pure metadata that decorates a Standalone row. SDK
 //!   cascade members, npm workspace members, single-project root,
-//!   nested submodule. Hints are never togglable; they are annotations.
+//!   nested submodule, pinned submodule. Hints are never togglable; they are annotations.
 //! - **ExternallyManaged**: read-only paths that need
 //!   `[allow_uncovered]` instead of a `[release_unit.<name>]`. Mobile apps
 //!   primarily.
@@ -54,8 +54,13 @@ pub enum BundleKind {
 pub enum HintKind {
     SdkCascade,
     NpmWorkspace,
-    SingleProject { ecosystem: SingleProjectEcosystem },
+    SingleProject {
+        ecosystem: SingleProjectEcosystem,
+    },
     NestedMonorepo,
+    /// A submodule released from its own repository, which
+    /// `[submodule.<name>]` can move to its newest release.
+    Submodule,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
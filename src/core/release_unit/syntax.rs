@@ -26,9 +26,14 @@
 //!
 //! # Partial override — omit `ecosystem` / `manifests` to inherit them
 //! # from auto-detection. Only override fields are allowed in this form
-//! # (`tag_format`, `visibility`, `satellites`, `cascade_from`).
+//! # (`tag_format`, `previous_names`, `visibility`, `satellites`,
+//! # `cascade_from`, `dep_requirement`, `scopes`, `release_checklist`).
 //! [release_unit.discord-bot]
 //! tag_format = "v{version}"
+//!
+//! # Renamed from `chat-bot`: its `chat-bot-v*` tags still count.
+//! [release_unit.support-bot]
+//! previous_names = ["chat-bot"]
 //! ```
 
 use std::collections::HashMap;
@@ -85,6 +90,13 @@ pub struct EcosystemCargoConfig {
     /// `cargo.rs::is_workspace_project`.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub workspace_mode: Option<String>,
+
+    /// `exact` | `caret` | `tilde` | `minor-pinned` | `keep`. How
+    /// dependents' requirements on released crates are rewritten; see
+    /// [`crate::core::rewriters::dep_requirement`]. Unset behaves like
+    /// `keep`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dep_requirement: Option<String>,
 }
 
 /// `[ecosystems.npm]` — npm-specific knobs.
@@ -94,6 +106,19 @@ pub struct EcosystemNpmConfig {
     /// suggests a `[group.<id>]` over its members.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub sync_workspaces: Option<String>,
+
+    /// Same as `[ecosystems.cargo] dep_requirement`, for packages.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dep_requirement: Option<String>,
+}
+
+/// `[ecosystems.pypa]` — Python packaging knobs.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct EcosystemPypaConfig {
+    /// Same as `[ecosystems.cargo] dep_requirement`, for
+    /// `[project] dependencies` entries.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dep_requirement: Option<String>,
 }
 
 /// `[ecosystems.tauri]` — Tauri detector knobs.
@@ -136,6 +161,9 @@ pub struct EcosystemsConfig {
     #[serde(default, skip_serializing_if = "is_default_npm")]
     pub npm: EcosystemNpmConfig,
 
+    #[serde(default, skip_serializing_if = "is_default_pypa")]
+    pub pypa: EcosystemPypaConfig,
+
     #[serde(default, skip_serializing_if = "is_default_tauri")]
     pub tauri: EcosystemTauriConfig,
 
@@ -148,10 +176,13 @@ pub struct EcosystemsConfig {
 }
 
 fn is_default_cargo(c: &EcosystemCargoConfig) -> bool {
-    c.hexagonal_pattern.is_none() && c.workspace_mode.is_none()
+    c.hexagonal_pattern.is_none() && c.workspace_mode.is_none() && c.dep_requirement.is_none()
 }
 fn is_default_npm(c: &EcosystemNpmConfig) -> bool {
-    c.sync_workspaces.is_none()
+    c.sync_workspaces.is_none() && c.dep_requirement.is_none()
+}
+fn is_default_pypa(c: &EcosystemPypaConfig) -> bool {
+    c.dep_requirement.is_none()
 }
 fn is_default_tauri(c: &EcosystemTauriConfig) -> bool {
     c.detect_triplet && c.prefer_single_source
@@ -164,6 +195,7 @@ impl EcosystemsConfig {
     pub fn is_empty(&self) -> bool {
         is_default_cargo(&self.cargo)
             && is_default_npm(&self.npm)
+            && is_default_pypa(&self.pypa)
             && is_default_tauri(&self.tauri)
             && is_default_jvm_library(&self.jvm_library)
     }
@@ -192,7 +224,8 @@ pub struct ReleaseUnitConfig {
     /// when omitted, the entry is a **partial override** that inherits
     /// ecosystem + manifests from the auto-detected unit with the same
     /// name. In that mode only override fields (`tag_format`,
-    /// `visibility`, `satellites`, `cascade_from`) may be set.
+    /// `previous_names`, `visibility`, `satellites`, `cascade_from`,
+    /// `dep_requirement`, `scopes`, `release_checklist`) may be set.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub ecosystem: Option<String>,
 
@@ -244,6 +277,11 @@ pub struct ReleaseUnitConfig {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub tag_format: Option<String>,
 
+    /// Names the unit was released under before a rename. Tags written
+    /// with an old `{name}` still count as this unit's releases.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub previous_names: Vec<String>,
+
     /// `public` (default) | `internal` | `hidden`.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub visibility: Option<String>,
@@ -251,6 +289,21 @@ pub struct ReleaseUnitConfig {
     /// Optional cascade rule.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub cascade_from: Option<CascadeRuleConfig>,
+
+    /// How this unit's requirements on released internal dependencies
+    /// are rewritten. Overrides `[ecosystems.<name>] dep_requirement`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dep_requirement: Option<String>,
+
+    /// Conventional-commit scopes this unit's commits may use. Empty
+    /// allows any scope.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scopes: Vec<String>,
+
+    /// Manual steps of a release, ticked off on the release PR before
+    /// `belaf tag` tags it. See [`crate::core::checklist`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub release_checklist: Vec<String>,
 }
 
 impl ReleaseUnitConfig {
@@ -194,6 +194,11 @@ pub enum ResolverError {
     #[error("release_unit `{config_key}`: glob-form entries must set `name = \"{{basename}}\"` (or similar) so each match gets a distinct unit name.")]
     GlobUnitMissingNameTemplate { config_key: String },
 
+    /// Glob-form entry with `previous_names` set. A rename applies to
+    /// one unit, not to every match of the glob.
+    #[error("release_unit `{config_key}`: glob-form entries cannot set `previous_names` — a rename belongs to one unit. Add a `[release_unit.<name>]` override block for the renamed unit instead.")]
+    GlobUnitHasPreviousNames { config_key: String },
+
     /// Partial-override block (no `ecosystem` field) for a name that
     /// auto-detection did not find. Most likely a typo on the TOML key
     /// or the block was meant to be a full explicit entry — in which
@@ -212,7 +217,7 @@ pub enum ResolverError {
 
     /// Partial-override block has no override fields set at all.
     #[error(
-        "release_unit `{unit}`: partial-override entries must set at least one override field (`tag_format`, `visibility`, `satellites`, `cascade_from`). An empty block has no effect."
+        "release_unit `{unit}`: partial-override entries must set at least one override field (`tag_format`, `previous_names`, `visibility`, `satellites`, `cascade_from`, `dep_requirement`, `scopes`, `release_checklist`). An empty block has no effect."
     )]
     PartialOverrideEmpty { unit: String },
 }
@@ -257,6 +262,7 @@ impl ResolverError {
             Self::GlobUnitHasExternal { .. } => "glob_unit_has_external",
             Self::GlobUnitHasExplicitManifests { .. } => "glob_unit_has_explicit_manifests",
             Self::GlobUnitMissingNameTemplate { .. } => "glob_unit_missing_name_template",
+            Self::GlobUnitHasPreviousNames { .. } => "glob_unit_has_previous_names",
         }
     }
 }
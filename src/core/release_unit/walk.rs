@@ -69,6 +69,11 @@ pub(in crate::core::release_unit) fn walk_capped<F: FnMut(&Path)>(
                         continue;
                     }
                 }
+                // A submodule (or any nested clone) is another
+                // repository, released from there.
+                if path.join(".git").exists() {
+                    continue;
+                }
                 rec(&path, depth_left - 1, f);
             }
         }
@@ -0,0 +1,272 @@
+//! Release manifests on disk, across schema versions.
+//!
+//! Every manifest in `belaf/releases/` records the `schema_version` it
+//! was written with (see [`crate::core::manifest`]). A release PR can sit
+//! open across a belaf upgrade, so files written by an older belaf are
+//! upgraded when they are read: the JSON is passed through each
+//! [`Migration`] from its version up to [`SCHEMA_VERSION`] before it is
+//! parsed. `belaf migrate` writes the upgraded files back.
+//!
+//! A breaking schema change bumps [`SCHEMA_VERSION`] and appends one
+//! migration from the previous version to [`MIGRATIONS`]. Files from a
+//! newer belaf are rejected rather than guessed at.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde_json::{Map, Value};
+
+use crate::core::{
+    git::repository::{RepoPath, RepoPathBuf, Repository},
+    manifest::{ManifestParseError, ReleaseManifest, MANIFEST_DIR, SCHEMA_VERSION},
+};
+
+/// The version of manifests written before `schema_version` existed.
+pub const UNVERSIONED: &str = "0";
+
+/// One step up the schema chain.
+pub struct Migration {
+    pub from: &'static str,
+    pub to: &'static str,
+    apply: fn(&mut Map<String, Value>),
+}
+
+/// Every migration, oldest first; each one's `to` is the next one's
+/// `from`, and the last one's is [`SCHEMA_VERSION`].
+pub const MIGRATIONS: &[Migration] = &[Migration {
+    from: UNVERSIONED,
+    to: "1",
+    apply: unversioned_to_v1,
+}];
+
+#[derive(Debug, thiserror::Error)]
+pub enum MigrationError {
+    #[error("failed to parse manifest JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("a release manifest must be a JSON object")]
+    NotAnObject,
+    #[error(
+        "manifest schema version {0} was written by a newer belaf \
+         (this one reads up to {SCHEMA_VERSION}); upgrade belaf"
+    )]
+    TooNew(String),
+    #[error("unknown manifest schema version `{0}`")]
+    Unknown(String),
+    #[error(transparent)]
+    Parse(#[from] ManifestParseError),
+}
+
+/// The version a manifest document was written with.
+pub fn schema_version_of(doc: &Map<String, Value>) -> &str {
+    doc.get("schema_version")
+        .and_then(Value::as_str)
+        .unwrap_or(UNVERSIONED)
+}
+
+/// Upgrade `doc` in place to [`SCHEMA_VERSION`]. Returns the version it
+/// was written with.
+pub fn upgrade(doc: &mut Value) -> Result<String, MigrationError> {
+    let doc = doc.as_object_mut().ok_or(MigrationError::NotAnObject)?;
+    let original = schema_version_of(doc).to_string();
+    let mut version = original.clone();
+    while version != SCHEMA_VERSION {
+        let Some(step) = MIGRATIONS.iter().find(|m| m.from == version) else {
+            let newer = matches!(
+                (version.parse::<u64>(), SCHEMA_VERSION.parse::<u64>()),
+                (Ok(v), Ok(current)) if v > current
+            );
+            return Err(if newer {
+                MigrationError::TooNew(version)
+            } else {
+                MigrationError::Unknown(version)
+            });
+        };
+        (step.apply)(doc);
+        doc.insert("schema_version".into(), step.to.into());
+        version = step.to.to_string();
+    }
+    Ok(original)
+}
+
+/// Parse a manifest of any supported version.
+pub fn parse(json: &str) -> Result<ReleaseManifest, MigrationError> {
+    let mut doc: Value = serde_json::from_str(json)?;
+    upgrade(&mut doc)?;
+    Ok(ReleaseManifest::from_value(doc)?)
+}
+
+/// A manifest read from the repository.
+#[derive(Debug)]
+pub struct StoredManifest {
+    pub path: PathBuf,
+    pub manifest: ReleaseManifest,
+    /// The version in the file, when older than [`SCHEMA_VERSION`].
+    pub migrated_from: Option<String>,
+}
+
+/// Read and upgrade the manifest at `path`.
+pub fn read(path: &Path) -> Result<StoredManifest> {
+    let json =
+        fs::read_to_string(path).with_context(|| format!("failed to read `{}`", path.display()))?;
+    from_json(path, &json)
+}
+
+/// Read and upgrade the manifest at `path` of `repo`, as
+/// [`Repository::open_file`] reads it.
+pub fn read_in(repo: &Repository, path: &RepoPath) -> Result<StoredManifest> {
+    let abs = repo.resolve_workdir(path);
+    let json = repo
+        .read_to_string(path)
+        .with_context(|| format!("failed to read `{}`", abs.display()))?;
+    from_json(&abs, &json)
+}
+
+fn from_json(path: &Path, json: &str) -> Result<StoredManifest> {
+    let mut doc: Value = serde_json::from_str(json)
+        .with_context(|| format!("failed to parse `{}`", path.display()))?;
+    let version = upgrade(&mut doc).with_context(|| format!("cannot read `{}`", path.display()))?;
+    let manifest = ReleaseManifest::from_value(doc)
+        .with_context(|| format!("failed to parse `{}`", path.display()))?;
+    Ok(StoredManifest {
+        path: path.to_owned(),
+        manifest,
+        migrated_from: (version != SCHEMA_VERSION).then_some(version),
+    })
+}
+
+/// The manifests in `belaf/releases/`, by file name, to be read with
+/// [`read_in`].
+pub fn list(repo: &Repository) -> Result<Vec<RepoPathBuf>> {
+    let dir = RepoPathBuf::new(MANIFEST_DIR.as_bytes());
+    if !repo.is_dir(&dir) {
+        return Ok(Vec::new());
+    }
+    let mut paths: Vec<RepoPathBuf> = repo
+        .list_dir(&dir)
+        .with_context(|| format!("failed to list `{MANIFEST_DIR}/`"))?
+        .into_iter()
+        .filter(|path| path.ends_with(".json"))
+        .collect();
+    paths.sort_by(|a, b| AsRef::<[u8]>::as_ref(a).cmp(b.as_ref()));
+    Ok(paths)
+}
+
+/// Resolve `release_id`, a manifest id or a unique prefix of it, to a
+/// manifest in the working tree: the absolute path and the repo-relative
+/// one for display.
+pub fn find(repo: &Repository, release_id: &str) -> Result<(PathBuf, String)> {
+    let dir = repo.resolve_workdir(&RepoPathBuf::new(MANIFEST_DIR.as_bytes()));
+    let id = release_id.trim_end_matches(".json");
+
+    let mut matches: Vec<String> = fs::read_dir(&dir)
+        .with_context(|| format!("no release manifests found in `{MANIFEST_DIR}/`"))?
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter(|name| name.ends_with(".json") && name.starts_with(id))
+        .collect();
+    matches.sort();
+
+    match matches.as_slice() {
+        [name] => Ok((dir.join(name), format!("{MANIFEST_DIR}/{name}"))),
+        [] => anyhow::bail!("no release `{release_id}` in `{MANIFEST_DIR}/`"),
+        _ => anyhow::bail!(
+            "release id `{release_id}` is ambiguous: {}",
+            matches.join(", ")
+        ),
+    }
+}
+
+/// Manifests from before `schema_version`: each release carried the
+/// unit's tag `prefix` instead of its tag names, and `prerelease`
+/// instead of `is_prerelease`.
+fn unversioned_to_v1(doc: &mut Map<String, Value>) {
+    let Some(releases) = doc.get_mut("releases").and_then(Value::as_array_mut) else {
+        return;
+    };
+    for release in releases.iter_mut().filter_map(Value::as_object_mut) {
+        let prefix = match release.remove("prefix") {
+            Some(Value::String(p)) if !p.trim_end_matches('/').is_empty() => {
+                format!("{}/", p.trim_end_matches('/'))
+            }
+            _ => String::new(),
+        };
+        let version_field = |release: &Map<String, Value>, key: &str| {
+            release
+                .get(key)
+                .and_then(Value::as_str)
+                .filter(|v| !v.is_empty())
+                .map(|v| format!("{prefix}v{v}"))
+        };
+        if !release.contains_key("tag_name") {
+            if let Some(tag) = version_field(release, "new_version") {
+                release.insert("tag_name".into(), tag.into());
+            }
+        }
+        if !release.contains_key("previous_tag") {
+            if let Some(tag) = version_field(release, "previous_version") {
+                release.insert("previous_tag".into(), tag.into());
+            }
+        }
+        if let Some(prerelease) = release.remove("prerelease") {
+            release.entry("is_prerelease").or_insert(prerelease);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UNVERSIONED_MANIFEST: &str = r#"{
+        "manifest_id": "0192f3a1-7c4e-7b2a-9f00-1234567890ab",
+        "created_at": "2025-06-01T10:00:00Z",
+        "created_by": "alice",
+        "base_branch": "main",
+        "releases": [{
+            "name": "core",
+            "ecosystem": "cargo",
+            "previous_version": "1.2.0",
+            "new_version": "1.3.0-rc.1",
+            "bump_type": "minor",
+            "prefix": "crates/core",
+            "prerelease": true
+        }]
+    }"#;
+
+    #[test]
+    fn unversioned_manifests_are_upgraded() {
+        let mut doc: Value = serde_json::from_str(UNVERSIONED_MANIFEST).unwrap();
+        assert_eq!(upgrade(&mut doc).unwrap(), UNVERSIONED);
+        assert_eq!(doc["schema_version"], SCHEMA_VERSION);
+
+        let manifest = parse(UNVERSIONED_MANIFEST).unwrap();
+        let release = &manifest.releases[0];
+        assert_eq!(release.tag_name, "crates/core/v1.3.0-rc.1");
+        assert_eq!(release.previous_tag.as_deref(), Some("crates/core/v1.2.0"));
+        assert!(release.is_prerelease);
+    }
+
+    #[test]
+    fn current_manifests_are_left_alone_and_newer_ones_rejected() {
+        let json = ReleaseManifest::new("main".into(), "alice".into())
+            .to_json()
+            .unwrap();
+        let mut doc: Value = serde_json::from_str(&json).unwrap();
+        let before = doc.clone();
+        assert_eq!(upgrade(&mut doc).unwrap(), SCHEMA_VERSION);
+        assert_eq!(doc, before);
+
+        doc["schema_version"] = "2".into();
+        assert!(matches!(upgrade(&mut doc), Err(MigrationError::TooNew(v)) if v == "2"));
+        doc["schema_version"] = "3.0".into();
+        assert!(matches!(upgrade(&mut doc), Err(MigrationError::Unknown(_))));
+    }
+
+    #[test]
+    fn the_chain_ends_at_the_current_version() {
+        for pair in MIGRATIONS.windows(2) {
+            assert_eq!(pair[0].to, pair[1].from);
+        }
+        assert_eq!(MIGRATIONS.last().unwrap().to, SCHEMA_VERSION);
+    }
+}
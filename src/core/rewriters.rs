@@ -3,8 +3,10 @@
 
 //! Updating project versioning metadata in the repository.
 
+pub mod dep_requirement;
 pub mod external;
 pub mod multi_manifest;
+pub mod submodule_pin;
 
 use crate::core::{errors::Result, git::repository::ChangeList, session::AppSession};
 
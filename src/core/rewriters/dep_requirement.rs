@@ -0,0 +1,223 @@
+//! How internal dependency requirements are rewritten on release.
+//!
+//! Without a strategy a dependent's requirement on an internal dependency
+//! is only rewritten when belaf metadata asks for it (a `manual:` or a
+//! resolved commit requirement); otherwise the manifest keeps whatever it
+//! says. A [`DepRequirementStrategy`] set under `[ecosystems.<name>]
+//! dep_requirement`, or under `[release_unit.<name>] dep_requirement`
+//! (which wins for that unit), instead rewrites the dependent's
+//! requirement on every unit the release bumps to one derived from the
+//! new version. That includes `manual:` requirements, which `belaf init`
+//! records for every internal dependency; a unit whose requirements are
+//! pinned by hand opts out with `keep`.
+
+use crate::core::{
+    resolved_release_unit::{DepRequirement, ReleaseUnitId},
+    session::AppSession,
+    version::Version,
+};
+
+/// `dep_requirement` values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DepRequirementStrategy {
+    /// Exactly the new version: `=1.2.3`.
+    Exact,
+    /// Compatible with the new major.minor: `^1.2`.
+    Caret,
+    /// The new version up to the next minor: `~1.2.3`.
+    Tilde,
+    /// Any patch of the new major.minor: `1.2.*`.
+    MinorPinned,
+    /// Leave the requirement as written.
+    Keep,
+}
+
+impl DepRequirementStrategy {
+    /// Accepted config values, for error messages.
+    pub const ALLOWED: &'static str = "exact, caret, tilde, minor-pinned, keep";
+
+    /// Stable config key.
+    pub fn wire_key(&self) -> &'static str {
+        match self {
+            Self::Exact => "exact",
+            Self::Caret => "caret",
+            Self::Tilde => "tilde",
+            Self::MinorPinned => "minor-pinned",
+            Self::Keep => "keep",
+        }
+    }
+
+    /// Parse the config value. None for unknown strings.
+    pub fn from_wire(s: &str) -> Option<Self> {
+        match s {
+            "exact" => Some(Self::Exact),
+            "caret" => Some(Self::Caret),
+            "tilde" => Some(Self::Tilde),
+            "minor-pinned" => Some(Self::MinorPinned),
+            "keep" => Some(Self::Keep),
+            _ => None,
+        }
+    }
+
+    /// The requirement on `package` at `version`, in the syntax of
+    /// `ecosystem`'s manifests. `None` for `keep`, and for ecosystems
+    /// whose rewriters don't write dependency requirements. Python
+    /// requirements name the package, since the rewriter replaces the
+    /// whole requirement string.
+    pub fn render(&self, ecosystem: &str, package: &str, version: &Version) -> Option<String> {
+        let (major, minor) = major_minor(version);
+        let spec = match (ecosystem, self) {
+            (_, Self::Keep) => return None,
+            ("cargo", Self::Exact) => format!("={version}"),
+            ("cargo", Self::Caret) => format!("^{major}.{minor}"),
+            ("cargo", Self::Tilde) => format!("~{version}"),
+            ("cargo", Self::MinorPinned) => format!("{major}.{minor}.*"),
+            ("npm", Self::Exact) => version.to_string(),
+            ("npm", Self::Caret) => format!("^{major}.{minor}"),
+            ("npm", Self::Tilde) => format!("~{version}"),
+            ("npm", Self::MinorPinned) => format!("{major}.{minor}.x"),
+            // PEP 440 has no caret; spell out the same range.
+            ("pypa", Self::Exact) => format!("{package}=={version}"),
+            ("pypa", Self::Caret) if major == 0 => {
+                format!("{package}>={major}.{minor},<0.{}", minor + 1)
+            }
+            ("pypa", Self::Caret) => format!("{package}>={major}.{minor},<{}", major + 1),
+            ("pypa", Self::Tilde) => format!("{package}~={version}"),
+            ("pypa", Self::MinorPinned) => format!("{package}=={major}.{minor}.*"),
+            _ => return None,
+        };
+        Some(spec)
+    }
+}
+
+fn major_minor(version: &Version) -> (u64, u64) {
+    match version {
+        Version::Semver(v) => (v.major, v.minor),
+        Version::Pep440(v) => {
+            let segment = |i: usize| v.segments.get(i).copied().unwrap_or(0) as u64;
+            (segment(0), segment(1))
+        }
+        Version::DotNet(v) => (v.major.max(0) as u64, v.minor.max(0) as u64),
+    }
+}
+
+/// Rewrite each dependent's requirement on the units in `released`
+/// according to its strategy. Runs once the release's versions are
+/// bumped and before the rewriters, which then write the new
+/// requirements like any `manual:` one.
+pub fn apply_strategies(sess: &mut AppSession, released: &[ReleaseUnitId]) {
+    let mut updates = Vec::new();
+    for unit in sess.graph().projects_slice() {
+        let Some(strategy) = sess.dep_requirement_strategy(unit.ident()) else {
+            continue;
+        };
+        let ecosystem = unit.qualified_names().get(1).map_or("", String::as_str);
+        for (idx, dep) in unit.internal_deps.iter().enumerate() {
+            if !released.contains(&dep.ident) {
+                continue;
+            }
+            let dependee = sess.graph().lookup(dep.ident);
+            let Some(mut text) =
+                strategy.render(ecosystem, &dependee.qualified_names()[0], &dependee.version)
+            else {
+                continue;
+            };
+            if let ("npm", Some(protocol)) = (ecosystem, &sess.npm_config.internal_dep_protocol) {
+                text = format!("{protocol}:{text}");
+            }
+            updates.push((unit.ident(), idx, text));
+        }
+    }
+
+    for (ident, idx, text) in updates {
+        let unit = sess.graph_mut().lookup_mut(ident);
+        unit.internal_deps[idx].belaf_requirement = DepRequirement::Manual(text);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn semver(v: &str) -> Version {
+        Version::Semver(v.parse().unwrap())
+    }
+
+    fn render_all(ecosystem: &str, version: &Version) -> Vec<Option<String>> {
+        [
+            DepRequirementStrategy::Exact,
+            DepRequirementStrategy::Caret,
+            DepRequirementStrategy::Tilde,
+            DepRequirementStrategy::MinorPinned,
+            DepRequirementStrategy::Keep,
+        ]
+        .iter()
+        .map(|s| s.render(ecosystem, "core", version))
+        .collect()
+    }
+
+    #[test]
+    fn cargo_requirements() {
+        assert_eq!(
+            render_all("cargo", &semver("1.2.3")),
+            vec![
+                Some("=1.2.3".into()),
+                Some("^1.2".into()),
+                Some("~1.2.3".into()),
+                Some("1.2.*".into()),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn npm_requirements() {
+        assert_eq!(
+            render_all("npm", &semver("0.4.1")),
+            vec![
+                Some("0.4.1".into()),
+                Some("^0.4".into()),
+                Some("~0.4.1".into()),
+                Some("0.4.x".into()),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn pypa_requirements_name_the_package() {
+        let version = Version::Pep440("2.1.0".parse().unwrap());
+        assert_eq!(
+            render_all("pypa", &version),
+            vec![
+                Some("core==2.1.0".into()),
+                Some("core>=2.1,<3".into()),
+                Some("core~=2.1.0".into()),
+                Some("core==2.1.*".into()),
+                None,
+            ]
+        );
+        let pre_one = Version::Pep440("0.3.2".parse().unwrap());
+        assert_eq!(
+            DepRequirementStrategy::Caret.render("pypa", "core", &pre_one),
+            Some("core>=0.3,<0.4".into())
+        );
+    }
+
+    #[test]
+    fn other_ecosystems_are_left_alone() {
+        assert_eq!(
+            render_all("maven", &semver("1.0.0")),
+            vec![None, None, None, None, None]
+        );
+    }
+
+    #[test]
+    fn wire_keys_round_trip() {
+        for key in DepRequirementStrategy::ALLOWED.split(", ") {
+            let strategy = DepRequirementStrategy::from_wire(key).unwrap();
+            assert_eq!(strategy.wire_key(), key);
+        }
+        assert_eq!(DepRequirementStrategy::from_wire("pinned"), None);
+    }
+}
@@ -0,0 +1,156 @@
+//! `SubmodulePinRewriter` — moves a `[submodule.<name>]` pin to the
+//! newest release of the submodule.
+//!
+//! A component kept in a git submodule is released from its own
+//! repository, which tags it; this repository pins it at one commit.
+//! When `belaf prepare` rewrites the project files, every configured
+//! submodule whose checkout has a release tag newer than the pinned
+//! commit is checked out at that tag, so the release commit moves the
+//! pin, and its `version_files` are rewritten to name the new version.
+//! A pin already at or past the newest release stays where it is.
+//!
+//! Tags are read from the submodule's checkout as it is; nothing is
+//! fetched. Moving a pin never starts a release by itself: it rides
+//! along with the units `prepare` releases.
+
+use anyhow::{bail, Context};
+use regex::Regex;
+use tracing::info;
+
+use crate::core::config::syntax::{SubmoduleConfig, VersionReferenceConfig};
+use crate::core::errors::Result;
+use crate::core::git::repository::{ChangeList, CommitId, RepoPathBuf, Repository, SubmoduleTag};
+use crate::core::rewriters::Rewriter;
+use crate::core::session::AppSession;
+use crate::core::tag_format::{build_tag_matcher, TagMatcher, TagPatternInputs};
+use crate::core::version_field::generic_regex;
+
+/// Tag format of submodule releases when `tag_format` is omitted.
+const DEFAULT_TAG_FORMAT: &str = "v{version}";
+
+/// Where [`SubmodulePinRewriter::plan`] moves a pin.
+#[derive(Clone, Debug)]
+pub struct PinUpdate {
+    /// Release the pinned commit is tagged as, if any.
+    pub from: Option<semver::Version>,
+    pub to: SubmoduleTag,
+}
+
+/// `Rewriter` for one `[submodule.<name>]` entry, checked against the
+/// repository's submodules at session-build time.
+#[derive(Debug)]
+pub struct SubmodulePinRewriter {
+    name: String,
+    path: RepoPathBuf,
+    pinned: Option<CommitId>,
+    matcher: TagMatcher,
+    version_files: Vec<VersionReferenceConfig>,
+}
+
+impl SubmodulePinRewriter {
+    pub fn new(name: &str, config: &SubmoduleConfig, repo: &Repository) -> Result<Self> {
+        let path = config.path.trim_end_matches('/');
+        let Some(submodule) = repo
+            .submodules()?
+            .into_iter()
+            .find(|s| s.path.escaped() == path)
+        else {
+            bail!("`{path}` is not a submodule of this repository");
+        };
+        for file in &config.version_files {
+            let re = Regex::new(&file.regex_pattern).with_context(|| {
+                format!(
+                    "invalid regex_pattern `{}` for `{}`",
+                    file.regex_pattern, file.path
+                )
+            })?;
+            if re.captures_len() != 2 {
+                bail!(
+                    "regex_pattern `{}` for `{}` must have exactly one capture group",
+                    file.regex_pattern,
+                    file.path
+                );
+            }
+        }
+        let matcher = build_tag_matcher(&TagPatternInputs {
+            project_name: name,
+            ecosystem: "submodule",
+            ecosystem_default: DEFAULT_TAG_FORMAT,
+            allowed_vars: &["name", "version"],
+            override_template: config.tag_format.as_deref(),
+            maven_coords: None,
+            module_path: None,
+            allow_bare_v_fallback: false,
+        })?;
+        Ok(Self {
+            name: name.to_string(),
+            path: submodule.path,
+            pinned: submodule.pinned,
+            matcher,
+            version_files: config.version_files.clone(),
+        })
+    }
+
+    /// The newest release of the submodule, unless the pin is already
+    /// there or past it.
+    pub fn plan(&self, repo: &Repository) -> Result<Option<PinUpdate>> {
+        let tags = repo.submodule_release_tags(&self.path, &self.matcher)?;
+        let Some(newest) = tags.first() else {
+            return Ok(None);
+        };
+        if let Some(pinned) = self.pinned {
+            if repo.submodule_contains(&self.path, pinned, newest.commit)? {
+                return Ok(None);
+            }
+        }
+        Ok(Some(PinUpdate {
+            from: tags
+                .iter()
+                .find(|t| Some(t.commit) == self.pinned)
+                .map(|t| t.version.clone()),
+            to: newest.clone(),
+        }))
+    }
+}
+
+impl Rewriter for SubmodulePinRewriter {
+    fn rewrite(&self, app: &AppSession, changes: &mut ChangeList) -> Result<()> {
+        let Some(update) = self
+            .plan(&app.repo)
+            .with_context(|| format!("failed to find the releases of submodule `{}`", self.name))?
+        else {
+            return Ok(());
+        };
+        app.repo.checkout_submodule(&self.path, update.to.commit)?;
+        changes.add_path(&self.path);
+
+        let version = update.to.version.to_string();
+        for file in &self.version_files {
+            let path = RepoPathBuf::new(file.path.as_bytes());
+            generic_regex::write(
+                &app.repo.resolve_workdir(&path),
+                &file.regex_pattern,
+                &file.regex_replace,
+                &version,
+            )
+            .with_context(|| {
+                format!(
+                    "failed to update `{}` for submodule `{}`",
+                    file.path, self.name
+                )
+            })?;
+            changes.add_path(&path);
+        }
+
+        info!(
+            "submodule {}: {} -> {} ({})",
+            self.name,
+            update
+                .from
+                .map_or_else(|| "unreleased commit".to_string(), |v| v.to_string()),
+            version,
+            update.to.name
+        );
+        Ok(())
+    }
+}
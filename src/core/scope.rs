@@ -0,0 +1,96 @@
+//! `--scope <dir>`: restrict a run to the release units under one directory
+//! of a large monorepo.
+//!
+//! The directory is selected once per process from the command line;
+//! [`crate::core::session::AppBuilder`] turns it into a repository path
+//! when it opens the repository. From there:
+//!
+//! - discovery only reads manifests under the directory, plus workspace
+//!   roots above it whose members may live inside;
+//! - configured `[release_unit.<name>]` blocks outside it stay out of the
+//!   graph, and dependencies on units outside it are dropped;
+//! - history analysis only diffs the paths of the scoped units;
+//! - the drift check only reports bundles under the directory.
+
+use std::{
+    path::{Path, PathBuf},
+    sync::RwLock,
+};
+
+use crate::core::{
+    git::repository::RepoPath,
+    release_unit::{ReleaseUnit, VersionSource},
+};
+
+static SCOPE: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+/// Restrict this process to `dir`, relative to the current directory;
+/// `None` lifts the restriction.
+pub fn select(dir: Option<&Path>) {
+    if let Ok(mut slot) = SCOPE.write() {
+        *slot = dir.map(Path::to_path_buf);
+    }
+}
+
+/// The directory passed to `--scope`, if any.
+pub fn selected() -> Option<PathBuf> {
+    SCOPE.read().ok().and_then(|slot| slot.clone())
+}
+
+/// Whether `path` is `scope` itself or lies below it.
+pub fn contains(scope: &RepoPath, path: &RepoPath) -> bool {
+    let (scope, path): (&[u8], &[u8]) = (scope.pop_sep().as_ref(), path.as_ref());
+    scope.is_empty()
+        || path == scope
+        || (path.starts_with(scope) && path.get(scope.len()) == Some(&b'/'))
+}
+
+/// Whether the file at `path` sits in `scope` or in one of its parent
+/// directories. Workspace manifests at those places can declare members
+/// inside the scope, so discovery still has to read them.
+pub fn may_declare_members(scope: &RepoPath, path: &RepoPath) -> bool {
+    let (dir, _) = path.split_basename();
+    contains(dir, scope) || contains(scope, path)
+}
+
+/// Whether a configured release unit belongs to `scope`: one of its
+/// manifests, or for an external versioner its `cwd` or a satellite, lies
+/// inside.
+pub fn covers_unit(scope: &RepoPath, unit: &ReleaseUnit) -> bool {
+    match &unit.source {
+        VersionSource::Manifests(ms) => ms.iter().any(|m| contains(scope, &m.path)),
+        VersionSource::External(ext) => ext
+            .cwd
+            .iter()
+            .chain(&unit.satellites)
+            .any(|p| contains(scope, p)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::git::repository::RepoPathBuf;
+
+    fn p(s: &str) -> RepoPathBuf {
+        RepoPathBuf::new(s.as_bytes())
+    }
+
+    #[test]
+    fn scope_containment() {
+        let scope = p("apps/team-a");
+        assert!(contains(&scope, &p("apps/team-a")));
+        assert!(contains(&scope, &p("apps/team-a/api/Cargo.toml")));
+        assert!(!contains(&scope, &p("apps/team-ab/Cargo.toml")));
+        assert!(!contains(&scope, &p("apps/Cargo.toml")));
+        assert!(contains(&p("apps/team-a/"), &p("apps/team-a/x")));
+
+        assert!(may_declare_members(&scope, &p("Cargo.toml")));
+        assert!(may_declare_members(&scope, &p("apps/package.json")));
+        assert!(may_declare_members(
+            &scope,
+            &p("apps/team-a/api/Cargo.toml")
+        ));
+        assert!(!may_declare_members(&scope, &p("apps/team-b/Cargo.toml")));
+    }
+}
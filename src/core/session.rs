@@ -4,23 +4,29 @@
 //! State for the Belaf CLI application.
 
 use anyhow::{anyhow, Context};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use thiserror::Error as ThisError;
 use tracing::{error, info, warn};
 
 use crate::{
     atry,
     core::{
+        changelog::{context_variable_error, LinkForge},
+        commit_scopes::ScopeUsage,
         config::{syntax::ChangelogConfiguration, ConfigurationFile},
         ecosystem::format_handler::FormatHandlerRegistry,
-        errors::Result,
-        git::repository::{ChangeList, ReleaseAvailability, Repository},
+        errors::{ConfigError, Result},
+        git::repository::{ChangeList, CommitId, ReleaseAvailability, Repository},
         graph::{ReleaseUnitGraph, ReleaseUnitGraphBuilder, RepoHistories},
         group::GroupSet,
+        release_line::ReleaseLine,
         resolved_release_unit::{DepRequirement, ReleaseUnitId, ResolvedReleaseUnit},
-        tag_format::{
-            build_tag_matcher, split_maven_coords, TagMatcher, TagPatternInputs,
+        rewriters::{
+            dep_requirement::DepRequirementStrategy, submodule_pin::SubmodulePinRewriter, Rewriter,
         },
+        scope,
+        tag_format::{build_tag_matcher, split_maven_coords, TagMatcher, TagPatternInputs},
+        timings,
         version::Version,
     },
     utils::theme::ReleaseProgressBar,
@@ -37,8 +43,13 @@ use crate::{
 /// project's last release" — `analyze_histories`,
 /// `find_earliest_release_containing`, the initial `resolve_versions_from_tags`
 /// pass during graph build.
+///
+/// `previous_names` come from `[release_unit.<name>].previous_names`;
+/// tags rendered with any of them under the same template also match,
+/// so a renamed unit keeps its release history.
 fn build_tag_matcher_for(
     project_name: &str,
+    previous_names: &[String],
     ecosystem_name: &str,
     tag_format_override: Option<&str>,
     group_tag_format: Option<&str>,
@@ -51,27 +62,35 @@ fn build_tag_matcher_for(
             Some(h) => (h.tag_format_default(), h.tag_template_vars()),
             None => ("{name}@v{version}", &["name", "version", "ecosystem"]),
         };
-    let maven_coords = if ecosystem_name == "maven" {
-        split_maven_coords(project_name)
-    } else {
-        None
-    };
-    let module_path = if ecosystem_name == "go" {
-        Some(project_name)
-    } else {
-        None
-    };
-    let inputs = TagPatternInputs {
-        project_name,
-        ecosystem: ecosystem_name,
-        ecosystem_default: eco_default_tag,
-        allowed_vars: eco_allowed_vars,
-        override_template: template_override,
-        maven_coords,
-        module_path,
-        allow_bare_v_fallback,
+    let matcher_for = |name: &str, allow_bare_v_fallback: bool| {
+        let maven_coords = if ecosystem_name == "maven" {
+            split_maven_coords(name)
+        } else {
+            None
+        };
+        let module_path = if ecosystem_name == "go" {
+            Some(name)
+        } else {
+            None
+        };
+        let inputs = TagPatternInputs {
+            project_name: name,
+            ecosystem: ecosystem_name,
+            ecosystem_default: eco_default_tag,
+            allowed_vars: eco_allowed_vars,
+            override_template: template_override,
+            maven_coords,
+            module_path,
+            allow_bare_v_fallback,
+        };
+        build_tag_matcher(&inputs)
     };
-    build_tag_matcher(&inputs)
+
+    let mut matcher = matcher_for(project_name, allow_bare_v_fallback)?;
+    for previous in previous_names {
+        matcher = matcher.with_previous_name(matcher_for(previous, false)?);
+    }
+    Ok(matcher)
 }
 
 /// Build a matcher per project for the *runtime* graph — used by
@@ -96,18 +115,17 @@ fn build_matchers_for_runtime_units(
                 .get(1)
                 .cloned()
                 .unwrap_or_else(|| "cargo".to_string());
-            let tag_format_override = cfg_units
-                .iter()
-                .find(|r| r.unit.name == *project_name)
-                .and_then(|r| r.unit.tag_format.as_deref())
-                .map(|s| s.to_string());
+            let cfg_unit = cfg_units.iter().find(|r| r.unit.name == *project_name);
+            let tag_format_override = cfg_unit.and_then(|r| r.unit.tag_format.as_deref());
+            let previous_names = cfg_unit.map_or(&[][..], |r| &r.unit.previous_names);
             let group_tag_format = groups
                 .group_of(unit.ident())
                 .and_then(|g| g.tag_format.clone());
             build_tag_matcher_for(
                 project_name,
+                previous_names,
                 &ecosystem_name,
-                tag_format_override.as_deref(),
+                tag_format_override,
                 group_tag_format.as_deref(),
                 registry,
                 allow_bare_v_fallback,
@@ -130,6 +148,9 @@ pub struct AppBuilder {
     populate_graph: bool,
     show_progress: bool,
     fetch_tags_first: bool,
+    maintenance_branch: Option<String>,
+    at: Option<String>,
+    as_of: Option<CommitId>,
 }
 
 fn detect_ci_environment() -> bool {
@@ -147,10 +168,41 @@ impl AppBuilder {
     /// This first phase of initialization may fail if the environment doesn't
     /// associate the process with a proper Git repository with a work tree.
     pub fn new() -> Result<AppBuilder> {
-        let repo = Repository::open_from_env()?;
-        let graph = ReleaseUnitGraphBuilder::new();
+        let _timing = timings::stage("open repository");
+        Self::with_repo(Repository::open_from_env()?)
+    }
+
+    /// Like [`Self::new`], for commands that only read: a bare repository
+    /// is read as of its `HEAD` commit. Nothing may be written through
+    /// the session, as there is no working tree to write to.
+    pub fn read_only() -> Result<AppBuilder> {
+        let _timing = timings::stage("open repository");
+        Self::with_repo(Repository::open_from_env_read_only()?)
+    }
+
+    fn with_repo(mut repo: Repository) -> Result<AppBuilder> {
+        let mut graph = ReleaseUnitGraphBuilder::new();
         let is_ci = detect_ci_environment();
 
+        if let Some(dir) = scope::selected() {
+            if repo.as_of().is_some() {
+                return Err(anyhow!(
+                    "--scope needs a working tree; it can't be used in a bare repository"
+                ));
+            }
+            if !dir.is_dir() {
+                return Err(anyhow!("--scope `{}` is not a directory", dir.display()));
+            }
+            let path = repo
+                .convert_path(&dir)
+                .with_context(|| format!("invalid --scope `{}`", dir.display()))?;
+            if !path.is_empty() {
+                info!("restricting release units to `{}`", path.escaped());
+                graph.set_scope(path.clone());
+                repo.set_scope(path);
+            }
+        }
+
         Ok(AppBuilder {
             graph,
             repo,
@@ -158,6 +210,9 @@ impl AppBuilder {
             populate_graph: true,
             show_progress: false,
             fetch_tags_first: false,
+            maintenance_branch: None,
+            at: None,
+            as_of: None,
         })
     }
 
@@ -183,6 +238,52 @@ impl AppBuilder {
         self
     }
 
+    /// Release from the maintenance branch `branch` (`release/1.x`):
+    /// check it out before anything is read, and only consider release
+    /// tags on the version line its name gives. See
+    /// [`crate::core::release_line`].
+    pub fn maintenance_branch(mut self, branch: Option<String>) -> Self {
+        self.maintenance_branch = branch;
+        self
+    }
+
+    /// Look at the repository as it was at `rev`: read files from that
+    /// commit's tree instead of the working tree, and only consider
+    /// release tags on its history. See [`Repository::set_as_of`].
+    pub fn at(mut self, rev: Option<String>) -> Self {
+        self.at = rev;
+        self
+    }
+
+    fn read_as_of(&mut self, rev: &str) -> Result<()> {
+        let cid = self.repo.resolve_commitish(rev)?;
+        self.repo.set_as_of(cid);
+        info!("reading the repository as of {cid}");
+        self.as_of = Some(cid);
+        Ok(())
+    }
+
+    fn checkout_maintenance_branch(&mut self, branch: &str) -> Result<()> {
+        let line = ReleaseLine::from_branch(branch).ok_or_else(|| {
+            anyhow!(
+                "cannot tell which versions `{branch}` releases; name maintenance \
+                 branches after their version line, e.g. `release/1.x` or `release/1.4.x`"
+            )
+        })?;
+        if self.repo.current_branch_name()?.as_deref() != Some(branch) {
+            if let Some(dirty) = self.repo.check_if_dirty(&[])? {
+                return Err(anyhow!(
+                    "cannot switch to `{branch}` with uncommitted changes (e.g.: `{}`)",
+                    dirty.escaped()
+                ));
+            }
+            self.repo.checkout_branch(branch)?;
+        }
+        info!("releasing the {line} line from `{branch}`");
+        self.repo.set_release_line(line);
+        Ok(())
+    }
+
     /// Walk every project whose manifest reported version `0.0.0` and
     /// try to recover the real current version from an existing git
     /// tag. Uses the same template-driven lookup as the post-init code
@@ -230,24 +331,21 @@ impl AppBuilder {
                 .get(1)
                 .cloned()
                 .unwrap_or_else(|| "cargo".to_string());
-            let tag_format_override = resolved_units
-                .iter()
-                .find(|r| r.unit.name == project_name)
-                .and_then(|r| r.unit.tag_format.as_deref())
-                .map(|s| s.to_string());
+            let cfg_unit = resolved_units.iter().find(|r| r.unit.name == project_name);
+            let tag_format_override = cfg_unit.and_then(|r| r.unit.tag_format.as_deref());
+            let previous_names = cfg_unit.map_or(&[][..], |r| &r.unit.previous_names);
 
             let matcher = build_tag_matcher_for(
                 &project_name,
+                previous_names,
                 &ecosystem_name,
-                tag_format_override.as_deref(),
+                tag_format_override,
                 None, // groups not assembled yet — see fn docstring
                 &registry,
                 is_single_project,
             )?;
 
-            if let Some((_, tag_name, version)) =
-                self.repo.find_latest_tag_for_project(&matcher)?
-            {
+            if let Some((_, tag_name, version)) = self.repo.find_latest_tag_for_project(&matcher)? {
                 if version.major != 0 || version.minor != 0 || version.patch != 0 {
                     info!(
                         "resolved version {} from tag '{}' for project '{}'",
@@ -263,23 +361,179 @@ impl AppBuilder {
 
     /// Finish app initialization, yielding a full AppSession object.
     pub fn initialize(mut self) -> Result<AppSession> {
+        if let Some(branch) = self.maintenance_branch.take() {
+            self.checkout_maintenance_branch(&branch)?;
+        }
+        if let Some(rev) = self.at.take() {
+            self.read_as_of(&rev)?;
+        }
+
         // Start by loading the configuration file, if it exists. If it doesn't
         // we'll get a sensible default.
 
+        let timing = timings::stage("load config");
         let mut cfg_path = self.repo.resolve_config_dir();
         cfg_path.push("config.toml");
-        let config = ConfigurationFile::get(&cfg_path).with_context(|| {
-            format!(
+        let config = ConfigurationFile::get_in(&self.repo).with_context(|| {
+            ConfigError(format!(
                 "failed to load repository config file `{}`",
                 cfg_path.display()
-            )
+            ))
         })?;
 
         self.repo
             .apply_config(config.repo)
             .with_context(|| "failed to finalize repository setup")?;
+        self.repo.apply_git_config(config.git);
+        self.repo.set_project_trailers(
+            config.commit_attribution.strategy == crate::core::project_trailers::STRATEGY,
+        );
+        let repo_root = self
+            .repo
+            .resolve_workdir(&crate::core::git::repository::RepoPathBuf::new(b""));
+        crate::core::net::configure(&config.network, &repo_root);
+        crate::core::github::forge::configure(&config.forge)
+            .with_context(|| ConfigError(format!("invalid [forge] in `{}`", cfg_path.display())))?;
+        if let Some(approvers) = &config.release.approvers {
+            crate::core::approval::validate(approvers).with_context(|| {
+                ConfigError(format!(
+                    "invalid [release.approvers] in `{}`",
+                    cfg_path.display()
+                ))
+            })?;
+        }
+        if let Some(signing) = &config.signing {
+            crate::core::signing::validate(signing).with_context(|| {
+                ConfigError(format!("invalid [signing] in `{}`", cfg_path.display()))
+            })?;
+        }
+        if let Some(milestones) = &config.release.milestones {
+            crate::core::github::client::validate_milestones(milestones).with_context(|| {
+                ConfigError(format!(
+                    "invalid [release.milestones] in `{}`",
+                    cfg_path.display()
+                ))
+            })?;
+        }
+        crate::core::auth::profile::configure(
+            &config.auth,
+            self.repo.upstream_url().ok().as_deref(),
+        );
+        let mut submodule_pins = Vec::new();
+        let submodules: BTreeMap<_, _> = config.submodules.iter().collect();
+        for (name, submodule) in submodules {
+            let pin =
+                SubmodulePinRewriter::new(name, submodule, &self.repo).with_context(|| {
+                    ConfigError(format!(
+                        "invalid [submodule.{name}] in `{}`",
+                        cfg_path.display()
+                    ))
+                })?;
+            submodule_pins.push(pin);
+        }
+        for (unit, packaging) in &config.packaging {
+            crate::core::packaging::validate(packaging).with_context(|| {
+                ConfigError(format!(
+                    "invalid [packaging.{unit}] in `{}`",
+                    cfg_path.display()
+                ))
+            })?;
+        }
+        if let Some(forge) = config
+            .changelog
+            .link_style
+            .as_ref()
+            .and_then(|style| style.forge.as_deref())
+        {
+            LinkForge::from_wire(forge).ok_or_else(|| {
+                ConfigError(format!(
+                    "invalid [changelog.link_style] forge `{forge}` in `{}` (expected one of: {})",
+                    cfg_path.display(),
+                    LinkForge::ALLOWED
+                ))
+            })?;
+        }
+        for name in config.changelog.context_commands.keys() {
+            if let Some(reason) = context_variable_error(name) {
+                return Err(ConfigError(format!(
+                    "invalid [changelog.context_commands] variable `{name}` in `{}`: it {reason}",
+                    cfg_path.display()
+                ))
+                .into());
+            }
+        }
+        let mut changelog_files = vec![config.changelog.output.as_str()];
+        for output in &config.changelog.outputs {
+            if output.language.trim().is_empty() {
+                return Err(ConfigError(format!(
+                    "invalid [[changelog.outputs]] entry for `{}` in `{}`: `language` is empty",
+                    output.output,
+                    cfg_path.display()
+                ))
+                .into());
+            }
+            if changelog_files.contains(&output.output.as_str()) {
+                return Err(ConfigError(format!(
+                    "invalid [[changelog.outputs]] entry `{}` in `{}`: `{}` is already written by another changelog",
+                    output.language,
+                    cfg_path.display(),
+                    output.output
+                ))
+                .into());
+            }
+            changelog_files.push(&output.output);
+        }
+        if let Some(url) = config
+            .webhooks
+            .urls
+            .iter()
+            .find(|url| !url.starts_with("https://") && !url.starts_with("http://"))
+        {
+            return Err(ConfigError(format!(
+                "invalid [webhooks] url `{url}` in `{}`: it must be an http(s) URL",
+                cfg_path.display()
+            ))
+            .into());
+        }
+        if let Some(jira) = &config.trackers.jira {
+            if !jira.url.starts_with("https://") && !jira.url.starts_with("http://") {
+                return Err(ConfigError(format!(
+                    "invalid [trackers.jira] url `{}` in `{}`: it must be an http(s) URL",
+                    jira.url,
+                    cfg_path.display()
+                ))
+                .into());
+            }
+            if !crate::core::jira::is_project_key(&jira.project) {
+                return Err(ConfigError(format!(
+                    "invalid [trackers.jira] project `{}` in `{}`: expected a project key such as `PROJ`",
+                    jira.project,
+                    cfg_path.display()
+                ))
+                .into());
+            }
+        }
+        let mut dep_requirements = HashMap::new();
+        for (ecosystem, raw) in [
+            ("cargo", &config.ecosystems.cargo.dep_requirement),
+            ("npm", &config.ecosystems.npm.dep_requirement),
+            ("pypa", &config.ecosystems.pypa.dep_requirement),
+        ] {
+            let Some(raw) = raw else { continue };
+            let strategy = DepRequirementStrategy::from_wire(raw).ok_or_else(|| {
+                ConfigError(format!(
+                    "invalid [ecosystems.{ecosystem}] dep_requirement `{raw}` in `{}` (expected one of: {})",
+                    cfg_path.display(),
+                    DepRequirementStrategy::ALLOWED
+                ))
+            })?;
+            dep_requirements.insert(ecosystem.to_owned(), strategy);
+        }
+
+        drop(timing);
 
         if self.fetch_tags_first && std::env::var_os("BELAF_NO_FETCH").is_none() {
+            let _timing = timings::stage("fetch tags");
             self.repo
                 .fetch_tags(None)
                 .with_context(|| "failed to fetch upstream tags before release prep")?;
@@ -307,6 +561,7 @@ impl AppBuilder {
             // as a skip-list, and (c) match partial-override blocks
             // (those without `ecosystem`) against the auto-detected set
             // after discovery returns.
+            let timing = timings::stage("resolve release units");
             let resolve_output =
                 crate::core::release_unit::resolver::resolve(&self.repo, &config.release_units)
                     .map_err(|e| {
@@ -343,18 +598,27 @@ impl AppBuilder {
             }
 
             for resolved in &resolved_units {
+                if let Some(scope) = self.repo.scope() {
+                    if !scope::covers_unit(scope, &resolved.unit) {
+                        continue;
+                    }
+                }
                 self.add_configured_unit_to_graph(&registry, resolved)?;
             }
 
+            drop(timing);
+
             // The skip-list keeps auto-discovery from claiming the
             // same manifest paths that a `[release_unit.X]` block
             // already covers.
+            let timing = timings::stage("discovery");
             let discovered = discover_implicit_release_units(
                 &self.repo,
                 &registry,
                 &discoverers,
                 &configured_skip_paths,
             )?;
+            drop(timing);
 
             // Match partial-override specs against the discovered set
             // and synthesize ResolvedReleaseUnits whose override fields
@@ -362,9 +626,16 @@ impl AppBuilder {
             // effect at workflow time. These are NOT registered via
             // `add_configured_unit_to_graph` — graph registration goes
             // through the discovered unit's already-built rewriters.
+            let mut partial_overrides = resolve_output.partial_overrides;
+            if self.repo.scope().is_some() {
+                // Overrides for units outside the scope have nothing to
+                // decorate in this run; that is not a config error.
+                partial_overrides
+                    .retain(|o| discovered.iter().any(|d| d.qnames.first() == Some(&o.name)));
+            }
             let partial_resolved =
                 crate::core::release_unit::resolver::resolve_partial_against_discovered(
-                    &resolve_output.partial_overrides,
+                    &partial_overrides,
                     &discovered,
                 )
                 .map_err(|e| {
@@ -386,12 +657,47 @@ impl AppBuilder {
                 }
             }
 
+            let _timing = timings::stage("versions from tags");
             self.resolve_versions_from_tags(&resolved_units)?;
         }
 
         // Apply project config and compile the graph.
 
+        let timing = timings::stage("graph");
         let graph = self.graph.complete_loading_with_groups(&config.groups)?;
+        drop(timing);
+        if let Some(unit) = config
+            .packaging
+            .keys()
+            .find(|u| graph.lookup_ident(u).is_none())
+        {
+            return Err(ConfigError(format!(
+                "[packaging.{unit}] in `{}` names no release unit",
+                cfg_path.display()
+            ))
+            .into());
+        }
+        if config.release.publish.parallelism == 0 {
+            return Err(ConfigError(format!(
+                "[release.publish] parallelism in `{}` must be at least 1",
+                cfg_path.display()
+            ))
+            .into());
+        }
+        if let Some(unit) = config
+            .release
+            .publish
+            .after
+            .iter()
+            .flat_map(|(unit, after)| std::iter::once(unit).chain(after))
+            .find(|u| graph.lookup_ident(u).is_none())
+        {
+            return Err(ConfigError(format!(
+                "[release.publish] after in `{}` names `{unit}`, which is no release unit",
+                cfg_path.display()
+            ))
+            .into());
+        }
 
         Ok(AppSession {
             repo: self.repo,
@@ -400,11 +706,24 @@ impl AppBuilder {
             changelog_config: config.changelog,
             bump_config: config.bump,
             bump_sources: config.bump_sources,
+            approvers: config.release.approvers,
+            milestones: config.release.milestones.filter(|m| m.enabled),
+            check_registries: config.release.check_registries,
+            publish: config.release.publish,
+            status_gates: config.ci.status_gates,
+            checks: config.checks,
+            packaging: config.packaging,
+            submodule_pins,
+            webhooks: config.webhooks,
+            trackers: config.trackers,
+            signing: config.signing,
+            dep_requirements,
             resolved_release_units: resolved_units,
             ignore_paths,
             allow_uncovered,
             detection_cache: std::sync::OnceLock::new(),
             is_ci: self.is_ci,
+            as_of: self.as_of,
         })
     }
 
@@ -437,7 +756,14 @@ impl AppBuilder {
                     anyhow!("release_unit `{}` has empty manifests = []", unit.name)
                 })?;
                 let abs = self.repo.resolve_workdir(&first.path);
-                let version_str = crate::core::version_field::read(&first.version_field, &abs)
+                let version_str = self
+                    .repo
+                    .read_to_string(&first.path)
+                    .map_err(anyhow::Error::from)
+                    .and_then(|content| {
+                        crate::core::version_field::read_str(&first.version_field, &abs, &content)
+                            .map_err(anyhow::Error::from)
+                    })
                     .with_context(|| {
                         format!(
                             "reading version for release_unit `{}` from `{}`",
@@ -456,6 +782,13 @@ impl AppBuilder {
                 (version, prefix_path.to_owned(), ms.clone())
             }
             VersionSource::External(ext) => {
+                if self.repo.as_of().is_some() {
+                    return Err(anyhow!(
+                        "release_unit `{}` reads its version with an external command, \
+                         which needs a working tree",
+                        unit.name
+                    ));
+                }
                 let version_str = crate::core::rewriters::external::read_current(ext, &self.repo)
                     .map_err(|e| {
                     anyhow!(
@@ -554,6 +887,31 @@ pub struct AppSession {
     /// `[[bump_source]]` entries from `belaf/config.toml`. Resolved at
     /// CI/wizard entry by [`crate::cmd::prepare`].
     bump_sources: Vec<super::config::syntax::BumpSourceConfig>,
+    /// `[release.approvers]`, already validated. `None` when releases
+    /// need no sign-off.
+    approvers: Option<super::config::syntax::ApproversConfiguration>,
+    /// `[release.milestones]`, already validated. `None` unless enabled.
+    milestones: Option<super::config::syntax::MilestonesConfiguration>,
+    /// `[release] check_registries`.
+    check_registries: bool,
+    /// `[release.publish]`, its units checked against the graph.
+    publish: super::config::syntax::PublishConfiguration,
+    /// `[ci.status_gates]`.
+    status_gates: super::config::syntax::StatusGatesConfiguration,
+    /// `[checks]`.
+    checks: super::config::syntax::ChecksConfiguration,
+    /// `[packaging.<unit>]`, already validated and keyed by unit name.
+    packaging: HashMap<String, super::config::syntax::PackagingConfig>,
+    /// `[submodule.<name>]`, checked against the repository's submodules.
+    submodule_pins: Vec<SubmodulePinRewriter>,
+    /// `[webhooks]`, already validated.
+    webhooks: super::config::syntax::WebhooksConfiguration,
+    /// `[trackers]`, already validated.
+    trackers: super::config::syntax::TrackersConfiguration,
+    /// `[signing]`, already validated.
+    signing: Option<super::config::syntax::SigningConfiguration>,
+    /// `[ecosystems.<name>] dep_requirement`, keyed by ecosystem.
+    dep_requirements: HashMap<String, DepRequirementStrategy>,
     /// Resolved `[release_unit.<name>]` / glob-form `[release_unit.<name>]` entries.
     /// Held so [`Self::pre_prepare_drift_check`] can compare detected
     /// bundles against the configured coverage set without re-running
@@ -573,6 +931,8 @@ pub struct AppSession {
     detection_cache: std::sync::OnceLock<crate::core::release_unit::detector::DetectionReport>,
     graph: ReleaseUnitGraph,
     is_ci: bool,
+    /// The commit of [`AppBuilder::at`].
+    as_of: Option<CommitId>,
 }
 
 impl AppSession {
@@ -581,6 +941,11 @@ impl AppSession {
         AppBuilder::new()?.initialize()
     }
 
+    /// [`AppBuilder::read_only`] with the default options.
+    pub fn initialize_read_only() -> Result<Self> {
+        AppBuilder::read_only()?.initialize()
+    }
+
     pub fn execution_environment(&self) -> Result<ExecutionEnvironment> {
         if self.is_ci {
             Ok(ExecutionEnvironment::Ci)
@@ -627,6 +992,103 @@ impl AppSession {
         &self.bump_sources
     }
 
+    /// `[release.approvers]` from `belaf/config.toml`, if configured.
+    pub fn config_approvers(&self) -> Option<&super::config::syntax::ApproversConfiguration> {
+        self.approvers.as_ref()
+    }
+
+    /// Whether `[release] check_registries` is set.
+    pub fn check_registries(&self) -> bool {
+        self.check_registries
+    }
+
+    /// `[release.publish]` from `belaf/config.toml`.
+    pub fn config_publish(&self) -> &super::config::syntax::PublishConfiguration {
+        &self.publish
+    }
+
+    /// `[release.milestones]` from `belaf/config.toml`, if enabled.
+    pub fn config_milestones(&self) -> Option<&super::config::syntax::MilestonesConfiguration> {
+        self.milestones.as_ref()
+    }
+
+    /// `[ci.status_gates]` from `belaf/config.toml`.
+    pub fn status_gates(&self) -> &super::config::syntax::StatusGatesConfiguration {
+        &self.status_gates
+    }
+
+    /// `[checks]` from `belaf/config.toml`.
+    pub fn checks(&self) -> &super::config::syntax::ChecksConfiguration {
+        &self.checks
+    }
+
+    /// `[packaging.<unit>]` for the unit named `name`, if configured.
+    pub fn config_packaging(&self, name: &str) -> Option<&super::config::syntax::PackagingConfig> {
+        self.packaging.get(name)
+    }
+
+    /// `[webhooks]` from `belaf/config.toml`.
+    pub fn webhooks(&self) -> &super::config::syntax::WebhooksConfiguration {
+        &self.webhooks
+    }
+
+    /// The commit the session reads the repository as of, with
+    /// [`AppBuilder::at`].
+    pub fn as_of(&self) -> Option<&CommitId> {
+        self.as_of.as_ref()
+    }
+
+    /// `[signing]` from `belaf/config.toml`, if configured.
+    pub fn config_signing(&self) -> Option<&super::config::syntax::SigningConfiguration> {
+        self.signing.as_ref()
+    }
+
+    /// Whether tags are signed without `--sign`: git's `tag.gpgSign`, or
+    /// `[signing] tags`.
+    pub fn signs_tags(&self) -> bool {
+        self.repo.signs_tags() || self.signing.as_ref().is_some_and(|s| s.tags)
+    }
+
+    /// `[trackers]` from `belaf/config.toml`.
+    pub fn trackers(&self) -> &super::config::syntax::TrackersConfiguration {
+        &self.trackers
+    }
+
+    /// How `ident`'s requirements on released internal dependencies are
+    /// rewritten: its `[release_unit.<name>]` setting, else its
+    /// ecosystem's. `None` when neither is configured.
+    pub fn dep_requirement_strategy(&self, ident: ReleaseUnitId) -> Option<DepRequirementStrategy> {
+        let unit = self.graph.lookup(ident);
+        self.resolved_release_units
+            .iter()
+            .find(|r| r.unit.name == unit.user_facing_name)
+            .and_then(|r| r.unit.dep_requirement)
+            .or_else(|| {
+                let ecosystem = unit.qualified_names().get(1)?;
+                self.dep_requirements.get(ecosystem).copied()
+            })
+    }
+
+    /// The conventional-commit scopes `ident`'s commits may use, from its
+    /// `[release_unit.<name>] scopes`. Empty allows any.
+    pub fn allowed_scopes(&self, ident: ReleaseUnitId) -> &[String] {
+        let unit = self.graph.lookup(ident);
+        self.resolved_release_units
+            .iter()
+            .find(|r| r.unit.name == unit.user_facing_name)
+            .map_or(&[], |r| &r.unit.scopes)
+    }
+
+    /// The manual release steps of `ident`, from its `[release_unit.<name>]
+    /// release_checklist`.
+    pub fn release_checklist(&self, ident: ReleaseUnitId) -> &[String] {
+        let unit = self.graph.lookup(ident);
+        self.resolved_release_units
+            .iter()
+            .find(|r| r.unit.name == unit.user_facing_name)
+            .map_or(&[], |r| &r.unit.release_checklist)
+    }
+
     /// Resolved `[release_unit.<name>]` / glob-form `[release_unit.<name>]` entries.
     pub fn resolved_release_units(&self) -> &[crate::core::release_unit::ResolvedReleaseUnit] {
         &self.resolved_release_units
@@ -677,8 +1139,13 @@ impl AppSession {
     /// output. The first caller pays the filesystem-walk cost; the
     /// rest reuse the materialised report.
     pub fn detection_report(&self) -> &crate::core::release_unit::detector::DetectionReport {
-        self.detection_cache
-            .get_or_init(|| crate::core::release_unit::detector::detect_all(&self.repo))
+        self.detection_cache.get_or_init(|| {
+            let mut report = crate::core::release_unit::detector::detect_all(&self.repo);
+            if let Some(scope) = self.repo.scope() {
+                report.matches.retain(|m| scope::contains(scope, &m.path));
+            }
+            report
+        })
     }
 
     pub fn graph(&self) -> &ReleaseUnitGraph {
@@ -756,7 +1223,7 @@ impl AppSession {
                                     dependee_proj.version.clone()
                                 }
 
-                                ReleaseAvailability::ExistingRelease(ref v) => v.clone(),
+                                ReleaseAvailability::ExistingRelease(ref v, _) => v.clone(),
 
                                 ReleaseAvailability::NewRelease => {
                                     if let Some(v) = new_versions.get(&dep.ident) {
@@ -873,8 +1340,7 @@ impl AppSession {
 
             Ok(
                 if let Some(bump_spec) = bump_specs.get(&unit.user_facing_name) {
-                    let scheme = unit.version.parse_bump_scheme(bump_spec)?;
-                    scheme.apply(&mut unit.version)?;
+                    unit.version = unit.version.bumped(bump_spec)?;
                     info!(
                         "{}: {} => {}",
                         unit.user_facing_name, baseline_version, unit.version
@@ -905,6 +1371,9 @@ impl AppSession {
                 rw.rewrite(self, &mut changes)?;
             }
         }
+        for pin in &self.submodule_pins {
+            pin.rewrite(self, &mut changes)?;
+        }
 
         Ok(changes)
     }
@@ -926,6 +1395,7 @@ impl AppSession {
     }
 
     pub fn analyze_histories(&self) -> Result<RepoHistories> {
+        let _timing = timings::stage("history analysis");
         let registry = FormatHandlerRegistry::with_defaults();
         let project_refs: Vec<&ResolvedReleaseUnit> = self.graph.projects_slice().iter().collect();
         let matchers = build_matchers_for_runtime_units(
@@ -934,7 +1404,54 @@ impl AppSession {
             self.graph.groups(),
             &registry,
         )?;
-        self.graph.analyze_histories(&self.repo, &matchers)
+        let histories = self.graph.analyze_histories(&self.repo, &matchers)?;
+        self.warn_unknown_scopes(&histories)?;
+        Ok(histories)
+    }
+
+    /// Warn once per unit whose pending commits use scopes outside its
+    /// `scopes` list. See [`crate::core::commit_scopes`].
+    fn warn_unknown_scopes(&self, histories: &RepoHistories) -> Result<()> {
+        for unit in self.graph.projects_slice() {
+            let allowed = self.allowed_scopes(unit.ident());
+            if allowed.is_empty() {
+                continue;
+            }
+            let summaries = histories
+                .lookup(unit.ident())
+                .commits()
+                .into_iter()
+                .map(|cid| self.repo.get_commit_summary(*cid))
+                .collect::<Result<Vec<_>>>()?;
+            let usage = ScopeUsage::tally(summaries.iter().map(String::as_str), allowed);
+            if !usage.unknown.is_empty() {
+                warn!(
+                    "{}: commits use scopes outside its `scopes` list: {} (allowed: {}); run `belaf lint-commits` for details",
+                    unit.user_facing_name,
+                    usage.unknown.join(", "),
+                    allowed.join(", ")
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// The [`TagMatcher`] that recognises `ident`'s release tags, as
+    /// [`Self::analyze_histories`] builds it.
+    pub fn tag_matcher(&self, ident: ReleaseUnitId) -> Result<TagMatcher> {
+        let registry = FormatHandlerRegistry::with_defaults();
+        let project_refs: Vec<&ResolvedReleaseUnit> = self.graph.projects_slice().iter().collect();
+        let idx = project_refs
+            .iter()
+            .position(|unit| unit.ident() == ident)
+            .ok_or_else(|| anyhow!("BUG: no release unit with ident {ident:?}"))?;
+        let mut matchers = build_matchers_for_runtime_units(
+            &project_refs,
+            &self.resolved_release_units,
+            self.graph.groups(),
+            &registry,
+        )?;
+        Ok(matchers.swap_remove(idx))
     }
 }
 
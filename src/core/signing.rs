@@ -0,0 +1,284 @@
+//! Signatures for release artifacts, made with cosign or minisign.
+//!
+//! With `[signing]` configured, `belaf release cut` writes a `SHA256SUMS`
+//! file for each draft, listing its artifacts the way `sha256sum` does,
+//! and signs that file and every artifact. The checksums and signatures
+//! are uploaded to the draft with the artifacts and recorded in the
+//! manifest like them, so `belaf release publish` checks them too.
+//!
+//! The tools are run as installed on `PATH`:
+//!
+//! * minisign signs with the secret key file `key`, writing
+//!   `<file>.minisig`. A password protecting the key is read from
+//!   `MINISIGN_PASSWORD`.
+//! * cosign signs with `key`: a key file, `env://VAR` or a KMS URI such
+//!   as `awskms://…`, writing `<file>.sig`. cosign reads the key's
+//!   password from `COSIGN_PASSWORD` itself. Without `key` it signs
+//!   keyless through Sigstore, with the OIDC identity of the CI job, and
+//!   writes a `<file>.sigstore.json` bundle holding the certificate too.
+//!
+//! Key files are taken relative to the repository root.
+
+use std::ffi::OsString;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use anyhow::{bail, Context};
+
+use crate::core::config::syntax::{SigningConfiguration, SigningTool};
+use crate::core::errors::Result;
+use crate::core::manifest::Artifact;
+use crate::core::publication;
+
+/// The checksums file written for each draft.
+pub const CHECKSUMS: &str = "SHA256SUMS";
+
+/// Environment variable holding the password of a minisign key.
+pub const MINISIGN_PASSWORD_ENV: &str = "MINISIGN_PASSWORD";
+
+/// Check `[signing]` beyond what its shape says.
+pub fn validate(cfg: &SigningConfiguration) -> Result<()> {
+    if cfg.tool == SigningTool::Minisign && cfg.key.is_none() {
+        bail!("minisign needs `key`, the secret key file to sign with");
+    }
+    if cfg.key.as_deref() == Some("") {
+        bail!("`key` is empty");
+    }
+    Ok(())
+}
+
+/// `artifacts` listed as `sha256sum` prints them, one per line.
+pub fn checksums(artifacts: &[Artifact]) -> String {
+    artifacts
+        .iter()
+        .map(|a| format!("{}  {}\n", a.sha256, a.name))
+        .collect()
+}
+
+/// The name of the signature of the file called `name`.
+pub fn signature_name(cfg: &SigningConfiguration, name: &str) -> String {
+    match (cfg.tool, &cfg.key) {
+        (SigningTool::Minisign, _) => format!("{name}.minisig"),
+        (SigningTool::Cosign, Some(_)) => format!("{name}.sig"),
+        (SigningTool::Cosign, None) => format!("{name}.sigstore.json"),
+    }
+}
+
+/// The checksums and signatures for a release with `artifacts`, written
+/// to `out_dir`. They are named after what they sign, so none may be
+/// among `artifacts` already.
+pub fn sign_release(
+    cfg: &SigningConfiguration,
+    repo_root: &Path,
+    out_dir: &Path,
+    artifacts: &[(PathBuf, Artifact)],
+) -> Result<Vec<(PathBuf, Artifact)>> {
+    let listed: Vec<Artifact> = artifacts.iter().map(|(_, a)| a.clone()).collect();
+    let written = listed
+        .iter()
+        .map(|a| signature_name(cfg, &a.name))
+        .chain([CHECKSUMS.to_string(), signature_name(cfg, CHECKSUMS)]);
+    for name in written {
+        if listed.iter().any(|a| a.name == name) {
+            bail!("`{name}` is among the artifacts, but belaf writes it when `[signing]` is set");
+        }
+    }
+
+    fs::create_dir_all(out_dir)
+        .with_context(|| format!("failed to create `{}`", out_dir.display()))?;
+    let sums = out_dir.join(CHECKSUMS);
+    fs::write(&sums, checksums(&listed))
+        .with_context(|| format!("failed to write `{}`", sums.display()))?;
+
+    let mut signed = vec![(sums.clone(), publication::artifact(&sums)?)];
+    let to_sign = artifacts
+        .iter()
+        .map(|(path, a)| (path.as_path(), a.name.as_str()))
+        .chain([(sums.as_path(), CHECKSUMS)]);
+    for (path, name) in to_sign {
+        let signature = out_dir.join(signature_name(cfg, name));
+        sign(cfg, repo_root, path, &signature)
+            .with_context(|| format!("failed to sign `{}`", path.display()))?;
+        signed.push((signature.clone(), publication::artifact(&signature)?));
+    }
+    Ok(signed)
+}
+
+/// Sign `file`, writing the signature to `signature`.
+fn sign(cfg: &SigningConfiguration, repo_root: &Path, file: &Path, signature: &Path) -> Result<()> {
+    let key = cfg.key.as_deref().map(|key| resolve_key(repo_root, key));
+    let (program, args) = command(cfg.tool, key.as_deref(), file, signature);
+    let password = match cfg.tool {
+        SigningTool::Minisign => std::env::var(MINISIGN_PASSWORD_ENV).ok(),
+        SigningTool::Cosign => None,
+    };
+
+    let mut child = Command::new(program)
+        .args(&args)
+        .stdin(if password.is_some() {
+            Stdio::piped()
+        } else {
+            Stdio::null()
+        })
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound => {
+                anyhow::anyhow!("`{program}` isn't installed, or not on PATH")
+            }
+            _ => anyhow::Error::new(e).context(format!("failed to run `{program}`")),
+        })?;
+    if let (Some(password), Some(mut stdin)) = (password, child.stdin.take()) {
+        writeln!(stdin, "{password}").with_context(|| format!("failed to talk to `{program}`"))?;
+    }
+    let output = child
+        .wait_with_output()
+        .with_context(|| format!("failed to run `{program}`"))?;
+    if !output.status.success() {
+        bail!(
+            "`{program}` failed ({}): {}",
+            output.status,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+/// `key` as the tool should see it: key files relative to the
+/// repository root, `env://` and KMS URIs as they are.
+fn resolve_key(repo_root: &Path, key: &str) -> OsString {
+    if key.contains("://") {
+        key.into()
+    } else {
+        repo_root.join(key).into_os_string()
+    }
+}
+
+/// The program and arguments that sign `file` into `signature`.
+fn command(
+    tool: SigningTool,
+    key: Option<&std::ffi::OsStr>,
+    file: &Path,
+    signature: &Path,
+) -> (&'static str, Vec<OsString>) {
+    let mut args: Vec<OsString> = Vec::new();
+    match tool {
+        SigningTool::Minisign => {
+            args.extend(["-S".into(), "-s".into()]);
+            args.extend(key.map(OsString::from));
+            args.extend(["-x".into(), signature.into(), "-m".into(), file.into()]);
+        }
+        SigningTool::Cosign => {
+            args.extend(["sign-blob".into(), "--yes".into()]);
+            match key {
+                Some(key) => {
+                    args.extend(["--key".into(), key.into()]);
+                    args.extend(["--output-signature".into(), signature.into()]);
+                }
+                None => args.extend(["--bundle".into(), signature.into()]),
+            }
+            args.push(file.into());
+        }
+    }
+    (tool.as_str(), args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(tool: SigningTool, key: Option<&str>) -> SigningConfiguration {
+        SigningConfiguration {
+            tool,
+            key: key.map(str::to_string),
+            tags: false,
+        }
+    }
+
+    fn args(tool: SigningTool, key: Option<&str>) -> Vec<String> {
+        let key = key.map(|k| resolve_key(Path::new("/repo"), k));
+        let (program, args) = command(
+            tool,
+            key.as_deref(),
+            Path::new("/dist/app.tar.gz"),
+            Path::new("/out/app.tar.gz.sig"),
+        );
+        std::iter::once(program.to_string())
+            .chain(args.iter().map(|a| a.to_string_lossy().into_owned()))
+            .collect()
+    }
+
+    #[test]
+    fn checksums_read_like_sha256sum() {
+        let artifact = |name: &str, sha256: &str| Artifact {
+            name: name.into(),
+            size: 1,
+            sha256: sha256.into(),
+        };
+        assert_eq!(
+            checksums(&[artifact("a.tar.gz", "aa"), artifact("b.zip", "bb")]),
+            "aa  a.tar.gz\nbb  b.zip\n"
+        );
+    }
+
+    #[test]
+    fn signatures_are_named_after_the_tool() {
+        let minisign = config(SigningTool::Minisign, Some("release.key"));
+        assert_eq!(signature_name(&minisign, CHECKSUMS), "SHA256SUMS.minisig");
+        let cosign = config(SigningTool::Cosign, Some("cosign.key"));
+        assert_eq!(signature_name(&cosign, "app.zip"), "app.zip.sig");
+        let keyless = config(SigningTool::Cosign, None);
+        assert_eq!(signature_name(&keyless, "app.zip"), "app.zip.sigstore.json");
+    }
+
+    #[test]
+    fn minisign_needs_a_key() {
+        assert!(validate(&config(SigningTool::Minisign, None)).is_err());
+        assert!(validate(&config(SigningTool::Cosign, Some(""))).is_err());
+        assert!(validate(&config(SigningTool::Cosign, None)).is_ok());
+    }
+
+    #[test]
+    fn commands_sign_into_the_signature_file() {
+        assert_eq!(
+            args(SigningTool::Minisign, Some("keys/release.key")),
+            [
+                "minisign",
+                "-S",
+                "-s",
+                "/repo/keys/release.key",
+                "-x",
+                "/out/app.tar.gz.sig",
+                "-m",
+                "/dist/app.tar.gz"
+            ]
+        );
+        assert_eq!(
+            args(SigningTool::Cosign, Some("awskms:///alias/release")),
+            [
+                "cosign",
+                "sign-blob",
+                "--yes",
+                "--key",
+                "awskms:///alias/release",
+                "--output-signature",
+                "/out/app.tar.gz.sig",
+                "/dist/app.tar.gz"
+            ]
+        );
+        assert_eq!(
+            args(SigningTool::Cosign, None),
+            [
+                "cosign",
+                "sign-blob",
+                "--yes",
+                "--bundle",
+                "/out/app.tar.gz.sig",
+                "/dist/app.tar.gz"
+            ]
+        );
+    }
+}
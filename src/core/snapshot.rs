@@ -0,0 +1,200 @@
+//! Snapshot releases: `belaf release --snapshot`.
+//!
+//! A snapshot is a prerelease of a unit's next version, built from HEAD
+//! for nightly pipelines. Its version is the one the unit's commits since
+//! its last release call for, at least a patch above the current one,
+//! marked with a `<label>.<date>` prerelease and the commit as build
+//! metadata: `1.3.0-nightly.20250601+4f1c2e9`. PEP 440 has neither, so
+//! Python units get `1.3.0.dev20250601+4f1c2e9`. Units without commits
+//! since their last release get no snapshot.
+//!
+//! Snapshots write no changelog, manifest or pull request. Their versions
+//! are committed on top of HEAD without moving a branch. They are
+//! published where the registry has a channel for prereleases (npm's
+//! `next` dist-tag; see [`Channel`]) and, when tagged, as GitHub
+//! prereleases. Snapshot tags live under [`TAG_NAMESPACE`], so they are
+//! never taken for a unit's last release.
+
+use anyhow::{bail, Context};
+use time::Date;
+
+use crate::core::{
+    bump::{self, BumpConfig, BumpRecommendation},
+    errors::Result,
+    resolved_release_unit::ReleaseUnitId,
+    session::AppSession,
+    version::Version,
+    wire::known::Ecosystem,
+};
+
+/// The prerelease label of snapshot versions unless `--label` says
+/// otherwise.
+pub const DEFAULT_LABEL: &str = "nightly";
+
+/// The npm dist-tag snapshots are published under, so `npm install`
+/// keeps resolving `latest` to the last release.
+pub const NPM_DIST_TAG: &str = "next";
+
+/// Prefix of snapshot tags, in front of the release tag the version
+/// would have: `snapshot/my-crate-v1.3.0-nightly.20250601+4f1c2e9`.
+pub const TAG_NAMESPACE: &str = "snapshot/";
+
+/// The snapshot tag for a release tagged `release_tag`.
+pub fn tag_name(release_tag: &str) -> String {
+    format!("{TAG_NAMESPACE}{release_tag}")
+}
+
+/// The release tag inside the snapshot tag `tag`; `None` if `tag` isn't
+/// one.
+pub fn release_tag(tag: &str) -> Option<&str> {
+    tag.strip_prefix(TAG_NAMESPACE)
+}
+
+/// A unit's snapshot, as planned by [`plan`].
+#[derive(Clone, Debug)]
+pub struct Snapshot {
+    pub ident: ReleaseUnitId,
+    pub name: String,
+    pub ecosystem: Ecosystem,
+    pub previous_version: String,
+    pub version: Version,
+}
+
+/// Where a snapshot is published.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Channel {
+    /// `npm publish --tag next`.
+    Npm,
+    /// Not published, for the reason given.
+    Unsupported(&'static str),
+}
+
+impl Channel {
+    pub fn of(ecosystem: &str) -> Self {
+        match ecosystem {
+            "npm" => Self::Npm,
+            "cargo" => Self::Unsupported("crates.io has no prerelease channel"),
+            _ => Self::Unsupported("no prerelease channel is supported for this ecosystem"),
+        }
+    }
+}
+
+/// The snapshot of every unit with commits since its last release, in
+/// dependency order, labelled `label` and stamped with `date` and the
+/// commit `sha`.
+pub fn plan(sess: &AppSession, label: &str, date: Date, sha: &str) -> Result<Vec<Snapshot>> {
+    let histories = sess
+        .analyze_histories()
+        .context("failed to analyze project histories")?;
+    let bump_config = BumpConfig::from_user_config(&sess.bump_config);
+
+    let mut snapshots = Vec::new();
+    for ident in sess.graph().toposorted() {
+        let unit = sess.graph().lookup(ident);
+        let history = histories.lookup(ident);
+        if history.n_commits() == 0 {
+            continue;
+        }
+        let commits = history
+            .commits()
+            .into_iter()
+            .map(|cid| sess.repo.get_commit_details(*cid))
+            .collect::<Result<Vec<_>>>()?;
+        let current = unit.version.to_string();
+        let bump = bump::analyze_commits(&commits)
+            .with_context(|| {
+                format!(
+                    "failed to analyze commit messages for {}",
+                    unit.user_facing_name
+                )
+            })?
+            .recommendation
+            .apply_config(&bump_config, Some(&current))
+            .merge(BumpRecommendation::Patch);
+        let next = unit.version.bumped(bump.as_str())?;
+        let qnames = unit.qualified_names();
+        snapshots.push(Snapshot {
+            ident,
+            name: unit.user_facing_name.clone(),
+            ecosystem: Ecosystem::classify(qnames.get(1).map_or("cargo", String::as_str)),
+            previous_version: current,
+            version: version(&next, label, date, sha)
+                .with_context(|| format!("no snapshot version for {}", unit.user_facing_name))?,
+        });
+    }
+    Ok(snapshots)
+}
+
+/// `next` as a snapshot: see the module docs.
+pub fn version(next: &Version, label: &str, date: Date, sha: &str) -> Result<Version> {
+    let stamp = format!(
+        "{:04}{:02}{:02}",
+        date.year(),
+        u8::from(date.month()),
+        date.day()
+    );
+    let mut snapshot = next.clone();
+    match &mut snapshot {
+        Version::Semver(v) => {
+            v.pre = semver::Prerelease::new(&format!("{label}.{stamp}"))
+                .with_context(|| format!("`{label}` is not a valid prerelease label"))?;
+            v.build = semver::BuildMetadata::new(sha)?;
+        }
+        Version::Pep440(v) => {
+            v.pre_release = None;
+            v.post_release = None;
+            v.dev_release = Some(stamp.parse()?);
+            v.local_identifier = Some(sha.to_owned());
+        }
+        Version::DotNet(_) => bail!("{next} is a .NET version, which has no prerelease form"),
+    }
+    Ok(snapshot)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::macros::date;
+
+    #[test]
+    fn semver_snapshots_carry_the_label_date_and_commit() {
+        let next = Version::Semver("1.3.0".parse().unwrap());
+        let v = version(&next, "nightly", date!(2025 - 06 - 01), "4f1c2e9").unwrap();
+        assert_eq!(v.to_string(), "1.3.0-nightly.20250601+4f1c2e9");
+        assert!(v > Version::Semver("1.2.9".parse().unwrap()));
+        assert!(v < next);
+    }
+
+    #[test]
+    fn pep440_snapshots_are_dev_releases() {
+        let next = Version::Pep440("1.3.0".parse().unwrap());
+        let v = version(&next, "nightly", date!(2025 - 06 - 01), "4f1c2e9").unwrap();
+        assert_eq!(v.to_string(), "1.3.0.dev20250601+4f1c2e9");
+    }
+
+    #[test]
+    fn invalid_labels_and_dotnet_versions_are_refused() {
+        let next = Version::Semver("1.3.0".parse().unwrap());
+        assert!(version(&next, "night ly", date!(2025 - 06 - 01), "4f1c2e9").is_err());
+        let dotnet = Version::DotNet("1.3.0.0".parse().unwrap());
+        assert!(version(&dotnet, "nightly", date!(2025 - 06 - 01), "4f1c2e9").is_err());
+    }
+
+    #[test]
+    fn snapshot_tags_are_namespaced() {
+        let tag = tag_name("my-crate-v1.3.0-nightly.20250601+4f1c2e9");
+        assert_eq!(tag, "snapshot/my-crate-v1.3.0-nightly.20250601+4f1c2e9");
+        assert_eq!(
+            release_tag(&tag),
+            Some("my-crate-v1.3.0-nightly.20250601+4f1c2e9")
+        );
+        assert_eq!(release_tag("my-crate-v1.3.0"), None);
+    }
+
+    #[test]
+    fn only_npm_has_a_prerelease_channel() {
+        assert_eq!(Channel::of("npm"), Channel::Npm);
+        assert!(matches!(Channel::of("cargo"), Channel::Unsupported(_)));
+        assert!(matches!(Channel::of("pypa"), Channel::Unsupported(_)));
+    }
+}
@@ -0,0 +1,179 @@
+//! `[ci.status_gates]` — conditions under which `belaf status --ci` fails.
+//!
+//! `belaf status --ci` normally reports and exits 0. A repository that
+//! wants a merge gate on release hygiene lists conditions here: a
+//! breaking change left unreleased for too long, any change left
+//! unreleased for too long, or a changelog that has fallen behind the
+//! released version. When one holds, the JSON output lists it under
+//! `gates` and the command exits 4 (precondition). Every gate is off
+//! unless configured.
+
+use serde::Serialize;
+
+use crate::core::{changelog::Commit, config::syntax::StatusGatesConfiguration};
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// What `status` knows about one release unit.
+pub struct UnitState<'a> {
+    pub name: &'a str,
+    /// The unit's commits since its last release.
+    pub commits: &'a [Commit],
+    /// The version of the unit's last release, if it has one.
+    pub released_version: Option<&'a str>,
+    /// The unit's changelog, if the file exists.
+    pub changelog: Option<&'a str>,
+}
+
+/// A gate a unit fails.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct GateFailure {
+    pub unit: String,
+    /// The `[ci.status_gates]` key that failed.
+    pub gate: &'static str,
+    pub detail: String,
+}
+
+/// The gates `unit` fails as of `now`, in Unix seconds.
+pub fn check(gates: &StatusGatesConfiguration, unit: &UnitState<'_>, now: i64) -> Vec<GateFailure> {
+    let mut failures = Vec::new();
+    let mut fail = |gate, detail| {
+        failures.push(GateFailure {
+            unit: unit.name.to_owned(),
+            gate,
+            detail,
+        })
+    };
+
+    if let Some(max) = gates.max_breaking_age_days {
+        let breaking = unit.commits.iter().filter(|c| is_breaking(c));
+        if let Some(days) = oldest_age_days(breaking, now).filter(|days| *days > max) {
+            fail(
+                "max_breaking_age_days",
+                format!("an unreleased breaking change is {days} days old (limit {max})"),
+            );
+        }
+    }
+
+    if let Some(max) = gates.max_unreleased_age_days {
+        if let Some(days) = oldest_age_days(unit.commits.iter(), now).filter(|days| *days > max) {
+            fail(
+                "max_unreleased_age_days",
+                format!("the oldest unreleased commit is {days} days old (limit {max})"),
+            );
+        }
+    }
+
+    if gates.changelog_drift {
+        if let (Some(version), Some(changelog)) = (unit.released_version, unit.changelog) {
+            if !has_entry_for(changelog, version) {
+                fail(
+                    "changelog_drift",
+                    format!("the changelog has no entry for the released version {version}"),
+                );
+            }
+        }
+    }
+
+    failures
+}
+
+fn is_breaking(commit: &Commit) -> bool {
+    git_conventional::Commit::parse(&commit.message).is_ok_and(|conv| conv.breaking())
+}
+
+/// Whole days since the oldest of `commits` landed.
+fn oldest_age_days<'a>(commits: impl Iterator<Item = &'a Commit>, now: i64) -> Option<u64> {
+    let oldest = commits.map(|c| c.committer.timestamp).min()?;
+    Some(u64::try_from((now - oldest) / SECONDS_PER_DAY).unwrap_or(0))
+}
+
+/// Whether a heading of `changelog` names `version`, as in
+/// `## [1.3.0] - 2026-01-02` or `## v1.3.0 (2026-01-02)`.
+pub(crate) fn has_entry_for(changelog: &str, version: &str) -> bool {
+    changelog
+        .lines()
+        .filter(|line| line.starts_with('#'))
+        .flat_map(|line| line.split(|c: char| c.is_whitespace() || "[]()".contains(c)))
+        .any(|token| token.strip_prefix('v').unwrap_or(token) == version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_800_000_000;
+
+    fn commit(message: &str, days_ago: i64) -> Commit {
+        let mut commit = Commit::new("0".repeat(40), message.to_owned());
+        commit.committer.timestamp = NOW - days_ago * SECONDS_PER_DAY;
+        commit
+    }
+
+    fn gates() -> StatusGatesConfiguration {
+        StatusGatesConfiguration {
+            max_breaking_age_days: Some(14),
+            max_unreleased_age_days: Some(60),
+            changelog_drift: true,
+        }
+    }
+
+    fn gate_names(unit: &UnitState<'_>) -> Vec<&'static str> {
+        check(&gates(), unit, NOW).iter().map(|f| f.gate).collect()
+    }
+
+    #[test]
+    fn old_unreleased_changes_fail_their_gates() {
+        let commits = [
+            commit("feat!: drop the v1 API", 20),
+            commit("fix: typo", 90),
+        ];
+        let unit = UnitState {
+            name: "api",
+            commits: &commits,
+            released_version: None,
+            changelog: None,
+        };
+        assert_eq!(
+            gate_names(&unit),
+            ["max_breaking_age_days", "max_unreleased_age_days"]
+        );
+        assert_eq!(
+            check(&gates(), &unit, NOW)[0].detail,
+            "an unreleased breaking change is 20 days old (limit 14)"
+        );
+
+        let commits = [commit("feat!: drop the v1 API", 3)];
+        let unit = UnitState {
+            commits: &commits,
+            ..unit
+        };
+        assert!(gate_names(&unit).is_empty());
+        assert!(check(
+            &StatusGatesConfiguration::default(),
+            &unit,
+            NOW + 400 * SECONDS_PER_DAY
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn changelogs_must_have_an_entry_for_the_released_version() {
+        let changelog = "# Changelog\n\n## [1.2.0] - 2026-01-02\n\n- fix\n\n## v1.1.0\n";
+        let unit = |version| UnitState {
+            name: "api",
+            commits: &[],
+            released_version: Some(version),
+            changelog: Some(changelog),
+        };
+        assert!(gate_names(&unit("1.2.0")).is_empty());
+        assert!(gate_names(&unit("1.1.0")).is_empty());
+        assert_eq!(gate_names(&unit("1.3.0")), ["changelog_drift"]);
+
+        let without_changelog = UnitState {
+            changelog: None,
+            ..unit("1.3.0")
+        };
+        assert!(gate_names(&without_changelog).is_empty());
+    }
+}
@@ -168,10 +168,12 @@ pub struct TagPatternInputs<'a> {
 /// `tag_format` (unit > group > ecosystem default). When
 /// `allow_bare_v_fallback` was set on the inputs, a secondary
 /// `v{version}` pattern is also tried — single-project cargo repos
-/// commonly tag plain `v1.2.3`.
+/// commonly tag plain `v1.2.3`. A renamed unit additionally recognises
+/// the tags of its former names (see [`TagMatcher::with_previous_name`]).
 #[derive(Debug, Clone)]
 pub struct TagMatcher {
     primary: Regex,
+    previous_names: Vec<Regex>,
     bare_v_fallback: Option<Regex>,
     project_name: String,
     template: String,
@@ -185,6 +187,13 @@ impl TagMatcher {
         if let Some(v) = capture_version(&self.primary, tag) {
             return Some(v);
         }
+        if let Some(v) = self
+            .previous_names
+            .iter()
+            .find_map(|re| capture_version(re, tag))
+        {
+            return Some(v);
+        }
         if let Some(re) = &self.bare_v_fallback {
             if let Some(v) = capture_version(re, tag) {
                 return Some(v);
@@ -193,6 +202,13 @@ impl TagMatcher {
         None
     }
 
+    /// Also accept tags matched by `previous` — the matcher built for a
+    /// name this project was released under before it was renamed.
+    pub fn with_previous_name(mut self, previous: TagMatcher) -> Self {
+        self.previous_names.push(previous.primary);
+        self
+    }
+
     pub fn project_name(&self) -> &str {
         &self.project_name
     }
@@ -213,8 +229,7 @@ fn capture_version(re: &Regex, tag: &str) -> Option<semver::Version> {
 /// Kept conservative — we feed the captured slice into
 /// [`semver::Version::parse`], so this only needs to be permissive
 /// enough to not pre-filter valid versions.
-const VERSION_CAPTURE: &str =
-    r"(?P<version>\d+\.\d+\.\d+(?:-[0-9A-Za-z\-]+(?:\.[0-9A-Za-z\-]+)*)?(?:\+[0-9A-Za-z\-]+(?:\.[0-9A-Za-z\-]+)*)?)";
+const VERSION_CAPTURE: &str = r"(?P<version>\d+\.\d+\.\d+(?:-[0-9A-Za-z\-]+(?:\.[0-9A-Za-z\-]+)*)?(?:\+[0-9A-Za-z\-]+(?:\.[0-9A-Za-z\-]+)*)?)";
 
 /// Compile a [`TagMatcher`] from the project's effective tag template.
 /// Mirrors [`format_tag`]'s variable-substitution + whitelist rules but
@@ -282,9 +297,8 @@ pub fn build_tag_matcher(inputs: &TagPatternInputs<'_>) -> Result<TagMatcher> {
         ));
     }
 
-    let primary = Regex::new(&pattern).map_err(|e| {
-        anyhow!("failed to compile tag-format regex from `{template}`: {e}")
-    })?;
+    let primary = Regex::new(&pattern)
+        .map_err(|e| anyhow!("failed to compile tag-format regex from `{template}`: {e}"))?;
 
     let bare_v_fallback = if inputs.allow_bare_v_fallback {
         // Bare `v{version}` — the cargo single-project convention.
@@ -297,6 +311,7 @@ pub fn build_tag_matcher(inputs: &TagPatternInputs<'_>) -> Result<TagMatcher> {
 
     Ok(TagMatcher {
         primary,
+        previous_names: Vec::new(),
         bare_v_fallback,
         project_name: inputs.project_name.to_string(),
         template: template.to_string(),
@@ -587,4 +602,23 @@ mod tests {
             .unwrap();
         assert_eq!(highest, semver::Version::new(0, 7, 0));
     }
+
+    #[test]
+    fn matcher_accepts_tags_of_previous_names() {
+        let mut old = npm_pattern_inputs();
+        old.project_name = "@clikd/site";
+        let m = build_tag_matcher(&npm_pattern_inputs())
+            .unwrap()
+            .with_previous_name(build_tag_matcher(&old).unwrap());
+        assert_eq!(
+            m.match_version("@clikd/site@v0.5.0"),
+            Some(semver::Version::new(0, 5, 0))
+        );
+        assert_eq!(
+            m.match_version("@clikd/landing@v0.6.0"),
+            Some(semver::Version::new(0, 6, 0))
+        );
+        assert_eq!(m.match_version("@clikd/docs@v1.0.0"), None);
+        assert_eq!(m.project_name(), "@clikd/landing");
+    }
 }
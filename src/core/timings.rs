@@ -0,0 +1,103 @@
+//! Wall-clock durations of the analysis stages, for `belaf debug
+//! timings`.
+//!
+//! Recording is off unless [`enable`] was called, so the guards that
+//! [`stage`] hands out cost one lock check in ordinary runs. A stage
+//! entered several times (changelog rendering runs once per unit) is
+//! reported once, with its durations summed.
+
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+
+static STAGES: Mutex<Option<Vec<StageTiming>>> = Mutex::new(None);
+
+/// Accumulated time spent in one stage.
+#[derive(Clone, Debug, Serialize)]
+pub struct StageTiming {
+    pub stage: &'static str,
+    #[serde(rename = "elapsed_ms", serialize_with = "as_millis")]
+    pub elapsed: Duration,
+    /// How many times the stage was entered.
+    pub calls: u32,
+}
+
+fn as_millis<S: serde::Serializer>(d: &Duration, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_f64(d.as_secs_f64() * 1000.0)
+}
+
+/// Start recording stage timings for this process.
+pub fn enable() {
+    if let Ok(mut slot) = STAGES.lock() {
+        slot.get_or_insert_with(Vec::new);
+    }
+}
+
+/// Time the enclosing scope as `name`; the duration is recorded when
+/// the returned guard is dropped.
+#[must_use = "the stage ends when the guard is dropped"]
+pub fn stage(name: &'static str) -> StageGuard {
+    StageGuard {
+        name,
+        start: Instant::now(),
+    }
+}
+
+/// Stages recorded so far, in the order they were first entered.
+pub fn recorded() -> Vec<StageTiming> {
+    STAGES
+        .lock()
+        .ok()
+        .and_then(|slot| slot.clone())
+        .unwrap_or_default()
+}
+
+pub struct StageGuard {
+    name: &'static str,
+    start: Instant,
+}
+
+impl Drop for StageGuard {
+    fn drop(&mut self) {
+        let Ok(mut slot) = STAGES.lock() else {
+            return;
+        };
+        let Some(stages) = slot.as_mut() else {
+            return;
+        };
+        let elapsed = self.start.elapsed();
+        match stages.iter_mut().find(|s| s.stage == self.name) {
+            Some(s) => {
+                s.elapsed += elapsed;
+                s.calls += 1;
+            }
+            None => stages.push(StageTiming {
+                stage: self.name,
+                elapsed,
+                calls: 1,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeated_stages_are_summed() {
+        enable();
+        for _ in 0..3 {
+            let _guard = stage("test: repeated");
+        }
+        let recorded = recorded();
+        let repeated = recorded
+            .iter()
+            .find(|s| s.stage == "test: repeated")
+            .expect("stage recorded");
+        assert_eq!(repeated.calls, 3);
+    }
+}
@@ -3,4 +3,5 @@ pub mod glyphs;
 pub mod keymap;
 pub mod markdown;
 pub mod release_unit_view;
+pub mod text;
 pub mod utils;
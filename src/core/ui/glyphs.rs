@@ -114,6 +114,24 @@ pub fn ecosystem(eco: &str) -> &'static str {
     }
 }
 
+/// Shape set next to a `MAJOR` / `MINOR` / `PATCH` bump label, so the
+/// level reads without telling red, yellow and green apart. Empty for
+/// any other label.
+pub fn bump(level: &str) -> &'static str {
+    match (mode(), level) {
+        (IconMode::Unicode, "MAJOR") => "\u{25B2}", // ▲ Up Triangle
+        (IconMode::Unicode, "MINOR") => "\u{25C6}", // ◆ Black Diamond
+        (IconMode::Unicode, "PATCH") => "\u{25CF}", // ● Black Circle
+        (IconMode::Nerd, "MAJOR") => "\u{f0737}",   // md-chevron_triple_up
+        (IconMode::Nerd, "MINOR") => "\u{f0736}",   // md-chevron_double_up
+        (IconMode::Nerd, "PATCH") => "\u{f0143}",   // md-chevron_up
+        (IconMode::Ascii, "MAJOR") => "!!!",
+        (IconMode::Ascii, "MINOR") => "!!",
+        (IconMode::Ascii, "PATCH") => "!",
+        _ => "",
+    }
+}
+
 /// Header banner glyph (top-of-screen "Review and toggle …").
 pub fn header_clipboard() -> &'static str {
     match mode() {
@@ -141,5 +159,9 @@ mod tests {
         assert!(!checkbox(false).is_empty());
         assert!(!locked().is_empty());
         assert!(!header_clipboard().is_empty());
+        for level in ["MAJOR", "MINOR", "PATCH"] {
+            assert!(!bump(level).is_empty());
+        }
+        assert!(bump("").is_empty());
     }
 }
@@ -29,12 +29,13 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::{List, ListItem};
 use ratatui::Frame;
 
-use super::glyphs;
+use super::{glyphs, text};
 use crate::core::git::repository::RepoPathBuf;
 use crate::core::release_unit::detector::{
     BundleKind, DetectedShape, DetectionReport, ExtKind, HexagonalPrimary, HintKind,
     JvmVersionSource,
 };
+use crate::utils::theme;
 
 // ---------------------------------------------------------------------------
 // Row data
@@ -130,6 +131,7 @@ pub enum HintAnnotation {
     NpmWorkspace,
     SingleProject,
     NestedMonorepo,
+    Submodule,
 }
 
 impl HintAnnotation {
@@ -139,6 +141,7 @@ impl HintAnnotation {
             Self::NpmWorkspace => "npm-workspace",
             Self::SingleProject => "single-project",
             Self::NestedMonorepo => "nested-monorepo",
+            Self::Submodule => "submodule",
         }
     }
 
@@ -148,6 +151,7 @@ impl HintAnnotation {
             HintKind::NpmWorkspace => Self::NpmWorkspace,
             HintKind::SingleProject { .. } => Self::SingleProject,
             HintKind::NestedMonorepo => Self::NestedMonorepo,
+            HintKind::Submodule => Self::Submodule,
         }
     }
 }
@@ -553,7 +557,7 @@ fn render_unit_row_line(
     overlay: Option<&PrepareOverlay>,
 ) -> (Line<'static>, Style) {
     let bg = if is_current {
-        Style::default().bg(Color::Rgb(40, 40, 50))
+        theme::row_highlight()
     } else {
         Style::default()
     };
@@ -573,8 +577,7 @@ fn render_unit_row_line(
     };
 
     let indicator = checkbox_or_lock(mode, row.selected, false);
-    let pad = label_width.saturating_sub(row.name.chars().count());
-    let padded = format!("{}{}", row.name, " ".repeat(pad));
+    let padded = text::fit(&row.name, label_width);
     let commit_count = overlay.and_then(|o| o.commits.get(&row.backref).copied());
     let secondary = match (mode, commit_count) {
         (RenderMode::Prepare, Some(n)) => format!("({} commits)", n),
@@ -615,7 +618,7 @@ fn render_unit_row_line(
             if !text.is_empty() {
                 spans.push(Span::styled("  ".to_string(), Style::default()));
                 spans.push(Span::styled(
-                    format!("→ {}", text),
+                    format!("→ {} {}", glyphs::bump(text), text),
                     Style::default().fg(color),
                 ));
             }
@@ -717,7 +720,7 @@ impl GroupRowDisplay {
             ),
             Span::styled(
                 if !suggestion_text.is_empty() {
-                    format!("  → {}", suggestion_text)
+                    format!("  → {} {}", glyphs::bump(suggestion_text), suggestion_text)
                 } else {
                     String::new()
                 },
@@ -813,12 +816,12 @@ impl ReleaseUnitView {
     fn label_width(&self) -> usize {
         self.bundles
             .iter()
-            .map(|b| b.label.chars().count())
-            .chain(self.units.iter().map(|u| u.name.chars().count()))
+            .map(|b| text::display_width(&b.label))
+            .chain(self.units.iter().map(|u| text::display_width(&u.name)))
             .chain(
                 self.externally_managed
                     .iter()
-                    .map(|e| e.label.chars().count()),
+                    .map(|e| text::display_width(&e.label)),
             )
             .max()
             .unwrap_or(0)
@@ -884,7 +887,7 @@ impl ReleaseUnitView {
         overlay: Option<&PrepareOverlay>,
     ) -> ListItem<'_> {
         let bg = if is_current {
-            Style::default().bg(Color::Rgb(40, 40, 50))
+            theme::row_highlight()
         } else {
             Style::default()
         };
@@ -915,8 +918,7 @@ impl ReleaseUnitView {
             RowIdx::Bundle(i) => {
                 let b = &self.bundles[i];
                 let indicator = checkbox_or_lock(mode, b.selected, false);
-                let pad = label_width.saturating_sub(b.label.chars().count());
-                let padded = format!("{}{}", b.label, " ".repeat(pad));
+                let padded = text::fit(&b.label, label_width);
                 ListItem::new(Line::from(vec![
                     Span::styled("    ", Style::default()),
                     Span::styled(
@@ -943,7 +945,7 @@ impl ReleaseUnitView {
                 let group = &self.groups[i];
                 let lines = group.render_lines(is_current);
                 let style = if is_current {
-                    Style::default().bg(Color::Rgb(40, 40, 50))
+                    theme::row_highlight()
                 } else {
                     Style::default()
                 };
@@ -952,8 +954,7 @@ impl ReleaseUnitView {
             RowIdx::Ext(i) => {
                 let e = &self.externally_managed[i];
                 let indicator = checkbox_or_lock(mode, true, true);
-                let pad = label_width.saturating_sub(e.label.chars().count());
-                let padded = format!("{}{}", e.label, " ".repeat(pad));
+                let padded = text::fit(&e.label, label_width);
                 ListItem::new(Line::from(vec![
                     Span::styled("    ", Style::default()),
                     Span::styled(
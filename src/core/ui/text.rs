@@ -0,0 +1,72 @@
+//! Shortening and padding text for terminal columns.
+//!
+//! Commit subjects and unit names carry emoji and CJK text, so byte
+//! lengths and `char` counts both lie about how much room a string takes:
+//! slicing at a byte offset can panic mid-codepoint, and a wide glyph
+//! occupies two cells. Everything here counts terminal cells and only
+//! cuts between grapheme clusters.
+
+use std::borrow::Cow;
+
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+const ELLIPSIS: &str = "…";
+
+/// Number of terminal cells `s` occupies.
+pub fn display_width(s: &str) -> usize {
+    UnicodeWidthStr::width(s)
+}
+
+/// `s` shortened to at most `max_width` cells, ending in `…` when cut.
+pub fn truncate(s: &str, max_width: usize) -> Cow<'_, str> {
+    if display_width(s) <= max_width {
+        return Cow::Borrowed(s);
+    }
+    if max_width == 0 {
+        return Cow::Borrowed("");
+    }
+
+    let budget = max_width - display_width(ELLIPSIS);
+    let mut used = 0;
+    let mut out = String::new();
+    for grapheme in s.graphemes(true) {
+        let width = display_width(grapheme);
+        if used + width > budget {
+            break;
+        }
+        used += width;
+        out.push_str(grapheme);
+    }
+    out.push_str(ELLIPSIS);
+    Cow::Owned(out)
+}
+
+/// `s` truncated or right-padded with spaces to exactly `width` cells.
+pub fn fit(s: &str, width: usize) -> String {
+    let shortened = truncate(s, width);
+    let pad = width.saturating_sub(display_width(&shortened));
+    format!("{}{}", shortened, " ".repeat(pad))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncates_on_grapheme_boundaries() {
+        assert_eq!(truncate("feat: add parser", 50), "feat: add parser");
+        assert_eq!(truncate("feat: add parser", 10), "feat: add…");
+        assert_eq!(truncate("✨ feat: 日本語のコミット", 12), "✨ feat: 日…");
+        // A family emoji is one grapheme made of several codepoints.
+        assert_eq!(truncate("👨‍👩‍👧 fix", 3), "👨‍👩‍👧…");
+        assert_eq!(truncate("abc", 0), "");
+    }
+
+    #[test]
+    fn fits_to_exact_cell_width() {
+        assert_eq!(fit("core", 6), "core  ");
+        assert_eq!(fit("日本語パッケージ", 7), "日本語…");
+        assert_eq!(fit("日本語パッケージ", 8), "日本語… ");
+    }
+}
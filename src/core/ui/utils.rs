@@ -3,7 +3,9 @@ use std::io::IsTerminal;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 
 pub fn is_interactive_terminal() -> bool {
-    std::io::stdout().is_terminal() && std::io::stdin().is_terminal()
+    !crate::core::output::is_capturing()
+        && std::io::stdout().is_terminal()
+        && std::io::stdin().is_terminal()
 }
 
 pub fn should_use_tui<T>(ci_mode: bool, format: &Option<T>) -> bool {
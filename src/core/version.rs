@@ -6,8 +6,8 @@
 use anyhow::bail;
 use std::fmt::{Display, Formatter};
 use thiserror::Error as ThisError;
-use time::OffsetDateTime;
 
+use crate::core::deterministic;
 use crate::core::errors::Result;
 
 const SECONDS_PER_DAY: i64 = 86400;
@@ -112,6 +112,18 @@ impl Version {
         }
     }
 
+    /// This version after `bump`, a bump scheme as accepted by
+    /// [`Self::parse_bump_scheme`], or unchanged for `"no bump"`. Previews
+    /// and the versions prepare writes both come from here, so they can't
+    /// disagree.
+    pub fn bumped(&self, bump: &str) -> Result<Version> {
+        let mut next = self.clone();
+        if bump != "no bump" {
+            self.parse_bump_scheme(bump)?.apply(&mut next)?;
+        }
+        Ok(next)
+    }
+
     pub fn as_pep440_tuple_literal(&self) -> Result<String> {
         if let Version::Pep440(v) = self {
             v.as_tuple_literal()
@@ -165,7 +177,7 @@ impl VersionBumpScheme {
 
         #[expect(clippy::unnecessary_wraps)]
         fn apply_dev_datecode(version: &mut Version) -> Result<()> {
-            let now = OffsetDateTime::now_utc();
+            let now = deterministic::now_utc();
 
             match version {
                 Version::Semver(v) => {
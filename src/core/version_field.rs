@@ -4,6 +4,9 @@
 //!
 //! - `read(path: &Path) -> Result<String>` — extract the current
 //!   version string from the file, idempotent
+//! - `read_str(path: &Path, content: &str) -> Result<String>` — the
+//!   same on `content`, for files that aren't on disk, e.g. ones read
+//!   from a commit; `path` only names the file in errors
 //! - `write(path: &Path, new_version: &str) -> Result<()>` — patch
 //!   the file in place to point at `new_version`, preserving
 //!   formatting (comments, ordering, indentation) wherever feasible
@@ -80,6 +83,22 @@ pub fn read(spec: &VersionFieldSpec, path: &Path) -> Result<String> {
     }
 }
 
+/// Read the current version string from `content`, the text of
+/// `path`, according to `spec`.
+pub fn read_str(spec: &VersionFieldSpec, path: &Path, content: &str) -> Result<String> {
+    match spec {
+        VersionFieldSpec::CargoToml => cargo_toml::read_str(path, content),
+        VersionFieldSpec::NpmPackageJson => npm_json::read_str(path, content),
+        VersionFieldSpec::TauriConfJson => tauri_conf::read_str(path, content),
+        VersionFieldSpec::GradleProperties => gradle_properties::read_str(path, content),
+        VersionFieldSpec::Pep621 => pyproject::read_str(path, content),
+        VersionFieldSpec::GenericRegex {
+            pattern,
+            replace: _,
+        } => generic_regex::read_str(path, content, pattern),
+    }
+}
+
 /// Write `new_version` to `path` according to `spec`. Idempotent —
 /// if the file is already at `new_version`, this is a no-op (no
 /// disk write).
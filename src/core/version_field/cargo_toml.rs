@@ -16,6 +16,10 @@ fn parse_doc(path: &Path) -> Result<DocumentMut> {
         path: path.display().to_string(),
         source: e,
     })?;
+    parse_str(path, &content)
+}
+
+fn parse_str(path: &Path, content: &str) -> Result<DocumentMut> {
     content
         .parse::<DocumentMut>()
         .map_err(|e| VersionFieldError::ParseError {
@@ -29,7 +33,16 @@ fn parse_doc(path: &Path) -> Result<DocumentMut> {
 /// `[workspace.package].version`. If both are missing, returns
 /// `VersionFieldMissing`.
 pub fn read(path: &Path) -> Result<String> {
-    let doc = parse_doc(path)?;
+    let content = fs::read_to_string(path).map_err(|e| VersionFieldError::Io {
+        path: path.display().to_string(),
+        source: e,
+    })?;
+    read_str(path, &content)
+}
+
+/// [`read`] on `content`, the text of `path`.
+pub fn read_str(path: &Path, content: &str) -> Result<String> {
+    let doc = parse_str(path, content)?;
     if let Some(v) = doc
         .get("package")
         .and_then(|p| p.as_table())
@@ -23,9 +23,14 @@ pub fn read(path: &Path, pattern: &str) -> Result<String> {
         path: path.display().to_string(),
         source: e,
     })?;
+    read_str(path, &content, pattern)
+}
+
+/// [`read`] on `content`, the text of `path`.
+pub fn read_str(path: &Path, content: &str, pattern: &str) -> Result<String> {
     let re = compile(pattern)?;
     let caps = re
-        .captures(&content)
+        .captures(content)
         .ok_or_else(|| VersionFieldError::VersionFieldMissing {
             path: path.display().to_string(),
             looked_for: "regex pattern (custom)",
@@ -17,15 +17,18 @@ fn parse_json(path: &Path) -> Result<(String, Value)> {
         path: path.display().to_string(),
         source: e,
     })?;
-    let value: Value =
-        serde_json::from_str(&content).map_err(|e| VersionFieldError::ParseError {
-            path: path.display().to_string(),
-            kind: KIND,
-            reason: e.to_string(),
-        })?;
+    let value = parse_str(path, &content)?;
     Ok((content, value))
 }
 
+fn parse_str(path: &Path, content: &str) -> Result<Value> {
+    serde_json::from_str(content).map_err(|e| VersionFieldError::ParseError {
+        path: path.display().to_string(),
+        kind: KIND,
+        reason: e.to_string(),
+    })
+}
+
 /// Detects the leading indentation of the first non-trivial nested
 /// line in the original content. Falls back to 2 spaces.
 fn detect_indent(original: &str) -> String {
@@ -43,6 +46,15 @@ fn detect_indent(original: &str) -> String {
 
 pub fn read(path: &Path) -> Result<String> {
     let (_, value) = parse_json(path)?;
+    version_of(path, &value)
+}
+
+/// [`read`] on `content`, the text of `path`.
+pub fn read_str(path: &Path, content: &str) -> Result<String> {
+    version_of(path, &parse_str(path, content)?)
+}
+
+fn version_of(path: &Path, value: &Value) -> Result<String> {
     let v = value
         .get("version")
         .and_then(|v| v.as_str())
@@ -120,6 +132,19 @@ mod tests {
         assert_eq!(read(f.path()).unwrap(), "1.2.3");
     }
 
+    #[test]
+    fn reads_version_from_a_string() {
+        let path = Path::new("package.json");
+        assert_eq!(
+            read_str(path, r#"{"name":"x","version":"1.2.3"}"#).unwrap(),
+            "1.2.3"
+        );
+        assert!(matches!(
+            read_str(path, "{").unwrap_err(),
+            VersionFieldError::ParseError { .. }
+        ));
+    }
+
     #[test]
     fn read_missing_version_errors() {
         let f = write_temp(r#"{"name":"x"}"#);
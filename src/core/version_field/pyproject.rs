@@ -23,6 +23,10 @@ fn parse_doc(path: &Path) -> Result<DocumentMut> {
         path: path.display().to_string(),
         source: e,
     })?;
+    parse_str(path, &content)
+}
+
+fn parse_str(path: &Path, content: &str) -> Result<DocumentMut> {
     content
         .parse::<DocumentMut>()
         .map_err(|e| VersionFieldError::ParseError {
@@ -33,7 +37,16 @@ fn parse_doc(path: &Path) -> Result<DocumentMut> {
 }
 
 pub fn read(path: &Path) -> Result<String> {
-    let doc = parse_doc(path)?;
+    let content = fs::read_to_string(path).map_err(|e| VersionFieldError::Io {
+        path: path.display().to_string(),
+        source: e,
+    })?;
+    read_str(path, &content)
+}
+
+/// [`read`] on `content`, the text of `path`.
+pub fn read_str(path: &Path, content: &str) -> Result<String> {
+    let doc = parse_str(path, content)?;
     if let Some(v) = doc
         .get("project")
         .and_then(|p| p.as_table())
@@ -96,6 +109,15 @@ mod tests {
         assert_eq!(read(f.path()).unwrap(), "1.2.3");
     }
 
+    #[test]
+    fn reads_project_version_from_a_string() {
+        let path = Path::new("pkg/pyproject.toml");
+        let content = "[project]\nname = \"x\"\nversion = \"1.2.3\"\n";
+        assert_eq!(read_str(path, content).unwrap(), "1.2.3");
+        let err = read_str(path, "[project]\n").unwrap_err();
+        assert!(err.to_string().contains("pkg/pyproject.toml"), "{err}");
+    }
+
     #[test]
     fn read_missing_version_errors() {
         let f = write_temp("[project]\nname = \"x\"\n");
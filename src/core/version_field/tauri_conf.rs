@@ -27,9 +27,14 @@ pub fn read(path: &Path) -> Result<String> {
         path: path.display().to_string(),
         source: e,
     })?;
+    read_str(path, &content)
+}
+
+/// [`read`] on `content`, the text of `path`.
+pub fn read_str(path: &Path, content: &str) -> Result<String> {
     let re = version_re()?;
     let caps = re
-        .captures(&content)
+        .captures(content)
         .ok_or_else(|| VersionFieldError::VersionFieldMissing {
             path: path.display().to_string(),
             looked_for: r#""version": "..." (top-level)"#,
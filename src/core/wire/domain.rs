@@ -11,9 +11,11 @@
 //! it. This is where the discriminated-union classification happens
 //! (`Ecosystem::classify`, `BumpType::classify`).
 
+use std::num::NonZeroU64;
+
 use serde_json::{Map, Value};
-use time::OffsetDateTime;
-use uuid::Uuid;
+
+use crate::core::deterministic;
 
 use super::codegen::{self, BelafReleaseManifest, Release as WireRelease};
 use super::known::{BumpType, Ecosystem};
@@ -35,6 +37,10 @@ pub struct Manifest {
     pub base_branch: String,
     pub groups: Vec<Group>,
     pub releases: Vec<Release>,
+    /// Sign-off gate; `None` when the repository requires no approval.
+    pub approval: Option<Approval>,
+    /// Two-phase release state; `None` until `belaf release cut`.
+    pub publication: Option<Publication>,
     pub x: Map<String, Value>,
 }
 
@@ -42,17 +48,19 @@ impl Manifest {
     /// Build a fresh empty manifest. `manifest_id` is a UUID v7 string;
     /// `created_at` is the current UTC time as RFC 3339.
     pub fn new(base_branch: String, created_by: String) -> Self {
-        let now = OffsetDateTime::now_utc();
+        let now = deterministic::now_utc();
         let format = time::format_description::well_known::Rfc3339;
         let created_at = now.format(&format).unwrap_or_else(|_| now.to_string());
         Self {
             schema_version: SCHEMA_VERSION.to_string(),
-            manifest_id: Uuid::now_v7().to_string(),
+            manifest_id: deterministic::uuid_v7().to_string(),
             created_at,
             created_by,
             base_branch,
             groups: Vec::new(),
             releases: Vec::new(),
+            approval: None,
+            publication: None,
             x: Map::new(),
         }
     }
@@ -79,6 +87,12 @@ impl Manifest {
         let wire: BelafReleaseManifest = serde_json::from_str(json)?;
         Ok(wire.into())
     }
+
+    /// Like [`Manifest::from_json`], for a document that is already parsed.
+    pub fn from_value(value: Value) -> Result<Self, ManifestParseError> {
+        let wire: BelafReleaseManifest = serde_json::from_value(value)?;
+        Ok(wire.into())
+    }
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -98,6 +112,65 @@ pub struct Group {
     pub x: Map<String, Value>,
 }
 
+// ---------------------------------------------------------------------------
+// Approval
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone)]
+pub struct Approval {
+    /// Distinct sign-offs needed; at least 1.
+    pub required: u64,
+    /// GitHub login of whoever prepared the release, if known.
+    pub requested_by: Option<String>,
+    pub approvals: Vec<Signoff>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Signoff {
+    pub approver: String,
+    /// RFC 3339.
+    pub approved_at: String,
+}
+
+// ---------------------------------------------------------------------------
+// Publication
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PublicationState {
+    /// Tagged, with draft GitHub releases.
+    Cut,
+    /// The drafts were made public.
+    Published,
+}
+
+#[derive(Debug, Clone)]
+pub struct Publication {
+    pub state: PublicationState,
+    /// RFC 3339.
+    pub cut_at: String,
+    /// RFC 3339; `None` until published.
+    pub published_at: Option<String>,
+    pub releases: Vec<PublicationRelease>,
+}
+
+/// The GitHub release of one tag.
+#[derive(Debug, Clone)]
+pub struct PublicationRelease {
+    pub tag_name: String,
+    pub release_id: u64,
+    pub url: String,
+    pub artifacts: Vec<Artifact>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Artifact {
+    pub name: String,
+    pub size: u64,
+    /// Hex SHA-256.
+    pub sha256: String,
+}
+
 // ---------------------------------------------------------------------------
 // Release
 // ---------------------------------------------------------------------------
@@ -113,6 +186,11 @@ pub struct Release {
     pub tag_name: String,
     pub previous_tag: Option<String>,
     pub compare_url: Option<String>,
+    /// `<previous_tag>..<sha>` of the commit the release was prepared
+    /// from; just `<sha>` for a first release.
+    pub commit_range: Option<String>,
+    /// Full SHAs of this unit's commits in `commit_range`.
+    pub commits: Vec<String>,
     pub is_prerelease: bool,
     pub changelog: String,
     pub contributors: Vec<String>,
@@ -189,6 +267,8 @@ impl From<Manifest> for BelafReleaseManifest {
                 .expect("base_branch must be non-empty"),
             groups: m.groups.into_iter().map(Into::into).collect(),
             releases: m.releases.into_iter().map(Into::into).collect(),
+            approval: m.approval.map(Into::into),
+            publication: m.publication.map(Into::into),
             x: m.x,
         }
     }
@@ -204,6 +284,8 @@ impl From<BelafReleaseManifest> for Manifest {
             base_branch: wire.base_branch.into(),
             groups: wire.groups.into_iter().map(Into::into).collect(),
             releases: wire.releases.into_iter().map(Into::into).collect(),
+            approval: wire.approval.map(Into::into),
+            publication: wire.publication.map(Into::into),
             x: wire.x,
         }
     }
@@ -236,6 +318,122 @@ impl From<codegen::Group> for Group {
     }
 }
 
+impl From<Approval> for codegen::Approval {
+    fn from(a: Approval) -> Self {
+        codegen::Approval {
+            required: NonZeroU64::new(a.required).unwrap_or(NonZeroU64::MIN),
+            requested_by: a.requested_by,
+            approvals: a.approvals.into_iter().map(Into::into).collect(),
+            x: Map::new(),
+        }
+    }
+}
+
+impl From<codegen::Approval> for Approval {
+    fn from(a: codegen::Approval) -> Self {
+        Self {
+            required: a.required.get(),
+            requested_by: a.requested_by,
+            approvals: a.approvals.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<Signoff> for codegen::ApprovalSignoff {
+    fn from(s: Signoff) -> Self {
+        codegen::ApprovalSignoff {
+            approver: s.approver.parse().expect("approver must be non-empty"),
+            approved_at: s
+                .approved_at
+                .parse()
+                .expect("approved_at must be non-empty"),
+            x: Map::new(),
+        }
+    }
+}
+
+impl From<codegen::ApprovalSignoff> for Signoff {
+    fn from(s: codegen::ApprovalSignoff) -> Self {
+        Self {
+            approver: s.approver.into(),
+            approved_at: s.approved_at.into(),
+        }
+    }
+}
+
+impl From<Publication> for codegen::Publication {
+    fn from(p: Publication) -> Self {
+        codegen::Publication {
+            state: match p.state {
+                PublicationState::Cut => codegen::PublicationState::Cut,
+                PublicationState::Published => codegen::PublicationState::Published,
+            },
+            cut_at: p.cut_at.parse().expect("cut_at must be non-empty"),
+            published_at: p.published_at,
+            releases: p.releases.into_iter().map(Into::into).collect(),
+            x: Map::new(),
+        }
+    }
+}
+
+impl From<codegen::Publication> for Publication {
+    fn from(p: codegen::Publication) -> Self {
+        Self {
+            state: match p.state {
+                codegen::PublicationState::Cut => PublicationState::Cut,
+                codegen::PublicationState::Published => PublicationState::Published,
+            },
+            cut_at: p.cut_at.into(),
+            published_at: p.published_at,
+            releases: p.releases.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<PublicationRelease> for codegen::PublicationRelease {
+    fn from(r: PublicationRelease) -> Self {
+        codegen::PublicationRelease {
+            tag_name: r.tag_name.parse().expect("tag_name must be non-empty"),
+            release_id: NonZeroU64::new(r.release_id).unwrap_or(NonZeroU64::MIN),
+            url: r.url.parse().expect("url must be non-empty"),
+            artifacts: r.artifacts.into_iter().map(Into::into).collect(),
+            x: Map::new(),
+        }
+    }
+}
+
+impl From<codegen::PublicationRelease> for PublicationRelease {
+    fn from(r: codegen::PublicationRelease) -> Self {
+        Self {
+            tag_name: r.tag_name.into(),
+            release_id: r.release_id.get(),
+            url: r.url.into(),
+            artifacts: r.artifacts.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<Artifact> for codegen::PublicationArtifact {
+    fn from(a: Artifact) -> Self {
+        codegen::PublicationArtifact {
+            name: a.name.parse().expect("artifact name must be non-empty"),
+            size: a.size,
+            sha256: a.sha256.parse().expect("sha256 must be a hex digest"),
+            x: Map::new(),
+        }
+    }
+}
+
+impl From<codegen::PublicationArtifact> for Artifact {
+    fn from(a: codegen::PublicationArtifact) -> Self {
+        Self {
+            name: a.name.into(),
+            size: a.size,
+            sha256: a.sha256.into(),
+        }
+    }
+}
+
 impl From<Release> for WireRelease {
     fn from(r: Release) -> Self {
         WireRelease {
@@ -261,6 +459,12 @@ impl From<Release> for WireRelease {
             tag_name: r.tag_name.parse().expect("tag_name must be non-empty"),
             previous_tag: r.previous_tag,
             compare_url: r.compare_url,
+            commit_range: r.commit_range,
+            commits: r
+                .commits
+                .into_iter()
+                .map(|c| c.parse().expect("commit sha must be non-empty"))
+                .collect(),
             is_prerelease: r.is_prerelease,
             changelog: r.changelog,
             contributors: r.contributors,
@@ -318,6 +522,8 @@ impl From<WireRelease> for Release {
             tag_name: r.tag_name.into(),
             previous_tag: r.previous_tag,
             compare_url: r.compare_url,
+            commit_range: r.commit_range,
+            commits: r.commits.into_iter().map(|c| c.into()).collect(),
             is_prerelease: r.is_prerelease,
             changelog: r.changelog,
             contributors: r.contributors,
@@ -404,6 +610,8 @@ mod tests {
             tag_name: "@org/foo@v0.2.0".to_string(),
             previous_tag: Some("@org/foo@v0.1.0".to_string()),
             compare_url: None,
+            commit_range: None,
+            commits: Vec::new(),
             is_prerelease: false,
             changelog: String::new(),
             contributors: vec![],
@@ -437,6 +645,8 @@ mod tests {
             tag_name: "weird-thing@v0.2.0".to_string(),
             previous_tag: None,
             compare_url: None,
+            commit_range: None,
+            commits: Vec::new(),
             is_prerelease: false,
             changelog: String::new(),
             contributors: vec![],
@@ -14,23 +14,45 @@
 
 use anyhow::{Context, Result};
 use std::collections::HashMap;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
 use crate::core::{
     api::{ApiClient, ApiError},
-    auth::token::load_or_exchange_token,
-    bump::{self, BumpConfig, BumpRecommendation},
-    changelog::{ChangelogConfig, Commit, GitConfig},
-    config::syntax::{BumpConfiguration, ChangelogConfiguration},
+    api_diff, approval,
+    audit::{self, AuditEntry},
+    auth::{
+        pat,
+        permissions::{
+            explain_api_error, is_workflow_push_rejection, preflight, AccessError, Permission,
+            PREPARE_PERMISSIONS,
+        },
+        token::load_or_exchange_token,
+    },
+    bump::{self, BumpConfig, BumpReason, BumpRecommendation},
+    changelog::{ChangelogConfig, Commit, GitConfig, RepoLinks},
+    config::syntax::{
+        ApiDiffMode, BumpConfiguration, ChangelogConfiguration, ReleaseCommits, WebhookEvent,
+    },
     ecosystem::format_handler::FormatHandlerRegistry,
-    git::repository::{ChangeList, RepoPathBuf, Repository},
-    github::{client::GitHubInformation, pr},
+    git::repository::{ChangeList, RepoPath, RepoPathBuf, Repository},
+    github::{
+        client::GitHubInformation,
+        codeowners::{self, CodeOwners},
+        forge,
+        pending::{find_pending_releases, PendingRelease},
+        pr,
+        rest::RestClient,
+    },
     graph::GraphQueryBuilder,
     group::GroupSet,
     manifest::{ReleaseEntry, ReleaseManifest, ReleaseStatistics, MANIFEST_DIR},
-    resolved_release_unit::ReleaseUnitId,
+    notify, publish_checks,
+    release_line::ReleaseLine,
+    resolved_release_unit::{DepRequirement, ReleaseUnitId},
+    rewriters::dep_requirement,
     session::AppSession,
     tag_format::{format_tag, split_maven_coords, TagFormatInputs},
+    version::Version,
     wire::known::Ecosystem,
 };
 
@@ -40,10 +62,50 @@ pub struct ReleaseUnitCandidate {
     pub name: String,
     pub prefix: String,
     pub current_version: String,
+    /// `current_version` in the unit's versioning scheme, which
+    /// [`Self::next_version`] bumps.
+    pub version: Version,
     pub commits: Vec<Commit>,
     pub commit_count: usize,
     pub suggested_bump: BumpRecommendation,
+    /// The commits behind `suggested_bump`, before `[bump]` and the
+    /// release line adjust it.
+    pub bump_reasons: Vec<BumpReason>,
+    /// The bump `cargo semver-checks` says the crate's API changes
+    /// require, with `[checks] api_diff` enabled.
+    pub api_bump: Option<BumpRecommendation>,
     pub ecosystem: Ecosystem,
+    /// The latest release tag's version, when the manifest has since been
+    /// bumped past it by hand. Such a unit can be released at its current
+    /// version with [`BumpChoice::AsIs`].
+    pub manual_bump_from: Option<String>,
+    /// The maintenance branch's version line when preparing with
+    /// `--branch`; only patch releases are offered then.
+    pub release_line: Option<ReleaseLine>,
+    /// The conventional-commit scopes the unit's commits may use. Empty
+    /// allows any.
+    pub scopes: Vec<String>,
+}
+
+impl ReleaseUnitCandidate {
+    /// The version `choice` releases this unit at; `custom_version` is
+    /// only read for [`BumpChoice::Custom`]. The wizard previews exactly
+    /// what `finalize` writes. Skipped units keep their version.
+    pub fn next_version(
+        &self,
+        choice: BumpChoice,
+        custom_version: Option<&str>,
+    ) -> Result<Version> {
+        match choice {
+            BumpChoice::AsIs | BumpChoice::Skip => Ok(self.version.clone()),
+            BumpChoice::Custom => {
+                let typed = custom_version.unwrap_or_default();
+                let version = validate_custom_version(self, typed)?;
+                Ok(self.version.parse_like(version)?)
+            }
+            _ => Ok(self.version.bumped(choice.resolve(self.suggested_bump))?),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -52,6 +114,14 @@ pub enum BumpChoice {
     Major,
     Minor,
     Patch,
+    /// Release the version already in the manifest: only the changelog
+    /// and tag are generated. Offered for hand-bumped units only.
+    AsIs,
+    /// Release an exact version, given in
+    /// [`ReleaseUnitSelection::custom_version`].
+    Custom,
+    /// Leave the unit out of this release.
+    Skip,
 }
 
 impl BumpChoice {
@@ -61,6 +131,9 @@ impl BumpChoice {
             Self::Major => "major",
             Self::Minor => "minor",
             Self::Patch => "patch",
+            Self::AsIs => "manual",
+            Self::Custom => "custom",
+            Self::Skip => "no bump",
         }
     }
 
@@ -70,11 +143,41 @@ impl BumpChoice {
             Self::Major => "major",
             Self::Minor => "minor",
             Self::Patch => "patch",
+            Self::AsIs => "as-is",
+            Self::Custom => "custom…",
+            Self::Skip => "skip",
         }
     }
 
     pub fn all() -> Vec<Self> {
-        vec![Self::Auto, Self::Major, Self::Minor, Self::Patch]
+        vec![
+            Self::Auto,
+            Self::Major,
+            Self::Minor,
+            Self::Patch,
+            Self::Custom,
+            Self::Skip,
+        ]
+    }
+
+    /// The choices offered for `candidate`: [`Self::all`], led by
+    /// [`Self::AsIs`] when its manifest was bumped by hand, and without
+    /// major and minor bumps on a maintenance branch.
+    pub fn choices_for(candidate: &ReleaseUnitCandidate) -> Vec<Self> {
+        let mut choices = Self::all();
+        if candidate.release_line.is_some() {
+            choices.retain(|c| !matches!(c, Self::Major | Self::Minor));
+        }
+        if candidate.manual_bump_from.is_some() {
+            choices.insert(0, Self::AsIs);
+        }
+        choices
+    }
+
+    /// The first of [`Self::choices_for`]: as-is for hand-bumped units,
+    /// since bumping them again would skip the version they were set to.
+    pub fn default_for(candidate: &ReleaseUnitCandidate) -> Self {
+        Self::choices_for(candidate)[0]
     }
 }
 
@@ -82,15 +185,75 @@ impl BumpChoice {
 pub struct ReleaseUnitSelection {
     pub candidate: ReleaseUnitCandidate,
     pub bump_choice: BumpChoice,
+    /// The exact version to release with [`BumpChoice::Custom`], already
+    /// checked by [`validate_custom_version`].
+    pub custom_version: Option<String>,
     pub cached_changelog: Option<String>,
 }
 
+impl ReleaseUnitSelection {
+    /// What the planned release does to the version: the custom version
+    /// for [`BumpChoice::Custom`], the resolved bump text otherwise.
+    pub fn planned_bump(&self) -> &str {
+        match (&self.bump_choice, &self.custom_version) {
+            (BumpChoice::Custom, Some(version)) => version,
+            (choice, _) => choice.resolve(self.candidate.suggested_bump),
+        }
+    }
+}
+
+/// Check a version typed for [`BumpChoice::Custom`]: it must be semver
+/// and newer than the unit's last release, and on a maintenance branch a
+/// patch release of it. Returns it normalized, without a leading `v`.
+pub fn validate_custom_version(candidate: &ReleaseUnitCandidate, text: &str) -> Result<String> {
+    let text = text.trim();
+    let text = text.strip_prefix('v').unwrap_or(text);
+    let new = semver::Version::parse(text)
+        .map_err(|e| anyhow::anyhow!("`{text}` is not a semver version: {e}"))?;
+    let last = candidate
+        .manual_bump_from
+        .as_deref()
+        .unwrap_or(&candidate.current_version);
+    if let Ok(last) = semver::Version::parse(last) {
+        if new <= last {
+            anyhow::bail!("{new} is not newer than the last release, {last}");
+        }
+        if let Some(line) = candidate.release_line {
+            if (new.major, new.minor) != (last.major, last.minor) {
+                anyhow::bail!("only patch releases of {last} are prepared on the {line} line");
+            }
+        }
+    }
+    Ok(new.to_string())
+}
+
+/// The standard bump type (`major`, `minor`, `patch`, `prerelease`) that
+/// takes `old` to `new`, for manifests of custom-version releases.
+fn bump_type_between(old: &str, new: &str) -> &'static str {
+    match (semver::Version::parse(old), semver::Version::parse(new)) {
+        (Ok(old), Ok(new)) if new.major != old.major => "major",
+        (Ok(old), Ok(new)) if new.minor != old.minor => "minor",
+        (Ok(old), Ok(new)) if new.patch != old.patch => "patch",
+        (Ok(_), Ok(new)) if !new.pre.is_empty() => "prerelease",
+        _ => "custom",
+    }
+}
+
 type ChangelogGenerationResult = (
     Vec<RepoPathBuf>,
     HashMap<String, String>,
     HashMap<String, Vec<Commit>>,
 );
 
+/// `prepare --allow-empty`: units to release even without changes since
+/// their last release, e.g. to rebuild for a CVE in a base image.
+#[derive(Debug, Clone)]
+pub struct EmptyRelease {
+    pub units: Vec<String>,
+    /// The changelog entry of a release without commits.
+    pub message: String,
+}
+
 pub struct PrepareContext<'a> {
     pub sess: &'a mut AppSession,
     pub base_branch: String,
@@ -99,10 +262,31 @@ pub struct PrepareContext<'a> {
     pub allow_dirty: bool,
     pub changelog_config: ChangelogConfiguration,
     pub bump_config: BumpConfiguration,
+    /// Release PRs still open on the forge.
+    pub pending_releases: Vec<PendingRelease>,
+    /// `--force`: prepare units an open release PR already covers; the
+    /// new PR lists its edits against that one, see [`ReleaseEdits`].
+    pub force: bool,
+    /// Set before [`Self::discover_projects`] to keep these units as
+    /// candidates when nothing changed.
+    pub empty_release: Option<EmptyRelease>,
 }
 
 impl<'a> PrepareContext<'a> {
-    pub fn initialize(sess: &'a mut AppSession, allow_dirty: bool) -> Result<Self> {
+    /// Set up a release branch off the current one. Open release PRs are
+    /// looked up first so [`Self::discover_projects`] can refuse to prepare
+    /// units that already have a release in flight, unless `force` is set.
+    /// Refuses while an earlier release is unfinished; see [`Journal`].
+    pub fn initialize(sess: &'a mut AppSession, allow_dirty: bool, force: bool) -> Result<Self> {
+        if let Some(journal) = Journal::load(&sess.repo)? {
+            anyhow::bail!(
+                "a previous release on `{}` stopped {}; run `belaf prepare --resume` \
+                 to finish it, or to roll it back",
+                journal.release_branch,
+                journal.step.describe()
+            );
+        }
+
         if !allow_dirty {
             if let Some(dirty) = sess
                 .repo
@@ -125,6 +309,15 @@ impl<'a> PrepareContext<'a> {
             );
         }
 
+        let pending_releases = match find_pending_releases(sess) {
+            Some(pending) => pending,
+            None if force => Vec::new(),
+            None => {
+                warn!("could not check for open release PRs; continuing without the guard");
+                Vec::new()
+            }
+        };
+
         let (base_branch, release_branch) = create_release_branch(sess)?;
         let changelog_config = sess.changelog_config.clone();
         let bump_config = sess.bump_config.clone();
@@ -137,6 +330,9 @@ impl<'a> PrepareContext<'a> {
             allow_dirty,
             changelog_config,
             bump_config,
+            pending_releases,
+            force,
+            empty_release: None,
         })
     }
 
@@ -170,7 +366,25 @@ impl<'a> PrepareContext<'a> {
             let history = histories.lookup(*ident);
             let n_commits = history.n_commits();
 
-            if n_commits == 0 {
+            // A manifest ahead of the latest release tag was bumped by hand;
+            // offer to release it as-is even if nothing else changed.
+            let manual_bump_from = history
+                .release_version()
+                .and_then(|tagged| unit.version.parse_like(tagged.to_string()).ok())
+                .filter(|tagged| unit.version > *tagged)
+                .map(|tagged| tagged.to_string());
+            if let Some(tagged) = &manual_bump_from {
+                info!(
+                    "{}: manifest version {} is ahead of the latest release tag ({})",
+                    unit.user_facing_name, unit.version, tagged
+                );
+            }
+
+            let allow_empty = self
+                .empty_release
+                .as_ref()
+                .is_some_and(|e| e.units.contains(&unit.user_facing_name));
+            if n_commits == 0 && manual_bump_from.is_none() && !allow_empty {
                 info!(
                     "{}: no changes since last release, skipping",
                     unit.user_facing_name
@@ -194,12 +408,41 @@ impl<'a> PrepareContext<'a> {
             })?;
 
             let bump_config = BumpConfig::from_user_config(&self.bump_config);
-            let suggested_bump = analysis
+            let mut suggested_bump = analysis
                 .recommendation
                 .apply_config(&bump_config, Some(&current_version));
+            let release_line = self.sess.repo.release_line();
+            if let Some(line) = release_line {
+                if matches!(
+                    suggested_bump,
+                    BumpRecommendation::Major | BumpRecommendation::Minor
+                ) {
+                    info!(
+                        "{}: {} bump lowered to patch on the {line} line",
+                        unit.user_facing_name,
+                        suggested_bump.as_str()
+                    );
+                    suggested_bump = BumpRecommendation::Patch;
+                }
+            }
 
             info!("{}: {}", unit.user_facing_name, analysis.summary());
 
+            let api_bump = match history.release_tag() {
+                Some(tag) if self.sess.checks().api_diff != ApiDiffMode::Off => {
+                    match api_diff::check_unit(self.sess, *ident, tag) {
+                        Ok(bump) => {
+                            bump.map(|b| b.apply_config(&bump_config, Some(&current_version)))
+                        }
+                        Err(e) => {
+                            warn!("{}: skipping the API diff: {e:#}", unit.user_facing_name);
+                            None
+                        }
+                    }
+                }
+                _ => None,
+            };
+
             let qnames = unit.qualified_names();
             let ecosystem = qnames
                 .get(1)
@@ -211,16 +454,48 @@ impl<'a> PrepareContext<'a> {
                 name: unit.user_facing_name.clone(),
                 prefix: unit.prefix().escaped(),
                 current_version,
+                version: unit.version.clone(),
                 commits,
                 commit_count: n_commits,
                 suggested_bump,
+                bump_reasons: analysis.drivers().cloned().collect(),
+                api_bump,
                 ecosystem,
+                manual_bump_from,
+                release_line,
+                scopes: self.sess.allowed_scopes(*ident).to_vec(),
             });
         }
 
+        if let Some(message) = self.pending_release_conflicts(self.force) {
+            cleanup_release_branch(self.sess, &self.base_branch, &self.release_branch);
+            anyhow::bail!(message);
+        }
+
         Ok(())
     }
 
+    /// Describe open release PRs that cover a candidate; see
+    /// [`pending_conflicts::find`].
+    fn pending_release_conflicts(&self, force: bool) -> Option<String> {
+        let groups = self.sess.graph().groups();
+        let candidates: Vec<pending_conflicts::Candidate<'_>> = self
+            .candidates
+            .iter()
+            .map(|c| pending_conflicts::Candidate {
+                name: &c.name,
+                group: groups.group_of(c.ident).map(|g| g.id.as_str()),
+            })
+            .collect();
+        pending_conflicts::find(&self.pending_releases, &candidates, force)
+    }
+
+    /// How `planned` differs from each open release PR it shares a unit
+    /// with; only `--force` gets past those.
+    pub fn release_edits(&self, planned: &[PlannedUnit<'_>]) -> Vec<ReleaseEdits> {
+        ReleaseEdits::against_pending(&self.pending_releases, planned)
+    }
+
     pub fn has_candidates(&self) -> bool {
         !self.candidates.is_empty()
     }
@@ -229,51 +504,190 @@ impl<'a> PrepareContext<'a> {
         cleanup_release_branch(self.sess, &self.base_branch, &self.release_branch);
     }
 
+    /// Snapshot of the graph's internal requirements for checking bump
+    /// plans against; see [`ConflictPlanner`].
+    pub fn conflict_planner(&self) -> ConflictPlanner {
+        ConflictPlanner::new(self.sess)
+    }
+
+    /// Internal dependency requirements that releasing `selections` would
+    /// break.
+    pub fn dep_conflicts(&self, selections: &[ReleaseUnitSelection]) -> Vec<DepConflict> {
+        let bumps: Vec<_> = selections
+            .iter()
+            .map(|s| (s.candidate.ident, s.planned_bump()))
+            .collect();
+        self.conflict_planner().conflicts(&bumps)
+    }
+
+    /// Apply the chosen resolution of each conflict: rewrite the dependent's
+    /// requirement to the widened or pinned one, or drop the dependee —
+    /// with the rest of its group — from `selections`.
+    pub fn resolve_dep_conflicts(
+        &mut self,
+        selections: &mut Vec<ReleaseUnitSelection>,
+        resolutions: &[(DepConflict, ConflictResolution)],
+    ) {
+        for (conflict, resolution) in resolutions {
+            match conflict.requirement_for(*resolution) {
+                Some(requirement) => {
+                    info!(
+                        "{}: requirement on {} `{}` -> `{}`",
+                        conflict.dependent_name,
+                        conflict.dependee_name,
+                        conflict.requirement,
+                        requirement
+                    );
+                    let unit = self.sess.graph_mut().lookup_mut(conflict.dependent);
+                    for dep in &mut unit.internal_deps {
+                        if dep.ident == conflict.dependee {
+                            dep.belaf_requirement = DepRequirement::Manual(requirement.to_owned());
+                        }
+                    }
+                }
+                None => {
+                    info!(
+                        "{}: left out of this release ({})",
+                        conflict.dependee_name,
+                        conflict.describe()
+                    );
+                    let skipped = match self.sess.graph().groups().group_of(conflict.dependee) {
+                        Some(group) => group.members.clone(),
+                        None => vec![conflict.dependee],
+                    };
+                    selections.retain(|s| !skipped.contains(&s.candidate.ident));
+                }
+            }
+        }
+    }
+
     pub fn finalize(self, selections: Vec<ReleaseUnitSelection>) -> Result<String> {
         if selections.is_empty() {
             return Err(anyhow::anyhow!("no projects selected for release"));
         }
 
+        // Checked before any version is bumped or file written, so a
+        // requirement the release would break fails here rather than in
+        // `cargo update` or the release PR's CI.
+        let conflicts = self.dep_conflicts(&selections);
+        if !conflicts.is_empty() {
+            let lines: Vec<String> = conflicts
+                .iter()
+                .map(|c| format!("  • {}", c.describe()))
+                .collect();
+            anyhow::bail!(
+                "internal dependency requirements would reject the new versions:\n{}\n\n\
+                 Run `belaf prepare` interactively to widen the requirement, pin the new \
+                 version or skip the project, or edit the requirement and commit it first.",
+                lines.join("\n")
+            );
+        }
+
         let mut prepared: Vec<SelectedReleaseUnit> = Vec::new();
+        let codeowners = CodeOwners::load(&self.sess.repo);
 
         for selection in &selections {
             let unit = self.sess.graph().lookup(selection.candidate.ident);
+            let owners = codeowners.owners_of(unit.prefix()).to_vec();
 
             let bump_scheme_text = selection
                 .bump_choice
                 .resolve(selection.candidate.suggested_bump);
 
+            if selection.bump_choice == BumpChoice::Skip {
+                info!("{}: skipped for this release", unit.user_facing_name);
+                continue;
+            }
             if bump_scheme_text == "no bump" {
                 info!("{}: no version bump needed", unit.user_facing_name);
                 continue;
             }
 
-            let bump_scheme = unit
-                .version
-                .parse_bump_scheme(bump_scheme_text)
-                .with_context(|| {
-                    format!(
-                        "invalid bump scheme \"{}\" for project {}",
-                        bump_scheme_text, unit.user_facing_name
-                    )
-                })?;
-
-            let old_version = selection.candidate.current_version.clone();
-
-            let proj_mut = self.sess.graph_mut().lookup_mut(selection.candidate.ident);
-
-            bump_scheme.apply(&mut proj_mut.version).with_context(|| {
-                format!(
-                    "failed to apply version bump to {}",
-                    proj_mut.user_facing_name
-                )
-            })?;
-
-            let new_version = proj_mut.version.to_string();
+            let name = unit.user_facing_name.clone();
+            let old_version = match selection.bump_choice {
+                BumpChoice::AsIs => {
+                    // The manifest already carries the new version; the last
+                    // tag is what this release follows.
+                    let Some(tagged) = selection.candidate.manual_bump_from.clone() else {
+                        anyhow::bail!(
+                            "{name}: cannot release as-is, version {} is already tagged",
+                            unit.version
+                        );
+                    };
+                    tagged
+                }
+                BumpChoice::Custom => selection
+                    .candidate
+                    .manual_bump_from
+                    .clone()
+                    .unwrap_or_else(|| selection.candidate.current_version.clone()),
+                _ => selection.candidate.current_version.clone(),
+            };
+            let version = selection
+                .candidate
+                .next_version(selection.bump_choice, selection.custom_version.as_deref())
+                .with_context(|| format!("failed to compute the new version of {name}"))?;
+            let new_version = version.to_string();
+            let bump_type = if selection.bump_choice == BumpChoice::Custom {
+                bump_type_between(&old_version, &new_version).to_string()
+            } else {
+                bump_scheme_text.to_string()
+            };
+            if let Some(line) = selection.candidate.release_line {
+                let kind = bump_type_between(&old_version, &new_version);
+                if kind == "major" || kind == "minor" {
+                    anyhow::bail!(
+                        "{name}: {old_version} -> {new_version} is a {kind} release; \
+                         only patch releases are prepared on the {line} line"
+                    );
+                }
+            }
+            if let (ApiDiffMode::Enforce, Some(required)) =
+                (self.sess.checks().api_diff, selection.candidate.api_bump)
+            {
+                let kind = bump_type_between(&old_version, &new_version);
+                if let Some(chosen) = BumpRecommendation::from_string(kind) {
+                    if chosen.merge(required) != chosen {
+                        anyhow::bail!(
+                            "{name}: the API diff requires a {} release, but \
+                             {old_version} -> {new_version} is a {kind} release",
+                            required.as_str()
+                        );
+                    }
+                }
+            }
+            // A release without commits gets the `--allow-empty` message
+            // as its changelog entry.
+            let empty_changelog = match &self.empty_release {
+                Some(empty)
+                    if selection.candidate.commits.is_empty() && empty.units.contains(&name) =>
+                {
+                    let path = changelog_path(
+                        &ChangelogConfig::from_user_config(&self.changelog_config),
+                        &selection.candidate.prefix,
+                    );
+                    let existing = std::fs::read_to_string(self.sess.repo.resolve_workdir(&path))
+                        .unwrap_or_default();
+                    let today = crate::core::deterministic::now_utc().date();
+                    let entry =
+                        format!("## [{new_version}] - {today}\n\n{}\n", empty.message.trim());
+                    let content = prepend_entry(&existing, &entry);
+                    Some(PromotedChangelog {
+                        path,
+                        entry,
+                        content,
+                    })
+                }
+                _ => None,
+            };
+            self.sess
+                .graph_mut()
+                .lookup_mut(selection.candidate.ident)
+                .version = version;
 
             info!(
                 "{}: {} -> {} ({} commit{})",
-                proj_mut.user_facing_name,
+                name,
                 old_version,
                 new_version,
                 selection.candidate.commit_count,
@@ -286,14 +700,20 @@ impl<'a> PrepareContext<'a> {
 
             prepared.push(SelectedReleaseUnit {
                 ident: selection.candidate.ident,
-                name: proj_mut.user_facing_name.clone(),
+                name,
                 prefix: selection.candidate.prefix.clone(),
                 old_version,
                 new_version,
-                bump_type: bump_scheme_text.to_string(),
+                bump_type,
                 commits: selection.candidate.commits.clone(),
                 ecosystem: selection.candidate.ecosystem.clone(),
                 cached_changelog: selection.cached_changelog.clone(),
+                owners,
+                promoted_changelog: empty_changelog,
+                checklist: self
+                    .sess
+                    .release_checklist(selection.candidate.ident)
+                    .to_vec(),
             });
         }
 
@@ -301,7 +721,23 @@ impl<'a> PrepareContext<'a> {
             return Err(anyhow::anyhow!("no projects needed version bumps"));
         }
 
-        let pipeline = ReleasePipeline::new(self.sess, self.base_branch, self.release_branch)?;
+        let released: Vec<ReleaseUnitId> = prepared.iter().map(|p| p.ident).collect();
+        dep_requirement::apply_strategies(self.sess, &released);
+
+        let planned: Vec<PlannedUnit<'_>> = prepared.iter().map(PlannedUnit::from).collect();
+        let edits = self.release_edits(&planned);
+        for release_edits in &edits {
+            info!(
+                "open release PR #{} is prepared again:",
+                release_edits.number
+            );
+            for edit in &release_edits.edits {
+                info!("  {}", edit.describe());
+            }
+        }
+
+        let pipeline = ReleasePipeline::new(self.sess, self.base_branch, self.release_branch)?
+            .with_release_edits(edits);
         pipeline.execute(prepared)
     }
 }
@@ -317,12 +753,35 @@ pub struct SelectedReleaseUnit {
     pub commits: Vec<Commit>,
     pub ecosystem: Ecosystem,
     pub cached_changelog: Option<String>,
+    /// `CODEOWNERS` owners of the unit's directory.
+    pub owners: Vec<String>,
+    /// Set by `belaf promote` and `prepare --allow-empty`: written instead
+    /// of a changelog entry generated from `commits`.
+    pub promoted_changelog: Option<PromotedChangelog>,
+    /// Manual release steps, rendered as a task list in the PR body.
+    pub checklist: Vec<String>,
+}
+
+impl<'a> From<&'a SelectedReleaseUnit> for PlannedUnit<'a> {
+    fn from(unit: &'a SelectedReleaseUnit) -> Self {
+        PlannedUnit {
+            name: &unit.name,
+            version: &unit.new_version,
+            commits: &unit.commits,
+        }
+    }
 }
 
 pub struct ReleasePipeline<'a> {
     sess: &'a mut AppSession,
     base_branch: String,
     release_branch: String,
+    /// Subcommand recorded in the audit log.
+    command: &'static str,
+    journal: Journal,
+    /// Edits against the open release PRs this one prepares again; the
+    /// PR body's edit log.
+    release_edits: Vec<ReleaseEdits>,
 }
 
 impl<'a> ReleasePipeline<'a> {
@@ -331,17 +790,99 @@ impl<'a> ReleasePipeline<'a> {
         base_branch: String,
         release_branch: String,
     ) -> Result<Self> {
+        let journal = match Journal::load(&sess.repo)? {
+            Some(journal) if journal.release_branch == release_branch => journal,
+            _ => Journal::new(&sess.repo, base_branch.clone(), release_branch.clone())?,
+        };
         Ok(Self {
             sess,
             base_branch,
             release_branch,
+            command: "prepare",
+            journal,
+            release_edits: Vec::new(),
         })
     }
 
+    /// Continue the release `journal` records from its last completed
+    /// step; see [`journal`]. Returns the release PR's URL.
+    pub fn resume(sess: &'a mut AppSession, journal: Journal) -> Result<String> {
+        if journal.step < JournalStep::FilesWritten {
+            anyhow::bail!("BUG: a release is only resumed once its files are written");
+        }
+        if !sess.repo.branch_exists(&journal.release_branch) {
+            Journal::remove(&sess.repo);
+            anyhow::bail!(
+                "release branch `{}` no longer exists; nothing to resume",
+                journal.release_branch
+            );
+        }
+        if sess.repo.current_branch_name()?.as_deref() != Some(journal.release_branch.as_str()) {
+            sess.repo
+                .checkout_branch(&journal.release_branch)
+                .context("failed to checkout release branch")?;
+        }
+
+        info!(
+            "resuming the release on {} {}",
+            journal.release_branch,
+            journal.step.describe()
+        );
+        let mut pipeline = Self {
+            sess,
+            base_branch: journal.base_branch.clone(),
+            release_branch: journal.release_branch.clone(),
+            command: "prepare",
+            journal,
+            release_edits: Vec::new(),
+        };
+        pipeline.finish()
+    }
+
+    /// Record the release as made by `command` rather than `prepare`.
+    pub fn for_command(mut self, command: &'static str) -> Self {
+        self.command = command;
+        self
+    }
+
+    /// List `edits` in the release PR's body.
+    pub fn with_release_edits(mut self, edits: Vec<ReleaseEdits>) -> Self {
+        self.release_edits = edits;
+        self
+    }
+
     pub fn execute(mut self, projects: Vec<SelectedReleaseUnit>) -> Result<String> {
         if projects.is_empty() {
             return Err(anyhow::anyhow!("no projects to release"));
         }
+        let releases: Vec<serde_json::Value> = projects
+            .iter()
+            .map(|p| {
+                serde_json::json!({
+                    "name": p.name,
+                    "previous_version": p.old_version,
+                    "new_version": p.new_version,
+                    "bump": p.bump_type,
+                })
+            })
+            .collect();
+        notify::emit(
+            self.sess.webhooks(),
+            WebhookEvent::PrepareStarted,
+            serde_json::json!({
+                "command": self.command,
+                "base_branch": self.base_branch,
+                "release_branch": self.release_branch,
+                "releases": releases,
+            }),
+        );
+
+        info!("checking release tags...");
+        self.check_release_tags(&projects)?;
+        if self.sess.checks().publish_metadata {
+            info!("checking publish metadata...");
+            self.check_publish_metadata(&projects)?;
+        }
 
         info!("updating project files with new versions...");
         let changes = self
@@ -356,26 +897,151 @@ impl<'a> ReleasePipeline<'a> {
         self.print_modified_files(&changes, &changelog_paths);
 
         info!("creating release manifest...");
-        let (_manifest, manifest_filename, manifest_repo_path) =
+        let (manifest, manifest_filename, manifest_repo_path) =
             self.create_manifest(&projects, &changelog_contents, &processed_commits)?;
+        if let Some(approval) = &manifest.approval {
+            info!(
+                "release {} needs {} approval(s) before it ships: approvers run \
+                 `belaf approve {}` on the release branch",
+                manifest.manifest_id, approval.required, manifest.manifest_id
+            );
+        }
 
-        info!("creating release commit...");
-        let all_changed_paths =
-            self.collect_all_paths(&changes, &changelog_paths, &manifest_repo_path);
-        self.create_commit(&projects, &all_changed_paths)?;
-
-        info!("pushing release branch to remote...");
-        self.push_branch()?;
+        let tags = manifest
+            .releases
+            .iter()
+            .map(|r| r.tag_name.clone())
+            .collect();
+        let entry = AuditEntry::new(&self.sess.repo, self.command)
+            .with_branch(self.release_branch.clone())
+            .with_manifest(manifest.manifest_id.clone())
+            .with_tags(tags);
+        let audit_path = audit::append(&self.sess.repo, &entry)?;
+
+        let commits = self.plan_commits(
+            &projects,
+            &changes,
+            &changelog_paths,
+            &manifest_repo_path,
+            &audit_path,
+        );
+        self.journal.release_units = projects
+            .iter()
+            .map(|p| JournalUnit {
+                name: p.name.clone(),
+                bump: p.bump_type.clone(),
+            })
+            .collect();
+        self.journal.commits = commits;
+        self.journal.pull_request =
+            Some(self.plan_pull_request(&projects, &manifest_filename, &changelog_contents));
+        self.journal
+            .record(&self.sess.repo, JournalStep::FilesWritten)?;
 
-        info!("creating pull request...");
-        let pr_url =
-            self.create_pull_request(&projects, &manifest_filename, &changelog_contents)?;
+        let pr_url = self.finish()?;
 
         self.print_summary(&projects, &pr_url);
 
         Ok(pr_url)
     }
 
+    /// Commit, push and open the PR, skipping the steps the journal
+    /// says are done.
+    fn finish(&mut self) -> Result<String> {
+        if self.journal.step < JournalStep::Committed {
+            info!("creating release commit...");
+            self.create_commits()?;
+            self.journal
+                .record(&self.sess.repo, JournalStep::Committed)?;
+        }
+
+        if self.journal.step < JournalStep::Pushed {
+            info!("pushing release branch to remote...");
+            self.push_branch()?;
+            self.journal.record(&self.sess.repo, JournalStep::Pushed)?;
+        }
+
+        info!("creating pull request...");
+        let pr_url = self.create_pull_request()?;
+        Journal::remove(&self.sess.repo);
+        notify::emit(
+            self.sess.webhooks(),
+            WebhookEvent::PrOpened,
+            serde_json::json!({
+                "url": pr_url,
+                "base_branch": self.base_branch,
+                "release_branch": self.release_branch,
+                "releases": self.journal.release_units,
+            }),
+        );
+
+        Ok(pr_url)
+    }
+
+    /// Refuse to release a unit whose manifest lacks the metadata its
+    /// registry needs. See [`publish_checks`].
+    fn check_publish_metadata(&self, projects: &[SelectedReleaseUnit]) -> Result<()> {
+        let mut problems = Vec::new();
+        for project in projects {
+            problems.extend(publish_checks::check_unit(self.sess, project.ident)?);
+        }
+        if problems.is_empty() {
+            return Ok(());
+        }
+
+        let lines: Vec<String> = problems
+            .iter()
+            .map(|p| format!("  • {}", p.describe()))
+            .collect();
+        anyhow::bail!(
+            "the release is missing publish metadata:\n{}\n\n\
+             Fill in the fields, or set `publish_metadata = false` under [checks] \
+             in belaf/config.toml.",
+            lines.join("\n")
+        );
+    }
+
+    /// Refuse to release a version whose tag already exists, locally or
+    /// on the remote, or that isn't newer than every release already
+    /// tagged. See [`tag_conflicts`].
+    fn check_release_tags(&self, projects: &[SelectedReleaseUnit]) -> Result<()> {
+        let local = self.sess.repo.tag_names()?;
+        let remote = if std::env::var_os("BELAF_NO_FETCH").is_none() {
+            self.sess.repo.remote_tag_names(None)?
+        } else {
+            Vec::new()
+        };
+        let existing = ExistingTags::new(local, remote);
+
+        let mut conflicts: Vec<TagConflict> = Vec::new();
+        for project in projects {
+            let tag = build_tag_name(self.sess, project, self.sess.graph().groups())?;
+            let matcher = self.sess.tag_matcher(project.ident)?;
+            conflicts.extend(tag_conflicts::find(
+                &project.name,
+                &tag,
+                &project.new_version,
+                &matcher,
+                &existing,
+                self.sess.repo.release_line(),
+            ));
+        }
+        if conflicts.is_empty() {
+            return Ok(());
+        }
+
+        let lines: Vec<String> = conflicts
+            .iter()
+            .map(|c| format!("  • {}", c.describe()))
+            .collect();
+        anyhow::bail!(
+            "the new versions collide with existing release tags:\n{}\n\n\
+             Pick a version above the latest release, or delete the stray tag if \
+             its release never shipped.",
+            lines.join("\n")
+        );
+    }
+
     fn generate_changelogs(
         &self,
         projects: &[SelectedReleaseUnit],
@@ -385,7 +1051,6 @@ impl<'a> ReleasePipeline<'a> {
         let mut processed_commits_map: HashMap<String, Vec<Commit>> = HashMap::new();
 
         let git_config = GitConfig::from_user_config(&self.sess.changelog_config);
-        let changelog_config = ChangelogConfig::from_user_config(&self.sess.changelog_config);
         let bump_config = BumpConfig::from_user_config(&self.sess.bump_config);
 
         let github_remote = extract_github_remote(&self.sess.repo);
@@ -396,6 +1061,26 @@ impl<'a> ReleasePipeline<'a> {
         }
 
         for project in projects {
+            let changelog_config = ChangelogConfig::from_user_config(
+                &self
+                    .sess
+                    .changelog_config
+                    .for_project(&project.name, project.ecosystem.as_str()),
+            );
+            if let Some(promoted) = &project.promoted_changelog {
+                let abs = self.sess.repo.resolve_workdir(&promoted.path);
+                std::fs::write(&abs, &promoted.content)
+                    .with_context(|| format!("failed to write changelog to {}", abs.display()))?;
+                changelog_contents.insert(
+                    project.name.clone(),
+                    self.release_notes(&promoted.entry, &changelog_config, &promoted.path),
+                );
+                changelog_paths.push(promoted.path.clone());
+                continue;
+            }
+            let tag = build_tag_name(self.sess, project, self.sess.graph().groups())?;
+            let previous_tag = Some(tag.replacen(&project.new_version, &project.old_version, 1))
+                .filter(|previous| self.sess.repo.tag_exists(previous));
             let params = ChangelogGenerationParams {
                 repo: &self.sess.repo,
                 project_name: &project.name,
@@ -410,10 +1095,22 @@ impl<'a> ReleasePipeline<'a> {
                 github_owner: github_remote.as_ref().map(|r| r.owner.as_str()),
                 github_repo: github_remote.as_ref().map(|r| r.repo.as_str()),
                 github_token: github_token.clone(),
+                previous_version: previous_tag.as_ref().map(|_| project.old_version.as_str()),
+                tags: previous_tag
+                    .as_deref()
+                    .map(|previous| (previous, tag.as_str())),
+                replace_unreleased: false,
             };
             let result = generate_and_write_project_changelog(&params)?;
 
-            changelog_contents.insert(project.name.clone(), result.content);
+            let path = result
+                .path
+                .clone()
+                .unwrap_or_else(|| changelog_path(&changelog_config, &project.prefix));
+            changelog_contents.insert(
+                project.name.clone(),
+                self.release_notes(&result.content, &changelog_config, &path),
+            );
             processed_commits_map.insert(project.name.clone(), result.processed_commits);
 
             if let Some(path) = result.path {
@@ -424,11 +1121,28 @@ impl<'a> ReleasePipeline<'a> {
                     project.name
                 );
             }
+            changelog_paths.extend(result.localized_paths);
         }
 
         Ok((changelog_paths, changelog_contents, processed_commits_map))
     }
 
+    /// `entry` as the release PR body and the release notes show it:
+    /// cut to `[changelog] max_entry_size`, linking to the changelog at
+    /// `path` on the base branch.
+    fn release_notes(&self, entry: &str, config: &ChangelogConfig, path: &RepoPathBuf) -> String {
+        let Some(max) = config.max_entry_size else {
+            return entry.to_string();
+        };
+        let path = path.escaped();
+        let remote = self.sess.repo.upstream_url().ok();
+        let full_changelog = match RepoLinks::resolve(&config.link_style, remote.as_deref()) {
+            Some(links) => links.file_url(&self.base_branch, &path),
+            None => path,
+        };
+        truncate_entry(entry, max, &full_changelog)
+    }
+
     fn print_modified_files(&self, changes: &ChangeList, changelog_paths: &[RepoPathBuf]) {
         let paths: Vec<_> = changes
             .paths()
@@ -457,9 +1171,24 @@ impl<'a> ReleasePipeline<'a> {
             .unwrap_or_else(|_| "belaf-ci".to_string());
 
         let github_base_url = self.get_github_compare_base_url();
+        let head_sha = self.sess.repo.head_commit_sha()?;
 
         let mut manifest = ReleaseManifest::new(self.base_branch.clone(), git_user);
 
+        if let Some(approvers) = self.sess.config_approvers() {
+            let future = approval::current_login();
+            let login = match tokio::runtime::Handle::try_current() {
+                Ok(handle) => tokio::task::block_in_place(|| handle.block_on(future)),
+                Err(_) => {
+                    let rt =
+                        tokio::runtime::Runtime::new().context("failed to create async runtime")?;
+                    rt.block_on(future)
+                }
+            }
+            .context("[release.approvers] needs the GitHub login of whoever prepares a release")?;
+            manifest.approval = Some(approval::requirement(approvers, Some(login)));
+        }
+
         // Emit `groups[]` entries for any group that has at least one
         // member in this release set. The github-app reads this to drive
         // atomic group releases (G6) — releases sharing a `group_id` are
@@ -602,6 +1331,17 @@ impl<'a> ReleasePipeline<'a> {
                 release = release.with_compare_url(base_url, |tag| self.sess.repo.tag_exists(tag));
             }
 
+            // The range is recorded even without a forge, so `belaf history`
+            // can say which commits shipped in a release.
+            let commit_range = match &release.previous_tag {
+                Some(prev) if self.sess.repo.tag_exists(prev) => format!("{prev}..{head_sha}"),
+                _ => head_sha.clone(),
+            };
+            release = release.with_commits(
+                commit_range,
+                project.commits.iter().map(|c| c.id.clone()).collect(),
+            );
+
             manifest.add_release(release);
         }
 
@@ -627,29 +1367,81 @@ impl<'a> ReleasePipeline<'a> {
         Ok((manifest, manifest_filename, manifest_repo_path))
     }
 
-    fn collect_all_paths<'b>(
+    /// The release commits: one for every changed file, or with
+    /// `[git] release_commits = "per_unit"` one per unit holding the files
+    /// under it and a last one for the manifest, the audit log and what no
+    /// released unit owns, such as a workspace lockfile.
+    fn plan_commits(
         &self,
-        changes: &'b ChangeList,
-        changelog_paths: &'b [RepoPathBuf],
-        manifest_repo_path: &'b RepoPathBuf,
-    ) -> Vec<&'b crate::core::git::repository::RepoPath> {
-        changes
+        projects: &[SelectedReleaseUnit],
+        changes: &ChangeList,
+        changelog_paths: &[RepoPathBuf],
+        manifest_repo_path: &RepoPathBuf,
+        audit_path: &RepoPathBuf,
+    ) -> Vec<PlannedCommit> {
+        let changed = changes
             .paths()
-            .chain(changelog_paths.iter().map(|p| p.as_ref()))
-            .chain(std::iter::once(manifest_repo_path.as_ref()))
-            .collect()
+            .chain(changelog_paths.iter().map(|p| p.as_ref()));
+        let release_files = [manifest_repo_path.as_ref(), audit_path.as_ref()];
+
+        if self.sess.repo.release_commits() == ReleaseCommits::Single {
+            return vec![PlannedCommit {
+                message: format_commit_message(projects),
+                paths: changed.chain(release_files).map(|p| p.escaped()).collect(),
+            }];
+        }
+
+        let mut unit_paths: Vec<Vec<String>> = vec![Vec::new(); projects.len()];
+        let mut shared: Vec<String> = Vec::new();
+        for path in changed {
+            let owner = projects.iter().position(|p| {
+                self.sess
+                    .graph()
+                    .lookup(p.ident)
+                    .repo_paths
+                    .repo_path_matches(path)
+            });
+            match owner {
+                Some(i) => unit_paths[i].push(path.escaped()),
+                None => shared.push(path.escaped()),
+            }
+        }
+
+        let mut commits: Vec<PlannedCommit> = projects
+            .iter()
+            .zip(unit_paths)
+            .filter(|(_, paths)| !paths.is_empty())
+            .map(|(project, paths)| PlannedCommit {
+                message: format_commit_message(std::slice::from_ref(project)),
+                paths,
+            })
+            .collect();
+        shared.extend(release_files.iter().map(|p| p.escaped()));
+        commits.push(PlannedCommit {
+            message: "chore(release): add release manifest".to_string(),
+            paths: shared,
+        });
+        commits
     }
 
-    fn create_commit(
-        &self,
-        projects: &[SelectedReleaseUnit],
-        all_changed_paths: &[&crate::core::git::repository::RepoPath],
-    ) -> Result<()> {
-        let commit_message = format_commit_message(projects);
-        self.sess
-            .repo
-            .create_commit(&commit_message, all_changed_paths)
-            .context("failed to create release commit")?;
+    /// Make the journal's planned commits, recording each so `--resume`
+    /// doesn't make it twice.
+    fn create_commits(&mut self) -> Result<()> {
+        while self.journal.commits_made < self.journal.commits.len() {
+            let commit = &self.journal.commits[self.journal.commits_made];
+            let paths: Vec<RepoPathBuf> = commit
+                .paths
+                .iter()
+                .map(|p| RepoPathBuf::new(p.as_bytes()))
+                .collect();
+            let paths: Vec<&RepoPath> = paths.iter().map(|p| p.as_ref()).collect();
+            self.sess
+                .repo
+                .create_commit(&commit.message, &paths)
+                .context("failed to create release commit")?;
+            self.journal.commits_made += 1;
+            self.journal.save(&self.sess.repo)?;
+        }
         Ok(())
     }
 
@@ -658,6 +1450,30 @@ impl<'a> ReleasePipeline<'a> {
         self.sess
             .repo
             .push_branch(&self.release_branch, Some(&git_token))
+            .map_err(|e| {
+                if is_workflow_push_rejection(&format!("{e:#}")) {
+                    let target = self
+                        .sess
+                        .repo
+                        .upstream_url()
+                        .ok()
+                        .and_then(|url| parse_github_url(&url).ok());
+                    let with_token = target.as_ref().is_some_and(|(owner, repo)| {
+                        matches!(pat::for_repo(owner, repo), Ok(Some(_)))
+                    });
+                    let repo = target
+                        .map(|(owner, repo)| format!("{owner}/{repo}"))
+                        .unwrap_or_else(|| "this repository".to_string());
+                    let permission = Permission::Workflows;
+                    anyhow::Error::from(if with_token {
+                        AccessError::TokenLacksPermission { repo, permission }
+                    } else {
+                        AccessError::MissingPermission { repo, permission }
+                    })
+                } else {
+                    e
+                }
+            })
             .context("failed to push release branch")?;
         Ok(())
     }
@@ -672,12 +1488,17 @@ impl<'a> ReleasePipeline<'a> {
         let (owner, repo) =
             parse_github_url(&upstream_url).context("failed to parse GitHub URL from upstream")?;
 
-        let api_client = ApiClient::new();
+        if let Some((pat, _)) = pat::for_repo(&owner, &repo)? {
+            return Ok(pat.token);
+        }
+
+        let api_client = ApiClient::try_new()?;
 
         let future = async {
             let token = load_or_exchange_token(&api_client)
                 .await
                 .context("failed to load token")?
+                .ok_or(ApiError::Unauthorized)
                 .context(
                     "not authenticated — run 'belaf install' (interactive) or run from a \
                      GitHub Actions job with `permissions: id-token: write` set",
@@ -687,15 +1508,12 @@ impl<'a> ReleasePipeline<'a> {
                 .get_git_credentials(&token, &owner, &repo)
                 .await
                 .map_err(|e| match &e {
-                    ApiError::ApiResponse { status, message } => {
-                        anyhow::anyhow!("failed to get git credentials ({}): {}", status, message)
-                    }
-                    ApiError::Unauthorized => {
-                        anyhow::anyhow!(
-                            "authentication expired - run 'belaf login' to re-authenticate"
-                        )
-                    }
-                    _ => anyhow::anyhow!("failed to get git credentials: {}", e),
+                    ApiError::ApiResponse {
+                        status: 403 | 404, ..
+                    } => explain_api_error(e, Permission::Contents, &owner, &repo),
+                    ApiError::Unauthorized => anyhow::Error::new(e)
+                        .context("authentication expired - run 'belaf login' to re-authenticate"),
+                    _ => anyhow::Error::new(e).context("failed to get git credentials"),
                 })
         };
 
@@ -711,25 +1529,92 @@ impl<'a> ReleasePipeline<'a> {
         Ok(credentials.token)
     }
 
-    fn create_pull_request(
+    fn plan_pull_request(
         &self,
         projects: &[SelectedReleaseUnit],
         manifest_filename: &str,
         changelog_contents: &HashMap<String, String>,
-    ) -> Result<String> {
+    ) -> PullRequestPlan {
+        let (reviewers, team_reviewers) =
+            codeowners::review_requests(projects.iter().flat_map(|p| &p.owners));
+        PullRequestPlan {
+            title: pr::generate_pr_title(projects),
+            body: pr::generate_pr_body(
+                projects,
+                manifest_filename,
+                changelog_contents,
+                &self.release_edits,
+            ),
+            reviewers,
+            team_reviewers,
+        }
+    }
+
+    fn create_pull_request(&self) -> Result<String> {
+        let plan = self
+            .journal
+            .pull_request
+            .as_ref()
+            .context("BUG: the pull request is planned when the release files are written")?;
+        self.sess
+            .repo
+            .check_upstream("open the release pull request")?;
+
+        if let Some(pr_url) = self.create_pull_request_with_token(plan)? {
+            return Ok(pr_url);
+        }
+
         let github =
             GitHubInformation::new(self.sess).context("failed to initialize GitHub client")?;
 
-        let pr_title = pr::generate_pr_title(projects);
-        let pr_body = pr::generate_pr_body(projects, manifest_filename, changelog_contents);
-
         let pr_url = github
-            .create_pull_request(&self.release_branch, &self.base_branch, &pr_title, &pr_body)
+            .create_pull_request(
+                &self.release_branch,
+                &self.base_branch,
+                &plan.title,
+                &plan.body,
+                &plan.reviewers,
+                &plan.team_reviewers,
+            )
             .context("failed to create pull request")?;
 
         Ok(pr_url)
     }
 
+    /// Open the pull request with the personal access token stored for
+    /// the upstream repository; `None` when there is none.
+    fn create_pull_request_with_token(&self, plan: &PullRequestPlan) -> Result<Option<String>> {
+        let upstream_url = self.sess.repo.upstream_url()?;
+        let Ok((owner, repo)) = parse_github_url(&upstream_url) else {
+            return Ok(None);
+        };
+        let Some((pat, _)) = pat::for_repo(&owner, &repo)? else {
+            return Ok(None);
+        };
+
+        let client = RestClient::new(&pat.token)?;
+        let future = client.create_pull_request(
+            &owner,
+            &repo,
+            &self.release_branch,
+            &self.base_branch,
+            &plan.title,
+            &plan.body,
+            &plan.reviewers,
+            &plan.team_reviewers,
+        );
+        let pr_url = match tokio::runtime::Handle::try_current() {
+            Ok(handle) => tokio::task::block_in_place(|| handle.block_on(future)),
+            Err(_) => {
+                let rt =
+                    tokio::runtime::Runtime::new().context("failed to create async runtime")?;
+                rt.block_on(future)
+            }
+        }
+        .context("failed to create pull request")?;
+        Ok(Some(pr_url))
+    }
+
     fn print_summary(&self, projects: &[SelectedReleaseUnit], pr_url: &str) {
         info!(
             "prepared {} project{} for release",
@@ -740,16 +1625,8 @@ impl<'a> ReleasePipeline<'a> {
     }
 
     fn get_github_compare_base_url(&self) -> Option<String> {
-        self.sess.repo.upstream_url().ok().and_then(|url| {
-            let url = url
-                .trim_end_matches(".git")
-                .replace("git@github.com:", "https://github.com/");
-            if url.contains("github.com") {
-                Some(url)
-            } else {
-                None
-            }
-        })
+        let url = self.sess.repo.upstream_url().ok()?;
+        forge::current().repo_url_for_remote(&url)
     }
 
     fn extract_contributors(commits: &[Commit]) -> Vec<String> {
@@ -828,19 +1705,92 @@ impl<'a> ReleasePipeline<'a> {
     }
 }
 
+/// Pre-flight for `prepare`: when credentials are available, confirm
+/// the belaf GitHub App is installed on the upstream repository before
+/// any files are rewritten, and that the upstream is one of `[repo]
+/// upstream_urls`. A personal access token stored for the
+/// repository is checked for the permissions it was found to have
+/// instead. Without credentials this is a no-op — the
+/// push step reports the missing login with its usual message. Failures
+/// that say nothing about access (network hiccups) are logged and left
+/// to the later steps.
+pub fn preflight_github_access(sess: &AppSession) -> Result<()> {
+    sess.repo.check_upstream("prepare a release")?;
+    let Ok(upstream_url) = sess.repo.upstream_url() else {
+        return Ok(());
+    };
+    let Ok((owner, repo)) = parse_github_url(&upstream_url) else {
+        return Ok(());
+    };
+    if let Some((_, capabilities)) = pat::for_repo(&owner, &repo)? {
+        return capabilities.require(&format!("{owner}/{repo}"), PREPARE_PERMISSIONS);
+    }
+
+    let future = async {
+        let api_client = ApiClient::try_new()?;
+        let Some(token) = load_or_exchange_token(&api_client).await? else {
+            return Ok(());
+        };
+        preflight(&api_client, &token, &owner, &repo).await
+    };
+
+    let result = match tokio::runtime::Handle::try_current() {
+        Ok(handle) => tokio::task::block_in_place(|| handle.block_on(future)),
+        Err(_) => {
+            let rt = tokio::runtime::Runtime::new().context("failed to create async runtime")?;
+            rt.block_on(future)
+        }
+    };
+
+    match result {
+        Ok(()) => Ok(()),
+        Err(e)
+            if e.downcast_ref::<AccessError>().is_some()
+                || matches!(e.downcast_ref::<ApiError>(), Some(ApiError::Unauthorized)) =>
+        {
+            Err(e)
+        }
+        Err(e) => {
+            warn!("skipping GitHub access pre-flight: {e:#}");
+            Ok(())
+        }
+    }
+}
+
 pub fn create_release_branch(sess: &mut AppSession) -> Result<(String, String)> {
-    let base_branch = sess
+    let current = sess
         .repo
         .current_branch_name()
-        .context("failed to get current branch")?
-        .ok_or_else(|| anyhow::anyhow!("not on a branch (detached HEAD state)"))?;
+        .context("failed to get current branch")?;
+    let base_branch = match current {
+        Some(branch) => branch,
+        None => {
+            let branch = sess.repo.detached_base_branch().ok_or_else(|| {
+                anyhow::anyhow!(
+                    "not on a branch (detached HEAD state); set `[git] base_branch` or \
+                     GITHUB_REF to the branch the release is for"
+                )
+            })?;
+            // CI checkouts have no local branch to return to once the
+            // release branch is pushed; start one at the detached commit.
+            if !sess.repo.branch_exists(&branch) {
+                sess.repo
+                    .create_branch(&branch)
+                    .with_context(|| format!("failed to create `{branch}` at HEAD"))?;
+            }
+            info!("HEAD is detached; preparing the release for `{branch}`");
+            branch
+        }
+    };
 
     let release_branch = Repository::generate_release_branch_name();
+    let journal = Journal::new(&sess.repo, base_branch.clone(), release_branch.clone())?;
     info!("creating release branch: {}", release_branch);
 
     sess.repo
         .create_branch(&release_branch)
         .context("failed to create release branch")?;
+    journal.save(&sess.repo)?;
     sess.repo
         .checkout_branch(&release_branch)
         .context("failed to checkout release branch")?;
@@ -848,7 +1798,37 @@ pub fn create_release_branch(sess: &mut AppSession) -> Result<(String, String)>
     Ok((base_branch, release_branch))
 }
 
+/// Undo a release that stopped before all its files were written: reset
+/// what it changed in the working tree, return to the base branch and
+/// delete the release branch.
+pub fn roll_back_release(sess: &mut AppSession, journal: &Journal) -> Result<()> {
+    if sess.repo.current_branch_name()?.as_deref() == Some(journal.release_branch.as_str()) {
+        let keep: Vec<RepoPathBuf> = journal
+            .preexisting_changes
+            .iter()
+            .map(|p| RepoPathBuf::new(p.as_bytes()))
+            .collect();
+        let discarded = sess
+            .repo
+            .discard_changes(&keep)
+            .context("failed to reset the files the release changed")?;
+        for path in &discarded {
+            info!("reset {}", path.escaped());
+        }
+        sess.repo
+            .checkout_branch(&journal.base_branch)
+            .with_context(|| format!("failed to checkout `{}`", journal.base_branch))?;
+    }
+    if sess.repo.branch_exists(&journal.release_branch) {
+        sess.repo.delete_branch(&journal.release_branch)?;
+    }
+    Journal::remove(&sess.repo);
+    Ok(())
+}
+
 pub fn cleanup_release_branch(sess: &mut AppSession, base_branch: &str, release_branch: &str) {
+    Journal::remove(&sess.repo);
+
     if let Err(e) = sess.repo.checkout_branch(base_branch) {
         tracing::warn!("failed to checkout base branch '{}': {}", base_branch, e);
     }
@@ -893,9 +1873,29 @@ fn build_tag_name(
     sess: &AppSession,
     project: &SelectedReleaseUnit,
     groups: &GroupSet,
+) -> Result<String> {
+    unit_tag_name(
+        sess,
+        project.ident,
+        &project.name,
+        &project.ecosystem,
+        &project.new_version,
+        groups,
+    )
+}
+
+/// [`build_tag_name`] for a release of unit `ident` at `version` that
+/// isn't prepared, e.g. a snapshot.
+pub fn unit_tag_name(
+    sess: &AppSession,
+    ident: ReleaseUnitId,
+    name: &str,
+    ecosystem: &Ecosystem,
+    version: &str,
+    groups: &GroupSet,
 ) -> Result<String> {
     let registry = FormatHandlerRegistry::with_defaults();
-    let eco_name = project.ecosystem.as_str();
+    let eco_name = ecosystem.as_str();
 
     // Bundle / synthetic ecosystems (`tauri`, `hexagonal-cargo`,
     // `jvm-library`) aren't `FormatHandler`-backed — they're
@@ -914,43 +1914,50 @@ fn build_tag_name(
     let unit_override = sess
         .resolved_release_units()
         .iter()
-        .find(|r| r.unit.name == project.name)
+        .find(|r| r.unit.name == name)
         .and_then(|r| r.unit.tag_format.as_deref());
-    let group_override = groups
-        .group_of(project.ident)
-        .and_then(|g| g.tag_format.as_deref());
+    let group_override = groups.group_of(ident).and_then(|g| g.tag_format.as_deref());
     let template = unit_override.or(group_override);
 
     let maven_coords = if eco_name == "maven" {
-        split_maven_coords(&project.name)
+        split_maven_coords(name)
     } else {
         None
     };
 
     let inputs = TagFormatInputs {
-        project_name: &project.name,
-        version: &project.new_version,
+        project_name: name,
+        version,
         ecosystem: eco_name,
         ecosystem_default: eco_default_tag,
         allowed_vars: eco_allowed_vars,
         override_template: template,
         maven_coords,
-        module_path: if eco_name == "go" {
-            Some(&project.name)
-        } else {
-            None
-        },
+        module_path: if eco_name == "go" { Some(name) } else { None },
     };
     format_tag(&inputs)
 }
 
 mod changelog_gen;
+mod dep_conflicts;
 mod github;
+mod journal;
+mod pending_conflicts;
+mod promote;
+mod release_edits;
+mod tag_conflicts;
 
 pub use changelog_gen::{
-    generate_and_write_project_changelog, generate_changelog_entry, ChangelogGenerationParams,
-    ChangelogResult,
+    changelog_path, generate_and_write_project_changelog, generate_changelog_entry, prepend_entry,
+    truncate_entry, ChangelogGenerationParams, ChangelogResult,
+};
+pub use dep_conflicts::{
+    ConflictPlanner, ConflictResolution, DepConflict, FollowUp, FollowUpReason,
 };
 pub use github::{extract_github_remote, load_github_token, GitHubRemoteInfo};
+pub use journal::{Journal, JournalStep, JournalUnit, PlannedCommit, PullRequestPlan};
+pub use promote::PromotedChangelog;
+pub use release_edits::{PlannedUnit, ReleaseEdits, UnitEdit};
+pub use tag_conflicts::{ExistingTags, TagConflict, TagConflictKind, TagLocation};
 
 use github::parse_github_url;
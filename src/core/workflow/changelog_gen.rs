@@ -9,8 +9,12 @@ use tracing::info;
 
 use crate::core::{
     bump::BumpConfig,
-    changelog::{Changelog, ChangelogConfig, Commit, GitConfig, Release},
+    changelog::{
+        dependency_updates, AuthorUsernames, Changelog, ChangelogConfig, Commit, EntryCache,
+        GitConfig, Release, RepoLinks,
+    },
     git::repository::{RepoPathBuf, Repository},
+    timings,
 };
 
 pub fn generate_changelog_entry(
@@ -20,8 +24,9 @@ pub fn generate_changelog_entry(
     changelog_config: &ChangelogConfig,
     bump_config: &BumpConfig,
 ) -> Result<String> {
+    let _timing = timings::stage("changelog rendering");
     if commits.is_empty() {
-        let now = time::OffsetDateTime::now_utc();
+        let now = crate::core::deterministic::now_utc();
         return Ok(format!(
             "## [{}] - {}\n\n\
             No user-facing changes in this release.\n\
@@ -31,11 +36,12 @@ pub fn generate_changelog_entry(
         ));
     }
 
-    let now = time::OffsetDateTime::now_utc();
+    let now = crate::core::deterministic::now_utc();
     let release = Release {
         version: Some(version.to_string()),
         commits: commits.to_vec(),
         timestamp: Some(now.unix_timestamp()),
+        release_date: Some(now.date().to_string()),
         ..Default::default()
     };
 
@@ -46,6 +52,7 @@ pub fn generate_changelog_entry(
         bump_config.clone(),
     )?;
     changelog.process_commits()?;
+    changelog.resolve_author_usernames(&mut AuthorUsernames::default());
 
     let mut output = Vec::new();
     changelog.generate(&mut output)?;
@@ -59,6 +66,10 @@ pub struct ChangelogResult {
     pub path: Option<RepoPathBuf>,
     pub has_user_changes: bool,
     pub processed_commits: Vec<Commit>,
+    /// The `[[changelog.outputs]]` files written next to `path`.
+    pub localized_paths: Vec<RepoPathBuf>,
+    /// Refreshing the Unreleased section left every file as it was.
+    pub unchanged: bool,
 }
 
 pub struct ChangelogGenerationParams<'a> {
@@ -75,11 +86,61 @@ pub struct ChangelogGenerationParams<'a> {
     pub github_owner: Option<&'a str>,
     pub github_repo: Option<&'a str>,
     pub github_token: Option<crate::core::api::StoredToken>,
+    /// The version of the unit's last release, for `previous_version`.
+    pub previous_version: Option<&'a str>,
+    /// The previous and the new release tag, for `previous_tag` and
+    /// `compare_url`.
+    pub tags: Option<(&'a str, &'a str)>,
+    /// Replace the file's Unreleased section instead of prepending an
+    /// entry (`belaf changelog --unreleased --write`).
+    pub replace_unreleased: bool,
+}
+
+/// Where a unit's changelog lives: `[changelog] output`, or
+/// `CHANGELOG.md`, in the unit's directory.
+pub fn changelog_path(changelog_config: &ChangelogConfig, prefix: &str) -> RepoPathBuf {
+    let file = changelog_config
+        .output
+        .as_ref()
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_else(|| "CHANGELOG.md".to_string());
+    let path = if prefix.is_empty() {
+        file
+    } else {
+        format!("{}/{}", prefix.trim_end_matches('/'), file)
+    };
+    RepoPathBuf::new(path.as_bytes())
+}
+
+/// Put `entry` above the newest release entry (the first `## ` heading)
+/// of `changelog`, below its title and preamble.
+pub fn prepend_entry(changelog: &str, entry: &str) -> String {
+    let lines: Vec<&str> = changelog.split_inclusive('\n').collect();
+    let first_entry = lines
+        .iter()
+        .position(|l| l.starts_with("## "))
+        .unwrap_or(lines.len());
+
+    let mut content: String = lines[..first_entry].concat();
+    if !content.is_empty() && !content.ends_with("\n\n") {
+        content.push_str(if content.ends_with('\n') {
+            "\n"
+        } else {
+            "\n\n"
+        });
+    }
+    content.push_str(entry);
+    if first_entry < lines.len() {
+        content.push('\n');
+        content.push_str(&lines[first_entry..].concat());
+    }
+    content
 }
 
 pub fn generate_and_write_project_changelog(
     params: &ChangelogGenerationParams,
 ) -> Result<ChangelogResult> {
+    let _timing = timings::stage("changelog rendering");
     let repo = params.repo;
     let project_name = params.project_name;
     let prefix = params.prefix;
@@ -91,7 +152,7 @@ pub fn generate_and_write_project_changelog(
     let write_to_file = params.write_to_file;
     let custom_output_path = params.custom_output_path;
     if commits.is_empty() {
-        let now = time::OffsetDateTime::now_utc();
+        let now = crate::core::deterministic::now_utc();
         let version_str = version.unwrap_or("Unreleased");
         let content = format!(
             "## [{}] - {}\n\nNo user-facing changes in this release.\n(Internal: docs, chore, ci, test, style)\n",
@@ -103,14 +164,19 @@ pub fn generate_and_write_project_changelog(
             path: None,
             has_user_changes: false,
             processed_commits: Vec::new(),
+            localized_paths: Vec::new(),
+            unchanged: false,
         });
     }
 
-    let now = time::OffsetDateTime::now_utc();
+    let now = crate::core::deterministic::now_utc();
     let release = Release {
         version: version.map(String::from),
         commits: commits.to_vec(),
         timestamp: Some(now.unix_timestamp()),
+        previous_version: params.previous_version.map(String::from),
+        previous_tag: params.tags.map(|(previous, _)| previous.to_string()),
+        release_date: Some(now.date().to_string()),
         ..Default::default()
     };
 
@@ -127,9 +193,27 @@ pub fn generate_and_write_project_changelog(
             changelog = changelog.with_github_token(token);
         }
     }
+    let remote = repo.upstream_url().ok();
+    if let Some(links) = RepoLinks::resolve(&changelog_config.link_style, remote.as_deref()) {
+        changelog = changelog.with_links(&links, params.tags);
+    }
+    if let Some(cache) = EntryCache::for_repo(repo, project_name) {
+        changelog = changelog.with_entry_cache(cache);
+    }
+    if let (true, Some((previous, _))) = (changelog_config.include_dependencies, params.tags) {
+        let from = repo.resolve_commitish(previous)?;
+        let to = repo.resolve_commitish("HEAD")?;
+        let updates = dependency_updates(repo, prefix, from, to)
+            .with_context(|| format!("failed to diff the lockfiles of `{project_name}`"))?;
+        changelog.add_context("dependencies", updates)?;
+    }
 
     changelog.process_commits()?;
     changelog.add_github_metadata_sync(None)?;
+    let usernames_cache = repo.git_dir().join(AuthorUsernames::CACHE_FILE);
+    let mut usernames = AuthorUsernames::load(&usernames_cache);
+    changelog.resolve_author_usernames(&mut usernames);
+    usernames.save(&usernames_cache);
 
     let commit_list = changelog
         .releases
@@ -138,7 +222,7 @@ pub fn generate_and_write_project_changelog(
         .unwrap_or_default();
 
     if commit_list.is_empty() {
-        let now = time::OffsetDateTime::now_utc();
+        let now = crate::core::deterministic::now_utc();
         let version_str = version.unwrap_or("Unreleased");
         let content = format!(
             "## [{}] - {}\n\nNo user-facing changes in this release.\n(Internal: docs, chore, ci, test, style)\n",
@@ -150,6 +234,8 @@ pub fn generate_and_write_project_changelog(
             path: None,
             has_user_changes: false,
             processed_commits: Vec::new(),
+            localized_paths: Vec::new(),
+            unchanged: false,
         });
     }
 
@@ -164,59 +250,278 @@ pub fn generate_and_write_project_changelog(
             path: None,
             has_user_changes: true,
             processed_commits: commit_list.clone(),
+            localized_paths: Vec::new(),
+            unchanged: false,
         });
     }
 
-    let default_output = changelog_config
-        .output
-        .as_ref()
-        .map(|p| p.to_string_lossy().to_string())
-        .unwrap_or_else(|| "CHANGELOG.md".to_string());
-
-    let changelog_rel_path = if let Some(path) = custom_output_path {
-        path.to_string()
-    } else if prefix.is_empty() {
-        default_output
-    } else {
-        let prefix = prefix.trim_end_matches('/');
-        format!("{}/{}", prefix, default_output)
+    let changelog_repo_path = match custom_output_path {
+        Some(path) => RepoPathBuf::new(path.as_bytes()),
+        None => changelog_path(changelog_config, prefix),
+    };
+    let write = |changelog: &Changelog, path: &RepoPathBuf| {
+        if params.replace_unreleased {
+            write_unreleased(repo, changelog, path)
+        } else {
+            write_prepended(repo, changelog, path).map(|()| true)
+        }
     };
+    let mut changed = write(&changelog, &changelog_repo_path)?;
+    info!(
+        "{}: wrote changelog to {}",
+        project_name,
+        changelog_repo_path.escaped()
+    );
 
-    let changelog_repo_path = RepoPathBuf::new(changelog_rel_path.as_bytes());
-    let changelog_full_path = repo.resolve_workdir(changelog_repo_path.as_ref());
+    // `--output` redirects a one-off changelog; the configured
+    // translations stay where they are.
+    let mut localized_paths = Vec::new();
+    if custom_output_path.is_none() {
+        for output in &changelog_config.outputs {
+            let localized = changelog.for_output(output)?;
+            let path = changelog_path(&localized.changelog_config, prefix);
+            changed |= write(&localized, &path)?;
+            info!(
+                "{}: wrote {} changelog to {}",
+                project_name,
+                output.language,
+                path.escaped()
+            );
+            localized_paths.push(path);
+        }
+    }
 
-    let existing_content = std::fs::read_to_string(&changelog_full_path).unwrap_or_default();
+    Ok(ChangelogResult {
+        content: generated_content,
+        path: Some(changelog_repo_path),
+        has_user_changes: true,
+        processed_commits: commit_list,
+        localized_paths,
+        unchanged: !changed,
+    })
+}
+
+/// Render `changelog`'s new entries above those already in the file at
+/// `path`, creating it as needed. A release entry takes the place of the
+/// file's Unreleased section.
+fn write_prepended(repo: &Repository, changelog: &Changelog, path: &RepoPathBuf) -> Result<()> {
+    let full_path = repo.resolve_workdir(path.as_ref());
+    let mut existing_content = std::fs::read_to_string(&full_path).unwrap_or_default();
+    if changelog.releases.iter().any(|r| r.version.is_some()) {
+        existing_content = remove_unreleased(&existing_content);
+    }
 
     let mut prepend_output = Vec::new();
     changelog.prepend(existing_content, &mut prepend_output)?;
     let final_content =
         String::from_utf8(prepend_output).context("changelog contains invalid UTF-8")?;
 
-    if let Some(parent) = changelog_full_path.parent() {
-        std::fs::create_dir_all(parent).with_context(|| {
-            format!(
-                "failed to create directory for {}",
-                changelog_full_path.display()
-            )
-        })?;
+    if let Some(parent) = full_path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create directory for {}", full_path.display()))?;
+    }
+
+    std::fs::write(&full_path, &final_content)
+        .with_context(|| format!("failed to write changelog to {}", full_path.display()))
+}
+
+/// Refresh the Unreleased section of the file at `path` with
+/// `changelog`'s entries, creating the file as needed. Returns whether
+/// the file changed.
+fn write_unreleased(repo: &Repository, changelog: &Changelog, path: &RepoPathBuf) -> Result<bool> {
+    let full_path = repo.resolve_workdir(path.as_ref());
+    let existing = std::fs::read_to_string(&full_path).unwrap_or_default();
+
+    let content = if existing.trim().is_empty() {
+        let mut output = Vec::new();
+        changelog.generate(&mut output)?;
+        String::from_utf8(output).context("changelog contains invalid UTF-8")?
+    } else {
+        replace_unreleased(&existing, &changelog.render_entries()?)
+    };
+    if content == existing {
+        return Ok(false);
     }
 
-    std::fs::write(&changelog_full_path, &final_content).with_context(|| {
-        format!(
-            "failed to write changelog to {}",
-            changelog_full_path.display()
-        )
+    if let Some(parent) = full_path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create directory for {}", full_path.display()))?;
+    }
+    std::fs::write(&full_path, &content)
+        .with_context(|| format!("failed to write changelog to {}", full_path.display()))?;
+    Ok(true)
+}
+
+/// The lines of `## Unreleased` (or `## [Unreleased]`) up to the next
+/// `## ` heading.
+fn unreleased_section(lines: &[&str]) -> Option<std::ops::Range<usize>> {
+    let start = lines.iter().position(|l| {
+        l.strip_prefix("## ").is_some_and(|title| {
+            title
+                .trim_start_matches('[')
+                .to_ascii_lowercase()
+                .starts_with("unreleased")
+        })
     })?;
+    let end = lines[start + 1..]
+        .iter()
+        .position(|l| l.starts_with("## "))
+        .map_or(lines.len(), |i| start + 1 + i);
+    Some(start..end)
+}
 
-    info!(
-        "{}: wrote changelog to {}",
-        project_name, changelog_rel_path
+/// Put `entry` in place of the Unreleased section of `changelog`, or
+/// above its newest release if it has none. Applying the same entry
+/// again changes nothing.
+pub fn replace_unreleased(changelog: &str, entry: &str) -> String {
+    let entry = format!("{}\n", entry.trim());
+    let lines: Vec<&str> = changelog.split_inclusive('\n').collect();
+    let Some(section) = unreleased_section(&lines) else {
+        return prepend_entry(changelog, &entry);
+    };
+
+    let mut content = lines[..section.start].concat();
+    content.push_str(&entry);
+    if section.end < lines.len() {
+        content.push('\n');
+        content.push_str(&lines[section.end..].concat());
+    }
+    content
+}
+
+/// `changelog` without its Unreleased section.
+pub fn remove_unreleased(changelog: &str) -> String {
+    let lines: Vec<&str> = changelog.split_inclusive('\n').collect();
+    match unreleased_section(&lines) {
+        Some(section) => [&lines[..section.start], &lines[section.end..]]
+            .concat()
+            .concat(),
+        None => changelog.to_owned(),
+    }
+}
+
+/// `entry` cut down to at most `max` bytes for a release PR body or
+/// release notes: whole lines are kept up to the budget, headings left
+/// without entries below them are dropped, and a closing line counts
+/// the list items left out and points at `full_changelog`, a link or a
+/// path. Entries within budget come back as they are.
+pub fn truncate_entry(entry: &str, max: usize, full_changelog: &str) -> String {
+    if entry.len() <= max {
+        return entry.to_string();
+    }
+    let is_item = |line: &str| line.starts_with("- ") || line.starts_with("* ");
+    let lines: Vec<&str> = entry.split_inclusive('\n').collect();
+    // Sized for every item being dropped, so the final line fits too.
+    let budget = max.saturating_sub(
+        truncation_note(lines.iter().filter(|l| is_item(l)).count(), full_changelog).len(),
     );
 
-    Ok(ChangelogResult {
-        content: generated_content,
-        path: Some(changelog_repo_path),
-        has_user_changes: true,
-        processed_commits: commit_list,
-    })
+    let mut kept = 0;
+    let mut size = 0;
+    for line in &lines {
+        if size + line.len() > budget {
+            break;
+        }
+        size += line.len();
+        kept += 1;
+    }
+    while kept > 0 && {
+        let line = lines[kept - 1].trim();
+        line.is_empty() || line.starts_with('#') || line.starts_with("**")
+    } {
+        kept -= 1;
+    }
+
+    let dropped = lines[kept..].iter().filter(|l| is_item(l)).count();
+    let mut truncated = lines[..kept].concat();
+    if !truncated.is_empty() {
+        truncated.truncate(truncated.trim_end().len());
+        truncated.push_str("\n\n");
+    }
+    truncated.push_str(&truncation_note(dropped, full_changelog));
+    truncated
+}
+
+fn truncation_note(dropped: usize, full_changelog: &str) -> String {
+    let link = if full_changelog.contains("://") {
+        format!("[full changelog]({full_changelog})")
+    } else {
+        format!("full changelog in `{full_changelog}`")
+    };
+    match dropped {
+        0 => format!("…see the {link} for the rest of this release.\n"),
+        1 => format!("…and 1 more commit, see the {link}.\n"),
+        n => format!("…and {n} more commits, see the {link}.\n"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entries_go_above_the_newest_release() {
+        let entry = "## [1.0.1] - 2026-10-16\n\nRebuilt for CVE-2026-1234.\n";
+        let changelog = "# Changelog\n\n## [1.0.0] - 2026-01-02\n\n- first\n";
+        assert_eq!(
+            prepend_entry(changelog, entry),
+            "# Changelog\n\n## [1.0.1] - 2026-10-16\n\nRebuilt for CVE-2026-1234.\n\n\
+             ## [1.0.0] - 2026-01-02\n\n- first\n"
+        );
+        assert_eq!(prepend_entry("", entry), entry);
+        assert_eq!(
+            prepend_entry("# Changelog\n", entry),
+            format!("# Changelog\n\n{entry}")
+        );
+    }
+
+    #[test]
+    fn unreleased_section_is_replaced_in_place() {
+        let changelog = "# Changelog\n\n## [1.0.0] - 2026-01-02\n\n- first\n";
+        let entry = "## Unreleased\n\n### Features\n\n- second\n";
+        let once = replace_unreleased(changelog, entry);
+        assert_eq!(
+            once,
+            "# Changelog\n\n## Unreleased\n\n### Features\n\n- second\n\n\
+             ## [1.0.0] - 2026-01-02\n\n- first\n"
+        );
+        assert_eq!(replace_unreleased(&once, entry), once);
+
+        let refreshed = replace_unreleased(&once, "## Unreleased\n\n- third\n\n");
+        assert_eq!(
+            refreshed,
+            "# Changelog\n\n## Unreleased\n\n- third\n\n## [1.0.0] - 2026-01-02\n\n- first\n"
+        );
+        assert_eq!(remove_unreleased(&refreshed), changelog);
+        assert_eq!(remove_unreleased(changelog), changelog);
+    }
+
+    #[test]
+    fn long_entries_are_cut_at_a_line_with_a_link_out() {
+        let fixes: String = (1..=8)
+            .map(|i| format!("- Fix timeout number {i}\n"))
+            .collect();
+        let entry = format!(
+            "## [2.0.0] - 2026-10-17\n\n### Features\n\n\
+             - Add streaming responses\n- Add retries\n\n### Bug Fixes\n\n{fixes}"
+        );
+        assert_eq!(truncate_entry(&entry, entry.len(), "CHANGELOG.md"), entry);
+
+        let url = "https://github.com/acme/widgets/blob/main/CHANGELOG.md";
+        let truncated = truncate_entry(&entry, 220, url);
+        assert!(truncated.len() <= 220, "{truncated}");
+        assert_eq!(
+            truncated,
+            format!(
+                "## [2.0.0] - 2026-10-17\n\n### Features\n\n\
+                 - Add streaming responses\n- Add retries\n\n\
+                 …and 8 more commits, see the [full changelog]({url}).\n"
+            )
+        );
+
+        assert_eq!(
+            truncate_entry(&entry, 10, "api/CHANGELOG.md"),
+            "…and 10 more commits, see the full changelog in `api/CHANGELOG.md`.\n"
+        );
+    }
 }
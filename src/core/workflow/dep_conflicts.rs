@@ -0,0 +1,570 @@
+//! Internal dependency requirements that a planned release would break.
+//!
+//! Preparing a release rewrites every internal dependency to the
+//! requirement belaf has for it: one rendered from the dependent's
+//! `dep_requirement` strategy, else a `manual:` requirement verbatim or
+//! one derived from a resolved commit requirement, and otherwise the
+//! literal already in the manifest. When that requirement does not accept the
+//! dependee's new version — typically an exact pin such as `=1.2.0` — the
+//! release commit doesn't build, and prepare only found out afterwards,
+//! from `cargo update` or CI. [`ConflictPlanner`] checks the plan before
+//! anything is written; each [`DepConflict`] is then settled with a
+//! [`ConflictResolution`].
+//!
+//! Only Cargo and npm requirements are checked, since both are semver
+//! ranges. Other ecosystems, and specs that aren't plain ranges
+//! (`workspace:*`, `file:..`, `||` alternatives), are never reported.
+//!
+//! The planner also lists the [`FollowUp`]s of a plan: units left out of
+//! the release that depend on one being released, or follow it through
+//! `cascade_from`, and may need a release of their own.
+
+use std::collections::HashMap;
+
+use semver::VersionReq;
+
+use crate::core::{
+    release_unit::{
+        cascade::{cascaded, BumpKind},
+        CascadeBumpStrategy,
+    },
+    resolved_release_unit::{DepRequirement, ReleaseUnitId},
+    rewriters::dep_requirement::DepRequirementStrategy,
+    session::AppSession,
+    version::Version,
+};
+
+/// A dependent whose requirement on a dependee rejects the dependee's
+/// planned version.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DepConflict {
+    pub dependent: ReleaseUnitId,
+    pub dependent_name: String,
+    pub dependee: ReleaseUnitId,
+    pub dependee_name: String,
+    /// The requirement the dependent's manifest would carry.
+    pub requirement: String,
+    pub old_version: String,
+    pub new_version: String,
+    /// What [`ConflictResolution::WidenRange`] writes instead.
+    pub widened: String,
+    /// What [`ConflictResolution::PinNewVersion`] writes instead.
+    pub pinned: String,
+}
+
+impl DepConflict {
+    /// One line for error messages, e.g. ``app requires core `=1.2.0`,
+    /// which rejects 1.3.0``.
+    pub fn describe(&self) -> String {
+        format!(
+            "{} requires {} `{}`, which rejects {}",
+            self.dependent_name, self.dependee_name, self.requirement, self.new_version
+        )
+    }
+
+    /// The requirement `resolution` writes into the dependent's manifest;
+    /// `None` for [`ConflictResolution::SkipProject`].
+    pub fn requirement_for(&self, resolution: ConflictResolution) -> Option<&str> {
+        match resolution {
+            ConflictResolution::WidenRange => Some(&self.widened),
+            ConflictResolution::PinNewVersion => Some(&self.pinned),
+            ConflictResolution::SkipProject => None,
+        }
+    }
+}
+
+/// A unit left out of the release that is tied to one being released.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FollowUp {
+    pub dependent: ReleaseUnitId,
+    pub dependent_name: String,
+    pub dependee_name: String,
+    pub new_version: String,
+    pub reason: FollowUpReason,
+}
+
+/// Why a [`FollowUp`] may need a release of its own.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FollowUpReason {
+    /// It depends on the dependee with this requirement. `accepts` says
+    /// whether the requirement takes the new version; `None` when belaf
+    /// rewrites the requirement or can't read it.
+    Requires {
+        requirement: String,
+        accepts: Option<bool>,
+    },
+    /// Its `cascade_from` rule asks for a bump of this size.
+    Cascade(BumpKind),
+}
+
+impl FollowUp {
+    /// One line for the wizard, e.g. ``cli pins core `=1.2.0`, which
+    /// rejects 1.3.0``.
+    pub fn describe(&self) -> String {
+        let (dependent, dependee, new) =
+            (&self.dependent_name, &self.dependee_name, &self.new_version);
+        match &self.reason {
+            FollowUpReason::Requires {
+                requirement,
+                accepts: Some(false),
+            } => format!("{dependent} requires {dependee} `{requirement}`, which rejects {new}"),
+            FollowUpReason::Requires {
+                requirement,
+                accepts: Some(true),
+            } => format!("{dependent} requires {dependee} `{requirement}`, which accepts {new}"),
+            FollowUpReason::Requires {
+                requirement,
+                accepts: None,
+            } => format!("{dependent} depends on {dependee} (`{requirement}`)"),
+            FollowUpReason::Cascade(bump) => format!(
+                "{dependent} cascades from {dependee}: a {} release is due",
+                bump.as_str()
+            ),
+        }
+    }
+
+    /// Whether leaving the unit out likely breaks something: a rejected
+    /// requirement or a due cascade bump.
+    pub fn needs_release(&self) -> bool {
+        matches!(
+            self.reason,
+            FollowUpReason::Requires {
+                accepts: Some(false),
+                ..
+            } | FollowUpReason::Cascade(_)
+        )
+    }
+}
+
+/// How to settle a [`DepConflict`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ConflictResolution {
+    /// Accept everything from the dependee's current version up to the
+    /// next breaking release after the new one.
+    #[default]
+    WidenRange,
+    /// Require exactly the new version.
+    PinNewVersion,
+    /// Leave the dependee (and its group) out of this release.
+    SkipProject,
+}
+
+impl ConflictResolution {
+    pub fn all() -> [Self; 3] {
+        [Self::WidenRange, Self::PinNewVersion, Self::SkipProject]
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::WidenRange => "widen range",
+            Self::PinNewVersion => "pin new version",
+            Self::SkipProject => "skip project",
+        }
+    }
+
+    /// The next option, wrapping around; the wizard cycles with ←/→.
+    pub fn next(self) -> Self {
+        match self {
+            Self::WidenRange => Self::PinNewVersion,
+            Self::PinNewVersion => Self::SkipProject,
+            Self::SkipProject => Self::WidenRange,
+        }
+    }
+
+    pub fn prev(self) -> Self {
+        self.next().next()
+    }
+}
+
+/// Requirement syntaxes the planner understands.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ReqSyntax {
+    Cargo,
+    Npm,
+}
+
+impl ReqSyntax {
+    fn for_ecosystem(ecosystem: &str) -> Option<Self> {
+        match ecosystem {
+            "cargo" => Some(Self::Cargo),
+            "npm" => Some(Self::Npm),
+            _ => None,
+        }
+    }
+
+    fn parse(self, text: &str) -> Option<VersionReq> {
+        let text = text.trim();
+        match self {
+            Self::Cargo => VersionReq::parse(text).ok(),
+            Self::Npm => {
+                if text.contains("||") || text.contains(':') {
+                    return None;
+                }
+                // A bare npm version is exact, and comparators are separated
+                // by spaces rather than commas.
+                if semver::Version::parse(text).is_ok() {
+                    return VersionReq::parse(&format!("={text}")).ok();
+                }
+                let comparators: Vec<&str> = text.split_whitespace().collect();
+                VersionReq::parse(&comparators.join(", ")).ok()
+            }
+        }
+    }
+
+    fn widen(self, old: &semver::Version, new: &semver::Version) -> String {
+        // Below 1.0 a minor bump is breaking, so that is where the range ends.
+        let upper = if new.major > 0 {
+            format!("{}.0.0", new.major + 1)
+        } else {
+            format!("0.{}.0", new.minor + 1)
+        };
+        match self {
+            Self::Cargo => format!(">={old}, <{upper}"),
+            Self::Npm => format!(">={old} <{upper}"),
+        }
+    }
+
+    fn pin(self, new: &semver::Version) -> String {
+        match self {
+            Self::Cargo => format!("={new}"),
+            Self::Npm => new.to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Requirement {
+    dependent: ReleaseUnitId,
+    dependee: ReleaseUnitId,
+    text: String,
+    /// `None` for requirements the planner doesn't check.
+    syntax: Option<ReqSyntax>,
+}
+
+#[derive(Clone, Debug)]
+struct Cascade {
+    dependent: ReleaseUnitId,
+    source: ReleaseUnitId,
+    strategy: CascadeBumpStrategy,
+}
+
+/// The graph's checkable internal requirements and current versions,
+/// detached from the session so the prepare wizard can recheck the plan
+/// whenever a bump changes.
+#[derive(Clone, Debug, Default)]
+pub struct ConflictPlanner {
+    requirements: Vec<Requirement>,
+    cascades: Vec<Cascade>,
+    units: HashMap<ReleaseUnitId, (String, Version)>,
+}
+
+impl ConflictPlanner {
+    pub fn new(sess: &AppSession) -> Self {
+        let mut planner = Self::default();
+        for unit in sess.graph().projects_slice() {
+            planner.units.insert(
+                unit.ident(),
+                (unit.user_facing_name.clone(), unit.version.clone()),
+            );
+            let syntax = unit
+                .qualified_names()
+                .get(1)
+                .and_then(|eco| ReqSyntax::for_ecosystem(eco));
+            // A strategy rewrites the requirement to match the new version.
+            let rewritten = sess
+                .dep_requirement_strategy(unit.ident())
+                .is_some_and(|s| s != DepRequirementStrategy::Keep);
+            for dep in &unit.internal_deps {
+                let (text, checked) = match &dep.belaf_requirement {
+                    _ if rewritten => (dep.literal.clone(), false),
+                    DepRequirement::Manual(t) => (t.clone(), true),
+                    // The rewriter derives these from the version the
+                    // commit resolved to.
+                    DepRequirement::Commit(_) if dep.resolved_version.is_some() => {
+                        (dep.literal.clone(), false)
+                    }
+                    _ => (dep.literal.clone(), true),
+                };
+                planner.requirements.push(Requirement {
+                    dependent: unit.ident(),
+                    dependee: dep.ident,
+                    text,
+                    syntax: syntax.filter(|_| checked),
+                });
+            }
+        }
+
+        let ident_of = |name: &str| {
+            sess.graph()
+                .projects_slice()
+                .iter()
+                .find(|u| u.user_facing_name == name)
+                .map(|u| u.ident())
+        };
+        for resolved in sess.resolved_release_units() {
+            let Some(rule) = &resolved.unit.cascade_from else {
+                continue;
+            };
+            if let (Some(dependent), Some(source)) =
+                (ident_of(&resolved.unit.name), ident_of(&rule.source))
+            {
+                planner.cascades.push(Cascade {
+                    dependent,
+                    source,
+                    strategy: rule.bump,
+                });
+            }
+        }
+        planner
+    }
+
+    /// The version each `(unit, bump)` moves to, for units that move.
+    fn planned(&self, bumps: &[(ReleaseUnitId, &str)]) -> HashMap<ReleaseUnitId, Version> {
+        let mut planned = HashMap::new();
+        for &(ident, bump) in bumps {
+            let Some((_, current)) = self.units.get(&ident) else {
+                continue;
+            };
+            // An exact custom version, or a bump of the current one.
+            let version = current
+                .parse_like(bump)
+                .or_else(|_| current.bumped(bump))
+                .ok()
+                .filter(|v| v != current);
+            if let Some(version) = version {
+                planned.insert(ident, version);
+            }
+        }
+        planned
+    }
+
+    fn name(&self, ident: ReleaseUnitId) -> String {
+        self.units
+            .get(&ident)
+            .map(|(name, _)| name.clone())
+            .unwrap_or_default()
+    }
+
+    /// The units `bumps` leaves out that depend on, or cascade from, a
+    /// unit it releases; arguments as for [`Self::conflicts`]. Sorted by
+    /// dependent.
+    pub fn follow_ups(&self, bumps: &[(ReleaseUnitId, &str)]) -> Vec<FollowUp> {
+        let planned = self.planned(bumps);
+        let mut follow_ups = Vec::new();
+
+        for req in &self.requirements {
+            let Some(new) = planned.get(&req.dependee) else {
+                continue;
+            };
+            if planned.contains_key(&req.dependent) {
+                continue;
+            }
+            let accepts = match (req.syntax, new) {
+                (Some(syntax), Version::Semver(new)) => {
+                    syntax.parse(&req.text).map(|parsed| parsed.matches(new))
+                }
+                _ => None,
+            };
+            follow_ups.push(FollowUp {
+                dependent: req.dependent,
+                dependent_name: self.name(req.dependent),
+                dependee_name: self.name(req.dependee),
+                new_version: new.to_string(),
+                reason: FollowUpReason::Requires {
+                    requirement: req.text.clone(),
+                    accepts,
+                },
+            });
+        }
+
+        for cascade in &self.cascades {
+            let (Some(new), Some((_, old))) = (
+                planned.get(&cascade.source),
+                self.units.get(&cascade.source),
+            ) else {
+                continue;
+            };
+            if planned.contains_key(&cascade.dependent) {
+                continue;
+            }
+            follow_ups.push(FollowUp {
+                dependent: cascade.dependent,
+                dependent_name: self.name(cascade.dependent),
+                dependee_name: self.name(cascade.source),
+                new_version: new.to_string(),
+                reason: FollowUpReason::Cascade(cascaded(cascade.strategy, bump_kind(old, new))),
+            });
+        }
+
+        follow_ups.sort_by(|a, b| a.dependent_name.cmp(&b.dependent_name));
+        follow_ups
+    }
+
+    /// The conflicts releasing each `(unit, bump)` would cause, where
+    /// `bump` is the resolved bump text (`"minor"`, `"no bump"`, …) or an
+    /// exact version. Bumps
+    /// that don't apply are ignored here; `finalize` reports them.
+    pub fn conflicts(&self, bumps: &[(ReleaseUnitId, &str)]) -> Vec<DepConflict> {
+        let planned = self.planned(bumps);
+
+        let mut conflicts = Vec::new();
+        for req in &self.requirements {
+            let Some(Version::Semver(new)) = planned.get(&req.dependee) else {
+                continue;
+            };
+            let Some((dependee_name, Version::Semver(old))) = self.units.get(&req.dependee) else {
+                continue;
+            };
+            let Some(syntax) = req.syntax else {
+                continue;
+            };
+            let Some(parsed) = syntax.parse(&req.text) else {
+                continue;
+            };
+            if parsed.matches(new) {
+                continue;
+            }
+            conflicts.push(DepConflict {
+                dependent: req.dependent,
+                dependent_name: self.name(req.dependent),
+                dependee: req.dependee,
+                dependee_name: dependee_name.clone(),
+                requirement: req.text.clone(),
+                old_version: old.to_string(),
+                new_version: new.to_string(),
+                widened: syntax.widen(old, new),
+                pinned: syntax.pin(new),
+            });
+        }
+        conflicts
+    }
+}
+
+/// The size of the bump from `old` to `new`; a patch for versions that
+/// aren't both semver.
+fn bump_kind(old: &Version, new: &Version) -> BumpKind {
+    match (old, new) {
+        (Version::Semver(old), Version::Semver(new)) if new.major != old.major => BumpKind::Major,
+        (Version::Semver(old), Version::Semver(new)) if new.minor != old.minor => BumpKind::Minor,
+        (Version::Semver(old), Version::Semver(new))
+            if new.patch == old.patch && !new.pre.is_empty() =>
+        {
+            BumpKind::Prerelease
+        }
+        _ => BumpKind::Patch,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn planner(requirements: &[(&str, ReqSyntax)]) -> ConflictPlanner {
+        let mut p = ConflictPlanner::default();
+        p.units.insert(
+            0,
+            ("core".into(), Version::Semver("1.2.0".parse().unwrap())),
+        );
+        for (i, (text, syntax)) in requirements.iter().enumerate() {
+            let ident = i + 1;
+            p.units.insert(
+                ident,
+                (
+                    format!("app{ident}"),
+                    Version::Semver("0.1.0".parse().unwrap()),
+                ),
+            );
+            p.requirements.push(Requirement {
+                dependent: ident,
+                dependee: 0,
+                text: (*text).into(),
+                syntax: Some(*syntax),
+            });
+        }
+        p
+    }
+
+    #[test]
+    fn exact_pins_conflict_and_ranges_do_not() {
+        let p = planner(&[
+            ("=1.2.0", ReqSyntax::Cargo),
+            ("^1.2", ReqSyntax::Cargo),
+            ("1.2.0", ReqSyntax::Npm),
+            (">=1.0.0 <2.0.0", ReqSyntax::Npm),
+            ("workspace:*", ReqSyntax::Npm),
+        ]);
+        let conflicts = p.conflicts(&[(0, "minor")]);
+        let dependents: Vec<_> = conflicts.iter().map(|c| c.dependent).collect();
+        assert_eq!(dependents, vec![1, 3]);
+
+        let cargo = &conflicts[0];
+        assert_eq!(cargo.new_version, "1.3.0");
+        assert_eq!(cargo.widened, ">=1.2.0, <2.0.0");
+        assert_eq!(cargo.pinned, "=1.3.0");
+        assert_eq!(
+            cargo.describe(),
+            "app1 requires core `=1.2.0`, which rejects 1.3.0"
+        );
+        assert_eq!(conflicts[1].widened, ">=1.2.0 <2.0.0");
+        assert_eq!(conflicts[1].pinned, "1.3.0");
+    }
+
+    #[test]
+    fn major_bumps_break_caret_ranges_and_unbumped_units_never_conflict() {
+        let p = planner(&[("^1.2", ReqSyntax::Cargo)]);
+        assert_eq!(p.conflicts(&[(0, "major")]).len(), 1);
+        assert!(p.conflicts(&[(0, "no bump")]).is_empty());
+        assert!(p.conflicts(&[(1, "major")]).is_empty());
+        assert_eq!(p.conflicts(&[(0, "2.0.0")]).len(), 1);
+        assert!(p.conflicts(&[(0, "1.9.0")]).is_empty());
+    }
+
+    #[test]
+    fn follow_ups_list_left_out_dependents_and_due_cascades() {
+        let mut p = planner(&[("=1.2.0", ReqSyntax::Cargo), ("^1.2", ReqSyntax::Cargo)]);
+        p.units
+            .insert(3, ("sdk".into(), Version::Semver("0.4.0".parse().unwrap())));
+        p.cascades.push(Cascade {
+            dependent: 3,
+            source: 0,
+            strategy: CascadeBumpStrategy::FloorMinor,
+        });
+
+        let follow_ups = p.follow_ups(&[(0, "patch")]);
+        let lines: Vec<_> = follow_ups.iter().map(FollowUp::describe).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "app1 requires core `=1.2.0`, which rejects 1.2.1",
+                "app2 requires core `^1.2`, which accepts 1.2.1",
+                "sdk cascades from core: a minor release is due",
+            ]
+        );
+        let due: Vec<_> = follow_ups.iter().map(FollowUp::needs_release).collect();
+        assert_eq!(due, vec![true, false, true]);
+
+        // Released dependents, and plans that leave core alone, need nothing.
+        assert_eq!(p.follow_ups(&[(0, "patch"), (1, "patch")]).len(), 2);
+        assert!(p.follow_ups(&[(0, "no bump")]).is_empty());
+    }
+
+    #[test]
+    fn widening_below_one_stops_at_the_next_minor() {
+        let old = semver::Version::new(0, 3, 1);
+        let new = semver::Version::new(0, 4, 0);
+        assert_eq!(ReqSyntax::Cargo.widen(&old, &new), ">=0.3.1, <0.5.0");
+    }
+
+    #[test]
+    fn resolutions_cycle_through_all_options() {
+        let mut r = ConflictResolution::default();
+        for expected in ConflictResolution::all().iter().cycle().skip(1).take(3) {
+            r = r.next();
+            assert_eq!(r, *expected);
+        }
+        assert_eq!(
+            ConflictResolution::WidenRange.prev(),
+            ConflictResolution::SkipProject
+        );
+    }
+}
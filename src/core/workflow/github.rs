@@ -6,7 +6,7 @@
 
 use anyhow::Result;
 
-use crate::core::git::repository::Repository;
+use crate::core::{git::repository::Repository, github::forge};
 
 pub struct GitHubRemoteInfo {
     pub owner: String,
@@ -15,13 +15,9 @@ pub struct GitHubRemoteInfo {
 
 pub fn extract_github_remote(repo: &Repository) -> Option<GitHubRemoteInfo> {
     let upstream_url = repo.upstream_url().ok()?;
-    let parsed = git_url_parse::GitUrl::parse(&upstream_url).ok()?;
-    let provider: git_url_parse::types::provider::GenericProvider = parsed.provider_info().ok()?;
+    let (owner, repo) = forge::current().parse_remote(&upstream_url).ok()?;
 
-    Some(GitHubRemoteInfo {
-        owner: provider.owner().to_string(),
-        repo: provider.repo().to_string(),
-    })
+    Some(GitHubRemoteInfo { owner, repo })
 }
 
 pub fn load_github_token() -> Option<crate::core::api::StoredToken> {
@@ -31,22 +27,8 @@ pub fn load_github_token() -> Option<crate::core::api::StoredToken> {
         .filter(|t| !t.is_expired())
 }
 
+/// `(owner, repo)` of a remote on the configured forge (github.com or
+/// the `[forge]` GitHub Enterprise host).
 pub(super) fn parse_github_url(url: &str) -> Result<(String, String)> {
-    if let Some(rest) = url.strip_prefix("git@github.com:") {
-        let repo = rest.trim_end_matches(".git");
-        let parts: Vec<&str> = repo.split('/').collect();
-        if parts.len() == 2 {
-            return Ok((parts[0].to_string(), parts[1].to_string()));
-        }
-    }
-
-    if let Some(rest) = url.strip_prefix("https://github.com/") {
-        let repo = rest.trim_end_matches(".git");
-        let parts: Vec<&str> = repo.split('/').collect();
-        if parts.len() >= 2 {
-            return Ok((parts[0].to_string(), parts[1].to_string()));
-        }
-    }
-
-    anyhow::bail!("Could not parse GitHub URL: {}", url)
+    forge::current().parse_remote(url)
 }
@@ -0,0 +1,155 @@
+//! The step journal that makes an interrupted release resumable.
+//!
+//! Preparing a release creates a branch, writes files, commits, pushes
+//! and opens a PR. A failure part way through — a rejected push, an
+//! expired token — used to leave the working tree on a half-finished
+//! release branch, and running `prepare` again started over on top of
+//! it. The pipeline now records each completed step in
+//! `.git/belaf/release-journal.json`, outside the working tree, and
+//! `belaf prepare --resume` continues from the last one. A run that
+//! stopped while writing files can't be continued; resuming it rolls the
+//! working tree back to the base branch instead.
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::core::git::repository::Repository;
+
+const JOURNAL_FILE: &str = "belaf/release-journal.json";
+
+/// The last step of a release that completed.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum JournalStep {
+    /// The release branch exists; files may be partly written.
+    BranchCreated,
+    /// Every file of the release is written.
+    FilesWritten,
+    Committed,
+    Pushed,
+}
+
+impl JournalStep {
+    pub fn describe(self) -> &'static str {
+        match self {
+            JournalStep::BranchCreated => "before all release files were written",
+            JournalStep::FilesWritten => "before the release commit",
+            JournalStep::Committed => "before pushing the release branch",
+            JournalStep::Pushed => "before opening the pull request",
+        }
+    }
+}
+
+/// The pull request a release opens, fixed once its files are written.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PullRequestPlan {
+    pub title: String,
+    pub body: String,
+    pub reviewers: Vec<String>,
+    pub team_reviewers: Vec<String>,
+}
+
+/// A commit the release makes.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PlannedCommit {
+    pub message: String,
+    /// Paths the commit stages.
+    pub paths: Vec<String>,
+}
+
+/// A release unit of the release, for status output.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct JournalUnit {
+    pub name: String,
+    pub bump: String,
+}
+
+/// A release in progress.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Journal {
+    pub base_branch: String,
+    pub release_branch: String,
+    pub step: JournalStep,
+    /// Paths that were already modified or untracked when the branch was
+    /// created; a rollback leaves them alone.
+    #[serde(default)]
+    pub preexisting_changes: Vec<String>,
+    #[serde(default)]
+    pub release_units: Vec<JournalUnit>,
+    /// The release commits, in order; one per release unit with
+    /// `[git] release_commits = "per_unit"`.
+    #[serde(default)]
+    pub commits: Vec<PlannedCommit>,
+    /// How many of `commits` are made.
+    #[serde(default)]
+    pub commits_made: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pull_request: Option<PullRequestPlan>,
+}
+
+impl Journal {
+    pub fn new(repo: &Repository, base_branch: String, release_branch: String) -> Result<Self> {
+        let preexisting_changes = repo
+            .changed_paths()
+            .context("failed to check repository for modified files")?
+            .iter()
+            .map(|p| p.escaped())
+            .collect();
+        Ok(Self {
+            base_branch,
+            release_branch,
+            step: JournalStep::BranchCreated,
+            preexisting_changes,
+            release_units: Vec::new(),
+            commits: Vec::new(),
+            commits_made: 0,
+            pull_request: None,
+        })
+    }
+
+    fn path(repo: &Repository) -> PathBuf {
+        repo.git_dir().join(JOURNAL_FILE)
+    }
+
+    /// The journal of an unfinished release, if there is one.
+    pub fn load(repo: &Repository) -> Result<Option<Self>> {
+        let path = Self::path(repo);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let json = fs::read_to_string(&path)
+            .with_context(|| format!("failed to read `{}`", path.display()))?;
+        let journal = serde_json::from_str(&json)
+            .with_context(|| format!("failed to parse `{}`", path.display()))?;
+        Ok(Some(journal))
+    }
+
+    pub fn save(&self, repo: &Repository) -> Result<()> {
+        let path = Self::path(repo);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("failed to create `{}`", dir.display()))?;
+        }
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(&path, json).with_context(|| format!("failed to write `{}`", path.display()))
+    }
+
+    /// Record that `step` completed.
+    pub fn record(&mut self, repo: &Repository, step: JournalStep) -> Result<()> {
+        self.step = step;
+        self.save(repo)
+    }
+
+    /// Forget the release, once it finished or was rolled back.
+    pub fn remove(repo: &Repository) {
+        let path = Self::path(repo);
+        if let Err(e) = fs::remove_file(&path) {
+            if e.kind() != std::io::ErrorKind::NotFound {
+                tracing::warn!("failed to remove `{}`: {e}", path.display());
+            }
+        }
+    }
+}
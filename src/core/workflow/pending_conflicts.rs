@@ -0,0 +1,122 @@
+//! Open release PRs that a new release would overlap.
+//!
+//! Two release PRs for the same unit bump its version twice once both
+//! merge. [`find`] names the open PRs that cover a candidate, directly or
+//! through its group, so prepare can refuse to start; `--force` prepares
+//! another release anyway.
+
+use crate::core::github::pending::PendingRelease;
+
+/// A unit about to be released, as [`find`] sees it.
+#[derive(Clone, Copy, Debug)]
+pub struct Candidate<'a> {
+    pub name: &'a str,
+    /// Id of the group the unit is a member of.
+    pub group: Option<&'a str>,
+}
+
+/// Describe the open release PRs that cover one of `candidates`, unless
+/// `force` is set. A PR whose units could not be determined conflicts
+/// with everything: preparing on top of it would bump the same versions
+/// twice.
+pub fn find(
+    pending: &[PendingRelease],
+    candidates: &[Candidate<'_>],
+    force: bool,
+) -> Option<String> {
+    if force {
+        return None;
+    }
+    let mut lines = Vec::new();
+
+    for pr in pending {
+        let overlapping: Vec<&str> = candidates
+            .iter()
+            .filter(|c| {
+                pr.units.is_empty()
+                    || pr.covers(c.name)
+                    || c.group.is_some_and(|g| pr.groups.iter().any(|id| id == g))
+            })
+            .map(|c| c.name)
+            .collect();
+        if overlapping.is_empty() {
+            continue;
+        }
+
+        let link = pr.url.as_deref().unwrap_or(&pr.branch);
+        let units = if pr.units.is_empty() {
+            "unknown units".to_string()
+        } else {
+            overlapping.join(", ")
+        };
+        lines.push(format!("  • #{} ({units}): {link}", pr.number));
+    }
+
+    if lines.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "a release is already pending for some of these units:\n{}\n\n\
+         Merge or close the open release PR first, or rerun with --force to \
+         prepare another release anyway.",
+        lines.join("\n")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pr(number: i64, units: &[&str], groups: &[&str]) -> PendingRelease {
+        PendingRelease {
+            number,
+            title: None,
+            url: Some(format!("https://github.com/o/r/pull/{number}")),
+            branch: format!("release/{number}"),
+            checks: None,
+            mergeable: None,
+            units: units.iter().map(|u| u.to_string()).collect(),
+            groups: groups.iter().map(|g| g.to_string()).collect(),
+            planned: Vec::new(),
+        }
+    }
+
+    const API: Candidate<'static> = Candidate {
+        name: "api",
+        group: None,
+    };
+    const CORE: Candidate<'static> = Candidate {
+        name: "core",
+        group: Some("sdk"),
+    };
+
+    #[test]
+    fn a_pr_on_the_same_unit_blocks() {
+        let message = find(&[pr(7, &["api"], &[])], &[API, CORE], false).unwrap();
+        assert!(
+            message.contains("  • #7 (api): https://github.com/o/r/pull/7"),
+            "{message}"
+        );
+        assert!(message.contains("--force"), "{message}");
+        assert_eq!(find(&[pr(7, &["web"], &[])], &[API, CORE], false), None);
+    }
+
+    #[test]
+    fn a_pr_on_another_group_member_blocks() {
+        let message = find(&[pr(8, &["core-wasm"], &["sdk"])], &[API, CORE], false).unwrap();
+        assert!(message.contains("  • #8 (core): "), "{message}");
+        assert!(!message.contains("api"), "{message}");
+    }
+
+    #[test]
+    fn a_pr_with_unknown_units_blocks_everything() {
+        let message = find(&[pr(9, &[], &[])], &[API], false).unwrap();
+        assert!(message.contains("  • #9 (unknown units): "), "{message}");
+    }
+
+    #[test]
+    fn force_prepares_anyway() {
+        let pending = [pr(7, &["api"], &[]), pr(9, &[], &[])];
+        assert_eq!(find(&pending, &[API, CORE], true), None);
+    }
+}
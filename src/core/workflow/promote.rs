@@ -0,0 +1,353 @@
+//! Promoting a prerelease to the stable release it leads to.
+//!
+//! `belaf promote <unit>` releases `1.3.0` from a unit whose latest tag is
+//! `1.3.0-rc.2` (or `-beta.1`, any semver prerelease) and that has no
+//! commits since. The release goes through the same pipeline as
+//! `prepare` — manifest, release commit, PR — so the GitHub App tags and
+//! publishes it when the PR merges. Only the changelog differs: instead
+//! of an entry generated from commits, the prerelease entries at the top
+//! of the unit's changelog are merged into one entry for the stable
+//! version.
+
+use anyhow::{Context, Result};
+use tracing::info;
+
+use super::{
+    changelog_gen::changelog_path, PrepareContext, ReleasePipeline, ReleaseUnitCandidate,
+    SelectedReleaseUnit,
+};
+use crate::core::{
+    bump::BumpRecommendation, changelog::ChangelogConfig, git::repository::RepoPathBuf,
+    github::codeowners::CodeOwners, resolved_release_unit::ReleaseUnitId,
+    rewriters::dep_requirement, wire::known::Ecosystem,
+};
+
+/// A unit's changelog with its prerelease entries merged.
+#[derive(Clone, Debug)]
+pub struct PromotedChangelog {
+    pub path: RepoPathBuf,
+    /// The stable entry, for the manifest and the PR body.
+    pub entry: String,
+    /// The whole file, the prerelease entries replaced by `entry`.
+    pub content: String,
+}
+
+/// The stable version a prerelease leads to: `1.3.0-rc.2` → `1.3.0`.
+/// `None` when `version` isn't a prerelease.
+pub fn stable_version_of(version: &semver::Version) -> Option<semver::Version> {
+    if version.pre.is_empty() {
+        return None;
+    }
+    Some(semver::Version::new(
+        version.major,
+        version.minor,
+        version.patch,
+    ))
+}
+
+/// The version a changelog heading is for, e.g. `1.3.0-rc.1` in
+/// `## [1.3.0-rc.1] - 2026-01-02` or `## v1.3.0-rc.1 (2026-01-02)`.
+fn heading_version(heading: &str) -> Option<(&str, semver::Version)> {
+    heading
+        .split(|c: char| c.is_whitespace() || "[]()".contains(c))
+        .filter_map(|token| {
+            let text = token.strip_prefix('v').unwrap_or(token);
+            Some((token, semver::Version::parse(text).ok()?))
+        })
+        .next()
+}
+
+/// Merge the entries for prereleases of `stable` at the top of
+/// `changelog` into one entry for `stable`, dated `date`. Sections with
+/// the same `###` heading are combined and repeated lines dropped. The
+/// newest entry's heading is kept with the version and date replaced.
+/// Without such entries, `stable` gets a short entry saying what it was
+/// promoted from. Returns the entry and the new changelog.
+pub fn merge_prerelease_entries(
+    changelog: &str,
+    prerelease: &semver::Version,
+    stable: &semver::Version,
+    date: &str,
+) -> (String, String) {
+    let lines: Vec<&str> = changelog.split_inclusive('\n').collect();
+    let first_entry = lines
+        .iter()
+        .position(|l| l.starts_with("## "))
+        .unwrap_or(lines.len());
+
+    let mut heading = None;
+    let mut sections: Vec<(Option<&str>, Vec<&str>)> = vec![(None, Vec::new())];
+    let mut end = first_entry;
+    let mut current = 0;
+    for (i, line) in lines.iter().enumerate().skip(first_entry) {
+        if line.starts_with("## ") {
+            let is_prerelease = heading_version(line).is_some_and(|(_, v)| {
+                !v.pre.is_empty() && stable_version_of(&v).as_ref() == Some(stable)
+            });
+            if !is_prerelease {
+                break;
+            }
+            heading.get_or_insert(*line);
+            current = 0;
+        } else if let Some(title) = line.strip_prefix("### ") {
+            let title = title.trim_end();
+            current = match sections.iter().position(|(t, _)| *t == Some(title)) {
+                Some(idx) => idx,
+                None => {
+                    sections.push((Some(title), Vec::new()));
+                    sections.len() - 1
+                }
+            };
+        } else {
+            let line = line.trim_end();
+            let body = &mut sections[current].1;
+            if !line.is_empty() && !body.contains(&line) {
+                body.push(line);
+            }
+        }
+        end = i + 1;
+    }
+
+    let entry = match heading {
+        Some(heading) => {
+            let (token, _) = heading_version(heading).expect("BUG: heading was matched by version");
+            let version = token.strip_prefix('v').unwrap_or(token);
+            let date_re =
+                regex::Regex::new(r"\d{4}-\d{2}-\d{2}").expect("BUG: literal regex compiles");
+            let heading = heading.trim_end().replacen(version, &stable.to_string(), 1);
+            let mut entry = format!("{}\n", date_re.replace(&heading, date));
+            for (title, body) in &sections {
+                if body.is_empty() {
+                    continue;
+                }
+                if let Some(title) = title {
+                    entry.push_str(&format!("\n### {title}\n"));
+                }
+                entry.push('\n');
+                for line in body {
+                    entry.push_str(line);
+                    entry.push('\n');
+                }
+            }
+            entry
+        }
+        None => format!("## [{stable}] - {date}\n\nPromoted from {prerelease}.\n"),
+    };
+
+    let mut content: String = lines[..first_entry].concat();
+    if !content.is_empty() && !content.ends_with("\n\n") {
+        content.push('\n');
+    }
+    content.push_str(&entry);
+    if end < lines.len() {
+        content.push('\n');
+        content.push_str(&lines[end..].concat());
+    }
+    (entry, content)
+}
+
+impl PrepareContext<'_> {
+    /// Release each unit in `names` at the stable version of its latest
+    /// prerelease tag; see the module docs. Every member of a group must
+    /// be listed. Returns the release PR's URL.
+    pub fn promote(mut self, names: &[String]) -> Result<String> {
+        let prepared = match self.plan_promotions(names) {
+            Ok(prepared) => prepared,
+            Err(e) => {
+                self.cleanup();
+                return Err(e);
+            }
+        };
+
+        let released: Vec<ReleaseUnitId> = prepared.iter().map(|p| p.ident).collect();
+        dep_requirement::apply_strategies(self.sess, &released);
+
+        let pipeline = ReleasePipeline::new(self.sess, self.base_branch, self.release_branch)?
+            .for_command("promote");
+        pipeline.execute(prepared)
+    }
+
+    fn plan_promotions(&mut self, names: &[String]) -> Result<Vec<SelectedReleaseUnit>> {
+        let histories = self
+            .sess
+            .analyze_histories()
+            .context("failed to analyze project histories")?;
+        let graph = self.sess.graph();
+
+        let mut idents = Vec::new();
+        for name in names {
+            let Some(ident) = graph.lookup_ident(name) else {
+                anyhow::bail!("no release unit named `{name}`");
+            };
+            idents.push(ident);
+        }
+        for (name, ident) in names.iter().zip(&idents) {
+            let Some(group) = graph.groups().group_of(*ident) else {
+                continue;
+            };
+            let missing: Vec<&str> = group
+                .members
+                .iter()
+                .filter(|m| !idents.contains(m))
+                .map(|m| graph.lookup(*m).user_facing_name.as_str())
+                .collect();
+            if !missing.is_empty() {
+                anyhow::bail!(
+                    "{name} releases together with group `{}`; promote {} as well",
+                    group.id.as_str(),
+                    missing.join(", ")
+                );
+            }
+        }
+
+        let changelog_config = ChangelogConfig::from_user_config(&self.changelog_config);
+        let codeowners = CodeOwners::load(&self.sess.repo);
+        let today = crate::core::deterministic::now_utc().date().to_string();
+        let mut prepared = Vec::new();
+
+        for (name, &ident) in names.iter().zip(&idents) {
+            let history = histories.lookup(ident);
+            let Some(tagged) = history.release_version() else {
+                anyhow::bail!("{name} has no release tag to promote");
+            };
+            let Some(stable) = stable_version_of(tagged) else {
+                anyhow::bail!("the latest release of {name}, {tagged}, is not a prerelease");
+            };
+            if history.n_commits() > 0 {
+                anyhow::bail!(
+                    "{name} has {} commit(s) since {tagged}; release them as another \
+                     prerelease with `belaf prepare` first, so that the stable release \
+                     ships what was tested",
+                    history.n_commits()
+                );
+            }
+
+            let unit = self.sess.graph().lookup(ident);
+            let version = unit
+                .version
+                .parse_like(stable.to_string())
+                .with_context(|| format!("cannot express {stable} as a version of {name}"))?;
+            let prefix = unit.prefix().escaped();
+            let ecosystem = unit
+                .qualified_names()
+                .get(1)
+                .map(|s| Ecosystem::classify(s))
+                .unwrap_or_else(|| Ecosystem::classify("cargo"));
+            let owners = codeowners.owners_of(unit.prefix()).to_vec();
+
+            let path = changelog_path(&changelog_config, &prefix);
+            let existing =
+                std::fs::read_to_string(self.sess.repo.resolve_workdir(&path)).unwrap_or_default();
+            let (entry, content) = merge_prerelease_entries(&existing, tagged, &stable, &today);
+
+            self.candidates.push(ReleaseUnitCandidate {
+                ident,
+                name: name.clone(),
+                prefix: prefix.clone(),
+                current_version: unit.version.to_string(),
+                version: unit.version.clone(),
+                commits: Vec::new(),
+                commit_count: 0,
+                suggested_bump: BumpRecommendation::None,
+                bump_reasons: Vec::new(),
+                api_bump: None,
+                ecosystem: ecosystem.clone(),
+                manual_bump_from: None,
+                release_line: None,
+                scopes: self.sess.allowed_scopes(ident).to_vec(),
+            });
+            info!("{name}: {tagged} -> {stable} (promoted)");
+
+            prepared.push(SelectedReleaseUnit {
+                ident,
+                name: name.clone(),
+                prefix,
+                old_version: tagged.to_string(),
+                new_version: version.to_string(),
+                bump_type: "promote".to_string(),
+                commits: Vec::new(),
+                ecosystem,
+                cached_changelog: None,
+                owners,
+                promoted_changelog: Some(PromotedChangelog {
+                    path,
+                    entry,
+                    content,
+                }),
+                checklist: self.sess.release_checklist(ident).to_vec(),
+            });
+            self.sess.graph_mut().lookup_mut(ident).version = version;
+        }
+
+        if let Some(message) = self.pending_release_conflicts(false) {
+            anyhow::bail!(message);
+        }
+        Ok(prepared)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(text: &str) -> semver::Version {
+        semver::Version::parse(text).unwrap()
+    }
+
+    const CHANGELOG: &str = "# Changelog\n\n\
+        ## [1.3.0-rc.2] - 2026-03-02\n\n\
+        ### Bug Fixes\n\n\
+        - Handle empty input\n\n\
+        ## [1.3.0-rc.1] - 2026-03-01\n\n\
+        ### Features\n\n\
+        - Add streaming\n\n\
+        ### Bug Fixes\n\n\
+        - Handle empty input\n\
+        - Fix timeout\n\n\
+        ## [1.2.0] - 2026-01-01\n\n\
+        - Older\n";
+
+    #[test]
+    fn prerelease_entries_merge_into_one_stable_entry() {
+        let (entry, content) =
+            merge_prerelease_entries(CHANGELOG, &v("1.3.0-rc.2"), &v("1.3.0"), "2026-04-01");
+        assert_eq!(
+            entry,
+            "## [1.3.0] - 2026-04-01\n\n\
+             ### Bug Fixes\n\n\
+             - Handle empty input\n\
+             - Fix timeout\n\n\
+             ### Features\n\n\
+             - Add streaming\n"
+        );
+        assert_eq!(
+            content,
+            format!("# Changelog\n\n{entry}\n## [1.2.0] - 2026-01-01\n\n- Older\n")
+        );
+    }
+
+    #[test]
+    fn without_prerelease_entries_a_short_entry_is_added() {
+        let changelog = "# Changelog\n\n## [1.2.0] - 2026-01-01\n\n- Older\n";
+        let (entry, content) =
+            merge_prerelease_entries(changelog, &v("1.3.0-beta.1"), &v("1.3.0"), "2026-04-01");
+        assert_eq!(
+            entry,
+            "## [1.3.0] - 2026-04-01\n\nPromoted from 1.3.0-beta.1.\n"
+        );
+        assert_eq!(
+            content,
+            format!("# Changelog\n\n{entry}\n{}", &changelog[13..])
+        );
+        assert_eq!(
+            merge_prerelease_entries("", &v("1.0.0-rc.1"), &v("1.0.0"), "2026-04-01").1,
+            "## [1.0.0] - 2026-04-01\n\nPromoted from 1.0.0-rc.1.\n"
+        );
+    }
+
+    #[test]
+    fn stable_versions_only_come_from_prereleases() {
+        assert_eq!(stable_version_of(&v("2.0.0-beta.3")), Some(v("2.0.0")));
+        assert_eq!(stable_version_of(&v("2.0.0")), None);
+    }
+}
@@ -0,0 +1,296 @@
+//! What a rerun of `prepare` changes about the release PR it replaces.
+//!
+//! Commits keep landing while a release PR waits for review. Preparing
+//! again with `--force` opens a new release PR next to the one still
+//! open, and reviewers had to compare the two by hand. The manifest on
+//! the open PR's branch is the plan of the earlier run (see
+//! [`PendingRelease::planned`]), so the plans are compared here: which
+//! commits are new, whose version moved, which units joined or dropped
+//! out. The prepare wizard lists the edits in its confirmation step and
+//! the new PR's body carries them as an edit log.
+
+use crate::core::{changelog::Commit, github::pending::PendingRelease};
+
+/// One release unit of the plan being prepared.
+#[derive(Clone, Copy, Debug)]
+pub struct PlannedUnit<'a> {
+    pub name: &'a str,
+    pub version: &'a str,
+    pub commits: &'a [Commit],
+}
+
+/// How one unit's release differs from the earlier plan.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UnitEdit {
+    /// Not part of the earlier release.
+    Added { name: String, version: String },
+    /// Part of the earlier release, left out of this one.
+    Dropped { name: String, version: String },
+    /// Released by both, at a different version or with more commits.
+    /// `new_commits` holds the subjects of the commits the earlier
+    /// release did not ship.
+    Changed {
+        name: String,
+        from: String,
+        to: String,
+        new_commits: Vec<String>,
+    },
+}
+
+impl UnitEdit {
+    pub fn describe(&self) -> String {
+        match self {
+            UnitEdit::Added { name, version } => format!("{name}: added at {version}"),
+            UnitEdit::Dropped { name, version } => {
+                format!("{name}: dropped (was {version})")
+            }
+            UnitEdit::Changed {
+                name,
+                from,
+                to,
+                new_commits,
+            } => {
+                let mut text = if from == to {
+                    format!("{name}: still {to}")
+                } else {
+                    format!("{name}: {from} -> {to}")
+                };
+                if !new_commits.is_empty() {
+                    let n = new_commits.len();
+                    text.push_str(&format!(
+                        ", {n} new commit{}",
+                        if n == 1 { "" } else { "s" }
+                    ));
+                }
+                text
+            }
+        }
+    }
+}
+
+/// The edits between an open release PR and the plan replacing it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReleaseEdits {
+    pub number: i64,
+    pub url: Option<String>,
+    pub edits: Vec<UnitEdit>,
+}
+
+impl ReleaseEdits {
+    /// Compare `pending`'s plan with `planned`. Units whose version and
+    /// commits are unchanged are left out.
+    pub fn between(pending: &PendingRelease, planned: &[PlannedUnit<'_>]) -> Self {
+        let mut edits = Vec::new();
+
+        for unit in planned {
+            let Some(previous) = pending.planned.iter().find(|p| p.name == unit.name) else {
+                edits.push(UnitEdit::Added {
+                    name: unit.name.to_owned(),
+                    version: unit.version.to_owned(),
+                });
+                continue;
+            };
+            // Without the earlier commit ids nothing can be called new.
+            let new_commits: Vec<String> = match &previous.commits {
+                Some(shipped) => unit
+                    .commits
+                    .iter()
+                    .filter(|c| !shipped.contains(&c.id))
+                    .map(|c| c.message.lines().next().unwrap_or_default().to_owned())
+                    .collect(),
+                None => Vec::new(),
+            };
+            if previous.new_version != unit.version || !new_commits.is_empty() {
+                edits.push(UnitEdit::Changed {
+                    name: unit.name.to_owned(),
+                    from: previous.new_version.clone(),
+                    to: unit.version.to_owned(),
+                    new_commits,
+                });
+            }
+        }
+
+        for previous in &pending.planned {
+            if !planned.iter().any(|u| u.name == previous.name) {
+                edits.push(UnitEdit::Dropped {
+                    name: previous.name.clone(),
+                    version: previous.new_version.clone(),
+                });
+            }
+        }
+
+        Self {
+            number: pending.number,
+            url: pending.url.clone(),
+            edits,
+        }
+    }
+
+    /// The edits against each open release PR whose plan shares a unit
+    /// with `planned`. PRs whose manifest could not be read are skipped.
+    pub fn against_pending(pending: &[PendingRelease], planned: &[PlannedUnit<'_>]) -> Vec<Self> {
+        pending
+            .iter()
+            .filter(|pr| {
+                pr.planned
+                    .iter()
+                    .any(|p| planned.iter().any(|u| u.name == p.name))
+            })
+            .map(|pr| Self::between(pr, planned))
+            .collect()
+    }
+
+    /// The edit log section of the release PR body.
+    pub fn to_markdown(&self) -> String {
+        let mut text = format!(
+            "Prepared again while #{} was open. Since then:\n\n",
+            self.number
+        );
+        if self.edits.is_empty() {
+            text.push_str("- nothing changed\n");
+        }
+        for edit in &self.edits {
+            match edit {
+                UnitEdit::Added { name, version } => {
+                    text.push_str(&format!("- **{name}**: added at `{version}`\n"));
+                }
+                UnitEdit::Dropped { name, version } => {
+                    text.push_str(&format!("- **{name}**: dropped (was `{version}`)\n"));
+                }
+                UnitEdit::Changed {
+                    name,
+                    from,
+                    to,
+                    new_commits,
+                } => {
+                    if from == to {
+                        text.push_str(&format!("- **{name}**: still `{to}`\n"));
+                    } else {
+                        text.push_str(&format!("- **{name}**: `{from}` → `{to}`\n"));
+                    }
+                    for subject in new_commits {
+                        text.push_str(&format!("  - new: {subject}\n"));
+                    }
+                }
+            }
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::github::pending::PlannedRelease;
+
+    fn commit(id: &str, message: &str) -> Commit {
+        Commit::new(id.to_owned(), message.to_owned())
+    }
+
+    fn pending(number: i64, planned: Vec<PlannedRelease>) -> PendingRelease {
+        PendingRelease {
+            number,
+            title: None,
+            url: None,
+            branch: "release/1".to_owned(),
+            checks: None,
+            mergeable: None,
+            units: planned.iter().map(|p| p.name.clone()).collect(),
+            groups: Vec::new(),
+            planned,
+        }
+    }
+
+    fn release(name: &str, version: &str, commits: Option<&[&str]>) -> PlannedRelease {
+        PlannedRelease {
+            name: name.to_owned(),
+            new_version: version.to_owned(),
+            commits: commits.map(|ids| ids.iter().map(|id| id.to_string()).collect()),
+        }
+    }
+
+    #[test]
+    fn lists_new_commits_version_changes_and_membership() {
+        let pr = pending(
+            12,
+            vec![
+                release("core", "1.1.0", Some(&["a"])),
+                release("utils", "0.3.1", Some(&["b"])),
+            ],
+        );
+        let core_commits = [commit("a", "feat: one"), commit("c", "feat!: two\n\nbody")];
+        let cli_commits = [commit("d", "fix: three")];
+        let planned = [
+            PlannedUnit {
+                name: "core",
+                version: "2.0.0",
+                commits: &core_commits,
+            },
+            PlannedUnit {
+                name: "cli",
+                version: "0.1.1",
+                commits: &cli_commits,
+            },
+        ];
+
+        let edits = ReleaseEdits::against_pending(
+            &[pr, pending(13, vec![release("web", "1.0.0", None)])],
+            &planned,
+        );
+        assert_eq!(edits.len(), 1);
+        assert_eq!(
+            edits[0].edits,
+            vec![
+                UnitEdit::Changed {
+                    name: "core".to_owned(),
+                    from: "1.1.0".to_owned(),
+                    to: "2.0.0".to_owned(),
+                    new_commits: vec!["feat!: two".to_owned()],
+                },
+                UnitEdit::Added {
+                    name: "cli".to_owned(),
+                    version: "0.1.1".to_owned(),
+                },
+                UnitEdit::Dropped {
+                    name: "utils".to_owned(),
+                    version: "0.3.1".to_owned(),
+                },
+            ]
+        );
+        assert_eq!(
+            edits[0].edits[0].describe(),
+            "core: 1.1.0 -> 2.0.0, 1 new commit"
+        );
+        let markdown = edits[0].to_markdown();
+        assert!(markdown.starts_with("Prepared again while #12 was open."));
+        assert!(markdown.contains("- **core**: `1.1.0` → `2.0.0`\n  - new: feat!: two\n"));
+    }
+
+    #[test]
+    fn unchanged_units_and_unknown_commits_are_not_edits() {
+        let pr = pending(
+            7,
+            vec![
+                release("core", "1.1.0", Some(&["a"])),
+                release("old", "2.0.0", None),
+            ],
+        );
+        let commits = [commit("a", "feat: one")];
+        let more = [commit("x", "fix: later")];
+        let planned = [
+            PlannedUnit {
+                name: "core",
+                version: "1.1.0",
+                commits: &commits,
+            },
+            PlannedUnit {
+                name: "old",
+                version: "2.0.0",
+                commits: &more,
+            },
+        ];
+        let edits = ReleaseEdits::between(&pr, &planned);
+        assert!(edits.edits.is_empty());
+        assert!(edits.to_markdown().contains("- nothing changed"));
+    }
+}
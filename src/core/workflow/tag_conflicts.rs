@@ -0,0 +1,195 @@
+//! Release tags that a planned release would collide with.
+//!
+//! The belaf GitHub App tags each release when its PR merges, so a tag
+//! name that already exists, or a version no newer than one already
+//! tagged, only surfaced after review — as a failed tag push or as a
+//! release that sorts below its predecessor. [`find`] checks the plan
+//! against the local tags and the upstream remote's before prepare
+//! writes anything.
+//!
+//! On a maintenance branch (see [`crate::core::release_line`]) only
+//! tags on its version line count: a `1.4.3` patch is expected to sort
+//! below `2.0.0`.
+
+use std::collections::BTreeMap;
+
+use crate::core::{release_line::ReleaseLine, tag_format::TagMatcher};
+
+/// Where an existing tag was found.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TagLocation {
+    Local,
+    Remote,
+    Both,
+}
+
+impl TagLocation {
+    fn describe(self) -> &'static str {
+        match self {
+            TagLocation::Local => "locally",
+            TagLocation::Remote => "on the remote",
+            TagLocation::Both => "locally and on the remote",
+        }
+    }
+}
+
+/// Every tag in the repository and on its upstream remote.
+#[derive(Debug, Default)]
+pub struct ExistingTags {
+    tags: BTreeMap<String, TagLocation>,
+}
+
+impl ExistingTags {
+    pub fn new(local: Vec<String>, remote: Vec<String>) -> Self {
+        let mut tags: BTreeMap<String, TagLocation> = local
+            .into_iter()
+            .map(|name| (name, TagLocation::Local))
+            .collect();
+        for name in remote {
+            tags.entry(name)
+                .and_modify(|loc| *loc = TagLocation::Both)
+                .or_insert(TagLocation::Remote);
+        }
+        Self { tags }
+    }
+}
+
+/// Why a planned tag can't be created.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TagConflictKind {
+    /// The tag name is taken.
+    Exists(TagLocation),
+    /// `tag` released `version`, which the new version doesn't exceed.
+    NotNewer {
+        tag: String,
+        version: semver::Version,
+    },
+}
+
+/// A planned release tag that collides with an existing one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TagConflict {
+    pub unit: String,
+    pub tag: String,
+    pub new_version: String,
+    pub kind: TagConflictKind,
+}
+
+impl TagConflict {
+    /// One line for error messages, e.g. ``core 1.3.0: tag `core-v1.3.0`
+    /// already exists on the remote``.
+    pub fn describe(&self) -> String {
+        match &self.kind {
+            TagConflictKind::Exists(location) => format!(
+                "{} {}: tag `{}` already exists {}",
+                self.unit,
+                self.new_version,
+                self.tag,
+                location.describe()
+            ),
+            TagConflictKind::NotNewer { tag, version } => format!(
+                "{} {}: not newer than {version}, already tagged as `{tag}`",
+                self.unit, self.new_version
+            ),
+        }
+    }
+}
+
+/// The conflict, if any, of releasing `unit` at `new_version` as `tag`.
+/// `matcher` recognises the unit's earlier release tags. Versions
+/// outside semver are only checked for a taken tag name.
+pub fn find(
+    unit: &str,
+    tag: &str,
+    new_version: &str,
+    matcher: &TagMatcher,
+    existing: &ExistingTags,
+    release_line: Option<ReleaseLine>,
+) -> Option<TagConflict> {
+    let conflict = |kind| TagConflict {
+        unit: unit.to_owned(),
+        tag: tag.to_owned(),
+        new_version: new_version.to_owned(),
+        kind,
+    };
+
+    if let Some(location) = existing.tags.get(tag) {
+        return Some(conflict(TagConflictKind::Exists(*location)));
+    }
+    let version = semver::Version::parse(new_version).ok()?;
+    existing
+        .tags
+        .keys()
+        .filter_map(|name| Some((name, matcher.match_version(name)?)))
+        .filter(|(_, released)| release_line.is_none_or(|line| line.contains(released)))
+        .filter(|(_, released)| *released >= version)
+        .max_by(|a, b| a.1.cmp(&b.1))
+        .map(|(name, released)| {
+            conflict(TagConflictKind::NotNewer {
+                tag: name.clone(),
+                version: released,
+            })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::tag_format::{build_tag_matcher, TagPatternInputs};
+
+    fn matcher() -> TagMatcher {
+        build_tag_matcher(&TagPatternInputs {
+            project_name: "core",
+            ecosystem: "cargo",
+            ecosystem_default: "{name}-v{version}",
+            allowed_vars: &["name", "version", "ecosystem"],
+            override_template: None,
+            maven_coords: None,
+            module_path: None,
+            allow_bare_v_fallback: false,
+        })
+        .unwrap()
+    }
+
+    fn tags(local: &[&str], remote: &[&str]) -> ExistingTags {
+        let owned = |names: &[&str]| names.iter().map(|n| n.to_string()).collect();
+        ExistingTags::new(owned(local), owned(remote))
+    }
+
+    #[test]
+    fn taken_tag_names_are_reported_with_their_location() {
+        let existing = tags(&["core-v1.2.0"], &["core-v1.2.0", "core-v1.3.0"]);
+        let found = find("core", "core-v1.3.0", "1.3.0", &matcher(), &existing, None);
+        assert_eq!(
+            found.unwrap().describe(),
+            "core 1.3.0: tag `core-v1.3.0` already exists on the remote"
+        );
+        let found = find("core", "core-v1.2.0", "1.2.0", &matcher(), &existing, None);
+        assert_eq!(
+            found.unwrap().kind,
+            TagConflictKind::Exists(TagLocation::Both)
+        );
+    }
+
+    #[test]
+    fn versions_must_exceed_every_earlier_release() {
+        let existing = tags(&["core-v1.2.0", "core-v2.0.0", "other-v9.0.0"], &[]);
+        let found = find("core", "core-v1.2.1", "1.2.1", &matcher(), &existing, None);
+        assert_eq!(
+            found,
+            Some(TagConflict {
+                unit: "core".into(),
+                tag: "core-v1.2.1".into(),
+                new_version: "1.2.1".into(),
+                kind: TagConflictKind::NotNewer {
+                    tag: "core-v2.0.0".into(),
+                    version: semver::Version::new(2, 0, 0),
+                },
+            })
+        );
+        assert!(find("core", "core-v2.0.1", "2.0.1", &matcher(), &existing, None).is_none());
+
+        let line = ReleaseLine::from_branch("release/1.x");
+        assert!(find("core", "core-v1.2.1", "1.2.1", &matcher(), &existing, line).is_none());
+    }
+}
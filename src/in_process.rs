@@ -0,0 +1,226 @@
+//! Running belaf commands inside the calling process.
+//!
+//! [`run`] takes the arguments and working directory a `belaf` process
+//! would get and hands back what it would have printed and its exit code,
+//! without spawning the binary. The integration tests drive belaf this
+//! way, and tools embedding belaf can do the same:
+//!
+//! ```no_run
+//! let output = belaf::in_process::run(&["status", "--format", "json"], "path/to/repo".as_ref());
+//! assert!(output.status.success());
+//! let status: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+//! ```
+//!
+//! The working directory, the environment and the selections made by the
+//! global flags are process-wide, so runs are serialised and each one
+//! sets them up afresh, like a new process would. The terminal UIs and
+//! progress spinners are never shown: output is captured, so commands
+//! behave as they do with their output piped.
+
+use std::{
+    ffi::OsString,
+    fmt,
+    path::Path,
+    sync::{Mutex, Once},
+};
+
+use anyhow::{anyhow, Context, Result};
+use clap::Parser;
+use tracing_subscriber::EnvFilter;
+
+use crate::{cli::Cli, core::output};
+
+/// Held for the whole of a run.
+static RUN_LOCK: Mutex<()> = Mutex::new(());
+
+/// Exit code of a run that panicked, as of a Rust binary that panics.
+const PANIC_EXIT_CODE: i32 = 101;
+
+/// What a run printed and how it exited.
+#[derive(Clone, Debug)]
+pub struct CommandOutput {
+    pub status: ExitStatus,
+    pub stdout: Vec<u8>,
+    pub stderr: Vec<u8>,
+}
+
+/// The exit code of a run.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ExitStatus(i32);
+
+impl ExitStatus {
+    pub fn success(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Always `Some`; shaped like [`std::process::ExitStatus::code`].
+    pub fn code(self) -> Option<i32> {
+        Some(self.0)
+    }
+}
+
+impl fmt::Display for ExitStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "exit status: {}", self.0)
+    }
+}
+
+/// Run `belaf <args>` in `cwd`.
+pub fn run(args: &[&str], cwd: &Path) -> CommandOutput {
+    run_with_env(args, cwd, &[])
+}
+
+/// Run `belaf <args>` in `cwd` with `env` added to the environment for
+/// the duration of the run.
+pub fn run_with_env(args: &[&str], cwd: &Path, env: &[(&str, &str)]) -> CommandOutput {
+    let _lock = RUN_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    init_logging();
+    let _env = EnvGuard::set(env);
+    let (code, captured) = output::capture(|| match CwdGuard::enter(cwd) {
+        Ok(_cwd) => invoke(args),
+        Err(e) => fail(&e, args),
+    });
+    CommandOutput {
+        status: ExitStatus(code),
+        stdout: captured.stdout,
+        stderr: captured.stderr,
+    }
+}
+
+/// Call `f` in `cwd` with `env` added to the environment, serialised with
+/// runs, for callers that drive the library itself: an
+/// [`AppSession`](crate::core::session::AppSession) opens the repository
+/// of the working directory.
+pub fn in_dir<T>(cwd: &Path, env: &[(&str, &str)], f: impl FnOnce() -> Result<T>) -> Result<T> {
+    let _lock = RUN_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let _env = EnvGuard::set(env);
+    let _cwd = CwdGuard::enter(cwd)?;
+    f()
+}
+
+/// Send warnings to the captured stderr, as the binary logs them to
+/// stderr. `-v` does not raise the level of later runs; a subscriber the
+/// caller installed first is left alone.
+fn init_logging() {
+    static INIT: Once = Once::new();
+    INIT.call_once(|| {
+        let _ = tracing_subscriber::fmt()
+            .with_env_filter(EnvFilter::new("warn"))
+            .with_writer(|| output::Stderr)
+            .with_ansi(false)
+            .try_init();
+    });
+}
+
+/// What `main` does, minus the update check.
+fn invoke(args: &[&str]) -> i32 {
+    let cli = match Cli::try_parse_from(std::iter::once("belaf").chain(args.iter().copied())) {
+        Ok(cli) => cli,
+        // `--help` and `--version` come through here too.
+        Err(e) => {
+            let rendered = e.render();
+            if e.use_stderr() {
+                eprint!("{rendered}");
+            } else {
+                print!("{rendered}");
+            }
+            return e.exit_code();
+        }
+    };
+    if let Err(e) = crate::configure(&cli) {
+        return fail(&e, args);
+    }
+    if cli.version {
+        crate::cmd::completions::print_version();
+        return 0;
+    }
+    if cli.command.is_none() {
+        return fail(
+            &anyhow!("no command given; the dashboard needs a terminal"),
+            args,
+        );
+    }
+
+    // On a thread of its own, so a caller inside an async runtime can
+    // still block on the command's runtime, and a panic comes back as
+    // an exit code.
+    let result = std::thread::scope(|s| {
+        s.spawn(|| {
+            tokio::runtime::Builder::new_multi_thread()
+                .enable_all()
+                .build()
+                .context("failed to create async runtime")?
+                .block_on(crate::run(cli))
+        })
+        .join()
+    });
+    match result {
+        Ok(Ok(code)) => code,
+        Ok(Err(e)) => fail(&e, args),
+        Err(panic) => {
+            let message = panic
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| panic.downcast_ref::<String>().cloned())
+                .unwrap_or_default();
+            eprintln!("belaf panicked: {message}");
+            PANIC_EXIT_CODE
+        }
+    }
+}
+
+/// Print `error` the way the binary does and return its exit code.
+fn fail(error: &anyhow::Error, args: &[&str]) -> i32 {
+    use crate::core::errors;
+
+    errors::display_diagnostic(error);
+    let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+    errors::record_error(error, &args);
+    errors::classify(error).exit_code().into()
+}
+
+/// Restores the working directory on drop.
+struct CwdGuard(std::path::PathBuf);
+
+impl CwdGuard {
+    fn enter(dir: &Path) -> Result<Self> {
+        let previous = std::env::current_dir().context("failed to get current directory")?;
+        std::env::set_current_dir(dir)
+            .with_context(|| format!("failed to enter `{}`", dir.display()))?;
+        Ok(Self(previous))
+    }
+}
+
+impl Drop for CwdGuard {
+    fn drop(&mut self) {
+        let _ = std::env::set_current_dir(&self.0);
+    }
+}
+
+/// Restores the variables it set on drop.
+struct EnvGuard(Vec<(String, Option<OsString>)>);
+
+impl EnvGuard {
+    fn set(vars: &[(&str, &str)]) -> Self {
+        let previous = vars
+            .iter()
+            .map(|(key, value)| {
+                let old = std::env::var_os(key);
+                std::env::set_var(key, value);
+                (key.to_string(), old)
+            })
+            .collect();
+        Self(previous)
+    }
+}
+
+impl Drop for EnvGuard {
+    fn drop(&mut self) {
+        for (key, old) in self.0.drain(..).rev() {
+            match old {
+                Some(value) => std::env::set_var(&key, value),
+                None => std::env::remove_var(&key),
+            }
+        }
+    }
+}
@@ -1,40 +1,117 @@
+// Shadow the standard printing macros for the whole crate, so that
+// in-process runs can capture what commands print (see `core::output`).
+macro_rules! print {
+    ($($arg:tt)*) => {
+        $crate::core::output::stdout(format_args!($($arg)*))
+    };
+}
+
+macro_rules! println {
+    () => {
+        $crate::core::output::stdout(format_args!("\n"))
+    };
+    ($($arg:tt)*) => {
+        $crate::core::output::stdout(format_args!("{}\n", format_args!($($arg)*)))
+    };
+}
+
+macro_rules! eprint {
+    ($($arg:tt)*) => {
+        $crate::core::output::stderr(format_args!($($arg)*))
+    };
+}
+
+macro_rules! eprintln {
+    () => {
+        $crate::core::output::stderr(format_args!("\n"))
+    };
+    ($($arg:tt)*) => {
+        $crate::core::output::stderr(format_args!("{}\n", format_args!($($arg)*)))
+    };
+}
+
 pub mod cli;
 pub mod error;
+pub mod in_process;
+#[cfg(feature = "test-support")]
+pub mod test_support;
 
 pub mod cmd {
+    pub mod affected;
+    pub mod approve;
+    pub mod audit;
     pub mod changelog;
+    pub mod comment;
     pub mod completions;
     pub mod dashboard;
+    pub mod debug;
     pub mod describe;
     pub mod doctor;
+    pub mod env;
     pub mod explain;
+    pub mod export;
     pub mod graph;
+    pub mod history;
     pub mod init;
     pub mod install;
+    pub mod label;
+    pub mod lint_commits;
+    pub mod migrate;
+    pub mod open;
+    pub mod owners;
+    pub mod package;
     pub mod prepare;
+    pub mod promote;
+    pub mod release;
     pub mod schema;
     pub mod status;
+    pub mod tag;
+    pub mod verify_tag;
+    pub mod which_release;
 }
 
 pub mod core {
     pub mod wire;
 
+    pub mod api_diff;
+    pub mod approval;
+    pub mod audit;
     pub mod bump;
     pub mod bump_source;
     pub mod cargo_lock;
+    pub mod checklist;
+    pub mod commit_scopes;
     pub mod config;
+    pub mod deterministic;
     pub mod embed;
     pub mod env;
     pub mod errors;
     pub mod exit_code;
     pub mod graph;
     pub mod group;
+    pub mod history_bounds;
+    pub mod jira;
     pub mod manifest;
+    pub mod net;
+    pub mod notify;
+    pub mod output;
+    pub mod packaging;
+    pub mod pr_impact;
+    pub mod project_trailers;
+    pub mod publication;
+    pub mod publish_checks;
+    pub mod release_line;
     pub mod release_unit;
+    pub mod releases;
     pub mod resolved_release_unit;
     pub mod rewriters;
+    pub mod scope;
     pub mod session;
+    pub mod signing;
+    pub mod snapshot;
+    pub mod status_gates;
     pub mod tag_format;
+    pub mod timings;
     pub mod version;
     pub mod version_field;
     pub mod workflow;
@@ -42,12 +119,16 @@ pub mod core {
     pub mod api;
 
     pub mod auth {
+        pub mod pat;
+        pub mod permissions;
+        pub mod profile;
         pub mod token;
     }
 
     pub mod git {
         pub mod branch;
         pub mod gitignore;
+        pub mod hooks;
         pub mod repository;
         pub mod url;
         pub mod utils;
@@ -64,12 +145,17 @@ pub mod core {
         pub mod maven;
         pub mod npm;
         pub mod pypa;
+        pub mod registry;
         pub mod swift;
     }
 
     pub mod github {
         pub mod client;
+        pub mod codeowners;
+        pub mod forge;
+        pub mod pending;
         pub mod pr;
+        pub mod rest;
     }
 
     pub mod changelog;
@@ -84,134 +170,159 @@ pub mod utils {
 }
 
 use anyhow::Result;
-use cli::{AuthCommands, Cli, Commands};
+use cli::{
+    AuditCommands, AuthCommands, Cli, Commands, DebugCommands, ExportCommands, ReleaseCommands,
+};
 
 pub async fn execute(cli: Cli) -> Result<()> {
+    let exit_code = run(cli).await?;
+    if exit_code != 0 {
+        std::process::exit(exit_code);
+    }
+    Ok(())
+}
+
+/// Set up the process-wide state the global flags of `cli` select:
+/// network and forge settings of the current repository, the auth
+/// profile, `--scope`, `--max-depth`, colors and JSON errors. Every
+/// selection is replaced, so running this again for another invocation
+/// leaves nothing over from the last one.
+pub fn configure(cli: &Cli) -> Result<()> {
+    core::errors::set_json_output(cli.command.as_ref().is_some_and(Commands::wants_json));
+    core::net::configure_from_env();
+    core::github::forge::configure_from_env();
+    core::auth::profile::configure_from_env();
+    core::auth::profile::select(cli.profile.as_deref())?;
+    core::scope::select(cli.scope.as_deref());
+    core::history_bounds::select_max_depth(cli.max_depth);
+    core::changelog::select_cache_disabled(cli.no_cache);
+    core::git::repository::allow_any_upstream(cli.i_know_what_im_doing);
+
+    if cli.no_color {
+        owo_colors::set_override(false);
+    } else {
+        owo_colors::unset_override();
+    }
+    core::errors::set_no_color(cli.no_color);
+    utils::theme::set_no_color(cli.no_color);
+    Ok(())
+}
+
+/// Run the command `cli` holds and return its exit code, without exiting
+/// the process the way [`execute`] does.
+pub async fn run(cli: Cli) -> Result<i32> {
     let command = cli.command.expect("Command must be present");
     match command {
-        Commands::Install => {
-            let exit_code = cmd::install::run().await?;
-            if exit_code != 0 {
-                std::process::exit(exit_code);
-            }
-            Ok(())
-        }
+        Commands::Install => cmd::install::run().await,
         Commands::Auth(auth_cmd) => match auth_cmd {
-            AuthCommands::Status => {
-                let exit_code = cmd::install::status().await?;
-                if exit_code != 0 {
-                    std::process::exit(exit_code);
-                }
-                Ok(())
-            }
-            AuthCommands::Whoami => {
-                let exit_code = cmd::install::whoami().await?;
-                if exit_code != 0 {
-                    std::process::exit(exit_code);
-                }
-                Ok(())
-            }
-            AuthCommands::Logout => {
-                let exit_code = cmd::install::logout().await?;
-                if exit_code != 0 {
-                    std::process::exit(exit_code);
-                }
-                Ok(())
-            }
+            AuthCommands::Login(args) => cmd::install::login(args.remotes, args.with_token).await,
+            AuthCommands::Profiles => cmd::install::profiles(),
+            AuthCommands::Status => cmd::install::status().await,
+            AuthCommands::Whoami => cmd::install::whoami().await,
+            AuthCommands::Logout => cmd::install::logout().await,
         },
         Commands::Completions { shell } => {
             cmd::completions::generate(shell);
-            Ok(())
+            Ok(0)
         }
         Commands::Version => {
             cmd::completions::print_version();
-            Ok(())
+            Ok(0)
         }
-        Commands::Init(args) => {
-            let exit_code = cmd::init::run(
-                args.force,
-                args.upstream,
-                args.ci,
-                args.preset,
-                args.auto_detect,
-            )?;
-            if exit_code != 0 {
-                std::process::exit(exit_code);
-            }
-            Ok(())
+        Commands::Init(args) => cmd::init::run(
+            args.force,
+            args.upstream,
+            args.ci,
+            args.preset,
+            args.auto_detect,
+        ),
+        Commands::Status(args) => cmd::status::run(args.format, args.ci, args.filter, args.at),
+        Commands::Prepare(args) => cmd::prepare::run(
+            args.ci,
+            args.release_unit,
+            args.projects_from_file,
+            args.bump_source,
+            args.bump_source_cmd,
+            args.force,
+            args.branch,
+            args.message.filter(|_| args.allow_empty),
+            args.resume,
+        ),
+        Commands::Promote(args) => cmd::promote::run(args.release_units, args.force),
+        Commands::Package(args) => {
+            cmd::package::run(args.release_unit, args.artifacts, args.dry_run).await
         }
-        Commands::Status(args) => {
-            let exit_code = cmd::status::run(args.format, args.ci)?;
-            if exit_code != 0 {
-                std::process::exit(exit_code);
+        Commands::Tag(args) => cmd::tag::run(args.release_id, args.sign, args.dry_run),
+        Commands::Release(release_args) => match release_args.command {
+            Some(ReleaseCommands::Cut(args)) => {
+                cmd::release::cut(args.release_id, args.artifacts, args.sign, args.dry_run).await
             }
-            Ok(())
-        }
-        Commands::Prepare(args) => {
-            let exit_code = cmd::prepare::run(
-                args.ci,
-                args.release_unit,
-                args.bump_source,
-                args.bump_source_cmd,
-            )?;
-            if exit_code != 0 {
-                std::process::exit(exit_code);
+            Some(ReleaseCommands::Publish(args)) => {
+                cmd::release::publish(args.release_id, args.jobs, args.dry_run, args.format).await
             }
-            Ok(())
-        }
-        Commands::Graph(args) => {
-            let exit_code = cmd::graph::run(args.format, args.ci, args.web, args.out)?;
-            if exit_code != 0 {
-                std::process::exit(exit_code);
+            None => {
+                cmd::release::snapshot(
+                    release_args.label,
+                    release_args.tag,
+                    release_args.dry_run,
+                    release_args.format,
+                )
+                .await
             }
-            Ok(())
+        },
+        Commands::Open(args) => cmd::open::run(args.target, args.project, args.print),
+        Commands::History(args) => {
+            cmd::history::run(args.project, args.show_commits, args.limit, args.format)
         }
-        Commands::Changelog(args) => {
-            let exit_code = cmd::changelog::run(
-                args.preview,
-                args.stdout,
-                args.release_unit,
-                args.output,
-                args.unreleased,
-                args.ci,
-            )?;
-            if exit_code != 0 {
-                std::process::exit(exit_code);
-            }
-            Ok(())
+        Commands::WhichRelease(args) => {
+            cmd::which_release::run(args.commit, args.project, args.format)
         }
-        Commands::Explain(args) => {
-            let exit_code = cmd::explain::run(args.format)?;
-            if exit_code != 0 {
-                std::process::exit(exit_code);
-            }
-            Ok(())
+        Commands::VerifyTag(args) => {
+            cmd::verify_tag::run(args.tag, args.artifacts, args.allow_unsigned, args.format)
+        }
+        Commands::Migrate(args) => cmd::migrate::run(args.check),
+        Commands::Approve(args) => {
+            cmd::approve::run(args.release_id, args.check, args.format).await
         }
+        Commands::Graph(args) => {
+            cmd::graph::run(args.format, args.ci, args.web, args.out, args.external)
+        }
+        Commands::Affected(args) => cmd::affected::run(args.base, args.format),
+        Commands::Comment(args) => cmd::comment::run(args.pr, args.base, args.dry_run).await,
+        Commands::Label(args) => cmd::label::run(args.pr, args.base, args.dry_run).await,
+        Commands::LintCommits(args) => cmd::lint_commits::run(args.strict, args.format),
+        Commands::Owners(args) => cmd::owners::run(args.project, args.format),
+        Commands::Changelog(args) => cmd::changelog::run(
+            args.preview,
+            args.stdout,
+            args.release_unit,
+            args.output,
+            args.unreleased,
+            args.write,
+            args.ci,
+        ),
+        Commands::Explain(args) => cmd::explain::run(args.format),
         Commands::Describe(args) => {
             // `--json` is the default; both `belaf describe` and
             // `belaf describe --json` produce JSON. `--text` opts into
             // the human-readable rendering. The two flags conflict via
             // clap, so at most one is set here.
             let _ = args.json;
-            let exit_code = cmd::describe::run(args.text)?;
-            if exit_code != 0 {
-                std::process::exit(exit_code);
-            }
-            Ok(())
-        }
-        Commands::Schema(args) => {
-            let exit_code = cmd::schema::run(args.name)?;
-            if exit_code != 0 {
-                std::process::exit(exit_code);
-            }
-            Ok(())
+            cmd::describe::run(args.text)
         }
-        Commands::Doctor(args) => {
-            let exit_code = cmd::doctor::run(args.json).await?;
-            if exit_code != 0 {
-                std::process::exit(exit_code);
+        Commands::Schema(args) => cmd::schema::run(args.name),
+        Commands::Doctor(args) => cmd::doctor::run(args.json).await,
+        Commands::Env(args) => cmd::env::run(args.redact),
+        Commands::Audit(audit_cmd) => match audit_cmd {
+            AuditCommands::Show(args) => {
+                cmd::audit::run_show(args.command, args.user, args.limit, args.format)
             }
-            Ok(())
-        }
+        },
+        Commands::Export(export_cmd) => match export_cmd {
+            ExportCommands::Site(args) => cmd::export::run_site(args.out, args.format),
+        },
+        Commands::Debug(debug_cmd) => match debug_cmd {
+            DebugCommands::Timings(args) => cmd::debug::run_timings(args.format),
+        },
     }
 }
@@ -6,10 +6,8 @@ use tracing_subscriber::EnvFilter;
 async fn main() -> Result<()> {
     let cli = belaf::cli::Cli::parse();
     init_logging(cli.verbose);
-
-    if cli.no_color {
-        owo_colors::set_override(false);
-        belaf::core::errors::set_no_color(true);
+    if let Err(e) = belaf::configure(&cli) {
+        std::process::exit(print_error(&e));
     }
 
     if cli.version {
@@ -24,6 +22,11 @@ async fn main() -> Result<()> {
         let res = belaf::execute(belaf::cli::Cli {
             verbose: cli.verbose,
             no_color: cli.no_color,
+            profile: cli.profile,
+            scope: cli.scope,
+            max_depth: cli.max_depth,
+            no_cache: cli.no_cache,
+            i_know_what_im_doing: cli.i_know_what_im_doing,
             version: false,
             command: Some(command),
         })
@@ -34,8 +37,7 @@ async fn main() -> Result<()> {
         }
 
         if let Err(e) = res {
-            print_error(&e);
-            std::process::exit(1);
+            std::process::exit(print_error(&e));
         }
     } else {
         match belaf::cmd::dashboard::run() {
@@ -43,26 +45,29 @@ async fn main() -> Result<()> {
                 use belaf::cmd::dashboard::DashboardAction;
                 match action {
                     DashboardAction::Prepare => {
-                        let exit_code = belaf::cmd::prepare::run(false, None, None, None)?;
+                        let exit_code = belaf::cmd::prepare::run(
+                            false, None, None, None, None, false, None, None, false,
+                        )?;
                         if exit_code != 0 {
                             std::process::exit(exit_code);
                         }
                     }
                     DashboardAction::Status => {
-                        let exit_code = belaf::cmd::status::run(None, false)?;
+                        let exit_code = belaf::cmd::status::run(None, false, None, None)?;
                         if exit_code != 0 {
                             std::process::exit(exit_code);
                         }
                     }
                     DashboardAction::Graph => {
-                        let exit_code = belaf::cmd::graph::run(None, false, false, None)?;
+                        let exit_code = belaf::cmd::graph::run(None, false, false, None, false)?;
                         if exit_code != 0 {
                             std::process::exit(exit_code);
                         }
                     }
                     DashboardAction::Changelog => {
-                        let exit_code =
-                            belaf::cmd::changelog::run(false, false, None, None, false, false)?;
+                        let exit_code = belaf::cmd::changelog::run(
+                            false, false, None, None, false, false, false,
+                        )?;
                         if exit_code != 0 {
                             std::process::exit(exit_code);
                         }
@@ -77,8 +82,10 @@ async fn main() -> Result<()> {
                         let url = std::env::var("BELAF_WEB_URL")
                             .unwrap_or_else(|_| "https://belaf.dev/dashboard".to_string());
                         if let Err(e) = open::that(&url) {
-                            print_error(&anyhow::anyhow!("failed to open browser: {}", e));
-                            std::process::exit(1);
+                            std::process::exit(print_error(&anyhow::anyhow!(
+                                "failed to open browser: {}",
+                                e
+                            )));
                         }
                     }
                     DashboardAction::Help => {
@@ -89,8 +96,7 @@ async fn main() -> Result<()> {
                 belaf::utils::version_check::check_for_updates(env!("CARGO_PKG_VERSION"), false);
             }
             Err(e) => {
-                print_error(&e);
-                std::process::exit(1);
+                std::process::exit(print_error(&e));
             }
         }
     }
@@ -98,8 +104,12 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
-fn print_error(error: &anyhow::Error) {
+/// Print `error`, remember it for `belaf env`, and return the exit code
+/// its [`belaf::core::errors::ErrorCode`] maps to.
+fn print_error(error: &anyhow::Error) -> i32 {
     belaf::core::errors::display_diagnostic(error);
+    belaf::core::errors::record_error(error, &std::env::args().skip(1).collect::<Vec<_>>());
+    belaf::core::errors::classify(error).exit_code().into()
 }
 
 fn init_logging(verbosity: u8) {
@@ -110,7 +120,9 @@ fn init_logging(verbosity: u8) {
         _ => "trace",
     };
 
+    // Logs go to stderr so they never corrupt `--format json` output.
     tracing_subscriber::fmt()
         .with_env_filter(EnvFilter::new(level))
+        .with_writer(std::io::stderr)
         .init();
 }
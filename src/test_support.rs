@@ -0,0 +1,121 @@
+//! Scripting belaf against throwaway repositories.
+//!
+//! Enabled by the `test-support` feature, for plugin authors and tools
+//! built around belaf that want to test against a real repository
+//! without spawning the `belaf` binary:
+//!
+//! ```no_run
+//! use belaf::test_support::TestRepo;
+//!
+//! let repo = TestRepo::new();
+//! repo.write_file("Cargo.toml", "[package]\nname = \"demo\"\nversion = \"0.1.0\"\n");
+//! repo.commit("Initial commit");
+//! assert!(repo.run(&["init", "--force", "--ci"]).status.success());
+//! ```
+//!
+//! [`TestRepo::run`] executes a command in this process, in the
+//! repository's directory, and captures what it prints; see
+//! [`crate::in_process`].
+
+use std::path::PathBuf;
+
+use tempfile::TempDir;
+
+use crate::in_process::{self, CommandOutput};
+
+/// A git repository in a temporary directory, removed on drop. It has an
+/// `origin` remote on GitHub that is never contacted; set
+/// `BELAF_NO_FETCH=1` before running commands that fetch tags.
+pub struct TestRepo {
+    _dir: TempDir,
+    pub path: PathBuf,
+}
+
+impl Default for TestRepo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TestRepo {
+    #[must_use]
+    pub fn new() -> Self {
+        let dir = TempDir::new().expect("failed to create temp dir");
+        let path = dir.path().to_path_buf();
+
+        let repo = git2::Repository::init(&path).expect("failed to init git");
+        let mut config = repo.config().expect("failed to open git config");
+        config
+            .set_str("user.email", "test@example.com")
+            .expect("failed to set git email");
+        config
+            .set_str("user.name", "Test User")
+            .expect("failed to set git name");
+        repo.remote("origin", "https://github.com/test/repo.git")
+            .expect("failed to add remote");
+
+        Self { _dir: dir, path }
+    }
+
+    pub fn write_file(&self, relative_path: &str, content: &str) {
+        let full_path = self.path.join(relative_path);
+        if let Some(parent) = full_path.parent() {
+            std::fs::create_dir_all(parent).expect("failed to create parent dirs");
+        }
+        std::fs::write(full_path, content).expect("failed to write file");
+    }
+
+    #[must_use]
+    pub fn read_file(&self, relative_path: &str) -> String {
+        std::fs::read_to_string(self.path.join(relative_path)).expect("failed to read file")
+    }
+
+    #[must_use]
+    pub fn file_exists(&self, relative_path: &str) -> bool {
+        self.path.join(relative_path).exists()
+    }
+
+    /// Stage every change, deletions included, and commit it on HEAD.
+    pub fn commit(&self, message: &str) {
+        let repo = git2::Repository::open(&self.path).expect("failed to open git repo");
+        let mut index = repo.index().expect("failed to open index");
+        index
+            .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+            .expect("failed to git add");
+        index
+            .update_all(["*"], None)
+            .expect("failed to stage deletions");
+        index.write().expect("failed to write index");
+        let tree = repo
+            .find_tree(index.write_tree().expect("failed to write tree"))
+            .expect("failed to find tree");
+        let signature = repo.signature().expect("failed to get signature");
+        let parent = repo.head().ok().and_then(|h| h.peel_to_commit().ok());
+        let parents: Vec<&git2::Commit> = parent.iter().collect();
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parents,
+        )
+        .expect("failed to git commit");
+    }
+
+    /// Tag HEAD with a lightweight tag.
+    pub fn tag(&self, name: &str) {
+        let repo = git2::Repository::open(&self.path).expect("failed to open git repo");
+        let head = repo
+            .head()
+            .and_then(|h| h.peel(git2::ObjectType::Commit))
+            .expect("failed to resolve HEAD");
+        repo.tag_lightweight(name, &head, false)
+            .expect("failed to create tag");
+    }
+
+    /// Run `belaf <args>` in the repository; see [`crate::in_process`].
+    pub fn run(&self, args: &[&str]) -> CommandOutput {
+        in_process::run(args, &self.path)
+    }
+}
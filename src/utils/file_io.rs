@@ -3,26 +3,42 @@ use std::fs::File;
 use std::io::Read;
 use std::path::Path;
 
+use crate::core::git::repository::{RepoFile, RepoPath, Repository};
+
 pub const MAX_CONFIG_FILE_SIZE: u64 = 10 * 1024 * 1024;
 
 pub fn read_config_file(path: &Path) -> Result<String> {
     read_config_file_with_limit(path, MAX_CONFIG_FILE_SIZE)
 }
 
-pub fn check_file_size(file: &File, path: &Path) -> Result<()> {
+/// [`read_config_file`] for `path` of `repo`, read as
+/// [`Repository::open_file`] does.
+pub fn read_repo_config_file(repo: &Repository, path: &RepoPath) -> Result<String> {
+    let display = repo.resolve_workdir(path);
+    let mut file = repo
+        .open_file(path)
+        .with_context(|| format!("failed to open {}", display.display()))?;
+    check_file_size(&file, &display)?;
+    let mut contents = String::new();
+    file.read_to_string(&mut contents)
+        .with_context(|| format!("failed to read {}", display.display()))?;
+    Ok(contents)
+}
+
+pub fn check_file_size(file: &RepoFile, path: &Path) -> Result<()> {
     check_file_size_with_limit(file, path, MAX_CONFIG_FILE_SIZE)
 }
 
-pub fn check_file_size_with_limit(file: &File, path: &Path, max_size: u64) -> Result<()> {
-    let metadata = file
-        .metadata()
+pub fn check_file_size_with_limit(file: &RepoFile, path: &Path, max_size: u64) -> Result<()> {
+    let size = file
+        .size()
         .with_context(|| format!("failed to get metadata for {}", path.display()))?;
 
-    if metadata.len() > max_size {
+    if size > max_size {
         bail!(
             "config file {} is too large ({} bytes, max {} bytes)",
             path.display(),
-            metadata.len(),
+            size,
             max_size
         );
     }
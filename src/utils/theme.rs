@@ -1,5 +1,9 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
 use kdam::{term, tqdm, BarExt, Column, RichProgress};
 use owo_colors::{OwoColorize, Rgb};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::Frame;
 use spinoff::{spinners, Color as SpinoffColor, Spinner};
 
 pub const ICON_SUCCESS: &str = "✓";
@@ -11,6 +15,43 @@ pub const ICON_POINTER: &str = "→";
 pub const SEPARATOR: &str = "─";
 pub const LOGO: &str = "🐱";
 
+static NO_COLOR: AtomicBool = AtomicBool::new(false);
+
+/// Turn colors off in the terminal UIs, as `--no-color` does for plain
+/// output.
+pub fn set_no_color(value: bool) {
+    NO_COLOR.store(value, Ordering::Relaxed);
+}
+
+/// Whether the terminal UIs draw in color: not with `--no-color` or
+/// `NO_COLOR` (https://no-color.org).
+pub fn colors_enabled() -> bool {
+    !NO_COLOR.load(Ordering::Relaxed) && std::env::var_os("NO_COLOR").is_none()
+}
+
+/// Style of the list row under the cursor: a tinted background, or
+/// reverse video when colors are off.
+pub fn row_highlight() -> Style {
+    if colors_enabled() {
+        Style::default().bg(Color::Rgb(40, 40, 50))
+    } else {
+        Style::default().add_modifier(Modifier::REVERSED)
+    }
+}
+
+/// Call last in every `Terminal::draw`: when colors are off, strips
+/// the foreground and background colors from everything drawn, keeping
+/// bold, reverse video and the other modifiers.
+pub fn apply_color_mode(frame: &mut Frame) {
+    if colors_enabled() {
+        return;
+    }
+    for cell in &mut frame.buffer_mut().content {
+        cell.fg = Color::Reset;
+        cell.bg = Color::Reset;
+    }
+}
+
 pub fn primary() -> Rgb {
     Rgb(114, 227, 173)
 }
@@ -116,6 +157,16 @@ pub fn create_spinner(msg: impl Into<String>) -> Spinner {
     Spinner::new(spinners::Arc, msg.into(), Some(primary_spinoff()))
 }
 
+/// Whether progress may be drawn on stderr: a terminal that is neither
+/// captured nor in deterministic mode.
+fn animate() -> bool {
+    use std::io::{stderr, IsTerminal};
+
+    !crate::core::output::is_capturing()
+        && !crate::core::deterministic::enabled()
+        && stderr().is_terminal()
+}
+
 pub struct ReleaseProgressBar {
     progress: RichProgress,
     is_tty: bool,
@@ -123,9 +174,7 @@ pub struct ReleaseProgressBar {
 
 impl ReleaseProgressBar {
     pub fn new(total: usize, message: &str) -> Self {
-        use std::io::{stderr, IsTerminal};
-
-        let is_tty = stderr().is_terminal();
+        let is_tty = animate();
         term::init(is_tty);
 
         let bar = tqdm!(total = total, animation = "arrow", ncols = 40);
@@ -165,9 +214,7 @@ pub struct PhaseSpinner {
 
 impl PhaseSpinner {
     pub fn new(message: impl Into<String>) -> Self {
-        use std::io::{stderr, IsTerminal};
-
-        let spinner = if stderr().is_terminal() {
+        let spinner = if animate() {
             Some(Spinner::new(
                 spinners::Arc,
                 format!("  └─ {}", message.into()),
@@ -58,6 +58,9 @@ impl InstallMethod {
 }
 
 pub fn check_for_updates(current_version: &str, force_fetch: bool) {
+    if crate::core::deterministic::enabled() {
+        return;
+    }
     let Some(cache_path) = get_cache_path() else {
         if force_fetch {
             if let Some(latest_version) = fetch_latest_from_github() {
@@ -115,7 +118,8 @@ fn get_latest_version(cache_path: &PathBuf, force_fetch: bool) -> Option<String>
 }
 
 fn fetch_latest_from_github() -> Option<String> {
-    let mut response = ureq::get(GITHUB_API_URL)
+    let mut response = crate::core::net::ureq_agent()
+        .get(GITHUB_API_URL)
         .header("User-Agent", "belaf-cli")
         .call()
         .ok()?;
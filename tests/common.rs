@@ -1,105 +1,95 @@
 #![allow(dead_code)]
 
-use std::path::{Path, PathBuf};
+use belaf::in_process::{self, CommandOutput};
+use std::ops::Deref;
 use std::process::Command;
-use tempfile::TempDir;
 
-pub struct TestRepo {
-    _dir: TempDir,
-    pub path: PathBuf,
-}
+/// [`belaf::test_support::TestRepo`], with commands run the way the
+/// integration tests expect: a dummy `GITHUB_TOKEN` and no tag fetches.
+#[derive(Default)]
+pub struct TestRepo(belaf::test_support::TestRepo);
+
+impl Deref for TestRepo {
+    type Target = belaf::test_support::TestRepo;
 
-impl Default for TestRepo {
-    fn default() -> Self {
-        Self::new()
+    fn deref(&self) -> &Self::Target {
+        &self.0
     }
 }
 
 impl TestRepo {
     #[must_use]
     pub fn new() -> Self {
-        let dir = TempDir::new().expect("failed to create temp dir");
-        let path = dir.path().to_path_buf();
-
-        Self::init_git(&path);
-
-        Self { _dir: dir, path }
+        Self(belaf::test_support::TestRepo::new())
     }
 
-    fn init_git(path: &Path) {
-        Command::new("git")
-            .args(["init"])
-            .current_dir(path)
-            .output()
-            .expect("failed to init git");
-
-        Command::new("git")
-            .args(["config", "user.email", "test@example.com"])
-            .current_dir(path)
-            .output()
-            .expect("failed to set git email");
-
-        Command::new("git")
-            .args(["config", "user.name", "Test User"])
-            .current_dir(path)
-            .output()
-            .expect("failed to set git name");
-
-        Command::new("git")
-            .args([
-                "remote",
-                "add",
-                "origin",
-                "https://github.com/test/repo.git",
-            ])
-            .current_dir(path)
-            .output()
-            .expect("failed to add remote");
+    #[must_use]
+    pub fn run_belaf_command(&self, args: &[&str]) -> CommandOutput {
+        in_process::run_with_env(
+            args,
+            &self.path,
+            &[
+                ("GITHUB_TOKEN", "test-token-for-tests"),
+                ("BELAF_NO_FETCH", "1"),
+            ],
+        )
     }
 
-    pub fn write_file(&self, relative_path: &str, content: &str) {
-        let full_path = self.path.join(relative_path);
-        if let Some(parent) = full_path.parent() {
-            std::fs::create_dir_all(parent).expect("failed to create parent dirs");
-        }
-        std::fs::write(full_path, content).expect("failed to write file");
+    /// Run `git <args>` in the repository and return what it prints,
+    /// trimmed.
+    pub fn git(&self, args: &[&str]) -> String {
+        self.git_with_env(args, &[])
     }
 
-    pub fn commit(&self, message: &str) {
-        Command::new("git")
-            .args(["add", "-A"])
-            .current_dir(&self.path)
-            .output()
-            .expect("failed to git add");
-
-        Command::new("git")
-            .args(["commit", "-m", message])
+    pub fn git_with_env(&self, args: &[&str], env_vars: &[(&str, &str)]) -> String {
+        let output = Command::new("git")
+            .args(args)
+            .envs(env_vars.iter().copied())
             .current_dir(&self.path)
             .output()
-            .expect("failed to git commit");
+            .expect("failed to run git");
+        assert!(
+            output.status.success(),
+            "git {args:?} failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
     }
 
-    #[must_use]
-    pub fn run_belaf_command(&self, args: &[&str]) -> std::process::Output {
-        let belaf_bin = env!("CARGO_BIN_EXE_belaf");
-
-        Command::new(belaf_bin)
-            .args(args)
-            .current_dir(&self.path)
-            .env("GITHUB_TOKEN", "test-token-for-tests")
-            .env("BELAF_NO_FETCH", "1")
-            .output()
-            .expect("failed to run belaf command")
+    /// Commit `my-crate` 1.0.0, then belaf's config for it with
+    /// `extra_config` appended.
+    pub fn init_crate(&self, extra_config: &str) {
+        self.write_file(
+            "Cargo.toml",
+            "[package]\nname = \"my-crate\"\nversion = \"1.0.0\"\nedition = \"2021\"\n",
+        );
+        self.write_file("src/lib.rs", "pub fn hello() {}\n");
+        self.commit("Initial commit");
+        let output = self.run_belaf_command(&["init", "--force"]);
+        assert!(output.status.success());
+        if !extra_config.is_empty() {
+            let config = self.read_file("belaf/config.toml");
+            self.write_file("belaf/config.toml", &format!("{config}\n{extra_config}"));
+        }
+        self.commit("chore: add belaf config");
     }
 
-    #[must_use]
-    pub fn file_exists(&self, relative_path: &str) -> bool {
-        self.path.join(relative_path).exists()
+    /// Run `prepare --ci` and return the id of the manifest it wrote.
+    /// Without a login prepare stops at pushing, after the release commit.
+    pub fn prepare(&self) -> String {
+        let _ = self.run_belaf_command(&["prepare", "--ci"]);
+        let output = self.run_belaf_command(&["history", "--format", "json"]);
+        let history: serde_json::Value =
+            serde_json::from_slice(&output.stdout).expect("history should print JSON");
+        history[0]["manifest_id"].as_str().unwrap().to_string()
     }
 
-    #[must_use]
-    pub fn read_file(&self, relative_path: &str) -> String {
-        std::fs::read_to_string(self.path.join(relative_path)).expect("failed to read file")
+    /// A prepared release of `my-crate` 1.1.0; returns its manifest id.
+    pub fn prepared_release(&self) -> String {
+        self.init_crate("");
+        self.write_file("src/more.rs", "pub fn more() {}\n");
+        self.commit("feat: more");
+        self.prepare()
     }
 
     #[must_use]
@@ -128,18 +118,9 @@ impl TestRepo {
         &self,
         args: &[&str],
         env_vars: &[(&str, &str)],
-    ) -> std::process::Output {
-        let belaf_bin = env!("CARGO_BIN_EXE_belaf");
-
-        let mut cmd = Command::new(belaf_bin);
-        cmd.args(args)
-            .current_dir(&self.path)
-            .env("BELAF_NO_FETCH", "1");
-
-        for (key, value) in env_vars {
-            cmd.env(key, value);
-        }
-
-        cmd.output().expect("failed to run belaf command")
+    ) -> CommandOutput {
+        let mut env = vec![("BELAF_NO_FETCH", "1")];
+        env.extend_from_slice(env_vars);
+        in_process::run_with_env(args, &self.path, &env)
     }
 }
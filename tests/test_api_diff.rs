@@ -0,0 +1,62 @@
+//! `[checks] api_diff`: the `cargo semver-checks` verdict is advice
+//! only; when the tool can't run, `status` and `prepare` go by the
+//! commits alone.
+
+mod common;
+
+use common::TestRepo;
+
+fn released_crate_with_api_diff(mode: &str) -> TestRepo {
+    let repo = TestRepo::new();
+    repo.write_file(
+        "Cargo.toml",
+        "[package]\nname = \"my-crate\"\nversion = \"1.0.0\"\nedition = \"2021\"\n",
+    );
+    repo.write_file("src/lib.rs", "pub fn hello() {}\n");
+    repo.commit("Initial commit");
+
+    let output = repo.run_belaf_command(&["init", "--force"]);
+    assert!(output.status.success());
+    let config = repo.read_file("belaf/config.toml");
+    repo.write_file(
+        "belaf/config.toml",
+        &format!("{config}\n[checks]\napi_diff = \"{mode}\"\n"),
+    );
+    repo.commit("chore: add belaf config");
+    repo.git(&["tag", "my-crate-v1.0.0"]);
+
+    repo.write_file("src/lib.rs", "pub fn hello() { println!(\"hi\") }\n");
+    repo.commit("fix: say hi");
+    repo
+}
+
+#[test]
+fn test_api_diff_falls_back_to_commits_when_it_cannot_run() {
+    let repo = released_crate_with_api_diff("enforce");
+    let output = repo.run_belaf_command(&["status", "--format", "json"]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let project = &json["projects"][0];
+    assert_eq!(project["commits_count"], 1);
+    if project.get("api_diff").is_none() {
+        assert!(
+            String::from_utf8_lossy(&output.stderr).contains("skipping the API diff"),
+            "a diff that can't run is reported"
+        );
+    }
+
+    let output = repo.run_belaf_command(&["prepare", "--ci"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        !stderr.contains("the API diff requires"),
+        "stderr: {stderr}"
+    );
+    assert!(
+        repo.read_file("Cargo.toml").contains("version = \"1.0.1\""),
+        "the commits decide the bump: {stderr}"
+    );
+}
@@ -0,0 +1,94 @@
+//! Read-only commands run in a bare repository, reading the tree of
+//! `HEAD`; commands that write still need a working tree.
+
+mod common;
+
+use std::path::Path;
+
+use belaf::in_process::{self, CommandOutput};
+use common::TestRepo;
+
+fn belaf(dir: &Path, args: &[&str]) -> CommandOutput {
+    in_process::run_with_env(
+        args,
+        dir,
+        &[
+            ("GITHUB_TOKEN", "test-token-for-tests"),
+            ("BELAF_NO_FETCH", "1"),
+        ],
+    )
+}
+
+/// A released crate with one feature since, cloned bare.
+fn bare_clone(repo: &TestRepo, into: &Path) -> std::path::PathBuf {
+    repo.write_file(
+        "Cargo.toml",
+        "[package]\nname = \"my-crate\"\nversion = \"1.0.0\"\nedition = \"2021\"\n",
+    );
+    repo.write_file("src/lib.rs", "pub fn f() {}\n");
+    repo.commit("Initial commit");
+    let output = repo.run_belaf_command(&["init", "--force", "--ci"]);
+    assert!(output.status.success());
+    repo.commit("chore: add belaf config");
+    repo.git(&["tag", "my-crate-v1.0.0"]);
+    repo.write_file("src/stream.rs", "pub fn stream() {}\n");
+    repo.commit("feat: add streaming");
+
+    let bare = into.join("repo.git");
+    repo.git(&["clone", "--bare", "--quiet", ".", bare.to_str().unwrap()]);
+    bare
+}
+
+#[test]
+fn test_bare_repository_status_reads_head() {
+    let repo = TestRepo::new();
+    let dir = tempfile::TempDir::new().unwrap();
+    let bare = bare_clone(&repo, dir.path());
+
+    let output = belaf(&bare, &["status", "--format", "json"]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let status: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let unit = &status["projects"][0];
+    assert_eq!(unit["name"], "my-crate");
+    assert_eq!(unit["commits"], serde_json::json!(["feat: add streaming"]));
+    assert!(status.get("at").is_none());
+
+    let output = belaf(&bare, &["graph", "--format", "json"]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(String::from_utf8_lossy(&output.stdout).contains("my-crate"));
+
+    let output = belaf(&bare, &["history", "--format", "json"]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    assert!(
+        !bare.join("worktrees").exists(),
+        "HEAD is read without checking it out"
+    );
+}
+
+#[test]
+fn test_bare_repository_refuses_to_prepare() {
+    let repo = TestRepo::new();
+    let dir = tempfile::TempDir::new().unwrap();
+    let bare = bare_clone(&repo, dir.path());
+
+    let output = belaf(&bare, &["prepare", "--ci"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("cannot operate on a bare repository"),
+        "{stderr}"
+    );
+}
@@ -396,3 +396,687 @@ edition = "2021"
         changelog
     );
 }
+
+#[test]
+fn test_changelog_entries_piped_through_format_command() {
+    let repo = TestRepo::new();
+
+    repo.write_file(
+        "Cargo.toml",
+        r#"[package]
+name = "test-crate"
+version = "0.1.0"
+edition = "2021"
+"#,
+    );
+    repo.write_file("src/lib.rs", "pub fn hello() {}\n");
+    repo.commit("Initial commit");
+
+    let _ = repo.run_belaf_command(&["init", "--force"]);
+
+    let config = repo.read_file("belaf/config.toml");
+    repo.write_file(
+        "belaf/config.toml",
+        &format!("{config}\n[changelog.format_command]\ncommands = [\"false\", \"tr a-z A-Z\"]\n"),
+    );
+    repo.commit("chore: format changelog entries");
+
+    repo.write_file("src/feature.rs", "pub fn feature() {}");
+    repo.commit("feat: add shiny feature");
+
+    let output = repo.run_belaf_command(&["changelog"]);
+
+    assert!(
+        output.status.success(),
+        "Changelog command failed: {:?}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let changelog = repo.read_file("CHANGELOG.md");
+    assert!(
+        changelog.contains("ADD SHINY FEATURE"),
+        "Entry should be upper-cased by the format command. Content:\n{}",
+        changelog
+    );
+}
+
+#[test]
+fn test_changelog_templates_see_context_command_output() {
+    let repo = TestRepo::new();
+
+    repo.write_file(
+        "Cargo.toml",
+        r#"[package]
+name = "test-crate"
+version = "0.1.0"
+edition = "2021"
+"#,
+    );
+    repo.write_file("src/lib.rs", "pub fn hello() {}\n");
+    repo.commit("Initial commit");
+
+    let _ = repo.run_belaf_command(&["init", "--force"]);
+
+    let config = repo.read_file("belaf/config.toml").replacen(
+        "body = \"\"\"\n",
+        "body = \"\"\"\nBuilt by job {{ build }}.\n",
+        1,
+    );
+    repo.write_file(
+        "belaf/config.toml",
+        &format!("{config}\n[changelog.context_commands]\nbuild = \"echo '  4711  '\"\n"),
+    );
+    repo.commit("chore: name the build in changelog entries");
+
+    repo.write_file("src/feature.rs", "pub fn feature() {}");
+    repo.commit("feat: add shiny feature");
+
+    let output = repo.run_belaf_command(&["changelog"]);
+    assert!(
+        output.status.success(),
+        "Changelog command failed: {:?}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let changelog = repo.read_file("CHANGELOG.md");
+    assert!(
+        changelog.contains("Built by job 4711."),
+        "Entry should name the build. Content:\n{}",
+        changelog
+    );
+
+    repo.write_file(
+        "belaf/config.toml",
+        &format!("{config}\n[changelog.context_commands]\nbuild = \"exit 3\"\n"),
+    );
+    let output = repo.run_belaf_command(&["changelog"]);
+    assert!(
+        !output.status.success(),
+        "a failing command must fail the run"
+    );
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("context command `build`"),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    repo.write_file(
+        "belaf/config.toml",
+        &format!("{config}\n[changelog.context_commands]\nversion = \"echo 9.9.9\"\n"),
+    );
+    let output = repo.run_belaf_command(&["changelog"]);
+    assert!(
+        !output.status.success(),
+        "built-in names can't be redefined"
+    );
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("built-in template variable"),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn test_changelog_writes_each_configured_output() {
+    let repo = TestRepo::new();
+
+    repo.write_file(
+        "Cargo.toml",
+        r#"[package]
+name = "test-crate"
+version = "0.1.0"
+edition = "2021"
+"#,
+    );
+    repo.write_file("src/lib.rs", "pub fn hello() {}\n");
+    repo.commit("Initial commit");
+
+    let _ = repo.run_belaf_command(&["init", "--force"]);
+
+    let config = repo.read_file("belaf/config.toml");
+    repo.write_file(
+        "belaf/config.toml",
+        &format!(
+            r#"{config}
+[[changelog.outputs]]
+language = "ja"
+output = "CHANGELOG.ja.md"
+body = """
+## {{{{ version }}}} リリース
+{{% for commit in commits %}}- {{{{ commit.message }}}}
+{{% endfor %}}"""
+
+[[changelog.outputs]]
+language = "de"
+output = "CHANGELOG.de.md"
+translate_command = 'sed "s/shiny/glänzend-$BELAF_LANGUAGE/"'
+"#
+        ),
+    );
+    repo.commit("chore: translate changelogs");
+
+    repo.write_file("src/feature.rs", "pub fn feature() {}");
+    repo.commit("feat: add shiny feature");
+
+    let output = repo.run_belaf_command(&["changelog"]);
+    assert!(
+        output.status.success(),
+        "Changelog command failed: {:?}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let changelog = repo.read_file("CHANGELOG.md");
+    assert!(changelog.contains("shiny feature"), "{changelog}");
+    let ja = repo.read_file("CHANGELOG.ja.md");
+    assert!(ja.contains("リリース"), "{ja}");
+    assert!(ja.contains("- add shiny feature"), "{ja}");
+    let de = repo.read_file("CHANGELOG.de.md");
+    assert!(de.contains("glänzend-de feature"), "{de}");
+
+    repo.write_file(
+        "belaf/config.toml",
+        &format!("{config}\n[[changelog.outputs]]\nlanguage = \"ja\"\noutput = \"CHANGELOG.md\"\n"),
+    );
+    let output = repo.run_belaf_command(&["changelog"]);
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("already written by another changelog"),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn test_changelog_dedupes_cherry_picked_commits() {
+    let repo = TestRepo::new();
+
+    repo.write_file(
+        "Cargo.toml",
+        r#"[package]
+name = "test-crate"
+version = "0.1.0"
+edition = "2021"
+"#,
+    );
+    repo.write_file("src/lib.rs", "pub fn hello() {}\n");
+    repo.commit("Initial commit");
+
+    let _ = repo.run_belaf_command(&["init", "--force"]);
+    let config = repo.read_file("belaf/config.toml");
+    repo.write_file(
+        "belaf/config.toml",
+        &config.replacen("dedupe_commits = false", "dedupe_commits = true", 1),
+    );
+    repo.commit("chore: drop duplicate changelog entries");
+
+    repo.write_file("src/parser.rs", "pub fn parse() {}");
+    repo.commit("fix(parser): handle empty input");
+    repo.write_file("src/parser.rs", "pub fn parse() { }");
+    repo.commit("fix(parser): handle empty input");
+    repo.write_file("src/lexer.rs", "pub fn lex() {}");
+    repo.commit("feat: tokenize unicode\n\nChange-Id: I8a5c2f");
+    repo.write_file("src/lexer.rs", "pub fn lex() { }");
+    repo.commit("feat: tokenize Unicode input\n\nChange-Id: I8a5c2f");
+    repo.write_file("src/cli.rs", "pub fn cli() {}");
+    repo.commit("fix(cli): handle empty input");
+
+    let output = repo.run_belaf_command(&["changelog"]);
+    assert!(
+        output.status.success(),
+        "Changelog command failed: {:?}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let changelog = repo.read_file("CHANGELOG.md");
+    assert_eq!(
+        changelog.matches("*(parser)* Handle empty input").count(),
+        1,
+        "Content:\n{changelog}"
+    );
+    assert!(
+        changelog.contains("*(cli)* Handle empty input"),
+        "another scope is another change. Content:\n{changelog}"
+    );
+    assert!(
+        changelog.contains("Tokenize unicode") && !changelog.contains("Tokenize Unicode input"),
+        "a Change-Id keeps the first commit. Content:\n{changelog}"
+    );
+}
+
+#[test]
+fn test_changelog_groups_commits_by_scope() {
+    let repo = TestRepo::new();
+
+    repo.write_file(
+        "Cargo.toml",
+        r#"[package]
+name = "test-crate"
+version = "0.1.0"
+edition = "2021"
+"#,
+    );
+    repo.write_file("src/lib.rs", "pub fn hello() {}\n");
+    repo.commit("Initial commit");
+
+    let _ = repo.run_belaf_command(&["init", "--force"]);
+    let config = repo.read_file("belaf/config.toml");
+    repo.write_file(
+        "belaf/config.toml",
+        &config.replacen("group_by_scope = false", "group_by_scope = true", 1),
+    );
+    repo.commit("chore: group changelog entries by scope");
+
+    repo.write_file("src/api.rs", "pub fn list() {}");
+    repo.commit("feat(api): add list endpoint");
+    repo.write_file("src/api.rs", "pub fn list() {}\npub fn get() {}");
+    repo.commit("feat(api): add get endpoint");
+    repo.write_file("src/theme.rs", "pub fn theme() {}");
+    repo.commit("feat: support dark mode");
+
+    let output = repo.run_belaf_command(&["changelog"]);
+    assert!(
+        output.status.success(),
+        "Changelog command failed: {:?}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let changelog = repo.read_file("CHANGELOG.md");
+    let features = changelog
+        .split("Features")
+        .nth(1)
+        .expect("a Features section");
+    let api = features.find("**api:**").expect("an api sub-group");
+    assert!(
+        features
+            .find("- Support dark mode")
+            .is_some_and(|i| i < api),
+        "unscoped commits come first. Content:\n{changelog}"
+    );
+    assert!(
+        features[api..].contains("- Add list endpoint")
+            && features[api..].contains("- Add get endpoint"),
+        "Content:\n{changelog}"
+    );
+    assert!(
+        !changelog.contains("*(api)*"),
+        "the sub-group names the scope. Content:\n{changelog}"
+    );
+}
+
+#[test]
+fn test_changelog_breaking_section_shows_migration_notes() {
+    let repo = TestRepo::new();
+
+    repo.write_file(
+        "Cargo.toml",
+        r#"[package]
+name = "test-crate"
+version = "1.0.0"
+edition = "2021"
+"#,
+    );
+    repo.write_file("src/lib.rs", "pub fn hello() {}\n");
+    repo.commit("Initial commit");
+
+    let _ = repo.run_belaf_command(&["init", "--force"]);
+    repo.commit("chore: add belaf config");
+
+    repo.write_file("src/api.rs", "pub fn v2() {}");
+    repo.commit(
+        "feat(api)!: remove the v1 endpoints\n\n\
+         BREAKING CHANGE: clients must call /v2,\nwhich takes the same arguments.",
+    );
+    repo.write_file("src/config.rs", "pub fn load() {}");
+    repo.commit("feat!: rename the config file");
+
+    let output = repo.run_belaf_command(&["changelog"]);
+    assert!(
+        output.status.success(),
+        "Changelog command failed: {:?}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let changelog = repo.read_file("CHANGELOG.md");
+    let breaking = changelog
+        .split("Breaking Changes")
+        .nth(1)
+        .expect("a breaking changes section");
+    assert!(
+        breaking.contains(
+            "*(api)* Remove the v1 endpoints: clients must call /v2, which takes the same arguments. by"
+        ),
+        "Content:\n{changelog}"
+    );
+    assert!(
+        breaking.contains("- Rename the config file by"),
+        "a `!` alone adds nothing to the subject. Content:\n{changelog}"
+    );
+}
+
+#[test]
+fn test_changelog_mentions_forge_usernames_of_authors() {
+    let repo = TestRepo::new();
+
+    repo.write_file(
+        "Cargo.toml",
+        r#"[package]
+name = "test-crate"
+version = "1.0.0"
+edition = "2021"
+"#,
+    );
+    repo.write_file("src/lib.rs", "pub fn hello() {}\n");
+    repo.commit("Initial commit");
+
+    let _ = repo.run_belaf_command(&["init", "--force"]);
+    repo.commit("chore: add belaf config");
+
+    // Learnt by an earlier run from the forge.
+    repo.write_file(
+        ".git/belaf/author-usernames.json",
+        r#"{ "test@example.com": "tester" }"#,
+    );
+    repo.write_file("src/a.rs", "pub fn a() {}");
+    repo.commit("feat: add a");
+
+    let set_email = |email: &str| {
+        std::process::Command::new("git")
+            .args(["config", "user.email", email])
+            .current_dir(&repo.path)
+            .output()
+            .expect("failed to set git email");
+    };
+    set_email("583231+octocat@users.noreply.github.com");
+    repo.write_file("src/b.rs", "pub fn b() {}");
+    repo.commit("feat: add b");
+    set_email("stranger@example.com");
+    repo.write_file("src/c.rs", "pub fn c() {}");
+    repo.commit("feat: add c");
+
+    let output = repo.run_belaf_command(&["changelog"]);
+    assert!(
+        output.status.success(),
+        "Changelog command failed: {:?}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let changelog = repo.read_file("CHANGELOG.md");
+    assert!(
+        changelog.contains("Add a by @tester"),
+        "Content:\n{changelog}"
+    );
+    assert!(
+        changelog.contains("Add b by @octocat"),
+        "Content:\n{changelog}"
+    );
+    assert!(
+        changelog.contains("Add c by Test User"),
+        "Content:\n{changelog}"
+    );
+}
+
+#[test]
+fn test_changelog_unreleased_write_refreshes_in_place() {
+    let repo = TestRepo::new();
+    repo.write_file(
+        "Cargo.toml",
+        "[package]\nname = \"test-crate\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+    );
+    repo.write_file("src/lib.rs", "pub fn hello() {}\n");
+    repo.commit("Initial commit");
+    let _ = repo.run_belaf_command(&["init", "--force", "--ci"]);
+    repo.commit("chore: add belaf config");
+
+    repo.write_file("src/a.rs", "pub fn a() {}\n");
+    repo.commit("feat: add a");
+
+    let preview = repo.run_belaf_command(&["changelog", "--unreleased"]);
+    assert!(preview.status.success());
+    assert!(
+        !repo.file_exists("CHANGELOG.md"),
+        "--unreleased only previews"
+    );
+
+    let output = repo.run_belaf_command(&["changelog", "--unreleased", "--write", "--ci"]);
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let first = repo.read_file("CHANGELOG.md");
+    assert_eq!(first.matches("## Unreleased").count(), 1, "{first}");
+    assert!(first.contains("Add a"), "{first}");
+
+    let output = repo.run_belaf_command(&["changelog", "--unreleased", "--write", "--ci"]);
+    let status: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(status["files_written"], serde_json::json!([]));
+    assert_eq!(repo.read_file("CHANGELOG.md"), first, "rerun is a no-op");
+
+    repo.write_file("src/b.rs", "pub fn b() {}\n");
+    repo.commit("fix: handle b");
+    let _ = repo.run_belaf_command(&["changelog", "--unreleased", "--write", "--ci"]);
+    let second = repo.read_file("CHANGELOG.md");
+    assert_eq!(second.matches("## Unreleased").count(), 1, "{second}");
+    assert!(
+        second.contains("Add a") && second.contains("Handle b"),
+        "{second}"
+    );
+}
+
+#[test]
+fn test_changelog_sections_follow_group_order() {
+    let repo = TestRepo::new();
+    repo.write_file(
+        "Cargo.toml",
+        "[package]\nname = \"test-crate\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+    );
+    repo.write_file("src/lib.rs", "pub fn hello() {}\n");
+    repo.commit("Initial commit");
+    let _ = repo.run_belaf_command(&["init", "--force", "--ci"]);
+    repo.commit("chore: add belaf config");
+
+    repo.write_file("src/a.rs", "pub fn a() {}\n");
+    repo.commit("fix: handle a");
+    repo.write_file("src/b.rs", "pub fn b() {}\n");
+    repo.commit("feat: add b");
+
+    let output = repo.run_belaf_command(&["changelog", "--stdout"]);
+    assert!(output.status.success());
+    let changelog = String::from_utf8_lossy(&output.stdout).into_owned();
+    let features = changelog.find("Features").expect("a Features section");
+    let fixes = changelog.find("Bug Fixes").expect("a Bug Fixes section");
+    assert!(features < fixes, "Features come first:\n{changelog}");
+    assert!(
+        !changelog.contains("Documentation"),
+        "empty groups are left out:\n{changelog}"
+    );
+
+    let config = repo.read_file("belaf/config.toml");
+    let start = config
+        .find("group_order = [")
+        .expect("a default group_order");
+    let end = start + config[start..].find(']').unwrap() + 1;
+    repo.write_file(
+        "belaf/config.toml",
+        &config.replace(&config[start..end], r#"group_order = ["bug fixes"]"#),
+    );
+
+    let output = repo.run_belaf_command(&["changelog", "--stdout"]);
+    let changelog = String::from_utf8_lossy(&output.stdout).into_owned();
+    let features = changelog.find("Features").expect("a Features section");
+    let fixes = changelog.find("Bug Fixes").expect("a Bug Fixes section");
+    assert!(fixes < features, "configured order wins:\n{changelog}");
+}
+
+#[test]
+fn test_changelog_templates_see_the_previous_release() {
+    let repo = TestRepo::new();
+    repo.write_file(
+        "Cargo.toml",
+        "[package]\nname = \"test-crate\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+    );
+    repo.write_file("src/lib.rs", "pub fn hello() {}\n");
+    repo.commit("Initial commit");
+    let _ = repo.run_belaf_command(&["init", "--force", "--ci"]);
+    let config = repo.read_file("belaf/config.toml").replacen(
+        "body = \"\"\"\n",
+        "body = \"\"\"\nSince {{ previous_version }} ({{ previous_tag }}) on {{ release_date }}: {{ compare_url }}\n",
+        1,
+    );
+    repo.write_file("belaf/config.toml", &config);
+    repo.commit("chore: add belaf config");
+    std::process::Command::new("git")
+        .args(["tag", "test-crate-v0.1.0"])
+        .current_dir(&repo.path)
+        .output()
+        .expect("failed to tag");
+
+    repo.write_file("src/feature.rs", "pub fn feature() {}\n");
+    repo.commit("feat: add shiny feature");
+
+    let output = repo
+        .run_belaf_command_with_env(&["changelog", "--stdout"], &[("BELAF_DETERMINISTIC", "1")]);
+    let changelog = String::from_utf8_lossy(&output.stdout).into_owned();
+    assert!(output.status.success(), "{changelog}");
+    assert!(
+        changelog.contains(
+            "Since 0.1.0 (test-crate-v0.1.0) on 2024-01-01: \
+             https://github.com/test/repo/compare/test-crate-v0.1.0...test-crate-v0.2.0"
+        ),
+        "{changelog}"
+    );
+}
+
+#[test]
+fn test_changelog_lists_dependencies_upgraded_in_the_lockfile() {
+    let lock = |serde: &str| {
+        format!(
+            "version = 4\n\n\
+             [[package]]\nname = \"serde\"\nversion = \"{serde}\"\n\
+             source = \"registry+https://github.com/rust-lang/crates.io-index\"\n\n\
+             [[package]]\nname = \"test-crate\"\nversion = \"0.1.0\"\n"
+        )
+    };
+    let repo = TestRepo::new();
+    repo.write_file(
+        "Cargo.toml",
+        "[package]\nname = \"test-crate\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+    );
+    repo.write_file("src/lib.rs", "pub fn hello() {}\n");
+    repo.commit("Initial commit");
+    let _ = repo.run_belaf_command(&["init", "--force", "--ci"]);
+    repo.write_file("Cargo.lock", &lock("1.0.200"));
+    repo.commit("chore: add belaf config");
+    std::process::Command::new("git")
+        .args(["tag", "test-crate-v0.1.0"])
+        .current_dir(&repo.path)
+        .output()
+        .expect("failed to tag");
+
+    repo.write_file("Cargo.lock", &lock("1.0.210"));
+    repo.commit("fix: pick up the serde fix");
+
+    let output = repo.run_belaf_command(&["changelog", "--stdout"]);
+    let changelog = String::from_utf8_lossy(&output.stdout).into_owned();
+    assert!(output.status.success(), "{changelog}");
+    assert!(!changelog.contains("### Dependencies"), "{changelog}");
+
+    let config = repo.read_file("belaf/config.toml").replacen(
+        "[changelog]\n",
+        "[changelog]\ninclude_dependencies = true\n",
+        1,
+    );
+    repo.write_file("belaf/config.toml", &config);
+    let output = repo.run_belaf_command(&["changelog", "--stdout"]);
+    let changelog = String::from_utf8_lossy(&output.stdout).into_owned();
+    assert!(output.status.success(), "{changelog}");
+    assert!(
+        changelog.contains("### Dependencies\n- serde: 1.0.200 → 1.0.210\n"),
+        "{changelog}"
+    );
+    assert!(!changelog.contains("test-crate: "), "{changelog}");
+}
+
+#[test]
+fn test_changelog_authors_go_through_the_mailmap() {
+    let repo = TestRepo::new();
+    let git = |args: &[&str]| {
+        let output = std::process::Command::new("git")
+            .args(args)
+            .current_dir(&repo.path)
+            .output()
+            .expect("failed to run git");
+        assert!(
+            output.status.success(),
+            "git {args:?} failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    };
+    repo.write_file(
+        "Cargo.toml",
+        "[package]\nname = \"test-crate\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+    );
+    repo.write_file("src/lib.rs", "pub fn hello() {}\n");
+    repo.write_file(
+        ".mailmap",
+        "Jane Doe <jane@example.com> <jd@old-laptop.local>\n",
+    );
+    repo.commit("Initial commit");
+    let _ = repo.run_belaf_command(&["init", "--force", "--ci"]);
+    repo.commit("chore: add belaf config");
+
+    git(&["config", "user.name", "jd"]);
+    git(&["config", "user.email", "jd@old-laptop.local"]);
+    repo.write_file("src/a.rs", "pub fn a() {}\n");
+    repo.commit("feat: add a");
+
+    let output = repo.run_belaf_command(&["changelog", "--stdout"]);
+    let changelog = String::from_utf8_lossy(&output.stdout).into_owned();
+    assert!(output.status.success(), "{changelog}");
+    assert!(changelog.contains("Add a by Jane Doe"), "{changelog}");
+    assert!(!changelog.contains("by jd"), "{changelog}");
+}
+
+#[test]
+fn test_changelog_entries_are_reused_until_no_cache() {
+    let repo = TestRepo::new();
+
+    repo.write_file(
+        "Cargo.toml",
+        r#"[package]
+name = "test-crate"
+version = "0.1.0"
+edition = "2021"
+"#,
+    );
+    repo.write_file("src/lib.rs", "pub fn hello() {}\n");
+    repo.commit("Initial commit");
+
+    let _ = repo.run_belaf_command(&["init", "--force"]);
+
+    // Counts its runs in a file git doesn't see.
+    let config = repo.read_file("belaf/config.toml");
+    repo.write_file(
+        "belaf/config.toml",
+        &format!(
+            "{config}\n[changelog.format_command]\ncommands = [\"sh -c 'echo run >> .git/format-runs; cat'\"]\n"
+        ),
+    );
+    repo.commit("chore: format changelog entries");
+
+    repo.write_file("src/feature.rs", "pub fn feature() {}");
+    repo.commit("feat: add shiny feature");
+
+    let runs = |args: &[&str]| {
+        let output = repo.run_belaf_command(args);
+        assert!(
+            output.status.success(),
+            "{args:?} failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let runs = repo.read_file(".git/format-runs").lines().count();
+        repo.write_file(".git/format-runs", "");
+        runs
+    };
+    // The entry is written to the file and echoed: rendered once, then
+    // taken from the cache.
+    assert_eq!(runs(&["changelog"]), 1);
+    assert_eq!(runs(&["changelog"]), 0);
+    assert_eq!(runs(&["changelog", "--no-cache"]), 2);
+    assert!(repo.read_file("CHANGELOG.md").contains("shiny feature"));
+}
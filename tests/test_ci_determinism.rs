@@ -28,14 +28,14 @@ impl Seedable for TestRepo {
         &self.path
     }
     fn write_file(&self, relative: &str, content: &str) {
-        TestRepo::write_file(self, relative, content);
+        belaf::test_support::TestRepo::write_file(self, relative, content);
     }
     fn commit(&self, message: &str) {
-        TestRepo::commit(self, message);
+        belaf::test_support::TestRepo::commit(self, message);
     }
 }
 
-fn run_init(repo: &TestRepo) -> std::process::Output {
+fn run_init(repo: &TestRepo) -> belaf::in_process::CommandOutput {
     repo.run_belaf_command_with_env(
         &["--no-color", "init", "--ci", "--auto-detect"],
         &[("BELAF_NO_KEYRING", "1"), ("NO_COLOR", "1")],
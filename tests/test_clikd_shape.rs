@@ -23,10 +23,10 @@ impl Seedable for TestRepo {
         &self.path
     }
     fn write_file(&self, relative: &str, content: &str) {
-        TestRepo::write_file(self, relative, content);
+        belaf::test_support::TestRepo::write_file(self, relative, content);
     }
     fn commit(&self, message: &str) {
-        TestRepo::commit(self, message);
+        belaf::test_support::TestRepo::commit(self, message);
     }
 }
 
@@ -58,6 +58,7 @@ fn detector_finds_expected_bundle_kinds() {
             DetectedShape::Hint(HintKind::SdkCascade) => "sdk_cascade_member",
             DetectedShape::Hint(HintKind::SingleProject { .. }) => "single_project",
             DetectedShape::Hint(HintKind::NestedMonorepo) => "nested_monorepo",
+            DetectedShape::Hint(HintKind::Submodule) => "submodule",
         })
         .collect();
 
@@ -0,0 +1,89 @@
+//! `scopes = [...]` on `[release_unit.<name>]`: `belaf lint-commits`
+//! flags pending commits with other scopes and `belaf status` counts
+//! scope usage.
+
+mod common;
+
+use common::TestRepo;
+
+fn repo_with_scoped_commits() -> TestRepo {
+    let repo = TestRepo::new();
+    repo.write_file(
+        "Cargo.toml",
+        "[package]\nname = \"api\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+    );
+    repo.write_file("src/lib.rs", "pub fn hello() {}\n");
+    repo.commit("Initial commit");
+
+    let output = repo.run_belaf_command(&["init", "--force"]);
+    assert!(
+        output.status.success(),
+        "init failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let config = repo.read_file("belaf/config.toml");
+    repo.write_file(
+        "belaf/config.toml",
+        &format!("{config}\n[release_unit.api]\nscopes = [\"http\", \"db\"]\n"),
+    );
+    repo.commit("chore: define api scopes");
+
+    repo.write_file("src/http.rs", "pub fn get() {}\n");
+    repo.commit("feat(http): add client");
+    repo.write_file("src/db.rs", "pub fn open() {}\n");
+    repo.commit("fix(DB): close the pool");
+    repo.write_file("src/http.rs", "pub fn get() { retry() }\n");
+    repo.commit("fix(htp): retry requests");
+    repo
+}
+
+#[test]
+fn test_lint_commits_flags_scopes_outside_the_taxonomy() {
+    let repo = repo_with_scoped_commits();
+
+    let output = repo.run_belaf_command(&["lint-commits"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "warnings must not fail: {stdout}");
+    assert!(stdout.contains("fix(htp): retry requests"), "{stdout}");
+    assert!(
+        stdout.contains("scope `htp` is not one of: http, db"),
+        "{stdout}"
+    );
+    assert!(!stdout.contains("close the pool"), "{stdout}");
+
+    let output = repo.run_belaf_command(&["lint-commits", "--strict", "--format", "json"]);
+    assert_eq!(output.status.code(), Some(4));
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("lint-commits prints JSON");
+    let problems = json["problems"].as_array().expect("problems array");
+    assert_eq!(problems.len(), 1, "{json}");
+    assert_eq!(problems[0]["unit"], "api");
+    assert_eq!(problems[0]["scope"], "htp");
+}
+
+#[test]
+fn test_status_reports_scope_usage() {
+    let repo = repo_with_scoped_commits();
+
+    let output = repo.run_belaf_command(&["status", "--format", "json"]);
+    assert!(
+        output.status.success(),
+        "status failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("status prints JSON");
+    let scopes = &json["projects"][0]["scopes"];
+    assert_eq!(scopes["scopes"]["http"], 1, "{scopes}");
+    assert_eq!(scopes["scopes"]["db"], 1, "{scopes}");
+    assert_eq!(scopes["unknown"], serde_json::json!(["htp"]), "{scopes}");
+
+    let output = repo.run_belaf_command(&["status", "--format", "text"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("scopes: db 1, htp 1, http 1"), "{stdout}");
+    assert!(stdout.contains("not in the unit's scopes: htp"), "{stdout}");
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("belaf lint-commits"),
+        "history analysis should warn about the unknown scope"
+    );
+}
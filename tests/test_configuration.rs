@@ -1,4 +1,5 @@
 mod common;
+use belaf::core::config::ConfigurationFile;
 use common::TestRepo;
 
 fn setup_basic_cargo_project(repo: &TestRepo) {
@@ -1165,6 +1166,40 @@ scope_matching = "smart"
     );
 }
 
+#[test]
+fn test_changelog_group_emojis_per_ecosystem_and_project() {
+    let repo = TestRepo::new();
+    setup_basic_cargo_project(&repo);
+    let _ = repo.run_belaf_command(&["init", "--force"]);
+    let config = repo.read_file("belaf/config.toml");
+    write_custom_config(
+        &repo,
+        &format!("{config}\n[changelog.ecosystems.cargo.group_emojis]\nfeatures = \"🚀\"\n"),
+    );
+
+    repo.write_file("src/feature.rs", "pub fn feature() {}");
+    repo.commit("feat: add rocket feature");
+
+    let output = repo.run_belaf_command(&["changelog", "--stdout"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("### 🚀 Features") && !stdout.contains("✨"),
+        "the cargo emoji replaces the default one, got: {stdout}"
+    );
+
+    let config = repo.read_file("belaf/config.toml");
+    write_custom_config(
+        &repo,
+        &format!("{config}\n[changelog.projects.test-crate]\nemoji_groups = false\n"),
+    );
+    let output = repo.run_belaf_command(&["changelog", "--stdout"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("### Features") && !stdout.contains("🚀"),
+        "the project turns emoji off, got: {stdout}"
+    );
+}
+
 #[test]
 fn test_changelog_custom_commit_parsers() {
     let repo = TestRepo::new();
@@ -1243,6 +1278,184 @@ scope_matching = "smart"
     );
 }
 
+#[test]
+fn test_changelog_commit_parsers_match_paths_and_group_from_scope() {
+    let repo = TestRepo::new();
+    setup_basic_cargo_project(&repo);
+    let _ = repo.run_belaf_command(&["init", "--force"]);
+
+    let config = r##"[repo]
+upstream_urls = []
+
+[repo.analysis]
+commit_cache_size = 512
+tree_cache_size = 3
+
+[changelog]
+body = """
+{% for group, group_commits in commits | group_by(attribute="group") %}
+### {{ group }}
+{% for commit in group_commits %}- {{ commit.message }}
+{% endfor %}{% endfor %}
+"""
+trim = true
+output = "CHANGELOG.md"
+conventional_commits = true
+protect_breaking_commits = true
+filter_unconventional = false
+filter_commits = false
+sort_commits = "oldest"
+include_breaking_section = false
+include_contributors = false
+include_statistics = false
+emoji_groups = false
+
+[[changelog.commit_parsers]]
+paths = ["docs/**"]
+group = "Documentation"
+
+[[changelog.commit_parsers]]
+message = "^chore"
+group_from_scope = true
+group = "Chores"
+
+[[changelog.commit_parsers]]
+message = "^feat"
+group = "New Features"
+
+[bump]
+features_always_bump_minor = true
+breaking_always_bump_major = true
+initial_tag = "0.1.0"
+
+[commit_attribution]
+strategy = "scope_first"
+scope_matching = "smart"
+"##;
+    write_custom_config(&repo, config);
+
+    repo.write_file("docs/guide/intro.md", "# Intro\n");
+    repo.commit("feat: write the guide");
+    repo.write_file("src/deps.rs", "pub fn deps() {}");
+    repo.commit("chore(deps): bump serde");
+    repo.write_file("src/feature.rs", "pub fn feature() {}");
+    repo.commit("feat: add new feature");
+
+    let output = repo.run_belaf_command(&["changelog", "--stdout"]);
+    assert!(
+        output.status.success(),
+        "Command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("### Documentation\n- write the guide"),
+        "a docs/ commit is documentation whatever its type, got: {stdout}"
+    );
+    assert!(
+        stdout.contains("### deps\n- bump serde"),
+        "the scope names the group, got: {stdout}"
+    );
+    assert!(
+        stdout.contains("### Chores\n- update config"),
+        "unscoped commits keep `group`, got: {stdout}"
+    );
+    assert!(
+        stdout.contains("### New Features\n- add new feature"),
+        "got: {stdout}"
+    );
+}
+
+#[test]
+fn test_changelog_commit_parsers_match_authors() {
+    let repo = TestRepo::new();
+    setup_basic_cargo_project(&repo);
+    let _ = repo.run_belaf_command(&["init", "--force"]);
+
+    let config = r##"[repo]
+upstream_urls = []
+
+[repo.analysis]
+commit_cache_size = 512
+tree_cache_size = 3
+
+[changelog]
+body = """
+{% for group, group_commits in commits | group_by(attribute="group") %}
+### {{ group }}
+{% for commit in group_commits %}- {{ commit.message }}
+{% endfor %}{% endfor %}
+"""
+trim = true
+output = "CHANGELOG.md"
+conventional_commits = true
+protect_breaking_commits = true
+filter_unconventional = false
+filter_commits = false
+sort_commits = "oldest"
+include_breaking_section = false
+include_contributors = false
+include_statistics = false
+emoji_groups = false
+
+[[changelog.commit_parsers]]
+author = "^dependabot"
+skip = true
+
+[[changelog.commit_parsers]]
+author = "^renovate"
+group = "Dependencies"
+
+[[changelog.commit_parsers]]
+message = "^(feat|chore)"
+group = "Changes"
+
+[bump]
+features_always_bump_minor = true
+breaking_always_bump_major = true
+initial_tag = "0.1.0"
+
+[commit_attribution]
+strategy = "scope_first"
+scope_matching = "smart"
+"##;
+    write_custom_config(&repo, config);
+
+    let set_author = |name: &str| {
+        std::process::Command::new("git")
+            .args(["config", "user.name", name])
+            .current_dir(&repo.path)
+            .output()
+            .expect("failed to set git user name");
+    };
+    set_author("dependabot[bot]");
+    repo.write_file("src/a.rs", "pub fn a() {}");
+    repo.commit("chore(deps): bump serde from 1.0.1 to 1.0.2");
+    set_author("renovate[bot]");
+    repo.write_file("src/b.rs", "pub fn b() {}");
+    repo.commit("chore(deps): update tokio to 1.40");
+    set_author("Test User");
+    repo.write_file("src/c.rs", "pub fn c() {}");
+    repo.commit("feat: add c");
+
+    let output = repo.run_belaf_command(&["changelog", "--stdout"]);
+    assert!(
+        output.status.success(),
+        "Command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        !stdout.contains("bump serde"),
+        "dependabot commits are skipped, got: {stdout}"
+    );
+    assert!(
+        stdout.contains("### Dependencies\n- update tokio to 1.40"),
+        "renovate commits are grouped, got: {stdout}"
+    );
+    assert!(stdout.contains("- add c"), "got: {stdout}");
+}
+
 #[test]
 fn test_changelog_commit_parser_skip() {
     let repo = TestRepo::new();
@@ -2471,3 +2684,282 @@ fn test_changelog_sha_links_format() {
         );
     }
 }
+
+#[test]
+fn test_changelog_links_follow_the_upstream_forge() {
+    let repo = TestRepo::new();
+    setup_basic_cargo_project(&repo);
+    let git = |args: &[&str]| {
+        std::process::Command::new("git")
+            .args(args)
+            .current_dir(&repo.path)
+            .output()
+            .expect("failed to run git")
+    };
+    git(&[
+        "remote",
+        "set-url",
+        "origin",
+        "git@gitlab.example.com:team/repo.git",
+    ]);
+
+    let _ = repo.run_belaf_command(&["init", "--force"]);
+    repo.commit("chore: init belaf");
+    git(&["tag", "test-crate-v0.5.0"]);
+    repo.write_file("src/feature.rs", "pub fn feature() {}");
+    repo.commit("feat: add new feature");
+
+    let output = repo.run_belaf_command(&["changelog", "--preview"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "Command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let commit_link =
+        regex::Regex::new(r"\(https://gitlab\.example\.com/team/repo/-/commit/[a-f0-9]{40}\)")
+            .unwrap();
+    assert!(
+        commit_link.is_match(&stdout),
+        "GitLab commit links: {stdout}"
+    );
+    assert!(
+        stdout.contains(
+            "(https://gitlab.example.com/team/repo/-/compare/test-crate-v0.5.0...test-crate-v0.6.0)"
+        ),
+        "compare link between the release tags: {stdout}"
+    );
+}
+
+#[test]
+fn test_migrate_rewrites_retired_config_keys() {
+    let repo = TestRepo::new();
+    setup_basic_cargo_project(&repo);
+    let config = format!(
+        "{}\n# Released on its own\n[[release_unit]]\nname = \"test-crate\"\ntag_format = \"v{{version}}\"\n",
+        base_config(true, false)
+    );
+    write_custom_config(&repo, &config);
+
+    let output = repo.run_belaf_command(&["status"]);
+    assert!(!output.status.success(), "retired keys must not load");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("run `belaf migrate`"),
+        "the error should suggest belaf migrate, got: {stderr}"
+    );
+
+    let output = repo.run_belaf_command(&["migrate"]);
+    assert!(
+        output.status.success(),
+        "migrate failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let migrated = repo.read_file("belaf/config.toml");
+    assert!(migrated.contains("# Released on its own\n[release_unit.test-crate]\ntag_format"));
+    assert!(!migrated.contains("[[release_unit]]"));
+
+    let output = repo.run_belaf_command(&["status"]);
+    assert!(
+        output.status.success(),
+        "the migrated config should load: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn test_webhook_urls_must_be_http() {
+    let repo = TestRepo::new();
+    setup_basic_cargo_project(&repo);
+    let config = format!(
+        "{}\n[webhooks]\nurls = [\"ftp://deploy.example.com/hook\"]\nevents = [\"released\"]\n",
+        base_config(true, false)
+    );
+    write_custom_config(&repo, &config);
+
+    let output = repo.run_belaf_command(&["status"]);
+    assert!(!output.status.success(), "a non-http webhook must not load");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("invalid [webhooks] url `ftp://deploy.example.com/hook`"),
+        "got: {stderr}"
+    );
+
+    let config = format!(
+        "{}\n[webhooks]\nurls = [\"https://deploy.example.com/hook\"]\nevents = [\"released\"]\n",
+        base_config(true, false)
+    );
+    write_custom_config(&repo, &config);
+    let output = repo.run_belaf_command(&["status"]);
+    assert!(
+        output.status.success(),
+        "an https webhook should load: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn test_config_layers_merge_includes_and_project_configs() {
+    let repo = TestRepo::new();
+    setup_basic_cargo_project(&repo);
+    repo.write_file(
+        "teams/payments/belaf.toml",
+        "[webhooks]\nurls = [\"https://payments.example.com/hook\"]\n",
+    );
+    repo.write_file(
+        "services/api/belaf.toml",
+        "[webhooks]\nurls = [\"https://api.example.com/hook\"]\nsecret_env = \"API_HOOK_SECRET\"\n\n\
+         [group.api]\nmembers = [\"api\", \"api-client\"]\n",
+    );
+    write_custom_config(
+        &repo,
+        &format!(
+            "include = [\"teams/payments/belaf.toml\"]\n\n{}",
+            base_config(true, false)
+        ),
+    );
+
+    let cfg = ConfigurationFile::get(repo.path.join("belaf/config.toml")).unwrap();
+    assert_eq!(
+        cfg.sources,
+        vec![
+            repo.path.join("services/api/belaf.toml"),
+            repo.path.join("teams/payments/belaf.toml"),
+            repo.path.join("belaf/config.toml"),
+        ]
+    );
+    assert_eq!(
+        cfg.webhooks.urls,
+        vec!["https://payments.example.com/hook".to_string()],
+        "the included fragment outranks the project config"
+    );
+    assert_eq!(cfg.webhooks.secret_env.as_deref(), Some("API_HOOK_SECRET"));
+    assert_eq!(cfg.groups.len(), 1);
+    assert_eq!(cfg.groups[0].members, vec!["api", "api-client"]);
+    assert_eq!(cfg.changelog.output, "CHANGELOG.md");
+}
+
+#[test]
+fn test_config_includes_must_exist_and_not_nest() {
+    let repo = TestRepo::new();
+    setup_basic_cargo_project(&repo);
+    let config = format!(
+        "include = [\"teams/payments/belaf.toml\"]\n\n{}",
+        base_config(true, false)
+    );
+    write_custom_config(&repo, &config);
+
+    let output = repo.run_belaf_command(&["status"]);
+    assert!(!output.status.success(), "a missing include must not load");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("`include` entry `teams/payments/belaf.toml`"),
+        "got: {stderr}"
+    );
+
+    repo.write_file(
+        "teams/payments/belaf.toml",
+        "include = [\"teams/search/belaf.toml\"]\n",
+    );
+    repo.commit("chore: add payments config");
+    let output = repo.run_belaf_command(&["status"]);
+    assert!(!output.status.success(), "a nested include must not load");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("sets `include`"), "got: {stderr}");
+}
+
+#[test]
+fn test_follow_renames_attributes_commits_before_a_move() {
+    let repo = TestRepo::new();
+    repo.write_file(
+        "packages/a/package.json",
+        r#"{"name": "a", "version": "1.0.0"}"#,
+    );
+    repo.write_file("packages/a/index.js", "module.exports = 1;\n");
+    repo.commit("Initial commit");
+    let _ = repo.run_belaf_command(&["init", "--force"]);
+    repo.commit("chore: add belaf config");
+
+    repo.write_file("packages/a/index.js", "module.exports = 2;\n");
+    repo.commit("feat: return two");
+    std::fs::create_dir_all(repo.path.join("libs")).unwrap();
+    std::fs::rename(repo.path.join("packages/a"), repo.path.join("libs/a")).unwrap();
+    repo.commit("refactor: move a under libs");
+
+    let pending = |repo: &TestRepo| -> serde_json::Value {
+        let output = repo.run_belaf_command(&["status", "--format", "json"]);
+        assert!(
+            output.status.success(),
+            "status failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let json: serde_json::Value =
+            serde_json::from_slice(&output.stdout).expect("status prints JSON");
+        json["projects"][0]["commits"].clone()
+    };
+    assert_eq!(
+        pending(&repo),
+        serde_json::json!(["refactor: move a under libs"])
+    );
+
+    let config = repo
+        .read_file("belaf/config.toml")
+        .replace("follow_renames = false", "follow_renames = true");
+    write_custom_config(&repo, &config);
+    assert_eq!(
+        pending(&repo),
+        serde_json::json!([
+            "refactor: move a under libs",
+            "feat: return two",
+            // `init` normalised packages/a/package.json.
+            "chore: add belaf config",
+        ])
+    );
+}
+
+#[test]
+fn test_history_bounds_truncate_the_walk() {
+    let repo = TestRepo::new();
+    repo.write_file("package.json", r#"{"name": "a", "version": "1.0.0"}"#);
+    repo.commit("Initial commit");
+    let _ = repo.run_belaf_command(&["init", "--force"]);
+    repo.commit("chore: add belaf config");
+    repo.write_file("index.js", "module.exports = 1;\n");
+    repo.commit("feat: export one");
+    repo.write_file("index.js", "module.exports = 2;\n");
+    repo.commit("fix: export two");
+
+    let status = |repo: &TestRepo, args: &[&str]| -> serde_json::Value {
+        let output = repo.run_belaf_command(args);
+        assert!(
+            output.status.success(),
+            "status failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        let json: serde_json::Value =
+            serde_json::from_slice(&output.stdout).expect("status prints JSON");
+        json["projects"][0].clone()
+    };
+
+    let full = status(&repo, &["status", "--format", "json"]);
+    assert!(full["commits_count"].as_u64() > Some(2), "got: {full}");
+    assert!(full.get("truncated").is_none(), "got: {full}");
+
+    let bounded = status(&repo, &["status", "--format", "json", "--max-depth", "2"]);
+    assert_eq!(
+        bounded["commits"],
+        serde_json::json!(["fix: export two", "feat: export one"])
+    );
+    assert_eq!(bounded["truncated"], "stopped after 2 commits");
+
+    let config = repo.read_file("belaf/config.toml").replace(
+        "[repo.analysis]\n",
+        "[repo.analysis]\nsince_date = \"not a date\"\n",
+    );
+    write_custom_config(&repo, &config);
+    let output = repo.run_belaf_command(&["status", "--format", "json"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("YYYY-MM-DD"), "got: {stderr}");
+}
@@ -0,0 +1,311 @@
+//! `dep_requirement` strategies end to end: prepare bumps a dependee
+//! and the dependent's manifest ends up with the requirement the
+//! configured strategy renders, once per ecosystem that writes
+//! internal requirements. A requirement the new version breaks stops
+//! `prepare --ci`; the wizard's resolutions rewrite it.
+
+mod common;
+
+use belaf::{
+    core::{
+        session::AppSession,
+        workflow::{BumpChoice, ConflictResolution, PrepareContext, ReleaseUnitSelection},
+    },
+    in_process,
+};
+use common::TestRepo;
+
+/// Run `init`, then append `extra` to the generated config and commit.
+fn init_with_config(repo: &TestRepo, extra: &str) {
+    let output = repo.run_belaf_command(&["init", "--force"]);
+    assert!(
+        output.status.success(),
+        "Init failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let config = repo.read_file("belaf/config.toml");
+    repo.write_file("belaf/config.toml", &format!("{config}\n{extra}"));
+    repo.commit("chore: add belaf config");
+}
+
+#[test]
+fn test_cargo_dependent_gets_caret_requirement() {
+    let repo = TestRepo::new();
+
+    repo.write_file(
+        "Cargo.toml",
+        r#"[workspace]
+members = ["packages/*"]
+resolver = "2"
+"#,
+    );
+    repo.write_file(
+        "packages/core/Cargo.toml",
+        r#"[package]
+name = "req-core"
+version = "1.0.0"
+edition = "2021"
+"#,
+    );
+    repo.write_file("packages/core/src/lib.rs", "pub fn core_fn() {}\n");
+    repo.write_file(
+        "packages/app/Cargo.toml",
+        r#"[package]
+name = "req-app"
+version = "1.0.0"
+edition = "2021"
+
+[dependencies]
+req-core = { path = "../core", version = "1.0.0" }
+"#,
+    );
+    repo.write_file("packages/app/src/lib.rs", "pub fn app_fn() {}\n");
+    repo.commit("Initial commit");
+
+    init_with_config(&repo, "[ecosystems.cargo]\ndep_requirement = \"caret\"\n");
+
+    repo.write_file("packages/core/src/feature.rs", "pub fn feature() {}\n");
+    repo.commit("feat(core): add feature");
+
+    let _ = repo.run_belaf_command(&["prepare", "--ci"]);
+
+    let core_toml = repo.read_file("packages/core/Cargo.toml");
+    assert!(
+        core_toml.contains("version = \"1.1.0\""),
+        "req-core should be bumped to 1.1.0. Got: {core_toml}"
+    );
+    let app_toml = repo.read_file("packages/app/Cargo.toml");
+    assert!(
+        app_toml.contains(r#"req-core = { path = "../core", version = "^1.1" }"#),
+        "req-app should require ^1.1 of req-core. Got: {app_toml}"
+    );
+}
+
+#[test]
+fn test_npm_dependent_gets_exact_requirement() {
+    let repo = TestRepo::new();
+
+    repo.write_file(
+        "package.json",
+        r#"{
+  "name": "req-root",
+  "private": true,
+  "workspaces": ["packages/*"]
+}
+"#,
+    );
+    repo.write_file(
+        "packages/core/package.json",
+        r#"{
+  "name": "@req/core",
+  "version": "1.0.0"
+}
+"#,
+    );
+    repo.write_file("packages/core/index.js", "module.exports = {};\n");
+    repo.write_file(
+        "packages/app/package.json",
+        r#"{
+  "name": "@req/app",
+  "version": "1.0.0",
+  "dependencies": {
+    "@req/core": "^1.0.0"
+  }
+}
+"#,
+    );
+    repo.write_file("packages/app/index.js", "require('@req/core');\n");
+    repo.commit("Initial commit");
+
+    init_with_config(&repo, "[ecosystems.npm]\ndep_requirement = \"exact\"\n");
+
+    repo.write_file("packages/core/fix.js", "module.exports.fix = () => {};\n");
+    repo.commit("fix(core): handle empty input");
+
+    let _ = repo.run_belaf_command(&["prepare", "--ci"]);
+
+    let core_json = repo.read_file("packages/core/package.json");
+    assert!(
+        core_json.contains("\"version\": \"1.0.1\""),
+        "@req/core should be bumped to 1.0.1. Got: {core_json}"
+    );
+    let app_json = repo.read_file("packages/app/package.json");
+    assert!(
+        app_json.contains("\"@req/core\": \"1.0.1\""),
+        "@req/app should require exactly 1.0.1 of @req/core. Got: {app_json}"
+    );
+}
+
+#[test]
+fn test_pypa_dependent_gets_tilde_requirement() {
+    let repo = TestRepo::new();
+
+    repo.write_file(
+        "core/setup.py",
+        r#"from setuptools import setup
+
+setup(
+    name="reqcore",  # belaf project-name
+    version="1.0.0",  # belaf project-version
+)
+"#,
+    );
+    repo.write_file("core/reqcore/__init__.py", "");
+    repo.write_file(
+        "app/setup.py",
+        r#"from setuptools import setup
+
+setup(
+    name="reqapp",  # belaf project-name
+    version="1.0.0",  # belaf project-version
+    install_requires=[
+        "reqcore>=1.0",  # belaf internal-req reqcore
+    ],
+)
+"#,
+    );
+    repo.write_file(
+        "app/pyproject.toml",
+        r#"[tool.belaf]
+annotated_files = ["setup.py"]
+
+[tool.belaf.internal_dep_versions]
+reqcore = "thiscommit:2026-01-01:reqcore"
+"#,
+    );
+    repo.write_file("app/reqapp/__init__.py", "");
+    repo.commit("Initial commit");
+
+    init_with_config(&repo, "[ecosystems.pypa]\ndep_requirement = \"tilde\"\n");
+
+    repo.write_file("core/reqcore/feature.py", "def feature(): pass\n");
+    repo.commit("feat(reqcore): add feature");
+
+    let _ = repo.run_belaf_command(&["prepare", "--ci"]);
+
+    let core_setup = repo.read_file("core/setup.py");
+    assert!(
+        core_setup.contains("version=\"1.1.0\""),
+        "reqcore should be bumped to 1.1.0. Got: {core_setup}"
+    );
+    let app_setup = repo.read_file("app/setup.py");
+    assert!(
+        app_setup.contains("\"reqcore~=1.1.0\",  # belaf internal-req reqcore"),
+        "reqapp should require reqcore~=1.1.0. Got: {app_setup}"
+    );
+}
+
+/// `reqapp` pins `reqcore` to `=1.0.0`, kept as written, and
+/// `reqcore` has a feature since.
+fn pinned_dependent(repo: &TestRepo) {
+    repo.write_file(
+        "Cargo.toml",
+        "[workspace]\nmembers = [\"packages/*\"]\nresolver = \"2\"\n",
+    );
+    repo.write_file(
+        "packages/core/Cargo.toml",
+        "[package]\nname = \"reqcore\"\nversion = \"1.0.0\"\nedition = \"2021\"\n",
+    );
+    repo.write_file("packages/core/src/lib.rs", "pub fn core_fn() {}\n");
+    repo.write_file(
+        "packages/app/Cargo.toml",
+        r#"[package]
+name = "reqapp"
+version = "1.0.0"
+edition = "2021"
+
+[dependencies]
+reqcore = { path = "../core", version = "=1.0.0" }
+"#,
+    );
+    repo.write_file("packages/app/src/lib.rs", "pub fn app_fn() {}\n");
+    repo.commit("Initial commit");
+    init_with_config(repo, "[ecosystems.cargo]\ndep_requirement = \"keep\"\n");
+    repo.write_file("packages/core/src/feature.rs", "pub fn feature() {}\n");
+    repo.commit("feat(core): add feature");
+}
+
+#[test]
+fn test_ci_prepare_fails_before_writing_on_a_rejected_requirement() {
+    let repo = TestRepo::new();
+    pinned_dependent(&repo);
+
+    let output = repo.run_belaf_command(&["prepare", "--ci"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(
+        stderr.contains("internal dependency requirements would reject the new versions"),
+        "{stderr}"
+    );
+    assert!(
+        stderr.contains("reqapp requires reqcore `=1.0.0`, which rejects 1.1.0"),
+        "{stderr}"
+    );
+
+    let core_toml = repo.read_file("packages/core/Cargo.toml");
+    assert!(core_toml.contains("version = \"1.0.0\""), "{core_toml}");
+    assert!(repo.list_files_in_dir("belaf/releases").is_empty());
+}
+
+/// Prepare the release of `reqcore` the way the wizard does once
+/// `resolution` is picked for its conflict; returns the requirement it
+/// picks.
+fn prepare_resolved(repo: &TestRepo, resolution: ConflictResolution) -> String {
+    in_process::in_dir(&repo.path, &[("BELAF_NO_FETCH", "1")], || {
+        let mut sess = AppSession::initialize_default()?;
+        let mut ctx = PrepareContext::initialize(&mut sess, false, false)?;
+        ctx.discover_projects()?;
+        let mut selections: Vec<ReleaseUnitSelection> = ctx
+            .candidates
+            .iter()
+            .cloned()
+            .map(|candidate| ReleaseUnitSelection {
+                bump_choice: BumpChoice::default_for(&candidate),
+                candidate,
+                custom_version: None,
+                cached_changelog: None,
+            })
+            .collect();
+        let conflicts = ctx.dep_conflicts(&selections);
+        assert_eq!(conflicts.len(), 1, "{conflicts:?}");
+        let requirement = conflicts[0].requirement_for(resolution).unwrap().to_owned();
+        let resolutions = [(conflicts[0].clone(), resolution)];
+        ctx.resolve_dep_conflicts(&mut selections, &resolutions);
+        // Without a login finalize stops at pushing, after the release commit.
+        let _ = ctx.finalize(selections);
+        Ok(requirement)
+    })
+    .unwrap()
+}
+
+#[test]
+fn test_widened_requirement_is_written_to_the_dependent() {
+    let repo = TestRepo::new();
+    pinned_dependent(&repo);
+
+    let widened = prepare_resolved(&repo, ConflictResolution::WidenRange);
+    assert_eq!(widened, ">=1.0.0, <2.0.0");
+    let core_toml = repo.read_file("packages/core/Cargo.toml");
+    assert!(core_toml.contains("version = \"1.1.0\""), "{core_toml}");
+    let app_toml = repo.read_file("packages/app/Cargo.toml");
+    assert!(
+        app_toml.contains(&format!(
+            r#"reqcore = {{ path = "../core", version = "{widened}" }}"#
+        )),
+        "{app_toml}"
+    );
+}
+
+#[test]
+fn test_pinned_requirement_is_written_to_the_dependent() {
+    let repo = TestRepo::new();
+    pinned_dependent(&repo);
+
+    let pinned = prepare_resolved(&repo, ConflictResolution::PinNewVersion);
+    assert_eq!(pinned, "=1.1.0");
+    let app_toml = repo.read_file("packages/app/Cargo.toml");
+    assert!(
+        app_toml.contains(r#"reqcore = { path = "../core", version = "=1.1.0" }"#),
+        "{app_toml}"
+    );
+}
@@ -465,3 +465,300 @@ setup()
         "Should detect Python package"
     );
 }
+
+#[test]
+fn test_scope_limits_units_to_directory() {
+    let repo = TestRepo::new();
+
+    repo.write_file(
+        "Cargo.toml",
+        r#"[workspace]
+members = ["crates/*"]
+resolver = "2"
+"#,
+    );
+    repo.write_file(
+        "crates/lib-a/Cargo.toml",
+        r#"[package]
+name = "scoped-lib-a"
+version = "0.1.0"
+edition = "2021"
+"#,
+    );
+    repo.write_file("crates/lib-a/src/lib.rs", "pub fn lib_a() {}\n");
+    repo.write_file(
+        "crates/lib-b/Cargo.toml",
+        r#"[package]
+name = "scoped-lib-b"
+version = "0.2.0"
+edition = "2021"
+
+[dependencies]
+scoped-lib-a = { path = "../lib-a" }
+"#,
+    );
+    repo.write_file("crates/lib-b/src/lib.rs", "pub fn lib_b() {}\n");
+    repo.commit("Initial workspace commit");
+
+    let output = repo.run_belaf_command(&["init", "--force"]);
+    assert!(
+        output.status.success(),
+        "Init failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    repo.commit("chore: add belaf config");
+
+    let output = repo.run_belaf_command(&["--scope", "crates/lib-b", "status", "--format", "json"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "Scoped status failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(
+        stdout.contains("scoped-lib-b"),
+        "lib-b is in scope: {stdout}"
+    );
+    assert!(
+        !stdout.contains("scoped-lib-a"),
+        "lib-a is outside the scope: {stdout}"
+    );
+
+    let output = repo.run_belaf_command(&["--scope", "crates/missing", "status"]);
+    assert!(!output.status.success(), "a missing scope must be rejected");
+}
+
+#[test]
+fn test_graph_external_dependencies() {
+    let repo = TestRepo::new();
+
+    repo.write_file(
+        "Cargo.toml",
+        r#"[workspace]
+members = ["crates/*"]
+resolver = "2"
+"#,
+    );
+    repo.write_file(
+        "crates/ext-core/Cargo.toml",
+        r#"[package]
+name = "ext-core"
+version = "0.1.0"
+edition = "2021"
+"#,
+    );
+    repo.write_file("crates/ext-core/src/lib.rs", "pub fn core() {}\n");
+    repo.write_file(
+        "crates/ext-app/Cargo.toml",
+        r#"[package]
+name = "ext-app"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+ext-core = { path = "../ext-core" }
+serde = "1.0"
+json = { package = "serde_json", version = "1.0.100" }
+"#,
+    );
+    repo.write_file("crates/ext-app/src/lib.rs", "pub fn app() {}\n");
+    repo.write_file(
+        "web/package.json",
+        r#"{"name": "ext-web", "version": "1.0.0", "dependencies": {"react": "^18.2.0"}}"#,
+    );
+    repo.commit("Initial commit");
+
+    let output = repo.run_belaf_command(&["graph", "--format", "json", "--external"]);
+    assert!(
+        output.status.success(),
+        "graph failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("graph output is JSON");
+    let external = |name: &str| -> Vec<(String, String)> {
+        json["projects"]
+            .as_array()
+            .expect("projects array")
+            .iter()
+            .find(|p| p["name"] == name)
+            .unwrap_or_else(|| panic!("{name} in graph: {stdout}"))["external_dependencies"]
+            .as_array()
+            .expect("external_dependencies array")
+            .iter()
+            .map(|d| {
+                (
+                    d["name"].as_str().unwrap_or_default().to_owned(),
+                    d["requirement"].as_str().unwrap_or_default().to_owned(),
+                )
+            })
+            .collect()
+    };
+
+    assert_eq!(
+        external("ext-app"),
+        vec![
+            ("serde".to_owned(), "1.0".to_owned()),
+            ("serde_json".to_owned(), "1.0.100".to_owned()),
+        ],
+        "internal ext-core is an edge, not an external dep"
+    );
+    assert!(external("ext-core").is_empty());
+    assert_eq!(
+        external("ext-web"),
+        vec![("react".to_owned(), "^18.2.0".to_owned())]
+    );
+
+    let output = repo.run_belaf_command(&["graph", "--format", "dot", "--external"]);
+    let dot = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        dot.contains(r#""ext-app" -> "ext:serde" [label="1.0", style=dashed];"#),
+        "dot output has a dashed edge to serde: {dot}"
+    );
+}
+
+#[test]
+fn test_affected_includes_transitive_dependents() {
+    let repo = TestRepo::new();
+
+    repo.write_file(
+        "Cargo.toml",
+        r#"[workspace]
+members = ["crates/*"]
+resolver = "2"
+"#,
+    );
+    for (name, deps) in [
+        ("aff-core", ""),
+        ("aff-service", "aff-core = { path = \"../aff-core\" }\n"),
+        ("aff-app", "aff-service = { path = \"../aff-service\" }\n"),
+        ("aff-tool", ""),
+    ] {
+        repo.write_file(
+            &format!("crates/{name}/Cargo.toml"),
+            &format!(
+                "[package]\nname = \"{name}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n[dependencies]\n{deps}"
+            ),
+        );
+        repo.write_file(&format!("crates/{name}/src/lib.rs"), "\n");
+    }
+    repo.commit("Initial commit");
+    std::process::Command::new("git")
+        .args(["branch", "base"])
+        .current_dir(&repo.path)
+        .output()
+        .expect("failed to create base branch");
+
+    repo.write_file("crates/aff-core/src/lib.rs", "pub fn core() {}\n");
+    repo.commit("feat(core): add core fn");
+
+    let output = repo.run_belaf_command(&["affected", "--base", "base", "--format", "json"]);
+    assert!(
+        output.status.success(),
+        "affected failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("affected output is JSON");
+    let units: Vec<(&str, &str)> = json["units"]
+        .as_array()
+        .expect("units array")
+        .iter()
+        .map(|u| {
+            (
+                u["name"].as_str().unwrap_or_default(),
+                u["reason"].as_str().unwrap_or_default(),
+            )
+        })
+        .collect();
+
+    assert_eq!(
+        units,
+        vec![
+            ("aff-core", "changed"),
+            ("aff-service", "dependent"),
+            ("aff-app", "dependent"),
+        ],
+        "aff-tool is untouched: {stdout}"
+    );
+    assert_eq!(json["changed_files"], 1);
+
+    let output = repo.run_belaf_command(&["affected", "--base", "no-such-ref"]);
+    assert!(!output.status.success(), "an unknown base must be rejected");
+}
+
+#[test]
+fn test_graph_json_nodes_and_edges() {
+    let repo = TestRepo::new();
+
+    repo.write_file(
+        "Cargo.toml",
+        r#"[workspace]
+members = ["crates/*"]
+resolver = "2"
+"#,
+    );
+    repo.write_file(
+        "crates/core/Cargo.toml",
+        r#"[package]
+name = "core"
+version = "0.3.0"
+edition = "2021"
+"#,
+    );
+    repo.write_file("crates/core/src/lib.rs", "pub fn core() {}\n");
+    repo.write_file(
+        "crates/app/Cargo.toml",
+        r#"[package]
+name = "app"
+version = "1.2.0"
+edition = "2021"
+
+[dependencies]
+core = { path = "../core", version = "0.3" }
+log = "0.4"
+"#,
+    );
+    repo.write_file("crates/app/src/lib.rs", "pub fn app() {}\n");
+    repo.commit("Initial commit");
+
+    let output = repo.run_belaf_command(&["graph", "--format", "json", "--external"]);
+    assert!(
+        output.status.success(),
+        "graph failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("graph output is JSON");
+
+    assert_eq!(json["schema_version"], "1");
+    let app = json["nodes"]
+        .as_array()
+        .expect("nodes array")
+        .iter()
+        .find(|n| n["name"] == "app")
+        .unwrap_or_else(|| panic!("app in nodes: {stdout}"));
+    assert_eq!(app["version"], "1.2.0");
+    assert_eq!(app["ecosystem"], "cargo");
+    assert_eq!(app["prefix"], "crates/app/");
+    assert_eq!(app["qualified_names"], serde_json::json!(["app", "cargo"]));
+
+    let edges = json["edges"].as_array().expect("edges array");
+    assert_eq!(
+        edges,
+        &vec![
+            serde_json::json!({
+                "from": "app",
+                "to": "core",
+                "kind": "internal",
+                "requirement": "^0.3",
+            }),
+            serde_json::json!({
+                "from": "app",
+                "to": "log",
+                "kind": "external",
+                "requirement": "0.4",
+            }),
+        ]
+    );
+}
@@ -3,6 +3,7 @@
 //! commands an AI agent will hit first on a fresh repo, so the
 //! contract has to be tight.
 
+use belaf::core::errors::ErrorCode;
 use belaf::core::exit_code::ExitCode;
 
 #[test]
@@ -29,6 +30,37 @@ fn exit_code_labels_documented() {
     }
 }
 
+#[test]
+fn error_codes_are_distinct_and_map_to_failure_exit_codes() {
+    let mut labels: Vec<&str> = ErrorCode::all().iter().map(|c| c.label()).collect();
+    let n = labels.len();
+    labels.sort_unstable();
+    labels.dedup();
+    assert_eq!(labels.len(), n, "error code labels must be unique");
+    for code in ErrorCode::all() {
+        assert_ne!(code.exit_code(), ExitCode::Ok, "{code:?} must fail");
+        assert!(!code.description().is_empty());
+    }
+}
+
+#[test]
+fn json_errors_go_to_stderr_with_code_and_exit_code() {
+    let dir = tempfile::tempdir().unwrap();
+    let out = std::process::Command::new(env!("CARGO_BIN_EXE_belaf"))
+        .args(["graph", "--format", "json"])
+        .current_dir(dir.path())
+        .env("GIT_CEILING_DIRECTORIES", dir.path().parent().unwrap())
+        .output()
+        .expect("run belaf graph");
+    assert!(out.stdout.is_empty(), "nothing on stdout");
+    assert_eq!(out.status.code(), Some(i32::from(ExitCode::Precondition)));
+    let stderr = String::from_utf8(out.stderr).expect("utf8");
+    let line = stderr.lines().last().expect("an error line");
+    let parsed: serde_json::Value = serde_json::from_str(line).expect("stderr is JSON");
+    assert_eq!(parsed["error"]["code"], "not_a_repository");
+    assert_eq!(parsed["error"]["exit_code"], 4);
+}
+
 #[test]
 fn schema_manifest_is_embedded_and_valid_json() {
     let cmd = std::process::Command::new(env!("CARGO_BIN_EXE_belaf"))
@@ -79,6 +111,7 @@ fn describe_json_has_required_keys() {
         "commands",
         "env_vars",
         "exit_codes",
+        "error_codes",
         "schemas",
         "example_workflows",
     ] {
@@ -123,3 +156,18 @@ fn describe_text_renders_human_readable() {
     assert!(stdout.contains("COMMANDS"));
     assert!(stdout.contains("EXIT CODES"));
 }
+
+#[test]
+fn schema_graph_is_embedded_and_valid_json() {
+    let cmd = std::process::Command::new(env!("CARGO_BIN_EXE_belaf"))
+        .args(["schema", "graph"])
+        .env("BELAF_NO_KEYRING", "1")
+        .env("BELAF_API_URL", "http://127.0.0.1:0")
+        .output()
+        .expect("run belaf schema graph");
+    assert!(cmd.status.success(), "exit code: {:?}", cmd.status);
+    let stdout = String::from_utf8(cmd.stdout).expect("utf8");
+    let parsed: serde_json::Value = serde_json::from_str(&stdout).expect("schema must be JSON");
+    assert_eq!(parsed["properties"]["schema_version"]["const"], "1");
+    assert!(parsed["$defs"]["edge"]["properties"]["kind"].is_object());
+}
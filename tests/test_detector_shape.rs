@@ -31,10 +31,10 @@ impl Seedable for TestRepo {
         &self.path
     }
     fn write_file(&self, relative: &str, content: &str) {
-        TestRepo::write_file(self, relative, content);
+        belaf::test_support::TestRepo::write_file(self, relative, content);
     }
     fn commit(&self, message: &str) {
-        TestRepo::commit(self, message);
+        belaf::test_support::TestRepo::commit(self, message);
     }
 }
 
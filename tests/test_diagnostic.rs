@@ -7,8 +7,13 @@
 //! review`.
 
 use belaf::core::api::ApiError;
-use belaf::core::errors::{display_diagnostic_to_string, AnnotatedReport};
-use belaf::core::git::repository::{BareRepositoryError, DirtyRepositoryError, RepoPathBuf};
+use belaf::core::errors::{
+    classify, display_diagnostic_to_string, render_json, AnnotatedReport, ConfigError, ErrorCode,
+};
+use belaf::core::git::repository::{
+    BareRepositoryError, DirtyRepositoryError, NoUpstreamError, RepoPathBuf,
+    UnexpectedUpstreamError,
+};
 
 #[test]
 fn dirty_repository_renders_with_hint() {
@@ -53,3 +58,46 @@ fn simple_error_no_hints_no_notes() {
     let err = anyhow::anyhow!("something broke");
     insta::assert_snapshot!(display_diagnostic_to_string(&err));
 }
+
+#[test]
+fn typed_errors_classify_through_context() {
+    let dirty = anyhow::Error::new(DirtyRepositoryError(RepoPathBuf::new(b"a.txt")))
+        .context("failed to prepare");
+    assert_eq!(classify(&dirty), ErrorCode::DirtyRepository);
+
+    let upstream =
+        anyhow::Error::new(NoUpstreamError).context("failed to finalize repository setup");
+    assert_eq!(classify(&upstream), ErrorCode::NoUpstream);
+
+    let unexpected = anyhow::Error::new(UnexpectedUpstreamError {
+        url: "https://github.com/someone/fork.git".into(),
+        operation: "push the release branch".into(),
+    });
+    assert_eq!(classify(&unexpected), ErrorCode::UnexpectedUpstream);
+
+    let auth = anyhow::Error::new(ApiError::Unauthorized).context("not authenticated");
+    assert_eq!(classify(&auth), ErrorCode::AuthRequired);
+
+    let config = anyhow::anyhow!("unknown field `foo`")
+        .context(ConfigError("failed to load repository config file".into()));
+    assert_eq!(classify(&config), ErrorCode::ConfigInvalid);
+
+    assert_eq!(
+        classify(&anyhow::anyhow!("something broke")),
+        ErrorCode::Internal
+    );
+}
+
+#[test]
+fn json_diagnostic_carries_code_causes_and_hints() {
+    let err = anyhow::Error::new(ApiError::RateLimited {
+        retry_after_secs: 12,
+    })
+    .context("failed to create pull request");
+    let parsed: serde_json::Value = serde_json::from_str(&render_json(&err)).unwrap();
+    assert_eq!(parsed["error"]["code"], "rate_limited");
+    assert_eq!(parsed["error"]["exit_code"], 6);
+    assert_eq!(parsed["error"]["message"], "failed to create pull request");
+    assert_eq!(parsed["error"]["causes"].as_array().unwrap().len(), 1);
+    assert_eq!(parsed["error"]["hints"][0], "re-run after 12 seconds");
+}
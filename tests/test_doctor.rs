@@ -45,6 +45,7 @@ fn doctor_json_has_required_keys() {
         "config",
         "repository",
         "ecosystems",
+        "metadata",
         "api",
         "environment",
     ] {
@@ -73,7 +74,14 @@ fn doctor_check_status_values_are_valid() {
     let stdout = String::from_utf8(out.stdout).expect("utf8");
     let parsed: serde_json::Value = serde_json::from_str(&stdout).unwrap();
 
-    for check_key in ["auth", "config", "repository", "ecosystems", "api"] {
+    for check_key in [
+        "auth",
+        "config",
+        "repository",
+        "ecosystems",
+        "metadata",
+        "api",
+    ] {
         let s = parsed[check_key]["status"]
             .as_str()
             .unwrap_or_else(|| panic!("{check_key}.status must be a string"));
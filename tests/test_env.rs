@@ -0,0 +1,36 @@
+//! `belaf env` output is pasted into public bug reports, so the tests
+//! pin what `--redact` hides and that recorded errors show up.
+
+mod common;
+use common::TestRepo;
+
+#[test]
+fn env_redacts_paths_and_lists_recent_errors() {
+    let repo = TestRepo::new();
+    repo.write_file(
+        "Cargo.toml",
+        "[package]\nname = \"test\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+    );
+    repo.write_file("src/lib.rs", "pub fn hello() {}\n");
+    repo.commit("Initial commit");
+
+    // Keep the recorded errors out of the real user cache.
+    let cache = tempfile::tempdir().unwrap();
+    let cache_dir = cache.path().to_str().unwrap();
+    let env = [("XDG_CACHE_HOME", cache_dir), ("HOME", cache_dir)];
+
+    let failed = repo.run_belaf_command_with_env(&["approve", "no-such-release"], &env);
+    assert!(!failed.status.success());
+
+    let output = repo.run_belaf_command_with_env(&["env", "--redact"], &env);
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(stdout.starts_with("```text\n"), "fenced block: {stdout}");
+    assert!(stdout.contains("libgit2"));
+    assert!(stdout.contains("ecosystems  cargo=1"), "{stdout}");
+    assert!(stdout.contains("belaf approve no-such-release"), "{stdout}");
+    assert!(stdout.contains("repository  <repo>"), "{stdout}");
+    let workdir = repo.path.to_str().unwrap();
+    assert!(!stdout.contains(workdir), "workdir not redacted: {stdout}");
+}
@@ -0,0 +1,63 @@
+//! `belaf export site`: release notes pages from the units' changelogs.
+
+mod common;
+
+use common::TestRepo;
+
+#[test]
+fn test_export_site_writes_index_project_and_version_pages() {
+    let repo = TestRepo::new();
+    repo.write_file(
+        "Cargo.toml",
+        "[package]\nname = \"my-crate\"\nversion = \"1.0.0\"\nedition = \"2021\"\n",
+    );
+    repo.write_file("src/lib.rs", "pub fn hello() {}\n");
+    repo.commit("Initial commit");
+    let output = repo.run_belaf_command(&["init", "--force"]);
+    assert!(output.status.success());
+    repo.commit("chore: add belaf config");
+    repo.write_file(
+        "CHANGELOG.md",
+        "# Changelog\n\n## [Unreleased]\n\n- wip\n\n\
+         ## [1.1.0] - 2026-10-16\n\n### Features\n\n- add shiny feature\n\n\
+         ## [1.0.0] - 2026-01-02\n\n- first release\n",
+    );
+    repo.commit("docs: changelog");
+
+    let output = repo.run_belaf_command(&["export", "site", "--out", "site"]);
+    assert!(
+        output.status.success(),
+        "export failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let index = repo.read_file("site/index.md");
+    assert!(
+        index.contains("- [my-crate](my-crate/index.md) — 1.1.0 (2026-10-16)"),
+        "{index}"
+    );
+    let listing = repo.read_file("site/my-crate/index.md");
+    assert!(
+        listing.contains("- [1.1.0](1.1.0.md) (2026-10-16)\n- [1.0.0](1.0.0.md) (2026-01-02)"),
+        "{listing}"
+    );
+    let page = repo.read_file("site/my-crate/1.1.0.md");
+    assert!(
+        page.starts_with("---\ntitle: \"my-crate 1.1.0\"\n"),
+        "{page}"
+    );
+    assert!(
+        page.contains("date: 2026-10-16\n---\n\n## [1.1.0]"),
+        "{page}"
+    );
+    assert!(page.contains("- add shiny feature"), "{page}");
+    assert!(!repo.file_exists("site/my-crate/Unreleased.md"));
+
+    let output = repo.run_belaf_command(&["export", "site", "--out", "html", "--format", "html"]);
+    assert!(output.status.success());
+    let page = repo.read_file("html/my-crate/1.0.0.html");
+    assert!(page.contains("<title>my-crate 1.0.0</title>"), "{page}");
+    assert!(page.contains("<li>first release</li>"), "{page}");
+    let index = repo.read_file("html/index.html");
+    assert!(index.contains("href=\"my-crate/index.html\""), "{index}");
+}
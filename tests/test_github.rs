@@ -178,3 +178,155 @@ edition = "2021"
         String::from_utf8_lossy(&output.stderr)
     );
 }
+
+#[test]
+fn test_owners_from_codeowners() {
+    let repo = TestRepo::new();
+
+    repo.write_file(
+        "Cargo.toml",
+        r#"[workspace]
+members = ["crates/*"]
+resolver = "2"
+"#,
+    );
+    for name in ["own-core", "own-cli"] {
+        repo.write_file(
+            &format!("crates/{name}/Cargo.toml"),
+            &format!("[package]\nname = \"{name}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n"),
+        );
+        repo.write_file(&format!("crates/{name}/src/lib.rs"), "\n");
+    }
+    repo.write_file(
+        ".github/CODEOWNERS",
+        "* @acme/maintainers\n/crates/own-core/ @alice @acme/core\n",
+    );
+    repo.commit("Initial commit");
+
+    let output = repo.run_belaf_command(&["owners", "own-core", "--format", "json"]);
+    assert!(
+        output.status.success(),
+        "owners failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("owners output is JSON");
+    assert_eq!(json["codeowners"], ".github/CODEOWNERS");
+    assert_eq!(json["units"].as_array().map(Vec::len), Some(1));
+    assert_eq!(json["units"][0]["name"], "own-core");
+    assert_eq!(
+        json["units"][0]["owners"],
+        serde_json::json!(["@alice", "@acme/core"])
+    );
+    assert_eq!(json["units"][0]["rule"], "/crates/own-core/");
+
+    let output = repo.run_belaf_command(&["status", "--format", "json"]);
+    assert!(
+        output.status.success(),
+        "status failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    // Untagged units log a "no release tag" warning ahead of the JSON.
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let body = &stdout[stdout.find("\n{").map_or(0, |i| i + 1)..];
+    let json: serde_json::Value = serde_json::from_str(body).expect("status output is JSON");
+    let cli = json["projects"]
+        .as_array()
+        .and_then(|ps| ps.iter().find(|p| p["name"] == "own-cli"))
+        .unwrap_or_else(|| panic!("own-cli missing from status: {stdout}"));
+    assert_eq!(cli["owners"], serde_json::json!(["@acme/maintainers"]));
+
+    let output = repo.run_belaf_command(&["owners", "no-such-unit"]);
+    assert!(!output.status.success(), "an unknown unit must be rejected");
+}
+
+#[test]
+fn test_comment_previews_the_units_a_pull_request_changes() {
+    let repo = TestRepo::new();
+    repo.write_file(
+        "Cargo.toml",
+        "[workspace]\nmembers = [\"crates/*\"]\nresolver = \"2\"\n",
+    );
+    for name in ["pr-core", "pr-tool"] {
+        repo.write_file(
+            &format!("crates/{name}/Cargo.toml"),
+            &format!("[package]\nname = \"{name}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n"),
+        );
+        repo.write_file(&format!("crates/{name}/src/lib.rs"), "\n");
+    }
+    repo.commit("Initial commit");
+    let _ = repo.run_belaf_command(&["init", "--force", "--ci"]);
+    repo.commit("chore: add belaf config");
+    repo.write_file("crates/pr-tool/src/lib.rs", "pub fn tool() {}\n");
+    repo.commit("fix(tool): handle empty input");
+    std::process::Command::new("git")
+        .args(["branch", "base"])
+        .current_dir(&repo.path)
+        .output()
+        .expect("failed to create base branch");
+
+    repo.write_file("crates/pr-core/src/lib.rs", "pub fn core() {}\n");
+    repo.commit("feat(core): add retries");
+
+    let output = repo.run_belaf_command(&["comment", "--pr", "7", "--base", "base", "--dry-run"]);
+    let body = String::from_utf8_lossy(&output.stdout).into_owned();
+    assert!(
+        output.status.success(),
+        "{body}{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(body.starts_with("<!-- belaf:pr-preview -->"), "{body}");
+    assert!(
+        body.contains("| **pr-core** | Rust (Cargo) | `0.1.0` → `0.2.0` | 🟡 MINOR |"),
+        "{body}"
+    );
+    assert!(body.contains("### 📝 Changelog\n\n## [0.2.0]"), "{body}");
+    assert!(body.contains("Add retries"), "{body}");
+    assert!(
+        !body.contains("pr-tool") && !body.contains("Handle empty input"),
+        "commits before the merge base are left out:\n{body}"
+    );
+}
+
+#[test]
+fn test_label_names_the_units_and_the_largest_bump() {
+    let repo = TestRepo::new();
+    repo.write_file(
+        "Cargo.toml",
+        "[workspace]\nmembers = [\"crates/*\"]\nresolver = \"2\"\n",
+    );
+    for name in ["lbl-api", "lbl-cli", "lbl-docs"] {
+        repo.write_file(
+            &format!("crates/{name}/Cargo.toml"),
+            &format!("[package]\nname = \"{name}\"\nversion = \"1.0.0\"\nedition = \"2021\"\n"),
+        );
+        repo.write_file(&format!("crates/{name}/src/lib.rs"), "\n");
+    }
+    repo.commit("Initial commit");
+    let _ = repo.run_belaf_command(&["init", "--force", "--ci"]);
+    repo.commit("chore: add belaf config");
+    std::process::Command::new("git")
+        .args(["branch", "base"])
+        .current_dir(&repo.path)
+        .output()
+        .expect("failed to create base branch");
+
+    repo.write_file("crates/lbl-api/src/lib.rs", "pub fn v2() {}\n");
+    repo.commit("feat(api)!: drop the v1 endpoints");
+    repo.write_file("crates/lbl-cli/src/lib.rs", "pub fn run() {}\n");
+    repo.commit("fix(cli): exit non-zero on errors");
+
+    let output = repo.run_belaf_command(&["label", "--pr", "7", "--base", "base", "--dry-run"]);
+    let labels = String::from_utf8_lossy(&output.stdout).into_owned();
+    assert!(
+        output.status.success(),
+        "{labels}{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let mut labels: Vec<&str> = labels.lines().collect();
+    labels.sort_unstable();
+    assert_eq!(
+        labels,
+        ["area:lbl-api", "area:lbl-cli", "breaking", "semver:major"]
+    );
+}
@@ -0,0 +1,45 @@
+//! Per-release commit ranges: what `prepare` records in the manifest and
+//! what `belaf history` shows of it.
+
+mod common;
+
+use common::TestRepo;
+
+#[test]
+fn test_prepare_records_commit_range_shown_by_history() {
+    let repo = TestRepo::new();
+    repo.init_crate("");
+    repo.git(&["tag", "my-crate-v1.0.0"]);
+    repo.write_file("src/fix.rs", "pub fn fix_bug() {}\n");
+    repo.commit("fix: resolve critical bug");
+    let fix = repo.git(&["rev-parse", "HEAD"]);
+    repo.prepare();
+
+    let output = repo.run_belaf_command(&["history", "--format", "json"]);
+    assert!(output.status.success());
+    let history: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("history should print JSON");
+    let release = &history[0];
+    assert_eq!(release["name"], "my-crate");
+    assert_eq!(release["new_version"], "1.0.1");
+    assert_eq!(
+        release["commit_range"],
+        format!("my-crate-v1.0.0..{fix}").as_str()
+    );
+    assert_eq!(release["commits"][0]["sha"], fix.as_str());
+    assert_eq!(
+        release["commits"][0]["summary"],
+        "fix: resolve critical bug"
+    );
+
+    let output = repo.run_belaf_command(&["history", "my-crate", "--show-commits"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success());
+    for expected in ["my-crate-v1.0.0..", &fix[..8], "fix: resolve critical bug"] {
+        assert!(stdout.contains(expected), "{stdout}");
+    }
+
+    let output = repo.run_belaf_command(&["history", "other"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("No releases of other"), "{stdout}");
+}
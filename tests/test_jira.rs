@@ -0,0 +1,53 @@
+//! `[trackers.jira]`: the fix versions and issues `belaf tag` records.
+
+mod common;
+
+use common::TestRepo;
+
+#[test]
+fn test_tag_dry_run_lists_the_jira_issues_of_each_release() {
+    let repo = TestRepo::new();
+    repo.init_crate("[trackers.jira]\nurl = \"https://acme.atlassian.net\"\nproject = \"PROJ\"\n");
+    repo.write_file("src/fix.rs", "pub fn fix_bug() {}\n");
+    repo.commit("fix: resolve critical bug\n\nCloses PROJ-42, see also OPS-7 and PROJ-5.");
+    repo.write_file("src/more.rs", "pub fn more() {}\n");
+    repo.commit("feat: more (PROJ-42)");
+
+    let manifest_id = repo.prepare();
+
+    let output = repo.run_belaf_command(&["tag", &manifest_id, "--dry-run"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+    assert!(
+        stdout.contains("Jira: fix version `my-crate 1.1.0` for PROJ-5, PROJ-42"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn test_jira_project_must_be_a_key() {
+    let repo = TestRepo::new();
+    repo.write_file(
+        "Cargo.toml",
+        "[package]\nname = \"my-crate\"\nversion = \"1.0.0\"\nedition = \"2021\"\n",
+    );
+    repo.commit("Initial commit");
+    let output = repo.run_belaf_command(&["init", "--force"]);
+    assert!(output.status.success());
+    let config = repo.read_file("belaf/config.toml");
+    repo.write_file(
+        "belaf/config.toml",
+        &format!(
+            "{config}\n[trackers.jira]\nurl = \"https://acme.atlassian.net\"\nproject = \"my project\"\n"
+        ),
+    );
+    repo.commit("chore: add belaf config");
+
+    let output = repo.run_belaf_command(&["status"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(
+        stderr.contains("invalid [trackers.jira] project `my project`"),
+        "{stderr}"
+    );
+}
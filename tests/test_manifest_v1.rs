@@ -165,3 +165,51 @@ fn manifest_does_not_emit_v1_only_fields() {
         "v1 `prefix` field is removed in v2 (CLI owns full tag_name now)"
     );
 }
+
+#[test]
+fn migrate_upgrades_unversioned_manifests() {
+    let repo = TestRepo::new();
+    let path = "belaf/releases/0192f3a1-7c4e-7b2a-9f00-1234567890ab.json";
+    repo.write_file(
+        path,
+        r#"{
+  "manifest_id": "0192f3a1-7c4e-7b2a-9f00-1234567890ab",
+  "created_at": "2025-06-01T10:00:00Z",
+  "created_by": "alice",
+  "base_branch": "main",
+  "releases": [{
+    "name": "schema-test",
+    "ecosystem": "cargo",
+    "previous_version": "1.0.0",
+    "new_version": "1.1.0",
+    "bump_type": "minor",
+    "prefix": "",
+    "prerelease": false
+  }]
+}"#,
+    );
+    repo.commit("add a manifest from before schema versions");
+
+    let check = repo.run_belaf_command(&["migrate", "--check"]);
+    assert_eq!(check.status.code(), Some(4), "--check must flag the file");
+    assert!(
+        !repo.read_file(path).contains("schema_version"),
+        "--check must not write"
+    );
+
+    let migrate = repo.run_belaf_command(&["migrate"]);
+    assert!(
+        migrate.status.success(),
+        "migrate failed: {}",
+        String::from_utf8_lossy(&migrate.stderr)
+    );
+    let m: serde_json::Value = serde_json::from_str(&repo.read_file(path)).unwrap();
+    assert_eq!(m["schema_version"], "1");
+    let r = &m["releases"][0];
+    assert_eq!(r["tag_name"], "v1.1.0");
+    assert_eq!(r["previous_tag"], "v1.0.0");
+    assert!(r.get("prefix").is_none() && r.get("prerelease").is_none());
+
+    let again = repo.run_belaf_command(&["migrate", "--check"]);
+    assert!(again.status.success(), "migrated files are current");
+}
@@ -440,8 +440,12 @@ fn release_unit_tag_format_lookup_by_name() {
             version_field: None,
             satellites: Vec::new(),
             tag_format: Some("custom-{name}-v{version}".into()),
+            previous_names: Vec::new(),
             visibility: None,
             cascade_from: None,
+            dep_requirement: None,
+            scopes: Vec::new(),
+            release_checklist: Vec::new(),
         },
     };
 
@@ -451,6 +455,9 @@ fn release_unit_tag_format_lookup_by_name() {
         belaf::core::config::syntax::AnalysisConfig {
             commit_cache_size: 1024,
             tree_cache_size: 1024,
+            follow_renames: false,
+            max_commits: None,
+            since_date: None,
         },
     )
     .unwrap();
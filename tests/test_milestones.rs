@@ -0,0 +1,48 @@
+//! `[release.milestones]`: the GitHub milestones `belaf tag` rolls over.
+
+mod common;
+
+use common::TestRepo;
+
+#[test]
+fn test_tag_dry_run_lists_the_milestones_of_each_release() {
+    let repo = TestRepo::new();
+    repo.init_crate("[release.milestones]\nenabled = true\ntitle_format = \"{name} v{version}\"\n");
+    repo.write_file("src/more.rs", "pub fn more() {}\n");
+    repo.commit("feat: more");
+
+    let manifest_id = repo.prepare();
+
+    let output = repo.run_belaf_command(&["tag", &manifest_id, "--dry-run"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+    assert!(
+        stdout.contains(
+            "Milestone `my-crate v1.1.0` would be closed, its open issues moved to `my-crate v1.2.0`"
+        ),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn test_milestone_title_must_name_the_version() {
+    let repo = TestRepo::new();
+    repo.write_file(
+        "Cargo.toml",
+        "[package]\nname = \"my-crate\"\nversion = \"1.0.0\"\nedition = \"2021\"\n",
+    );
+    repo.commit("Initial commit");
+    let output = repo.run_belaf_command(&["init", "--force"]);
+    assert!(output.status.success());
+    let config = repo.read_file("belaf/config.toml");
+    repo.write_file(
+        "belaf/config.toml",
+        &format!("{config}\n[release.milestones]\nenabled = true\ntitle_format = \"next\"\n"),
+    );
+    repo.commit("chore: add belaf config");
+
+    let output = repo.run_belaf_command(&["status"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(stderr.contains("invalid [release.milestones]"), "{stderr}");
+}
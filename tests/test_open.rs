@@ -0,0 +1,72 @@
+//! `belaf open --print`: the URLs and paths `open` would hand to the
+//! browser.
+
+mod common;
+
+use common::TestRepo;
+
+fn released_crate() -> TestRepo {
+    let repo = TestRepo::new();
+    repo.write_file(
+        "Cargo.toml",
+        "[package]\nname = \"my-crate\"\nversion = \"1.0.0\"\nedition = \"2021\"\n",
+    );
+    repo.write_file("src/lib.rs", "pub fn hello() {}\n");
+    repo.commit("Initial commit");
+    let output = repo.run_belaf_command(&["init", "--force"]);
+    assert!(output.status.success());
+    repo.commit("chore: add belaf config");
+    repo.git(&["tag", "my-crate-v1.0.0"]);
+    repo
+}
+
+fn print(repo: &TestRepo, args: &[&str]) -> Result<String, String> {
+    let mut args = args.to_vec();
+    args.insert(0, "open");
+    args.push("--print");
+    let output = repo.run_belaf_command(&args);
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).to_string())
+    }
+}
+
+#[test]
+fn open_prints_forge_links() {
+    let repo = released_crate();
+
+    assert_eq!(
+        print(&repo, &["repo"]).unwrap(),
+        "https://github.com/test/repo"
+    );
+    assert_eq!(
+        print(&repo, &["release"]).unwrap(),
+        "https://github.com/test/repo/releases/tag/my-crate-v1.0.0"
+    );
+    assert_eq!(
+        print(&repo, &["release", "my-crate"]).unwrap(),
+        "https://github.com/test/repo/releases/tag/my-crate-v1.0.0"
+    );
+
+    let err = print(&repo, &["release", "nope"]).unwrap_err();
+    assert!(
+        err.contains("no release unit `nope`; known: my-crate"),
+        "{err}"
+    );
+}
+
+#[test]
+fn open_changelog_prints_the_units_changelog_file() {
+    let repo = released_crate();
+
+    let err = print(&repo, &["changelog"]).unwrap_err();
+    assert!(err.contains("my-crate has no changelog yet"), "{err}");
+
+    repo.write_file("CHANGELOG.md", "# Changelog\n");
+    let path = print(&repo, &["changelog", "my-crate"]).unwrap();
+    assert!(
+        std::path::Path::new(&path).ends_with("CHANGELOG.md"),
+        "{path}"
+    );
+}
@@ -34,8 +34,12 @@ fn pypa_unit(name: &str, manifest_path: &str, version_field: &str) -> NamedRelea
             version_field: None,
             satellites: vec![],
             tag_format: None,
+            previous_names: vec![],
             visibility: None,
             cascade_from: None,
+            dep_requirement: None,
+            scopes: vec![],
+            release_checklist: vec![],
         },
     }
 }
@@ -98,8 +102,12 @@ fn pep_621_rejects_non_pypa_ecosystem() {
             version_field: None,
             satellites: vec![],
             tag_format: None,
+            previous_names: vec![],
             visibility: None,
             cascade_from: None,
+            dep_requirement: None,
+            scopes: vec![],
+            release_checklist: vec![],
         },
     };
     let err = resolve(&r, &[bad]).unwrap_err();
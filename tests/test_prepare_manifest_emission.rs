@@ -8,7 +8,7 @@
 //!   - fix anywhere                 → PATCH
 //!   - chore (non-conventional)     → no manifest
 //!
-//! Each test runs the actual `belaf` binary; `prepare --ci` will
+//! Each test runs `belaf` in-process; `prepare --ci` will
 //! eventually fail at the push/PR step (no network, no auth), but
 //! by then the manifest has already been emitted to disk. We assert
 //! on the manifest, not on the exit code.
@@ -183,3 +183,30 @@ fn chore_commit_emits_no_manifest() {
         "chore commit must NOT produce a manifest; got: {manifests:?}"
     );
 }
+
+#[test]
+fn deterministic_mode_pins_ids_and_dates() {
+    let repo = TestRepo::new();
+    seed_single_crate(&repo, "lib-d", "1.0.0");
+
+    repo.write_file("src/fix.rs", "pub fn fix_bug() {}\n");
+    repo.commit("fix: address a regression in hello()");
+
+    let _ = repo.run_belaf_command_with_env(
+        &["prepare", "--ci"],
+        &[("BELAF_NO_KEYRING", "1"), ("BELAF_DETERMINISTIC", "1")],
+    );
+
+    let manifests = manifest_files(&repo);
+    assert_eq!(manifests.len(), 1, "one manifest expected");
+    let manifest = read_manifest(&manifests[0]);
+    assert_eq!(manifest["created_at"], "2024-01-01T00:00:00Z");
+    let id = manifest["manifest_id"].as_str().unwrap();
+    assert!(id.starts_with("018cc251-f400-7"), "seeded id: {id}");
+
+    let changelog = repo.read_file("CHANGELOG.md");
+    assert!(
+        changelog.contains("## [1.0.1] - 2024-01-01"),
+        "changelog dated by the fixed clock:\n{changelog}"
+    );
+}
@@ -362,3 +362,111 @@ let package = Package(
         "Swift package not detected"
     );
 }
+
+#[test]
+fn test_discovery_cache_follows_manifest_edits() {
+    let repo = TestRepo::new();
+
+    repo.write_file("tools/cli/go.mod", "module example.com/cli\n\ngo 1.21\n");
+    repo.write_file(
+        "crates/core/Cargo.toml",
+        r#"[package]
+name = "cached-core"
+version = "0.1.0"
+edition = "2021"
+"#,
+    );
+    repo.write_file("crates/core/src/lib.rs", "pub fn core() {}\n");
+    repo.commit("initial commit");
+
+    let output = repo.run_belaf_command(&["init", "--force"]);
+    assert!(
+        output.status.success(),
+        "Failed to init: {:?}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    repo.commit("chore: add belaf config");
+
+    let status_output = repo.run_belaf_command(&["status"]);
+    let status = String::from_utf8_lossy(&status_output.stdout).to_string();
+    assert!(status.contains("cached-core"), "cargo crate not detected");
+    assert!(repo.file_exists("belaf/.cache/projects.bin"));
+
+    let porcelain = std::process::Command::new("git")
+        .args(["status", "--porcelain"])
+        .current_dir(&repo.path)
+        .output()
+        .expect("failed to run git status");
+    assert!(
+        porcelain.stdout.is_empty(),
+        "the cache must not dirty the tree: {}",
+        String::from_utf8_lossy(&porcelain.stdout)
+    );
+
+    repo.write_file(
+        "crates/core/Cargo.toml",
+        r#"[package]
+name = "renamed-core"
+version = "0.1.0"
+edition = "2021"
+"#,
+    );
+    repo.commit("rename crate");
+
+    let status_output = repo.run_belaf_command(&["status"]);
+    let status = String::from_utf8_lossy(&status_output.stdout).to_string();
+    assert!(
+        status.contains("renamed-core"),
+        "edit not picked up: {status}"
+    );
+    assert!(
+        !status.contains("cached-core"),
+        "stale cache entry: {status}"
+    );
+    assert!(
+        status.contains("example.com/cli"),
+        "go module lost: {status}"
+    );
+}
+
+#[test]
+fn test_debug_timings_reports_stages() {
+    let repo = TestRepo::new();
+
+    repo.write_file(
+        "Cargo.toml",
+        r#"[package]
+name = "timed-crate"
+version = "0.1.0"
+edition = "2021"
+"#,
+    );
+    repo.write_file("src/lib.rs", "pub fn hello() {}\n");
+    repo.commit("feat: initial commit");
+    std::process::Command::new("git")
+        .args(["tag", "v0.1.0"])
+        .current_dir(&repo.path)
+        .output()
+        .expect("failed to tag");
+    repo.write_file("src/lib.rs", "pub fn hello() {}\npub fn bye() {}\n");
+    repo.commit("feat: add bye");
+
+    let output = repo.run_belaf_command(&["debug", "timings", "--format", "json"]);
+    assert!(
+        output.status.success(),
+        "debug timings failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let parsed: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("debug timings must emit JSON");
+    assert_eq!(parsed["units"], 1);
+    let stages: Vec<&str> = parsed["stages"]
+        .as_array()
+        .expect("stages is an array")
+        .iter()
+        .filter_map(|s| s["stage"].as_str())
+        .collect();
+    for stage in ["discovery", "history analysis", "changelog rendering"] {
+        assert!(stages.contains(&stage), "missing `{stage}` in {stages:?}");
+    }
+}
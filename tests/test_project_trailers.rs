@@ -0,0 +1,102 @@
+//! `[commit_attribution] strategy = "trailer_first"`: a `Belaf-Projects`
+//! trailer decides which units a commit belongs to, and `belaf
+//! lint-commits` flags trailer names that match no unit.
+
+mod common;
+
+use common::TestRepo;
+
+fn workspace_repo(strategy: &str) -> TestRepo {
+    let repo = TestRepo::new();
+    repo.write_file(
+        "Cargo.toml",
+        "[workspace]\nmembers = [\"crates/*\"]\nresolver = \"2\"\n",
+    );
+    for name in ["api", "web"] {
+        repo.write_file(
+            &format!("crates/{name}/Cargo.toml"),
+            &format!("[package]\nname = \"{name}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n"),
+        );
+        repo.write_file(&format!("crates/{name}/src/lib.rs"), "pub fn hello() {}\n");
+    }
+    repo.commit("Initial commit");
+
+    let output = repo.run_belaf_command(&["init", "--force"]);
+    assert!(
+        output.status.success(),
+        "init failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let config = repo.read_file("belaf/config.toml").replace(
+        "strategy = \"scope_first\"",
+        &format!("strategy = \"{strategy}\""),
+    );
+    repo.write_file("belaf/config.toml", &config);
+    repo.commit("chore: configure attribution");
+
+    repo.write_file("crates/api/src/retry.rs", "pub fn retry() {}\n");
+    repo.commit("fix(api): share the retry helper\n\nBelaf-Projects: web, Mobile");
+    repo
+}
+
+fn pending_commits(repo: &TestRepo) -> serde_json::Value {
+    let output = repo.run_belaf_command(&["status", "--format", "json"]);
+    assert!(
+        output.status.success(),
+        "status failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("status prints JSON");
+    json["projects"]
+        .as_array()
+        .expect("projects array")
+        .iter()
+        .map(|p| (p["name"].as_str().unwrap().to_owned(), p["commits"].clone()))
+        .collect::<serde_json::Map<_, _>>()
+        .into()
+}
+
+#[test]
+fn test_trailer_overrides_scope_and_path_attribution() {
+    let repo = workspace_repo("trailer_first");
+    let commits = pending_commits(&repo);
+    assert_eq!(
+        commits["web"],
+        serde_json::json!(["fix(api): share the retry helper"]),
+        "{commits}"
+    );
+    assert_eq!(commits["api"], serde_json::json!([]), "{commits}");
+
+    let output = repo.run_belaf_command(&["lint-commits", "--strict", "--format", "json"]);
+    assert_eq!(output.status.code(), Some(4));
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("lint-commits prints JSON");
+    let problems = json["trailer_problems"]
+        .as_array()
+        .expect("trailer_problems");
+    assert_eq!(problems.len(), 1, "{json}");
+    assert_eq!(
+        problems[0]["unknown_projects"],
+        serde_json::json!(["Mobile"])
+    );
+}
+
+#[test]
+fn test_trailer_is_ignored_by_other_strategies() {
+    let repo = workspace_repo("scope_first");
+    let commits = pending_commits(&repo);
+    assert_eq!(
+        commits["api"],
+        serde_json::json!(["fix(api): share the retry helper"]),
+        "{commits}"
+    );
+    assert_eq!(commits["web"], serde_json::json!([]), "{commits}");
+
+    let output = repo.run_belaf_command(&["lint-commits", "--strict"]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stdout)
+    );
+}
@@ -0,0 +1,73 @@
+//! `[checks] publish_metadata`: `prepare` refuses to release a unit whose
+//! manifest lacks registry metadata, and `belaf doctor` reports it.
+
+mod common;
+
+use common::TestRepo;
+
+fn crate_without_metadata() -> TestRepo {
+    let repo = TestRepo::new();
+    repo.write_file(
+        "Cargo.toml",
+        "[package]\nname = \"my-crate\"\nversion = \"1.0.0\"\nedition = \"2021\"\n\
+         license = \"MIT\"\n",
+    );
+    repo.write_file("src/lib.rs", "pub fn hello() {}\n");
+    repo.commit("Initial commit");
+
+    let output = repo.run_belaf_command(&["init", "--force"]);
+    assert!(output.status.success());
+    let config = repo.read_file("belaf/config.toml");
+    repo.write_file(
+        "belaf/config.toml",
+        &format!("{config}\n[checks]\npublish_metadata = true\n"),
+    );
+    repo.commit("chore: add belaf config");
+
+    repo.write_file("src/lib.rs", "pub fn hello() { println!(\"hi\") }\n");
+    repo.commit("fix: say hi");
+    repo
+}
+
+#[test]
+fn test_prepare_refuses_units_without_publish_metadata() {
+    let repo = crate_without_metadata();
+
+    let output = repo.run_belaf_command(&["prepare", "--ci"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(
+        stderr.contains("my-crate: `Cargo.toml` has no `description`")
+            && stderr.contains("has no `repository`"),
+        "stderr: {stderr}"
+    );
+    assert!(!stderr.contains("has no `license`"), "stderr: {stderr}");
+    assert!(
+        repo.read_file("Cargo.toml").contains("version = \"1.0.0\""),
+        "nothing is written before the check"
+    );
+}
+
+#[test]
+fn test_doctor_reports_missing_publish_metadata() {
+    let repo = crate_without_metadata();
+
+    let output = repo.run_belaf_command_with_env(
+        &["doctor", "--json"],
+        &[
+            ("BELAF_NO_KEYRING", "1"),
+            ("BELAF_API_URL", "http://127.0.0.1:9"),
+        ],
+    );
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("doctor prints JSON");
+    assert_eq!(json["metadata"]["status"], "error", "{json}");
+    assert!(
+        json["metadata"]["detail"]
+            .as_str()
+            .unwrap()
+            .contains("has no `description`"),
+        "{json}"
+    );
+    assert_eq!(json["ok"], false);
+}
@@ -155,3 +155,45 @@ edition = "2021"
         "Config should not be overwritten"
     );
 }
+
+#[test]
+fn test_release_init_records_audit_entry() {
+    let repo = TestRepo::new();
+
+    repo.write_file(
+        "Cargo.toml",
+        "[package]\nname = \"test\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+    );
+    repo.write_file("src/lib.rs", "pub fn hello() {}\n");
+    repo.commit("Initial commit");
+
+    let output = repo.run_belaf_command(&["init", "--force"]);
+    assert!(output.status.success());
+    assert!(repo.file_exists("belaf/audit.jsonl"));
+
+    let show = |args: &[&str]| -> Vec<serde_json::Value> {
+        let mut full = vec!["audit", "show", "--format", "json"];
+        full.extend_from_slice(args);
+        let output = repo.run_belaf_command(&full);
+        assert!(output.status.success());
+        serde_json::from_slice(&output.stdout).expect("audit show emits JSON")
+    };
+
+    let entries = show(&[]);
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0]["command"], "init");
+    assert_eq!(entries[0]["tags"][0], "belaf-baseline");
+    assert!(entries[0]["commit"].is_null(), "not committed yet");
+
+    repo.commit("Initialize belaf");
+    let head = std::process::Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .current_dir(&repo.path)
+        .output()
+        .expect("git rev-parse");
+    let head = String::from_utf8_lossy(&head.stdout).trim().to_string();
+
+    let entries = show(&["--command", "init"]);
+    assert_eq!(entries[0]["commit"], head.as_str());
+    assert!(show(&["--command", "prepare"]).is_empty());
+}
@@ -735,3 +735,919 @@ edition = "2021"
     assert!(names.contains(&"alpha"), "Should contain alpha project");
     assert!(names.contains(&"beta"), "Should contain beta project");
 }
+
+#[test]
+fn test_approve_check_enforces_release_approvers() {
+    let repo = TestRepo::new();
+
+    repo.write_file(
+        "Cargo.toml",
+        r#"[package]
+name = "my-crate"
+version = "1.0.0"
+edition = "2021"
+"#,
+    );
+    repo.write_file("src/lib.rs", "pub fn hello() {}\n");
+    repo.commit("Initial commit");
+
+    let output = repo.run_belaf_command(&["init", "--force"]);
+    assert!(
+        output.status.success(),
+        "Init failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let config = repo.read_file("belaf/config.toml");
+    repo.write_file(
+        "belaf/config.toml",
+        &format!("{config}\n[release.approvers]\nusers = [\"alice\", \"bob\", \"carol\"]\nrequired = 2\n"),
+    );
+
+    let id = "0192f3a1-7c2e-7d4a-9b1e-3f2a1c0d9e8f";
+    let signoff = |login: &str| serde_json::json!({ "approver": login, "approved_at": "2026-01-01T00:00:00Z" });
+    let write_manifest = |approvals: Vec<serde_json::Value>| {
+        let manifest = serde_json::json!({
+            "schema_version": "1",
+            "manifest_id": id,
+            "created_at": "2026-01-01T00:00:00Z",
+            "created_by": "Alice",
+            "base_branch": "main",
+            "releases": [],
+            "approval": {
+                "required": 1,
+                "requested_by": "alice",
+                "approvals": approvals,
+            },
+        });
+        repo.write_file(
+            &format!("belaf/releases/{id}.json"),
+            &serde_json::to_string_pretty(&manifest).unwrap(),
+        );
+    };
+
+    // The preparer's own sign-off doesn't count, and the config raises
+    // the manifest's `required` from 1 to 2.
+    write_manifest(vec![signoff("alice"), signoff("bob")]);
+    let output = repo.run_belaf_command(&["approve", "0192f3a1", "--check", "--format", "json"]);
+    assert_eq!(
+        output.status.code(),
+        Some(4),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("approve output is JSON");
+    assert_eq!(json["release_id"], id);
+    assert_eq!(json["approved"], false);
+    assert_eq!(json["required"], 2);
+    assert_eq!(json["approved_by"], serde_json::json!(["bob"]));
+    assert_eq!(
+        json["rejected"],
+        serde_json::json!(["alice: prepared the release"])
+    );
+
+    // Two approvers other than the preparer are enough on paper, but
+    // the manifest is writable by anyone who can push: without the
+    // release PR's reviews to back the sign-offs, the gate stays shut.
+    write_manifest(vec![signoff("bob"), signoff("Carol")]);
+    let output =
+        repo.run_belaf_command_with_env(&["approve", id, "--check"], &[("GITHUB_TOKEN", "")]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success(), "unverified sign-offs passed");
+    assert!(stderr.contains("release PR's reviews"), "{stderr}");
+
+    let output = repo.run_belaf_command(&["approve", "no-such-release", "--check"]);
+    assert!(
+        !output.status.success(),
+        "an unknown release must be rejected"
+    );
+}
+
+#[test]
+fn test_promote_prerelease_to_stable() {
+    let repo = TestRepo::new();
+
+    repo.write_file(
+        "Cargo.toml",
+        r#"[package]
+name = "my-crate"
+version = "1.3.0-rc.2"
+edition = "2021"
+"#,
+    );
+    repo.write_file("src/lib.rs", "pub fn hello() {}\n");
+    repo.commit("Initial commit");
+
+    let output = repo.run_belaf_command(&["init", "--force"]);
+    assert!(
+        output.status.success(),
+        "Init failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    repo.write_file(
+        "CHANGELOG.md",
+        "# Changelog\n\n\
+         ## [1.3.0-rc.2] - 2026-03-02\n\n### Bug Fixes\n\n- Fix timeout\n\n\
+         ## [1.3.0-rc.1] - 2026-03-01\n\n### Features\n\n- Add streaming\n\n\
+         ## [1.2.0] - 2026-01-01\n\n- Older\n",
+    );
+    repo.commit("chore(release): my-crate v1.3.0-rc.2");
+    let tag = |name: &str| {
+        std::process::Command::new("git")
+            .args(["tag", name])
+            .current_dir(&repo.path)
+            .output()
+            .expect("failed to git tag")
+    };
+    tag("my-crate-v1.3.0-rc.2");
+
+    let _ = repo.run_belaf_command(&["promote", "my-crate"]);
+
+    let cargo_toml = repo.read_file("Cargo.toml");
+    assert!(
+        cargo_toml.contains("version = \"1.3.0\""),
+        "the prerelease identifier is stripped: {cargo_toml}"
+    );
+    let changelog = repo.read_file("CHANGELOG.md");
+    assert!(!changelog.contains("rc."), "entries merged: {changelog}");
+    assert!(changelog.contains("## [1.3.0] - "), "{changelog}");
+    assert!(changelog.contains("- Fix timeout") && changelog.contains("- Add streaming"));
+    assert!(changelog.contains("## [1.2.0] - 2026-01-01"));
+
+    let manifests = repo.list_files_in_dir("belaf/releases");
+    let manifest = repo.read_file(&format!("belaf/releases/{}", manifests[0]));
+    let json: serde_json::Value = serde_json::from_str(&manifest).unwrap();
+    let release = &json["releases"][0];
+    assert_eq!(release["previous_version"], "1.3.0-rc.2");
+    assert_eq!(release["new_version"], "1.3.0");
+    assert_eq!(release["bump_type"], "promote");
+    assert_eq!(release["tag_name"], "my-crate-v1.3.0");
+
+    // Unknown units are rejected before anything is written.
+    let output = repo.run_belaf_command(&["promote", "no-such-unit"]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_prepare_patch_release_from_maintenance_branch() {
+    let repo = TestRepo::new();
+    let git = |args: &[&str]| {
+        let output = std::process::Command::new("git")
+            .args(args)
+            .current_dir(&repo.path)
+            .output()
+            .expect("failed to run git");
+        assert!(output.status.success(), "git {args:?} failed");
+    };
+
+    repo.write_file(
+        "Cargo.toml",
+        "[package]\nname = \"my-crate\"\nversion = \"1.4.0\"\nedition = \"2021\"\n",
+    );
+    repo.write_file("src/lib.rs", "pub fn hello() {}\n");
+    repo.commit("Initial commit");
+    let output = repo.run_belaf_command(&["init", "--force"]);
+    assert!(
+        output.status.success(),
+        "Init failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    repo.commit("chore: init belaf");
+    git(&["tag", "my-crate-v1.4.0"]);
+    git(&["branch", "release/1.x"]);
+
+    // The default branch moves on to 2.x.
+    repo.write_file(
+        "Cargo.toml",
+        "[package]\nname = \"my-crate\"\nversion = \"2.0.0\"\nedition = \"2021\"\n",
+    );
+    repo.commit("feat!: new API");
+    git(&["tag", "my-crate-v2.0.0"]);
+
+    git(&["checkout", "-q", "release/1.x"]);
+    repo.write_file("src/lib.rs", "pub fn hello() { let _ = 1; }\n");
+    repo.commit("feat: backport an option");
+    git(&["checkout", "-q", "-"]);
+
+    let output = repo.run_belaf_command(&["prepare", "--ci", "--branch", "main"]);
+    assert!(!output.status.success(), "main names no version line");
+
+    let _ = repo.run_belaf_command(&["prepare", "--ci", "--branch", "release/1.x"]);
+
+    let cargo_toml = repo.read_file("Cargo.toml");
+    assert!(
+        cargo_toml.contains("version = \"1.4.1\""),
+        "feat is released as a patch of the 1.x line: {cargo_toml}"
+    );
+    let manifests = repo.list_files_in_dir("belaf/releases");
+    let manifest = repo.read_file(&format!("belaf/releases/{}", manifests[0]));
+    let json: serde_json::Value = serde_json::from_str(&manifest).unwrap();
+    assert_eq!(json["base_branch"], "release/1.x");
+    let release = &json["releases"][0];
+    assert_eq!(release["previous_version"], "1.4.0");
+    assert_eq!(release["bump_type"], "patch");
+}
+
+#[test]
+fn test_package_renders_manifests_for_the_latest_release() {
+    use sha2::{Digest, Sha256};
+
+    let repo = TestRepo::new();
+    repo.write_file(
+        "Cargo.toml",
+        r#"[package]
+name = "mycli"
+version = "1.2.0"
+edition = "2021"
+"#,
+    );
+    repo.write_file("src/main.rs", "fn main() {}\n");
+    repo.commit("Initial commit");
+    let output = repo.run_belaf_command(&["init", "--force"]);
+    assert!(
+        output.status.success(),
+        "Init failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let config = repo.read_file("belaf/config.toml");
+    repo.write_file(
+        "belaf/config.toml",
+        &format!(
+            "{config}\n[packaging.mycli]\ndescription = \"Does things\"\nlicense = \"MIT\"\n\n\
+             [packaging.mycli.homebrew]\nrepo = \"acme/homebrew-tap\"\n\n\
+             [packaging.mycli.aur]\nrepo = \"acme/aur-mycli\"\n"
+        ),
+    );
+    repo.commit("chore(release): mycli v1.2.0");
+    std::process::Command::new("git")
+        .args(["tag", "mycli-v1.2.0"])
+        .current_dir(&repo.path)
+        .output()
+        .expect("failed to git tag");
+
+    let dist = repo.path.join("target/dist");
+    std::fs::create_dir_all(&dist).unwrap();
+    for name in [
+        "mycli-x86_64-unknown-linux-gnu.tar.gz",
+        "mycli-aarch64-apple-darwin.tar.gz",
+    ] {
+        std::fs::write(dist.join(name), name).unwrap();
+    }
+
+    let output = repo.run_belaf_command(&[
+        "package",
+        "mycli",
+        "--artifacts",
+        dist.to_str().unwrap(),
+        "--dry-run",
+    ]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "package failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(stdout.contains("Homebrew → acme/homebrew-tap:Formula/mycli.rb"));
+    assert!(stdout.contains("class Mycli < Formula"));
+    assert!(stdout.contains(
+        "url \"https://github.com/test/repo/releases/download/mycli-v1.2.0/mycli-aarch64-apple-darwin.tar.gz\""
+    ));
+    let linux_sha = format!(
+        "{:x}",
+        Sha256::digest(b"mycli-x86_64-unknown-linux-gnu.tar.gz")
+    );
+    assert!(stdout.contains(&format!("sha256 \"{linux_sha}\"")));
+    assert!(stdout.contains("AUR → acme/aur-mycli:PKGBUILD"));
+    assert!(stdout.contains("pkgname=mycli-bin\npkgver=1.2.0\n"));
+    assert!(stdout.contains(&format!("sha256sums_x86_64=('{linux_sha}')")));
+}
+
+/// A `my-crate` 1.0.0 repo with `tags` on its initial commit and a fix
+/// on top.
+fn repo_with_tags_before_a_fix(tags: &[&str]) -> TestRepo {
+    let repo = TestRepo::new();
+    repo.write_file(
+        "Cargo.toml",
+        r#"[package]
+name = "my-crate"
+version = "1.0.0"
+edition = "2021"
+"#,
+    );
+    repo.write_file("src/lib.rs", "pub fn hello() {}\n");
+    repo.commit("Initial commit");
+    let output = repo.run_belaf_command(&["init", "--force"]);
+    assert!(
+        output.status.success(),
+        "Init failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    repo.commit("chore: add belaf config");
+    for tag in tags {
+        std::process::Command::new("git")
+            .args(["tag", tag])
+            .current_dir(&repo.path)
+            .output()
+            .expect("failed to git tag");
+    }
+    repo.write_file("src/fix.rs", "pub fn fix_bug() {}\n");
+    repo.commit("fix: resolve critical bug");
+    repo
+}
+
+#[test]
+fn test_prepare_refuses_versions_that_collide_with_existing_tags() {
+    // 1.0.1 was tagged, but its version bump never landed.
+    let repo = repo_with_tags_before_a_fix(&["my-crate-v1.0.0", "my-crate-v1.0.1"]);
+    let output = repo.run_belaf_command(&["prepare", "--ci"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(
+        stderr.contains("my-crate 1.0.1: tag `my-crate-v1.0.1` already exists locally"),
+        "stderr: {stderr}"
+    );
+    assert!(
+        repo.read_file("Cargo.toml").contains("version = \"1.0.0\""),
+        "nothing should be written when the tag collides"
+    );
+
+    let repo = repo_with_tags_before_a_fix(&["my-crate-v1.0.0", "my-crate-v2.0.0"]);
+    let output = repo.run_belaf_command(&["prepare", "--ci"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(
+        stderr
+            .contains("my-crate 1.0.1: not newer than 2.0.0, already tagged as `my-crate-v2.0.0`"),
+        "stderr: {stderr}"
+    );
+}
+
+fn git_stdout(repo: &TestRepo, args: &[&str]) -> String {
+    let output = std::process::Command::new("git")
+        .args(args)
+        .current_dir(&repo.path)
+        .output()
+        .expect("failed to run git");
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
+
+#[test]
+fn test_prepare_refuses_an_upstream_outside_upstream_urls() {
+    let repo = repo_with_tags_before_a_fix(&["my-crate-v1.0.0"]);
+    git_stdout(
+        &repo,
+        &[
+            "remote",
+            "set-url",
+            "origin",
+            "https://github.com/someone/fork.git",
+        ],
+    );
+
+    let output = repo.run_belaf_command(&["prepare", "--ci"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(
+        stderr.contains("is not one of `[repo] upstream_urls`")
+            && stderr.contains("--i-know-what-im-doing"),
+        "stderr: {stderr}"
+    );
+    assert!(repo.read_file("Cargo.toml").contains("version = \"1.0.0\""));
+
+    // Overridden, it gets as far as the push (no credentials in tests).
+    let output = repo.run_belaf_command(&["prepare", "--ci", "--i-know-what-im-doing"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        !stderr.contains("upstream_urls"),
+        "the override should skip the check: {stderr}"
+    );
+    assert!(repo.read_file("Cargo.toml").contains("version = \"1.0.1\""));
+}
+
+#[test]
+fn test_prepare_resume_continues_after_the_release_commit() {
+    let repo = repo_with_tags_before_a_fix(&["my-crate-v1.0.0"]);
+
+    // No belaf credentials in tests: the release is committed, the push fails.
+    let output = repo.run_belaf_command(&["prepare", "--ci"]);
+    assert!(!output.status.success());
+    let release_commit = git_stdout(&repo, &["rev-parse", "HEAD"]);
+    assert!(git_stdout(&repo, &["log", "-1", "--format=%s"]).starts_with("chore(release)"));
+    assert!(repo.path.join(".git/belaf/release-journal.json").exists());
+
+    let output = repo.run_belaf_command(&["prepare", "--ci"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(
+        stderr.contains("stopped before pushing the release branch")
+            && stderr.contains("belaf prepare --resume"),
+        "stderr: {stderr}"
+    );
+
+    // Resuming retries the push without committing again.
+    let output = repo.run_belaf_command(&["prepare", "--ci", "--resume"]);
+    assert!(!output.status.success());
+    assert_eq!(git_stdout(&repo, &["rev-parse", "HEAD"]), release_commit);
+    assert!(repo.path.join(".git/belaf/release-journal.json").exists());
+}
+
+#[test]
+fn test_prepare_resume_rolls_back_a_release_that_wrote_nothing() {
+    // The tag check fails after the release branch was created.
+    let repo = repo_with_tags_before_a_fix(&["my-crate-v1.0.0", "my-crate-v1.0.1"]);
+    let base = git_stdout(&repo, &["branch", "--show-current"]);
+    let output = repo.run_belaf_command(&["prepare", "--ci"]);
+    assert!(!output.status.success());
+    assert!(git_stdout(&repo, &["branch", "--show-current"]).starts_with("release/"));
+
+    let output = repo.run_belaf_command(&["prepare", "--ci", "--resume"]);
+    assert!(
+        output.status.success(),
+        "resume failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let status: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(status["status"], "rolled_back");
+    assert_eq!(git_stdout(&repo, &["branch", "--show-current"]), base);
+    assert_eq!(git_stdout(&repo, &["branch", "--list", "release/*"]), "");
+    assert_eq!(git_stdout(&repo, &["status", "--porcelain"]), "");
+    assert!(!repo.path.join(".git/belaf/release-journal.json").exists());
+
+    let output = repo.run_belaf_command(&["prepare", "--ci", "--resume"]);
+    let status: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(status["status"], "nothing_to_do");
+}
+
+#[test]
+fn test_prepare_commits_each_unit_separately_with_per_unit_release_commits() {
+    let repo = TestRepo::new();
+    repo.write_file(
+        "Cargo.toml",
+        "[workspace]\nmembers = [\"packages/*\"]\nresolver = \"2\"\n",
+    );
+    for (name, version) in [("api", "1.2.0"), ("cli", "0.4.0")] {
+        repo.write_file(
+            &format!("packages/{name}/Cargo.toml"),
+            &format!("[package]\nname = \"{name}\"\nversion = \"{version}\"\nedition = \"2021\"\n"),
+        );
+        repo.write_file(&format!("packages/{name}/src/lib.rs"), "pub fn f() {}\n");
+    }
+    repo.commit("Initial commit");
+
+    let output = repo.run_belaf_command(&["init", "--force"]);
+    assert!(output.status.success());
+    let config = repo.read_file("belaf/config.toml");
+    repo.write_file(
+        "belaf/config.toml",
+        &config.replace(
+            "release_commits = \"single\"",
+            "release_commits = \"per_unit\"",
+        ),
+    );
+    repo.commit("chore: add belaf config");
+
+    repo.write_file("packages/api/src/routes.rs", "pub fn routes() {}\n");
+    repo.commit("feat(api): add routes");
+    repo.write_file("packages/cli/src/main.rs", "fn main() {}\n");
+    repo.commit("fix(cli): handle empty input");
+
+    // No belaf credentials in tests: the release is committed, the push fails.
+    let _ = repo.run_belaf_command(&["prepare", "--ci"]);
+
+    let subjects = git_stdout(&repo, &["log", "-3", "--format=%s"]);
+    let subjects: Vec<&str> = subjects.lines().collect();
+    assert_eq!(subjects.len(), 3, "{subjects:?}");
+    assert_eq!(subjects[0], "chore(release): add release manifest");
+    let mut unit_subjects = vec![subjects[1], subjects[2]];
+    unit_subjects.sort();
+    assert_eq!(
+        unit_subjects,
+        ["chore(release): api v1.3.0", "chore(release): cli v0.4.1"]
+    );
+
+    for (rev, subject) in [("HEAD~1", subjects[1]), ("HEAD~2", subjects[2])] {
+        let files = git_stdout(&repo, &["show", "--name-only", "--format=", rev]);
+        let unit = if subject.contains("api") {
+            "api"
+        } else {
+            "cli"
+        };
+        assert!(
+            files
+                .lines()
+                .all(|f| f.starts_with(&format!("packages/{unit}/"))),
+            "{subject} touched files outside its unit: {files}"
+        );
+    }
+    let files = git_stdout(&repo, &["show", "--name-only", "--format=", "HEAD"]);
+    assert!(
+        files.lines().any(|f| f.starts_with("belaf/releases/")),
+        "{files}"
+    );
+    assert!(!files.contains("packages/"), "{files}");
+}
+
+#[test]
+fn test_release_prepare_allow_empty_releases_unchanged_units() {
+    let repo = TestRepo::new();
+    repo.write_file(
+        "Cargo.toml",
+        r#"[package]
+name = "my-crate"
+version = "1.0.0"
+edition = "2021"
+"#,
+    );
+    repo.write_file("src/lib.rs", "pub fn hello() {}\n");
+    repo.commit("Initial commit");
+    let output = repo.run_belaf_command(&["init", "--force"]);
+    assert!(
+        output.status.success(),
+        "Init failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    repo.commit("chore(release): my-crate v1.0.0");
+    std::process::Command::new("git")
+        .args(["tag", "my-crate-v1.0.0"])
+        .current_dir(&repo.path)
+        .output()
+        .expect("failed to git tag");
+
+    let output = repo.run_belaf_command(&["prepare", "--ci"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("\"nothing_to_do\""), "{stdout}");
+
+    // --allow-empty names its units with --release-unit and needs a message.
+    let output =
+        repo.run_belaf_command(&["prepare", "--ci", "--allow-empty", "-p", "my-crate:patch"]);
+    assert!(!output.status.success());
+
+    let _ = repo.run_belaf_command(&[
+        "prepare",
+        "--ci",
+        "--allow-empty",
+        "-p",
+        "my-crate:patch",
+        "--message",
+        "Rebuilt for CVE-2026-1234 in the base image.",
+    ]);
+    let cargo_toml = repo.read_file("Cargo.toml");
+    assert!(cargo_toml.contains("version = \"1.0.1\""), "{cargo_toml}");
+    let changelog = repo.read_file("CHANGELOG.md");
+    assert!(changelog.contains("## [1.0.1] - "), "{changelog}");
+    assert!(
+        changelog.contains("Rebuilt for CVE-2026-1234 in the base image.\n"),
+        "{changelog}"
+    );
+}
+
+#[test]
+fn test_tag_creates_annotated_tags_from_the_manifest() {
+    let repo = TestRepo::new();
+    repo.write_file(
+        "Cargo.toml",
+        r#"[package]
+name = "my-crate"
+version = "1.0.0"
+edition = "2021"
+"#,
+    );
+    repo.write_file("src/lib.rs", "pub fn hello() {}\n");
+    repo.commit("Initial commit");
+    let output = repo.run_belaf_command(&["init", "--force"]);
+    assert!(
+        output.status.success(),
+        "Init failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    repo.commit("chore: add belaf config");
+    repo.write_file("src/fix.rs", "pub fn fix_bug() {}\n");
+    repo.commit("fix: resolve critical bug");
+    let _ = repo.run_belaf_command(&["prepare", "--ci"]);
+
+    let manifest = repo
+        .list_files_in_dir("belaf/releases")
+        .into_iter()
+        .find(|f| f.ends_with(".json"))
+        .expect("prepare should write a manifest");
+    let id = &manifest[..8];
+
+    let output = repo.run_belaf_command(&["tag", id, "--dry-run"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+    assert!(stdout.contains("my-crate 1.0.1\n\n## [1.0.1]"), "{stdout}");
+
+    let output = repo.run_belaf_command(&["tag", id]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "{stdout}{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let tag = stdout
+        .lines()
+        .find_map(|l| l.split_once("Tagged ").map(|(_, tag)| tag))
+        .expect("a tag should be created")
+        .to_string();
+
+    let shown = std::process::Command::new("git")
+        .args(["tag", "-l", "-n99", &tag])
+        .current_dir(&repo.path)
+        .output()
+        .expect("failed to run git tag");
+    let shown = String::from_utf8_lossy(&shown.stdout);
+    assert!(shown.contains("my-crate 1.0.1"), "{shown}");
+    assert!(shown.contains("Resolve critical bug"), "{shown}");
+    let kind = std::process::Command::new("git")
+        .args(["cat-file", "-t", &tag])
+        .current_dir(&repo.path)
+        .output()
+        .expect("failed to run git cat-file");
+    assert_eq!(String::from_utf8_lossy(&kind.stdout).trim(), "tag");
+
+    let audit = |repo: &TestRepo| -> Vec<serde_json::Value> {
+        let output =
+            repo.run_belaf_command(&["audit", "show", "--command", "tag", "--format", "json"]);
+        serde_json::from_slice(&output.stdout).expect("audit show emits JSON")
+    };
+    let entries = audit(&repo);
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0]["manifest"], &manifest[..manifest.len() - 5]);
+    assert_eq!(entries[0]["tags"][0], tag.as_str());
+
+    let output = repo.run_belaf_command(&["tag", id]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("already exists, skipped"), "{stdout}");
+    assert_eq!(audit(&repo).len(), 1, "nothing tagged, nothing recorded");
+}
+
+#[test]
+fn test_tag_checks_the_release_checklist_first() {
+    let repo = TestRepo::new();
+    repo.write_file(
+        "Cargo.toml",
+        r#"[package]
+name = "my-crate"
+version = "1.0.0"
+edition = "2021"
+"#,
+    );
+    repo.write_file("src/lib.rs", "pub fn hello() {}\n");
+    repo.commit("Initial commit");
+    let output = repo.run_belaf_command(&["init", "--force"]);
+    assert!(output.status.success());
+    let mut config = repo.read_file("belaf/config.toml");
+    config
+        .push_str("\n[release_unit.my-crate]\nrelease_checklist = [\"Update the status page\"]\n");
+    repo.write_file("belaf/config.toml", &config);
+    repo.commit("chore: add belaf config");
+    repo.write_file("src/fix.rs", "pub fn fix_bug() {}\n");
+    repo.commit("fix: resolve critical bug");
+    let _ = repo.run_belaf_command(&["prepare", "--ci"]);
+
+    let manifest = repo
+        .list_files_in_dir("belaf/releases")
+        .into_iter()
+        .find(|f| f.ends_with(".json"))
+        .expect("prepare should write a manifest");
+    let id = &manifest[..8];
+
+    let output = repo.run_belaf_command(&["tag", id, "--dry-run"]);
+    assert!(output.status.success());
+
+    let output = repo.run_belaf_command(&["tag", id]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success(), "{stderr}");
+    assert!(stderr.contains("release checklist"), "{stderr}");
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("Tagged "));
+}
+
+#[test]
+fn test_release_prepare_from_detached_head() {
+    let repo = TestRepo::new();
+    repo.write_file(
+        "Cargo.toml",
+        r#"[package]
+name = "my-crate"
+version = "1.0.0"
+edition = "2021"
+"#,
+    );
+    repo.write_file("src/lib.rs", "pub fn hello() {}\n");
+    repo.commit("Initial commit");
+    let output = repo.run_belaf_command(&["init", "--force"]);
+    assert!(
+        output.status.success(),
+        "Init failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    repo.commit("chore: add belaf config");
+    repo.write_file("src/fix.rs", "pub fn fix_bug() {}\n");
+    repo.commit("fix: resolve critical bug");
+    std::process::Command::new("git")
+        .args(["checkout", "--detach", "HEAD"])
+        .current_dir(&repo.path)
+        .output()
+        .expect("failed to detach HEAD");
+
+    // A tag build names no branch to release for.
+    let output =
+        repo.run_belaf_command_with_env(&["prepare", "--ci"], &[("GITHUB_REF", "refs/tags/v1")]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(stderr.contains("set `[git] base_branch`"), "{stderr}");
+
+    let _ = repo
+        .run_belaf_command_with_env(&["prepare", "--ci"], &[("GITHUB_REF", "refs/heads/stable")]);
+    let cargo_toml = repo.read_file("Cargo.toml");
+    assert!(cargo_toml.contains("version = \"1.0.1\""), "{cargo_toml}");
+    let manifest = repo
+        .list_files_in_dir("belaf/releases")
+        .into_iter()
+        .find(|f| f.ends_with(".json"))
+        .expect("prepare should write a manifest");
+    let manifest = repo.read_file(&format!("belaf/releases/{manifest}"));
+    assert!(
+        manifest.contains("\"base_branch\": \"stable\""),
+        "{manifest}"
+    );
+    let stable = std::process::Command::new("git")
+        .args(["rev-parse", "--verify", "--quiet", "refs/heads/stable"])
+        .current_dir(&repo.path)
+        .output()
+        .expect("failed to run git rev-parse");
+    assert!(stable.status.success(), "`stable` should exist locally");
+}
+
+#[test]
+fn test_prepare_cuts_long_entries_in_the_release_notes() {
+    let repo = TestRepo::new();
+    repo.write_file(
+        "Cargo.toml",
+        "[package]\nname = \"my-crate\"\nversion = \"1.0.0\"\nedition = \"2021\"\n",
+    );
+    repo.write_file("src/lib.rs", "pub fn hello() {}\n");
+    repo.commit("Initial commit");
+    let output = repo.run_belaf_command(&["init", "--force"]);
+    assert!(
+        output.status.success(),
+        "Init failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let config = repo.read_file("belaf/config.toml");
+    repo.write_file(
+        "belaf/config.toml",
+        &config.replacen("[changelog]\n", "[changelog]\nmax_entry_size = 400\n", 1),
+    );
+    repo.commit("chore: add belaf config");
+    for i in 0..30 {
+        repo.write_file(&format!("src/fix{i}.rs"), "pub fn fix() {}\n");
+        repo.commit(&format!("fix: resolve bug number {i} in the parser"));
+    }
+
+    let _ = repo.run_belaf_command(&["prepare", "--ci"]);
+
+    let changelog = repo.read_file("CHANGELOG.md");
+    assert!(changelog.contains("bug number 29"), "{changelog}");
+    let manifests = repo.list_files_in_dir("belaf/releases");
+    let manifest = repo.read_file(&format!("belaf/releases/{}", manifests[0]));
+    let json: serde_json::Value = serde_json::from_str(&manifest).unwrap();
+    let notes = json["releases"][0]["changelog"].as_str().unwrap();
+    assert!(notes.len() <= 400, "{notes}");
+    assert!(notes.contains("more commits, see the"), "{notes}");
+    assert!(notes.contains("CHANGELOG.md"), "{notes}");
+}
+
+#[test]
+fn test_prepare_releases_the_units_listed_in_a_file() {
+    let repo = TestRepo::new();
+    repo.write_file(
+        "Cargo.toml",
+        "[workspace]\nmembers = [\"packages/*\"]\nresolver = \"2\"\n",
+    );
+    for (name, version) in [("core", "1.0.0"), ("utils", "2.0.0"), ("cli", "0.3.0")] {
+        repo.write_file(
+            &format!("packages/{name}/Cargo.toml"),
+            &format!(
+                "[package]\nname = \"train-{name}\"\nversion = \"{version}\"\nedition = \"2021\"\n"
+            ),
+        );
+        repo.write_file(&format!("packages/{name}/src/lib.rs"), "pub fn f() {}\n");
+    }
+    repo.commit("Initial commit");
+    let output = repo.run_belaf_command(&["init", "--force"]);
+    assert!(output.status.success());
+    repo.commit("chore: add belaf config");
+    for name in ["core", "utils", "cli"] {
+        repo.write_file(&format!("packages/{name}/src/fix.rs"), "pub fn fix() {}\n");
+        repo.commit(&format!("fix({name}): fix a bug"));
+    }
+
+    // Kept in .git so the working tree stays clean.
+    repo.write_file(".git/train.txt", "train-nope\n");
+    let output =
+        repo.run_belaf_command(&["prepare", "--ci", "--projects-from-file", ".git/train.txt"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("train-nope"), "{stderr}");
+
+    repo.write_file(
+        ".git/train.txt",
+        "# computed by the release train\ntrain-core:minor\n\ntrain-utils\n",
+    );
+    let _ = repo.run_belaf_command(&[
+        "prepare",
+        "--ci",
+        "--projects-from-file",
+        ".git/train.txt",
+        "-p",
+        "train-utils:major",
+    ]);
+    assert!(repo
+        .read_file("packages/core/Cargo.toml")
+        .contains("version = \"1.1.0\""));
+    assert!(repo
+        .read_file("packages/utils/Cargo.toml")
+        .contains("version = \"3.0.0\""));
+    assert!(repo
+        .read_file("packages/cli/Cargo.toml")
+        .contains("version = \"0.3.0\""));
+}
+
+#[test]
+fn test_prepare_moves_submodule_pins_to_their_newest_release() {
+    let git = |dir: &std::path::Path, args: &[&str]| {
+        let output = std::process::Command::new("git")
+            .args(["-c", "protocol.file.allow=always"])
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .expect("failed to run git");
+        assert!(
+            output.status.success(),
+            "git {args:?}: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    };
+
+    let core = TestRepo::new();
+    core.write_file("core.txt", "one\n");
+    core.commit("feat: first");
+    git(&core.path, &["tag", "v1.0.0"]);
+    core.write_file("core.txt", "two\n");
+    core.commit("feat: second");
+    git(&core.path, &["tag", "v1.1.0"]);
+    let released = git(&core.path, &["rev-parse", "v1.1.0^{commit}"]);
+
+    let repo = TestRepo::new();
+    repo.write_file(
+        "Cargo.toml",
+        "[package]\nname = \"my-crate\"\nversion = \"1.0.0\"\nedition = \"2021\"\n",
+    );
+    repo.write_file("src/lib.rs", "pub fn hello() {}\n");
+    repo.write_file("versions.env", "CORE=1.0.0\n");
+    git(
+        &repo.path,
+        &[
+            "submodule",
+            "add",
+            core.path.to_str().unwrap(),
+            "vendor/core",
+        ],
+    );
+    git(
+        &repo.path.join("vendor/core"),
+        &["checkout", "-q", "v1.0.0"],
+    );
+    repo.commit("Initial commit");
+    let output = repo.run_belaf_command(&["init", "--force"]);
+    assert!(
+        output.status.success(),
+        "Init failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let config = repo.read_file("belaf/config.toml");
+    repo.write_file(
+        "belaf/config.toml",
+        &format!(
+            "{config}\n[submodule.core]\npath = \"vendor/core\"\nversion_files = [\n  \
+             {{ path = \"versions.env\", regex_pattern = \"(?m)^CORE=(.+)$\", regex_replace = \"CORE={{version}}\" }},\n]\n"
+        ),
+    );
+    repo.commit("chore: add belaf config");
+    git(&repo.path, &["tag", "my-crate-v1.0.0"]);
+    repo.write_file("src/fix.rs", "pub fn fix_bug() {}\n");
+    repo.commit("fix: resolve critical bug");
+
+    // No belaf credentials in tests: the release is committed, the push fails.
+    let output = repo.run_belaf_command(&["prepare", "--ci"]);
+    assert!(!output.status.success());
+    assert!(git_stdout(&repo, &["log", "-1", "--format=%s"]).starts_with("chore(release)"));
+    let pinned = git_stdout(&repo, &["ls-tree", "HEAD", "vendor/core"]);
+    assert!(pinned.contains(&released), "{pinned}");
+    assert_eq!(
+        git_stdout(&repo, &["show", "HEAD:versions.env"]),
+        "CORE=1.1.0"
+    );
+}
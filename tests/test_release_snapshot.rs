@@ -0,0 +1,123 @@
+//! `belaf release --snapshot`.
+
+mod common;
+
+use common::TestRepo;
+
+/// `my-crate` 1.0.0, released, with a feature since. Returns the short
+/// SHA of HEAD.
+fn unreleased_feature(repo: &TestRepo) -> String {
+    repo.init_crate("");
+    repo.write_file("src/more.rs", "pub fn more() {}\n");
+    repo.commit("feat: more");
+
+    repo.git(&["rev-parse", "--short=7", "HEAD"])
+}
+
+#[test]
+fn test_snapshot_dry_run_plans_a_nightly_of_the_next_version() {
+    let repo = TestRepo::new();
+    let sha = unreleased_feature(&repo);
+
+    let output = repo.run_belaf_command(&[
+        "release",
+        "--snapshot",
+        "--tag",
+        "--dry-run",
+        "--format",
+        "json",
+    ]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let payload: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("snapshot should print JSON");
+    let snapshot = &payload["snapshots"][0];
+    let version = snapshot["version"].as_str().unwrap();
+    assert_eq!(snapshot["name"], "my-crate");
+    assert!(version.starts_with("1.1.0-nightly."), "{version}");
+    assert!(version.ends_with(&format!("+{sha}")), "{version}");
+    assert_eq!(
+        snapshot["tag"],
+        format!("snapshot/my-crate-v{version}").as_str()
+    );
+    assert!(snapshot["channel"].is_null());
+    assert_eq!(snapshot["skipped"], "crates.io has no prerelease channel");
+
+    let cargo_toml = repo.read_file("Cargo.toml");
+    assert!(cargo_toml.contains("version = \"1.0.0\""), "{cargo_toml}");
+    assert!(!repo.file_exists("CHANGELOG.md"));
+}
+
+#[test]
+fn test_snapshot_commits_the_version_without_a_changelog() {
+    let repo = TestRepo::new();
+    let sha = unreleased_feature(&repo);
+
+    let output = repo.run_belaf_command(&[
+        "release",
+        "--snapshot",
+        "--label",
+        "canary",
+        "--format",
+        "json",
+    ]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let payload: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("snapshot should print JSON");
+    let commit = payload["snapshot_commit"].as_str().unwrap();
+
+    let cargo_toml = repo.git(&["show", &format!("{commit}:Cargo.toml")]);
+    assert!(
+        cargo_toml.contains("version = \"1.1.0-canary."),
+        "{cargo_toml}"
+    );
+    assert!(cargo_toml.contains(&format!("+{sha}\"")), "{cargo_toml}");
+
+    let cargo_toml = repo.read_file("Cargo.toml");
+    assert!(cargo_toml.contains("version = \"1.0.0\""), "{cargo_toml}");
+    assert!(!repo.file_exists("CHANGELOG.md"));
+    assert!(repo.list_files_in_dir("belaf/releases").is_empty());
+
+    let audit = repo.read_file("belaf/audit.jsonl");
+    assert!(audit.contains("\"release --snapshot\""), "{audit}");
+}
+
+#[test]
+fn test_status_after_a_tagged_snapshot_still_counts_unreleased_commits() {
+    let repo = TestRepo::new();
+    unreleased_feature(&repo);
+    repo.git(&["tag", "my-crate-v1.0.0", "HEAD~2"]);
+
+    let output = repo.run_belaf_command(&["release", "--snapshot", "--format", "json"]);
+    let payload: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("snapshot should print JSON");
+    let version = payload["snapshots"][0]["version"].as_str().unwrap();
+    let commit = payload["snapshot_commit"].as_str().unwrap();
+    repo.git(&["tag", &format!("snapshot/my-crate-v{version}"), commit]);
+
+    let output = repo.run_belaf_command(&["status", "--format", "json"]);
+    let status: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let unit = &status["projects"][0];
+    assert_eq!(unit["commits_count"], 2);
+    assert_eq!(unit["bump"]["suggested"], "minor");
+
+    let tag = format!("snapshot/my-crate-v{version}");
+    let output = repo.run_belaf_command(&["verify-tag", &tag, "--allow-unsigned"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+    assert!(
+        stdout.contains(&format!("my-crate: Cargo.toml says {version}")),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("my-crate: a snapshot, which has no entry"),
+        "{stdout}"
+    );
+}
@@ -0,0 +1,178 @@
+//! `belaf release cut` and `belaf release publish`.
+
+mod common;
+
+use common::TestRepo;
+
+#[test]
+fn test_cut_dry_run_lists_the_drafts_and_their_artifacts() {
+    let repo = TestRepo::new();
+    let manifest_id = repo.prepared_release();
+    repo.write_file("dist/SHA256SUMS", "sums\n");
+    repo.write_file("dist/my-crate/my-crate.tar.gz", "archive");
+    repo.write_file("dist/other/other.tar.gz", "not ours");
+
+    let dist = repo.path.join("dist");
+    let output = repo.run_belaf_command(&[
+        "release",
+        "cut",
+        &manifest_id,
+        "--artifacts",
+        dist.to_str().unwrap(),
+        "--dry-run",
+    ]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+    assert!(
+        stdout.contains("Draft release my-crate-v1.1.0 with 2 artifact(s)"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("my-crate.tar.gz"), "{stdout}");
+    assert!(!stdout.contains("other.tar.gz"), "{stdout}");
+    let tags = repo.git(&["tag", "-l", "my-crate-v1.1.0"]);
+    assert!(tags.is_empty(), "--dry-run created tags");
+}
+
+#[test]
+fn test_publish_needs_a_cut_release() {
+    let repo = TestRepo::new();
+    let manifest_id = repo.prepared_release();
+
+    let output = repo.run_belaf_command(&["release", "publish", &manifest_id]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(stderr.contains("has not been cut"), "{stderr}");
+
+    let path = format!("belaf/releases/{manifest_id}.json");
+    let mut manifest: serde_json::Value = serde_json::from_str(&repo.read_file(&path)).unwrap();
+    manifest["publication"] = serde_json::json!({
+        "state": "published",
+        "cut_at": "2026-10-01T09:00:00Z",
+        "published_at": "2026-10-02T15:30:00Z",
+        "releases": [{
+            "tag_name": "my-crate-v1.1.0",
+            "release_id": 42,
+            "url": "https://github.com/acme/widgets/releases/tag/my-crate-v1.1.0",
+            "artifacts": [{ "name": "my-crate.tar.gz", "size": 7, "sha256": "ab".repeat(32) }],
+        }],
+    });
+    repo.write_file(&path, &serde_json::to_string_pretty(&manifest).unwrap());
+
+    let output = repo.run_belaf_command(&["release", "publish", &manifest_id]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(
+        stderr.contains("was already published on 2026-10-02T15:30:00Z"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn test_publish_order_must_name_release_units() {
+    let repo = TestRepo::new();
+    repo.write_file(
+        "Cargo.toml",
+        "[package]\nname = \"my-crate\"\nversion = \"1.0.0\"\nedition = \"2021\"\n",
+    );
+    repo.commit("Initial commit");
+    let output = repo.run_belaf_command(&["init", "--force"]);
+    assert!(output.status.success());
+    let config = repo.read_file("belaf/config.toml");
+    repo.write_file(
+        "belaf/config.toml",
+        &format!(
+            "{config}\n[release.publish]\nwait_for_registry = true\nafter = {{ my-crate = [\"docs\"] }}\n"
+        ),
+    );
+    repo.commit("chore: add belaf config");
+
+    let output = repo.run_belaf_command(&["status"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(
+        stderr.contains("[release.publish] after in") && stderr.contains("names `docs`"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn test_publish_parallelism_must_be_positive() {
+    let repo = TestRepo::new();
+    repo.write_file(
+        "Cargo.toml",
+        "[package]\nname = \"my-crate\"\nversion = \"1.0.0\"\nedition = \"2021\"\n",
+    );
+    repo.commit("Initial commit");
+    let output = repo.run_belaf_command(&["init", "--force"]);
+    assert!(output.status.success());
+    let config = repo.read_file("belaf/config.toml");
+    repo.write_file(
+        "belaf/config.toml",
+        &format!("{config}\n[release.publish]\nparallelism = 0\n"),
+    );
+    repo.commit("chore: add belaf config");
+
+    let output = repo.run_belaf_command(&["status"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(
+        stderr.contains("[release.publish] parallelism in") && stderr.contains("at least 1"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn test_cut_dry_run_names_the_signatures() {
+    let repo = TestRepo::new();
+    let manifest_id = repo.prepared_release();
+    let config = repo.read_file("belaf/config.toml");
+    repo.write_file(
+        "belaf/config.toml",
+        &format!("{config}\n[signing]\ntool = \"minisign\"\nkey = \"keys/release.key\"\n"),
+    );
+    repo.write_file("dist/my-crate.tar.gz", "archive");
+
+    let dist = repo.path.join("dist");
+    let output = repo.run_belaf_command(&[
+        "release",
+        "cut",
+        &manifest_id,
+        "--artifacts",
+        dist.to_str().unwrap(),
+        "--dry-run",
+    ]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+    assert!(
+        stdout.contains(
+            "my-crate-v1.1.0: SHA256SUMS and 2 signature(s) would be added, made with minisign"
+        ),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn test_minisign_needs_a_key() {
+    let repo = TestRepo::new();
+    repo.write_file(
+        "Cargo.toml",
+        "[package]\nname = \"my-crate\"\nversion = \"1.0.0\"\nedition = \"2021\"\n",
+    );
+    repo.commit("Initial commit");
+    let output = repo.run_belaf_command(&["init", "--force"]);
+    assert!(output.status.success());
+    let config = repo.read_file("belaf/config.toml");
+    repo.write_file(
+        "belaf/config.toml",
+        &format!("{config}\n[signing]\ntool = \"minisign\"\n"),
+    );
+    repo.commit("chore: add belaf config");
+
+    let output = repo.run_belaf_command(&["status"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(
+        stderr.contains("invalid [signing]") && stderr.contains("minisign needs `key`"),
+        "{stderr}"
+    );
+}
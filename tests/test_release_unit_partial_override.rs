@@ -266,3 +266,30 @@ fn partial_cascade_from_override() {
         .expect("cascade_from must be set");
     assert_eq!(cascade.source, "schema");
 }
+
+// ---------------------------------------------------------------------------
+// previous_names override (renamed unit).
+// ---------------------------------------------------------------------------
+
+#[test]
+fn partial_previous_names_only_override() {
+    let repo = TestRepo::new();
+    repo.write_file(
+        "crates/bar/Cargo.toml",
+        "[package]\nname = \"bar\"\nversion = \"1.4.0\"\nedition = \"2021\"\n",
+    );
+    repo.commit("seed renamed crate");
+
+    let r = open_repo(&repo);
+    let discovered = discover(&r);
+
+    let blk = partial("bar", |c| {
+        c.previous_names = vec!["foo".into()];
+    });
+    let out = resolve(&r, &[blk]).expect("previous_names alone is a valid override");
+    let merged = resolve_partial_against_discovered(&out.partial_overrides, &discovered).unwrap();
+
+    assert_eq!(merged.len(), 1);
+    assert_eq!(merged[0].unit.previous_names, ["foo"]);
+    assert!(merged[0].unit.tag_format.is_none());
+}
@@ -84,8 +84,12 @@ impl ExplicitBuilder {
                 version_field: None,
                 satellites: self.satellites,
                 tag_format: None,
+                previous_names: vec![],
                 visibility: None,
                 cascade_from: self.cascade_from,
+                dep_requirement: None,
+                scopes: vec![],
+                release_checklist: vec![],
             },
         }
     }
@@ -137,8 +141,12 @@ impl GlobBuilder {
                 version_field: None,
                 satellites: self.satellites,
                 tag_format: None,
+                previous_names: vec![],
                 visibility: None,
                 cascade_from: None,
+                dep_requirement: None,
+                scopes: vec![],
+                release_checklist: vec![],
             },
         }
     }
@@ -416,3 +424,23 @@ fn glob_zero_matches_does_not_error() {
     let resolved = resolve(&r, &[services]).expect("must succeed").resolved;
     assert!(resolved.is_empty());
 }
+
+#[test]
+fn glob_with_previous_names_errors() {
+    let repo = TestRepo::new();
+    repo.write_file(
+        "apps/services/aura/crates/bin/Cargo.toml",
+        "[package]\nname=\"aura-bin\"\nversion=\"0.1.0\"\n",
+    );
+    repo.commit("seed");
+
+    let r = open_repo(&repo);
+
+    let mut services = glob("services", "cargo", "apps/services/*", "{basename}")
+        .with_manifest("{path}/crates/bin/Cargo.toml")
+        .build("services");
+    services.config.previous_names = vec!["old-services".into()];
+
+    let err = resolve(&r, &[services]).unwrap_err();
+    assert_eq!(err.rule(), "glob_unit_has_previous_names");
+}
@@ -0,0 +1,160 @@
+mod common;
+use common::TestRepo;
+
+#[test]
+fn test_status_groups_by_ecosystem_and_sorts_by_readiness() {
+    let repo = TestRepo::new();
+    for name in ["quiet", "fixed", "broken"] {
+        repo.write_file(
+            &format!("crates/{name}/Cargo.toml"),
+            &format!("[package]\nname = \"{name}\"\nversion = \"1.0.0\"\nedition = \"2021\"\n"),
+        );
+        repo.write_file(&format!("crates/{name}/src/lib.rs"), "pub fn f() {}\n");
+    }
+    repo.write_file(
+        "web/package.json",
+        r#"{"name": "a-rather-long-web-package", "version": "1.0.0"}"#,
+    );
+    repo.commit("Initial commit");
+    let output = repo.run_belaf_command(&["init", "--force", "--ci"]);
+    assert!(output.status.success());
+    repo.commit("chore: add belaf config");
+
+    repo.write_file("crates/fixed/src/fix.rs", "pub fn fix() {}\n");
+    repo.commit("fix(fixed): handle the edge case");
+    repo.write_file("crates/broken/src/api.rs", "pub fn api() {}\n");
+    repo.commit("feat(broken)!: drop the old API");
+    repo.write_file("web/index.js", "module.exports = 1;\n");
+    repo.commit("feat(web): export one");
+
+    let output = repo.run_belaf_command(&["status", "--format", "text"]);
+    let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    assert!(output.status.success(), "{stdout}");
+
+    let at = |needle: &str| {
+        stdout
+            .find(needle)
+            .unwrap_or_else(|| panic!("`{needle}` missing:\n{stdout}"))
+    };
+    assert!(at("Node.js (npm)") < at("Rust (Cargo)"), "{stdout}");
+    assert!(at("  broken ") < at("  fixed "), "{stdout}");
+    assert!(at("  fixed ") < at("  quiet "), "{stdout}");
+    assert!(
+        stdout.contains("  broken                     breaking  1 relevant commit(s) since"),
+        "names are padded to the longest one:\n{stdout}"
+    );
+
+    let output = repo.run_belaf_command(&["status", "--format", "text", "--filter", "breaking"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("broken"), "{stdout}");
+    assert!(
+        !stdout.contains("fixed") && !stdout.contains("Node.js"),
+        "{stdout}"
+    );
+
+    let output = repo.run_belaf_command(&["status", "--format", "json", "--filter", "clean"]);
+    let status: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let names: Vec<&str> = status["projects"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|p| p["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(names, ["quiet"]);
+}
+
+#[test]
+fn test_status_names_the_commits_behind_the_suggested_bump() {
+    let repo = TestRepo::new();
+    repo.write_file(
+        "Cargo.toml",
+        "[package]\nname = \"my-crate\"\nversion = \"1.0.0\"\nedition = \"2021\"\n",
+    );
+    repo.write_file("src/lib.rs", "pub fn f() {}\n");
+    repo.commit("Initial commit");
+    let output = repo.run_belaf_command(&["init", "--force", "--ci"]);
+    assert!(output.status.success());
+    repo.commit("chore: add belaf config");
+
+    repo.write_file("src/fix.rs", "pub fn fix() {}\n");
+    repo.commit("fix: handle the edge case");
+    repo.write_file("src/stream.rs", "pub fn stream() {}\n");
+    repo.commit("feat: add streaming");
+    let head = repo.git(&["rev-parse", "--short=7", "HEAD"]);
+
+    let output = repo.run_belaf_command(&["status", "--format", "json"]);
+    let status: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let bump = &status["projects"][0]["bump"];
+    assert_eq!(bump["suggested"], "minor");
+    assert_eq!(
+        bump["reasons"],
+        serde_json::json!([{"commit": head, "kind": "feature", "subject": "feat: add streaming"}])
+    );
+
+    let output = repo.run_belaf_command(&["status", "--format", "text"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains(&format!("suggests minor: {head}\n")),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn test_status_at_a_past_commit_ignores_later_releases() {
+    let repo = TestRepo::new();
+    repo.write_file(
+        "Cargo.toml",
+        "[package]\nname = \"my-crate\"\nversion = \"1.0.0\"\nedition = \"2021\"\n",
+    );
+    repo.write_file("src/lib.rs", "pub fn f() {}\n");
+    repo.commit("Initial commit");
+    let output = repo.run_belaf_command(&["init", "--force", "--ci"]);
+    assert!(output.status.success());
+    repo.commit("chore: add belaf config");
+    repo.git(&["tag", "my-crate-v1.0.0"]);
+
+    repo.write_file("src/stream.rs", "pub fn stream() {}\n");
+    repo.commit("feat: add streaming");
+    let past = repo.git(&["rev-parse", "HEAD"]);
+
+    repo.write_file(
+        "Cargo.toml",
+        "[package]\nname = \"my-crate\"\nversion = \"1.1.0\"\nedition = \"2021\"\n",
+    );
+    repo.commit("chore: release 1.1.0");
+    repo.git(&["tag", "my-crate-v1.1.0"]);
+    repo.write_file("src/fix.rs", "pub fn fix() {}\n");
+    repo.commit("fix: handle the edge case");
+
+    let output = repo.run_belaf_command(&["status", "--format", "json", "--at", &past[..10]]);
+    let status: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(status["at"], past.as_str());
+    let unit = &status["projects"][0];
+    assert_eq!(unit["version"], "1.0.0");
+    assert_eq!(unit["release_tag"], "my-crate-v1.0.0");
+    assert_eq!(unit["commits"], serde_json::json!(["feat: add streaming"]));
+
+    let output = repo.run_belaf_command(&["status", "--format", "text", "--at", &past]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.starts_with(&format!("As of {} feat: add streaming\n", &past[..8])),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("      at 1.0.0, released as my-crate-v1.0.0\n"),
+        "{stdout}"
+    );
+
+    let output = repo.run_belaf_command(&["status", "--format", "json"]);
+    let status: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        status["projects"][0]["commits"],
+        serde_json::json!(["fix: handle the edge case"])
+    );
+    assert!(status.get("at").is_none());
+
+    assert!(
+        !repo.path.join(".git/worktrees").exists(),
+        "the commit is read without checking it out"
+    );
+}
@@ -0,0 +1,88 @@
+//! `[ci.status_gates]`: `belaf status --ci` exits 4 and lists the
+//! failures when a configured gate fails.
+
+mod common;
+
+use common::TestRepo;
+
+fn released_crate_with_gates(gates: &str) -> TestRepo {
+    let repo = TestRepo::new();
+    repo.write_file(
+        "Cargo.toml",
+        "[package]\nname = \"my-crate\"\nversion = \"1.0.0\"\nedition = \"2021\"\n",
+    );
+    repo.write_file("src/lib.rs", "pub fn hello() {}\n");
+    repo.write_file(
+        "CHANGELOG.md",
+        "# Changelog\n\n## [1.0.0] - 2020-01-01\n\n- first\n",
+    );
+    repo.commit("Initial commit");
+
+    let output = repo.run_belaf_command(&["init", "--force"]);
+    assert!(output.status.success());
+    let config = repo.read_file("belaf/config.toml");
+    repo.write_file(
+        "belaf/config.toml",
+        &format!("{config}\n[ci.status_gates]\n{gates}"),
+    );
+    repo.commit("chore: add belaf config");
+    repo.git(&["tag", "my-crate-v1.0.0"]);
+    repo
+}
+
+#[test]
+fn test_status_ci_fails_on_an_old_unreleased_breaking_change() {
+    let repo = released_crate_with_gates("max_breaking_age_days = 14\n");
+
+    let output = repo.run_belaf_command(&["status", "--ci"]);
+    assert!(
+        output.status.success(),
+        "no pending commits must pass: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["gates"]["passed"], true);
+
+    repo.write_file("src/lib.rs", "pub fn hello(name: &str) {}\n");
+    repo.git(&["add", "-A"]);
+    let date = "2020-02-01T12:00:00Z";
+    repo.git_with_env(
+        &["commit", "-m", "feat!: take a name"],
+        &[("GIT_COMMITTER_DATE", date), ("GIT_AUTHOR_DATE", date)],
+    );
+
+    let output = repo.run_belaf_command(&["status", "--ci"]);
+    assert_eq!(output.status.code(), Some(4));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["gates"]["passed"], false);
+    let failures = json["gates"]["failures"].as_array().unwrap();
+    assert_eq!(failures.len(), 1, "{json}");
+    assert_eq!(failures[0]["unit"], "my-crate");
+    assert_eq!(failures[0]["gate"], "max_breaking_age_days");
+
+    // Without --ci status only reports.
+    let output = repo.run_belaf_command(&["status", "--format", "json"]);
+    assert!(output.status.success());
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(json.get("gates").is_none());
+}
+
+#[test]
+fn test_status_ci_fails_on_changelog_drift() {
+    let repo = released_crate_with_gates("changelog_drift = true\n");
+    let output = repo.run_belaf_command(&["status", "--ci"]);
+    assert!(output.status.success());
+
+    repo.write_file("CHANGELOG.md", "# Changelog\n\n## [0.9.0] - 2019-01-01\n");
+    repo.commit("docs: rewrite changelog");
+    repo.git(&["tag", "-f", "my-crate-v1.0.0"]);
+
+    let output = repo.run_belaf_command(&["status", "--ci"]);
+    assert_eq!(output.status.code(), Some(4));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json["gates"]["failures"][0]["gate"], "changelog_drift");
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("no entry for the released version 1.0.0"),
+        "the failure is reported on stderr too"
+    );
+}
@@ -0,0 +1,26 @@
+use belaf::test_support::TestRepo;
+
+#[test]
+fn test_support_runs_commands_in_process() {
+    std::env::set_var("BELAF_NO_FETCH", "1");
+    let repo = TestRepo::new();
+    repo.write_file("package.json", r#"{"name": "demo", "version": "1.0.0"}"#);
+    repo.commit("Initial commit");
+
+    assert!(repo.run(&["init", "--force", "--ci"]).status.success());
+    assert!(repo.file_exists("belaf/config.toml"));
+    repo.commit("chore: add belaf config");
+    repo.tag("v1.0.0");
+
+    repo.write_file("index.js", "module.exports = 1;\n");
+    repo.commit("feat: export one");
+    let output = repo.run(&["status", "--format", "json"]);
+    assert!(output.status.success());
+    let status: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert!(status.is_object(), "got: {status}");
+
+    let output = repo.run(&["no-such-command"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("no-such-command"));
+    assert!(!repo.run(&["--no-color"]).status.success());
+}
@@ -0,0 +1,55 @@
+//! `belaf verify-tag`: what a tag's consumers can check.
+
+mod common;
+
+use common::TestRepo;
+
+/// A release of `my-crate` 1.1.0, tagged; returns the tag.
+fn tagged_release(repo: &TestRepo) -> &'static str {
+    let manifest_id = repo.prepared_release();
+    let output = repo.run_belaf_command(&["tag", &manifest_id]);
+    assert!(output.status.success());
+    "my-crate-v1.1.0"
+}
+
+#[test]
+fn test_verify_tag_checks_version_and_changelog() {
+    let repo = TestRepo::new();
+    let tag = tagged_release(&repo);
+
+    let output = repo.run_belaf_command(&["verify-tag", tag, "--allow-unsigned"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{stdout}");
+    assert!(
+        stdout.contains("my-crate: Cargo.toml says 1.1.0"),
+        "{stdout}"
+    );
+    assert!(
+        stdout.contains("my-crate: CHANGELOG.md has an entry for 1.1.0"),
+        "{stdout}"
+    );
+
+    let output = repo.run_belaf_command(&["verify-tag", tag, "--format", "json"]);
+    let report: serde_json::Value =
+        serde_json::from_slice(&output.stdout).expect("verify-tag should print JSON");
+    assert!(!output.status.success());
+    assert_eq!(report["ok"], false);
+    assert_eq!(report["checks"][0]["name"], "signature");
+    assert_eq!(report["checks"][0]["status"], "error");
+    assert_eq!(report["checks"][0]["summary"], "the tag is not signed");
+}
+
+#[test]
+fn test_verify_tag_needs_a_release_tag() {
+    let repo = TestRepo::new();
+    tagged_release(&repo);
+    repo.git(&["tag", "nightly"]);
+
+    let output = repo.run_belaf_command(&["verify-tag", "nightly"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(
+        stderr.contains("`nightly` is not a release tag of any release unit"),
+        "{stderr}"
+    );
+}
@@ -0,0 +1,70 @@
+//! `belaf which-release`: the first release of a project whose tag
+//! contains a commit.
+
+mod common;
+
+use common::TestRepo;
+
+fn which_release(repo: &TestRepo, commit: &str) -> serde_json::Value {
+    let output = repo.run_belaf_command(&["which-release", commit, "--format", "json"]);
+    assert!(
+        output.status.success(),
+        "which-release failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).expect("which-release should print JSON")
+}
+
+#[test]
+fn test_which_release_reports_the_earliest_release_containing_a_commit() {
+    let repo = TestRepo::new();
+    repo.write_file(
+        "Cargo.toml",
+        "[package]\nname = \"my-crate\"\nversion = \"1.0.0\"\nedition = \"2021\"\n",
+    );
+    repo.write_file("src/lib.rs", "pub fn hello() {}\n");
+    repo.commit("Initial commit");
+    let output = repo.run_belaf_command(&["init", "--force"]);
+    assert!(output.status.success());
+    repo.commit("chore: add belaf config");
+
+    repo.write_file("src/a.rs", "pub fn a() {}\n");
+    repo.commit("fix: first fix");
+    let first = repo.git(&["rev-parse", "HEAD"]);
+    repo.git(&["tag", "my-crate-v1.0.1"]);
+    repo.write_file("src/b.rs", "pub fn b() {}\n");
+    repo.commit("fix: second fix");
+    let second = repo.git(&["rev-parse", "HEAD"]);
+    repo.git(&["tag", "my-crate-v1.0.2"]);
+    repo.write_file("src/c.rs", "pub fn c() {}\n");
+    repo.commit("fix: unreleased fix");
+
+    repo.git(&["checkout", "-q", "-b", "side", "HEAD~1"]);
+    repo.write_file("src/side.rs", "pub fn side() {}\n");
+    repo.commit("fix: side fix");
+    let side = repo.git(&["rev-parse", "HEAD"]);
+    repo.git(&["checkout", "-q", "-"]);
+
+    let report = which_release(&repo, &first[..10]);
+    assert_eq!(report["commit"], first.as_str());
+    assert_eq!(report["summary"], "fix: first fix");
+    let project = &report["projects"][0];
+    assert_eq!(project["name"], "my-crate");
+    assert_eq!(project["status"], "released");
+    assert_eq!(project["version"], "1.0.1");
+    assert_eq!(project["tag"], "my-crate-v1.0.1");
+
+    let report = which_release(&repo, &second);
+    assert_eq!(report["projects"][0]["tag"], "my-crate-v1.0.2");
+
+    let report = which_release(&repo, "HEAD");
+    assert_eq!(report["projects"][0]["status"], "unreleased");
+
+    let report = which_release(&repo, &side);
+    assert_eq!(report["projects"][0]["status"], "not_on_branch");
+
+    let output = repo.run_belaf_command(&["which-release", "HEAD", "nope"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("no release unit `nope`"), "{stderr}");
+}